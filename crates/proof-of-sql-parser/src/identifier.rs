@@ -204,6 +204,9 @@ mod tests {
             "false",
             "timestamp",
             "to_timestamp",
+            "case",
+            "distinct",
+            "null",
         ];
 
         for keyword in &keywords {
@@ -214,6 +217,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn we_can_parse_non_reserved_keywords_as_identifiers() {
+        let keywords = [
+            "avg",
+            "median",
+            "percentile_cont",
+            "percentile_disc",
+            "within",
+            "date_trunc",
+            "extract",
+            "interval",
+            "over",
+            "partition",
+            "inner",
+            "join",
+            "on",
+            "is",
+            "like",
+            "between",
+            "in",
+            "when",
+            "then",
+            "else",
+            "end",
+            "having",
+            "cast",
+            "union",
+            "intersect",
+            "except",
+        ];
+
+        for keyword in &keywords {
+            assert_eq!(
+                Identifier::from_str(&keyword.to_uppercase())
+                    .unwrap()
+                    .name(),
+                *keyword
+            );
+        }
+    }
+
     #[test]
     fn serialize_works() {
         let identifier = Identifier::from_str("GOOD_IDENTIFIER").unwrap();
@@ -4,7 +4,10 @@
 * https://docs.rs/vervolg/latest/vervolg/ast/enum.Statement.html
 ***/
 
-use crate::{posql_time::PoSQLTimestamp, Identifier};
+use crate::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimestamp},
+    Identifier, SelectStatement,
+};
 use alloc::{boxed::Box, string::String, vec::Vec};
 use bigdecimal::BigDecimal;
 use core::{
@@ -15,7 +18,7 @@ use core::{
 use serde::{Deserialize, Serialize};
 
 /// Representation of a `SetExpression`, a collection of rows, each having one or more columns.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum SetExpression {
     /// Query result as `SetExpression`
     Query {
@@ -27,11 +30,52 @@ pub enum SetExpression {
         /// If None, no filter is applied
         where_expr: Option<Box<Expression>>,
         /// Group by expressions e.g. `a` in `SELECT a, COUNT(*) FROM table GROUP BY a`
-        group_by: Vec<Identifier>,
+        /// or `a + b` in `SELECT a + b AS c, COUNT(*) FROM table GROUP BY a + b`
+        group_by: Vec<Expression>,
+        /// Filter expression on the groups e.g. `SUM(b) > 5` in
+        /// `SELECT a, SUM(b) FROM table GROUP BY a HAVING SUM(b) > 5`
+        /// If None, no filter is applied
+        having: Option<Box<Expression>>,
+        /// Whether duplicate rows are removed e.g. `SELECT DISTINCT a FROM table`
+        distinct: bool,
+    },
+    /// Set operation combining the rows of two `SetExpression`s e.g.
+    /// `SELECT a FROM t UNION SELECT b FROM u`
+    SetOperation {
+        /// The set operator e.g. `UNION`
+        op: SetOperator,
+        /// Whether duplicate rows are kept e.g. `UNION ALL`
+        all: bool,
+        /// The left operand
+        left: Box<SetExpression>,
+        /// The right operand
+        right: Box<SetExpression>,
     },
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+/// Set operators combining the rows of two queries
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SetOperator {
+    /// The rows of either query e.g. `UNION`
+    Union,
+    /// The rows of both queries e.g. `INTERSECT`
+    Intersect,
+    /// The rows of the left query which are not rows of the right one e.g. `EXCEPT`
+    Except,
+}
+
+impl Display for SetOperator {
+    // This trait requires `fmt` with this exact signature.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "union"),
+            SetOperator::Intersect => write!(f, "intersect"),
+            SetOperator::Except => write!(f, "except"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// What to select in a query
 pub enum SelectResultExpr {
     /// All columns in a table e.g. `SELECT * FROM table`
@@ -40,7 +84,7 @@ pub enum SelectResultExpr {
     AliasedResultExpr(AliasedResultExpr),
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// An expression with an alias e.g. `a + 1 AS b`
 pub struct AliasedResultExpr {
     /// The expression e.g. `a + 1`, `COUNT(*)`, etc.
@@ -71,7 +115,7 @@ impl AliasedResultExpr {
 }
 
 /// Representations of base queries
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named {
@@ -80,6 +124,33 @@ pub enum TableExpression {
         /// Namespace / schema for the table
         schema: Option<Identifier>,
     },
+    /// The inner equi-join of two row sets e.g. `a JOIN b ON a.k = b.k`
+    Join {
+        /// The left side of the join
+        left: Box<TableExpression>,
+        /// The right side of the join
+        right: Box<TableExpression>,
+        /// The column on the left side of the join condition e.g. `a.k` in `a JOIN b ON a.k = b.k`
+        left_on: QualifiedColumn,
+        /// The column on the right side of the join condition e.g. `b.k` in `a JOIN b ON a.k = b.k`
+        right_on: QualifiedColumn,
+    },
+    /// The result of a subquery e.g. `(SELECT a FROM t) AS s`
+    Subquery {
+        /// The subquery
+        query: Box<SelectStatement>,
+        /// The name of the result of the subquery
+        alias: Identifier,
+    },
+}
+
+/// A column optionally qualified by the name of its table e.g. `a.k` or `k`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct QualifiedColumn {
+    /// The table name e.g. `a` in `a.k`
+    pub table: Option<Identifier>,
+    /// The column name e.g. `k` in `a.k`
+    pub column: Identifier,
 }
 
 /// Binary operators for simple expressions
@@ -106,9 +177,18 @@ pub enum BinaryOperator {
     /// Comparison =
     Equal,
 
+    /// Comparison !=
+    NotEqual,
+
+    /// Comparison <
+    LessThan,
+
     /// Comparison <=
     LessThanOrEqual,
 
+    /// Comparison >
+    GreaterThan,
+
     /// Comparison >=
     GreaterThanOrEqual,
 }
@@ -118,6 +198,12 @@ pub enum BinaryOperator {
 pub enum UnaryOperator {
     /// Logical inversion
     Not,
+
+    /// Whether the operand is NULL
+    IsNull,
+
+    /// Whether the operand is not NULL
+    IsNotNull,
 }
 
 // Aggregation operators
@@ -130,10 +216,21 @@ pub enum AggregationOperator {
     Min,
     /// Sum
     Sum,
+    /// Average
+    Avg,
     /// Count
     Count,
+    /// Count of the distinct values
+    CountDistinct,
     /// Return the first value
     First,
+    /// Median, i.e. `PERCENTILE_CONT(0.5)`
+    Median,
+    /// `PERCENTILE_CONT(percentile) WITHIN GROUP (ORDER BY expr)`, interpolating between the two nearest values
+    PercentileCont(Percentile),
+    /// `PERCENTILE_DISC(percentile) WITHIN GROUP (ORDER BY expr)`, the first value whose cumulative
+    /// distribution is at least the percentile
+    PercentileDisc(Percentile),
 }
 
 impl Display for AggregationOperator {
@@ -142,8 +239,238 @@ impl Display for AggregationOperator {
             AggregationOperator::Max => write!(f, "max"),
             AggregationOperator::Min => write!(f, "min"),
             AggregationOperator::Sum => write!(f, "sum"),
+            AggregationOperator::Avg => write!(f, "avg"),
             AggregationOperator::Count => write!(f, "count"),
+            AggregationOperator::CountDistinct => write!(f, "count_distinct"),
             AggregationOperator::First => write!(f, "first"),
+            AggregationOperator::Median => write!(f, "median"),
+            AggregationOperator::PercentileCont(_) => write!(f, "percentile_cont"),
+            AggregationOperator::PercentileDisc(_) => write!(f, "percentile_disc"),
+        }
+    }
+}
+
+/// A percentile between 0 and 1, which is the fraction `numerator / 10^scale`
+///
+/// Trailing zeros are removed, so that equal percentiles have the same representation.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Percentile {
+    numerator: u64,
+    scale: u8,
+}
+
+impl Percentile {
+    /// The largest number of digits after the decimal point of a percentile
+    pub const MAX_SCALE: u8 = 18;
+
+    /// The median, `0.5`
+    pub const MEDIAN: Self = Self {
+        numerator: 5,
+        scale: 1,
+    };
+
+    const OUT_OF_RANGE: &'static str =
+        "percentile must be between 0 and 1 with at most 18 digits after the decimal point";
+
+    /// Create the percentile `numerator / 10^scale`
+    ///
+    /// # Errors
+    /// Returns an error if the percentile is larger than 1 or `scale` is larger than [`Self::MAX_SCALE`].
+    pub fn try_new(mut numerator: u64, mut scale: u8) -> Result<Self, &'static str> {
+        if scale > Self::MAX_SCALE || numerator > 10_u64.pow(scale.into()) {
+            return Err(Self::OUT_OF_RANGE);
+        }
+        while scale > 0 && numerator % 10 == 0 {
+            numerator /= 10;
+            scale -= 1;
+        }
+        Ok(Self { numerator, scale })
+    }
+
+    /// Create the percentile from a literal
+    pub(crate) fn try_from_decimal(value: &BigDecimal) -> Result<Self, &'static str> {
+        let (digits, exponent) = value.normalized().into_bigint_and_exponent();
+        match (u64::try_from(digits), u8::try_from(exponent)) {
+            (Ok(numerator), Ok(scale)) => Self::try_new(numerator, scale),
+            _ => Err(Self::OUT_OF_RANGE),
+        }
+    }
+
+    /// The numerator of the percentile, whose denominator is `10^scale`
+    #[must_use]
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    /// The number of digits after the decimal point
+    #[must_use]
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+}
+
+impl Display for Percentile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        BigDecimal::new(self.numerator.into(), self.scale.into()).fmt(f)
+    }
+}
+
+/// Window functions, which are evaluated over the partition of each row
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub enum WindowFunction {
+    /// `ROW_NUMBER()`, the position of the row within its partition, starting at 1
+    RowNumber,
+    /// `RANK()`, one more than the number of rows of the partition ordered before the row
+    Rank,
+    /// `SUM(expr)`, the sum over the rows of the partition up to the row and its peers
+    Sum(Box<Expression>),
+}
+
+impl WindowFunction {
+    /// Create the window function without arguments named `name`, ignoring case
+    pub(crate) fn try_new(name: &str) -> Result<Self, &'static str> {
+        match name.to_lowercase().as_str() {
+            "row_number" => Ok(WindowFunction::RowNumber),
+            "rank" => Ok(WindowFunction::Rank),
+            _ => Err("unsupported window function"),
+        }
+    }
+
+    /// The name of the window function
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowFunction::RowNumber => "row_number",
+            WindowFunction::Rank => "rank",
+            WindowFunction::Sum(_) => "sum",
+        }
+    }
+}
+
+/// The data types an expression can be cast to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CastType {
+    /// `TINYINT`
+    TinyInt,
+    /// `SMALLINT`
+    SmallInt,
+    /// `INT` or `INTEGER`
+    Int,
+    /// `BIGINT`
+    BigInt,
+    /// `DECIMAL(precision, scale)` or `NUMERIC(precision, scale)`, where the scale defaults to 0
+    Decimal {
+        /// The total number of digits
+        precision: u8,
+        /// The number of digits after the decimal point
+        scale: u8,
+    },
+    /// `VARCHAR`
+    VarChar,
+    /// `TIMESTAMP(precision)`, where the precision is 0, 3, 6 or 9
+    Timestamp(PoSQLTimeUnit),
+}
+
+impl CastType {
+    /// Create the type named `name` with the parameters in parentheses following it, if any
+    pub(crate) fn try_new(name: Identifier, parameters: &[u64]) -> Result<Self, &'static str> {
+        match (name.as_str(), parameters) {
+            ("tinyint", []) => Ok(CastType::TinyInt),
+            ("smallint", []) => Ok(CastType::SmallInt),
+            ("int" | "integer", []) => Ok(CastType::Int),
+            ("bigint", []) => Ok(CastType::BigInt),
+            ("decimal" | "numeric", [precision]) => Self::try_new_decimal(*precision, 0),
+            ("decimal" | "numeric", [precision, scale]) => {
+                Self::try_new_decimal(*precision, *scale)
+            }
+            ("varchar", []) => Ok(CastType::VarChar),
+            _ => Err("unsupported type in CAST"),
+        }
+    }
+
+    fn try_new_decimal(precision: u64, scale: u64) -> Result<Self, &'static str> {
+        match (u8::try_from(precision), u8::try_from(scale)) {
+            (Ok(precision), Ok(scale)) => Ok(CastType::Decimal { precision, scale }),
+            _ => Err("decimal precision or scale out of range in CAST"),
+        }
+    }
+}
+
+/// The fields of a timestamp, which `DATE_TRUNC` truncates to, `EXTRACT` extracts
+/// and intervals are counted in
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DateTimeField {
+    /// `YEAR`
+    Year,
+    /// `QUARTER`
+    Quarter,
+    /// `MONTH`
+    Month,
+    /// `WEEK`, where weeks start on Monday
+    Week,
+    /// `DAY`, which is the day of the month when extracted
+    Day,
+    /// `DOW`, the day of the week from Sunday (0) to Saturday (6)
+    DayOfWeek,
+    /// `DOY`, the day of the year starting at 1
+    DayOfYear,
+    /// `HOUR`
+    Hour,
+    /// `MINUTE`
+    Minute,
+    /// `SECOND`
+    Second,
+    /// `MILLISECOND`
+    Millisecond,
+    /// `MICROSECOND`
+    Microsecond,
+    /// `NANOSECOND`
+    Nanosecond,
+}
+
+impl DateTimeField {
+    /// Create the field named `name`, ignoring case and a plural `s` if `allow_plural` is true
+    pub(crate) fn try_new(name: &str, allow_plural: bool) -> Result<Self, &'static str> {
+        let name = name.to_lowercase();
+        let name = match name.strip_suffix('s') {
+            Some(singular) if allow_plural => singular,
+            _ => name.as_str(),
+        };
+        match name {
+            "year" => Ok(DateTimeField::Year),
+            "quarter" => Ok(DateTimeField::Quarter),
+            "month" => Ok(DateTimeField::Month),
+            "week" => Ok(DateTimeField::Week),
+            "day" => Ok(DateTimeField::Day),
+            "dow" => Ok(DateTimeField::DayOfWeek),
+            "doy" => Ok(DateTimeField::DayOfYear),
+            "hour" => Ok(DateTimeField::Hour),
+            "minute" => Ok(DateTimeField::Minute),
+            "second" => Ok(DateTimeField::Second),
+            "millisecond" => Ok(DateTimeField::Millisecond),
+            "microsecond" => Ok(DateTimeField::Microsecond),
+            "nanosecond" => Ok(DateTimeField::Nanosecond),
+            _ => Err("unsupported date or time field"),
+        }
+    }
+}
+
+impl Display for DateTimeField {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DateTimeField::Year => write!(f, "year"),
+            DateTimeField::Quarter => write!(f, "quarter"),
+            DateTimeField::Month => write!(f, "month"),
+            DateTimeField::Week => write!(f, "week"),
+            DateTimeField::Day => write!(f, "day"),
+            DateTimeField::DayOfWeek => write!(f, "dow"),
+            DateTimeField::DayOfYear => write!(f, "doy"),
+            DateTimeField::Hour => write!(f, "hour"),
+            DateTimeField::Minute => write!(f, "minute"),
+            DateTimeField::Second => write!(f, "second"),
+            DateTimeField::Millisecond => write!(f, "millisecond"),
+            DateTimeField::Microsecond => write!(f, "microsecond"),
+            DateTimeField::Nanosecond => write!(f, "nanosecond"),
         }
     }
 }
@@ -175,6 +502,76 @@ pub enum Expression {
         right: Box<Expression>,
     },
 
+    /// `expr LIKE pattern`, where `_` in the pattern matches any single character,
+    /// `%` matches any sequence of characters and a backslash escapes the next character
+    Like {
+        /// The expression to match
+        expr: Box<Expression>,
+        /// The pattern to match against
+        pattern: String,
+    },
+
+    /// `expr IN (list...)`
+    InList {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The expressions to compare against
+        list: Vec<Expression>,
+    },
+
+    /// `expr BETWEEN low AND high`, which is `low <= expr AND expr <= high`
+    Between {
+        /// The expression to bound
+        expr: Box<Expression>,
+        /// The lower bound, inclusive
+        low: Box<Expression>,
+        /// The upper bound, inclusive
+        high: Box<Expression>,
+    },
+
+    /// `expr IN (SELECT ...)`, where the subquery has a single result column
+    InSubquery {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The subquery whose result is searched
+        subquery: Box<SelectStatement>,
+    },
+
+    /// `(SELECT ...)`, a subquery with a single result column and at most one row
+    Subquery(Box<SelectStatement>),
+
+    /// `CASE WHEN condition THEN result ... ELSE else_result END`
+    Case {
+        /// The conditions along with the results of the `WHEN` clauses, in order
+        conditions: Vec<(Expression, Expression)>,
+        /// The result if none of the conditions hold, which is NULL if missing
+        else_result: Option<Box<Expression>>,
+    },
+
+    /// `CAST(expr AS data_type)`
+    Cast {
+        /// The expression to convert
+        expr: Box<Expression>,
+        /// The type to convert to
+        data_type: CastType,
+    },
+
+    /// `DATE_TRUNC('field', expr)`, the start of the `field` a timestamp is in
+    DateTrunc {
+        /// The field to truncate to
+        field: DateTimeField,
+        /// The timestamp to truncate
+        expr: Box<Expression>,
+    },
+
+    /// `EXTRACT(field FROM expr)`, a field of a timestamp as an integer
+    Extract {
+        /// The field to extract
+        field: DateTimeField,
+        /// The timestamp to extract the field from
+        expr: Box<Expression>,
+    },
+
     /// * expression
     Wildcard,
 
@@ -185,6 +582,16 @@ pub enum Expression {
         /// The expression to aggregate
         expr: Box<Expression>,
     },
+
+    /// `function OVER (PARTITION BY ... ORDER BY ...)`
+    Window {
+        /// The window function
+        function: WindowFunction,
+        /// The columns partitioning the rows
+        partition_by: Vec<Identifier>,
+        /// The ordering of the rows within each partition
+        order_by: Vec<OrderBy>,
+    },
 }
 
 impl Expression {
@@ -215,6 +622,15 @@ impl Expression {
         })
     }
 
+    /// Create a new `AVG()`
+    #[must_use]
+    pub fn avg(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::Avg,
+            expr: Box::new(self),
+        })
+    }
+
     /// Create a new `COUNT()`
     #[must_use]
     pub fn count(self) -> Box<Self> {
@@ -291,7 +707,7 @@ impl core::ops::Sub<Box<Expression>> for Box<Expression> {
 }
 
 /// `OrderBy`
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct OrderBy {
     /// which column to order by
     pub expr: Identifier,
@@ -300,7 +716,7 @@ pub struct OrderBy {
 }
 
 /// `OrderByDirection` values
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum OrderByDirection {
     /// Ascending
     Asc,
@@ -319,7 +735,7 @@ impl Display for OrderByDirection {
 }
 
 /// Limits for a limit clause
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Slice {
     /// number of rows to return
     ///
@@ -349,6 +765,34 @@ pub enum Literal {
     Decimal(BigDecimal),
     /// Timestamp Literal
     Timestamp(PoSQLTimestamp),
+    /// Interval Literal e.g. `INTERVAL '2 days'`, which is a count of a single field
+    Interval {
+        /// The number of fields, which may be negative
+        value: i64,
+        /// The field the interval is counted in
+        field: DateTimeField,
+    },
+}
+
+impl Literal {
+    /// Create the interval written as `'<value> <field>'` in `INTERVAL '<value> <field>'`
+    pub(crate) fn try_new_interval(content: &str) -> Result<Self, &'static str> {
+        let mut parts = content.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(value), Some(field), None) => {
+                let value = value
+                    .parse()
+                    .map_err(|_| "interval value out of range or not an integer")?;
+                match DateTimeField::try_new(field, true)? {
+                    DateTimeField::DayOfWeek | DateTimeField::DayOfYear => {
+                        Err("unsupported interval field")
+                    }
+                    field => Ok(Literal::Interval { value, field }),
+                }
+            }
+            _ => Err("intervals must be written as '<value> <field>'"),
+        }
+    }
 }
 
 impl From<bool> for Literal {
@@ -1,11 +1,18 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        CastType, DateTimeField,
+        OrderByDirection::{Asc, Desc},
+        Percentile, SetOperator, TableExpression, WindowFunction,
+    },
+    posql_time::PoSQLTimeUnit,
     sql::*,
     utility::*,
-    SelectStatement,
+    ParseError, SelectStatement,
 };
 use alloc::{
     borrow::ToOwned,
+    boxed::Box,
+    format,
     string::{String, ToString},
     vec,
 };
@@ -358,7 +365,7 @@ fn we_can_parse_a_query_with_one_not_equals_filter_expression() {
             query(
                 cols_res(&["a"]),
                 tab(None, "sxt_tab"),
-                not(equal(col("b"), lit(-4))),
+                not_equal(col("b"), lit(-4)),
                 vec![],
             ),
             vec![],
@@ -616,6 +623,534 @@ fn we_can_parse_logical_not_with_less_precedence_priority_than_equal_operator()
     assert_eq!(parsed_ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_is_null_and_is_not_null_filter_expressions() {
+    let parsed_ast = "select a from sxt_tab where b IS NULL or not c is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(is_null(col("b")), not(is_not_null(col("c")))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_is_as_an_identifier() {
+    let parsed_ast = "select is from is where is is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["is"]),
+            tab(None, "is"),
+            is_not_null(col("is")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_is_null_with_more_precedence_priority_than_equal_operator() {
+    let parsed_ast = "select a from sxt_tab where b is null = c is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(is_null(col("b")), is_not_null(col("c"))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_like_and_not_like_filter_expressions() {
+    let parsed_ast = "select a from sxt_tab where b LIKE 'ab%' and c not like 'it''s\\_%'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(like(col("b"), "ab%"), not(like(col("c"), "it's\\_%"))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_like_as_an_identifier() {
+    let parsed_ast = "select like from like where like like 'a%'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["like"]),
+            tab(None, "like"),
+            like(col("like"), "a%"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_like_with_a_non_literal_pattern() {
+    assert!("select a from sxt_tab where b like c"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_in_list_and_not_in_list_filter_expressions() {
+    let parsed_ast = "select a from sxt_tab where b IN (1, -2, c + 3) and c not in ('x')"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                in_list(col("b"), vec![lit(1), lit(-2), add(col("c"), lit(3))]),
+                not(in_list(col("c"), vec![lit("x")])),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_an_empty_in_list() {
+    assert!("select a from sxt_tab where b in ()"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_between_and_not_between_filter_expressions() {
+    let parsed_ast =
+        "select a from sxt_tab where b BETWEEN 1 AND c + 2 and c not between -1 and 1 or d"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(
+                and(
+                    between(col("b"), lit(1), add(col("c"), lit(2))),
+                    not(between(col("c"), lit(-1), lit(1))),
+                ),
+                col("d"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_columns_whose_names_start_with_in_or_between() {
+    let parsed_ast = "select index, betweenness from sxt_tab where inner_value = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["index", "betweenness"]),
+            tab(None, "sxt_tab"),
+            equal(col("inner_value"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_in_and_between_as_identifiers() {
+    let parsed_ast = "select in from between where in in (between) and between between in and 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["in"]),
+            tab(None, "between"),
+            and(
+                in_list(col("in"), vec![col("between")]),
+                between(col("between"), col("in"), lit(1)),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_case_expressions() {
+    let parsed_ast = "select case when a > 1 then b when c then 2 else b + 1 end * 2 as d, \
+        CASE WHEN a THEN 'x' END as e from sxt_tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(
+                    mul(
+                        case(
+                            vec![(gt(col("a"), lit(1)), col("b")), (col("c"), lit(2))],
+                            Some(add(col("b"), lit(1))),
+                        ),
+                        lit(2),
+                    ),
+                    "d",
+                ),
+                col_res(case(vec![(col("a"), lit("x"))], None), "e"),
+            ],
+            tab(None, "sxt_tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_the_case_keywords_as_identifiers() {
+    let parsed_ast =
+        "select case when when then then when else then else else end end as end from end"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(
+                case(
+                    vec![(col("when"), col("then")), (col("else"), col("else"))],
+                    Some(col("end")),
+                ),
+                "end",
+            )],
+            tab(None, "end"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_cast_expressions() {
+    let parsed_ast = "select cast(a as tinyint) as a, CAST(b + 1 AS Integer) as b, \
+        cast(c as decimal(10, 2)) as c, cast(d as numeric(5)) as d, cast(e as timestamp(3)) as e \
+        from sxt_tab where cast(f as varchar) = 'x' and cast(g as bigint) >= cast(h as smallint)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(cast(col("a"), CastType::TinyInt), "a"),
+                col_res(cast(add(col("b"), lit(1)), CastType::Int), "b"),
+                col_res(
+                    cast(
+                        col("c"),
+                        CastType::Decimal {
+                            precision: 10,
+                            scale: 2,
+                        },
+                    ),
+                    "c",
+                ),
+                col_res(
+                    cast(
+                        col("d"),
+                        CastType::Decimal {
+                            precision: 5,
+                            scale: 0,
+                        },
+                    ),
+                    "d",
+                ),
+                col_res(
+                    cast(col("e"), CastType::Timestamp(PoSQLTimeUnit::Millisecond)),
+                    "e",
+                ),
+            ],
+            tab(None, "sxt_tab"),
+            and(
+                equal(cast(col("f"), CastType::VarChar), lit("x")),
+                ge(
+                    cast(col("g"), CastType::BigInt),
+                    cast(col("h"), CastType::SmallInt),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_cast_as_an_identifier() {
+    let parsed_ast = "select cast(cast as bigint) as cast from cast"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(cast(col("cast"), CastType::BigInt), "cast")],
+            tab(None, "cast"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_cast_expressions_with_unsupported_types() {
+    for sql in [
+        "select cast(a as float) as a from sxt_tab",
+        "select cast(a as bigint(3)) as a from sxt_tab",
+        "select cast(a as decimal) as a from sxt_tab",
+        "select cast(a as decimal(300, 2)) as a from sxt_tab",
+        "select cast(a as decimal(10, -2)) as a from sxt_tab",
+        "select cast(a as timestamp(2)) as a from sxt_tab",
+        "select cast(a as timestamp) as a from sxt_tab",
+        "select cast(a) as a from sxt_tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+#[test]
+fn we_can_parse_timestamp_functions_and_intervals() {
+    let parsed_ast =
+        "select date_trunc('day', a) as d, DATE_TRUNC('Month', a + interval '-2 hours') as m, \
+        extract(year from a) as y, EXTRACT(DOW FROM a) as w \
+        from sxt_tab where a - INTERVAL '1 week' >= b and interval '3 seconds' + b <= a"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(date_trunc(DateTimeField::Day, col("a")), "d"),
+                col_res(
+                    date_trunc(
+                        DateTimeField::Month,
+                        add(col("a"), interval(-2, DateTimeField::Hour)),
+                    ),
+                    "m",
+                ),
+                col_res(extract(DateTimeField::Year, col("a")), "y"),
+                col_res(extract(DateTimeField::DayOfWeek, col("a")), "w"),
+            ],
+            tab(None, "sxt_tab"),
+            and(
+                ge(sub(col("a"), interval(1, DateTimeField::Week)), col("b")),
+                le(add(interval(3, DateTimeField::Second), col("b")), col("a")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_unsupported_timestamp_functions_and_intervals() {
+    for sql in [
+        "select date_trunc('days', a) as a from sxt_tab",
+        "select date_trunc('fortnight', a) as a from sxt_tab",
+        "select date_trunc(day, a) as a from sxt_tab",
+        "select extract(years from a) as a from sxt_tab",
+        "select extract('year' from a) as a from sxt_tab",
+        "select a + interval '1.5 days' as a from sxt_tab",
+        "select a + interval '1' as a from sxt_tab",
+        "select a + interval '1 day 2 hours' as a from sxt_tab",
+        "select a + interval '1 dow' as a from sxt_tab",
+        "select a + interval 1 as a from sxt_tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+#[test]
+fn we_can_parse_window_functions() {
+    let parsed_ast = "select a, ROW_NUMBER() over (partition by b order by c desc) as rn, \
+        rank() OVER (ORDER BY c, d DESC) as r, sum(c + 1) over (partition by a, b) as s, \
+        row_number() over () as n from sxt_tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a"])
+                .into_iter()
+                .chain([
+                    col_res(
+                        window(WindowFunction::RowNumber, &["b"], order("c", Desc)),
+                        "rn",
+                    ),
+                    col_res(
+                        window(WindowFunction::Rank, &[], orders(&["c", "d"], &[Asc, Desc])),
+                        "r",
+                    ),
+                    col_res(
+                        window(
+                            WindowFunction::Sum(add(col("c"), lit(1))),
+                            &["a", "b"],
+                            vec![],
+                        ),
+                        "s",
+                    ),
+                    col_res(window(WindowFunction::RowNumber, &[], vec![]), "n"),
+                ])
+                .collect(),
+            tab(None, "sxt_tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_unsupported_window_functions() {
+    for sql in [
+        "select lag() over (order by a) as l from sxt_tab",
+        "select row_number(a) over (order by a) as l from sxt_tab",
+        "select row_number() as l from sxt_tab",
+        "select row_number() over (order by a + 1) as l from sxt_tab",
+        "select row_number() over (partition by a + 1) as l from sxt_tab",
+        "select row_number() over (order by a partition by b) as l from sxt_tab",
+        "select max(a) over (order by a) as l from sxt_tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+#[test]
+fn we_can_parse_non_reserved_keywords_as_identifiers() {
+    let parsed_ast = "select extract(year from interval) as median, date_trunc as avg, \
+        sum(within) over (partition by partition order by over) as extract from date_trunc \
+        where interval >= interval '1 day' + percentile_cont"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(extract(DateTimeField::Year, col("interval")), "median"),
+                col_res(col("date_trunc"), "avg"),
+                col_res(
+                    window(
+                        WindowFunction::Sum(col("within")),
+                        &["partition"],
+                        order("over", Asc),
+                    ),
+                    "extract",
+                ),
+            ],
+            tab(None, "date_trunc"),
+            ge(
+                col("interval"),
+                add(interval(1, DateTimeField::Day), col("percentile_cont")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_reserved_keywords_as_identifiers() {
+    for sql in [
+        "select case from sxt_tab",
+        "select a as null from sxt_tab",
+        "select a from distinct",
+        "select a from sxt_tab where distinct = 1",
+        "select a as case from sxt_tab",
+    ] {
+        assert!(
+            matches!(
+                sql.parse::<SelectStatement>(),
+                Err(ParseError::QueryParseError { .. })
+            ),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_parse_a_non_reserved_keyword_as_a_bare_alias() {
+    assert!("select a over from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert_eq!(
+        "select a as over from sxt_tab"
+            .parse::<SelectStatement>()
+            .unwrap(),
+        select(
+            query_all(
+                vec![col_res(col("a"), "over")],
+                tab(None, "sxt_tab"),
+                vec![]
+            ),
+            vec![],
+            None,
+        )
+    );
+}
+
+#[test]
+fn we_cannot_parse_a_case_expression_without_when_clauses() {
+    assert!("select case else 1 end as a from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select case when b then 1 as a from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_cannot_parse_a_null_literal() {
+    assert!("select a from sxt_tab where b = null"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_can_parse_logical_and_with_more_precedence_priority_than_logical_or() {
     let ast = "select a from sxt_tab where a = -1 or c = -3 and a = 3"
@@ -987,7 +1522,7 @@ fn we_can_parse_a_query_with_filter_lt() {
         query(
             cols_res(&["a"]),
             tab(None, "tab"),
-            not(ge(col("b"), lit(4))),
+            lt(col("b"), lit(4)),
             vec![],
         ),
         vec![],
@@ -1023,7 +1558,7 @@ fn we_can_parse_a_query_with_filter_gt() {
         query(
             cols_res(&["a"]),
             tab(None, "tab"),
-            not(le(col("b"), lit(4))),
+            gt(col("b"), lit(4)),
             vec![],
         ),
         vec![],
@@ -1136,6 +1671,23 @@ fn we_can_parse_a_simple_group_by_clause_with_multiple_columns() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_group_by_clause_with_expressions() {
+    let ast = "select a + b as c, count(*) as n from tab group by a + b, d * 2;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(add(col("a"), col("b")), "c"), count_all_res("n")],
+            tab(None, "tab"),
+            vec![*add(col("a"), col("b")), *mul(col("d"), lit(2))],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_simple_group_by_clause_using_the_wildcard() {
     let ast = "select * from tab group by a"
@@ -1197,35 +1749,85 @@ fn we_can_parse_a_group_by_clause_containing_multiple_aggregations_where_clause_
 }
 
 #[test]
-fn we_cannot_parse_a_group_by_clause_after_order_by() {
-    assert!("select a from tab order by a group by a"
-        .parse::<SelectStatement>()
-        .is_err());
-}
-
-#[test]
-fn we_cannot_parse_a_group_by_clause_before_where_expr() {
-    assert!("select a from tab group by a where a = 3;"
-        .parse::<SelectStatement>()
-        .is_err());
-}
-
-#[test]
-fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
-    let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
+fn we_cannot_parse_a_group_by_clause_after_order_by() {
+    assert!("select a from tab order by a group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_cannot_parse_a_group_by_clause_before_where_expr() {
+    assert!("select a from tab group by a where a = 3;"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
+    let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("f"), "f_col"),
+                min_res(col("a"), "__min__"),
+                max_res(col("a"), "max_a"),
+                count_res(col("a"), "__count__"),
+                count_all_res("count_all"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_averages() {
+    let ast = "select avg(a), AVG(b + 1) as avg_b, count(*) as c from tab group by f"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                avg_res(col("a"), "__avg__"),
+                avg_res(add(col("b"), lit(1)), "avg_b"),
+                count_all_res("c"),
+            ],
+            tab(None, "tab"),
+            group_by(&["f"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_medians_and_percentiles() {
+    let ast = "select median(a), PERCENTILE_CONT(0.950) WITHIN GROUP (ORDER BY b + 1) as p95, \
+        percentile_disc(1) within group (order by c) as top, count(*) as c from tab group by f"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query_all(
             vec![
-                col_res(col("f"), "f_col"),
-                min_res(col("a"), "__min__"),
-                max_res(col("a"), "max_a"),
-                count_res(col("a"), "__count__"),
-                count_all_res("count_all"),
+                col_res(median(col("a")), "__median__"),
+                col_res(
+                    percentile_cont(Percentile::try_new(95, 2).unwrap(), add(col("b"), lit(1))),
+                    "p95",
+                ),
+                col_res(
+                    percentile_disc(Percentile::try_new(1, 0).unwrap(), col("c")),
+                    "top",
+                ),
+                count_all_res("c"),
             ],
             tab(None, "tab"),
-            vec![],
+            group_by(&["f"]),
         ),
         vec![],
         None,
@@ -1233,11 +1835,39 @@ fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_inco
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_cannot_parse_percentiles_out_of_range() {
+    for percentile in ["1.5", "-0.5", "2", "0.0000000000000000001"] {
+        assert!(format!(
+            "select percentile_cont({percentile}) within group (order by a) as p from tab"
+        )
+        .parse::<SelectStatement>()
+        .is_err());
+    }
+    assert!("select percentile_disc(0.5) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_create_percentiles_without_trailing_zeros() {
+    assert_eq!(Percentile::try_new(500, 3), Ok(Percentile::MEDIAN));
+    assert_eq!(Percentile::try_new(0, 5), Percentile::try_new(0, 0));
+    assert_eq!(Percentile::try_new(10, 1).unwrap().to_string(), "1");
+    assert_eq!(Percentile::try_new(5, 2).unwrap().to_string(), "0.05");
+    assert!(Percentile::try_new(11, 1).is_err());
+    assert!(Percentile::try_new(1, 19).is_err());
+}
+
 #[test]
 fn we_cannot_parse_a_non_count_aggregations_with_wildcard() {
     assert!("select min(*) from tab".parse::<SelectStatement>().is_err());
     assert!("select max(*) from tab".parse::<SelectStatement>().is_err());
     assert!("select sum(*) from tab".parse::<SelectStatement>().is_err());
+    assert!("select avg(*) from tab".parse::<SelectStatement>().is_err());
+    assert!("select median(*) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
 }
 
 #[test]
@@ -1439,3 +2069,373 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_can_parse_a_query_with_an_inner_join() {
+    let ast = "select a, c from sxt.tab Join other on tab.b = other.b where a = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a", "c"]),
+            join(
+                tab(Some("sxt"), "tab"),
+                tab(None, "other"),
+                qualified_col(Some("tab"), "b"),
+                qualified_col(Some("other"), "b"),
+            ),
+            equal(col("a"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_multiple_inner_joins() {
+    let ast = "select * from tab inner join other on b = c INNER JOIN third on third.d = tab.d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res_all()],
+            join(
+                join(
+                    tab(None, "tab"),
+                    tab(None, "other"),
+                    qualified_col(None, "b"),
+                    qualified_col(None, "c"),
+                ),
+                tab(None, "third"),
+                qualified_col(Some("third"), "d"),
+                qualified_col(Some("tab"), "d"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_the_join_keywords_as_identifiers() {
+    let ast = "select inner from join inner join on on join.on = on.inner"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["inner"]),
+            join(
+                tab(None, "join"),
+                tab(None, "on"),
+                qualified_col(Some("join"), "on"),
+                qualified_col(Some("on"), "inner"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_join_without_a_join_condition() {
+    assert!("select * from tab join other"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select * from tab join other on b < c"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_subqueries_in_the_from_clause() {
+    let parsed_ast =
+        "select a from (select a, b from sxt_tab where b = 1 order by a limit 2) as s \
+        join (select c from other) t on s.a = t.c"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            join(
+                derived_tab(
+                    select(
+                        query(
+                            cols_res(&["a", "b"]),
+                            tab(None, "sxt_tab"),
+                            equal(col("b"), lit(1)),
+                            vec![],
+                        ),
+                        order("a", Asc),
+                        slice(2, 0),
+                    ),
+                    "s",
+                ),
+                derived_tab(
+                    select(
+                        query_all(cols_res(&["c"]), tab(None, "other"), vec![]),
+                        vec![],
+                        None,
+                    ),
+                    "t",
+                ),
+                qualified_col(Some("s"), "a"),
+                qualified_col(Some("t"), "c"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_subqueries_in_expressions() {
+    let parsed_ast = "select a from sxt_tab where b in (select b from other) \
+        and c not in (select c from other) and a > (select max(a) from other)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let other = |result_exprs| {
+        select(
+            query_all(result_exprs, tab(None, "other"), vec![]),
+            vec![],
+            None,
+        )
+    };
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                and(
+                    in_subquery(col("b"), other(cols_res(&["b"]))),
+                    not(in_subquery(col("c"), other(cols_res(&["c"])))),
+                ),
+                gt(
+                    col("a"),
+                    subquery(other(vec![max_res(col("a"), "__max__")])),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_subquery_in_the_from_clause_without_an_alias() {
+    assert!("select a from (select a from sxt_tab)"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_distinct() {
+    let parsed_ast = "SELECT DISTINCT a, b FROM sxt_tab WHERE a = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(query(
+            cols_res(&["a", "b"]),
+            tab(None, "sxt_tab"),
+            equal(col("a"), lit(1)),
+            vec![],
+        )),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_count_distinct() {
+    let parsed_ast = "select count(distinct a) as n, count(distinct b) from sxt_tab group by c"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                crate::intermediate_ast::SelectResultExpr::AliasedResultExpr(aliased_expr(
+                    count_distinct(col("a")),
+                    "n",
+                )),
+                crate::intermediate_ast::SelectResultExpr::AliasedResultExpr(aliased_expr(
+                    count_distinct(col("b")),
+                    "__count_distinct__",
+                )),
+            ],
+            tab(None, "sxt_tab"),
+            group_by(&["c"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_distinct_as_an_identifier() {
+    assert!("select distinct from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_having() {
+    let parsed_ast =
+        "select a, sum(b) as s from sxt_tab where c = 1 group by a having sum(b) >= 10 and a = 2"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        having(
+            query(
+                vec![col_res(col("a"), "a"), sum_res(col("b"), "s")],
+                tab(None, "sxt_tab"),
+                equal(col("c"), lit(1)),
+                group_by(&["a"]),
+            ),
+            and(ge(sum(col("b")), lit(10)), equal(col("a"), lit(2))),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_having_and_without_group_by() {
+    let parsed_ast = "select count(*) as n from sxt_tab having count(*) >= 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query_all(vec![count_all_res("n")], tab(None, "sxt_tab"), vec![]),
+            ge(count_all(), lit(2)),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_having_as_an_identifier() {
+    let parsed_ast = "select having from having group by having having having >= 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query_all(
+                cols_res(&["having"]),
+                tab(None, "having"),
+                group_by(&["having"]),
+            ),
+            ge(col("having"), lit(2)),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_having_before_group_by() {
+    assert!(
+        "select a, count(*) as n from sxt_tab having count(*) > 1 group by a"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_set_operations_with_intersect_binding_more_tightly() {
+    let parsed_ast = "select a from t1 union all select b from t2 intersect select c from t3 except select d from t4 order by a desc limit 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        set_operation(
+            set_operation(
+                query_all(cols_res(&["a"]), tab(None, "t1"), vec![]),
+                SetOperator::Union,
+                true,
+                set_operation(
+                    query_all(cols_res(&["b"]), tab(None, "t2"), vec![]),
+                    SetOperator::Intersect,
+                    false,
+                    query_all(cols_res(&["c"]), tab(None, "t3"), vec![]),
+                ),
+            ),
+            SetOperator::Except,
+            false,
+            query_all(cols_res(&["d"]), tab(None, "t4"), vec![]),
+        ),
+        order("a", Desc),
+        slice(2, 0),
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_the_set_operators_as_identifiers() {
+    let parsed_ast = "select union from intersect union select except from except"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        set_operation(
+            query_all(cols_res(&["union"]), tab(None, "intersect"), vec![]),
+            SetOperator::Union,
+            false,
+            query_all(cols_res(&["except"]), tab(None, "except"), vec![]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_set_operations_in_subqueries() {
+    let parsed_ast = "select a from (select a from t1 INTERSECT ALL select b from t2) as s"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            Box::new(TableExpression::Subquery {
+                query: Box::new(select(
+                    set_operation(
+                        query_all(cols_res(&["a"]), tab(None, "t1"), vec![]),
+                        SetOperator::Intersect,
+                        true,
+                        query_all(cols_res(&["b"]), tab(None, "t2"), vec![]),
+                    ),
+                    vec![],
+                    None,
+                )),
+                alias: "s".parse().unwrap(),
+            }),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_set_operations_with_misplaced_order_by() {
+    assert!("select a from t1 order by a union select b from t2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from t1 union distinct select b from t2"
+        .parse::<SelectStatement>()
+        .is_err());
+}
@@ -1,11 +1,13 @@
-use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
+use super::intermediate_ast::{
+    Expression, OrderBy, SelectResultExpr, SetExpression, Slice, TableExpression, WindowFunction,
+};
 use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use core::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 /// Representation of a select statement, that is, the only type of queries allowed.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
 pub struct SelectStatement {
     /// the query expression
     pub expr: Box<SetExpression>,
@@ -44,15 +46,44 @@ impl SelectStatement {
     /// - The vector with all tables referenced by the intermediate ast, encoded as resource ids.
     #[must_use]
     pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
-        let set_expression: &SetExpression = &(self.expr);
+        let mut tables = Vec::new();
+        push_set_expression_table_references(&self.expr, default_schema, &mut tables);
+        tables
+    }
+}
 
-        match set_expression {
-            SetExpression::Query {
-                result_exprs: _,
+/// Append the tables referenced by `set_expression` to `tables` in the order they appear
+fn push_set_expression_table_references(
+    set_expression: &SetExpression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match set_expression {
+        SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by: _,
+            having: _,
+            distinct: _,
+        } => {
+            tables.extend(convert_table_expr_to_resource_id_vector(
                 from,
-                where_expr: _,
-                group_by: _,
-            } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
+                default_schema,
+            ));
+            // Tables referenced by subqueries in expressions come after the `FROM` clause
+            for result_expr in result_exprs {
+                if let SelectResultExpr::AliasedResultExpr(aliased_expr) = result_expr {
+                    push_expression_table_references(&aliased_expr.expr, default_schema, tables);
+                }
+            }
+            if let Some(where_expr) = where_expr {
+                push_expression_table_references(where_expr, default_schema, tables);
+            }
+        }
+        SetExpression::SetOperation { left, right, .. } => {
+            push_set_expression_table_references(left, default_schema, tables);
+            push_set_expression_table_references(right, default_schema, tables);
         }
     }
 }
@@ -81,21 +112,97 @@ fn convert_table_expr_to_resource_id_vector(
     let mut tables = Vec::new();
 
     for table_expression in table_expressions {
-        let table_ref: &TableExpression = table_expression;
+        push_table_references(table_expression, default_schema, &mut tables);
+    }
 
-        match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema.as_ref().map_or_else(
-                    || default_schema.name(),
-                    super::identifier::Identifier::as_str,
-                );
+    tables
+}
 
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
-            }
+/// Append the tables referenced by `table_expression` to `tables` in the order they appear
+///
+/// # Panics
+///
+/// This function will panic if `ResourceId::try_new` fails to create a valid `ResourceId`.
+fn push_table_references(
+    table_expression: &TableExpression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match table_expression {
+        TableExpression::Named { table, schema } => {
+            let schema = schema.as_ref().map_or_else(
+                || default_schema.name(),
+                super::identifier::Identifier::as_str,
+            );
+
+            tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+        }
+        TableExpression::Join { left, right, .. } => {
+            push_table_references(left, default_schema, tables);
+            push_table_references(right, default_schema, tables);
+        }
+        TableExpression::Subquery { query, .. } => {
+            tables.extend(query.get_table_references(default_schema));
         }
     }
+}
 
-    tables
+/// Append the tables referenced by the subqueries in `expression` to `tables` in the order they appear
+fn push_expression_table_references(
+    expression: &Expression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match expression {
+        Expression::Subquery(subquery) => {
+            tables.extend(subquery.get_table_references(default_schema));
+        }
+        Expression::InSubquery { expr, subquery } => {
+            push_expression_table_references(expr, default_schema, tables);
+            tables.extend(subquery.get_table_references(default_schema));
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. } => {
+            push_expression_table_references(expr, default_schema, tables);
+        }
+        Expression::Binary { left, right, .. } => {
+            push_expression_table_references(left, default_schema, tables);
+            push_expression_table_references(right, default_schema, tables);
+        }
+        Expression::InList { expr, list } => {
+            push_expression_table_references(expr, default_schema, tables);
+            for item in list {
+                push_expression_table_references(item, default_schema, tables);
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            push_expression_table_references(expr, default_schema, tables);
+            push_expression_table_references(low, default_schema, tables);
+            push_expression_table_references(high, default_schema, tables);
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            for (condition, result) in conditions {
+                push_expression_table_references(condition, default_schema, tables);
+                push_expression_table_references(result, default_schema, tables);
+            }
+            if let Some(else_result) = else_result {
+                push_expression_table_references(else_result, default_schema, tables);
+            }
+        }
+        Expression::Window { function, .. } => {
+            if let WindowFunction::Sum(expr) = function {
+                push_expression_table_references(expr, default_schema, tables);
+            }
+        }
+        Expression::Literal(_) | Expression::Column(_) | Expression::Wildcard => {}
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +245,57 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_join() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM SCHEMA.TAB JOIN OTHER ON TAB.B = OTHER.B WHERE C = 3")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("schema", "tab").unwrap(),
+                ResourceId::try_new("eth", "other").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_subqueries() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A, (SELECT MAX(D) FROM T3) AS M FROM (SELECT A, B FROM SCHEMA.TAB) AS S WHERE B IN (SELECT B FROM OTHER)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("schema", "tab").unwrap(),
+                ResourceId::try_new("eth", "t3").unwrap(),
+                ResourceId::try_new("eth", "other").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_set_operations() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB UNION SELECT B FROM SCHEMA.OTHER EXCEPT SELECT C FROM T3")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "other").unwrap(),
+                ResourceId::try_new("eth", "t3").unwrap()
+            ]
+        );
+    }
 }
@@ -1,18 +1,22 @@
 //! This module exists to adapt the current parser to `sqlparser`.
 use crate::{
     intermediate_ast::{
-        AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator, Expression, Literal,
-        OrderBy as PoSqlOrderBy, OrderByDirection, SelectResultExpr, SetExpression,
-        TableExpression, UnaryOperator as PoSqlUnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator, CastType,
+        DateTimeField as PoSqlDateTimeField, Expression, Literal, OrderBy as PoSqlOrderBy,
+        OrderByDirection, QualifiedColumn, SelectResultExpr, SetExpression,
+        SetOperator as PoSqlSetOperator, TableExpression, UnaryOperator as PoSqlUnaryOperator,
+        WindowFunction,
     },
     Identifier, ResourceId, SelectStatement,
 };
-use alloc::{boxed::Box, string::ToString, vec};
+use alloc::{boxed::Box, format, string::ToString, vec};
 use core::fmt::Display;
 use sqlparser::ast::{
-    BinaryOperator, DataType, Expr, Function, FunctionArg, FunctionArgExpr, GroupByExpr, Ident,
-    ObjectName, Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, TableFactor,
-    TableWithJoins, TimezoneInfo, UnaryOperator, Value, WildcardAdditionalOptions,
+    BinaryOperator, DataType, DateTimeField, Distinct, ExactNumberInfo, Expr, Function,
+    FunctionArg, FunctionArgExpr, GroupByExpr, Ident, Interval, Join, JoinConstraint, JoinOperator,
+    ObjectName, Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator,
+    SetQuantifier, TableAlias, TableFactor, TableWithJoins, TimezoneInfo, UnaryOperator, Value,
+    WildcardAdditionalOptions, WindowSpec, WindowType,
 };
 
 /// Convert a number into a [`Expr`].
@@ -40,6 +44,15 @@ impl From<ResourceId> for ObjectName {
     }
 }
 
+impl From<QualifiedColumn> for Expr {
+    fn from(column: QualifiedColumn) -> Self {
+        match column.table {
+            Some(table) => Expr::CompoundIdentifier(vec![table.into(), column.column.into()]),
+            None => id(column.column),
+        }
+    }
+}
+
 impl From<TableExpression> for TableFactor {
     fn from(table: TableExpression) -> Self {
         match table {
@@ -58,6 +71,48 @@ impl From<TableExpression> for TableFactor {
                     partitions: vec![],
                 }
             }
+            join @ TableExpression::Join { .. } => TableFactor::NestedJoin {
+                table_with_joins: Box::new(join.into()),
+                alias: None,
+            },
+            TableExpression::Subquery { query, alias } => TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new((*query).into()),
+                alias: Some(TableAlias {
+                    name: alias.into(),
+                    columns: vec![],
+                }),
+            },
+        }
+    }
+}
+
+impl From<TableExpression> for TableWithJoins {
+    fn from(table: TableExpression) -> Self {
+        match table {
+            TableExpression::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+            } => {
+                let mut table_with_joins = TableWithJoins::from(*left);
+                table_with_joins.joins.push(Join {
+                    relation: (*right).into(),
+                    join_operator: JoinOperator::Inner(JoinConstraint::On(Expr::BinaryOp {
+                        left: Box::new(left_on.into()),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(right_on.into()),
+                    })),
+                });
+                table_with_joins
+            }
+            table @ (TableExpression::Named { .. } | TableExpression::Subquery { .. }) => {
+                TableWithJoins {
+                    relation: table.into(),
+                    joins: vec![],
+                }
+            }
         }
     }
 }
@@ -80,6 +135,15 @@ impl From<Literal> for Expr {
                     value: timestamp.timestamp().to_string(),
                 }
             }
+            Literal::Interval { value, field } => Expr::Interval(Interval {
+                value: Box::new(Expr::Value(Value::SingleQuotedString(format!(
+                    "{value} {field}"
+                )))),
+                leading_field: None,
+                leading_precision: None,
+                last_field: None,
+                fractional_seconds_precision: None,
+            }),
         }
     }
 }
@@ -90,7 +154,10 @@ impl From<PoSqlBinaryOperator> for BinaryOperator {
             PoSqlBinaryOperator::And => BinaryOperator::And,
             PoSqlBinaryOperator::Or => BinaryOperator::Or,
             PoSqlBinaryOperator::Equal => BinaryOperator::Eq,
+            PoSqlBinaryOperator::NotEqual => BinaryOperator::NotEq,
+            PoSqlBinaryOperator::LessThan => BinaryOperator::Lt,
             PoSqlBinaryOperator::LessThanOrEqual => BinaryOperator::LtEq,
+            PoSqlBinaryOperator::GreaterThan => BinaryOperator::Gt,
             PoSqlBinaryOperator::GreaterThanOrEqual => BinaryOperator::GtEq,
             PoSqlBinaryOperator::Add => BinaryOperator::Plus,
             PoSqlBinaryOperator::Subtract => BinaryOperator::Minus,
@@ -100,14 +167,6 @@ impl From<PoSqlBinaryOperator> for BinaryOperator {
     }
 }
 
-impl From<PoSqlUnaryOperator> for UnaryOperator {
-    fn from(op: PoSqlUnaryOperator) -> Self {
-        match op {
-            PoSqlUnaryOperator::Not => UnaryOperator::Not,
-        }
-    }
-}
-
 impl From<PoSqlOrderBy> for OrderByExpr {
     fn from(order_by: PoSqlOrderBy) -> Self {
         let asc = match order_by.direction {
@@ -122,27 +181,180 @@ impl From<PoSqlOrderBy> for OrderByExpr {
     }
 }
 
+impl From<CastType> for DataType {
+    fn from(data_type: CastType) -> Self {
+        match data_type {
+            CastType::TinyInt => DataType::TinyInt(None),
+            CastType::SmallInt => DataType::SmallInt(None),
+            CastType::Int => DataType::Int(None),
+            CastType::BigInt => DataType::BigInt(None),
+            CastType::Decimal { precision, scale } => DataType::Decimal(
+                ExactNumberInfo::PrecisionAndScale(precision.into(), scale.into()),
+            ),
+            CastType::VarChar => DataType::Varchar(None),
+            CastType::Timestamp(timeunit) => {
+                DataType::Timestamp(Some(timeunit.into()), TimezoneInfo::None)
+            }
+        }
+    }
+}
+
+impl From<PoSqlDateTimeField> for DateTimeField {
+    fn from(field: PoSqlDateTimeField) -> Self {
+        match field {
+            PoSqlDateTimeField::Year => DateTimeField::Year,
+            PoSqlDateTimeField::Quarter => DateTimeField::Quarter,
+            PoSqlDateTimeField::Month => DateTimeField::Month,
+            PoSqlDateTimeField::Week => DateTimeField::Week(None),
+            PoSqlDateTimeField::Day => DateTimeField::Day,
+            PoSqlDateTimeField::DayOfWeek => DateTimeField::Dow,
+            PoSqlDateTimeField::DayOfYear => DateTimeField::Doy,
+            PoSqlDateTimeField::Hour => DateTimeField::Hour,
+            PoSqlDateTimeField::Minute => DateTimeField::Minute,
+            PoSqlDateTimeField::Second => DateTimeField::Second,
+            PoSqlDateTimeField::Millisecond => DateTimeField::Millisecond,
+            PoSqlDateTimeField::Microsecond => DateTimeField::Microsecond,
+            PoSqlDateTimeField::Nanosecond => DateTimeField::Nanosecond,
+        }
+    }
+}
+
 impl From<Expression> for Expr {
+    #[allow(clippy::too_many_lines)]
     fn from(expr: Expression) -> Self {
         match expr {
             Expression::Literal(literal) => literal.into(),
             Expression::Column(identifier) => id(identifier),
-            Expression::Unary { op, expr } => Expr::UnaryOp {
-                op: op.into(),
-                expr: Box::new((*expr).into()),
+            Expression::Unary { op, expr } => match op {
+                PoSqlUnaryOperator::Not => Expr::UnaryOp {
+                    op: UnaryOperator::Not,
+                    expr: Box::new((*expr).into()),
+                },
+                PoSqlUnaryOperator::IsNull => Expr::IsNull(Box::new((*expr).into())),
+                PoSqlUnaryOperator::IsNotNull => Expr::IsNotNull(Box::new((*expr).into())),
             },
             Expression::Binary { op, left, right } => Expr::BinaryOp {
                 left: Box::new((*left).into()),
                 op: op.into(),
                 right: Box::new((*right).into()),
             },
+            Expression::Like { expr, pattern } => Expr::Like {
+                negated: false,
+                expr: Box::new((*expr).into()),
+                pattern: Box::new(Expr::Value(Value::SingleQuotedString(pattern))),
+                escape_char: None,
+            },
+            Expression::InList { expr, list } => Expr::InList {
+                expr: Box::new((*expr).into()),
+                list: list.into_iter().map(Into::into).collect(),
+                negated: false,
+            },
+            Expression::Between { expr, low, high } => Expr::Between {
+                expr: Box::new((*expr).into()),
+                negated: false,
+                low: Box::new((*low).into()),
+                high: Box::new((*high).into()),
+            },
+            Expression::InSubquery { expr, subquery } => Expr::InSubquery {
+                expr: Box::new((*expr).into()),
+                subquery: Box::new((*subquery).into()),
+                negated: false,
+            },
+            Expression::Subquery(subquery) => Expr::Subquery(Box::new((*subquery).into())),
+            Expression::Case {
+                conditions,
+                else_result,
+            } => {
+                let (conditions, results) = conditions
+                    .into_iter()
+                    .map(|(condition, result)| (condition.into(), result.into()))
+                    .unzip();
+                Expr::Case {
+                    operand: None,
+                    conditions,
+                    results,
+                    else_result: else_result.map(|else_result| Box::new((*else_result).into())),
+                }
+            }
+            Expression::Cast { expr, data_type } => Expr::Cast {
+                expr: Box::new((*expr).into()),
+                data_type: data_type.into(),
+                format: None,
+            },
+            Expression::DateTrunc { field, expr } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new("date_trunc")]),
+                args: vec![
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                        Value::SingleQuotedString(field.to_string()),
+                    ))),
+                    FunctionArg::Unnamed((*expr).into()),
+                ],
+                filter: None,
+                null_treatment: None,
+                over: None,
+                distinct: false,
+                special: false,
+                order_by: vec![],
+            }),
+            Expression::Extract { field, expr } => Expr::Extract {
+                field: field.into(),
+                expr: Box::new((*expr).into()),
+            },
             Expression::Wildcard => Expr::Wildcard,
-            Expression::Aggregation { op, expr } => Expr::Function(Function {
+            // `sqlparser` has no `WITHIN GROUP` for general functions, so the ordering of a percentile
+            // is kept as the ordering of its argument
+            Expression::Aggregation {
+                op:
+                    op @ (AggregationOperator::PercentileCont(percentile)
+                    | AggregationOperator::PercentileDisc(percentile)),
+                expr,
+            } => Expr::Function(Function {
                 name: ObjectName(vec![Ident::new(op.to_string())]),
+                args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(number(
+                    percentile,
+                )))],
+                filter: None,
+                null_treatment: None,
+                over: None,
+                distinct: false,
+                special: false,
+                order_by: vec![OrderByExpr {
+                    expr: (*expr).into(),
+                    asc: None,
+                    nulls_first: None,
+                }],
+            }),
+            Expression::Aggregation { op, expr } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(match op {
+                    AggregationOperator::CountDistinct => "count".to_string(),
+                    _ => op.to_string(),
+                })]),
                 args: vec![FunctionArg::Unnamed((*expr).into())],
                 filter: None,
                 null_treatment: None,
                 over: None,
+                distinct: op == AggregationOperator::CountDistinct,
+                special: false,
+                order_by: vec![],
+            }),
+            Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(function.name())]),
+                args: match function {
+                    WindowFunction::Sum(expr) => vec![FunctionArg::Unnamed((*expr).into())],
+                    WindowFunction::RowNumber | WindowFunction::Rank => vec![],
+                },
+                filter: None,
+                null_treatment: None,
+                over: Some(WindowType::WindowSpec(WindowSpec {
+                    window_name: None,
+                    partition_by: partition_by.into_iter().map(id).collect(),
+                    order_by: order_by.into_iter().map(OrderByExpr::from).collect(),
+                    window_frame: None,
+                })),
                 distinct: false,
                 special: false,
                 order_by: vec![],
@@ -180,7 +392,7 @@ impl From<SelectResultExpr> for SelectItem {
     }
 }
 
-impl From<SetExpression> for Select {
+impl From<SetExpression> for SetExpr {
     fn from(select: SetExpression) -> Self {
         match select {
             SetExpression::Query {
@@ -188,28 +400,46 @@ impl From<SetExpression> for Select {
                 from,
                 where_expr,
                 group_by,
-            } => Select {
-                distinct: None,
+                having,
+                distinct,
+            } => SetExpr::Select(Box::new(Select {
+                distinct: distinct.then_some(Distinct::Distinct),
                 top: None,
                 projection: result_exprs.into_iter().map(SelectItem::from).collect(),
                 into: None,
                 from: from
                     .into_iter()
-                    .map(|table_expression| TableWithJoins {
-                        relation: (*table_expression).into(),
-                        joins: vec![],
-                    })
+                    .map(|table_expression| (*table_expression).into())
                     .collect(),
                 lateral_views: vec![],
                 selection: where_expr.map(|expr| (*expr).into()),
-                group_by: GroupByExpr::Expressions(group_by.into_iter().map(id).collect()),
+                group_by: GroupByExpr::Expressions(group_by.into_iter().map(Expr::from).collect()),
                 cluster_by: vec![],
                 distribute_by: vec![],
                 sort_by: vec![],
-                having: None,
+                having: having.map(|expr| (*expr).into()),
                 named_window: vec![],
                 qualify: None,
                 value_table_mode: None,
+            })),
+            SetExpression::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op: match op {
+                    PoSqlSetOperator::Union => SetOperator::Union,
+                    PoSqlSetOperator::Intersect => SetOperator::Intersect,
+                    PoSqlSetOperator::Except => SetOperator::Except,
+                },
+                set_quantifier: if all {
+                    SetQuantifier::All
+                } else {
+                    SetQuantifier::None
+                },
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
             },
         }
     }
@@ -219,7 +449,7 @@ impl From<SelectStatement> for Query {
     fn from(select: SelectStatement) -> Self {
         Query {
             with: None,
-            body: Box::new(SetExpr::Select(Box::new((*select.expr).into()))),
+            body: Box::new((*select.expr).into()),
             order_by: select.order_by.into_iter().map(OrderByExpr::from).collect(),
             limit: select.slice.clone().map(|slice| number(slice.number_rows)),
             limit_by: vec![],
@@ -256,6 +486,10 @@ mod test {
 
     #[test]
     fn we_can_convert_posql_intermediate_ast_to_sqlparser_with_slight_modification() {
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select percentile_cont(0.25) within group (order by a) as q, percentile_disc(1) within group (order by b + 1) as m from t;",
+            "select percentile_cont(0.25 order by a) as q, percentile_disc(1 order by b + 1) as m from t;",
+        );
         check_posql_intermediate_ast_to_sqlparser_equivalence(
             "select a, b from t limit 10;",
             "select a as a, b as b from t limit 10 offset 0;",
@@ -268,6 +502,10 @@ mod test {
             "select timestamp '2024-11-07T04:55:12.345+03:00' as time from t;",
             "select timestamp(3) '2024-11-07 01:55:12.345 UTC' as time from t;",
         );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a from t inner join u on t.b = u.c;",
+            "select a as a from t join u on t.b = u.c;",
+        );
     }
 
     // Check that PoSQL intermediate AST can be converted to SQL parser AST and that the two are equal.
@@ -299,5 +537,53 @@ mod test {
         check_posql_intermediate_ast_to_sqlparser_equality(
             "select cat as cat, sum(a) as s, count(*) as rows from tab where d = 'Space and Time' group by cat;",
         );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, c as c from tab join namespace.other on tab.b = other.b;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab join other on b = c join third on third.d = tab.d;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a IS NULL as a_null from tab where NOT b IS NOT NULL;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b LIKE 'ab_%';",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b IN (1, 2 + c) and c BETWEEN 1 AND 10;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select CASE WHEN a THEN 1 WHEN b THEN 2 ELSE 3 END as c, CASE WHEN a THEN 1 END as d from tab;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from (select a as a from tab) AS s where a IN (select b as b from t) and a >= (select max(c) as m from t);",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select DISTINCT a as a, count(DISTINCT b) as n from tab group by a;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, sum(b) as s from tab group by a HAVING sum(b) >= 1 and a = 2;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a + b as c, count(*) as n from tab group by a + b;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select date_trunc('day', a + INTERVAL '2 hour') as d, EXTRACT(YEAR FROM a) as y, EXTRACT(DOW FROM a) as w from tab where b <= a - INTERVAL '-1 week';",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select CAST(a AS BIGINT) as b, CAST(c + 1 AS DECIMAL(10,2)) as d, CAST(e AS TIMESTAMP(3)) as f from tab where CAST(g AS VARCHAR) = 'x';",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select cat as cat, avg(a) as m, count(*) as rows from tab group by cat;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select cat as cat, median(a) as m, count(*) as rows from tab group by cat;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, row_number() OVER (PARTITION BY b ORDER BY c DESC) as rn, rank() OVER (ORDER BY c ASC) as r, sum(c) OVER (PARTITION BY a, b) as s from tab;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab UNION select b as b from t EXCEPT select c as c from u INTERSECT ALL select d as d from v order by a desc;",
+        );
     }
 }
@@ -1,11 +1,13 @@
 use crate::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        OrderByDirection, SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, CastType, DateTimeField,
+        Expression, Literal, OrderBy, OrderByDirection, Percentile, QualifiedColumn,
+        SelectResultExpr, SetExpression, SetOperator, Slice, TableExpression, UnaryOperator,
+        WindowFunction,
     },
     Identifier, SelectStatement,
 };
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 
 ///
 /// # Panics
@@ -27,6 +29,26 @@ pub fn equal(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A != B
+#[must_use]
+pub fn not_equal(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::NotEqual,
+        left,
+        right,
+    })
+}
+
+/// Construct a new boxed `Expression` A > B
+#[must_use]
+pub fn gt(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::GreaterThan,
+        left,
+        right,
+    })
+}
+
 /// Construct a new boxed `Expression` A >= B
 #[must_use]
 pub fn ge(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
@@ -47,6 +69,16 @@ pub fn le(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A < B
+#[must_use]
+pub fn lt(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::LessThan,
+        left,
+        right,
+    })
+}
+
 /// Construct a new boxed `Expression` NOT P
 #[must_use]
 pub fn not(expr: Box<Expression>) -> Box<Expression> {
@@ -56,6 +88,124 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A IS NULL
+#[must_use]
+pub fn is_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNull,
+        expr,
+    })
+}
+
+/// Construct a new boxed `Expression` A IS NOT NULL
+#[must_use]
+pub fn is_not_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNotNull,
+        expr,
+    })
+}
+
+/// Construct a new boxed `Expression` A LIKE 'pattern'
+#[must_use]
+pub fn like(expr: Box<Expression>, pattern: &str) -> Box<Expression> {
+    Box::new(Expression::Like {
+        expr,
+        pattern: pattern.to_string(),
+    })
+}
+
+/// Construct a new boxed `Expression` A IN (B, C, ...)
+#[must_use]
+pub fn in_list(expr: Box<Expression>, list: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().map(|item| *item).collect(),
+    })
+}
+
+/// Construct a new boxed `Expression` A BETWEEN B AND C
+#[must_use]
+pub fn between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between { expr, low, high })
+}
+
+/// Construct a new boxed `Expression` A IN (SELECT ...)
+#[must_use]
+pub fn in_subquery(expr: Box<Expression>, subquery: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::InSubquery {
+        expr,
+        subquery: Box::new(subquery),
+    })
+}
+
+/// Construct a new boxed `Expression` (SELECT ...)
+#[must_use]
+pub fn subquery(subquery: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::Subquery(Box::new(subquery)))
+}
+
+/// Construct a new boxed `Expression` CASE WHEN P THEN A ... ELSE B END
+#[must_use]
+pub fn case(
+    conditions: Vec<(Box<Expression>, Box<Expression>)>,
+    else_result: Option<Box<Expression>>,
+) -> Box<Expression> {
+    Box::new(Expression::Case {
+        conditions: conditions
+            .into_iter()
+            .map(|(condition, result)| (*condition, *result))
+            .collect(),
+        else_result,
+    })
+}
+
+/// Construct a new boxed `Expression` CAST(EXPR AS TYPE)
+#[must_use]
+pub fn cast(expr: Box<Expression>, data_type: CastType) -> Box<Expression> {
+    Box::new(Expression::Cast { expr, data_type })
+}
+
+/// Construct a new boxed `Expression` `DATE_TRUNC('FIELD', EXPR)`
+#[must_use]
+pub fn date_trunc(field: DateTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::DateTrunc { field, expr })
+}
+
+/// Construct a new boxed `Expression` EXTRACT(FIELD FROM EXPR)
+#[must_use]
+pub fn extract(field: DateTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Extract { field, expr })
+}
+
+/// Construct a new boxed `Expression` FUNCTION OVER (PARTITION BY A, ... ORDER BY B [ASC|DESC], ...)
+///
+/// # Panics
+///
+/// This function will panic if a name in `partition_by` cannot be parsed.
+#[must_use]
+pub fn window(
+    function: WindowFunction,
+    partition_by: &[&str],
+    order_by: Vec<OrderBy>,
+) -> Box<Expression> {
+    Box::new(Expression::Window {
+        function,
+        partition_by: partition_by.iter().map(|name| ident(name)).collect(),
+        order_by,
+    })
+}
+
+/// Construct a new boxed `Expression` INTERVAL 'VALUE FIELD'
+#[must_use]
+pub fn interval(value: i64, field: DateTimeField) -> Box<Expression> {
+    Box::new(Expression::Literal(Literal::Interval { value, field }))
+}
+
 /// Construct a new boxed `Expression` P AND Q
 #[must_use]
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
@@ -130,6 +280,48 @@ pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     })
 }
 
+/// Construct a new boxed `TableExpression` for the subquery `(SELECT ...) AS ALIAS`
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn derived_tab(query: SelectStatement, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Subquery {
+        query: Box::new(query),
+        alias: alias.parse().unwrap(),
+    })
+}
+
+/// Construct a new boxed `TableExpression` for the join `LEFT JOIN RIGHT ON LEFT_ON = RIGHT_ON`
+#[must_use]
+pub fn join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    left_on: QualifiedColumn,
+    right_on: QualifiedColumn,
+) -> Box<TableExpression> {
+    Box::new(TableExpression::Join {
+        left,
+        right,
+        left_on,
+        right_on,
+    })
+}
+
+/// Get a column optionally qualified by a table name
+///
+/// # Panics
+///
+/// This function will panic if either the `name` or the `table` (if provided) cannot be parsed as valid [Identifier]s.
+#[must_use]
+pub fn qualified_col(table: Option<&str>, name: &str) -> QualifiedColumn {
+    QualifiedColumn {
+        table: table.map(|table| table.parse().unwrap()),
+        column: name.parse().unwrap(),
+    }
+}
+
 /// Get column from name
 ///
 /// # Panics
@@ -172,6 +364,44 @@ pub fn max(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Compute the average of an expression
+#[must_use]
+pub fn avg(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::Avg,
+        expr,
+    })
+}
+
+/// Compute the median of an expression
+#[must_use]
+pub fn median(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::Median,
+        expr,
+    })
+}
+
+/// Compute the continuous `percentile` of an expression,
+/// i.e. `PERCENTILE_CONT(percentile) WITHIN GROUP (ORDER BY expr)`
+#[must_use]
+pub fn percentile_cont(percentile: Percentile, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::PercentileCont(percentile),
+        expr,
+    })
+}
+
+/// Compute the discrete `percentile` of an expression,
+/// i.e. `PERCENTILE_DISC(percentile) WITHIN GROUP (ORDER BY expr)`
+#[must_use]
+pub fn percentile_disc(percentile: Percentile, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::PercentileDisc(percentile),
+        expr,
+    })
+}
+
 /// Count the amount of non-null entries of expression
 #[must_use]
 pub fn count(expr: Box<Expression>) -> Box<Expression> {
@@ -181,6 +411,15 @@ pub fn count(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Count the distinct values of an expression i.e. COUNT(DISTINCT EXPR)
+#[must_use]
+pub fn count_distinct(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::CountDistinct,
+        expr,
+    })
+}
+
 /// Count the rows
 #[must_use]
 pub fn count_all() -> Box<Expression> {
@@ -264,6 +503,19 @@ pub fn sum_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
     })
 }
 
+/// Compute the average of an expression and give it an alias i.e. SELECT AVG(EXPR) AS ALIAS
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed.
+#[must_use]
+pub fn avg_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
+    SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+        expr: avg(expr),
+        alias: alias.parse().unwrap(),
+    })
+}
+
 /// Count the amount of non-null entries of expression and give it an alias i.e. SELECT COUNT(EXPR) AS ALIAS
 ///
 /// # Panics
@@ -300,13 +552,15 @@ pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
     where_expr: Box<Expression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: Some(where_expr),
         group_by,
+        having: None,
+        distinct: false,
     })
 }
 
@@ -317,13 +571,57 @@ pub fn query(
 pub fn query_all(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: None,
         group_by,
+        having: None,
+        distinct: false,
+    })
+}
+
+/// Remove the duplicate rows of a `SetExpression` i.e. SELECT DISTINCT ...
+///
+/// # Panics
+/// Panics if `expr` is not a query.
+#[must_use]
+pub fn distinct(mut expr: Box<SetExpression>) -> Box<SetExpression> {
+    let SetExpression::Query { distinct, .. } = expr.as_mut() else {
+        panic!("DISTINCT can only be applied to a query")
+    };
+    *distinct = true;
+    expr
+}
+
+/// Filter the groups of a `SetExpression` i.e. SELECT ... GROUP BY ... HAVING ...
+///
+/// # Panics
+/// Panics if `expr` is not a query.
+#[must_use]
+pub fn having(mut expr: Box<SetExpression>, having_expr: Box<Expression>) -> Box<SetExpression> {
+    let SetExpression::Query { having, .. } = expr.as_mut() else {
+        panic!("HAVING can only be applied to a query")
+    };
+    *having = Some(having_expr);
+    expr
+}
+
+/// Combine the rows of two `SetExpression`s i.e. ... UNION [ALL] ..., ... INTERSECT ... or ... EXCEPT ...
+#[must_use]
+pub fn set_operation(
+    left: Box<SetExpression>,
+    op: SetOperator,
+    all: bool,
+    right: Box<SetExpression>,
+) -> Box<SetExpression> {
+    Box::new(SetExpression::SetOperation {
+        op,
+        all,
+        left,
+        right,
     })
 }
 
@@ -389,6 +687,8 @@ pub fn slice(number_rows: u64, offset_value: i64) -> Option<Slice> {
 /// This function will panic if any of the `ids` cannot be parsed
 /// into an identifier.
 #[must_use]
-pub fn group_by(ids: &[&str]) -> Vec<Identifier> {
-    ids.iter().map(|id| id.parse().unwrap()).collect()
+pub fn group_by(ids: &[&str]) -> Vec<Expression> {
+    ids.iter()
+        .map(|id| Expression::Column(id.parse().unwrap()))
+        .collect()
 }
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {ECADD_ADDRESS, ECMUL_ADDRESS, ECPAIRING_ADDRESS, FIELD_MODULUS} from "./Constants.sol";
+import {InvalidECPoint} from "./Errors.sol";
+
+/// @title BN254
+/// @dev The group operations of the BN254 curve, which use the precompiles of EIP-196 and EIP-197.
+///
+/// The point at infinity is `(0, 0)`, which is how the precompiles represent it.
+library BN254 {
+    /// @dev A point of G1 in affine coordinates.
+    struct G1Point {
+        uint256 x;
+        uint256 y;
+    }
+
+    /// @dev A point of G2 in affine coordinates, where each coordinate is `imaginary * i + real`.
+    /// This is the order in which the pairing precompile reads the coordinates.
+    struct G2Point {
+        uint256 xImaginary;
+        uint256 xReal;
+        uint256 yImaginary;
+        uint256 yReal;
+    }
+
+    /// @dev Computes `a + b`. Reverts if either point is not on the curve.
+    /// @param a The first point
+    /// @param b The second point
+    /// @return sum The sum
+    function add(G1Point memory a, G1Point memory b) internal view returns (G1Point memory sum) {
+        (bool success, bytes memory output) = ECADD_ADDRESS.staticcall(abi.encode(a.x, a.y, b.x, b.y));
+        if (!success) revert InvalidECPoint();
+        (sum.x, sum.y) = abi.decode(output, (uint256, uint256));
+    }
+
+    /// @dev Computes `scalar * a`. Reverts if the point is not on the curve.
+    /// @param a The point
+    /// @param scalar The scalar
+    /// @return product The product
+    function mul(G1Point memory a, uint256 scalar) internal view returns (G1Point memory product) {
+        (bool success, bytes memory output) = ECMUL_ADDRESS.staticcall(abi.encode(a.x, a.y, scalar));
+        if (!success) revert InvalidECPoint();
+        (product.x, product.y) = abi.decode(output, (uint256, uint256));
+    }
+
+    /// @dev Computes `-a`.
+    /// @param a The point, which has to be on the curve
+    /// @return negated The negated point
+    function negate(G1Point memory a) internal pure returns (G1Point memory negated) {
+        negated.x = a.x;
+        negated.y = a.y == 0 ? 0 : FIELD_MODULUS - a.y;
+    }
+
+    /// @dev Checks that `e(a1, a2) * e(b1, b2) == 1`. Reverts if any point is not on the curve.
+    /// @param a1 The G1 point of the first pairing
+    /// @param a2 The G2 point of the first pairing
+    /// @param b1 The G1 point of the second pairing
+    /// @param b2 The G2 point of the second pairing
+    /// @return holds Whether the product of the pairings is `1`
+    function pairingCheck(G1Point memory a1, G2Point memory a2, G1Point memory b1, G2Point memory b2)
+        internal
+        view
+        returns (bool holds)
+    {
+        (bool success, bytes memory output) = ECPAIRING_ADDRESS.staticcall(
+            abi.encode(
+                a1.x,
+                a1.y,
+                a2.xImaginary,
+                a2.xReal,
+                a2.yImaginary,
+                a2.yReal,
+                b1.x,
+                b1.y,
+                b2.xImaginary,
+                b2.xReal,
+                b2.yImaginary,
+                b2.yReal
+            )
+        );
+        if (!success) revert InvalidECPoint();
+        holds = abi.decode(output, (uint256)) == 1;
+    }
+}
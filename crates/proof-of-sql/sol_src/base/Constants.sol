@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+/// @dev The order of the scalar field that proofs are over, i.e. the order of the BN254 curve.
+/// This matches `BNScalar`, the scalar of `HyperKZGCommitmentEvaluationProof`.
+uint256 constant MODULUS = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+/// @dev The order of the base field of the BN254 curve, i.e. the field of the coordinates of its points.
+uint256 constant FIELD_MODULUS = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+
+/// @dev The addresses of the BN254 precompiles, see EIP-196 and EIP-197.
+address constant ECADD_ADDRESS = address(0x06);
+address constant ECMUL_ADDRESS = address(0x07);
+address constant ECPAIRING_ADDRESS = address(0x08);
+
+/// @dev The numbers of the plans, expressions and column types in a serialized plan.
+/// These match `evm_compatibility::dyn_proof_plan_serializer::constants`.
+uint8 constant FILTER_EXEC_NUM = 0;
+uint8 constant PROJECTION_EXEC_NUM = 3;
+
+uint8 constant COLUMN_EXPR_NUM = 0;
+uint8 constant EQUALS_EXPR_NUM = 1;
+uint8 constant LITERAL_EXPR_NUM = 2;
+uint8 constant AND_EXPR_NUM = 3;
+uint8 constant OR_EXPR_NUM = 4;
+uint8 constant NOT_EXPR_NUM = 5;
+uint8 constant ADD_SUBTRACT_EXPR_NUM = 9;
+uint8 constant MULTIPLY_EXPR_NUM = 10;
+
+uint8 constant BIGINT_TYPE_NUM = 0;
+uint8 constant BOOLEAN_TYPE_NUM = 1;
+uint8 constant TINYINT_TYPE_NUM = 2;
+uint8 constant SMALLINT_TYPE_NUM = 3;
+uint8 constant INT_TYPE_NUM = 4;
+uint8 constant INT128_TYPE_NUM = 5;
+uint8 constant SCALAR_TYPE_NUM = 9;
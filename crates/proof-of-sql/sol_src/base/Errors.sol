@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+/// @dev A scalar is not in its canonical form, i.e. it is not less than the modulus.
+error NonCanonicalScalar();
+/// @dev The number of coefficients of a sumcheck proof is not a nonzero multiple of the number of variables.
+error InvalidSumcheckProofSize();
+/// @dev The round polynomial of a sumcheck proof does not sum to the expected evaluation.
+error RoundEvaluationMismatch();
+/// @dev The length of a truncated Lagrange basis is larger than the full basis.
+error TruncatedLengthTooLarge();
+/// @dev A point is not on the BN254 curve, so a precompile rejected it.
+error InvalidECPoint();
+/// @dev A coordinate of a point is not in its canonical form, i.e. it is not less than the field modulus.
+error NonCanonicalECPoint();
+/// @dev The setup has fewer generators than the table that the evaluation proof is for.
+error HyperKZGSetupTooSmall();
+/// @dev The number of commitments or evaluations of a `HyperKZG` evaluation proof does not match the evaluation point.
+error HyperKZGProofSizeMismatch();
+/// @dev A `HyperKZG` evaluation proof does not verify.
+error HyperKZGVerificationFailed();
+/// @dev A serialized plan ends before it is fully read, or has bytes left over after it.
+error InvalidSerializedPlan();
+/// @dev A serialized plan contains a plan that the Solidity verifier does not support.
+error UnsupportedProofPlan();
+/// @dev A serialized plan contains an expression that the Solidity verifier does not support.
+error UnsupportedProofExpr();
+/// @dev A serialized plan or a result contains a column type that the Solidity verifier does not support.
+error UnsupportedColumnType();
+/// @dev A serialized plan refers to a table or a column that it does not list.
+error InvalidReference();
+/// @dev The number of table lengths or column commitments does not match the tables and columns of the plan.
+error ReferenceCountMismatch();
+/// @dev An entry of a result column does not fit into the type of the column.
+error ResultValueOutOfRange();
+/// @dev The result columns do not match the results of the plan.
+error ResultColumnCountMismatch();
+/// @dev The result columns do not all have the same length, or have more rows than the evaluation point covers.
+error ResultColumnLengthMismatch();
+/// @dev The proof has fewer MLE evaluations than the plan consumes.
+error TooFewMLEEvaluations();
+/// @dev The proof has fewer one evaluation lengths than the plan consumes.
+error TooFewOneLengths();
+/// @dev The proof has fewer post-result challenges than the plan consumes.
+error PostResultCountMismatch();
+/// @dev The plan produces more subpolynomials than the proof has constraints for.
+error ConstraintCountMismatch();
+/// @dev The degree of a subpolynomial is too large for the sumcheck proof.
+error SumcheckProofTooSmall();
+/// @dev The plan does not consume all evaluations and challenges of the proof, or produces too few subpolynomials.
+error IncompleteVerification();
+/// @dev The evaluations of the results do not match the MLEs of the result columns.
+error ResultEvaluationMismatch();
+/// @dev The evaluation of the sumcheck polynomial does not match the subclaim of the sumcheck proof.
+error SumcheckEvaluationMismatch();
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {MODULUS} from "./Constants.sol";
+import {TruncatedLengthTooLarge} from "./Errors.sol";
+
+/// @title LagrangeBasisEvaluation
+/// @dev Evaluations over the Lagrange basis of length `2^nu` at a point with `nu` coordinates,
+/// truncated to the first `length` entries. These are the ports of `compute_truncated_lagrange_basis_sum`,
+/// `compute_truncated_lagrange_basis_inner_product` and `compute_rho_eval`.
+///
+/// Rather than recursing on the last coordinate, these iterate from the first coordinate. Unless the basis is not
+/// truncated at all, the truncated length of the basis over the first `j + 1` coordinates is `length mod 2^(j + 1)`,
+/// and so whether it covers the upper half of that basis is bit `j` of `length`.
+library LagrangeBasisEvaluation {
+    /// @dev Computes `sum A[i] for i in 0..length`, where `A` is the Lagrange basis evaluated at `point`.
+    /// This is the evaluation of the MLE of a column of `length` ones.
+    /// @param length The truncated length
+    /// @param point The evaluation point
+    /// @return result The sum
+    function computeTruncatedLagrangeBasisSum(uint256 length, uint256[] memory point)
+        internal
+        pure
+        returns (uint256 result)
+    {
+        uint256 numVariables = point.length;
+        if (length > uint256(1) << numVariables) revert TruncatedLengthTooLarge();
+        if (length == uint256(1) << numVariables) return 1;
+        for (uint256 i = 0; i < numVariables; ++i) {
+            uint256 firstHalfTerm = addmod(1, MODULUS - point[i], MODULUS);
+            if (((length >> i) & 1) == 1) {
+                result = addmod(firstHalfTerm, mulmod(point[i], result, MODULUS), MODULUS);
+            } else {
+                result = mulmod(firstHalfTerm, result, MODULUS);
+            }
+        }
+    }
+
+    /// @dev Computes `sum A[i] * B[i] for i in 0..length`, where `A` and `B` are the Lagrange basis evaluated at
+    /// `a` and `b`.
+    /// @param length The truncated length
+    /// @param a The first evaluation point
+    /// @param b The second evaluation point, which has the same number of coordinates as `a`
+    /// @return result The inner product
+    function computeTruncatedLagrangeBasisInnerProduct(uint256 length, uint256[] memory a, uint256[] memory b)
+        internal
+        pure
+        returns (uint256 result)
+    {
+        uint256 numVariables = a.length;
+        assert(numVariables == b.length);
+        if (length > uint256(1) << numVariables) revert TruncatedLengthTooLarge();
+        uint256 full = 1;
+        for (uint256 i = 0; i < numVariables; ++i) {
+            uint256 firstHalfTerm =
+                mulmod(addmod(1, MODULUS - a[i], MODULUS), addmod(1, MODULUS - b[i], MODULUS), MODULUS);
+            uint256 secondHalfTerm = mulmod(a[i], b[i], MODULUS);
+            if (((length >> i) & 1) == 1) {
+                result = addmod(mulmod(full, firstHalfTerm, MODULUS), mulmod(result, secondHalfTerm, MODULUS), MODULUS);
+            } else {
+                result = mulmod(result, firstHalfTerm, MODULUS);
+            }
+            full = mulmod(full, addmod(firstHalfTerm, secondHalfTerm, MODULUS), MODULUS);
+        }
+        if (length == uint256(1) << numVariables) result = full;
+    }
+
+    /// @dev Computes `sum i * A[i] for i in 0..length`, where `A` is the Lagrange basis evaluated at `point`.
+    /// This is the evaluation of the MLE of the row index column `0, 1, ..., length - 1`.
+    /// @param length The truncated length
+    /// @param point The evaluation point
+    /// @return result The evaluation
+    function computeRhoEval(uint256 length, uint256[] memory point) internal pure returns (uint256 result) {
+        uint256 numVariables = point.length;
+        if (length > uint256(1) << numVariables) revert TruncatedLengthTooLarge();
+        // The truncated sum and the untruncated row index evaluation over the coordinates so far
+        uint256 sum = 0;
+        uint256 full = 0;
+        uint256 halfLength = 1;
+        for (uint256 i = 0; i < numVariables; ++i) {
+            uint256 firstHalfTerm = addmod(1, MODULUS - point[i], MODULUS);
+            if (((length >> i) & 1) == 1) {
+                // Every entry of the upper half is offset by `2^i`
+                uint256 upperHalf = addmod(result, mulmod(halfLength, sum, MODULUS), MODULUS);
+                result = addmod(mulmod(firstHalfTerm, full, MODULUS), mulmod(point[i], upperHalf, MODULUS), MODULUS);
+                sum = addmod(firstHalfTerm, mulmod(point[i], sum, MODULUS), MODULUS);
+            } else {
+                result = mulmod(firstHalfTerm, result, MODULUS);
+                sum = mulmod(firstHalfTerm, sum, MODULUS);
+            }
+            full = addmod(full, mulmod(halfLength, point[i], MODULUS), MODULUS);
+            halfLength = mulmod(halfLength, 2, MODULUS);
+        }
+        if (length == uint256(1) << numVariables) result = full;
+    }
+}
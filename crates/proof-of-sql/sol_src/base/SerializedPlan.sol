@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {
+    BIGINT_TYPE_NUM,
+    BOOLEAN_TYPE_NUM,
+    INT128_TYPE_NUM,
+    INT_TYPE_NUM,
+    SMALLINT_TYPE_NUM,
+    TINYINT_TYPE_NUM
+} from "./Constants.sol";
+import {InvalidReference, InvalidSerializedPlan, UnsupportedColumnType} from "./Errors.sol";
+
+/// @title SerializedPlan
+/// @dev Reads a plan in the format of `EVMProofPlan`, where integers are big-endian and strings are their `u64`
+/// length followed by their bytes. Each function takes the offset to read at and returns the offset after what it read.
+library SerializedPlan {
+    /// @dev Reads a `u8`.
+    /// @param plan The serialized plan
+    /// @param offset The offset to read at
+    /// @return value The integer
+    /// @return next The offset after the integer
+    function readUint8(bytes memory plan, uint256 offset) internal pure returns (uint8 value, uint256 next) {
+        if (offset + 1 > plan.length) revert InvalidSerializedPlan();
+        value = uint8(plan[offset]);
+        next = offset + 1;
+    }
+
+    /// @dev Reads a `u64`.
+    /// @param plan The serialized plan
+    /// @param offset The offset to read at
+    /// @return value The integer
+    /// @return next The offset after the integer
+    function readUint64(bytes memory plan, uint256 offset) internal pure returns (uint64 value, uint256 next) {
+        if (offset + 8 > plan.length) revert InvalidSerializedPlan();
+        for (uint256 i = 0; i < 8; ++i) {
+            value = (value << 8) | uint8(plan[offset + i]);
+        }
+        next = offset + 8;
+    }
+
+    /// @dev Reads a 256-bit integer, which is how scalars are serialized.
+    /// @param plan The serialized plan
+    /// @param offset The offset to read at
+    /// @return value The integer
+    /// @return next The offset after the integer
+    function readUint256(bytes memory plan, uint256 offset) internal pure returns (uint256 value, uint256 next) {
+        if (offset + 32 > plan.length) revert InvalidSerializedPlan();
+        assembly {
+            value := mload(add(add(plan, 0x20), offset))
+        }
+        next = offset + 32;
+    }
+
+    /// @dev Skips a string, such as the name of a table or an alias.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the string
+    /// @return next The offset after the string
+    function skipString(bytes memory plan, uint256 offset) internal pure returns (uint256 next) {
+        uint64 length;
+        (length, next) = readUint64(plan, offset);
+        next += length;
+        if (next > plan.length) revert InvalidSerializedPlan();
+    }
+
+    /// @dev Skips a column type. Only the integer and boolean types are supported, which have no parameters.
+    /// @param plan The serialized plan
+    /// @param offset The offset to read at
+    /// @return next The offset after the column type
+    function skipColumnType(bytes memory plan, uint256 offset) internal pure returns (uint256 next) {
+        uint8 columnType;
+        (columnType, next) = readUint8(plan, offset);
+        if (!isSupportedColumnType(columnType)) revert UnsupportedColumnType();
+    }
+
+    /// @dev Reads the tables and columns that a plan refers to, which come before the plan itself.
+    /// @param plan The serialized plan
+    /// @return numTables The number of tables
+    /// @return numColumns The number of columns
+    /// @return next The offset of the plan itself
+    function readReferences(bytes memory plan)
+        internal
+        pure
+        returns (uint256 numTables, uint256 numColumns, uint256 next)
+    {
+        uint8 count;
+        (count, next) = readUint8(plan, 0);
+        numTables = count;
+        for (uint256 i = 0; i < numTables; ++i) {
+            next = skipString(plan, next);
+        }
+        (count, next) = readUint8(plan, next);
+        numColumns = count;
+        for (uint256 i = 0; i < numColumns; ++i) {
+            uint8 tableNumber;
+            (tableNumber, next) = readUint8(plan, next);
+            if (tableNumber >= numTables) revert InvalidReference();
+            next = skipColumnType(plan, skipString(plan, next));
+        }
+    }
+
+    /// @dev Whether the Solidity verifier supports a column type.
+    /// @param columnType The number of the column type
+    /// @return supported Whether the column type is supported
+    function isSupportedColumnType(uint8 columnType) internal pure returns (bool supported) {
+        supported = columnType == BIGINT_TYPE_NUM || columnType == BOOLEAN_TYPE_NUM || columnType == TINYINT_TYPE_NUM
+            || columnType == SMALLINT_TYPE_NUM || columnType == INT_TYPE_NUM || columnType == INT128_TYPE_NUM;
+    }
+}
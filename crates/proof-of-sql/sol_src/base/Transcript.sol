@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {BN254} from "./BN254.sol";
+import {FIELD_MODULUS, MODULUS} from "./Constants.sol";
+import {NonCanonicalECPoint, NonCanonicalScalar} from "./Errors.sol";
+
+/// @title Transcript
+/// @dev The public coin transcript of `Keccak256Transcript`.
+///
+/// Each challenge is the keccak256 hash of the previous challenge followed by the messages appended since then:
+/// ```pseudo-code
+/// challenge_(i+1) = keccak256(challenge_i, message_(i+1))
+/// ```
+library Transcript {
+    /// @dev The messages appended since the last challenge, prefixed by that challenge if there is one.
+    struct State {
+        bytes pending;
+    }
+
+    /// @dev Creates a new transcript.
+    /// @return state The empty transcript
+    function newTranscript() internal pure returns (State memory state) {
+        state.pending = "";
+    }
+
+    /// @dev Appends a raw message to the transcript.
+    /// @param self The transcript
+    /// @param message The message
+    function append(State memory self, bytes memory message) internal pure {
+        self.pending = bytes.concat(self.pending, message);
+    }
+
+    /// @dev Appends a `u64` as big-endian bytes, which is what `extend_as_be` does.
+    /// @param self The transcript
+    /// @param value The integer
+    function appendUint64(State memory self, uint64 value) internal pure {
+        append(self, abi.encodePacked(value));
+    }
+
+    /// @dev Appends a scalar as big-endian bytes, which is what `extend_scalars_as_be` does.
+    /// Reverts if the scalar is not canonical, since the prover can only append canonical scalars.
+    /// @param self The transcript
+    /// @param scalar The scalar
+    function appendScalar(State memory self, uint256 scalar) internal pure {
+        if (scalar > MODULUS - 1) revert NonCanonicalScalar();
+        append(self, abi.encodePacked(scalar));
+    }
+
+    /// @dev Appends an unsigned integer as a LEB128 varint, which is what `extend_serialize_as_le` does for a `usize`.
+    /// @param self The transcript
+    /// @param value The integer
+    function appendVarint(State memory self, uint64 value) internal pure {
+        bytes memory encoded;
+        while (value > 0x7f) {
+            encoded = bytes.concat(encoded, bytes1(uint8(value) | 0x80));
+            value >>= 7;
+        }
+        append(self, bytes.concat(encoded, bytes1(uint8(value))));
+    }
+
+    /// @dev Appends a `u64` as little-endian bytes, which is how `extend_canonical_serialize_as_le` appends the
+    /// length of a `Vec`.
+    /// @param self The transcript
+    /// @param value The integer
+    function appendUint64AsLe(State memory self, uint64 value) internal pure {
+        append(self, abi.encodePacked(bytes8(bytes32(reverseBytes(value)))));
+    }
+
+    /// @dev Appends scalars as their length followed by the little-endian bytes of each scalar,
+    /// which is what `extend_canonical_serialize_as_le` does for a `Vec` of scalars.
+    /// Reverts if a scalar is not canonical, since such a scalar can not be deserialized.
+    /// @param self The transcript
+    /// @param scalars The scalars
+    function appendScalarsAsLe(State memory self, uint256[] memory scalars) internal pure {
+        uint256 length = scalars.length;
+        appendUint64AsLe(self, uint64(length));
+        for (uint256 i = 0; i < length; ++i) {
+            if (scalars[i] > MODULUS - 1) revert NonCanonicalScalar();
+            append(self, abi.encodePacked(reverseBytes(scalars[i])));
+        }
+    }
+
+    /// @dev Appends points as their length followed by each compressed point,
+    /// which is what `extend_canonical_serialize_as_le` does for a `Vec` of points.
+    /// @param self The transcript
+    /// @param points The points
+    function appendPoints(State memory self, BN254.G1Point[] memory points) internal pure {
+        uint256 length = points.length;
+        appendUint64AsLe(self, uint64(length));
+        for (uint256 i = 0; i < length; ++i) {
+            appendPoint(self, points[i]);
+        }
+    }
+
+    /// @dev Appends a compressed point, which is what `append_to_transcript` does for a `HyperKZGCommitment`.
+    ///
+    /// A compressed point is the little-endian bytes of its x-coordinate, where the highest bit flags that the
+    /// y-coordinate is the larger of `y` and `-y` and the second highest bit flags the point at infinity.
+    /// Reverts if a coordinate is not canonical, since such a point can not be deserialized.
+    /// @param self The transcript
+    /// @param point The point
+    function appendPoint(State memory self, BN254.G1Point memory point) internal pure {
+        if (point.x > FIELD_MODULUS - 1 || point.y > FIELD_MODULUS - 1) revert NonCanonicalECPoint();
+        uint256 compressed;
+        if (point.x == 0 && point.y == 0) {
+            compressed = 1 << 254;
+        } else if (point.y > FIELD_MODULUS - point.y) {
+            compressed = point.x | (1 << 255);
+        } else {
+            compressed = point.x;
+        }
+        append(self, abi.encodePacked(reverseBytes(compressed)));
+    }
+
+    /// @dev Draws a challenge from the transcript, which is what `challenge_as_le` does.
+    /// @param self The transcript
+    /// @return challenge The raw challenge
+    function drawChallenge(State memory self) internal pure returns (bytes32 challenge) {
+        challenge = keccak256(self.pending);
+        self.pending = abi.encodePacked(challenge);
+    }
+
+    /// @dev Draws a scalar challenge from the transcript, which is what `scalar_challenge_as_be` does.
+    /// @param self The transcript
+    /// @return scalar The challenge as a big-endian integer reduced modulo the field order
+    function drawScalarChallenge(State memory self) internal pure returns (uint256 scalar) {
+        scalar = uint256(drawChallenge(self)) % MODULUS;
+    }
+
+    /// @dev Reverses the order of the bytes of a 256-bit integer.
+    /// @param value The integer
+    /// @return reversed The integer with its bytes reversed
+    function reverseBytes(uint256 value) private pure returns (uint256 reversed) {
+        reversed = value;
+        reversed = ((reversed & 0xff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00) >> 8)
+            | ((reversed & 0x00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff) << 8);
+        reversed = ((reversed & 0xffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000) >> 16)
+            | ((reversed & 0x0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff) << 16);
+        reversed = ((reversed & 0xffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000) >> 32)
+            | ((reversed & 0x00000000ffffffff00000000ffffffff00000000ffffffff00000000ffffffff) << 32);
+        reversed = ((reversed & 0xffffffffffffffff0000000000000000ffffffffffffffff0000000000000000) >> 64)
+            | ((reversed & 0x0000000000000000ffffffffffffffff0000000000000000ffffffffffffffff) << 64);
+        reversed = (reversed >> 128) | (reversed << 128);
+    }
+}
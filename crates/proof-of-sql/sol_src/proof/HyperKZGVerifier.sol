@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {BN254} from "../base/BN254.sol";
+import {MODULUS} from "../base/Constants.sol";
+import {HyperKZGProofSizeMismatch, HyperKZGSetupTooSmall, HyperKZGVerificationFailed} from "../base/Errors.sol";
+import {Transcript} from "../base/Transcript.sol";
+
+/// @title HyperKZGVerifier
+/// @dev The verifier of `HyperKZGCommitmentEvaluationProof`, for columns at offset `0`.
+library HyperKZGVerifier {
+    /// @dev An evaluation proof, see `HyperKZGCommitmentEvaluationProof`.
+    /// `com` are the commitments of the folded polynomials `f_1, ..., f_{nu-1}`, `v` are the evaluations of
+    /// `f_0, ..., f_{nu-1}` at `r`, `-r` and `r^2` and `w` are the KZG witnesses of the batched polynomial
+    /// at these points.
+    struct Proof {
+        BN254.G1Point[] com;
+        uint256[3][] v;
+        BN254.G1Point[] w;
+    }
+
+    /// @dev The public setup of the verifier, see `HyperKZGVerifierSetup`.
+    struct VerifierSetup {
+        BN254.G1Point g1;
+        BN254.G2Point g2;
+        BN254.G2Point tauG2;
+        uint256 numGenerators;
+    }
+
+    /// @dev The challenges of an evaluation proof, in the order they are drawn.
+    struct Challenges {
+        uint256 r;
+        uint256 q;
+        uint256 d;
+    }
+
+    /// @dev Verifies that the batched commitments evaluate to the batched evaluations at `point`,
+    /// which is what `verify_batched_proof` does.
+    /// @param transcript The transcript
+    /// @param proof The evaluation proof
+    /// @param commitments The commitments
+    /// @param batchingFactors The factor of each commitment and evaluation in the batch
+    /// @param evaluations The evaluation of each committed column
+    /// @param point The evaluation point
+    /// @param tableLength The length of the range of generators that the columns are committed with
+    /// @param setup The public setup of the verifier
+    function verifyBatchedProof(
+        Transcript.State memory transcript,
+        Proof memory proof,
+        BN254.G1Point[] memory commitments,
+        uint256[] memory batchingFactors,
+        uint256[] memory evaluations,
+        uint256[] memory point,
+        uint256 tableLength,
+        VerifierSetup memory setup
+    ) internal view {
+        if (tableLength > setup.numGenerators) revert HyperKZGSetupTooSmall();
+        (BN254.G1Point memory commit, uint256 product) = batch(commitments, batchingFactors, evaluations);
+        if (point.length == 0) {
+            // The committed polynomial is the constant `product`.
+            if (proof.com.length != 0 || proof.v.length != 0 || proof.w.length != 0) {
+                revert HyperKZGProofSizeMismatch();
+            }
+            BN254.G1Point memory expected = BN254.mul(setup.g1, product);
+            if (commit.x != expected.x || commit.y != expected.y) revert HyperKZGVerificationFailed();
+            return;
+        }
+        Challenges memory challenges = drawChallenges(transcript, proof, point.length);
+        checkFolds(proof.v, point, product, challenges.r);
+        checkOpenings(proof, commit, challenges, setup);
+    }
+
+    /// @dev Combines the commitments and the evaluations with the batching factors.
+    /// @param commitments The commitments
+    /// @param batchingFactors The factor of each commitment and evaluation in the batch
+    /// @param evaluations The evaluation of each committed column
+    /// @return commit The batched commitment
+    /// @return product The batched evaluation
+    function batch(BN254.G1Point[] memory commitments, uint256[] memory batchingFactors, uint256[] memory evaluations)
+        private
+        view
+        returns (BN254.G1Point memory commit, uint256 product)
+    {
+        uint256 numCommitments = commitments.length;
+        if (batchingFactors.length != numCommitments || evaluations.length != numCommitments) {
+            revert HyperKZGProofSizeMismatch();
+        }
+        for (uint256 i = 0; i < numCommitments; ++i) {
+            commit = BN254.add(commit, BN254.mul(commitments[i], batchingFactors[i]));
+            product = addmod(product, mulmod(evaluations[i], batchingFactors[i], MODULUS), MODULUS);
+        }
+    }
+
+    /// @dev Appends the proof to the transcript and draws the challenges the same way the prover does.
+    /// @param transcript The transcript
+    /// @param proof The evaluation proof
+    /// @param nu The number of coordinates of the evaluation point, which is not `0`
+    /// @return challenges The challenges
+    function drawChallenges(Transcript.State memory transcript, Proof memory proof, uint256 nu)
+        private
+        pure
+        returns (Challenges memory challenges)
+    {
+        if (proof.com.length != nu - 1 || proof.v.length != nu || proof.w.length != 3) {
+            revert HyperKZGProofSizeMismatch();
+        }
+        Transcript.appendPoints(transcript, proof.com);
+        challenges.r = Transcript.drawScalarChallenge(transcript);
+        if (challenges.r == 0) revert HyperKZGVerificationFailed();
+        for (uint256 j = 0; j < nu; ++j) {
+            for (uint256 k = 0; k < 3; ++k) {
+                Transcript.appendScalar(transcript, proof.v[j][k]);
+            }
+        }
+        challenges.q = Transcript.drawScalarChallenge(transcript);
+        Transcript.appendPoints(transcript, proof.w);
+        challenges.d = Transcript.drawScalarChallenge(transcript);
+    }
+
+    /// @dev Checks that `f_{j+1}(r^2)` is the fold of `f_j(r)` and `f_j(-r)`, where `f_nu` is the constant `product`.
+    /// @param v The evaluations of `f_0, ..., f_{nu-1}` at `r`, `-r` and `r^2`
+    /// @param point The evaluation point, whose coordinates are the folding factors
+    /// @param product The batched evaluation, which is the evaluation of `f_nu`
+    /// @param r The challenge that the folded polynomials are evaluated at
+    function checkFolds(uint256[3][] memory v, uint256[] memory point, uint256 product, uint256 r) private pure {
+        for (uint256 j = 0; j < point.length; ++j) {
+            uint256 x = point[j];
+            // r * (1 - x) * (f_j(r) + f_j(-r)) + x * (f_j(r) - f_j(-r))
+            uint256 expected = mulmod(r, addmod(1, MODULUS - x, MODULUS), MODULUS);
+            expected = mulmod(expected, addmod(v[j][0], v[j][1], MODULUS), MODULUS);
+            expected = addmod(expected, mulmod(x, addmod(v[j][0], MODULUS - v[j][1], MODULUS), MODULUS), MODULUS);
+            uint256 folded = j + 1 < point.length ? v[j + 1][2] : product;
+            if (mulmod(mulmod(2, r, MODULUS), folded, MODULUS) != expected) revert HyperKZGVerificationFailed();
+        }
+    }
+
+    /// @dev Checks the openings of the batched polynomial `B = f_0 + q * f_1 + ... + q^(nu-1) * f_{nu-1}`
+    /// at `u_k` all at once, i.e. that `tau * W_k = C_B - B(u_k) * G + u_k * W_k`, combined with powers of `d`.
+    /// @param proof The evaluation proof
+    /// @param commit The batched commitment, which is the commitment of `f_0`
+    /// @param challenges The challenges
+    /// @param setup The public setup of the verifier
+    function checkOpenings(
+        Proof memory proof,
+        BN254.G1Point memory commit,
+        Challenges memory challenges,
+        VerifierSetup memory setup
+    ) private view {
+        uint256[3] memory dPowers = [uint256(1), challenges.d, mulmod(challenges.d, challenges.d, MODULUS)];
+        (BN254.G1Point memory batchedCommit, uint256 batchedEvaluation) =
+            batchFolds(proof, commit, challenges.q, dPowers);
+        uint256 dSum = addmod(addmod(dPowers[0], dPowers[1], MODULUS), dPowers[2], MODULUS);
+        BN254.G1Point memory lhs =
+            BN254.add(BN254.mul(batchedCommit, dSum), BN254.mul(setup.g1, MODULUS - batchedEvaluation));
+        uint256 r = challenges.r;
+        lhs = BN254.add(lhs, combineWitnesses(proof.w, dPowers, [r, MODULUS - r, mulmod(r, r, MODULUS)]));
+        BN254.G1Point memory rhs = combineWitnesses(proof.w, dPowers, [uint256(1), 1, 1]);
+        if (!BN254.pairingCheck(lhs, setup.g2, BN254.negate(rhs), setup.tauG2)) revert HyperKZGVerificationFailed();
+    }
+
+    /// @dev Computes the commitment of `B` and `sum_k d^k * B(u_k)`.
+    /// @param proof The evaluation proof
+    /// @param commit The batched commitment, which is the commitment of `f_0`
+    /// @param q The challenge that the folded polynomials are batched with
+    /// @param dPowers The powers `1`, `d` and `d^2`
+    /// @return batchedCommit The commitment of `B`
+    /// @return batchedEvaluation The combined evaluations of `B`
+    function batchFolds(Proof memory proof, BN254.G1Point memory commit, uint256 q, uint256[3] memory dPowers)
+        private
+        view
+        returns (BN254.G1Point memory batchedCommit, uint256 batchedEvaluation)
+    {
+        batchedCommit = commit;
+        uint256 qPower = 1;
+        uint256 nu = proof.v.length;
+        for (uint256 j = 0; j < nu; ++j) {
+            if (j > 0) batchedCommit = BN254.add(batchedCommit, BN254.mul(proof.com[j - 1], qPower));
+            uint256[3] memory v = proof.v[j];
+            uint256 combined = addmod(v[0], mulmod(dPowers[1], v[1], MODULUS), MODULUS);
+            combined = addmod(combined, mulmod(dPowers[2], v[2], MODULUS), MODULUS);
+            batchedEvaluation = addmod(batchedEvaluation, mulmod(qPower, combined, MODULUS), MODULUS);
+            qPower = mulmod(qPower, q, MODULUS);
+        }
+    }
+
+    /// @dev Computes `sum_k d^k * factors_k * W_k`.
+    /// @param w The witnesses
+    /// @param dPowers The powers `1`, `d` and `d^2`
+    /// @param factors The factor of each witness
+    /// @return sum The combined witnesses
+    function combineWitnesses(BN254.G1Point[] memory w, uint256[3] memory dPowers, uint256[3] memory factors)
+        private
+        view
+        returns (BN254.G1Point memory sum)
+    {
+        for (uint256 k = 0; k < 3; ++k) {
+            sum = BN254.add(sum, BN254.mul(w[k], mulmod(dPowers[k], factors[k], MODULUS)));
+        }
+    }
+}
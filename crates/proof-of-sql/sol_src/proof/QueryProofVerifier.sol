@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {BN254} from "../base/BN254.sol";
+import {
+    BIGINT_TYPE_NUM,
+    BOOLEAN_TYPE_NUM,
+    INT128_TYPE_NUM,
+    INT_TYPE_NUM,
+    MODULUS,
+    SCALAR_TYPE_NUM,
+    SMALLINT_TYPE_NUM,
+    TINYINT_TYPE_NUM
+} from "../base/Constants.sol";
+import {
+    IncompleteVerification,
+    InvalidSerializedPlan,
+    ReferenceCountMismatch,
+    ResultColumnCountMismatch,
+    ResultColumnLengthMismatch,
+    ResultEvaluationMismatch,
+    ResultValueOutOfRange,
+    SumcheckEvaluationMismatch,
+    UnsupportedColumnType
+} from "../base/Errors.sol";
+import {LagrangeBasisEvaluation} from "../base/LagrangeBasisEvaluation.sol";
+import {SerializedPlan} from "../base/SerializedPlan.sol";
+import {Transcript} from "../base/Transcript.sol";
+import {ProofPlan} from "../proof_plans/ProofPlan.sol";
+import {HyperKZGVerifier} from "./HyperKZGVerifier.sol";
+import {Sumcheck} from "./Sumcheck.sol";
+import {VerificationBuilder} from "./VerificationBuilder.sol";
+
+/// @title QueryProofVerifier
+/// @dev The verifier of a `QueryProof` with `HyperKZG` commitments for a single `EVMProofPlan`,
+/// which is what `QueryProof::verify` does.
+///
+/// Only filters and projections are supported, see `ProofPlan`, whose expressions are column references, literals,
+/// `=`, `AND`, `OR`, `NOT`, `+`, `-` and `*`, see `ProofExpr`. Every other plan or expression, such as a join,
+/// an aggregation, a `GROUP BY`, an `ORDER BY`, an inequality or a division, reverts with `UnsupportedProofPlan` or
+/// `UnsupportedProofExpr` and can only be verified in Rust. The supported plans never produce first round MLEs,
+/// rho evaluations, bit distributions or division by zero counts, so the proof has none of them.
+/// Every table has to start at row `0`, which is the only offset `HyperKZG` supports.
+library QueryProofVerifier {
+    /// @dev A column of the result before `finalize_result`, which is the result that is proven.
+    /// Booleans are `0` or `1`, and the results of arithmetic are scalars.
+    struct ResultColumn {
+        string name;
+        uint8 columnType;
+        int256[] values;
+    }
+
+    /// @dev The parts of a `QueryProof` that the supported plans produce.
+    struct QueryProof {
+        uint64[] oneEvaluationLengths;
+        BN254.G1Point[] commitments;
+        uint256[] sumcheckCoefficients;
+        uint256[] pcsProofEvaluations;
+        HyperKZGVerifier.Proof evaluationProof;
+        uint64 rangeLength;
+        uint64 subpolynomialConstraintCount;
+        uint64 postResultChallengeCount;
+    }
+
+    /// @dev Verifies that `result` is the result of `plan`.
+    /// @param plan The bytes of the `EVMProofPlan`
+    /// @param result The result
+    /// @param proof The proof
+    /// @param tableLengths The length of each table that the plan refers to
+    /// @param columnCommitments The commitment of each column that the plan refers to
+    /// @param setup The public setup of the verifier
+    /// @return verificationHash The hash that `QueryData` carries, which is the final challenge of the transcript
+    function verify(
+        bytes memory plan,
+        ResultColumn[] memory result,
+        QueryProof memory proof,
+        uint256[] memory tableLengths,
+        BN254.G1Point[] memory columnCommitments,
+        HyperKZGVerifier.VerifierSetup memory setup
+    ) internal view returns (bytes32 verificationHash) {
+        uint256 planOffset = checkReferences(plan, tableLengths.length, columnCommitments.length);
+        Transcript.State memory transcript = makeTranscript(plan, result, proof);
+        (uint256[] memory batchingFactors, uint256[] memory evaluationPoint) =
+            verifyProofOfPlan(transcript, plan, planOffset, result, proof, tableLengths, columnCommitments.length);
+        verifyEvaluations(transcript, proof, columnCommitments, batchingFactors, evaluationPoint, setup);
+        verificationHash = Transcript.drawChallenge(transcript);
+    }
+
+    /// @dev Checks that the number of tables and columns that the plan refers to match what the caller passed.
+    /// @param plan The bytes of the `EVMProofPlan`
+    /// @param numTables The number of table lengths
+    /// @param numColumns The number of column commitments
+    /// @return planOffset The offset of the plan after its references
+    function checkReferences(bytes memory plan, uint256 numTables, uint256 numColumns)
+        private
+        pure
+        returns (uint256 planOffset)
+    {
+        uint256 numPlanTables;
+        uint256 numPlanColumns;
+        (numPlanTables, numPlanColumns, planOffset) = SerializedPlan.readReferences(plan);
+        if (numPlanTables != numTables || numPlanColumns != numColumns) revert ReferenceCountMismatch();
+    }
+
+    /// @dev Creates the transcript along with the result, the plan and the lengths of the proof,
+    /// which is what `make_transcript` does.
+    /// @param plan The bytes of the `EVMProofPlan`
+    /// @param result The result
+    /// @param proof The proof
+    /// @return transcript The transcript
+    function makeTranscript(bytes memory plan, ResultColumn[] memory result, QueryProof memory proof)
+        private
+        pure
+        returns (Transcript.State memory transcript)
+    {
+        transcript = Transcript.newTranscript();
+        uint256 numResultColumns = result.length;
+        for (uint256 i = 0; i < numResultColumns; ++i) {
+            appendResultColumn(transcript, result[i]);
+        }
+        // The plan is serialized as a byte array.
+        Transcript.appendVarint(transcript, uint64(plan.length));
+        Transcript.append(transcript, plan);
+        Transcript.appendVarint(transcript, proof.rangeLength);
+        // min_row_num
+        Transcript.appendVarint(transcript, 0);
+        uint256 numOneEvaluationLengths = proof.oneEvaluationLengths.length;
+        Transcript.appendVarint(transcript, uint64(numOneEvaluationLengths));
+        for (uint256 i = 0; i < numOneEvaluationLengths; ++i) {
+            Transcript.appendVarint(transcript, proof.oneEvaluationLengths[i]);
+        }
+        // rho_evaluation_lengths
+        Transcript.appendVarint(transcript, 0);
+        Transcript.appendVarint(transcript, proof.postResultChallengeCount);
+        // There are no first round commitments, and the bit distributions are always empty.
+        Transcript.appendVarint(transcript, 0);
+    }
+
+    /// @dev Appends a result column, which is what `extend_transcript_with_owned_table` does for each column.
+    /// @param transcript The transcript
+    /// @param column The result column
+    function appendResultColumn(Transcript.State memory transcript, ResultColumn memory column) private pure {
+        Transcript.append(transcript, bytes(column.name));
+        uint8 columnType = column.columnType;
+        if (!SerializedPlan.isSupportedColumnType(columnType) && columnType != SCALAR_TYPE_NUM) {
+            revert UnsupportedColumnType();
+        }
+        uint256 numValues = column.values.length;
+        for (uint256 i = 0; i < numValues; ++i) {
+            Transcript.append(transcript, encodeResultValue(columnType, column.values[i]));
+        }
+    }
+
+    /// @dev Encodes an entry of a result column as big-endian bytes of the size of its type.
+    /// Reverts if the entry does not fit into the type of the column.
+    /// @param columnType The type of the column
+    /// @param value The entry
+    /// @return encoded The encoded entry
+    function encodeResultValue(uint8 columnType, int256 value) private pure returns (bytes memory encoded) {
+        if (columnType == BOOLEAN_TYPE_NUM) {
+            if (value != 0 && value != 1) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(uint8(uint256(value)));
+        } else if (columnType == TINYINT_TYPE_NUM) {
+            if (value != int8(value)) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(int8(value));
+        } else if (columnType == SMALLINT_TYPE_NUM) {
+            if (value != int16(value)) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(int16(value));
+        } else if (columnType == INT_TYPE_NUM) {
+            if (value != int32(value)) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(int32(value));
+        } else if (columnType == BIGINT_TYPE_NUM) {
+            if (value != int64(value)) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(int64(value));
+        } else if (columnType == INT128_TYPE_NUM) {
+            if (value != int128(value)) revert ResultValueOutOfRange();
+            encoded = abi.encodePacked(int128(value));
+        } else {
+            // A scalar is encoded as its canonical value.
+            encoded = abi.encodePacked(toScalar(value));
+        }
+    }
+
+    /// @dev Converts an entry of a result column to a scalar, where `-x` is `MODULUS - x`.
+    /// Reverts if the entry is not in `(-MODULUS, MODULUS)`.
+    /// @param value The entry
+    /// @return scalar The scalar
+    function toScalar(int256 value) private pure returns (uint256 scalar) {
+        if (value > -int256(MODULUS) && value < int256(MODULUS)) {
+            scalar = value < 0 ? MODULUS - uint256(-value) : uint256(value);
+        } else {
+            revert ResultValueOutOfRange();
+        }
+    }
+
+    /// @dev Draws `count` scalar challenges.
+    /// @param transcript The transcript
+    /// @param count The number of challenges
+    /// @return challenges The challenges
+    function drawScalarChallenges(Transcript.State memory transcript, uint256 count)
+        private
+        pure
+        returns (uint256[] memory challenges)
+    {
+        challenges = new uint256[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            challenges[i] = Transcript.drawScalarChallenge(transcript);
+        }
+    }
+
+    /// @dev Verifies the sumcheck proof up to its evaluation check and sets up the verification builder.
+    /// @param transcript The transcript
+    /// @param proof The proof
+    /// @return builder The verification builder
+    /// @return expectedEvaluation The evaluation of the sumcheck polynomial that the plan has to match
+    /// @return batchingFactors The factor of each MLE evaluation in the evaluation proof
+    function verifySumcheckRounds(Transcript.State memory transcript, QueryProof memory proof)
+        private
+        pure
+        returns (
+            VerificationBuilder.Builder memory builder,
+            uint256 expectedEvaluation,
+            uint256[] memory batchingFactors
+        )
+    {
+        builder.postResultChallenges = drawScalarChallenges(transcript, proof.postResultChallengeCount);
+        builder.remainingPostResultChallenges = proof.postResultChallengeCount;
+        uint256 numCommitments = proof.commitments.length;
+        for (uint256 i = 0; i < numCommitments; ++i) {
+            Transcript.appendPoint(transcript, proof.commitments[i]);
+        }
+        // The bit distributions and the division by zero counts are empty.
+        Transcript.appendVarint(transcript, 0);
+        Transcript.appendVarint(transcript, 0);
+
+        uint256 numVariables = 1;
+        while (uint256(1) << numVariables < proof.rangeLength) {
+            ++numVariables;
+        }
+        uint256[] memory entrywisePoint = drawScalarChallenges(transcript, numVariables);
+        builder.subpolynomialMultipliers = drawScalarChallenges(transcript, proof.subpolynomialConstraintCount);
+        Sumcheck.Subclaim memory subclaim =
+            Sumcheck.verifyWithoutEvaluation(transcript, proof.sumcheckCoefficients, numVariables, 0);
+        expectedEvaluation = subclaim.expectedEvaluation;
+        builder.maxMultiplicands = subclaim.maxMultiplicands;
+        builder.evaluationPoint = subclaim.evaluationPoint;
+        builder.randomEvaluation = LagrangeBasisEvaluation.computeTruncatedLagrangeBasisInnerProduct(
+            proof.rangeLength, subclaim.evaluationPoint, entrywisePoint
+        );
+
+        // There are no first round MLE evaluations.
+        Transcript.appendScalarsAsLe(transcript, new uint256[](0));
+        Transcript.appendScalarsAsLe(transcript, proof.pcsProofEvaluations);
+        batchingFactors = drawScalarChallenges(transcript, proof.pcsProofEvaluations.length);
+        builder.mleEvaluations = proof.pcsProofEvaluations;
+        builder.oneEvaluationLengths = proof.oneEvaluationLengths;
+    }
+
+    /// @dev Verifies the sumcheck proof and checks it against the evaluation of the plan.
+    /// @param transcript The transcript
+    /// @param plan The bytes of the `EVMProofPlan`
+    /// @param planOffset The offset of the plan after its references
+    /// @param result The result
+    /// @param proof The proof
+    /// @param tableLengths The length of each table that the plan refers to
+    /// @param numColumns The number of columns that the plan refers to
+    /// @return batchingFactors The factor of each MLE evaluation in the evaluation proof
+    /// @return evaluationPoint The evaluation point
+    function verifyProofOfPlan(
+        Transcript.State memory transcript,
+        bytes memory plan,
+        uint256 planOffset,
+        ResultColumn[] memory result,
+        QueryProof memory proof,
+        uint256[] memory tableLengths,
+        uint256 numColumns
+    ) private pure returns (uint256[] memory batchingFactors, uint256[] memory evaluationPoint) {
+        VerificationBuilder.Builder memory builder;
+        uint256 expectedEvaluation;
+        (builder, expectedEvaluation, batchingFactors) = verifySumcheckRounds(transcript, proof);
+        evaluationPoint = builder.evaluationPoint;
+        verifyPlan(plan, planOffset, result, builder, tableLengths, numColumns);
+        if (builder.sumcheckEvaluation != expectedEvaluation) revert SumcheckEvaluationMismatch();
+    }
+
+    /// @dev Evaluates the plan and checks the evaluations of the result.
+    /// @param plan The bytes of the `EVMProofPlan`
+    /// @param planOffset The offset of the plan after its references
+    /// @param result The result
+    /// @param builder The verification builder
+    /// @param tableLengths The length of each table that the plan refers to
+    /// @param numColumns The number of columns that the plan refers to
+    function verifyPlan(
+        bytes memory plan,
+        uint256 planOffset,
+        ResultColumn[] memory result,
+        VerificationBuilder.Builder memory builder,
+        uint256[] memory tableLengths,
+        uint256 numColumns
+    ) private pure {
+        // The columns are evaluated first, in the order that the plan refers to them.
+        uint256[] memory columnEvaluations = VerificationBuilder.consumeMleEvaluations(builder, numColumns);
+        (uint256 end, uint256[] memory evaluations) = ProofPlan.verifierEvaluate(
+            plan,
+            planOffset,
+            builder,
+            columnEvaluations,
+            computeTableOneEvaluations(tableLengths, builder.evaluationPoint)
+        );
+        if (end != plan.length) revert InvalidSerializedPlan();
+        checkResult(result, evaluations, builder.evaluationPoint);
+        if (!VerificationBuilder.isCompleted(builder)) revert IncompleteVerification();
+    }
+
+    /// @dev Computes the evaluation of a column of ones as long as each table.
+    /// @param tableLengths The length of each table
+    /// @param point The evaluation point
+    /// @return tableOneEvaluations The evaluation of each column of ones
+    function computeTableOneEvaluations(uint256[] memory tableLengths, uint256[] memory point)
+        private
+        pure
+        returns (uint256[] memory tableOneEvaluations)
+    {
+        uint256 numTables = tableLengths.length;
+        tableOneEvaluations = new uint256[](numTables);
+        for (uint256 i = 0; i < numTables; ++i) {
+            tableOneEvaluations[i] = LagrangeBasisEvaluation.computeTruncatedLagrangeBasisSum(tableLengths[i], point);
+        }
+    }
+
+    /// @dev Checks that the evaluations of the result that the plan computed match the MLEs of the result columns.
+    /// @param result The result
+    /// @param evaluations The evaluation of each result column that the plan computed
+    /// @param point The evaluation point
+    function checkResult(ResultColumn[] memory result, uint256[] memory evaluations, uint256[] memory point)
+        private
+        pure
+    {
+        uint256 numResultColumns = result.length;
+        if (numResultColumns != evaluations.length) revert ResultColumnCountMismatch();
+        if (numResultColumns == 0) return;
+        uint256 numRows = result[0].values.length;
+        uint256[] memory evaluationVector = computeEvaluationVector(numRows, point);
+        for (uint256 i = 0; i < numResultColumns; ++i) {
+            if (result[i].values.length != numRows) revert ResultColumnLengthMismatch();
+            if (computeColumnEvaluation(result[i].values, evaluationVector) != evaluations[i]) {
+                revert ResultEvaluationMismatch();
+            }
+        }
+    }
+
+    /// @dev Computes the MLE of a result column as its inner product with the evaluation vector.
+    /// @param values The entries of the column
+    /// @param evaluationVector The evaluation vector
+    /// @return eval The evaluation of the column
+    function computeColumnEvaluation(int256[] memory values, uint256[] memory evaluationVector)
+        private
+        pure
+        returns (uint256 eval)
+    {
+        uint256 numRows = values.length;
+        for (uint256 j = 0; j < numRows; ++j) {
+            eval = addmod(eval, mulmod(toScalar(values[j]), evaluationVector[j], MODULUS), MODULUS);
+        }
+    }
+
+    /// @dev Computes the first `length` entries of the Lagrange basis evaluated at `point`,
+    /// which is what `compute_evaluation_vector` does.
+    /// @param length The number of entries
+    /// @param point The evaluation point
+    /// @return evaluationVector The entries
+    function computeEvaluationVector(uint256 length, uint256[] memory point)
+        private
+        pure
+        returns (uint256[] memory evaluationVector)
+    {
+        uint256 numVariables = point.length;
+        if (length > uint256(1) << numVariables) revert ResultColumnLengthMismatch();
+        evaluationVector = new uint256[](length);
+        for (uint256 j = 0; j < length; ++j) {
+            uint256 entry = 1;
+            for (uint256 k = 0; k < numVariables; ++k) {
+                entry = mulmod(entry, (j >> k) & 1 == 1 ? point[k] : addmod(1, MODULUS - point[k], MODULUS), MODULUS);
+            }
+            evaluationVector[j] = entry;
+        }
+    }
+
+    /// @dev Verifies the evaluation proof of all MLE evaluations, where the column commitments come before the
+    /// commitments of the proof.
+    /// @param transcript The transcript
+    /// @param proof The proof
+    /// @param columnCommitments The commitment of each column that the plan refers to
+    /// @param batchingFactors The factor of each MLE evaluation in the evaluation proof
+    /// @param evaluationPoint The evaluation point
+    /// @param setup The public setup of the verifier
+    function verifyEvaluations(
+        Transcript.State memory transcript,
+        QueryProof memory proof,
+        BN254.G1Point[] memory columnCommitments,
+        uint256[] memory batchingFactors,
+        uint256[] memory evaluationPoint,
+        HyperKZGVerifier.VerifierSetup memory setup
+    ) private view {
+        uint256 numColumns = columnCommitments.length;
+        uint256 numCommitments = proof.commitments.length;
+        BN254.G1Point[] memory commitments = new BN254.G1Point[](numColumns + numCommitments);
+        for (uint256 i = 0; i < numColumns; ++i) {
+            commitments[i] = columnCommitments[i];
+        }
+        for (uint256 i = 0; i < numCommitments; ++i) {
+            commitments[numColumns + i] = proof.commitments[i];
+        }
+        HyperKZGVerifier.verifyBatchedProof(
+            transcript,
+            proof.evaluationProof,
+            commitments,
+            batchingFactors,
+            proof.pcsProofEvaluations,
+            evaluationPoint,
+            proof.rangeLength,
+            setup
+        );
+    }
+}
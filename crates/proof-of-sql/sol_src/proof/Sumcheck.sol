@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {MODULUS} from "../base/Constants.sol";
+import {InvalidSumcheckProofSize, RoundEvaluationMismatch} from "../base/Errors.sol";
+import {Transcript} from "../base/Transcript.sol";
+
+/// @title Sumcheck
+/// @dev The verifier of `SumcheckProof`, up to the final evaluation check.
+library Sumcheck {
+    /// @dev The claim that remains after the rounds of the sumcheck protocol are verified, i.e. that the
+    /// polynomial evaluates to `expectedEvaluation` at `evaluationPoint`.
+    struct Subclaim {
+        uint256[] evaluationPoint;
+        uint256 expectedEvaluation;
+        uint256 maxMultiplicands;
+    }
+
+    /// @dev Verifies the rounds of a sumcheck proof, which is what `verify_without_evaluation` does.
+    ///
+    /// The coefficients of each round polynomial are in order of decreasing degree, so a round polynomial `p`
+    /// has `p(0)` as its last coefficient and `p(1)` as the sum of its coefficients.
+    /// @param transcript The transcript
+    /// @param coefficients The coefficients of all round polynomials
+    /// @param numVariables The number of variables of the polynomial
+    /// @param claimedSum The claimed sum of the polynomial over the boolean hypercube
+    /// @return subclaim The claim about the evaluation of the polynomial that remains to be checked
+    function verifyWithoutEvaluation(
+        Transcript.State memory transcript,
+        uint256[] memory coefficients,
+        uint256 numVariables,
+        uint256 claimedSum
+    ) internal pure returns (Subclaim memory subclaim) {
+        uint256 numCoefficients = coefficients.length;
+        if (numVariables == 0 || numCoefficients == 0 || numCoefficients % numVariables != 0) {
+            revert InvalidSumcheckProofSize();
+        }
+        uint256 maxMultiplicands = numCoefficients / numVariables - 1;
+        Transcript.appendUint64(transcript, uint64(maxMultiplicands));
+        Transcript.appendUint64(transcript, uint64(numVariables));
+        // This challenge keeps the transcript messages grouped.
+        Transcript.drawScalarChallenge(transcript);
+
+        subclaim.evaluationPoint = new uint256[](numVariables);
+        subclaim.maxMultiplicands = maxMultiplicands;
+        uint256 expectedEvaluation = claimedSum;
+        for (uint256 round = 0; round < numVariables; ++round) {
+            uint256 start = round * (maxMultiplicands + 1);
+            uint256 end = start + maxMultiplicands + 1;
+            for (uint256 i = start; i < end; ++i) {
+                Transcript.appendScalar(transcript, coefficients[i]);
+            }
+            uint256 roundChallenge = Transcript.drawScalarChallenge(transcript);
+            subclaim.evaluationPoint[round] = roundChallenge;
+
+            uint256 roundEvaluation = coefficients[start];
+            uint256 actualSum = addmod(roundEvaluation, coefficients[end - 1], MODULUS);
+            for (uint256 i = start + 1; i < end; ++i) {
+                roundEvaluation = addmod(mulmod(roundEvaluation, roundChallenge, MODULUS), coefficients[i], MODULUS);
+                actualSum = addmod(actualSum, coefficients[i], MODULUS);
+            }
+            if (actualSum != expectedEvaluation) revert RoundEvaluationMismatch();
+            expectedEvaluation = roundEvaluation;
+        }
+        subclaim.expectedEvaluation = expectedEvaluation;
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {MODULUS} from "../base/Constants.sol";
+import {
+    ConstraintCountMismatch,
+    PostResultCountMismatch,
+    SumcheckProofTooSmall,
+    TooFewMLEEvaluations,
+    TooFewOneLengths
+} from "../base/Errors.sol";
+import {LagrangeBasisEvaluation} from "../base/LagrangeBasisEvaluation.sol";
+
+/// @title VerificationBuilder
+/// @dev The state of the verifier while it evaluates a plan, which is what `VerificationBuilder` tracks.
+library VerificationBuilder {
+    /// @dev The evaluations and challenges that the plan consumes, along with the subpolynomials it has produced.
+    struct Builder {
+        uint256[] mleEvaluations;
+        uint256 consumedMleEvaluations;
+        uint64[] oneEvaluationLengths;
+        uint256 consumedOneEvaluations;
+        // The last challenge is consumed first.
+        uint256[] postResultChallenges;
+        uint256 remainingPostResultChallenges;
+        uint256[] subpolynomialMultipliers;
+        uint256 producedSubpolynomials;
+        uint256 maxMultiplicands;
+        uint256[] evaluationPoint;
+        uint256 randomEvaluation;
+        uint256 sumcheckEvaluation;
+    }
+
+    /// @dev Consumes the next MLE evaluation of the proof.
+    /// @param builder The builder
+    /// @return eval The evaluation
+    function consumeMleEvaluation(Builder memory builder) internal pure returns (uint256 eval) {
+        uint256 index = builder.consumedMleEvaluations;
+        if (index >= builder.mleEvaluations.length) revert TooFewMLEEvaluations();
+        eval = builder.mleEvaluations[index];
+        builder.consumedMleEvaluations = index + 1;
+    }
+
+    /// @dev Consumes the next `count` MLE evaluations of the proof.
+    /// @param builder The builder
+    /// @param count The number of evaluations
+    /// @return evals The evaluations
+    function consumeMleEvaluations(Builder memory builder, uint256 count)
+        internal
+        pure
+        returns (uint256[] memory evals)
+    {
+        evals = new uint256[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            evals[i] = consumeMleEvaluation(builder);
+        }
+    }
+
+    /// @dev Consumes the evaluation of a column of ones whose length is the next one evaluation length of the proof.
+    /// @param builder The builder
+    /// @return eval The evaluation
+    function consumeOneEvaluation(Builder memory builder) internal pure returns (uint256 eval) {
+        uint256 index = builder.consumedOneEvaluations;
+        if (index >= builder.oneEvaluationLengths.length) revert TooFewOneLengths();
+        eval = LagrangeBasisEvaluation.computeTruncatedLagrangeBasisSum(
+            builder.oneEvaluationLengths[index], builder.evaluationPoint
+        );
+        builder.consumedOneEvaluations = index + 1;
+    }
+
+    /// @dev Consumes the next post-result challenge.
+    /// @param builder The builder
+    /// @return challenge The challenge
+    function consumePostResultChallenge(Builder memory builder) internal pure returns (uint256 challenge) {
+        uint256 remaining = builder.remainingPostResultChallenges;
+        if (remaining == 0) revert PostResultCountMismatch();
+        challenge = builder.postResultChallenges[remaining - 1];
+        builder.remainingPostResultChallenges = remaining - 1;
+    }
+
+    /// @dev Adds the evaluation of a subpolynomial that is zero on every row to the sumcheck evaluation.
+    /// @param builder The builder
+    /// @param eval The evaluation of the subpolynomial
+    /// @param degree The degree of the subpolynomial
+    function produceIdentitySubpolynomialEvaluation(Builder memory builder, uint256 eval, uint256 degree)
+        internal
+        pure
+    {
+        if (degree + 1 > builder.maxMultiplicands) revert SumcheckProofTooSmall();
+        produceSubpolynomialEvaluation(builder, mulmod(eval, builder.randomEvaluation, MODULUS));
+    }
+
+    /// @dev Adds the evaluation of a subpolynomial that sums to zero over all rows to the sumcheck evaluation.
+    /// @param builder The builder
+    /// @param eval The evaluation of the subpolynomial
+    /// @param degree The degree of the subpolynomial
+    function produceZeroSumSubpolynomialEvaluation(Builder memory builder, uint256 eval, uint256 degree)
+        internal
+        pure
+    {
+        if (degree > builder.maxMultiplicands) revert SumcheckProofTooSmall();
+        produceSubpolynomialEvaluation(builder, eval);
+    }
+
+    /// @dev Whether the plan has consumed all evaluations and challenges and produced all subpolynomials.
+    /// @param builder The builder
+    /// @return completed Whether the verification is complete
+    function isCompleted(Builder memory builder) internal pure returns (bool completed) {
+        completed = builder.producedSubpolynomials == builder.subpolynomialMultipliers.length
+            && builder.consumedMleEvaluations == builder.mleEvaluations.length
+            && builder.remainingPostResultChallenges == 0;
+    }
+
+    /// @dev Adds the evaluation of the next subpolynomial, times its multiplier, to the sumcheck evaluation.
+    /// @param builder The builder
+    /// @param eval The evaluation of the subpolynomial, times the random evaluation if it is an identity
+    function produceSubpolynomialEvaluation(Builder memory builder, uint256 eval) private pure {
+        uint256 index = builder.producedSubpolynomials;
+        if (index >= builder.subpolynomialMultipliers.length) revert ConstraintCountMismatch();
+        builder.sumcheckEvaluation =
+            addmod(builder.sumcheckEvaluation, mulmod(builder.subpolynomialMultipliers[index], eval, MODULUS), MODULUS);
+        builder.producedSubpolynomials = index + 1;
+    }
+}
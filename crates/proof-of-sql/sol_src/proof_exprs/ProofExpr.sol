@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {
+    ADD_SUBTRACT_EXPR_NUM,
+    AND_EXPR_NUM,
+    COLUMN_EXPR_NUM,
+    EQUALS_EXPR_NUM,
+    LITERAL_EXPR_NUM,
+    MODULUS,
+    MULTIPLY_EXPR_NUM,
+    NOT_EXPR_NUM,
+    OR_EXPR_NUM
+} from "../base/Constants.sol";
+import {InvalidReference, InvalidSerializedPlan, UnsupportedProofExpr} from "../base/Errors.sol";
+import {SerializedPlan} from "../base/SerializedPlan.sol";
+import {VerificationBuilder} from "../proof/VerificationBuilder.sol";
+
+/// @title ProofExpr
+/// @dev The `verifier_evaluate` of the expressions that the Solidity verifier supports, which are column references,
+/// literals, `=`, `AND`, `OR`, `NOT`, `+`, `-` and `*` over integers and booleans.
+///
+/// Since none of the supported types has a scale, no evaluation needs to be scaled.
+library ProofExpr {
+    /// @dev Evaluates an expression, which is what `verifier_evaluate` does.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the expression
+    /// @param builder The verification builder
+    /// @param columnEvaluations The evaluation of each column that the plan refers to
+    /// @param oneEval The evaluation of a column of ones as long as the table
+    /// @return next The offset after the expression
+    /// @return eval The evaluation of the expression
+    function verifierEvaluate(
+        bytes memory plan,
+        uint256 offset,
+        VerificationBuilder.Builder memory builder,
+        uint256[] memory columnEvaluations,
+        uint256 oneEval
+    ) internal pure returns (uint256 next, uint256 eval) {
+        uint8 exprNum;
+        (exprNum, next) = SerializedPlan.readUint8(plan, offset);
+        if (exprNum == COLUMN_EXPR_NUM) {
+            uint8 columnNumber;
+            (columnNumber, next) = SerializedPlan.readUint8(plan, next);
+            if (columnNumber >= columnEvaluations.length) revert InvalidReference();
+            eval = columnEvaluations[columnNumber];
+        } else if (exprNum == LITERAL_EXPR_NUM) {
+            uint256 value;
+            (value, next) = SerializedPlan.readUint256(plan, SerializedPlan.skipColumnType(plan, next));
+            eval = mulmod(oneEval, value, MODULUS);
+        } else if (exprNum == NOT_EXPR_NUM) {
+            (next, eval) = verifierEvaluate(plan, next, builder, columnEvaluations, oneEval);
+            eval = addmod(oneEval, MODULUS - eval, MODULUS);
+        } else if (isBinaryExpr(exprNum)) {
+            uint256 lhs;
+            uint256 rhs;
+            (next, lhs) = verifierEvaluate(plan, next, builder, columnEvaluations, oneEval);
+            (next, rhs) = verifierEvaluate(plan, next, builder, columnEvaluations, oneEval);
+            (next, eval) = evaluateBinaryExpr(plan, next, builder, exprNum, lhs, rhs, oneEval);
+        } else {
+            revert UnsupportedProofExpr();
+        }
+    }
+
+    /// @dev Skips an expression without evaluating it.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the expression
+    /// @return next The offset after the expression
+    function skip(bytes memory plan, uint256 offset) internal pure returns (uint256 next) {
+        uint8 exprNum;
+        (exprNum, next) = SerializedPlan.readUint8(plan, offset);
+        if (exprNum == COLUMN_EXPR_NUM) {
+            next += 1;
+        } else if (exprNum == LITERAL_EXPR_NUM) {
+            next = SerializedPlan.skipColumnType(plan, next) + 32;
+        } else if (exprNum == NOT_EXPR_NUM) {
+            next = skip(plan, next);
+        } else if (isBinaryExpr(exprNum)) {
+            next = skip(plan, skip(plan, next));
+            // `+` and `-` are followed by whether they subtract
+            if (exprNum == ADD_SUBTRACT_EXPR_NUM) next += 1;
+        } else {
+            revert UnsupportedProofExpr();
+        }
+        if (next > plan.length) revert InvalidSerializedPlan();
+    }
+
+    /// @dev Whether an expression has two operands.
+    /// @param exprNum The number of the expression
+    /// @return binary Whether the expression has two operands
+    function isBinaryExpr(uint8 exprNum) private pure returns (bool binary) {
+        binary = exprNum == EQUALS_EXPR_NUM || exprNum == AND_EXPR_NUM || exprNum == OR_EXPR_NUM
+            || exprNum == ADD_SUBTRACT_EXPR_NUM || exprNum == MULTIPLY_EXPR_NUM;
+    }
+
+    /// @dev Evaluates an expression with two operands, given the evaluations of the operands.
+    /// For `+` and `-`, this also reads whether the expression subtracts.
+    /// @param plan The serialized plan
+    /// @param offset The offset after the operands
+    /// @param builder The verification builder
+    /// @param exprNum The number of the expression
+    /// @param lhs The evaluation of the left operand
+    /// @param rhs The evaluation of the right operand
+    /// @param oneEval The evaluation of a column of ones as long as the table
+    /// @return next The offset after the expression
+    /// @return eval The evaluation of the expression
+    function evaluateBinaryExpr(
+        bytes memory plan,
+        uint256 offset,
+        VerificationBuilder.Builder memory builder,
+        uint8 exprNum,
+        uint256 lhs,
+        uint256 rhs,
+        uint256 oneEval
+    ) private pure returns (uint256 next, uint256 eval) {
+        next = offset;
+        if (exprNum == EQUALS_EXPR_NUM) {
+            eval = equalsZeroEvaluate(builder, addmod(lhs, MODULUS - rhs, MODULUS), oneEval);
+        } else if (exprNum == AND_EXPR_NUM || exprNum == MULTIPLY_EXPR_NUM) {
+            eval = productEvaluate(builder, lhs, rhs);
+        } else if (exprNum == OR_EXPR_NUM) {
+            eval = addmod(addmod(lhs, rhs, MODULUS), MODULUS - productEvaluate(builder, lhs, rhs), MODULUS);
+        } else {
+            // `+` or `-`
+            uint8 isSubtract;
+            (isSubtract, next) = SerializedPlan.readUint8(plan, offset);
+            if (isSubtract > 1) revert InvalidSerializedPlan();
+            eval = addmod(lhs, isSubtract == 1 ? MODULUS - rhs : rhs, MODULUS);
+        }
+    }
+
+    /// @dev Consumes the evaluation of `lhs * rhs` and checks it, which is how `AND`, `OR` and `*` are verified.
+    /// @param builder The verification builder
+    /// @param lhs The evaluation of the left operand
+    /// @param rhs The evaluation of the right operand
+    /// @return eval The evaluation of the product
+    function productEvaluate(VerificationBuilder.Builder memory builder, uint256 lhs, uint256 rhs)
+        private
+        pure
+        returns (uint256 eval)
+    {
+        eval = VerificationBuilder.consumeMleEvaluation(builder);
+        // subpolynomial: product - lhs * rhs
+        VerificationBuilder.produceIdentitySubpolynomialEvaluation(
+            builder, addmod(eval, MODULUS - mulmod(lhs, rhs, MODULUS), MODULUS), 2
+        );
+    }
+
+    /// @dev Evaluates whether an expression is zero, which is what `verifier_evaluate_equals_zero` does.
+    /// @param builder The verification builder
+    /// @param lhs The evaluation of the expression
+    /// @param oneEval The evaluation of a column of ones as long as the table
+    /// @return selectionEval The evaluation of the selection of the rows where the expression is zero
+    function equalsZeroEvaluate(VerificationBuilder.Builder memory builder, uint256 lhs, uint256 oneEval)
+        private
+        pure
+        returns (uint256 selectionEval)
+    {
+        uint256 lhsPseudoInvEval = VerificationBuilder.consumeMleEvaluation(builder);
+        uint256 selectionNotEval = VerificationBuilder.consumeMleEvaluation(builder);
+        selectionEval = addmod(oneEval, MODULUS - selectionNotEval, MODULUS);
+        // subpolynomial: selection * lhs
+        VerificationBuilder.produceIdentitySubpolynomialEvaluation(builder, mulmod(selectionEval, lhs, MODULUS), 2);
+        // subpolynomial: selection_not - lhs * lhs_pseudo_inv
+        VerificationBuilder.produceIdentitySubpolynomialEvaluation(
+            builder, addmod(selectionNotEval, MODULUS - mulmod(lhs, lhsPseudoInvEval, MODULUS), MODULUS), 2
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {FILTER_EXEC_NUM, MODULUS, PROJECTION_EXEC_NUM} from "../base/Constants.sol";
+import {InvalidReference, UnsupportedProofPlan} from "../base/Errors.sol";
+import {SerializedPlan} from "../base/SerializedPlan.sol";
+import {VerificationBuilder} from "../proof/VerificationBuilder.sol";
+import {ProofExpr} from "../proof_exprs/ProofExpr.sol";
+
+/// @title ProofPlan
+/// @dev The `verifier_evaluate` of the plans that the Solidity verifier supports, which are filters and projections.
+library ProofPlan {
+    /// @dev Evaluates a plan, which is what `verifier_evaluate` does.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the plan
+    /// @param builder The verification builder
+    /// @param columnEvaluations The evaluation of each column that the plan refers to
+    /// @param tableOneEvaluations The evaluation of a column of ones as long as each table that the plan refers to
+    /// @return next The offset after the plan
+    /// @return evaluations The evaluation of each result column
+    function verifierEvaluate(
+        bytes memory plan,
+        uint256 offset,
+        VerificationBuilder.Builder memory builder,
+        uint256[] memory columnEvaluations,
+        uint256[] memory tableOneEvaluations
+    ) internal pure returns (uint256 next, uint256[] memory evaluations) {
+        uint8 planNum;
+        (planNum, next) = SerializedPlan.readUint8(plan, offset);
+        uint256 oneEval;
+        (next, oneEval) = readTableOneEvaluation(plan, next, tableOneEvaluations);
+        if (planNum == FILTER_EXEC_NUM) {
+            (next, evaluations) = filterEvaluate(plan, next, builder, columnEvaluations, oneEval);
+        } else if (planNum == PROJECTION_EXEC_NUM) {
+            // For projections input and output have the same length and hence the same one eval
+            uint256[] memory exprEvaluations;
+            (next, exprEvaluations) = evaluateAliasedExprs(plan, next, builder, columnEvaluations, oneEval);
+            evaluations = VerificationBuilder.consumeMleEvaluations(builder, exprEvaluations.length);
+        } else {
+            revert UnsupportedProofPlan();
+        }
+    }
+
+    /// @dev Reads the table of a plan and looks up the evaluation of its column of ones.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the table number
+    /// @param tableOneEvaluations The evaluation of a column of ones as long as each table that the plan refers to
+    /// @return next The offset after the table number
+    /// @return oneEval The evaluation of a column of ones as long as the table
+    function readTableOneEvaluation(bytes memory plan, uint256 offset, uint256[] memory tableOneEvaluations)
+        private
+        pure
+        returns (uint256 next, uint256 oneEval)
+    {
+        uint8 tableNumber;
+        (tableNumber, next) = SerializedPlan.readUint8(plan, offset);
+        if (tableNumber >= tableOneEvaluations.length) revert InvalidReference();
+        oneEval = tableOneEvaluations[tableNumber];
+    }
+
+    /// @dev Evaluates a filter, which is what `FilterExec::verifier_evaluate` does.
+    /// The where clause comes after the results in the serialized plan, but is evaluated first.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the results of the filter
+    /// @param builder The verification builder
+    /// @param columnEvaluations The evaluation of each column that the plan refers to
+    /// @param inputOneEval The evaluation of a column of ones as long as the table
+    /// @return next The offset after the filter
+    /// @return filteredEvaluations The evaluation of each filtered column
+    function filterEvaluate(
+        bytes memory plan,
+        uint256 offset,
+        VerificationBuilder.Builder memory builder,
+        uint256[] memory columnEvaluations,
+        uint256 inputOneEval
+    ) private pure returns (uint256 next, uint256[] memory filteredEvaluations) {
+        uint256 selectionEval;
+        (next, selectionEval) =
+            ProofExpr.verifierEvaluate(plan, skipAliasedExprs(plan, offset), builder, columnEvaluations, inputOneEval);
+        (, uint256[] memory columnEvals) = evaluateAliasedExprs(plan, offset, builder, columnEvaluations, inputOneEval);
+        filteredEvaluations = VerificationBuilder.consumeMleEvaluations(builder, columnEvals.length);
+        verifyFilter(builder, inputOneEval, columnEvals, selectionEval, filteredEvaluations);
+    }
+
+    /// @dev Checks that the filtered columns are the columns restricted to the selected rows,
+    /// which is what `verify_filter` does.
+    /// @param builder The verification builder
+    /// @param inputOneEval The evaluation of a column of ones as long as the table
+    /// @param columnEvals The evaluation of each column before it is filtered
+    /// @param selectionEval The evaluation of the selection
+    /// @param filteredEvals The evaluation of each filtered column
+    function verifyFilter(
+        VerificationBuilder.Builder memory builder,
+        uint256 inputOneEval,
+        uint256[] memory columnEvals,
+        uint256 selectionEval,
+        uint256[] memory filteredEvals
+    ) private pure {
+        uint256 alpha = VerificationBuilder.consumePostResultChallenge(builder);
+        uint256 beta = VerificationBuilder.consumePostResultChallenge(builder);
+        uint256 cStarEval = VerificationBuilder.consumeMleEvaluation(builder);
+        uint256 dStarEval = VerificationBuilder.consumeMleEvaluation(builder);
+
+        // sum c_star * s - d_star = 0
+        uint256 eval = addmod(mulmod(cStarEval, selectionEval, MODULUS), MODULUS - dStarEval, MODULUS);
+        VerificationBuilder.produceZeroSumSubpolynomialEvaluation(builder, eval, 2);
+
+        // c_star + c_fold * c_star - input_ones = 0
+        eval = mulmod(alpha, fold(beta, columnEvals), MODULUS);
+        eval = addmod(cStarEval, mulmod(eval, cStarEval, MODULUS), MODULUS);
+        eval = addmod(eval, MODULUS - inputOneEval, MODULUS);
+        VerificationBuilder.produceIdentitySubpolynomialEvaluation(builder, eval, 2);
+
+        // d_star + d_fold * d_star - chi = 0
+        eval = mulmod(alpha, fold(beta, filteredEvals), MODULUS);
+        eval = addmod(dStarEval, mulmod(eval, dStarEval, MODULUS), MODULUS);
+        eval = addmod(eval, MODULUS - VerificationBuilder.consumeOneEvaluation(builder), MODULUS);
+        VerificationBuilder.produceIdentitySubpolynomialEvaluation(builder, eval, 2);
+    }
+
+    /// @dev Computes `sum beta^j * evals[j]`, which is what `fold_vals` does.
+    /// @param beta The base of the powers
+    /// @param evals The evaluations to fold
+    /// @return folded The folded evaluation
+    function fold(uint256 beta, uint256[] memory evals) private pure returns (uint256 folded) {
+        for (uint256 j = evals.length; j > 0; --j) {
+            folded = addmod(mulmod(folded, beta, MODULUS), evals[j - 1], MODULUS);
+        }
+    }
+
+    /// @dev Evaluates the expressions of the results of a plan, each of which is followed by its alias.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the number of results
+    /// @param builder The verification builder
+    /// @param columnEvaluations The evaluation of each column that the plan refers to
+    /// @param oneEval The evaluation of a column of ones as long as the table
+    /// @return next The offset after the results
+    /// @return evaluations The evaluation of each expression
+    function evaluateAliasedExprs(
+        bytes memory plan,
+        uint256 offset,
+        VerificationBuilder.Builder memory builder,
+        uint256[] memory columnEvaluations,
+        uint256 oneEval
+    ) private pure returns (uint256 next, uint256[] memory evaluations) {
+        uint8 count;
+        (count, next) = SerializedPlan.readUint8(plan, offset);
+        evaluations = new uint256[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            (next, evaluations[i]) = ProofExpr.verifierEvaluate(plan, next, builder, columnEvaluations, oneEval);
+            next = SerializedPlan.skipString(plan, next);
+        }
+    }
+
+    /// @dev Skips the expressions of the results of a plan along with their aliases.
+    /// @param plan The serialized plan
+    /// @param offset The offset of the number of results
+    /// @return next The offset after the results
+    function skipAliasedExprs(bytes memory plan, uint256 offset) private pure returns (uint256 next) {
+        uint8 count;
+        (count, next) = SerializedPlan.readUint8(plan, offset);
+        for (uint256 i = 0; i < count; ++i) {
+            next = SerializedPlan.skipString(plan, ProofExpr.skip(plan, next));
+        }
+    }
+}
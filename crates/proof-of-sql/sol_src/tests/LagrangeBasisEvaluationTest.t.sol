@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {LagrangeBasisEvaluation} from "../base/LagrangeBasisEvaluation.sol";
+
+library LagrangeBasisEvaluationTest {
+    struct EvaluationCase {
+        uint256 length;
+        uint256[] a;
+        uint256[] b;
+        uint256 expectedSum;
+        uint256 expectedInnerProduct;
+        uint256 expectedRhoEval;
+    }
+
+    error EvaluationMismatch();
+
+    function checkEvaluations(EvaluationCase memory evaluationCase) internal pure {
+        uint256 length = evaluationCase.length;
+        uint256[] memory a = evaluationCase.a;
+        uint256[] memory b = evaluationCase.b;
+        if (LagrangeBasisEvaluation.computeTruncatedLagrangeBasisSum(length, a) != evaluationCase.expectedSum) {
+            revert EvaluationMismatch();
+        }
+        uint256 innerProduct = LagrangeBasisEvaluation.computeTruncatedLagrangeBasisInnerProduct(length, a, b);
+        if (innerProduct != evaluationCase.expectedInnerProduct) revert EvaluationMismatch();
+        if (LagrangeBasisEvaluation.computeRhoEval(length, a) != evaluationCase.expectedRhoEval) {
+            revert EvaluationMismatch();
+        }
+    }
+
+    function rustTestWeCanComputeTheSameEvaluationsAsRust(bytes calldata encoded) public pure {
+        EvaluationCase[] memory evaluationCases = abi.decode(encoded, (EvaluationCase[]));
+        uint256 numCases = evaluationCases.length;
+        for (uint256 i = 0; i < numCases; ++i) {
+            checkEvaluations(evaluationCases[i]);
+        }
+    }
+
+    function testWeCanComputeTheSameEvaluationsAsRust() public pure {
+        uint256[] memory a = new uint256[](3);
+        a[0] = 2;
+        a[1] = 3;
+        a[2] = 5;
+        uint256[] memory b = new uint256[](3);
+        b[0] = 7;
+        b[1] = 11;
+        b[2] = 13;
+        checkEvaluations(EvaluationCase(0, a, b, 0, 0, 0));
+        checkEvaluations(
+            EvaluationCase(1, a, b, 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffff9, 0x1680, 0)
+        );
+        checkEvaluations(EvaluationCase(3, a, b, 0x14, 0x7020, 0x28));
+        checkEvaluations(EvaluationCase(5, a, b, 6, 0xe538, 8));
+        checkEvaluations(EvaluationCase(8, a, b, 1, 0x1d3e4, 0x1c));
+    }
+
+    function testWeCanEvaluateAnEmptyBasis() public pure {
+        uint256[] memory point = new uint256[](0);
+        checkEvaluations(EvaluationCase(0, point, point, 0, 0, 0));
+        checkEvaluations(EvaluationCase(1, point, point, 1, 1, 0));
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {BN254} from "../base/BN254.sol";
+import {HyperKZGVerifier} from "../proof/HyperKZGVerifier.sol";
+import {QueryProofVerifier} from "../proof/QueryProofVerifier.sol";
+
+library QueryProofTest {
+    struct QueryProofCase {
+        bytes plan;
+        QueryProofVerifier.ResultColumn[] result;
+        QueryProofVerifier.QueryProof proof;
+        uint256[] tableLengths;
+        BN254.G1Point[] columnCommitments;
+        HyperKZGVerifier.VerifierSetup setup;
+        bytes32 expectedVerificationHash;
+    }
+
+    error VerificationHashMismatch();
+
+    /// @dev Verifies the proof and checks that the verification hash is the one that Rust computed.
+    function checkQueryProof(QueryProofCase memory queryProofCase) internal view {
+        bytes32 verificationHash = QueryProofVerifier.verify(
+            queryProofCase.plan,
+            queryProofCase.result,
+            queryProofCase.proof,
+            queryProofCase.tableLengths,
+            queryProofCase.columnCommitments,
+            queryProofCase.setup
+        );
+        if (verificationHash != queryProofCase.expectedVerificationHash) revert VerificationHashMismatch();
+    }
+
+    function rustTestWeCanVerifyQueryProofsFromRust(bytes calldata encoded) public view {
+        checkQueryProof(abi.decode(encoded, (QueryProofCase)));
+    }
+}
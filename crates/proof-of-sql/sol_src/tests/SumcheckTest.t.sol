@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Transcript} from "../base/Transcript.sol";
+import {Sumcheck} from "../proof/Sumcheck.sol";
+
+library SumcheckTest {
+    struct SumcheckCase {
+        uint256[] coefficients;
+        uint256 numVariables;
+        uint256 claimedSum;
+        uint256[] expectedEvaluationPoint;
+        uint256 expectedEvaluation;
+    }
+
+    error SubclaimMismatch();
+
+    function checkSubclaim(SumcheckCase memory sumcheckCase) internal pure {
+        Sumcheck.Subclaim memory subclaim = Sumcheck.verifyWithoutEvaluation(
+            Transcript.newTranscript(), sumcheckCase.coefficients, sumcheckCase.numVariables, sumcheckCase.claimedSum
+        );
+        if (subclaim.expectedEvaluation != sumcheckCase.expectedEvaluation) revert SubclaimMismatch();
+        uint256 numVariables = sumcheckCase.numVariables;
+        if (subclaim.evaluationPoint.length != numVariables) revert SubclaimMismatch();
+        for (uint256 i = 0; i < numVariables; ++i) {
+            if (subclaim.evaluationPoint[i] != sumcheckCase.expectedEvaluationPoint[i]) revert SubclaimMismatch();
+        }
+    }
+
+    function rustTestWeCanVerifySumcheckProofsFromRust(bytes calldata encoded) public pure {
+        checkSubclaim(abi.decode(encoded, (SumcheckCase)));
+    }
+
+    /// @dev The proof that `a * b` sums to `70` for `a = [1, 2, 3, 4]` and `b = [5, 6, 7, 8]`
+    function innerProductCase() internal pure returns (SumcheckCase memory sumcheckCase) {
+        sumcheckCase.coefficients = new uint256[](6);
+        sumcheckCase.coefficients[0] = 2;
+        sumcheckCase.coefficients[1] = 16;
+        sumcheckCase.coefficients[2] = 26;
+        sumcheckCase.coefficients[3] = 4;
+        sumcheckCase.coefficients[4] = 0x24474a9857977e46aa72bd6beac8924aa751faa62e420a28195d9eb9d0bcc0d2;
+        sumcheckCase.coefficients[5] = 0x05f0edc11b08026df344a3b51b3145ee511baf565935501dbb9290a53fe77aa3;
+        sumcheckCase.numVariables = 2;
+        sumcheckCase.claimedSum = 70;
+        sumcheckCase.expectedEvaluationPoint = new uint256[](2);
+        sumcheckCase.expectedEvaluationPoint[0] = 0x2143f9df867eafa686c4d2363b72d0c13dee72cdc86d3ad2a84862786c2f3032;
+        sumcheckCase.expectedEvaluationPoint[1] = 0x0bd6813f2230954c8e6024c88e07f14e1a40718678e9e535fdd38bbc4dc3651f;
+        sumcheckCase.expectedEvaluation = 0x03e6585b10a46ce6055c18e95bb62a6f5c57f124d2641b68b758c78eb1c55844;
+    }
+
+    function testWeCanVerifyASumcheckProofFromRust() public pure {
+        checkSubclaim(innerProductCase());
+    }
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Transcript} from "../base/Transcript.sol";
+
+library TranscriptTest {
+    struct ChallengeCase {
+        uint64[] integers;
+        uint256[] scalars;
+        uint256 expectedScalarChallenge;
+        bytes32 expectedChallenge;
+    }
+
+    error ChallengeMismatch();
+
+    /// @dev Appends the integers, draws a scalar challenge, appends the scalars and draws a raw challenge.
+    function checkChallenges(ChallengeCase memory challengeCase) internal pure {
+        Transcript.State memory transcript = Transcript.newTranscript();
+        uint256 numIntegers = challengeCase.integers.length;
+        for (uint256 i = 0; i < numIntegers; ++i) {
+            Transcript.appendUint64(transcript, challengeCase.integers[i]);
+        }
+        if (Transcript.drawScalarChallenge(transcript) != challengeCase.expectedScalarChallenge) {
+            revert ChallengeMismatch();
+        }
+        uint256 numScalars = challengeCase.scalars.length;
+        for (uint256 i = 0; i < numScalars; ++i) {
+            Transcript.appendScalar(transcript, challengeCase.scalars[i]);
+        }
+        if (Transcript.drawChallenge(transcript) != challengeCase.expectedChallenge) revert ChallengeMismatch();
+    }
+
+    function rustTestWeCanDrawTheSameChallengesAsRust(bytes calldata encoded) public pure {
+        checkChallenges(abi.decode(encoded, (ChallengeCase)));
+    }
+
+    function testWeCanDrawTheSameChallengesAsRust() public pure {
+        uint64[] memory integers = new uint64[](2);
+        integers[0] = 7;
+        integers[1] = 3;
+        uint256[] memory scalars = new uint256[](2);
+        scalars[0] = 123;
+        scalars[1] = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000;
+        checkChallenges(
+            ChallengeCase({
+                integers: integers,
+                scalars: scalars,
+                expectedScalarChallenge: 0x07e8caea8c763acb3492a6ff9aa5933ea8b2b2e28971336301fc8a6bd145a72f,
+                expectedChallenge: 0xb3eccbfaf27e0a11da56341ae15d0578ee5aed824922038abfd66b2a9d26474b
+            })
+        );
+    }
+
+    function testConsecutiveChallengesDiffer() public pure {
+        Transcript.State memory transcript = Transcript.newTranscript();
+        bytes32 first = Transcript.drawChallenge(transcript);
+        bytes32 second = Transcript.drawChallenge(transcript);
+        assert(first != second);
+        assert(first == keccak256(""));
+        assert(second == keccak256(abi.encodePacked(first)));
+    }
+}
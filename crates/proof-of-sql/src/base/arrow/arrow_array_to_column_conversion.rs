@@ -16,9 +16,6 @@ use snafu::Snafu;
 #[derive(Snafu, Debug, PartialEq)]
 /// Errors caused by conversions between Arrow and owned types.
 pub enum ArrowArrayToColumnConversionError {
-    /// This error occurs when an array contains a non-zero number of null elements
-    #[snafu(display("arrow array must not contain nulls"))]
-    ArrayContainsNulls,
     /// This error occurs when trying to convert from an unsupported arrow type.
     #[snafu(display(
         "unsupported type: attempted conversion from ArrayRef of type {datatype} to OwnedColumn"
@@ -69,13 +66,44 @@ pub trait ArrayRefExt {
     ///    Some types don't require this slice (see [`Column::BigInt`]). But for types requiring it,
     ///    `scals` must be provided and have a length equal to `range.len()`.
     ///
-    /// Note: this function must not be called from unsupported or nullable arrays as it will panic.
+    /// NULL entries are replaced by the default value of the column type. Their positions are
+    /// given by [`ArrayRefExt::to_presence_column`].
+    ///
+    /// Note: this function must not be called from unsupported arrays as it will panic.
     fn to_column<'a, S: Scalar>(
         &'a self,
         alloc: &'a Bump,
         range: &Range<usize>,
         scals: Option<&'a [S]>,
     ) -> Result<Column<'a, S>, ArrowArrayToColumnConversionError>;
+
+    /// Convert the validity of an [`ArrayRef`] into the presence column of the Proof of SQL Column,
+    /// which is `true` exactly where the entries are not NULL.
+    ///
+    /// Returns `None` if the array contains no NULLs, in which case no presence column is needed.
+    fn to_presence_column<'a>(&self, alloc: &'a Bump, range: &Range<usize>) -> Option<&'a [bool]>;
+}
+
+/// The `values` of `array` in `range` with the NULL entries replaced by the default value.
+///
+/// The values are only copied if the array contains NULLs.
+fn values_without_nulls<'a, T: Copy + Default>(
+    alloc: &'a Bump,
+    array: &dyn Array,
+    values: &'a [T],
+    range: &Range<usize>,
+) -> &'a [T] {
+    if array.null_count() == 0 {
+        values
+    } else {
+        alloc.alloc_slice_fill_with(values.len(), |i| {
+            if array.is_null(range.start + i) {
+                T::default()
+            } else {
+                values[i]
+            }
+        })
+    }
 }
 
 impl ArrayRefExt for ArrayRef {
@@ -104,11 +132,6 @@ impl ArrayRefExt for ArrayRef {
         range: &Range<usize>,
         precomputed_scals: Option<&'a [S]>,
     ) -> Result<Column<'a, S>, ArrowArrayToColumnConversionError> {
-        // Start by checking for nulls
-        if self.null_count() != 0 {
-            return Err(ArrowArrayToColumnConversionError::ArrayContainsNulls);
-        }
-
         // Before performing any operations, check if the range is out of bounds
         if range.end > self.len() {
             return Err(ArrowArrayToColumnConversionError::IndexOutOfBounds {
@@ -120,13 +143,9 @@ impl ArrayRefExt for ArrayRef {
         match self.data_type() {
             DataType::Boolean => {
                 if let Some(array) = self.as_any().downcast_ref::<BooleanArray>() {
-                    let boolean_slice = array
-                        .iter()
-                        .skip(range.start)
-                        .take(range.len())
-                        .collect::<Option<Vec<bool>>>()
-                        .ok_or(ArrowArrayToColumnConversionError::ArrayContainsNulls)?;
-                    let values = alloc.alloc_slice_fill_with(range.len(), |i| boolean_slice[i]);
+                    let values = alloc.alloc_slice_fill_with(range.len(), |i| {
+                        array.is_valid(range.start + i) && array.value(range.start + i)
+                    });
                     Ok(Column::Boolean(values))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
@@ -136,7 +155,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int8 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int8Array>() {
-                    Ok(Column::TinyInt(&array.values()[range.start..range.end]))
+                    Ok(Column::TinyInt(values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    )))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
                         datatype: self.data_type().clone(),
@@ -145,7 +169,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int16 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int16Array>() {
-                    Ok(Column::SmallInt(&array.values()[range.start..range.end]))
+                    Ok(Column::SmallInt(values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    )))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
                         datatype: self.data_type().clone(),
@@ -154,7 +183,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int32 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int32Array>() {
-                    Ok(Column::Int(&array.values()[range.start..range.end]))
+                    Ok(Column::Int(values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    )))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
                         datatype: self.data_type().clone(),
@@ -163,7 +197,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int64 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int64Array>() {
-                    Ok(Column::BigInt(&array.values()[range.start..range.end]))
+                    Ok(Column::BigInt(values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    )))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
                         datatype: self.data_type().clone(),
@@ -172,7 +211,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Decimal128(38, 0) => {
                 if let Some(array) = self.as_any().downcast_ref::<Decimal128Array>() {
-                    Ok(Column::Int128(&array.values()[range.start..range.end]))
+                    Ok(Column::Int128(values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    )))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType {
                         datatype: self.data_type().clone(),
@@ -181,7 +225,12 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Decimal256(precision, scale) if *precision <= 75 => {
                 if let Some(array) = self.as_any().downcast_ref::<Decimal256Array>() {
-                    let i256_slice = &array.values()[range.start..range.end];
+                    let i256_slice = values_without_nulls(
+                        alloc,
+                        array,
+                        &array.values()[range.start..range.end],
+                        range,
+                    );
                     let scalars = alloc.alloc_slice_fill_default(i256_slice.len());
                     for (scalar, value) in scalars.iter_mut().zip(i256_slice) {
                         *scalar = convert_i256_to_scalar(value).ok_or(
@@ -208,7 +257,12 @@ impl ArrayRefExt for ArrayRef {
                         Ok(Column::TimestampTZ(
                             PoSQLTimeUnit::Second,
                             PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
+                            values_without_nulls(
+                                alloc,
+                                array,
+                                &array.values()[range.start..range.end],
+                                range,
+                            ),
                         ))
                     } else {
                         Err(ArrowArrayToColumnConversionError::UnsupportedType {
@@ -221,7 +275,12 @@ impl ArrayRefExt for ArrayRef {
                         Ok(Column::TimestampTZ(
                             PoSQLTimeUnit::Millisecond,
                             PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
+                            values_without_nulls(
+                                alloc,
+                                array,
+                                &array.values()[range.start..range.end],
+                                range,
+                            ),
                         ))
                     } else {
                         Err(ArrowArrayToColumnConversionError::UnsupportedType {
@@ -234,7 +293,12 @@ impl ArrayRefExt for ArrayRef {
                         Ok(Column::TimestampTZ(
                             PoSQLTimeUnit::Microsecond,
                             PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
+                            values_without_nulls(
+                                alloc,
+                                array,
+                                &array.values()[range.start..range.end],
+                                range,
+                            ),
                         ))
                     } else {
                         Err(ArrowArrayToColumnConversionError::UnsupportedType {
@@ -247,7 +311,12 @@ impl ArrayRefExt for ArrayRef {
                         Ok(Column::TimestampTZ(
                             PoSQLTimeUnit::Nanosecond,
                             PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
+                            values_without_nulls(
+                                alloc,
+                                array,
+                                &array.values()[range.start..range.end],
+                                range,
+                            ),
                         ))
                     } else {
                         Err(ArrowArrayToColumnConversionError::UnsupportedType {
@@ -258,15 +327,18 @@ impl ArrayRefExt for ArrayRef {
             },
             DataType::Utf8 => {
                 if let Some(array) = self.as_any().downcast_ref::<StringArray>() {
-                    let vals = alloc
-                        .alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
-                            array.value(range.start + i)
+                    let vals =
+                        alloc.alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
+                            if array.is_null(range.start + i) {
+                                ""
+                            } else {
+                                array.value(range.start + i)
+                            }
                         });
 
-                    let scals = if let Some(scals) = precomputed_scals {
-                        &scals[range.start..range.end]
-                    } else {
-                        alloc.alloc_slice_fill_with(vals.len(), |i| -> S { vals[i].into() })
+                    let scals = match precomputed_scals {
+                        Some(scals) if array.null_count() == 0 => &scals[range.start..range.end],
+                        _ => alloc.alloc_slice_fill_with(vals.len(), |i| -> S { vals[i].into() }),
                     };
 
                     Ok(Column::VarChar((vals, scals)))
@@ -281,6 +353,12 @@ impl ArrayRefExt for ArrayRef {
             }),
         }
     }
+
+    fn to_presence_column<'a>(&self, alloc: &'a Bump, range: &Range<usize>) -> Option<&'a [bool]> {
+        (self.null_count() != 0).then(|| {
+            alloc.alloc_slice_fill_with(range.len(), |i| self.is_valid(range.start + i)) as &[_]
+        })
+    }
 }
 
 #[cfg(test)]
@@ -369,10 +447,18 @@ mod tests {
         ));
 
         let result = array.to_column::<DoryScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(
+            result.unwrap(),
+            Column::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                &[1_625_072_400, 0, 1_625_083_200]
+            )
+        );
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -413,10 +499,15 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(StringArray::from(vec![Some("hello"), None, Some("test")]));
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let scals: Vec<TestScalar> = ["hello", "", "test"].iter().map(Into::into).collect();
+        assert_eq!(
+            result.unwrap(),
+            Column::VarChar((&["hello", "", "test"], &scals))
+        );
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -518,10 +609,28 @@ mod tests {
         let array: ArrayRef = Arc::new(builder.finish().with_precision_and_scale(75, 0).unwrap());
 
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(
+            result.unwrap(),
+            Column::Decimal75(
+                Precision::new(75).unwrap(),
+                0,
+                &[
+                    convert_i256_to_scalar(
+                        &i256::from_str("100000000000000000000000000000000000000").unwrap()
+                    )
+                    .unwrap(),
+                    TestScalar::ZERO,
+                    convert_i256_to_scalar(
+                        &i256::from_str("4200000000000000000000000000000000000000").unwrap()
+                    )
+                    .unwrap()
+                ]
+            )
+        );
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -566,10 +675,11 @@ mod tests {
         );
 
         let result = array.to_column::<DoryScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::Int128(&[100, 0, 4200]));
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -632,10 +742,11 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None, Some(true)]));
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::Boolean(&[true, false, true]));
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -701,10 +812,11 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(Int8Array::from(vec![Some(1), None, Some(42)]));
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::TinyInt(&[1, 0, 42]));
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -712,10 +824,11 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(Int16Array::from(vec![Some(1), None, Some(42)]));
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::SmallInt(&[1, 0, 42]));
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -752,10 +865,11 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(42)]));
         let result = array.to_column::<TestScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::Int(&[1, 0, 42]));
+        assert_eq!(
+            array.to_presence_column(&alloc, &(0..3)),
+            Some(&[true, false, true][..])
+        );
     }
 
     #[test]
@@ -824,6 +938,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_do_not_build_a_presence_column_for_an_array_without_nulls() {
+        let alloc = Bump::new();
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![1, -3, 7]));
+        assert_eq!(array.to_presence_column(&alloc, &(0..3)), None);
+    }
+
     #[test]
     fn we_can_build_an_empty_column_from_an_empty_range_boolean() {
         let alloc = Bump::new();
@@ -906,15 +1027,20 @@ mod tests {
     }
 
     #[test]
-    fn we_cannot_build_a_column_from_an_array_with_nulls_utf8() {
+    fn we_can_build_a_column_from_an_array_with_nulls_utf8() {
         let alloc = Bump::new();
         let data = vec![Some("ab"), Some("-f34"), None];
         let array: ArrayRef = Arc::new(arrow::array::StringArray::from(data.clone()));
         let result = array.to_column::<DoryScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let scals: Vec<DoryScalar> = ["ab", "-f34", ""].iter().map(Into::into).collect();
+        assert_eq!(
+            result.unwrap(),
+            Column::VarChar((&["ab", "-f34", ""], &scals))
+        );
+        assert_eq!(
+            array.to_presence_column(&alloc, &(1..3)),
+            Some(&[true, false][..])
+        );
     }
 
     #[test]
@@ -12,20 +12,24 @@
 //! This is because there is no `Int128` type in Arrow.
 //! This does not check that the values are less than 39 digits.
 //! However, the actual arrow backing `i128` is the correct value.
+//!
+//! Arrays with NULLs are converted into a column whose NULLs are replaced by the default value
+//! followed by its presence column, see [`presence_ident`].
 use super::scalar_and_i256_conversions::{convert_i256_to_scalar, convert_scalar_to_i256};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable, OwnedTableError},
+    database::{presence_ident, OwnedColumn, OwnedTable, OwnedTableError},
     map::IndexMap,
     math::decimal::Precision,
     scalar::Scalar,
 };
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 use arrow::{
     array::{
-        ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, Int8Array, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-        TimestampNanosecondArray, TimestampSecondArray,
+        make_array, Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array,
+        Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
     },
+    buffer::NullBuffer,
     datatypes::{i256, DataType, Schema, SchemaRef, TimeUnit as ArrowTimeUnit},
     error::ArrowError,
     record_batch::RecordBatch,
@@ -64,9 +68,6 @@ pub enum OwnedArrowConversionError {
         /// The underlying source error
         source: OwnedTableError,
     },
-    /// This error occurs when trying to convert from an Arrow array with nulls.
-    #[snafu(display("null values are not supported in OwnedColumn yet"))]
-    NullNotSupportedYet,
     /// Using `TimeError` to handle all time-related errors
     #[snafu(transparent)]
     TimestampConversionError {
@@ -114,19 +115,45 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
     }
 }
 
+/// Presence columns are turned into the validity of the columns they belong to, whose fields are
+/// then nullable.
 impl<S: Scalar> TryFrom<OwnedTable<S>> for RecordBatch {
     type Error = ArrowError;
     fn try_from(value: OwnedTable<S>) -> Result<Self, Self::Error> {
         if value.is_empty() {
             Ok(RecordBatch::new_empty(SchemaRef::new(Schema::empty())))
         } else {
-            RecordBatch::try_from_iter(
-                value
-                    .into_inner()
+            let mut table = value.into_inner();
+            let presence_idents = table
+                .keys()
+                .map(presence_ident)
+                .filter(|ident| table.contains_key(ident))
+                .collect::<Vec<_>>();
+            let presences = presence_idents
+                .into_iter()
+                .filter_map(|ident| table.shift_remove(&ident).map(|column| (ident, column)))
+                .collect::<IndexMap<_, _>>();
+            RecordBatch::try_from_iter_with_nullable(
+                table
                     .into_iter()
                     .map(|(identifier, owned_column)| {
-                        (identifier.value, ArrayRef::from(owned_column))
-                    }),
+                        let array = ArrayRef::from(owned_column);
+                        Ok(match presences.get(&presence_ident(&identifier)) {
+                            Some(OwnedColumn::Boolean(presence)) => (
+                                identifier.value,
+                                make_array(
+                                    array
+                                        .to_data()
+                                        .into_builder()
+                                        .nulls(Some(NullBuffer::from(presence.clone())))
+                                        .build()?,
+                                ),
+                                true,
+                            ),
+                            _ => (identifier.value, array, false),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ArrowError>>()?,
             )
         }
     }
@@ -142,6 +169,8 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
     type Error = OwnedArrowConversionError;
 
     #[allow(clippy::too_many_lines)]
+    /// NULL entries are replaced by the default value of the column type.
+    ///
     /// # Panics
     ///
     /// Will panic if downcasting fails for the following types:
@@ -162,48 +191,53 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .downcast_ref::<BooleanArray>()
                     .unwrap()
                     .iter()
-                    .collect::<Option<Vec<bool>>>()
-                    .ok_or(OwnedArrowConversionError::NullNotSupportedYet)?,
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int8 => Ok(Self::TinyInt(
                 value
                     .as_any()
                     .downcast_ref::<Int8Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int16 => Ok(Self::SmallInt(
                 value
                     .as_any()
                     .downcast_ref::<Int16Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int32 => Ok(Self::Int(
                 value
                     .as_any()
                     .downcast_ref::<Int32Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int64 => Ok(Self::BigInt(
                 value
                     .as_any()
                     .downcast_ref::<Int64Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Decimal128(38, 0) => Ok(Self::Int128(
                 value
                     .as_any()
                     .downcast_ref::<Decimal128Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(Self::Decimal75(
                 Precision::new(*precision).expect("precision is less than 76"),
@@ -212,9 +246,8 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .as_any()
                     .downcast_ref::<Decimal256Array>()
                     .unwrap()
-                    .values()
                     .iter()
-                    .map(convert_i256_to_scalar)
+                    .map(|value| convert_i256_to_scalar(&value.unwrap_or_default()))
                     .map(Option::unwrap)
                     .collect(),
             )),
@@ -224,7 +257,7 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .downcast_ref::<StringArray>()
                     .unwrap()
                     .iter()
-                    .map(|s| s.unwrap().to_string())
+                    .map(|s| s.unwrap_or_default().to_string())
                     .collect(),
             )),
             DataType::Timestamp(time_unit, timezone) => match time_unit {
@@ -235,7 +268,10 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                         .expect(
                             "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
                         );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                    let timestamps = array
+                        .iter()
+                        .map(Option::unwrap_or_default)
+                        .collect::<Vec<i64>>();
                     Ok(OwnedColumn::TimestampTZ(
                         PoSQLTimeUnit::Second,
                         PoSQLTimeZone::try_from(timezone)?,
@@ -249,7 +285,10 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                         .expect(
                             "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
                         );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                    let timestamps = array
+                        .iter()
+                        .map(Option::unwrap_or_default)
+                        .collect::<Vec<i64>>();
                     Ok(OwnedColumn::TimestampTZ(
                         PoSQLTimeUnit::Millisecond,
                         PoSQLTimeZone::try_from(timezone)?,
@@ -263,7 +302,10 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                         .expect(
                             "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
                         );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                    let timestamps = array
+                        .iter()
+                        .map(Option::unwrap_or_default)
+                        .collect::<Vec<i64>>();
                     Ok(OwnedColumn::TimestampTZ(
                         PoSQLTimeUnit::Microsecond,
                         PoSQLTimeZone::try_from(timezone)?,
@@ -277,7 +319,10 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                         .expect(
                             "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
                         );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                    let timestamps = array
+                        .iter()
+                        .map(Option::unwrap_or_default)
+                        .collect::<Vec<i64>>();
                     Ok(OwnedColumn::TimestampTZ(
                         PoSQLTimeUnit::Nanosecond,
                         PoSQLTimeZone::try_from(timezone)?,
@@ -292,22 +337,29 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
     }
 }
 
+/// Arrays that contain NULLs are followed by their presence column.
+///
+/// Nullable fields without NULLs get no presence column.
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
-        let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
-            .schema()
-            .fields()
-            .iter()
-            .zip(value.columns())
-            .map(|(field, array_ref)| {
-                let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Ident::new(field.name());
-                Ok((identifier, owned_column))
-            })
-            .collect();
-        let owned_table = Self::try_new(table?)?;
+        let mut columns = Vec::with_capacity(value.num_columns());
+        for (field, array_ref) in value.schema().fields().iter().zip(value.columns()) {
+            let identifier = Ident::new(field.name());
+            columns.push((identifier.clone(), OwnedColumn::try_from(array_ref)?));
+            if array_ref.null_count() != 0 {
+                columns.push((
+                    presence_ident(&identifier),
+                    OwnedColumn::Boolean(
+                        (0..array_ref.len())
+                            .map(|i| array_ref.is_valid(i))
+                            .collect(),
+                    ),
+                ));
+            }
+        }
+        let num_columns = columns.len();
+        let owned_table = Self::try_new(columns.into_iter().collect())?;
         if num_columns == owned_table.num_columns() {
             Ok(owned_table)
         } else {
@@ -10,7 +10,7 @@ use crate::{
 use alloc::sync::Arc;
 use arrow::{
     array::{ArrayRef, BooleanArray, Decimal128Array, Float32Array, Int64Array, StringArray},
-    datatypes::Schema,
+    datatypes::{DataType, Field, Schema},
     record_batch::RecordBatch,
 };
 
@@ -129,6 +129,72 @@ fn we_can_convert_between_owned_table_and_record_batch() {
     );
 }
 
+#[test]
+fn we_can_convert_between_an_owned_table_with_presence_columns_and_a_record_batch_with_nulls() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("int64", DataType::Int64, true),
+        Field::new("string", DataType::Utf8, true),
+        Field::new("boolean", DataType::Boolean, false),
+    ]));
+    let record_batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])),
+            Arc::new(StringArray::from(vec![Some("a"), Some(""), None])),
+            Arc::new(BooleanArray::from(vec![true, false, true])),
+        ],
+    )
+    .unwrap();
+    we_can_convert_between_owned_table_and_record_batch_impl(
+        &owned_table([
+            bigint("int64", [1, 0, 3]),
+            presence("int64", [true, false, true]),
+            varchar("string", ["a", "", ""]),
+            presence("string", [true, true, false]),
+            boolean("boolean", [true, false, true]),
+        ]),
+        &record_batch,
+    );
+}
+
+#[test]
+fn we_can_convert_an_owned_table_with_a_full_presence_column_to_a_nullable_record_batch() {
+    let record_batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new(
+            "int64",
+            DataType::Int64,
+            true,
+        )])),
+        vec![Arc::new(Int64Array::from(vec![1, 2, 3]))],
+    )
+    .unwrap();
+    assert_eq!(
+        RecordBatch::try_from(owned_table::<TestScalar>([
+            bigint("int64", [1, 2, 3]),
+            presence("int64", [true; 3])
+        ]))
+        .unwrap(),
+        record_batch
+    );
+}
+
+#[test]
+fn we_do_not_add_presence_columns_for_nullable_fields_without_nulls() {
+    let record_batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new(
+            "int64",
+            DataType::Int64,
+            true,
+        )])),
+        vec![Arc::new(Int64Array::from(vec![1, 2, 3]))],
+    )
+    .unwrap();
+    assert_eq!(
+        OwnedTable::<TestScalar>::try_from(record_batch).unwrap(),
+        owned_table([bigint("int64", [1, 2, 3])])
+    );
+}
+
 #[test]
 #[should_panic(expected = "not implemented: Cannot convert Scalar type to arrow type")]
 fn we_panic_when_converting_an_owned_table_with_a_scalar_column() {
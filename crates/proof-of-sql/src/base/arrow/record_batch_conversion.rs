@@ -7,13 +7,17 @@ use crate::base::{
         AppendColumnCommitmentsError, AppendTableCommitmentError, Commitment, TableCommitment,
         TableCommitmentFromColumnsError,
     },
-    database::Column,
+    database::{presence_ident, Column},
     scalar::Scalar,
 };
+use alloc::vec::Vec;
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
 use sqlparser::ast::Ident;
 
+/// Arrays that contain NULLs are followed by their presence column,
+/// see [`ArrayRefExt::to_presence_column`].
+///
 /// This function will return an error if:
 /// - The field name cannot be parsed into an [`Identifier`].
 /// - The conversion of an Arrow array to a [`Column`] fails.
@@ -21,17 +25,40 @@ pub fn batch_to_columns<'a, S: Scalar + 'a>(
     batch: &'a RecordBatch,
     alloc: &'a Bump,
 ) -> Result<Vec<(Ident, Column<'a, S>)>, RecordBatchToColumnsError> {
-    batch
-        .schema()
-        .fields()
-        .into_iter()
-        .zip(batch.columns())
-        .map(|(field, array)| {
-            let identifier: Ident = field.name().as_str().into();
-            let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
-            Ok((identifier, column))
-        })
-        .collect()
+    batch_to_columns_with_presence(batch, alloc, |_| false)
+}
+
+/// Like [`batch_to_columns`], but columns for which `has_presence` is `true` are followed by a
+/// presence column even if they contain no NULLs.
+///
+/// This lets a column that has no NULLs yet get a presence column, so that batches with NULLs
+/// can be appended to it later.
+///
+/// This function will return an error if:
+/// - The field name cannot be parsed into an [`Identifier`].
+/// - The conversion of an Arrow array to a [`Column`] fails.
+pub fn batch_to_columns_with_presence<'a, S: Scalar + 'a>(
+    batch: &'a RecordBatch,
+    alloc: &'a Bump,
+    has_presence: impl Fn(&Ident) -> bool,
+) -> Result<Vec<(Ident, Column<'a, S>)>, RecordBatchToColumnsError> {
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (field, array) in batch.schema().fields().into_iter().zip(batch.columns()) {
+        let identifier: Ident = field.name().as_str().into();
+        let range = 0..array.len();
+        let column: Column<S> = array.to_column(alloc, &range, None)?;
+        let presence = array.to_presence_column(alloc, &range).or_else(|| {
+            has_presence(&identifier)
+                .then(|| alloc.alloc_slice_fill_copy(range.len(), true) as &[_])
+        });
+        if let Some(presence) = presence {
+            columns.push((identifier.clone(), column));
+            columns.push((presence_ident(&identifier), Column::Boolean(presence)));
+        } else {
+            columns.push((identifier, column));
+        }
+    }
+    Ok(columns)
 }
 
 impl<C: Commitment> TableCommitment<C> {
@@ -46,12 +73,15 @@ impl<C: Commitment> TableCommitment<C> {
         batch: &RecordBatch,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendRecordBatchTableCommitmentError> {
-        match self.try_append_rows(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
-                .iter()
-                .map(|(a, b)| (a, b)),
-            setup,
-        ) {
+        let alloc = Bump::new();
+        // The presence columns already in the commitment must be appended to even if the batch
+        // contains no NULLs for them.
+        let columns = batch_to_columns_with_presence::<C::Scalar>(batch, &alloc, |identifier| {
+            self.column_commitments()
+                .get_metadata(&presence_ident(identifier))
+                .is_some()
+        })?;
+        match self.try_append_rows(columns.iter().map(|(a, b)| (a, b)), setup) {
             Ok(()) => Ok(()),
             Err(AppendTableCommitmentError::MixedLengthColumns { .. }) => {
                 panic!("RecordBatches cannot have columns of mixed length")
@@ -75,14 +105,35 @@ impl<C: Commitment> TableCommitment<C> {
     }
 
     /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`] with the given row offset.
-    #[allow(clippy::missing_panics_doc)]
     pub fn try_from_record_batch_with_offset(
         batch: &RecordBatch,
         offset: usize,
         setup: &C::PublicSetup<'_>,
+    ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
+        Self::try_from_record_batch_with_offset_and_presence(batch, offset, |_| false, setup)
+    }
+
+    /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`] in which the columns for
+    /// which `has_presence` is `true` have a presence column, see [`batch_to_columns_with_presence`].
+    ///
+    /// Only these columns, and those containing NULLs, accept NULLs in later appends.
+    pub fn try_from_record_batch_with_presence(
+        batch: &RecordBatch,
+        has_presence: impl Fn(&Ident) -> bool,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
+        Self::try_from_record_batch_with_offset_and_presence(batch, 0, has_presence, setup)
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn try_from_record_batch_with_offset_and_presence(
+        batch: &RecordBatch,
+        offset: usize,
+        has_presence: impl Fn(&Ident) -> bool,
+        setup: &C::PublicSetup<'_>,
     ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
         match Self::try_from_columns_with_offset(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
+            batch_to_columns_with_presence::<C::Scalar>(batch, &Bump::new(), has_presence)?
                 .iter()
                 .map(|(a, b)| (a, b)),
             offset,
@@ -103,8 +154,21 @@ impl<C: Commitment> TableCommitment<C> {
 mod tests {
     use super::*;
     use crate::{base::scalar::Curve25519Scalar, record_batch};
+    use alloc::sync::Arc;
+    use arrow::{
+        array::Int64Array,
+        datatypes::{DataType, Field, Schema},
+    };
     use curve25519_dalek::RistrettoPoint;
 
+    fn nullable_batch(values: Vec<Option<i64>>) -> RecordBatch {
+        RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)])),
+            vec![Arc::new(Int64Array::from(values))],
+        )
+        .unwrap()
+    }
+
     #[test]
     fn we_can_create_and_append_table_commitments_with_record_batchs() {
         let batch = record_batch!(
@@ -151,4 +215,51 @@ mod tests {
 
         assert_eq!(commitment, expected_commitment);
     }
+
+    #[test]
+    fn we_can_append_record_batchs_with_nulls_to_table_commitments_with_presence_columns() {
+        let mut commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch_with_presence(
+                &nullable_batch(vec![Some(1), Some(2)]),
+                |identifier| identifier.value == "a",
+                &(),
+            )
+            .unwrap();
+        commitment
+            .try_append_record_batch(&nullable_batch(vec![None, Some(4)]), &())
+            .unwrap();
+        commitment
+            .try_append_record_batch(&nullable_batch(vec![Some(5)]), &())
+            .unwrap();
+
+        let expected_commitment = TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(
+            [
+                (
+                    &"a".into(),
+                    &Column::<Curve25519Scalar>::BigInt(&[1, 2, 0, 4, 5]),
+                ),
+                (
+                    &presence_ident(&"a".into()),
+                    &Column::<Curve25519Scalar>::Boolean(&[true, true, false, true, true]),
+                ),
+            ],
+            0,
+            &(),
+        )
+        .unwrap();
+        assert_eq!(commitment, expected_commitment);
+    }
+
+    #[test]
+    fn we_cannot_append_record_batchs_with_nulls_to_table_commitments_without_presence_columns() {
+        let mut commitment = TableCommitment::<RistrettoPoint>::try_from_record_batch(
+            &nullable_batch(vec![Some(1), Some(2)]),
+            &(),
+        )
+        .unwrap();
+        assert_eq!(commitment.num_columns(), 1);
+        assert!(commitment
+            .try_append_record_batch(&nullable_batch(vec![None, Some(4)]), &())
+            .is_err());
+    }
 }
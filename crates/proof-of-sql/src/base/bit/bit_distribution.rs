@@ -73,17 +73,35 @@ impl BitDistribution {
     /// Currently this is set to be the minimal value that will include the sum of two signed 128-bit
     /// integers. The range will likely be expanded in the future as we support additional expressions.
     pub fn is_within_acceptable_range(&self) -> bool {
-        // handle the case of everything zero
-        if self.num_varying_bits() == 0 && self.constant_part() == [0; 4] {
-            return true;
-        }
-
         // signed 128 bit numbers range from
         //      -2^127 to 2^127-1
         // the maximum absolute value of the sum of two signed 128-integers is
         // then
         //       2 * (2^127) = 2^128
-        self.most_significant_abs_bit() <= 128
+        self.has_abs_bits_up_to(128)
+    }
+
+    /// Like [`Self::is_within_acceptable_range`], but with one more bit, which is the range
+    /// comparisons need.
+    ///
+    /// A comparison takes the sign of `2 * x + 1`, where `x` is the difference of two signed
+    /// 128-bit integers, so that the sign is never taken of zero. This doubles the largest
+    /// absolute value that has to be supported. Accepting it is still sound, since the absolute
+    /// value stays far below half the order of any supported scalar field, so that every value
+    /// in range still has a unique sign. It is only accepted for comparisons, so that no other
+    /// sign proof can be made of a value that its expression can't produce.
+    pub fn is_within_acceptable_comparison_range(&self) -> bool {
+        // the maximum absolute value of twice the sum of two signed 128-bit integers plus one is
+        //       2 * (2^128) + 1 < 2^130
+        self.has_abs_bits_up_to(129)
+    }
+
+    fn has_abs_bits_up_to(&self, max_abs_bit: usize) -> bool {
+        // handle the case of everything zero
+        if self.num_varying_bits() == 0 && self.constant_part() == [0; 4] {
+            return true;
+        }
+        self.most_significant_abs_bit() <= max_abs_bit
     }
 
     /// If `{b_i}` represents the non-varying 1-bits of the absolute values, return the value
@@ -312,3 +312,22 @@ fn we_reject_distributions_that_are_outside_of_maximum_range() {
     let dist = BitDistribution::new::<TestScalar, _>(&data);
     assert!(!dist.is_within_acceptable_range());
 }
+
+#[test]
+fn twice_the_sum_of_two_signed_128_bit_numbers_plus_one_is_only_within_the_comparison_range() {
+    let sum = TestScalar::from(i128::MIN) + TestScalar::from(i128::MIN);
+    let data: Vec<TestScalar> = vec![
+        sum + sum + TestScalar::one(),
+        -(sum + sum) + TestScalar::one(),
+    ];
+    let dist = BitDistribution::new::<TestScalar, _>(&data);
+    assert!(dist.is_within_acceptable_comparison_range());
+    assert!(!dist.is_within_acceptable_range());
+}
+
+#[test]
+fn we_reject_distributions_that_are_outside_of_the_comparison_range() {
+    let data: Vec<TestScalar> = vec![TestScalar::from(u128::MAX) * TestScalar::from(8)];
+    let dist = BitDistribution::new::<TestScalar, _>(&data);
+    assert!(!dist.is_within_acceptable_comparison_range());
+}
@@ -0,0 +1,104 @@
+//! To prove `LIKE` expressions on a `VARCHAR` column, the characters of the column can be stored
+//! alongside it: an `INT` length column holding the number of characters of every string, and
+//! `INT` character columns, the `i`-th of which holds the code point of the `i`-th character of
+//! every string, or 0 if the string is shorter than that.
+//!
+//! These columns are committed to like any other column, so that the verifier can hold the prover
+//! to them. Only patterns whose fixed characters lie within the committed character columns can be
+//! proven.
+//!
+//! # Trust assumption
+//! Nothing ties these columns to the `VARCHAR` column they belong to: a `VARCHAR` column is only
+//! committed to through the hashes of its strings, which the characters can not be checked against.
+//! A proof of a `LIKE` expression therefore only shows that the committed length and character
+//! columns match the pattern, and the verifier relies on the data owner who commits to the table
+//! to have built them from the same strings with [`character_columns`].
+use super::OwnedColumn;
+use crate::base::scalar::Scalar;
+use alloc::{format, string::String, vec::Vec};
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the name of a column to get the name of its length column.
+pub const LENGTH_SUFFIX: &str = "$length";
+
+/// The infix between the name of a column and the index of one of its character columns.
+pub const CHARACTER_INFIX: &str = "$char_";
+
+/// The name of the length column of the column named `ident`.
+#[must_use]
+pub fn length_ident(ident: &Ident) -> Ident {
+    Ident::new(format!("{}{LENGTH_SUFFIX}", ident.value))
+}
+
+/// The name of the character column holding the `index`-th characters of the column named `ident`.
+#[must_use]
+pub fn character_ident(ident: &Ident, index: usize) -> Ident {
+    Ident::new(format!("{}{CHARACTER_INFIX}{index}", ident.value))
+}
+
+/// Whether `ident` is the name of a length or character column.
+#[must_use]
+pub fn is_character_ident(ident: &Ident) -> bool {
+    ident.value.ends_with(LENGTH_SUFFIX) || ident.value.contains(CHARACTER_INFIX)
+}
+
+/// The value of `c` in a character column.
+#[must_use]
+#[allow(clippy::cast_possible_wrap)]
+pub fn code_point(c: char) -> i32 {
+    // Code points are at most 0x10FFFF, so this never wraps.
+    u32::from(c) as i32
+}
+
+/// Builds the length column and the first `num_characters` character columns of the `VARCHAR`
+/// column named `ident` holding `strings`.
+///
+/// # Panics
+/// Panics if a string has more than `i32::MAX` characters.
+#[must_use]
+pub fn character_columns<S: Scalar>(
+    ident: &Ident,
+    strings: &[String],
+    num_characters: usize,
+) -> Vec<(Ident, OwnedColumn<S>)> {
+    let lengths = strings
+        .iter()
+        .map(|s| i32::try_from(s.chars().count()).expect("string is too long"))
+        .collect();
+    core::iter::once((length_ident(ident), OwnedColumn::Int(lengths)))
+        .chain((0..num_characters).map(|index| {
+            let characters = strings
+                .iter()
+                .map(|s| s.chars().nth(index).map_or(0, code_point))
+                .collect();
+            (character_ident(ident, index), OwnedColumn::Int(characters))
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::scalar::test_scalar::TestScalar;
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn we_can_build_the_character_columns_of_a_column() {
+        let ident = Ident::new("a");
+        let strings = vec!["ab".to_string(), String::new(), "茶".to_string()];
+        assert_eq!(
+            character_columns::<TestScalar>(&ident, &strings, 2),
+            vec![
+                (Ident::new("a$length"), OwnedColumn::Int(vec![2, 0, 1])),
+                (
+                    Ident::new("a$char_0"),
+                    OwnedColumn::Int(vec![97, 0, 0x8336])
+                ),
+                (Ident::new("a$char_1"), OwnedColumn::Int(vec![98, 0, 0])),
+            ]
+        );
+        assert!(is_character_ident(&length_ident(&ident)));
+        assert!(is_character_ident(&character_ident(&ident, 3)));
+        assert!(!is_character_ident(&ident));
+    }
+}
@@ -4,6 +4,7 @@ use crate::base::{
     math::decimal::{DecimalError, Precision},
 };
 use alloc::{format, string::ToString};
+use proof_of_sql_parser::intermediate_ast::Percentile;
 // For decimal type manipulation please refer to
 // https://learn.microsoft.com/en-us/sql/t-sql/data-types/precision-scale-and-length-transact-sql?view=sql-server-ver16
 
@@ -169,9 +170,144 @@ pub fn try_divide_column_types(
     Ok(ColumnType::Decimal75(precision, scale))
 }
 
+/// Determine the output type of a `CASE` expression whose branches have the given types if
+/// it is possible to pick between values of the two types. Other than numeric types, which are
+/// converted to the type that holds both of them, the types have to be equal.
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
+/// - Panics if `lhs` or `rhs` is an integer, and `lhs.max_integer_type(&rhs)` returns `None`.
+pub fn try_case_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if lhs == rhs {
+        return Ok(lhs);
+    }
+    if !lhs.is_numeric() || !rhs.is_numeric() {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "CASE".to_string(),
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    if lhs.is_integer() && rhs.is_integer() {
+        // We can unwrap here because we know that both types are integers
+        return Ok(lhs.max_integer_type(&rhs).unwrap());
+    }
+    if lhs == ColumnType::Scalar || rhs == ColumnType::Scalar {
+        return Ok(ColumnType::Scalar);
+    }
+    let left_precision_value =
+        i16::from(lhs.precision_value().expect("Numeric types have precision"));
+    let right_precision_value =
+        i16::from(rhs.precision_value().expect("Numeric types have precision"));
+    let left_scale = lhs.scale().expect("Numeric types have scale");
+    let right_scale = rhs.scale().expect("Numeric types have scale");
+    let scale = left_scale.max(right_scale);
+    let precision_value: i16 = i16::from(scale)
+        + (left_precision_value - i16::from(left_scale))
+            .max(right_precision_value - i16::from(right_scale));
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of an `AVG` aggregation over a column of type `column_type` if it is
+/// possible to average the type. The average is a decimal with four more digits of scale than
+/// the input, but at least six, and with as many integer digits as the input.
+///
+/// # Panics
+///
+/// - Panics if `column_type` does not have a precision or scale when it is expected to be a numeric type.
+pub fn try_avg_column_type(column_type: ColumnType) -> ColumnOperationResult<ColumnType> {
+    if !column_type.is_numeric() || column_type == ColumnType::Scalar {
+        return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: "AVG".to_string(),
+            operand_type: column_type,
+        });
+    }
+    let precision_value = i16::from(
+        column_type
+            .precision_value()
+            .expect("Numeric types have precision"),
+    );
+    let scale = i16::from(column_type.scale().expect("Numeric types have scale"));
+    let raw_scale = (scale + 4_i16).max(6_i16);
+    try_decimal_column_type(precision_value - scale + raw_scale, raw_scale)
+}
+
+/// Determine the output type of a `PERCENTILE_CONT` aggregation, including `MEDIAN`, over a column
+/// of type `column_type` if it is possible. Interpolating between two values of the column is exact
+/// with as many more digits of scale as the percentile has, so the result is a decimal with that
+/// many more digits of precision and scale than the input.
+///
+/// # Panics
+///
+/// - Panics if `column_type` does not have a precision or scale when it is expected to be a numeric type.
+pub fn try_percentile_cont_column_type(
+    column_type: ColumnType,
+    percentile: Percentile,
+) -> ColumnOperationResult<ColumnType> {
+    if !column_type.is_numeric() || column_type == ColumnType::Scalar {
+        return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: "PERCENTILE_CONT".to_string(),
+            operand_type: column_type,
+        });
+    }
+    let precision_value = i16::from(
+        column_type
+            .precision_value()
+            .expect("Numeric types have precision"),
+    );
+    let scale = i16::from(column_type.scale().expect("Numeric types have scale"));
+    let extra_scale = i16::from(percentile.scale());
+    try_decimal_column_type(precision_value + extra_scale, scale + extra_scale)
+}
+
+/// The decimal type with the given precision and scale if it is supported
+fn try_decimal_column_type(
+    precision_value: i16,
+    raw_scale: i16,
+) -> ColumnOperationResult<ColumnType> {
+    let scale =
+        i8::try_from(raw_scale).map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidScale {
+                scale: raw_scale.to_string(),
+            },
+        })?;
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_can_add_numeric_types() {
@@ -750,4 +886,134 @@ mod test {
             })
         ));
     }
+
+    #[test]
+    fn we_can_pick_between_types_in_a_case() {
+        let actual = try_case_column_types(ColumnType::VarChar, ColumnType::VarChar).unwrap();
+        assert_eq!(ColumnType::VarChar, actual);
+
+        let actual = try_case_column_types(ColumnType::SmallInt, ColumnType::BigInt).unwrap();
+        assert_eq!(ColumnType::BigInt, actual);
+
+        let lhs = ColumnType::Decimal75(Precision::new(10).unwrap(), 2);
+        let rhs = ColumnType::Int;
+        let actual = try_case_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(12).unwrap(), 2);
+        assert_eq!(expected, actual);
+
+        let lhs = ColumnType::Decimal75(Precision::new(5).unwrap(), -2);
+        let rhs = ColumnType::Decimal75(Precision::new(4).unwrap(), 3);
+        let actual = try_case_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(10).unwrap(), 3);
+        assert_eq!(expected, actual);
+
+        let actual = try_case_column_types(ColumnType::Scalar, ColumnType::TinyInt).unwrap();
+        assert_eq!(ColumnType::Scalar, actual);
+    }
+
+    #[test]
+    fn we_cannot_pick_between_incompatible_types_in_a_case() {
+        let incompatible_pairs = [
+            (ColumnType::VarChar, ColumnType::BigInt),
+            (ColumnType::Boolean, ColumnType::TinyInt),
+            (
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc()),
+            ),
+        ];
+        for (lhs, rhs) in incompatible_pairs {
+            assert!(matches!(
+                try_case_column_types(lhs, rhs),
+                Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+            ));
+        }
+
+        let lhs = ColumnType::Decimal75(Precision::new(75).unwrap(), 0);
+        let rhs = ColumnType::Decimal75(Precision::new(2).unwrap(), 1);
+        assert!(matches!(
+            try_case_column_types(lhs, rhs),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_average_numeric_types() {
+        assert_eq!(
+            try_avg_column_type(ColumnType::BigInt).unwrap(),
+            ColumnType::Decimal75(Precision::new(25).unwrap(), 6)
+        );
+        assert_eq!(
+            try_avg_column_type(ColumnType::TinyInt).unwrap(),
+            ColumnType::Decimal75(Precision::new(9).unwrap(), 6)
+        );
+        assert_eq!(
+            try_avg_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), 4)).unwrap(),
+            ColumnType::Decimal75(Precision::new(14).unwrap(), 8)
+        );
+        assert_eq!(
+            try_avg_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), -2)).unwrap(),
+            ColumnType::Decimal75(Precision::new(18).unwrap(), 6)
+        );
+    }
+
+    #[test]
+    fn we_can_interpolate_percentiles_of_numeric_types() {
+        assert_eq!(
+            try_percentile_cont_column_type(ColumnType::BigInt, Percentile::MEDIAN).unwrap(),
+            ColumnType::Decimal75(Precision::new(20).unwrap(), 1)
+        );
+        assert_eq!(
+            try_percentile_cont_column_type(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), -2),
+                Percentile::try_new(999, 3).unwrap()
+            )
+            .unwrap(),
+            ColumnType::Decimal75(Precision::new(13).unwrap(), 1)
+        );
+        assert_eq!(
+            try_percentile_cont_column_type(ColumnType::Int, Percentile::try_new(1, 0).unwrap())
+                .unwrap(),
+            ColumnType::Decimal75(Precision::new(10).unwrap(), 0)
+        );
+    }
+
+    #[test]
+    fn we_cannot_interpolate_percentiles_of_non_numeric_or_too_precise_types() {
+        for column_type in [
+            ColumnType::VarChar,
+            ColumnType::Scalar,
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        ] {
+            assert!(matches!(
+                try_percentile_cont_column_type(column_type, Percentile::MEDIAN),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_percentile_cont_column_type(
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 2),
+                Percentile::MEDIAN
+            ),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_average_non_numeric_or_too_precise_types() {
+        for column_type in [
+            ColumnType::VarChar,
+            ColumnType::Boolean,
+            ColumnType::Scalar,
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        ] {
+            assert!(matches!(
+                try_avg_column_type(column_type),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_avg_column_type(ColumnType::Decimal75(Precision::new(73).unwrap(), 2)),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
 }
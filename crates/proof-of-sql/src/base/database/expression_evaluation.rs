@@ -1,6 +1,6 @@
 use super::{ExpressionEvaluationError, ExpressionEvaluationResult};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable},
+    database::{presence_ident, LikePattern, OwnedColumn, OwnedTable},
     math::{
         decimal::{try_convert_intermediate_decimal_to_scalar, DecimalError, Precision},
         BigDecimalExt,
@@ -8,8 +8,8 @@ use crate::base::{
     scalar::Scalar,
 };
 use alloc::{format, string::ToString, vec};
-use proof_of_sql_parser::intermediate_ast::{Expression, Literal};
-use sqlparser::ast::{BinaryOperator, Ident, UnaryOperator};
+use proof_of_sql_parser::intermediate_ast::{Expression, Literal, UnaryOperator};
+use sqlparser::ast::{BinaryOperator, Ident};
 
 impl<S: Scalar> OwnedTable<S> {
     /// Evaluate an expression on the table.
@@ -20,7 +20,28 @@ impl<S: Scalar> OwnedTable<S> {
             Expression::Binary { op, left, right } => {
                 self.evaluate_binary_expr(&(*op).into(), left, right)
             }
-            Expression::Unary { op, expr } => self.evaluate_unary_expr((*op).into(), expr),
+            Expression::Unary { op, expr } => self.evaluate_unary_expr(*op, expr),
+            Expression::Like { expr, pattern } => Ok(self
+                .evaluate(expr)?
+                .element_wise_like(&LikePattern::new(pattern))?),
+            Expression::InList { expr, list } => self.evaluate_in_list(expr, list),
+            Expression::Case {
+                conditions,
+                else_result: Some(else_result),
+            } => conditions.iter().rev().try_fold(
+                self.evaluate(else_result)?,
+                |else_column, (condition, result)| {
+                    Ok(self
+                        .evaluate(condition)?
+                        .element_wise_case(&self.evaluate(result)?, &else_column)?)
+                },
+            ),
+            Expression::Between { expr, low, high } => {
+                let value = self.evaluate(expr)?;
+                let is_above_low = value.element_wise_ge(&self.evaluate(low)?)?;
+                let is_below_high = value.element_wise_le(&self.evaluate(high)?)?;
+                Ok(is_above_low.element_wise_and(&is_below_high)?)
+            }
             _ => Err(ExpressionEvaluationError::Unsupported {
                 expression: format!("Expression {expr:?} is not supported yet"),
             }),
@@ -60,6 +81,9 @@ impl<S: Scalar> OwnedTable<S> {
                 its.timezone(),
                 vec![its.timestamp().timestamp(); len],
             )),
+            Literal::Interval { .. } => Err(ExpressionEvaluationError::Unsupported {
+                expression: format!("{lit:?}"),
+            }),
         }
     }
 
@@ -68,16 +92,48 @@ impl<S: Scalar> OwnedTable<S> {
         op: UnaryOperator,
         expr: &Expression,
     ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
-        let column = self.evaluate(expr)?;
         match op {
-            UnaryOperator::Not => Ok(column.element_wise_not()?),
-            // Handle unsupported unary operators
+            UnaryOperator::Not => Ok(self.evaluate(expr)?.element_wise_not()?),
+            UnaryOperator::IsNull => Ok(self.evaluate_presence(expr)?.element_wise_not()?),
+            UnaryOperator::IsNotNull => self.evaluate_presence(expr),
+        }
+    }
+
+    /// Evaluate whether the entries of a column are not NULL.
+    ///
+    /// A column without a presence column contains no NULLs.
+    fn evaluate_presence(&self, expr: &Expression) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        match expr {
+            Expression::Column(identifier) => {
+                let identifier = Ident::from(*identifier);
+                self.evaluate_column(&identifier)?;
+                Ok(self
+                    .inner_table()
+                    .get(&presence_ident(&identifier))
+                    .cloned()
+                    .unwrap_or_else(|| OwnedColumn::Boolean(vec![true; self.num_rows()])))
+            }
             _ => Err(ExpressionEvaluationError::Unsupported {
-                expression: format!("Unary operator '{op}' is not supported."),
+                expression: format!("NULL check of {expr:?}"),
             }),
         }
     }
 
+    fn evaluate_in_list(
+        &self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        let value = self.evaluate(expr)?;
+        list.iter().try_fold(
+            OwnedColumn::Boolean(vec![false; self.num_rows()]),
+            |is_in_list, item| {
+                let is_item = value.element_wise_eq(&self.evaluate(item)?)?;
+                Ok(is_in_list.element_wise_or(&is_item)?)
+            },
+        )
+    }
+
     fn evaluate_binary_expr(
         &self,
         op: &BinaryOperator,
@@ -90,8 +146,11 @@ impl<S: Scalar> OwnedTable<S> {
             BinaryOperator::And => Ok(left.element_wise_and(&right)?),
             BinaryOperator::Or => Ok(left.element_wise_or(&right)?),
             BinaryOperator::Eq => Ok(left.element_wise_eq(&right)?),
+            BinaryOperator::NotEq => Ok(left.element_wise_eq(&right)?.element_wise_not()?),
             BinaryOperator::GtEq => Ok(left.element_wise_ge(&right)?),
             BinaryOperator::LtEq => Ok(left.element_wise_le(&right)?),
+            BinaryOperator::Gt => Ok(left.element_wise_le(&right)?.element_wise_not()?),
+            BinaryOperator::Lt => Ok(left.element_wise_ge(&right)?.element_wise_not()?),
             BinaryOperator::Plus => Ok(left.element_wise_add(&right)?),
             BinaryOperator::Minus => Ok(left.element_wise_sub(&right)?),
             BinaryOperator::Multiply => Ok(left.element_wise_mul(&right)?),
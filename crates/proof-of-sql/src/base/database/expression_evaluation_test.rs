@@ -96,6 +96,8 @@ fn we_can_evaluate_a_logical_expression() {
         varchar("sk", ["Alžbeta", "Ján", "mačka", "pes", "Mníchov"]),
         varchar("hr", ["Elizabeta", "Ivan", "mačka", "pas", "München"]),
         varchar("sl", ["Elizabeta", "Janez", "mačka", "pes", "München"]),
+        varchar("hu", ["Erzsébet", "János", "", "kutya", "München"]),
+        presence("hu", [true, true, false, true, true]),
         boolean("is_proper_noun", [true, true, false, false, true]),
     ]);
 
@@ -105,6 +107,36 @@ fn we_can_evaluate_a_logical_expression() {
     let expected_column = OwnedColumn::Boolean(vec![false, false, true, true, false]);
     assert_eq!(actual_column, expected_column);
 
+    // Which words have no Hungarian translation?
+    let expr = is_null(col("hu"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Columns without a presence column have no NULLs
+    let expr = is_not_null(col("en"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true; 5]);
+    assert_eq!(actual_column, expected_column);
+
+    // Which Croatian words start with "ma" and have five letters?
+    let expr = like(col("hr"), "ma___");
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Which Polish words do not contain an "i"?
+    let expr = not(like(col("pl"), "%i%"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Which Croatian words are among a few given ones?
+    let expr = in_list(col("hr"), vec![lit("Ivan"), lit("pas"), lit("mama")]);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, false, true, false]);
+    assert_eq!(actual_column, expected_column);
+
     // Which Czech and Slovak words agree?
     let expr = equal(col("cz"), col("sk"));
     let actual_column = table.evaluate(&expr).unwrap();
@@ -156,6 +188,37 @@ fn we_can_evaluate_an_arithmetic_expression() {
     let expected_column = OwnedColumn::BigInt(vec![-9, -5, -1, 3, 7]);
     assert_eq!(actual_column, expected_column);
 
+    // Which ints lie between the smallints and 3?
+    let expr = between(col("ints"), col("smallints"), lit(3));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, true, true, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Which bigints are not in a given list?
+    let expr = not(in_list(col("bigints"), vec![lit(-4), lit(4), lit(5)]));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, false, true, false, true]);
+    assert_eq!(actual_column, expected_column);
+
+    // Bucket the bigints by sign
+    let expr = case(
+        vec![
+            (le(col("bigints"), lit(-1)), lit(-1)),
+            (equal(col("bigints"), lit(0)), col("smallints")),
+        ],
+        Some(lit(1)),
+    );
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![-1, -1, 0, 1, 1]);
+    assert_eq!(actual_column, expected_column);
+
+    // A CASE without ELSE may be NULL
+    let expr = case(vec![(equal(col("bigints"), lit(0)), lit(0))], None);
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::Unsupported { .. })
+    ));
+
     // Add bigints to the smallints and multiply the sum by the ints
     let expr = mul(add(col("bigints"), col("smallints")), col("ints"));
     let actual_column = table.evaluate(&expr).unwrap();
@@ -215,6 +278,15 @@ fn we_cannot_evaluate_expressions_if_column_operation_errors_out() {
         })
     ));
 
+    // LIKE doesn't work on bigint
+    let expr = like(col("bigints"), "1%");
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::ColumnOperationError {
+            source: ColumnOperationError::UnaryOperationInvalidColumnType { .. }
+        })
+    ));
+
     // + doesn't work on varchar
     let expr = add(col("sarah"), col("bigints"));
     assert!(matches!(
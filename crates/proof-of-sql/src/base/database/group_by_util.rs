@@ -5,9 +5,9 @@ use crate::base::{
         filter_util::filter_column_by_index, order_by_util::compare_indexes_by_columns, Column,
     },
     if_rayon,
-    scalar::Scalar,
+    scalar::{Scalar, ScalarExt},
 };
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
@@ -70,24 +70,7 @@ pub fn aggregate_columns<'a, S: Scalar>(
 
     // `filtered_indexes` is a vector of indexes of the rows that are selected. We sort this vector
     // so that all the rows in the same group are next to each other.
-    let mut filtered_indexes: Vec<_> = selection_column_in
-        .iter()
-        .enumerate()
-        .filter(|&(_, &b)| b)
-        .map(|(i, _)| i)
-        .collect();
-    if_rayon!(
-        filtered_indexes.par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(
-            group_by_columns_in,
-            a,
-            b
-        )),
-        filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(
-            group_by_columns_in,
-            a,
-            b
-        ))
-    );
+    let filtered_indexes = sorted_filtered_indexes(group_by_columns_in, selection_column_in);
 
     // `group_by_result_indexes` gives a single index for each group in `filtered_indexes`. It does
     // not matter which index is chosen for each group, so we choose the first one. This is only used
@@ -142,6 +125,80 @@ pub fn aggregate_columns<'a, S: Scalar>(
     })
 }
 
+/// Returns the indexes of the selected rows sorted by the `group_by` columns.
+fn sorted_filtered_indexes<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Vec<usize> {
+    let mut filtered_indexes: Vec<_> = selection_column_in
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(i, _)| i)
+        .collect();
+    if_rayon!(
+        filtered_indexes.par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        )),
+        filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        ))
+    );
+    filtered_indexes
+}
+
+/// Returns, for each row, the index of its group in the output of [`aggregate_columns`],
+/// or `None` if the row is not selected.
+pub(crate) fn group_indexes<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Vec<Option<usize>> {
+    let filtered_indexes = sorted_filtered_indexes(group_by_columns_in, selection_column_in);
+    let mut group_indexes = vec![None; selection_column_in.len()];
+    let mut group_index = 0;
+    for (position, &row) in filtered_indexes.iter().enumerate() {
+        if position > 0
+            && compare_indexes_by_columns(group_by_columns_in, filtered_indexes[position - 1], row)
+                != Ordering::Equal
+        {
+            group_index += 1;
+        }
+        group_indexes[row] = Some(group_index);
+    }
+    group_indexes
+}
+
+/// Returns a slice with the lifetime of `alloc` that contains, for each group, the value of `column`
+/// of rank `ranks[j]`, counting from `0`, among the selected values of the group in ascending order.
+///
+/// `group_indexes` is as returned by [`group_indexes`] and `ranks` has one entry per group.
+///
+/// # Panics
+/// Panics if a group index or rank is out of bounds.
+pub(crate) fn order_statistics_by_group_indexes<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &[S],
+    group_indexes: &[Option<usize>],
+    ranks: &[usize],
+) -> &'a [S] {
+    let mut groups = vec![Vec::new(); ranks.len()];
+    for (value, group_index) in column.iter().zip(group_indexes) {
+        if let Some(j) = group_index {
+            groups[*j].push(*value);
+        }
+    }
+    alloc.alloc_slice_fill_iter(
+        groups
+            .iter_mut()
+            .zip(ranks)
+            .map(|(values, &rank)| *values.select_nth_unstable_by(rank, ScalarExt::signed_cmp).1),
+    )
+}
+
 /// Returns a slice with the lifetime of `alloc` that contains the grouped sums of `column`.
 /// The `counts` slice contains the number of elements in each group and the `indexes` slice
 /// contains the indexes of the elements in `column`.
@@ -111,6 +111,44 @@ fn we_can_aggregate_columns_with_empty_group_by() {
 }
 
 #[allow(clippy::too_many_lines)]
+#[test]
+fn we_can_compute_the_group_indexes_of_rows() {
+    let column_a = Column::BigInt::<TestScalar>(&[3, 1, 3, 2, 1, 3]);
+    let column_b = Column::Int(&[5, 4, 5, 4, 4, 6]);
+    let selection = &[true, true, true, false, true, true];
+    assert_eq!(
+        group_indexes(&[column_a, column_b], selection),
+        vec![Some(1), Some(0), Some(1), None, Some(0), Some(2)]
+    );
+    assert_eq!(
+        group_indexes::<TestScalar>(&[], selection),
+        vec![Some(0), Some(0), Some(0), None, Some(0), Some(0)]
+    );
+    assert_eq!(group_indexes::<TestScalar>(&[], &[]), vec![]);
+}
+
+#[test]
+fn we_can_compute_order_statistics_by_group() {
+    let alloc = Bump::new();
+    let column: Vec<TestScalar> = [5, -2, 7, 3, 3, 9, -4]
+        .into_iter()
+        .map(TestScalar::from)
+        .collect();
+    let group_indexes = &[Some(0), Some(1), Some(0), None, Some(0), Some(1), Some(1)];
+    assert_eq!(
+        order_statistics_by_group_indexes(&alloc, &column, group_indexes, &[0, 2]),
+        &[TestScalar::from(3), TestScalar::from(9)]
+    );
+    assert_eq!(
+        order_statistics_by_group_indexes(&alloc, &column, group_indexes, &[1, 0]),
+        &[TestScalar::from(5), TestScalar::from(-4)]
+    );
+    assert_eq!(
+        order_statistics_by_group_indexes::<TestScalar>(&alloc, &[], &[], &[]),
+        &[]
+    );
+}
+
 #[test]
 fn we_can_aggregate_columns() {
     let slice_a = &[3, 3, 3, 2, 2, 1, 1, 2, 2, 3, 3, 3];
@@ -1,5 +1,6 @@
 use super::{ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp, Table, TableOptions};
 use crate::base::scalar::Scalar;
+use alloc::{collections::BTreeMap, vec::Vec};
 use bumpalo::Bump;
 
 /// Compute the CROSS JOIN / cartesian product of two tables.
@@ -34,6 +35,31 @@ pub fn cross_join<'a, S: Scalar>(
     .expect("Table creation should not fail")
 }
 
+/// Compute the row index pairs of the INNER JOIN of two tables on the equality of two key columns.
+///
+/// The returned pairs `(left_index, right_index)` are sorted lexicographically,
+/// i.e. first by the left row index and then by the right row index.
+pub fn equi_join_indexes<S: Scalar>(left_key: &[S], right_key: &[S]) -> Vec<(usize, usize)> {
+    let mut right_indexes_by_key: BTreeMap<S, Vec<usize>> = BTreeMap::new();
+    for (right_index, key) in right_key.iter().enumerate() {
+        right_indexes_by_key
+            .entry(*key)
+            .or_default()
+            .push(right_index);
+    }
+    left_key
+        .iter()
+        .enumerate()
+        .flat_map(|(left_index, key)| {
+            right_indexes_by_key
+                .get(key)
+                .into_iter()
+                .flatten()
+                .map(move |&right_index| (left_index, right_index))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,4 +267,24 @@ mod tests {
         assert_eq!(result.num_rows(), 0);
         assert_eq!(result.num_columns(), 0);
     }
+
+    #[test]
+    fn we_can_compute_equi_join_indexes() {
+        let left_key: Vec<TestScalar> = [1, 2, 3, 2, 5].into_iter().map(TestScalar::from).collect();
+        let right_key: Vec<TestScalar> =
+            [2, 4, 1, 2, 1].into_iter().map(TestScalar::from).collect();
+        assert_eq!(
+            equi_join_indexes(&left_key, &right_key),
+            vec![(0, 2), (0, 4), (1, 0), (1, 3), (3, 0), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn we_can_compute_equi_join_indexes_with_no_matches() {
+        let left_key: Vec<TestScalar> = [1, 2].into_iter().map(TestScalar::from).collect();
+        let right_key: Vec<TestScalar> = [3, 4].into_iter().map(TestScalar::from).collect();
+        assert!(equi_join_indexes(&left_key, &right_key).is_empty());
+        assert!(equi_join_indexes::<TestScalar>(&[], &right_key).is_empty());
+        assert!(equi_join_indexes::<TestScalar>(&left_key, &[]).is_empty());
+    }
 }
@@ -0,0 +1,168 @@
+//! Patterns of `LIKE` expressions.
+use alloc::vec::Vec;
+
+/// A single element of a [`LikePattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikePatternElement {
+    /// Matches exactly this character
+    Character(char),
+    /// `_`, which matches any single character
+    AnyCharacter,
+    /// `%`, which matches any sequence of characters, including the empty one
+    AnyString,
+}
+
+/// The pattern of a `LIKE` expression.
+///
+/// In the pattern, `_` matches any single character, `%` matches any sequence of characters and a
+/// backslash matches the character following it literally. Consecutive `%`s are merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LikePattern(Vec<LikePatternElement>);
+
+impl LikePattern {
+    /// Parses a `LIKE` pattern. A trailing backslash matches a backslash.
+    #[must_use]
+    pub fn new(pattern: &str) -> Self {
+        let mut elements = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let element = match c {
+                '_' => LikePatternElement::AnyCharacter,
+                '%' if elements.last() == Some(&LikePatternElement::AnyString) => continue,
+                '%' => LikePatternElement::AnyString,
+                '\\' => LikePatternElement::Character(chars.next().unwrap_or('\\')),
+                c => LikePatternElement::Character(c),
+            };
+            elements.push(element);
+        }
+        Self(elements)
+    }
+
+    /// The elements of the pattern.
+    #[must_use]
+    pub fn elements(&self) -> &[LikePatternElement] {
+        &self.0
+    }
+
+    /// Splits the pattern into the elements matching the start of a string and whether they are
+    /// followed by a `%`.
+    ///
+    /// Returns `None` if there is a `%` anywhere but at the end of the pattern.
+    #[must_use]
+    pub fn as_prefix(&self) -> Option<(&[LikePatternElement], bool)> {
+        let (prefix, is_open) = match self.0.split_last() {
+            Some((LikePatternElement::AnyString, prefix)) => (prefix, true),
+            _ => (&self.0[..], false),
+        };
+        (!prefix.contains(&LikePatternElement::AnyString)).then_some((prefix, is_open))
+    }
+
+    /// Whether `s` matches the pattern.
+    #[must_use]
+    pub fn matches(&self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        let (mut pattern_index, mut char_index) = (0, 0);
+        // The pattern index after the last `%` along with where in `s` matching after it started
+        let mut backtrack = None;
+        while char_index < chars.len() {
+            match self.0.get(pattern_index) {
+                Some(LikePatternElement::AnyString) => {
+                    backtrack = Some((pattern_index + 1, char_index));
+                    pattern_index += 1;
+                    continue;
+                }
+                Some(LikePatternElement::AnyCharacter) => {
+                    pattern_index += 1;
+                    char_index += 1;
+                    continue;
+                }
+                Some(LikePatternElement::Character(c)) if *c == chars[char_index] => {
+                    pattern_index += 1;
+                    char_index += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            // Let the last `%` absorb one more character and try again
+            match backtrack {
+                Some((next_pattern_index, absorbed)) => {
+                    backtrack = Some((next_pattern_index, absorbed + 1));
+                    pattern_index = next_pattern_index;
+                    char_index = absorbed + 1;
+                }
+                None => return false,
+            }
+        }
+        self.0[pattern_index..]
+            .iter()
+            .all(|element| *element == LikePatternElement::AnyString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LikePattern, LikePatternElement::*};
+
+    #[test]
+    fn we_can_parse_a_like_pattern() {
+        assert_eq!(
+            LikePattern::new(r"a_%%\%\\b\").elements(),
+            [
+                Character('a'),
+                AnyCharacter,
+                AnyString,
+                Character('%'),
+                Character('\\'),
+                Character('b'),
+                Character('\\'),
+            ]
+        );
+        assert!(LikePattern::new("").elements().is_empty());
+    }
+
+    #[test]
+    fn we_can_get_the_prefix_of_a_like_pattern() {
+        assert_eq!(
+            LikePattern::new("a_%").as_prefix(),
+            Some((&[Character('a'), AnyCharacter][..], true))
+        );
+        assert_eq!(
+            LikePattern::new("ab").as_prefix(),
+            Some((&[Character('a'), Character('b')][..], false))
+        );
+        assert_eq!(LikePattern::new("%").as_prefix(), Some((&[][..], true)));
+        assert_eq!(LikePattern::new("%a").as_prefix(), None);
+        assert_eq!(LikePattern::new("a%b%").as_prefix(), None);
+    }
+
+    #[test]
+    fn we_can_match_strings_against_a_like_pattern() {
+        let cases = [
+            ("abc", "abc", true),
+            ("abc", "abd", false),
+            ("abc", "ab", false),
+            ("ab%", "ab", true),
+            ("ab%", "abcd", true),
+            ("ab%", "a", false),
+            ("a_c", "abc", true),
+            ("a_c", "ac", false),
+            ("%c", "abc", true),
+            ("%b%", "abc", true),
+            ("%b%", "ac", false),
+            ("a%b%c", "axxbyybzc", true),
+            ("a%b%c", "axxyyc", false),
+            ("%", "", true),
+            ("_", "", false),
+            ("_", "茶", true),
+            (r"100\%", "100%", true),
+            (r"100\%", "1000", false),
+        ];
+        for (pattern, s, expected) in cases {
+            assert_eq!(
+                LikePattern::new(pattern).matches(s),
+                expected,
+                "{s:?} LIKE {pattern:?}"
+            );
+        }
+    }
+}
@@ -14,7 +14,8 @@ mod slice_decimal_operation;
 
 mod column_type_operation;
 pub use column_type_operation::{
-    try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+    try_add_subtract_column_types, try_avg_column_type, try_case_column_types,
+    try_divide_column_types, try_multiply_column_types, try_percentile_cont_column_type,
 };
 
 mod column_arithmetic_operation;
@@ -26,6 +27,7 @@ pub(super) use column_comparison_operation::{
 };
 
 mod column_index_operation;
+pub(crate) use column_index_operation::apply_column_to_indexes;
 
 mod column_repetition_operation;
 pub(super) use column_repetition_operation::{ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp};
@@ -42,6 +44,20 @@ pub use columnar_value::ColumnarValue;
 mod literal_value;
 pub use literal_value::LiteralValue;
 
+mod character_column;
+pub use character_column::{
+    character_columns, character_ident, code_point, is_character_ident, length_ident,
+    CHARACTER_INFIX, LENGTH_SUFFIX,
+};
+
+mod like_pattern;
+pub use like_pattern::{LikePattern, LikePatternElement};
+
+mod presence_column;
+pub use presence_column::{
+    is_presence_ident, presence_column_ref, presence_ident, PRESENCE_SUFFIX,
+};
+
 mod table_ref;
 #[cfg(feature = "arrow")]
 pub use crate::base::arrow::{
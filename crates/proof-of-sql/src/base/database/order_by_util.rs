@@ -31,6 +31,27 @@ pub(crate) fn compare_indexes_by_columns<S: Scalar>(
         .unwrap_or(Ordering::Equal)
 }
 
+/// Compares the tuples `(order_by_pairs[0][i], order_by_pairs[1][i], ...)` and
+/// `(order_by_pairs[0][j], order_by_pairs[1][j], ...)` in lexicographic order.
+/// Note that direction flips the ordering.
+pub(crate) fn compare_indexes_by_columns_with_direction<S: Scalar>(
+    order_by_pairs: &[(Column<S>, OrderByDirection)],
+    i: usize,
+    j: usize,
+) -> Ordering {
+    order_by_pairs
+        .iter()
+        .map(|(col, direction)| {
+            let ordering = compare_indexes_by_columns(&[*col], i, j);
+            match direction {
+                OrderByDirection::Asc => ordering,
+                OrderByDirection::Desc => ordering.reverse(),
+            }
+        })
+        .find(|&ord| ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
 /// Compares the tuples `(left[0][i], left[1][i], ...)` and
 /// `(right[0][j], right[1][j], ...)` in lexicographic order.
 ///
@@ -249,6 +249,43 @@ fn we_can_compare_indexes_by_columns_for_scalar_columns() {
     assert_eq!(compare_indexes_by_columns(columns, 6, 9), Ordering::Equal);
 }
 
+#[test]
+fn we_can_compare_indexes_by_columns_with_direction() {
+    let slice_a = &[55, 44, 66, 66, 66];
+    let slice_b = &[22, 44, 11, 44, 33];
+    let column_a = Column::BigInt::<TestScalar>(slice_a);
+    let column_b = Column::BigInt::<TestScalar>(slice_b);
+    let order_by_pairs = &[
+        (column_a, OrderByDirection::Asc),
+        (column_b, OrderByDirection::Desc),
+    ];
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 0, 1),
+        Ordering::Greater
+    );
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 2, 3),
+        Ordering::Greater
+    );
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 3, 4),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 2, 2),
+        Ordering::Equal
+    );
+    let order_by_pairs = &[(column_a, OrderByDirection::Desc)];
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 0, 1),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare_indexes_by_columns_with_direction(order_by_pairs, 2, 3),
+        Ordering::Equal
+    );
+}
+
 #[test]
 fn we_can_compare_columns_with_direction() {
     let col1: OwnedColumn<TestScalar> = OwnedColumn::SmallInt(vec![1, 1, 2, 1, 1]);
@@ -343,7 +343,7 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for OwnedColumn<S> {
 }
 
 impl<S: Scalar> OwnedColumn<S> {
-    /// Attempts to coerce a column of scalars to a numeric column of the specified type.
+    /// Attempts to coerce a column of scalars to a numeric or timestamp column of the specified type.
     /// If the specified type is the same as the current column type, the function will return the column as is.
     ///
     /// # Arguments
@@ -361,7 +361,7 @@ impl<S: Scalar> OwnedColumn<S> {
     ///
     /// Otherwise, this function will return an error if:
     /// * The column type is not `Scalar`.
-    /// * The target type is not a numeric type.
+    /// * The target type is neither a numeric nor a timestamp type.
     /// * There is an overflow during the coercion.
     pub(crate) fn try_coerce_scalar_to_numeric(
         self,
@@ -404,6 +404,12 @@ impl<S: Scalar> OwnedColumn<S> {
                 ColumnType::Decimal75(precision, scale) => {
                     Ok(OwnedColumn::Decimal75(precision, scale, vec))
                 }
+                ColumnType::TimestampTZ(time_unit, timezone) => vec
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .try_collect()
+                    .map_err(|_| ColumnCoercionError::Overflow)
+                    .map(|times| OwnedColumn::TimestampTZ(time_unit, timezone, times)),
                 _ => Err(ColumnCoercionError::InvalidTypeCoercion),
             }
         } else {
@@ -731,6 +737,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn we_can_coerce_scalar_to_timestamp() {
+        let scalars = vec![TestScalar::from(-1), TestScalar::from(1_625_072_400)];
+        let col = OwnedColumn::Scalar(scalars);
+        let coerced_col = col
+            .try_coerce_scalar_to_numeric(ColumnType::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+            ))
+            .unwrap();
+        assert_eq!(
+            coerced_col,
+            OwnedColumn::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                vec![-1, 1_625_072_400]
+            )
+        );
+    }
+
     #[test]
     fn we_cannot_coerce_scalar_to_invalid_type() {
         let scalars = vec![
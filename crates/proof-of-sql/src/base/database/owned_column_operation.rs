@@ -5,11 +5,13 @@ use super::{
 use crate::base::{
     database::{
         slice_operation::{slice_and, slice_not, slice_or},
-        OwnedColumn,
+        try_case_column_types, Column, LikePattern, OwnedColumn,
     },
     scalar::Scalar,
 };
-use alloc::string::ToString;
+use alloc::{string::ToString, vec::Vec};
+use bumpalo::Bump;
+use itertools::multizip;
 
 impl<S: Scalar> OwnedColumn<S> {
     /// Element-wise NOT operation for a column
@@ -23,6 +25,65 @@ impl<S: Scalar> OwnedColumn<S> {
         }
     }
 
+    /// Element-wise LIKE operation for a column
+    pub fn element_wise_like(&self, pattern: &LikePattern) -> ColumnOperationResult<Self> {
+        match self {
+            Self::VarChar(values) => Ok(Self::Boolean(
+                values.iter().map(|value| pattern.matches(value)).collect(),
+            )),
+            _ => Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+                operator: "LIKE".to_string(),
+                operand_type: self.column_type(),
+            }),
+        }
+    }
+
+    /// Element-wise `CASE WHEN self THEN then_column ELSE else_column END` for a boolean column
+    ///
+    /// # Panics
+    /// Panics if a value of a branch does not fit into the type of the result, which is impossible.
+    pub fn element_wise_case(
+        &self,
+        then_column: &Self,
+        else_column: &Self,
+    ) -> ColumnOperationResult<Self> {
+        let Self::Boolean(condition) = self else {
+            return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+                operator: "CASE".to_string(),
+                operand_type: self.column_type(),
+            });
+        };
+        for branch in [then_column, else_column] {
+            if self.len() != branch.len() {
+                return Err(ColumnOperationError::DifferentColumnLength {
+                    len_a: self.len(),
+                    len_b: branch.len(),
+                });
+            }
+        }
+        let result_type =
+            try_case_column_types(then_column.column_type(), else_column.column_type())?;
+        if let (Self::VarChar(then_values), Self::VarChar(else_values)) = (then_column, else_column)
+        {
+            return Ok(Self::VarChar(
+                multizip((condition, then_values, else_values))
+                    .map(|(c, t, e)| if *c { t.clone() } else { e.clone() })
+                    .collect(),
+            ));
+        }
+        let alloc = Bump::new();
+        let result_scale = result_type.scale().unwrap_or(0);
+        let [then_scalars, else_scalars] = [then_column, else_column].map(|branch| {
+            Column::from_owned_column(branch, &alloc)
+                .to_scalar_with_scaling(result_scale - branch.column_type().scale().unwrap_or(0))
+        });
+        let scalars: Vec<S> = multizip((condition, then_scalars, else_scalars))
+            .map(|(c, t, e)| if *c { t } else { e })
+            .collect();
+        Ok(Self::try_from_scalars(&scalars, result_type)
+            .expect("the values of both branches fit into the result type"))
+    }
+
     /// Element-wise AND for two columns
     pub fn element_wise_and(&self, rhs: &Self) -> ColumnOperationResult<Self> {
         if self.len() != rhs.len() {
@@ -99,7 +160,7 @@ impl<S: Scalar> OwnedColumn<S> {
 mod test {
     use super::*;
     use crate::base::{math::decimal::Precision, scalar::test_scalar::TestScalar};
-    use alloc::vec;
+    use alloc::{string::String, vec};
 
     #[test]
     fn we_cannot_do_binary_operation_on_columns_with_different_lengths() {
@@ -208,6 +269,100 @@ mod test {
         ));
     }
 
+    #[test]
+    fn we_can_do_like_operations_on_varchar_columns() {
+        let column = OwnedColumn::<TestScalar>::VarChar(
+            ["abc", "ab", "xabc", ""].map(ToString::to_string).to_vec(),
+        );
+        let result = column.element_wise_like(&LikePattern::new("ab%"));
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<TestScalar>::Boolean(vec![
+                true, true, false, false
+            ]))
+        );
+
+        let result =
+            OwnedColumn::<TestScalar>::Int(vec![1]).element_wise_like(&LikePattern::new("%"));
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_do_case_operations_on_columns() {
+        let condition = OwnedColumn::<TestScalar>::Boolean(vec![true, false, true]);
+        let then_column = OwnedColumn::VarChar(["a", "b", "c"].map(ToString::to_string).to_vec());
+        let else_column = OwnedColumn::VarChar(["x", "y", "z"].map(ToString::to_string).to_vec());
+        let result = condition.element_wise_case(&then_column, &else_column);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::VarChar(
+                ["a", "y", "c"].map(ToString::to_string).to_vec()
+            ))
+        );
+
+        // The branches are converted to a common type
+        let then_column = OwnedColumn::Decimal75(
+            Precision::new(3).unwrap(),
+            1,
+            vec![
+                TestScalar::from(15),
+                TestScalar::from(25),
+                TestScalar::from(35),
+            ],
+        );
+        let else_column = OwnedColumn::SmallInt(vec![1, 2, 3]);
+        let result = condition.element_wise_case(&then_column, &else_column);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::Decimal75(
+                Precision::new(6).unwrap(),
+                1,
+                vec![
+                    TestScalar::from(15),
+                    TestScalar::from(20),
+                    TestScalar::from(35)
+                ]
+            ))
+        );
+
+        let then_column = OwnedColumn::TinyInt(vec![1, 2, 3]);
+        let else_column = OwnedColumn::BigInt(vec![-1, -2, -3]);
+        let result = condition.element_wise_case(&then_column, &else_column);
+        assert_eq!(result, Ok(OwnedColumn::BigInt(vec![1, -2, 3])));
+    }
+
+    #[test]
+    fn we_cannot_do_case_operations_on_invalid_columns() {
+        let condition = OwnedColumn::<TestScalar>::Boolean(vec![true, false]);
+        let result = condition.element_wise_case(
+            &OwnedColumn::BigInt(vec![1, 2]),
+            &OwnedColumn::VarChar(vec![String::new(), String::new()]),
+        );
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+        ));
+
+        let result = condition.element_wise_case(
+            &OwnedColumn::BigInt(vec![1, 2]),
+            &OwnedColumn::BigInt(vec![1]),
+        );
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::DifferentColumnLength { .. })
+        ));
+
+        let result = OwnedColumn::<TestScalar>::BigInt(vec![1])
+            .element_wise_case(&OwnedColumn::BigInt(vec![1]), &OwnedColumn::BigInt(vec![1]));
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+        ));
+    }
+
     #[test]
     fn we_can_do_logical_operation_on_boolean_columns() {
         let lhs = OwnedColumn::<TestScalar>::Boolean(vec![true, false, true, false]);
@@ -13,9 +13,9 @@
 //!     decimal75("f", 12, 1, [1, 2, 3]),
 //! ]);
 //! ```
-use super::{OwnedColumn, OwnedTable};
+use super::{character_columns, presence_ident, OwnedColumn, OwnedTable};
 use crate::base::scalar::Scalar;
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 use sqlparser::ast::Ident;
 
@@ -150,6 +150,27 @@ pub fn boolean<S: Scalar>(
     )
 }
 
+/// Creates a `(Identifier, OwnedColumn)` pair for the presence column of the column named `name`,
+/// which marks the entries that are not NULL.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     bigint("a", [1, 0, 3]),
+///     presence("a", [true, false, true]),
+/// ]);
+/// ```
+pub fn presence<S: Scalar>(
+    name: impl Into<Ident>,
+    data: impl IntoIterator<Item = impl Into<bool>>,
+) -> (Ident, OwnedColumn<S>) {
+    (
+        presence_ident(&name.into()),
+        OwnedColumn::Boolean(data.into_iter().map(Into::into).collect()),
+    )
+}
+
 /// Creates a `(Identifier, OwnedColumn)` pair for a int128 column.
 /// This is primarily intended for use in conjunction with [`owned_table`].
 /// # Example
@@ -216,6 +237,31 @@ pub fn varchar<S: Scalar>(
     )
 }
 
+/// Creates the `(Identifier, OwnedColumn)` pairs for a varchar column followed by its length column
+/// and its first `num_characters` character columns, which allow proving `LIKE` expressions.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>(
+///     varchar_with_characters("a", ["a", "b", "c"], 1)
+///         .into_iter()
+///         .chain([bigint("b", [1, 2, 3])]),
+/// );
+/// ```
+pub fn varchar_with_characters<S: Scalar>(
+    name: impl Into<Ident>,
+    data: impl IntoIterator<Item = impl Into<String>>,
+    num_characters: usize,
+) -> Vec<(Ident, OwnedColumn<S>)> {
+    let name = name.into();
+    let strings: Vec<String> = data.into_iter().map(Into::into).collect();
+    let characters = character_columns(&name, &strings, num_characters);
+    core::iter::once((name, OwnedColumn::VarChar(strings)))
+        .chain(characters)
+        .collect()
+}
+
 /// Creates a `(Identifier, OwnedColumn)` pair for a decimal75 column.
 /// This is primarily intended for use in conjunction with [`owned_table`].
 /// # Example
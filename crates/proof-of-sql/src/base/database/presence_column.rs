@@ -0,0 +1,57 @@
+//! Nullable columns are stored as two columns: the values of the column, where every NULL is
+//! replaced by the default value of the column type, and a companion `BOOLEAN` presence column
+//! that is `true` exactly where the value is not NULL.
+//!
+//! The presence column is committed to like any other column, so the verifier can hold the
+//! prover to the NULLs in the data. Columns without a presence column never contain NULLs.
+use super::{ColumnRef, ColumnType};
+use alloc::format;
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the name of a column to get the name of its presence column.
+///
+/// Since `$` can not appear in an identifier of a query, presence columns can not be referenced
+/// directly by a query.
+pub const PRESENCE_SUFFIX: &str = "$presence";
+
+/// The name of the presence column of the column named `ident`.
+#[must_use]
+pub fn presence_ident(ident: &Ident) -> Ident {
+    Ident::new(format!("{}{PRESENCE_SUFFIX}", ident.value))
+}
+
+/// Whether `ident` is the name of a presence column.
+#[must_use]
+pub fn is_presence_ident(ident: &Ident) -> bool {
+    ident.value.ends_with(PRESENCE_SUFFIX)
+}
+
+/// The presence column of the column referenced by `column_ref`.
+#[must_use]
+pub fn presence_column_ref(column_ref: &ColumnRef) -> ColumnRef {
+    ColumnRef::new(
+        column_ref.table_ref(),
+        presence_ident(&column_ref.column_id()),
+        ColumnType::Boolean,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_get_the_presence_column_of_a_column() {
+        let ident = Ident::new("a");
+        let presence = presence_ident(&ident);
+        assert_eq!(presence.value, "a$presence");
+        assert!(is_presence_ident(&presence));
+        assert!(!is_presence_ident(&ident));
+
+        let column_ref = ColumnRef::new("sxt.t".parse().unwrap(), ident, ColumnType::BigInt);
+        assert_eq!(
+            presence_column_ref(&column_ref),
+            ColumnRef::new("sxt.t".parse().unwrap(), presence, ColumnType::Boolean)
+        );
+    }
+}
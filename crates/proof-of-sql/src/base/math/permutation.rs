@@ -70,6 +70,11 @@ impl Permutation {
         self.permutation.len()
     }
 
+    /// Get the permutation as a slice of indexes
+    pub(crate) fn as_slice(&self) -> &[usize] {
+        &self.permutation
+    }
+
     /// Apply the permutation to the given slice
     pub fn try_apply<T>(&self, slice: &[T]) -> Result<Vec<T>, PermutationError>
     where
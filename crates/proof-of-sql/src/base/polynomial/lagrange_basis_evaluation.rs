@@ -118,3 +118,51 @@ where
         }
     }
 }
+
+/// Given the point `point` (or `a`) with length nu, we can evaluate the lagrange basis of length 2^nu at that point.
+/// This is what [`super::compute_evaluation_vector`] does.
+/// Call the resulting evaluation vector A. This function computes `sum i * A[i] for i in 0..length`.
+/// In other words, this is the evaluation of the MLE of the row index column `0, 1, ..., length - 1`.
+/// ```text
+/// 0 * (1-a[0])(1-a[1])...(1-a[nu-1]) +
+/// 1 * (a[0])(1-a[1])...(1-a[nu-1]) +
+/// 2 * (1-a[0])(a[1])...(1-a[nu-1]) +
+/// 3 * (a[0])(a[1])...(1-a[nu-1]) + ...
+/// ```
+/// # Panics
+/// Panics if:
+/// - The length is greater than `1` when `point` is empty.
+/// - The length is greater than the maximum allowed for the given number of points, which is `2^nu`
+///   where `nu` is the number of elements in `point`.
+pub fn compute_rho_eval<F>(length: usize, point: &[F]) -> F
+where
+    F: One + Zero + Mul<Output = F> + Add<Output = F> + Sub<Output = F> + Copy,
+{
+    let nu = point.len();
+    if nu == 0 {
+        assert!(length <= 1);
+        // The only possible entry is at index 0.
+        F::zero()
+    } else {
+        let first_half_term = F::one() - point[nu - 1];
+        let second_half_term = point[nu - 1];
+        let half_full_length = 1 << (nu - 1);
+        if length >= half_full_length {
+            // The first half is the full row index column over the remaining variables,
+            // which is the linear function `sum 2^k * a[k]`.
+            let (sub_full, half_full_length_as_f) = point[..nu - 1]
+                .iter()
+                .fold((F::zero(), F::one()), |(acc, power), &a| {
+                    (acc + power * a, power + power)
+                });
+            let sub_part_length = length - half_full_length;
+            // Every entry of the second half is offset by `2^(nu-1)`.
+            let sub_part = compute_rho_eval(sub_part_length, &point[..nu - 1])
+                + half_full_length_as_f
+                    * compute_truncated_lagrange_basis_sum(sub_part_length, &point[..nu - 1]);
+            first_half_term * sub_full + second_half_term * sub_part
+        } else {
+            first_half_term * compute_rho_eval(length, &point[..nu - 1])
+        }
+    }
+}
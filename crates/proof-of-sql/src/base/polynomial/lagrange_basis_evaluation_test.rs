@@ -1,7 +1,7 @@
 use crate::base::{
     polynomial::{
-        compute_evaluation_vector, compute_truncated_lagrange_basis_inner_product,
-        compute_truncated_lagrange_basis_sum,
+        compute_evaluation_vector, compute_rho_eval,
+        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
     },
     scalar::test_scalar::TestScalar,
 };
@@ -401,3 +401,52 @@ fn compute_truncated_lagrange_basis_inner_product_matches_inner_product_of_resul
         // -----------------------------------------------------------
     }
 }
+
+#[test]
+fn compute_rho_eval_gives_correct_values_with_2_variables_using_i32() {
+    let point = vec![2, 5];
+    // The evaluation vector is [4, -8, -5, 10]
+    assert_eq!(compute_rho_eval(4, &point), -8 - 10 + 30);
+    assert_eq!(compute_rho_eval(3, &point), -8 - 10);
+    assert_eq!(compute_rho_eval(2, &point), -8);
+    assert_eq!(compute_rho_eval(1, &point), 0);
+    assert_eq!(compute_rho_eval(0, &point), 0);
+}
+
+#[test]
+fn compute_rho_eval_gives_correct_values_with_0_variables() {
+    let point: Vec<TestScalar> = vec![];
+    assert_eq!(compute_rho_eval(1, &point), TestScalar::zero());
+    assert_eq!(compute_rho_eval(0, &point), TestScalar::zero());
+}
+
+#[test]
+fn compute_rho_eval_matches_inner_product_of_row_index_and_result_from_compute_evaluation_vector() {
+    use ark_std::rand::{
+        distributions::{Distribution, Uniform},
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let dist = Uniform::new(1, 10);
+    for _ in 0..20 {
+        let variables = dist.sample(&mut rng);
+        let length = Uniform::new(0, (1 << variables) + 1).sample(&mut rng);
+        let point: Vec<_> = iter::repeat_with(|| TestScalar::rand(&mut rng))
+            .take(variables)
+            .collect();
+        let mut eval_vec = vec![TestScalar::zero(); length];
+        compute_evaluation_vector(&mut eval_vec, &point);
+        // ---------------- This is the actual test --------------------
+        assert_eq!(
+            compute_rho_eval(length, &point),
+            eval_vec
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| TestScalar::from(i as u64) * x)
+                .sum()
+        );
+        // -----------------------------------------------------------
+    }
+}
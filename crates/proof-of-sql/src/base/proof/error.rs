@@ -40,7 +40,16 @@ pub enum ProofSizeMismatch {
     /// This error occurs when the proof has too few bit distributions.
     #[snafu(display("Proof has too few bit distributions"))]
     TooFewBitDistributions,
+    /// This error occurs when the proof has too few division by zero counts.
+    #[snafu(display("Proof has too few division by zero counts"))]
+    TooFewDivisionByZeroCounts,
     /// This error occurs when the proof has too few one lengths.
     #[snafu(display("Proof has too few one lengths"))]
     TooFewOneLengths,
+    /// This error occurs when the proof has too few rho lengths.
+    #[snafu(display("Proof has too few rho lengths"))]
+    TooFewRhoLengths,
+    /// This error occurs when the proof has elements that the proof plan never consumes.
+    #[snafu(display("Proof has elements that the proof plan does not consume"))]
+    UnconsumedProofElements,
 }
@@ -0,0 +1,443 @@
+use super::dyn_proof_plan_deserializer::{DynProofPlanDeserializer, ProofPlanDeserializationError};
+use crate::{
+    base::{
+        database::{ColumnRef, TableRef},
+        map::IndexSet,
+        scalar::Scalar,
+    },
+    sql::proof_plans::DynProofPlan,
+};
+
+/// Deserializes a `DynProofPlan` from the bytes that `serialize_query_expr` serialized it into.
+///
+/// The table and column references have to be the ones that the plan references, in the same order, i.e.
+/// `plan.get_table_references()` and `plan.get_column_references()`.
+///
+/// # Arguments
+///
+/// * `bytes` - The serialized plan.
+/// * `table_refs` - The table references of the plan.
+/// * `column_refs` - The column references of the plan.
+///
+/// # Returns
+///
+/// * `Ok(DynProofPlan)` - The deserialized plan.
+/// * `Err(ProofPlanDeserializationError)` - An error indicating why the deserialization failed.
+///
+/// # Errors
+///
+/// This function returns an error if `bytes` is not exactly a serialized plan with the given references.
+pub fn deserialize_proof_plan<S: Scalar>(
+    bytes: &[u8],
+    table_refs: IndexSet<TableRef>,
+    column_refs: IndexSet<ColumnRef>,
+) -> Result<DynProofPlan, ProofPlanDeserializationError> {
+    let mut deserializer = DynProofPlanDeserializer::<S>::new(table_refs, column_refs, bytes);
+    let plan = deserializer.deserialize_dyn_proof_plan()?;
+    deserializer.finish()?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        base::{
+            database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
+            map::indexset,
+            math::{decimal::Precision, i256::I256},
+            scalar::test_scalar::TestScalar,
+        },
+        evm_compatibility::{
+            deserialize_proof_plan, dyn_proof_plan_deserializer::ProofPlanDeserializationError,
+            serialize_query_expr,
+        },
+        sql::{
+            parse::QueryExpr,
+            proof::ProofPlan,
+            proof_exprs::{
+                AddIntervalExpr, AddSubtractExpr, AggregateExpr, AliasedDynProofExpr, AndExpr,
+                CaseExpr, CastExpr, ColumnExpr, DateTruncExpr, DivideAndModuloExpr, DynProofExpr,
+                EqualsExpr, ExtractExpr, InListExpr, InequalityExpr, LiteralExpr, MultiplyExpr,
+                NotExpr, OrExpr, TableExpr,
+            },
+            proof_plans::{
+                AggregateExec, DistinctExec, DynProofPlan, EmptyExec, FilterExec, GroupByExec,
+                IntersectExec, JoinExec, OrderByExec, ProjectionExec, SemiJoinExec, SliceExec,
+                SubqueryExec, TableExec, UnionExec, WindowExec, WindowFunction,
+            },
+        },
+    };
+    use proof_of_sql_parser::{
+        intermediate_ast::{AggregationOperator, DateTimeField, OrderByDirection, Percentile},
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    };
+
+    fn table_ref() -> TableRef {
+        "namespace.table".parse().unwrap()
+    }
+
+    fn column(name: &str, column_type: ColumnType) -> DynProofExpr {
+        DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+            table_ref(),
+            name.into(),
+            column_type,
+        )))
+    }
+
+    fn literal(value: LiteralValue) -> DynProofExpr {
+        DynProofExpr::Literal(LiteralExpr::new(value))
+    }
+
+    fn aliased(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+        AliasedDynProofExpr {
+            expr,
+            alias: alias.into(),
+        }
+    }
+
+    fn table_exec() -> DynProofPlan {
+        DynProofPlan::Table(TableExec::new(
+            table_ref(),
+            vec![
+                ColumnField::new("a".into(), ColumnType::BigInt),
+                ColumnField::new("b".into(), ColumnType::Int),
+            ],
+        ))
+    }
+
+    fn projection_exec(exprs: Vec<DynProofExpr>) -> DynProofPlan {
+        DynProofPlan::Projection(ProjectionExec::new(
+            exprs
+                .into_iter()
+                .enumerate()
+                .map(|(i, expr)| aliased(expr, &format!("alias_{i}")))
+                .collect(),
+            TableExpr {
+                table_ref: table_ref(),
+            },
+        ))
+    }
+
+    fn serialize(plan: &DynProofPlan) -> Vec<u8> {
+        serialize_query_expr::<TestScalar>(&QueryExpr::new(plan.clone(), vec![])).unwrap()
+    }
+
+    fn deserialize(
+        plan: &DynProofPlan,
+        bytes: &[u8],
+    ) -> Result<DynProofPlan, ProofPlanDeserializationError> {
+        deserialize_proof_plan::<TestScalar>(
+            bytes,
+            plan.get_table_references(),
+            plan.get_column_references(),
+        )
+    }
+
+    fn assert_round_trip(plan: &DynProofPlan) {
+        let bytes = serialize(plan);
+        assert_eq!(&deserialize(plan, &bytes).unwrap(), plan);
+    }
+
+    fn a() -> DynProofExpr {
+        column("a", ColumnType::BigInt)
+    }
+
+    fn b() -> DynProofExpr {
+        column("b", ColumnType::Int)
+    }
+
+    fn where_clause() -> DynProofExpr {
+        DynProofExpr::Equals(EqualsExpr::new(
+            Box::new(a()),
+            Box::new(literal(LiteralValue::BigInt(5))),
+        ))
+    }
+
+    fn table() -> TableExpr {
+        TableExpr {
+            table_ref: table_ref(),
+        }
+    }
+
+    #[test]
+    fn we_can_round_trip_every_plan_over_a_table() {
+        assert_round_trip(&DynProofPlan::Empty(EmptyExec::new()));
+        assert_round_trip(&table_exec());
+        assert_round_trip(&DynProofPlan::Filter(FilterExec::new(
+            vec![aliased(a(), "x"), aliased(b(), "y")],
+            table(),
+            where_clause(),
+        )));
+        assert_round_trip(&projection_exec(vec![a(), b()]));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_aggregating_plan() {
+        assert_round_trip(&DynProofPlan::GroupBy(GroupByExec::new(
+            vec![aliased(b(), "b")],
+            vec![aliased(a(), "sum_a")],
+            "__count__".into(),
+            table(),
+            where_clause(),
+        )));
+        assert_round_trip(&DynProofPlan::Aggregate(AggregateExec::new_with_presences(
+            vec![
+                aliased(
+                    DynProofExpr::Aggregate(AggregateExpr::new(
+                        AggregationOperator::Sum,
+                        Box::new(a()),
+                    )),
+                    "sum_a",
+                ),
+                aliased(
+                    DynProofExpr::Aggregate(AggregateExpr::new(
+                        AggregationOperator::Max,
+                        Box::new(a()),
+                    )),
+                    "max_a",
+                ),
+            ],
+            vec![None, Some(where_clause())],
+            table(),
+            where_clause(),
+        )));
+        assert_round_trip(&DynProofPlan::Distinct(DistinctExec::new(Box::new(
+            table_exec(),
+        ))));
+        assert_round_trip(&DynProofPlan::Window(WindowExec::new(
+            Box::new(table_exec()),
+            vec![1],
+            vec![(0, OrderByDirection::Desc)],
+            vec![
+                (WindowFunction::RowNumber, "row_number".into()),
+                (WindowFunction::Rank, "rank".into()),
+                (WindowFunction::Sum(0), "sum".into()),
+            ],
+        )));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_plan_over_a_single_input_plan() {
+        assert_round_trip(&DynProofPlan::Slice(SliceExec::new(
+            Box::new(table_exec()),
+            1,
+            Some(2),
+        )));
+        assert_round_trip(&DynProofPlan::Slice(SliceExec::new(
+            Box::new(table_exec()),
+            3,
+            None,
+        )));
+        assert_round_trip(&DynProofPlan::OrderBy(OrderByExec::new(
+            Box::new(table_exec()),
+            vec![(1, OrderByDirection::Desc), (0, OrderByDirection::Asc)],
+        )));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_plan_over_several_input_plans() {
+        assert_round_trip(&DynProofPlan::Union(UnionExec::new(
+            vec![table_exec(), projection_exec(vec![a(), b()])],
+            vec![
+                ColumnField::new("a".into(), ColumnType::BigInt),
+                ColumnField::new("b".into(), ColumnType::Int),
+            ],
+        )));
+        assert_round_trip(&DynProofPlan::Join(JoinExec::new(
+            Box::new(table_exec()),
+            Box::new(projection_exec(vec![a()])),
+            0,
+            0,
+            vec!["a".into(), "b".into()],
+        )));
+        assert_round_trip(&DynProofPlan::SemiJoin(SemiJoinExec::new(
+            Box::new(table_exec()),
+            Box::new(projection_exec(vec![a()])),
+            0,
+            0,
+            true,
+        )));
+        assert_round_trip(&DynProofPlan::Intersect(IntersectExec::new(
+            Box::new(table_exec()),
+            Box::new(projection_exec(vec![a(), b()])),
+            false,
+        )));
+    }
+
+    #[test]
+    fn we_can_round_trip_a_subquery_exec() {
+        let alias: TableRef = "sub.query".parse().unwrap();
+        let input = projection_exec(vec![column("a", ColumnType::BigInt)]);
+        let plan = DynProofPlan::Subquery(SubqueryExec::new(
+            Box::new(input),
+            alias,
+            Box::new(DynProofPlan::Filter(FilterExec::new(
+                vec![aliased(
+                    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+                        alias,
+                        "alias_0".into(),
+                        ColumnType::BigInt,
+                    ))),
+                    "a",
+                )],
+                TableExpr { table_ref: alias },
+                literal(LiteralValue::Boolean(true)),
+            ))),
+        ));
+        assert!(!plan.get_table_references().contains(&alias));
+        assert_round_trip(&plan);
+    }
+
+    fn timestamp_column() -> DynProofExpr {
+        column(
+            "t",
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        )
+    }
+
+    #[test]
+    fn we_can_round_trip_every_expr() {
+        let a = || Box::new(a());
+        let c = || Box::new(column("c", ColumnType::Boolean));
+        let one = || Box::new(literal(LiteralValue::BigInt(1)));
+        assert_round_trip(&projection_exec(vec![
+            *a(),
+            *one(),
+            DynProofExpr::Equals(EqualsExpr::new(a(), one())),
+            DynProofExpr::And(AndExpr::new(c(), c())),
+            DynProofExpr::Or(OrExpr::new(c(), c())),
+            DynProofExpr::Not(NotExpr::new(c())),
+            DynProofExpr::Inequality(InequalityExpr::new(a(), one(), true, false)),
+            DynProofExpr::Inequality(InequalityExpr::new(a(), one(), false, true)),
+            DynProofExpr::InList(InListExpr::new(
+                a(),
+                vec![LiteralValue::BigInt(1), LiteralValue::BigInt(-2)],
+            )),
+            DynProofExpr::Case(CaseExpr::new(c(), a(), one())),
+            DynProofExpr::AddSubtract(AddSubtractExpr::new(a(), one(), true)),
+            DynProofExpr::Multiply(MultiplyExpr::new(a(), one())),
+            DynProofExpr::DivideAndModulo(DivideAndModuloExpr::new(a(), one(), true)),
+            DynProofExpr::Cast(CastExpr::new(
+                a(),
+                ColumnType::Decimal75(Precision::new(30).unwrap(), -2),
+            )),
+        ]));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_aggregate_expr() {
+        let percentile = Percentile::try_new(25, 2).unwrap();
+        let aggregations = [
+            AggregationOperator::Max,
+            AggregationOperator::Min,
+            AggregationOperator::Sum,
+            AggregationOperator::Avg,
+            AggregationOperator::Count,
+            AggregationOperator::CountDistinct,
+            AggregationOperator::First,
+            AggregationOperator::Median,
+            AggregationOperator::PercentileCont(percentile),
+            AggregationOperator::PercentileDisc(percentile),
+        ]
+        .into_iter()
+        .map(|op| DynProofExpr::Aggregate(AggregateExpr::new(op, Box::new(a()))));
+        assert_round_trip(&projection_exec(aggregations.collect()));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_timestamp_expr() {
+        let fields = [
+            DateTimeField::Year,
+            DateTimeField::Quarter,
+            DateTimeField::Week,
+            DateTimeField::Day,
+            DateTimeField::DayOfYear,
+            DateTimeField::Hour,
+            DateTimeField::Minute,
+            DateTimeField::Second,
+            DateTimeField::Millisecond,
+            DateTimeField::Microsecond,
+            DateTimeField::Nanosecond,
+        ]
+        .into_iter()
+        .map(|field| DynProofExpr::Extract(ExtractExpr::new(field, Box::new(timestamp_column()))));
+        let exprs = [
+            DynProofExpr::DateTrunc(DateTruncExpr::new(
+                DateTimeField::Month,
+                Box::new(timestamp_column()),
+            )),
+            DynProofExpr::Extract(ExtractExpr::new(
+                DateTimeField::DayOfWeek,
+                Box::new(timestamp_column()),
+            )),
+            DynProofExpr::AddInterval(AddIntervalExpr::new(Box::new(timestamp_column()), -86_400)),
+        ];
+        assert_round_trip(&projection_exec(exprs.into_iter().chain(fields).collect()));
+    }
+
+    #[test]
+    fn we_can_round_trip_every_literal_and_column_type() {
+        let time_zone = PoSQLTimeZone::new(-18_000);
+        let values = vec![
+            LiteralValue::Boolean(true),
+            LiteralValue::TinyInt(-8),
+            LiteralValue::SmallInt(-16),
+            LiteralValue::Int(-32),
+            LiteralValue::BigInt(i64::MIN),
+            LiteralValue::Int128(i128::MAX),
+            LiteralValue::VarChar("hello world".to_string()),
+            LiteralValue::Decimal75(Precision::new(75).unwrap(), -12, I256::from(-123_456_789)),
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Nanosecond, time_zone, -1),
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Microsecond, time_zone, 2),
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Millisecond, time_zone, 3),
+            LiteralValue::Scalar([1, 2, 3, 4]),
+        ];
+        let columns = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| column(&format!("column_{i}"), value.column_type()));
+        let exprs = values.iter().cloned().map(literal).chain(columns).collect();
+        assert_round_trip(&projection_exec(exprs));
+    }
+
+    #[test]
+    fn we_cannot_deserialize_invalid_data() {
+        let plan = projection_exec(vec![column("a", ColumnType::BigInt)]);
+        let bytes = serialize(&plan);
+
+        assert!(matches!(
+            deserialize(&plan, &bytes[..bytes.len() - 1]),
+            Err(ProofPlanDeserializationError::UnexpectedEndOfData)
+        ));
+        assert!(matches!(
+            deserialize(&plan, &[bytes.as_slice(), &[0]].concat()),
+            Err(ProofPlanDeserializationError::TrailingData)
+        ));
+        assert!(matches!(
+            deserialize(&plan, &[u8::MAX]),
+            Err(ProofPlanDeserializationError::UnknownPlan { number: u8::MAX })
+        ));
+        assert!(matches!(
+            deserialize_proof_plan::<TestScalar>(&bytes, indexset! {}, indexset! {}),
+            Err(ProofPlanDeserializationError::TableNotFound)
+        ));
+        assert!(matches!(
+            deserialize_proof_plan::<TestScalar>(&bytes, plan.get_table_references(), indexset! {}),
+            Err(ProofPlanDeserializationError::ColumnNotFound)
+        ));
+    }
+
+    #[test]
+    fn we_cannot_deserialize_a_non_canonical_scalar() {
+        let plan = projection_exec(vec![literal(LiteralValue::Scalar([0, 0, 0, 0]))]);
+        let mut bytes = serialize(&plan);
+        // The scalar is followed by the alias, so the 32 bytes before it are the scalar.
+        let alias_length = 8 + "alias_0".len();
+        let scalar_start = bytes.len() - alias_length - 32;
+        bytes[scalar_start..scalar_start + 32].fill(u8::MAX);
+        assert!(matches!(
+            deserialize(&plan, &bytes),
+            Err(ProofPlanDeserializationError::InvalidValue)
+        ));
+    }
+}
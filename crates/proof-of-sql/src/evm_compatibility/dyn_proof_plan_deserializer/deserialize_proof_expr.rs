@@ -0,0 +1,218 @@
+use super::{
+    error::{InvalidValueSnafu, UnknownExprSnafu},
+    DynProofPlanDeserializer, ProofPlanDeserializationError,
+};
+use crate::{
+    base::{
+        database::{ColumnType, LiteralValue},
+        math::i256::I256,
+        scalar::Scalar,
+    },
+    evm_compatibility::dyn_proof_plan_serializer::constants::{
+        ADD_INTERVAL_EXPR_NUM, ADD_SUBTRACT_EXPR_NUM, AGGREGATE_EXPR_NUM, AND_EXPR_NUM,
+        AVG_AGGREGATION_NUM, CASE_EXPR_NUM, CAST_EXPR_NUM, COLUMN_EXPR_NUM, COUNT_AGGREGATION_NUM,
+        COUNT_DISTINCT_AGGREGATION_NUM, DATE_TRUNC_EXPR_NUM, DAY_FIELD_NUM, DAY_OF_WEEK_FIELD_NUM,
+        DAY_OF_YEAR_FIELD_NUM, DIVIDE_AND_MODULO_EXPR_NUM, EQUALS_EXPR_NUM, EXTRACT_EXPR_NUM,
+        FIRST_AGGREGATION_NUM, HOUR_FIELD_NUM, INEQUALITY_EXPR_NUM, IN_LIST_EXPR_NUM,
+        LITERAL_EXPR_NUM, MAX_AGGREGATION_NUM, MEDIAN_AGGREGATION_NUM, MICROSECOND_FIELD_NUM,
+        MILLISECOND_FIELD_NUM, MINUTE_FIELD_NUM, MIN_AGGREGATION_NUM, MONTH_FIELD_NUM,
+        MULTIPLY_EXPR_NUM, NANOSECOND_FIELD_NUM, NOT_EXPR_NUM, OR_EXPR_NUM,
+        PERCENTILE_CONT_AGGREGATION_NUM, PERCENTILE_DISC_AGGREGATION_NUM, QUARTER_FIELD_NUM,
+        SECOND_FIELD_NUM, SUM_AGGREGATION_NUM, WEEK_FIELD_NUM, YEAR_FIELD_NUM,
+    },
+    sql::proof_exprs::{
+        AddIntervalExpr, AddSubtractExpr, AggregateExpr, AndExpr, CaseExpr, CastExpr, ColumnExpr,
+        DateTruncExpr, DivideAndModuloExpr, DynProofExpr, EqualsExpr, ExtractExpr, InListExpr,
+        InequalityExpr, LiteralExpr, MultiplyExpr, NotExpr, OrExpr,
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, DateTimeField, Percentile};
+use snafu::OptionExt;
+
+impl<S: Scalar> DynProofPlanDeserializer<'_, S> {
+    pub(super) fn deserialize_dyn_proof_expr(
+        &mut self,
+    ) -> Result<DynProofExpr, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_u8()? {
+            COLUMN_EXPR_NUM => {
+                DynProofExpr::Column(ColumnExpr::new(self.deserialize_column_ref()?))
+            }
+            LITERAL_EXPR_NUM => {
+                DynProofExpr::Literal(LiteralExpr::new(self.deserialize_literal_value()?))
+            }
+            EQUALS_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                DynProofExpr::Equals(EqualsExpr::new(lhs, rhs))
+            }
+            AND_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                DynProofExpr::And(AndExpr::new(lhs, rhs))
+            }
+            OR_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                DynProofExpr::Or(OrExpr::new(lhs, rhs))
+            }
+            NOT_EXPR_NUM => {
+                DynProofExpr::Not(NotExpr::new(self.deserialize_boxed_dyn_proof_expr()?))
+            }
+            INEQUALITY_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                let is_lt = self.deserialize_bool()?;
+                let is_strict = self.deserialize_bool()?;
+                DynProofExpr::Inequality(InequalityExpr::new(lhs, rhs, is_lt, is_strict))
+            }
+            IN_LIST_EXPR_NUM => {
+                let expr = self.deserialize_boxed_dyn_proof_expr()?;
+                let length = self.deserialize_length()?;
+                let list = (0..length)
+                    .map(|_| self.deserialize_literal_value())
+                    .collect::<Result<Vec<_>, _>>()?;
+                DynProofExpr::InList(InListExpr::new(expr, list))
+            }
+            CASE_EXPR_NUM => {
+                let condition = self.deserialize_boxed_dyn_proof_expr()?;
+                let (then_result, else_result) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                DynProofExpr::Case(CaseExpr::new(condition, then_result, else_result))
+            }
+            ADD_SUBTRACT_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                let is_subtract = self.deserialize_bool()?;
+                DynProofExpr::AddSubtract(AddSubtractExpr::new(lhs, rhs, is_subtract))
+            }
+            MULTIPLY_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                DynProofExpr::Multiply(MultiplyExpr::new(lhs, rhs))
+            }
+            DIVIDE_AND_MODULO_EXPR_NUM => {
+                let (lhs, rhs) = self.deserialize_boxed_dyn_proof_expr_pair()?;
+                let is_modulo = self.deserialize_bool()?;
+                DynProofExpr::DivideAndModulo(DivideAndModuloExpr::new(lhs, rhs, is_modulo))
+            }
+            CAST_EXPR_NUM => {
+                let from_expr = self.deserialize_boxed_dyn_proof_expr()?;
+                let to_type = self.deserialize_column_type()?;
+                DynProofExpr::Cast(CastExpr::new(from_expr, to_type))
+            }
+            DATE_TRUNC_EXPR_NUM => {
+                let field = self.deserialize_date_time_field()?;
+                let expr = self.deserialize_boxed_dyn_proof_expr()?;
+                DynProofExpr::DateTrunc(DateTruncExpr::new(field, expr))
+            }
+            EXTRACT_EXPR_NUM => {
+                let field = self.deserialize_date_time_field()?;
+                let expr = self.deserialize_boxed_dyn_proof_expr()?;
+                DynProofExpr::Extract(ExtractExpr::new(field, expr))
+            }
+            ADD_INTERVAL_EXPR_NUM => {
+                let expr = self.deserialize_boxed_dyn_proof_expr()?;
+                let units = self.deserialize_integer()?;
+                DynProofExpr::AddInterval(AddIntervalExpr::new(expr, units))
+            }
+            AGGREGATE_EXPR_NUM => {
+                let op = self.deserialize_aggregation_operator()?;
+                let expr = self.deserialize_boxed_dyn_proof_expr()?;
+                DynProofExpr::Aggregate(AggregateExpr::new(op, expr))
+            }
+            number => UnknownExprSnafu { number }.fail()?,
+        })
+    }
+
+    fn deserialize_boxed_dyn_proof_expr(
+        &mut self,
+    ) -> Result<Box<DynProofExpr>, ProofPlanDeserializationError> {
+        Ok(Box::new(self.deserialize_dyn_proof_expr()?))
+    }
+
+    fn deserialize_boxed_dyn_proof_expr_pair(
+        &mut self,
+    ) -> Result<(Box<DynProofExpr>, Box<DynProofExpr>), ProofPlanDeserializationError> {
+        let lhs = self.deserialize_boxed_dyn_proof_expr()?;
+        Ok((lhs, self.deserialize_boxed_dyn_proof_expr()?))
+    }
+
+    /// Deserializes a scalar as an integer type that the scalar has to fit into.
+    fn deserialize_integer<T>(&mut self) -> Result<T, ProofPlanDeserializationError>
+    where
+        S: TryInto<T>,
+    {
+        self.deserialize_scalar()?
+            .try_into()
+            .ok()
+            .context(InvalidValueSnafu)
+    }
+
+    pub(super) fn deserialize_literal_value(
+        &mut self,
+    ) -> Result<LiteralValue, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_column_type()? {
+            ColumnType::Boolean => LiteralValue::Boolean(self.deserialize_integer()?),
+            ColumnType::TinyInt => LiteralValue::TinyInt(self.deserialize_integer()?),
+            ColumnType::SmallInt => LiteralValue::SmallInt(self.deserialize_integer()?),
+            ColumnType::Int => LiteralValue::Int(self.deserialize_integer()?),
+            ColumnType::BigInt => LiteralValue::BigInt(self.deserialize_integer()?),
+            ColumnType::Int128 => LiteralValue::Int128(self.deserialize_integer()?),
+            ColumnType::VarChar => LiteralValue::VarChar(self.deserialize_string()?),
+            ColumnType::Decimal75(precision, scale) => LiteralValue::Decimal75(
+                precision,
+                scale,
+                I256::from_num_bigint(&self.deserialize_scalar()?.into()),
+            ),
+            ColumnType::TimestampTZ(time_unit, time_zone) => {
+                LiteralValue::TimeStampTZ(time_unit, time_zone, self.deserialize_integer()?)
+            }
+            ColumnType::Scalar => LiteralValue::Scalar(self.deserialize_scalar()?.into()),
+        })
+    }
+
+    fn deserialize_date_time_field(
+        &mut self,
+    ) -> Result<DateTimeField, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_u8()? {
+            YEAR_FIELD_NUM => DateTimeField::Year,
+            QUARTER_FIELD_NUM => DateTimeField::Quarter,
+            MONTH_FIELD_NUM => DateTimeField::Month,
+            WEEK_FIELD_NUM => DateTimeField::Week,
+            DAY_FIELD_NUM => DateTimeField::Day,
+            DAY_OF_WEEK_FIELD_NUM => DateTimeField::DayOfWeek,
+            DAY_OF_YEAR_FIELD_NUM => DateTimeField::DayOfYear,
+            HOUR_FIELD_NUM => DateTimeField::Hour,
+            MINUTE_FIELD_NUM => DateTimeField::Minute,
+            SECOND_FIELD_NUM => DateTimeField::Second,
+            MILLISECOND_FIELD_NUM => DateTimeField::Millisecond,
+            MICROSECOND_FIELD_NUM => DateTimeField::Microsecond,
+            NANOSECOND_FIELD_NUM => DateTimeField::Nanosecond,
+            _ => InvalidValueSnafu.fail()?,
+        })
+    }
+
+    fn deserialize_aggregation_operator(
+        &mut self,
+    ) -> Result<AggregationOperator, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_u8()? {
+            MAX_AGGREGATION_NUM => AggregationOperator::Max,
+            MIN_AGGREGATION_NUM => AggregationOperator::Min,
+            SUM_AGGREGATION_NUM => AggregationOperator::Sum,
+            AVG_AGGREGATION_NUM => AggregationOperator::Avg,
+            COUNT_AGGREGATION_NUM => AggregationOperator::Count,
+            COUNT_DISTINCT_AGGREGATION_NUM => AggregationOperator::CountDistinct,
+            FIRST_AGGREGATION_NUM => AggregationOperator::First,
+            MEDIAN_AGGREGATION_NUM => AggregationOperator::Median,
+            PERCENTILE_CONT_AGGREGATION_NUM => {
+                AggregationOperator::PercentileCont(self.deserialize_percentile()?)
+            }
+            PERCENTILE_DISC_AGGREGATION_NUM => {
+                AggregationOperator::PercentileDisc(self.deserialize_percentile()?)
+            }
+            _ => InvalidValueSnafu.fail()?,
+        })
+    }
+
+    fn deserialize_percentile(&mut self) -> Result<Percentile, ProofPlanDeserializationError> {
+        let numerator = self.deserialize_u64()?;
+        let scale = self.deserialize_u8()?;
+        Percentile::try_new(numerator, scale)
+            .ok()
+            .context(InvalidValueSnafu)
+    }
+}
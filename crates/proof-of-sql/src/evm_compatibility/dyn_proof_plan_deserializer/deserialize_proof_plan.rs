@@ -0,0 +1,262 @@
+use super::{
+    error::{InvalidValueSnafu, UnknownPlanSnafu},
+    DynProofPlanDeserializer, ProofPlanDeserializationError,
+};
+use crate::{
+    base::{database::ColumnField, scalar::Scalar},
+    evm_compatibility::dyn_proof_plan_serializer::constants::{
+        AGGREGATE_EXEC_NUM, ASC_DIRECTION_NUM, DESC_DIRECTION_NUM, DISTINCT_EXEC_NUM,
+        EMPTY_EXEC_NUM, FILTER_EXEC_NUM, GROUP_BY_EXEC_NUM, INTERSECT_EXEC_NUM, JOIN_EXEC_NUM,
+        ORDER_BY_EXEC_NUM, PROJECTION_EXEC_NUM, RANK_WINDOW_FUNCTION_NUM,
+        ROW_NUMBER_WINDOW_FUNCTION_NUM, SEMI_JOIN_EXEC_NUM, SLICE_EXEC_NUM, SUBQUERY_EXEC_NUM,
+        SUM_WINDOW_FUNCTION_NUM, TABLE_EXEC_NUM, UNION_EXEC_NUM, WINDOW_EXEC_NUM,
+    },
+    sql::{
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{
+            AggregateExec, DistinctExec, DynProofPlan, EmptyExec, FilterExec, GroupByExec,
+            IntersectExec, JoinExec, OrderByExec, ProjectionExec, SemiJoinExec, SliceExec,
+            SubqueryExec, TableExec, UnionExec, WindowExec, WindowFunction,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use snafu::OptionExt;
+
+impl<S: Scalar> DynProofPlanDeserializer<'_, S> {
+    /// Deserializes a plan.
+    ///
+    /// The plan is not checked beyond what is needed to build it, just like when it is deserialized with `serde`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DynProofPlan, ProofPlanDeserializationError>` - The plan or an error if the data is not a serialized plan.
+    #[allow(clippy::too_many_lines)]
+    pub fn deserialize_dyn_proof_plan(
+        &mut self,
+    ) -> Result<DynProofPlan, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_u8()? {
+            FILTER_EXEC_NUM => {
+                let table = self.deserialize_table_expr()?;
+                let aliased_results = self.deserialize_aliased_dyn_proof_exprs()?;
+                let where_clause = self.deserialize_dyn_proof_expr()?;
+                DynProofPlan::Filter(FilterExec::new(aliased_results, table, where_clause))
+            }
+            EMPTY_EXEC_NUM => DynProofPlan::Empty(EmptyExec::new()),
+            TABLE_EXEC_NUM => {
+                let table_ref = self.deserialize_table_ref()?;
+                let length = self.deserialize_length()?;
+                let schema = (0..length)
+                    .map(|_| {
+                        self.deserialize_column_ref().map(|column_ref| {
+                            ColumnField::new(column_ref.column_id(), *column_ref.column_type())
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                DynProofPlan::Table(TableExec::new(table_ref, schema))
+            }
+            PROJECTION_EXEC_NUM => {
+                let table = self.deserialize_table_expr()?;
+                let aliased_results = self.deserialize_aliased_dyn_proof_exprs()?;
+                DynProofPlan::Projection(ProjectionExec::new(aliased_results, table))
+            }
+            GROUP_BY_EXEC_NUM => {
+                let table = self.deserialize_table_expr()?;
+                let group_by_exprs = self.deserialize_aliased_dyn_proof_exprs()?;
+                let aggregate_exprs = self.deserialize_aliased_dyn_proof_exprs()?;
+                let where_clause = self.deserialize_dyn_proof_expr()?;
+                let count_alias = self.deserialize_ident()?;
+                DynProofPlan::GroupBy(GroupByExec::new(
+                    group_by_exprs,
+                    aggregate_exprs,
+                    count_alias,
+                    table,
+                    where_clause,
+                ))
+            }
+            AGGREGATE_EXEC_NUM => {
+                let table = self.deserialize_table_expr()?;
+                let aggregate_exprs = self.deserialize_aliased_dyn_proof_exprs()?;
+                let presences = aggregate_exprs
+                    .iter()
+                    .map(|_| self.deserialize_optional_dyn_proof_expr())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let where_clause = self.deserialize_dyn_proof_expr()?;
+                DynProofPlan::Aggregate(AggregateExec {
+                    aggregate_exprs,
+                    presences,
+                    table,
+                    where_clause,
+                })
+            }
+            SLICE_EXEC_NUM => {
+                let input = self.deserialize_boxed_dyn_proof_plan()?;
+                let skip = self.deserialize_usize()?;
+                let fetch = if self.deserialize_bool()? {
+                    Some(self.deserialize_usize()?)
+                } else {
+                    None
+                };
+                DynProofPlan::Slice(SliceExec::new(input, skip, fetch))
+            }
+            ORDER_BY_EXEC_NUM => {
+                let input = self.deserialize_boxed_dyn_proof_plan()?;
+                let order_by = self.deserialize_order_by()?;
+                DynProofPlan::OrderBy(OrderByExec { input, order_by })
+            }
+            UNION_EXEC_NUM => {
+                let length = self.deserialize_length()?;
+                let inputs = (0..length)
+                    .map(|_| self.deserialize_dyn_proof_plan())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let length = self.deserialize_length()?;
+                let schema = (0..length)
+                    .map(|_| self.deserialize_column_field())
+                    .collect::<Result<Vec<_>, _>>()?;
+                DynProofPlan::Union(UnionExec::new(inputs, schema))
+            }
+            JOIN_EXEC_NUM => {
+                let left = self.deserialize_boxed_dyn_proof_plan()?;
+                let right = self.deserialize_boxed_dyn_proof_plan()?;
+                let left_join_column_index = self.deserialize_usize()?;
+                let right_join_column_index = self.deserialize_usize()?;
+                let length = self.deserialize_length()?;
+                let result_idents = (0..length)
+                    .map(|_| self.deserialize_ident())
+                    .collect::<Result<Vec<_>, _>>()?;
+                DynProofPlan::Join(JoinExec {
+                    left,
+                    right,
+                    left_join_column_index,
+                    right_join_column_index,
+                    result_idents,
+                })
+            }
+            SEMI_JOIN_EXEC_NUM => {
+                let left = self.deserialize_boxed_dyn_proof_plan()?;
+                let right = self.deserialize_boxed_dyn_proof_plan()?;
+                let left_join_column_index = self.deserialize_usize()?;
+                let right_join_column_index = self.deserialize_usize()?;
+                let negated = self.deserialize_bool()?;
+                DynProofPlan::SemiJoin(SemiJoinExec {
+                    left,
+                    right,
+                    left_join_column_index,
+                    right_join_column_index,
+                    negated,
+                })
+            }
+            SUBQUERY_EXEC_NUM => {
+                let input = self.deserialize_boxed_dyn_proof_plan()?;
+                let alias = self
+                    .deserialize_string()?
+                    .parse()
+                    .ok()
+                    .context(InvalidValueSnafu)?;
+                self.register_alias(alias, &input.get_column_result_fields());
+                let plan = self.deserialize_boxed_dyn_proof_plan()?;
+                DynProofPlan::Subquery(SubqueryExec::new(input, alias, plan))
+            }
+            DISTINCT_EXEC_NUM => DynProofPlan::Distinct(DistinctExec {
+                input: self.deserialize_boxed_dyn_proof_plan()?,
+            }),
+            WINDOW_EXEC_NUM => {
+                let input = self.deserialize_boxed_dyn_proof_plan()?;
+                let length = self.deserialize_length()?;
+                let partition_by = (0..length)
+                    .map(|_| self.deserialize_usize())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let order_by = self.deserialize_order_by()?;
+                let length = self.deserialize_length()?;
+                let functions = (0..length)
+                    .map(|_| {
+                        let function = self.deserialize_window_function()?;
+                        Ok((function, self.deserialize_ident()?))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                DynProofPlan::Window(WindowExec {
+                    input,
+                    partition_by,
+                    order_by,
+                    functions,
+                })
+            }
+            INTERSECT_EXEC_NUM => {
+                let left = self.deserialize_boxed_dyn_proof_plan()?;
+                let right = self.deserialize_boxed_dyn_proof_plan()?;
+                let negated = self.deserialize_bool()?;
+                DynProofPlan::Intersect(IntersectExec {
+                    left,
+                    right,
+                    negated,
+                })
+            }
+            number => UnknownPlanSnafu { number }.fail()?,
+        })
+    }
+
+    fn deserialize_boxed_dyn_proof_plan(
+        &mut self,
+    ) -> Result<Box<DynProofPlan>, ProofPlanDeserializationError> {
+        Ok(Box::new(self.deserialize_dyn_proof_plan()?))
+    }
+
+    fn deserialize_table_expr(&mut self) -> Result<TableExpr, ProofPlanDeserializationError> {
+        Ok(TableExpr {
+            table_ref: self.deserialize_table_ref()?,
+        })
+    }
+
+    fn deserialize_aliased_dyn_proof_exprs(
+        &mut self,
+    ) -> Result<Vec<AliasedDynProofExpr>, ProofPlanDeserializationError> {
+        let length = self.deserialize_length()?;
+        (0..length)
+            .map(|_| {
+                let expr = self.deserialize_dyn_proof_expr()?;
+                Ok(AliasedDynProofExpr {
+                    expr,
+                    alias: self.deserialize_ident()?,
+                })
+            })
+            .collect()
+    }
+
+    fn deserialize_optional_dyn_proof_expr(
+        &mut self,
+    ) -> Result<Option<DynProofExpr>, ProofPlanDeserializationError> {
+        self.deserialize_bool()?
+            .then(|| self.deserialize_dyn_proof_expr())
+            .transpose()
+    }
+
+    fn deserialize_order_by(
+        &mut self,
+    ) -> Result<Vec<(usize, OrderByDirection)>, ProofPlanDeserializationError> {
+        let length = self.deserialize_length()?;
+        (0..length)
+            .map(|_| {
+                let index = self.deserialize_usize()?;
+                let direction = match self.deserialize_u8()? {
+                    ASC_DIRECTION_NUM => OrderByDirection::Asc,
+                    DESC_DIRECTION_NUM => OrderByDirection::Desc,
+                    _ => InvalidValueSnafu.fail()?,
+                };
+                Ok((index, direction))
+            })
+            .collect()
+    }
+
+    fn deserialize_window_function(
+        &mut self,
+    ) -> Result<WindowFunction, ProofPlanDeserializationError> {
+        Ok(match self.deserialize_u8()? {
+            ROW_NUMBER_WINDOW_FUNCTION_NUM => WindowFunction::RowNumber,
+            RANK_WINDOW_FUNCTION_NUM => WindowFunction::Rank,
+            SUM_WINDOW_FUNCTION_NUM => WindowFunction::Sum(self.deserialize_usize()?),
+            _ => InvalidValueSnafu.fail()?,
+        })
+    }
+}
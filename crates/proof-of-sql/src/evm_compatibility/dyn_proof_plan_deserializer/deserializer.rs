@@ -0,0 +1,215 @@
+use super::{
+    error::{
+        ColumnNotFoundSnafu, InvalidValueSnafu, TableNotFoundSnafu, TrailingDataSnafu,
+        UnexpectedEndOfDataSnafu, UnknownTypeSnafu,
+    },
+    ProofPlanDeserializationError,
+};
+use crate::{
+    base::{
+        database::{ColumnField, ColumnRef, ColumnType, TableRef},
+        map::IndexSet,
+        math::decimal::Precision,
+        scalar::Scalar,
+    },
+    evm_compatibility::dyn_proof_plan_serializer::constants::{
+        BIGINT_TYPE_NUM, BOOLEAN_TYPE_NUM, DECIMAL75_TYPE_NUM, INT128_TYPE_NUM, INT_TYPE_NUM,
+        MICROSECOND_TIME_UNIT_NUM, MILLISECOND_TIME_UNIT_NUM, NANOSECOND_TIME_UNIT_NUM,
+        SCALAR_TYPE_NUM, SECOND_TIME_UNIT_NUM, SMALLINT_TYPE_NUM, TIMESTAMPTZ_TYPE_NUM,
+        TINYINT_TYPE_NUM, VARCHAR_TYPE_NUM,
+    },
+};
+use alloc::string::String;
+use core::marker::PhantomData;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+use snafu::{ensure, OptionExt};
+use sqlparser::ast::Ident;
+
+/// A deserializer for a `DynProofPlan` serialized by a `DynProofPlanSerializer`.
+#[derive(Debug, Clone)]
+pub struct DynProofPlanDeserializer<'a, S: Scalar> {
+    pub(super) table_refs: IndexSet<TableRef>,
+    pub(super) column_refs: IndexSet<ColumnRef>,
+    data: &'a [u8],
+    _phantom: PhantomData<S>,
+}
+
+impl<'a, S: Scalar> DynProofPlanDeserializer<'a, S> {
+    /// Creates a new deserializer of `data` with the table and column references the plan was serialized with.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_refs` - A set of table references.
+    /// * `column_refs` - A set of column references.
+    /// * `data` - The serialized plan.
+    #[must_use]
+    pub fn new(
+        table_refs: IndexSet<TableRef>,
+        column_refs: IndexSet<ColumnRef>,
+        data: &'a [u8],
+    ) -> Self {
+        Self {
+            table_refs,
+            column_refs,
+            data,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Checks that all of the data has been deserialized.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), ProofPlanDeserializationError>` - An error if there is data left.
+    pub fn finish(self) -> Result<(), ProofPlanDeserializationError> {
+        ensure!(self.data.is_empty(), TrailingDataSnafu);
+        Ok(())
+    }
+
+    /// Registers the derived table `alias` with the columns `fields`, just like the serializer did.
+    pub(super) fn register_alias(&mut self, alias: TableRef, fields: &[ColumnField]) {
+        self.table_refs.insert(alias);
+        self.column_refs.extend(
+            fields
+                .iter()
+                .map(|field| ColumnRef::new(alias, field.name(), field.data_type())),
+        );
+    }
+
+    /// Deserializes the next `length` bytes.
+    pub(super) fn deserialize_slice(
+        &mut self,
+        length: usize,
+    ) -> Result<&'a [u8], ProofPlanDeserializationError> {
+        ensure!(length <= self.data.len(), UnexpectedEndOfDataSnafu);
+        let (value, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(value)
+    }
+
+    /// Deserializes the next `N` bytes as an array.
+    fn deserialize_array<const N: usize>(
+        &mut self,
+    ) -> Result<[u8; N], ProofPlanDeserializationError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.deserialize_slice(N)?);
+        Ok(array)
+    }
+
+    pub(super) fn deserialize_u8(&mut self) -> Result<u8, ProofPlanDeserializationError> {
+        Ok(self.deserialize_array::<1>()?[0])
+    }
+
+    pub(super) fn deserialize_u64(&mut self) -> Result<u64, ProofPlanDeserializationError> {
+        Ok(u64::from_be_bytes(self.deserialize_array()?))
+    }
+
+    /// Deserializes a scalar, which has to be in canonical form.
+    pub(super) fn deserialize_scalar(&mut self) -> Result<S, ProofPlanDeserializationError> {
+        let bytes: [u8; 32] = self.deserialize_array()?;
+        let mut limbs = [0; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+            let mut limb_bytes = [0; 8];
+            limb_bytes.copy_from_slice(chunk);
+            *limb = u64::from_be_bytes(limb_bytes);
+        }
+        let scalar = S::from(limbs);
+        ensure!(Into::<[u64; 4]>::into(scalar) == limbs, InvalidValueSnafu);
+        Ok(scalar)
+    }
+
+    pub(super) fn deserialize_bool(&mut self) -> Result<bool, ProofPlanDeserializationError> {
+        match self.deserialize_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => InvalidValueSnafu.fail(),
+        }
+    }
+
+    pub(super) fn deserialize_usize(&mut self) -> Result<usize, ProofPlanDeserializationError> {
+        usize::try_from(self.deserialize_u64()?)
+            .ok()
+            .context(InvalidValueSnafu)
+    }
+
+    /// Deserializes the length of a list, including the number of results of a plan.
+    pub(super) fn deserialize_length(&mut self) -> Result<usize, ProofPlanDeserializationError> {
+        Ok(self.deserialize_u8()?.into())
+    }
+
+    pub(super) fn deserialize_string(&mut self) -> Result<String, ProofPlanDeserializationError> {
+        let length = self.deserialize_usize()?;
+        let bytes = self.deserialize_slice(length)?;
+        String::from_utf8(bytes.to_vec())
+            .ok()
+            .context(InvalidValueSnafu)
+    }
+
+    pub(super) fn deserialize_ident(&mut self) -> Result<Ident, ProofPlanDeserializationError> {
+        Ok(Ident::new(self.deserialize_string()?))
+    }
+
+    pub(super) fn deserialize_table_ref(
+        &mut self,
+    ) -> Result<TableRef, ProofPlanDeserializationError> {
+        let table_number = self.deserialize_u8()?;
+        self.table_refs
+            .get_index(table_number.into())
+            .copied()
+            .context(TableNotFoundSnafu)
+    }
+
+    pub(super) fn deserialize_column_ref(
+        &mut self,
+    ) -> Result<ColumnRef, ProofPlanDeserializationError> {
+        let column_number = self.deserialize_u8()?;
+        self.column_refs
+            .get_index(column_number.into())
+            .cloned()
+            .context(ColumnNotFoundSnafu)
+    }
+
+    pub(super) fn deserialize_column_field(
+        &mut self,
+    ) -> Result<ColumnField, ProofPlanDeserializationError> {
+        let name = self.deserialize_ident()?;
+        Ok(ColumnField::new(name, self.deserialize_column_type()?))
+    }
+
+    pub(super) fn deserialize_column_type(
+        &mut self,
+    ) -> Result<ColumnType, ProofPlanDeserializationError> {
+        match self.deserialize_u8()? {
+            BOOLEAN_TYPE_NUM => Ok(ColumnType::Boolean),
+            TINYINT_TYPE_NUM => Ok(ColumnType::TinyInt),
+            SMALLINT_TYPE_NUM => Ok(ColumnType::SmallInt),
+            INT_TYPE_NUM => Ok(ColumnType::Int),
+            BIGINT_TYPE_NUM => Ok(ColumnType::BigInt),
+            INT128_TYPE_NUM => Ok(ColumnType::Int128),
+            VARCHAR_TYPE_NUM => Ok(ColumnType::VarChar),
+            DECIMAL75_TYPE_NUM => {
+                let precision = Precision::new(self.deserialize_u8()?)
+                    .ok()
+                    .context(InvalidValueSnafu)?;
+                let scale = i8::from_be_bytes(self.deserialize_array()?);
+                Ok(ColumnType::Decimal75(precision, scale))
+            }
+            TIMESTAMPTZ_TYPE_NUM => {
+                let time_unit = match self.deserialize_u8()? {
+                    SECOND_TIME_UNIT_NUM => PoSQLTimeUnit::Second,
+                    MILLISECOND_TIME_UNIT_NUM => PoSQLTimeUnit::Millisecond,
+                    MICROSECOND_TIME_UNIT_NUM => PoSQLTimeUnit::Microsecond,
+                    NANOSECOND_TIME_UNIT_NUM => PoSQLTimeUnit::Nanosecond,
+                    _ => InvalidValueSnafu.fail()?,
+                };
+                let offset = i32::from_be_bytes(self.deserialize_array()?);
+                Ok(ColumnType::TimestampTZ(
+                    time_unit,
+                    PoSQLTimeZone::new(offset),
+                ))
+            }
+            SCALAR_TYPE_NUM => Ok(ColumnType::Scalar),
+            number => UnknownTypeSnafu { number }.fail(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use snafu::Snafu;
+
+/// Errors that can occur during proof plan deserialization.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum ProofPlanDeserializationError {
+    /// Error indicating that the data ended before the plan did.
+    #[snafu(display("Unexpected end of data."))]
+    UnexpectedEndOfData,
+    /// Error indicating that there is data left after the plan.
+    #[snafu(display("Unexpected data after the plan."))]
+    TrailingData,
+    /// Error indicating that the plan number is unknown.
+    #[snafu(display("Unknown plan number {number}."))]
+    UnknownPlan {
+        /// The unknown plan number
+        number: u8,
+    },
+    /// Error indicating that the expression number is unknown.
+    #[snafu(display("Unknown expression number {number}."))]
+    UnknownExpr {
+        /// The unknown expression number
+        number: u8,
+    },
+    /// Error indicating that the type number is unknown.
+    #[snafu(display("Unknown type number {number}."))]
+    UnknownType {
+        /// The unknown type number
+        number: u8,
+    },
+    /// Error indicating that a value is not valid for what it encodes.
+    #[snafu(display("Invalid value."))]
+    InvalidValue,
+    /// Error indicating that the table was not found.
+    #[snafu(display("Table not found"))]
+    TableNotFound,
+    /// Error indicating that the column was not found.
+    #[snafu(display("Column not found"))]
+    ColumnNotFound,
+}
@@ -0,0 +1,14 @@
+/// This module defines errors that can occur during proof plan deserialization.
+mod error;
+
+/// This module handles the deserialization of proof expressions.
+mod deserialize_proof_expr;
+
+/// This module handles the deserialization of proof plans.
+mod deserialize_proof_plan;
+
+/// This module provides the main deserializer for proof plans.
+mod deserializer;
+
+pub use deserializer::DynProofPlanDeserializer;
+pub use error::ProofPlanDeserializationError;
@@ -1,7 +1,81 @@
 pub const FILTER_EXEC_NUM: u8 = 0;
+pub const EMPTY_EXEC_NUM: u8 = 1;
+pub const TABLE_EXEC_NUM: u8 = 2;
+pub const PROJECTION_EXEC_NUM: u8 = 3;
+pub const GROUP_BY_EXEC_NUM: u8 = 4;
+pub const AGGREGATE_EXEC_NUM: u8 = 5;
+pub const SLICE_EXEC_NUM: u8 = 6;
+pub const ORDER_BY_EXEC_NUM: u8 = 7;
+pub const UNION_EXEC_NUM: u8 = 8;
+pub const JOIN_EXEC_NUM: u8 = 9;
+pub const SEMI_JOIN_EXEC_NUM: u8 = 10;
+pub const SUBQUERY_EXEC_NUM: u8 = 11;
+pub const DISTINCT_EXEC_NUM: u8 = 12;
+pub const WINDOW_EXEC_NUM: u8 = 13;
+pub const INTERSECT_EXEC_NUM: u8 = 14;
 
 pub const COLUMN_EXPR_NUM: u8 = 0;
 pub const EQUALS_EXPR_NUM: u8 = 1;
 pub const LITERAL_EXPR_NUM: u8 = 2;
+pub const AND_EXPR_NUM: u8 = 3;
+pub const OR_EXPR_NUM: u8 = 4;
+pub const NOT_EXPR_NUM: u8 = 5;
+pub const INEQUALITY_EXPR_NUM: u8 = 6;
+pub const IN_LIST_EXPR_NUM: u8 = 7;
+pub const CASE_EXPR_NUM: u8 = 8;
+pub const ADD_SUBTRACT_EXPR_NUM: u8 = 9;
+pub const MULTIPLY_EXPR_NUM: u8 = 10;
+pub const DIVIDE_AND_MODULO_EXPR_NUM: u8 = 11;
+pub const CAST_EXPR_NUM: u8 = 12;
+pub const DATE_TRUNC_EXPR_NUM: u8 = 13;
+pub const EXTRACT_EXPR_NUM: u8 = 14;
+pub const ADD_INTERVAL_EXPR_NUM: u8 = 15;
+pub const AGGREGATE_EXPR_NUM: u8 = 16;
 
 pub const BIGINT_TYPE_NUM: u8 = 0;
+pub const BOOLEAN_TYPE_NUM: u8 = 1;
+pub const TINYINT_TYPE_NUM: u8 = 2;
+pub const SMALLINT_TYPE_NUM: u8 = 3;
+pub const INT_TYPE_NUM: u8 = 4;
+pub const INT128_TYPE_NUM: u8 = 5;
+pub const VARCHAR_TYPE_NUM: u8 = 6;
+pub const DECIMAL75_TYPE_NUM: u8 = 7;
+pub const TIMESTAMPTZ_TYPE_NUM: u8 = 8;
+pub const SCALAR_TYPE_NUM: u8 = 9;
+
+pub const SECOND_TIME_UNIT_NUM: u8 = 0;
+pub const MILLISECOND_TIME_UNIT_NUM: u8 = 1;
+pub const MICROSECOND_TIME_UNIT_NUM: u8 = 2;
+pub const NANOSECOND_TIME_UNIT_NUM: u8 = 3;
+
+pub const YEAR_FIELD_NUM: u8 = 0;
+pub const QUARTER_FIELD_NUM: u8 = 1;
+pub const MONTH_FIELD_NUM: u8 = 2;
+pub const WEEK_FIELD_NUM: u8 = 3;
+pub const DAY_FIELD_NUM: u8 = 4;
+pub const DAY_OF_WEEK_FIELD_NUM: u8 = 5;
+pub const DAY_OF_YEAR_FIELD_NUM: u8 = 6;
+pub const HOUR_FIELD_NUM: u8 = 7;
+pub const MINUTE_FIELD_NUM: u8 = 8;
+pub const SECOND_FIELD_NUM: u8 = 9;
+pub const MILLISECOND_FIELD_NUM: u8 = 10;
+pub const MICROSECOND_FIELD_NUM: u8 = 11;
+pub const NANOSECOND_FIELD_NUM: u8 = 12;
+
+pub const MAX_AGGREGATION_NUM: u8 = 0;
+pub const MIN_AGGREGATION_NUM: u8 = 1;
+pub const SUM_AGGREGATION_NUM: u8 = 2;
+pub const AVG_AGGREGATION_NUM: u8 = 3;
+pub const COUNT_AGGREGATION_NUM: u8 = 4;
+pub const COUNT_DISTINCT_AGGREGATION_NUM: u8 = 5;
+pub const FIRST_AGGREGATION_NUM: u8 = 6;
+pub const MEDIAN_AGGREGATION_NUM: u8 = 7;
+pub const PERCENTILE_CONT_AGGREGATION_NUM: u8 = 8;
+pub const PERCENTILE_DISC_AGGREGATION_NUM: u8 = 9;
+
+pub const ASC_DIRECTION_NUM: u8 = 0;
+pub const DESC_DIRECTION_NUM: u8 = 1;
+
+pub const ROW_NUMBER_WINDOW_FUNCTION_NUM: u8 = 0;
+pub const RANK_WINDOW_FUNCTION_NUM: u8 = 1;
+pub const SUM_WINDOW_FUNCTION_NUM: u8 = 2;
@@ -7,9 +7,12 @@ pub enum ProofPlanSerializationError {
     /// Error indicating that the operation is not supported.
     #[snafu(display("Not supported"))]
     NotSupported,
-    /// Error indicating that there are more than 255 results in the filter.
-    #[snafu(display("More than 255 results in filter."))]
+    /// Error indicating that there are more than 255 results in a plan.
+    #[snafu(display("More than 255 results in plan."))]
     TooManyResults,
+    /// Error indicating that there are more than 255 elements in a list other than the results of a plan.
+    #[snafu(display("More than 255 elements in list."))]
+    TooManyElements,
     /// Error indicating that there are more than 255 tables referenced in the plan.
     #[snafu(display("More than 255 tables referenced in the plan."))]
     TooManyTables,
@@ -1,5 +1,5 @@
 /// This module contains constants used in the proof serialization process.
-pub(super) mod constants;
+pub(crate) mod constants;
 
 /// This module defines errors that can occur during proof plan serialization.
 mod error;
@@ -1,13 +1,32 @@
 use super::{
-    constants::{BIGINT_TYPE_NUM, COLUMN_EXPR_NUM, EQUALS_EXPR_NUM, LITERAL_EXPR_NUM},
-    error::{ColumnNotFoundSnafu, NotSupportedSnafu},
+    constants::{
+        ADD_INTERVAL_EXPR_NUM, ADD_SUBTRACT_EXPR_NUM, AGGREGATE_EXPR_NUM, AND_EXPR_NUM,
+        AVG_AGGREGATION_NUM, CASE_EXPR_NUM, CAST_EXPR_NUM, COLUMN_EXPR_NUM, COUNT_AGGREGATION_NUM,
+        COUNT_DISTINCT_AGGREGATION_NUM, DATE_TRUNC_EXPR_NUM, DAY_FIELD_NUM, DAY_OF_WEEK_FIELD_NUM,
+        DAY_OF_YEAR_FIELD_NUM, DIVIDE_AND_MODULO_EXPR_NUM, EQUALS_EXPR_NUM, EXTRACT_EXPR_NUM,
+        FIRST_AGGREGATION_NUM, HOUR_FIELD_NUM, INEQUALITY_EXPR_NUM, IN_LIST_EXPR_NUM,
+        LITERAL_EXPR_NUM, MAX_AGGREGATION_NUM, MEDIAN_AGGREGATION_NUM, MICROSECOND_FIELD_NUM,
+        MILLISECOND_FIELD_NUM, MINUTE_FIELD_NUM, MIN_AGGREGATION_NUM, MONTH_FIELD_NUM,
+        MULTIPLY_EXPR_NUM, NANOSECOND_FIELD_NUM, NOT_EXPR_NUM, OR_EXPR_NUM,
+        PERCENTILE_CONT_AGGREGATION_NUM, PERCENTILE_DISC_AGGREGATION_NUM, QUARTER_FIELD_NUM,
+        SECOND_FIELD_NUM, SUM_AGGREGATION_NUM, WEEK_FIELD_NUM, YEAR_FIELD_NUM,
+    },
+    error::ColumnNotFoundSnafu,
     DynProofPlanSerializer, ProofPlanSerializationError,
 };
 use crate::{
-    base::{database::LiteralValue, scalar::Scalar},
+    base::{
+        database::{ColumnRef, LiteralValue},
+        scalar::Scalar,
+    },
     evm_compatibility::primitive_serialize_ext::PrimitiveSerializeExt,
-    sql::proof_exprs::{ColumnExpr, DynProofExpr, EqualsExpr, LiteralExpr},
+    sql::proof_exprs::{
+        AddIntervalExpr, AddSubtractExpr, AggregateExpr, AndExpr, CaseExpr, CastExpr, ColumnExpr,
+        DateTruncExpr, DivideAndModuloExpr, DynProofExpr, EqualsExpr, ExtractExpr, InListExpr,
+        InequalityExpr, LiteralExpr, MultiplyExpr, NotExpr, OrExpr,
+    },
 };
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, DateTimeField, Percentile};
 use snafu::OptionExt;
 
 impl<S: Scalar> DynProofPlanSerializer<S> {
@@ -19,37 +38,85 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
             DynProofExpr::Column(column_expr) => self
                 .serialize_u8(COLUMN_EXPR_NUM)
                 .serialize_column_expr(column_expr),
-            DynProofExpr::Literal(literal_expr) => self
+            DynProofExpr::Literal(literal_expr) => Ok(self
                 .serialize_u8(LITERAL_EXPR_NUM)
-                .serialize_literal_expr(literal_expr),
+                .serialize_literal_expr(literal_expr)),
             DynProofExpr::Equals(equals_expr) => self
                 .serialize_u8(EQUALS_EXPR_NUM)
                 .serialize_equals_expr(equals_expr),
-            _ => NotSupportedSnafu.fail(),
+            DynProofExpr::And(and_expr) => {
+                self.serialize_u8(AND_EXPR_NUM).serialize_and_expr(and_expr)
+            }
+            DynProofExpr::Or(or_expr) => self.serialize_u8(OR_EXPR_NUM).serialize_or_expr(or_expr),
+            DynProofExpr::Not(not_expr) => {
+                self.serialize_u8(NOT_EXPR_NUM).serialize_not_expr(not_expr)
+            }
+            DynProofExpr::Inequality(inequality_expr) => self
+                .serialize_u8(INEQUALITY_EXPR_NUM)
+                .serialize_inequality_expr(inequality_expr),
+            DynProofExpr::InList(in_list_expr) => self
+                .serialize_u8(IN_LIST_EXPR_NUM)
+                .serialize_in_list_expr(in_list_expr),
+            DynProofExpr::Case(case_expr) => self
+                .serialize_u8(CASE_EXPR_NUM)
+                .serialize_case_expr(case_expr),
+            DynProofExpr::AddSubtract(add_subtract_expr) => self
+                .serialize_u8(ADD_SUBTRACT_EXPR_NUM)
+                .serialize_add_subtract_expr(add_subtract_expr),
+            DynProofExpr::Multiply(multiply_expr) => self
+                .serialize_u8(MULTIPLY_EXPR_NUM)
+                .serialize_multiply_expr(multiply_expr),
+            DynProofExpr::DivideAndModulo(divide_and_modulo_expr) => self
+                .serialize_u8(DIVIDE_AND_MODULO_EXPR_NUM)
+                .serialize_divide_and_modulo_expr(divide_and_modulo_expr),
+            DynProofExpr::Cast(cast_expr) => self
+                .serialize_u8(CAST_EXPR_NUM)
+                .serialize_cast_expr(cast_expr),
+            DynProofExpr::DateTrunc(date_trunc_expr) => self
+                .serialize_u8(DATE_TRUNC_EXPR_NUM)
+                .serialize_date_trunc_expr(date_trunc_expr),
+            DynProofExpr::Extract(extract_expr) => self
+                .serialize_u8(EXTRACT_EXPR_NUM)
+                .serialize_extract_expr(extract_expr),
+            DynProofExpr::AddInterval(add_interval_expr) => self
+                .serialize_u8(ADD_INTERVAL_EXPR_NUM)
+                .serialize_add_interval_expr(add_interval_expr),
+            DynProofExpr::Aggregate(aggregate_expr) => self
+                .serialize_u8(AGGREGATE_EXPR_NUM)
+                .serialize_aggregate_expr(aggregate_expr),
         }
     }
 
     fn serialize_column_expr(
         self,
         column_expr: &ColumnExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_column_ref(&column_expr.column_ref)
+    }
+
+    pub(super) fn serialize_column_ref(
+        self,
+        column_ref: &ColumnRef,
     ) -> Result<Self, ProofPlanSerializationError> {
         let column_number = self
             .column_refs
-            .get(&column_expr.column_ref)
+            .get(column_ref)
             .copied()
             .context(ColumnNotFoundSnafu)?;
         Ok(self.serialize_u8(column_number))
     }
 
-    fn serialize_literal_expr(
-        self,
-        literal_expr: &LiteralExpr,
-    ) -> Result<Self, ProofPlanSerializationError> {
-        match literal_expr.value {
-            LiteralValue::BigInt(value) => Ok(self
-                .serialize_u8(BIGINT_TYPE_NUM)
-                .serialize_scalar(value.into())),
-            _ => NotSupportedSnafu.fail(),
+    fn serialize_literal_expr(self, literal_expr: &LiteralExpr) -> Self {
+        self.serialize_literal_value(&literal_expr.value)
+    }
+
+    /// Serializes a literal as its type followed by its value. The value is a scalar,
+    /// except for a `VARCHAR`, which is a string since its scalar is a hash.
+    pub(super) fn serialize_literal_value(self, value: &LiteralValue) -> Self {
+        let serializer = self.serialize_column_type(value.column_type());
+        match value {
+            LiteralValue::VarChar(string) => serializer.serialize_str(string),
+            _ => serializer.serialize_scalar(value.to_scalar()),
         }
     }
 
@@ -60,6 +127,167 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
         self.serialize_dyn_proof_expr(equals_expr.lhs.as_ref())?
             .serialize_dyn_proof_expr(equals_expr.rhs.as_ref())
     }
+
+    fn serialize_and_expr(self, and_expr: &AndExpr) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_expr(&and_expr.lhs)?
+            .serialize_dyn_proof_expr(&and_expr.rhs)
+    }
+
+    fn serialize_or_expr(self, or_expr: &OrExpr) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_expr(&or_expr.lhs)?
+            .serialize_dyn_proof_expr(&or_expr.rhs)
+    }
+
+    fn serialize_not_expr(self, not_expr: &NotExpr) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_expr(&not_expr.expr)
+    }
+
+    fn serialize_inequality_expr(
+        self,
+        inequality_expr: &InequalityExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_expr(&inequality_expr.lhs)?
+            .serialize_dyn_proof_expr(&inequality_expr.rhs)?
+            .serialize_bool(inequality_expr.is_lt)
+            .serialize_bool(inequality_expr.is_strict))
+    }
+
+    fn serialize_in_list_expr(
+        self,
+        in_list_expr: &InListExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(in_list_expr.list.iter().fold(
+            self.serialize_dyn_proof_expr(&in_list_expr.expr)?
+                .serialize_length(in_list_expr.list.len())?,
+            Self::serialize_literal_value,
+        ))
+    }
+
+    fn serialize_case_expr(
+        self,
+        case_expr: &CaseExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_expr(&case_expr.condition)?
+            .serialize_dyn_proof_expr(&case_expr.then_result)?
+            .serialize_dyn_proof_expr(&case_expr.else_result)
+    }
+
+    fn serialize_add_subtract_expr(
+        self,
+        add_subtract_expr: &AddSubtractExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_expr(&add_subtract_expr.lhs)?
+            .serialize_dyn_proof_expr(&add_subtract_expr.rhs)?
+            .serialize_bool(add_subtract_expr.is_subtract))
+    }
+
+    fn serialize_multiply_expr(
+        self,
+        multiply_expr: &MultiplyExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_expr(&multiply_expr.lhs)?
+            .serialize_dyn_proof_expr(&multiply_expr.rhs)
+    }
+
+    fn serialize_divide_and_modulo_expr(
+        self,
+        divide_and_modulo_expr: &DivideAndModuloExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_expr(&divide_and_modulo_expr.lhs)?
+            .serialize_dyn_proof_expr(&divide_and_modulo_expr.rhs)?
+            .serialize_bool(divide_and_modulo_expr.is_modulo))
+    }
+
+    fn serialize_cast_expr(
+        self,
+        cast_expr: &CastExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_expr(&cast_expr.from_expr)?
+            .serialize_column_type(cast_expr.to_type))
+    }
+
+    fn serialize_date_trunc_expr(
+        self,
+        date_trunc_expr: &DateTruncExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_date_time_field(date_trunc_expr.field)
+            .serialize_dyn_proof_expr(&date_trunc_expr.expr)
+    }
+
+    fn serialize_extract_expr(
+        self,
+        extract_expr: &ExtractExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_date_time_field(extract_expr.field)
+            .serialize_dyn_proof_expr(&extract_expr.expr)
+    }
+
+    fn serialize_add_interval_expr(
+        self,
+        add_interval_expr: &AddIntervalExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_expr(&add_interval_expr.expr)?
+            .serialize_scalar(add_interval_expr.units.into()))
+    }
+
+    fn serialize_aggregate_expr(
+        self,
+        aggregate_expr: &AggregateExpr,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_aggregation_operator(aggregate_expr.op)
+            .serialize_dyn_proof_expr(&aggregate_expr.expr)
+    }
+
+    fn serialize_date_time_field(self, field: DateTimeField) -> Self {
+        self.serialize_u8(match field {
+            DateTimeField::Year => YEAR_FIELD_NUM,
+            DateTimeField::Quarter => QUARTER_FIELD_NUM,
+            DateTimeField::Month => MONTH_FIELD_NUM,
+            DateTimeField::Week => WEEK_FIELD_NUM,
+            DateTimeField::Day => DAY_FIELD_NUM,
+            DateTimeField::DayOfWeek => DAY_OF_WEEK_FIELD_NUM,
+            DateTimeField::DayOfYear => DAY_OF_YEAR_FIELD_NUM,
+            DateTimeField::Hour => HOUR_FIELD_NUM,
+            DateTimeField::Minute => MINUTE_FIELD_NUM,
+            DateTimeField::Second => SECOND_FIELD_NUM,
+            DateTimeField::Millisecond => MILLISECOND_FIELD_NUM,
+            DateTimeField::Microsecond => MICROSECOND_FIELD_NUM,
+            DateTimeField::Nanosecond => NANOSECOND_FIELD_NUM,
+        })
+    }
+
+    /// Serializes an aggregation operator as its number, followed by the numerator and the scale
+    /// of its percentile if it has one.
+    fn serialize_aggregation_operator(self, op: AggregationOperator) -> Self {
+        let serialize_percentile = |serializer: Self, percentile: Percentile| {
+            serializer
+                .serialize_u64(percentile.numerator())
+                .serialize_u8(percentile.scale())
+        };
+        match op {
+            AggregationOperator::Max => self.serialize_u8(MAX_AGGREGATION_NUM),
+            AggregationOperator::Min => self.serialize_u8(MIN_AGGREGATION_NUM),
+            AggregationOperator::Sum => self.serialize_u8(SUM_AGGREGATION_NUM),
+            AggregationOperator::Avg => self.serialize_u8(AVG_AGGREGATION_NUM),
+            AggregationOperator::Count => self.serialize_u8(COUNT_AGGREGATION_NUM),
+            AggregationOperator::CountDistinct => self.serialize_u8(COUNT_DISTINCT_AGGREGATION_NUM),
+            AggregationOperator::First => self.serialize_u8(FIRST_AGGREGATION_NUM),
+            AggregationOperator::Median => self.serialize_u8(MEDIAN_AGGREGATION_NUM),
+            AggregationOperator::PercentileCont(percentile) => serialize_percentile(
+                self.serialize_u8(PERCENTILE_CONT_AGGREGATION_NUM),
+                percentile,
+            ),
+            AggregationOperator::PercentileDisc(percentile) => serialize_percentile(
+                self.serialize_u8(PERCENTILE_DISC_AGGREGATION_NUM),
+                percentile,
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -71,7 +299,9 @@ mod tests {
             map::indexset,
             scalar::test_scalar::TestScalar,
         },
-        sql::proof_exprs::AndExpr,
+        evm_compatibility::dyn_proof_plan_serializer::constants::{
+            BIGINT_TYPE_NUM, SMALLINT_TYPE_NUM, VARCHAR_TYPE_NUM,
+        },
     };
     use core::iter;
     use itertools::Itertools;
@@ -139,7 +369,6 @@ mod tests {
         let bigint_bytes = serializer
             .clone()
             .serialize_literal_expr(&literal_bigint_expr)
-            .unwrap()
             .into_bytes();
         let expected_bigint_bytes = iter::empty::<u8>()
             .chain([BIGINT_TYPE_NUM])
@@ -162,16 +391,33 @@ mod tests {
             .collect_vec();
         assert_eq!(wrapped_bytes, expected_wrapped_bytes);
 
-        // Serialization of a small int literal should result in an error
-        // because only big int literals are supported so far
+        // Serialization of a small int literal should result in a byte with the small int type number,
+        // followed by the value as a scalar, just like a big int literal.
         let literal_smallint_expr = LiteralExpr::new(LiteralValue::SmallInt(4200));
-        let result = serializer
+        let smallint_bytes = serializer
             .clone()
-            .serialize_literal_expr(&literal_smallint_expr);
-        assert!(matches!(
-            result,
-            Err(ProofPlanSerializationError::NotSupported)
-        ));
+            .serialize_literal_expr(&literal_smallint_expr)
+            .into_bytes();
+        let expected_smallint_bytes = iter::empty::<u8>()
+            .chain([SMALLINT_TYPE_NUM])
+            .chain([0; 30])
+            .chain([16, 104])
+            .collect_vec();
+        assert_eq!(smallint_bytes, expected_smallint_bytes);
+
+        // Serialization of a varchar literal should result in a byte with the varchar type number,
+        // followed by the length of the string as 8 bytes and the string itself.
+        let literal_varchar_expr = LiteralExpr::new(LiteralValue::VarChar("abc".to_string()));
+        let varchar_bytes = serializer
+            .clone()
+            .serialize_literal_expr(&literal_varchar_expr)
+            .into_bytes();
+        let expected_varchar_bytes = iter::empty::<u8>()
+            .chain([VARCHAR_TYPE_NUM])
+            .chain([0, 0, 0, 0, 0, 0, 0, 3])
+            .chain(*b"abc")
+            .collect_vec();
+        assert_eq!(varchar_bytes, expected_varchar_bytes);
     }
 
     #[test]
@@ -228,7 +474,7 @@ mod tests {
     }
 
     #[test]
-    fn we_cannot_serialize_an_unsupported_expr() {
+    fn we_can_serialize_an_inequality_expr() {
         let table_ref: TableRef = "namespace.table".parse().unwrap();
         let column_0_ref: ColumnRef =
             ColumnRef::new(table_ref, "column_0".into(), ColumnType::BigInt);
@@ -238,13 +484,46 @@ mod tests {
         )
         .unwrap();
 
+        // Serialization of an inequality expression should result in the serialization of both sides,
+        // followed by a byte for whether it is a less than and a byte for whether it is strict.
         let lhs = DynProofExpr::Column(ColumnExpr::new(column_0_ref));
         let rhs = DynProofExpr::Literal(LiteralExpr::new(LiteralValue::BigInt(4200)));
-        let expr = DynProofExpr::And(AndExpr::new(Box::new(lhs.clone()), Box::new(rhs.clone())));
-        let result = serializer.clone().serialize_dyn_proof_expr(&expr);
+        let expr = DynProofExpr::Inequality(InequalityExpr::new(
+            Box::new(lhs.clone()),
+            Box::new(rhs.clone()),
+            true,
+            false,
+        ));
+        let bytes = serializer
+            .clone()
+            .serialize_dyn_proof_expr(&expr)
+            .unwrap()
+            .into_bytes();
+        let expected_bytes = iter::empty::<u8>()
+            .chain([INEQUALITY_EXPR_NUM, COLUMN_EXPR_NUM, 0])
+            .chain([LITERAL_EXPR_NUM, BIGINT_TYPE_NUM])
+            .chain([0; 30])
+            .chain([16, 104])
+            .chain([1, 0])
+            .collect_vec();
+        assert_eq!(bytes, expected_bytes);
+    }
+
+    #[test]
+    fn we_cannot_serialize_an_expr_with_an_unknown_column() {
+        let table_ref: TableRef = "namespace.table".parse().unwrap();
+        let column_0_ref: ColumnRef =
+            ColumnRef::new(table_ref, "column_0".into(), ColumnType::BigInt);
+        let serializer =
+            DynProofPlanSerializer::<TestScalar>::try_new(indexset! {}, indexset! {}).unwrap();
+
+        let lhs = DynProofExpr::Column(ColumnExpr::new(column_0_ref));
+        let rhs = DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Boolean(true)));
+        let expr = DynProofExpr::And(AndExpr::new(Box::new(rhs), Box::new(lhs)));
+        let result = serializer.serialize_dyn_proof_expr(&expr);
         assert!(matches!(
             result,
-            Err(ProofPlanSerializationError::NotSupported)
+            Err(ProofPlanSerializationError::ColumnNotFound)
         ));
     }
 }
@@ -1,16 +1,29 @@
 use super::{
-    constants::FILTER_EXEC_NUM,
-    error::{NotSupportedSnafu, TableNotFoundSnafu, TooManyResultsSnafu},
+    constants::{
+        AGGREGATE_EXEC_NUM, ASC_DIRECTION_NUM, DESC_DIRECTION_NUM, DISTINCT_EXEC_NUM,
+        EMPTY_EXEC_NUM, FILTER_EXEC_NUM, GROUP_BY_EXEC_NUM, INTERSECT_EXEC_NUM, JOIN_EXEC_NUM,
+        ORDER_BY_EXEC_NUM, PROJECTION_EXEC_NUM, RANK_WINDOW_FUNCTION_NUM,
+        ROW_NUMBER_WINDOW_FUNCTION_NUM, SEMI_JOIN_EXEC_NUM, SLICE_EXEC_NUM, SUBQUERY_EXEC_NUM,
+        SUM_WINDOW_FUNCTION_NUM, TABLE_EXEC_NUM, UNION_EXEC_NUM, WINDOW_EXEC_NUM,
+    },
+    error::TableNotFoundSnafu,
     DynProofPlanSerializer, ProofPlanSerializationError,
 };
 use crate::{
-    base::scalar::Scalar,
+    base::{database::ColumnRef, scalar::Scalar},
     evm_compatibility::primitive_serialize_ext::PrimitiveSerializeExt,
     sql::{
-        proof_exprs::{AliasedDynProofExpr, TableExpr},
-        proof_plans::{DynProofPlan, FilterExec},
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{
+            AggregateExec, DistinctExec, DynProofPlan, FilterExec, GroupByExec, IntersectExec,
+            JoinExec, OrderByExec, ProjectionExec, SemiJoinExec, SliceExec, SubqueryExec,
+            TableExec, UnionExec, WindowExec, WindowFunction,
+        },
     },
 };
+use alloc::string::ToString;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
 use snafu::OptionExt;
 
 impl<S: Scalar> DynProofPlanSerializer<S> {
@@ -22,7 +35,46 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
             DynProofPlan::Filter(filter_exec) => self
                 .serialize_u8(FILTER_EXEC_NUM)
                 .serialize_filter_exec(filter_exec),
-            _ => NotSupportedSnafu.fail(),
+            DynProofPlan::Empty(_) => Ok(self.serialize_u8(EMPTY_EXEC_NUM)),
+            DynProofPlan::Table(table_exec) => self
+                .serialize_u8(TABLE_EXEC_NUM)
+                .serialize_table_exec(table_exec),
+            DynProofPlan::Projection(projection_exec) => self
+                .serialize_u8(PROJECTION_EXEC_NUM)
+                .serialize_projection_exec(projection_exec),
+            DynProofPlan::GroupBy(group_by_exec) => self
+                .serialize_u8(GROUP_BY_EXEC_NUM)
+                .serialize_group_by_exec(group_by_exec),
+            DynProofPlan::Aggregate(aggregate_exec) => self
+                .serialize_u8(AGGREGATE_EXEC_NUM)
+                .serialize_aggregate_exec(aggregate_exec),
+            DynProofPlan::Slice(slice_exec) => self
+                .serialize_u8(SLICE_EXEC_NUM)
+                .serialize_slice_exec(slice_exec),
+            DynProofPlan::OrderBy(order_by_exec) => self
+                .serialize_u8(ORDER_BY_EXEC_NUM)
+                .serialize_order_by_exec(order_by_exec),
+            DynProofPlan::Union(union_exec) => self
+                .serialize_u8(UNION_EXEC_NUM)
+                .serialize_union_exec(union_exec),
+            DynProofPlan::Join(join_exec) => self
+                .serialize_u8(JOIN_EXEC_NUM)
+                .serialize_join_exec(join_exec),
+            DynProofPlan::SemiJoin(semi_join_exec) => self
+                .serialize_u8(SEMI_JOIN_EXEC_NUM)
+                .serialize_semi_join_exec(semi_join_exec),
+            DynProofPlan::Subquery(subquery_exec) => self
+                .serialize_u8(SUBQUERY_EXEC_NUM)
+                .serialize_subquery_exec(subquery_exec),
+            DynProofPlan::Distinct(distinct_exec) => self
+                .serialize_u8(DISTINCT_EXEC_NUM)
+                .serialize_distinct_exec(distinct_exec),
+            DynProofPlan::Window(window_exec) => self
+                .serialize_u8(WINDOW_EXEC_NUM)
+                .serialize_window_exec(window_exec),
+            DynProofPlan::Intersect(intersect_exec) => self
+                .serialize_u8(INTERSECT_EXEC_NUM)
+                .serialize_intersect_exec(intersect_exec),
         }
     }
 
@@ -30,19 +82,185 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
         self,
         filter_exec: &FilterExec,
     ) -> Result<Self, ProofPlanSerializationError> {
-        let result_count = u8::try_from(filter_exec.aliased_results.len())
-            .ok()
-            .context(TooManyResultsSnafu)?;
+        self.serialize_table_expr(&filter_exec.table)?
+            .serialize_aliased_dyn_proof_exprs(&filter_exec.aliased_results)?
+            .serialize_dyn_proof_expr(&filter_exec.where_clause)
+    }
+
+    /// Serializes a table scan as its table followed by the numbers of the columns it scans.
+    fn serialize_table_exec(
+        self,
+        table_exec: &TableExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        table_exec.schema.iter().try_fold(
+            self.serialize_table_expr(&TableExpr {
+                table_ref: table_exec.table_ref,
+            })?
+            .serialize_result_count(table_exec.schema.len())?,
+            |serializer, field| {
+                serializer.serialize_column_ref(&ColumnRef::new(
+                    table_exec.table_ref,
+                    field.name(),
+                    field.data_type(),
+                ))
+            },
+        )
+    }
+
+    fn serialize_projection_exec(
+        self,
+        projection_exec: &ProjectionExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_table_expr(&projection_exec.table)?
+            .serialize_aliased_dyn_proof_exprs(&projection_exec.aliased_results)
+    }
+
+    fn serialize_group_by_exec(
+        self,
+        group_by_exec: &GroupByExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_table_expr(&group_by_exec.table)?
+            .serialize_aliased_dyn_proof_exprs(&group_by_exec.group_by_exprs)?
+            .serialize_aliased_dyn_proof_exprs(&group_by_exec.aggregate_exprs)?
+            .serialize_dyn_proof_expr(&group_by_exec.where_clause)?
+            .serialize_ident(&group_by_exec.count_alias))
+    }
 
-        filter_exec
-            .aliased_results
+    fn serialize_aggregate_exec(
+        self,
+        aggregate_exec: &AggregateExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        aggregate_exec
+            .presences
             .iter()
             .try_fold(
-                self.serialize_table_expr(&filter_exec.table)?
-                    .serialize_u8(result_count),
-                Self::serialize_aliased_dyn_proof_expr,
+                self.serialize_table_expr(&aggregate_exec.table)?
+                    .serialize_aliased_dyn_proof_exprs(&aggregate_exec.aggregate_exprs)?,
+                |serializer, presence| {
+                    serializer.serialize_optional_dyn_proof_expr(presence.as_ref())
+                },
             )?
-            .serialize_dyn_proof_expr(&filter_exec.where_clause)
+            .serialize_dyn_proof_expr(&aggregate_exec.where_clause)
+    }
+
+    fn serialize_slice_exec(
+        self,
+        slice_exec: &SliceExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let serializer = self
+            .serialize_dyn_proof_plan(&slice_exec.input)?
+            .serialize_usize(slice_exec.skip);
+        Ok(match slice_exec.fetch {
+            Some(fetch) => serializer.serialize_bool(true).serialize_usize(fetch),
+            None => serializer.serialize_bool(false),
+        })
+    }
+
+    fn serialize_order_by_exec(
+        self,
+        order_by_exec: &OrderByExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_plan(&order_by_exec.input)?
+            .serialize_order_by(&order_by_exec.order_by)
+    }
+
+    fn serialize_union_exec(
+        self,
+        union_exec: &UnionExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let serializer = union_exec.inputs.iter().try_fold(
+            self.serialize_length(union_exec.inputs.len())?,
+            Self::serialize_dyn_proof_plan,
+        )?;
+        Ok(union_exec.schema.iter().fold(
+            serializer.serialize_result_count(union_exec.schema.len())?,
+            Self::serialize_column_field,
+        ))
+    }
+
+    fn serialize_join_exec(
+        self,
+        join_exec: &JoinExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let serializer = self
+            .serialize_dyn_proof_plan(&join_exec.left)?
+            .serialize_dyn_proof_plan(&join_exec.right)?
+            .serialize_usize(join_exec.left_join_column_index)
+            .serialize_usize(join_exec.right_join_column_index)
+            .serialize_result_count(join_exec.result_idents.len())?;
+        Ok(join_exec
+            .result_idents
+            .iter()
+            .fold(serializer, Self::serialize_ident))
+    }
+
+    fn serialize_semi_join_exec(
+        self,
+        semi_join_exec: &SemiJoinExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_plan(&semi_join_exec.left)?
+            .serialize_dyn_proof_plan(&semi_join_exec.right)?
+            .serialize_usize(semi_join_exec.left_join_column_index)
+            .serialize_usize(semi_join_exec.right_join_column_index)
+            .serialize_bool(semi_join_exec.negated))
+    }
+
+    /// Serializes a subquery as its input, its alias and the plan using it.
+    ///
+    /// The plan refers to the columns of the alias, so the alias and its columns are registered
+    /// before the plan is serialized.
+    fn serialize_subquery_exec(
+        self,
+        subquery_exec: &SubqueryExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_plan(&subquery_exec.input)?
+            .serialize_str(&subquery_exec.alias.to_string())
+            .try_register_alias(
+                subquery_exec.alias,
+                &subquery_exec.input.get_column_result_fields(),
+            )?
+            .serialize_dyn_proof_plan(&subquery_exec.plan)
+    }
+
+    fn serialize_distinct_exec(
+        self,
+        distinct_exec: &DistinctExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        self.serialize_dyn_proof_plan(&distinct_exec.input)
+    }
+
+    fn serialize_window_exec(
+        self,
+        window_exec: &WindowExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let serializer = window_exec.partition_by.iter().fold(
+            self.serialize_dyn_proof_plan(&window_exec.input)?
+                .serialize_length(window_exec.partition_by.len())?,
+            |serializer, &index| serializer.serialize_usize(index),
+        );
+        let serializer = serializer
+            .serialize_order_by(&window_exec.order_by)?
+            .serialize_result_count(window_exec.functions.len())?;
+        Ok(window_exec
+            .functions
+            .iter()
+            .fold(serializer, |serializer, (function, alias)| {
+                serializer
+                    .serialize_window_function(*function)
+                    .serialize_ident(alias)
+            }))
+    }
+
+    fn serialize_intersect_exec(
+        self,
+        intersect_exec: &IntersectExec,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(self
+            .serialize_dyn_proof_plan(&intersect_exec.left)?
+            .serialize_dyn_proof_plan(&intersect_exec.right)?
+            .serialize_bool(intersect_exec.negated))
     }
 
     fn serialize_table_expr(
@@ -61,7 +279,60 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
         self,
         aliased_expr: &AliasedDynProofExpr,
     ) -> Result<Self, ProofPlanSerializationError> {
-        self.serialize_dyn_proof_expr(&aliased_expr.expr)
+        Ok(self
+            .serialize_dyn_proof_expr(&aliased_expr.expr)?
+            .serialize_ident(&aliased_expr.alias))
+    }
+
+    /// Serializes the number of aliased expressions followed by the expressions.
+    fn serialize_aliased_dyn_proof_exprs(
+        self,
+        aliased_exprs: &[AliasedDynProofExpr],
+    ) -> Result<Self, ProofPlanSerializationError> {
+        aliased_exprs.iter().try_fold(
+            self.serialize_result_count(aliased_exprs.len())?,
+            Self::serialize_aliased_dyn_proof_expr,
+        )
+    }
+
+    /// Serializes `0` if there is no expression and `1` followed by the expression otherwise.
+    fn serialize_optional_dyn_proof_expr(
+        self,
+        expr: Option<&DynProofExpr>,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        match expr {
+            Some(expr) => self.serialize_bool(true).serialize_dyn_proof_expr(expr),
+            None => Ok(self.serialize_bool(false)),
+        }
+    }
+
+    /// Serializes the number of ordering columns followed by the index and direction of each.
+    fn serialize_order_by(
+        self,
+        order_by: &[(usize, OrderByDirection)],
+    ) -> Result<Self, ProofPlanSerializationError> {
+        Ok(order_by.iter().fold(
+            self.serialize_length(order_by.len())?,
+            |serializer, &(index, direction)| {
+                serializer
+                    .serialize_usize(index)
+                    .serialize_u8(match direction {
+                        OrderByDirection::Asc => ASC_DIRECTION_NUM,
+                        OrderByDirection::Desc => DESC_DIRECTION_NUM,
+                    })
+            },
+        ))
+    }
+
+    /// Serializes a window function as its number, followed by the index of the summed column for a `SUM`.
+    fn serialize_window_function(self, function: WindowFunction) -> Self {
+        match function {
+            WindowFunction::RowNumber => self.serialize_u8(ROW_NUMBER_WINDOW_FUNCTION_NUM),
+            WindowFunction::Rank => self.serialize_u8(RANK_WINDOW_FUNCTION_NUM),
+            WindowFunction::Sum(index) => self
+                .serialize_u8(SUM_WINDOW_FUNCTION_NUM)
+                .serialize_usize(index),
+        }
     }
 }
 
@@ -96,7 +367,15 @@ mod tests {
             .serialize_aliased_dyn_proof_expr(&aliased_expr)
             .unwrap()
             .into_bytes();
-        assert_eq!(bytes, expr_bytes);
+
+        // Serialization of an aliased expression should result in the serialized expression,
+        // followed by the length of the alias as 8 bytes and the alias itself.
+        let expected_bytes = iter::empty::<u8>()
+            .chain(expr_bytes)
+            .chain([0, 0, 0, 0, 0, 0, 0, 5])
+            .chain(*b"alias")
+            .collect_vec();
+        assert_eq!(bytes, expected_bytes);
     }
 
     #[test]
@@ -215,4 +494,29 @@ mod tests {
             .into_bytes();
         assert_eq!(wrapped_bytes, expected_wrapped_bytes);
     }
+
+    #[test]
+    fn we_can_serialize_a_slice_exec() {
+        let table_ref = "namespace.table".parse().unwrap();
+        let serializer =
+            DynProofPlanSerializer::<TestScalar>::try_new(indexset! { table_ref }, indexset! {})
+                .unwrap();
+
+        // Serialization of a slice exec should result in the serialized input, the skip as 8 bytes,
+        // a byte for whether there is a fetch and the fetch as 8 bytes if there is one.
+        let input = DynProofPlan::Table(TableExec::new(table_ref, vec![]));
+        let slice_exec = DynProofPlan::Slice(SliceExec::new(Box::new(input), 2, Some(3)));
+        let bytes = serializer
+            .clone()
+            .serialize_dyn_proof_plan(&slice_exec)
+            .unwrap()
+            .into_bytes();
+        let expected_bytes = iter::empty::<u8>()
+            .chain([SLICE_EXEC_NUM, TABLE_EXEC_NUM, 0, 0])
+            .chain([0, 0, 0, 0, 0, 0, 0, 2])
+            .chain([1])
+            .chain([0, 0, 0, 0, 0, 0, 0, 3])
+            .collect_vec();
+        assert_eq!(bytes, expected_bytes);
+    }
 }
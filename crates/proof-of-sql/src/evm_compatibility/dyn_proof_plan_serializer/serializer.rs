@@ -1,17 +1,29 @@
 use super::{
-    error::{TooManyColumnsSnafu, TooManyTablesSnafu},
+    constants::{
+        BIGINT_TYPE_NUM, BOOLEAN_TYPE_NUM, DECIMAL75_TYPE_NUM, INT128_TYPE_NUM, INT_TYPE_NUM,
+        MICROSECOND_TIME_UNIT_NUM, MILLISECOND_TIME_UNIT_NUM, NANOSECOND_TIME_UNIT_NUM,
+        SCALAR_TYPE_NUM, SECOND_TIME_UNIT_NUM, SMALLINT_TYPE_NUM, TIMESTAMPTZ_TYPE_NUM,
+        TINYINT_TYPE_NUM, VARCHAR_TYPE_NUM,
+    },
+    error::{
+        TableNotFoundSnafu, TooManyColumnsSnafu, TooManyElementsSnafu, TooManyResultsSnafu,
+        TooManyTablesSnafu,
+    },
     ProofPlanSerializationError,
 };
 use crate::{
     base::{
-        database::{ColumnRef, TableRef},
+        database::{ColumnField, ColumnRef, ColumnType, TableRef},
         map::{IndexMap, IndexSet},
         scalar::Scalar,
     },
     evm_compatibility::primitive_serialize_ext::PrimitiveSerializeExt,
 };
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec::Vec};
 use core::marker::PhantomData;
+use proof_of_sql_parser::posql_time::PoSQLTimeUnit;
+use snafu::OptionExt;
+use sqlparser::ast::Ident;
 
 /// A serializer for a `DynProofPlan`.
 #[derive(Debug, Clone)]
@@ -67,6 +79,128 @@ impl<S: Scalar> DynProofPlanSerializer<S> {
             _phantom: PhantomData,
         })
     }
+
+    /// Registers the derived table `alias` with the columns `fields`, so that the plan using it can be serialized.
+    ///
+    /// The table and columns get the next free numbers, unless they already have one.
+    pub(super) fn try_register_alias(
+        mut self,
+        alias: TableRef,
+        fields: &[ColumnField],
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let next_table_number = u8::try_from(self.table_refs.len())
+            .ok()
+            .context(TooManyTablesSnafu)?;
+        self.table_refs.entry(alias).or_insert(next_table_number);
+        for field in fields {
+            let next_column_number = u8::try_from(self.column_refs.len())
+                .ok()
+                .context(TooManyColumnsSnafu)?;
+            self.column_refs
+                .entry(ColumnRef::new(alias, field.name(), field.data_type()))
+                .or_insert(next_column_number);
+        }
+        Ok(self)
+    }
+
+    /// Serializes a boolean as a single byte, which is `1` if it is true and `0` otherwise.
+    pub(super) fn serialize_bool(self, value: bool) -> Self {
+        self.serialize_u8(u8::from(value))
+    }
+
+    /// Serializes an index or a count as a 64-bit integer.
+    pub(super) fn serialize_usize(self, value: usize) -> Self {
+        self.serialize_u64(value as u64)
+    }
+
+    /// Serializes the number of results of a plan as a single byte.
+    pub(super) fn serialize_result_count(
+        self,
+        count: usize,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let count = u8::try_from(count).ok().context(TooManyResultsSnafu)?;
+        Ok(self.serialize_u8(count))
+    }
+
+    /// Serializes the length of any other list as a single byte.
+    pub(super) fn serialize_length(
+        self,
+        length: usize,
+    ) -> Result<Self, ProofPlanSerializationError> {
+        let length = u8::try_from(length).ok().context(TooManyElementsSnafu)?;
+        Ok(self.serialize_u8(length))
+    }
+
+    /// Serializes a string as its length in bytes followed by its UTF-8 bytes.
+    pub(super) fn serialize_str(self, value: &str) -> Self {
+        self.serialize_usize(value.len())
+            .serialize_slice(value.as_bytes())
+    }
+
+    /// Serializes an identifier, such as an alias, as a string.
+    pub(super) fn serialize_ident(self, ident: &Ident) -> Self {
+        self.serialize_str(&ident.value)
+    }
+
+    /// Serializes a column field as its name followed by its type.
+    pub(super) fn serialize_column_field(self, field: &ColumnField) -> Self {
+        self.serialize_ident(&field.name())
+            .serialize_column_type(field.data_type())
+    }
+
+    /// Serializes a column type as its type number followed by its parameters, if it has any.
+    pub(super) fn serialize_column_type(self, column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Boolean => self.serialize_u8(BOOLEAN_TYPE_NUM),
+            ColumnType::TinyInt => self.serialize_u8(TINYINT_TYPE_NUM),
+            ColumnType::SmallInt => self.serialize_u8(SMALLINT_TYPE_NUM),
+            ColumnType::Int => self.serialize_u8(INT_TYPE_NUM),
+            ColumnType::BigInt => self.serialize_u8(BIGINT_TYPE_NUM),
+            ColumnType::Int128 => self.serialize_u8(INT128_TYPE_NUM),
+            ColumnType::VarChar => self.serialize_u8(VARCHAR_TYPE_NUM),
+            ColumnType::Decimal75(precision, scale) => self
+                .serialize_u8(DECIMAL75_TYPE_NUM)
+                .serialize_u8(precision.value())
+                .serialize_slice(&scale.to_be_bytes()),
+            ColumnType::TimestampTZ(time_unit, time_zone) => self
+                .serialize_u8(TIMESTAMPTZ_TYPE_NUM)
+                .serialize_u8(match time_unit {
+                    PoSQLTimeUnit::Second => SECOND_TIME_UNIT_NUM,
+                    PoSQLTimeUnit::Millisecond => MILLISECOND_TIME_UNIT_NUM,
+                    PoSQLTimeUnit::Microsecond => MICROSECOND_TIME_UNIT_NUM,
+                    PoSQLTimeUnit::Nanosecond => NANOSECOND_TIME_UNIT_NUM,
+                })
+                .serialize_slice(&time_zone.offset().to_be_bytes()),
+            ColumnType::Scalar => self.serialize_u8(SCALAR_TYPE_NUM),
+        }
+    }
+
+    /// Serializes the tables and columns that a plan refers to by their numbers, in the order of their numbers.
+    ///
+    /// This is the number of tables followed by the name of each table, and the number of columns followed by
+    /// the number of the table, the name and the type of each column.
+    pub fn serialize_references(self) -> Result<Self, ProofPlanSerializationError> {
+        let table_refs: Vec<TableRef> = self.table_refs.keys().copied().collect();
+        let column_refs: Vec<ColumnRef> = self.column_refs.keys().cloned().collect();
+        let serializer = table_refs.iter().fold(
+            self.serialize_length(table_refs.len())?,
+            |serializer, table_ref| serializer.serialize_str(&table_ref.to_string()),
+        );
+        column_refs.iter().try_fold(
+            serializer.serialize_length(column_refs.len())?,
+            |serializer, column_ref| {
+                let table_number = serializer
+                    .table_refs
+                    .get(&column_ref.table_ref())
+                    .copied()
+                    .context(TableNotFoundSnafu)?;
+                Ok(serializer
+                    .serialize_u8(table_number)
+                    .serialize_ident(&column_ref.column_id())
+                    .serialize_column_type(*column_ref.column_type()))
+            },
+        )
+    }
 }
 
 #[cfg(test)]
@@ -78,9 +212,12 @@ mod tests {
             scalar::test_scalar::TestScalar,
         },
         evm_compatibility::dyn_proof_plan_serializer::{
+            constants::{BIGINT_TYPE_NUM, BOOLEAN_TYPE_NUM},
             DynProofPlanSerializer, ProofPlanSerializationError,
         },
     };
+    use core::iter;
+    use itertools::Itertools;
 
     #[test]
     fn we_can_create_dyn_proof_plan_serializer() {
@@ -105,6 +242,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_serialize_the_references_of_a_plan() {
+        let table_ref_1: TableRef = "namespace.t1".parse().unwrap();
+        let table_ref_2: TableRef = "namespace.t2".parse().unwrap();
+        let column_refs = indexset! {
+            ColumnRef::new(table_ref_2, "a".into(), ColumnType::BigInt),
+            ColumnRef::new(table_ref_1, "bc".into(), ColumnType::Boolean),
+        };
+        let bytes = DynProofPlanSerializer::<TestScalar>::try_new(
+            indexset! { table_ref_1, table_ref_2 },
+            column_refs,
+        )
+        .unwrap()
+        .serialize_references()
+        .unwrap()
+        .into_bytes();
+        let expected_bytes = iter::empty::<u8>()
+            .chain([2]) // table count
+            .chain([0, 0, 0, 0, 0, 0, 0, 12]) // table name length
+            .chain(*b"namespace.t1")
+            .chain([0, 0, 0, 0, 0, 0, 0, 12])
+            .chain(*b"namespace.t2")
+            .chain([2]) // column count
+            .chain([1, 0, 0, 0, 0, 0, 0, 0, 1]) // table number, column name length
+            .chain(*b"a")
+            .chain([BIGINT_TYPE_NUM])
+            .chain([0, 0, 0, 0, 0, 0, 0, 0, 2])
+            .chain(*b"bc")
+            .chain([BOOLEAN_TYPE_NUM])
+            .collect_vec();
+        assert_eq!(bytes, expected_bytes);
+    }
+
     #[test]
     fn we_cannot_create_dyn_proof_plan_serializer_with_too_many_tables() {
         let table_refs = (0..=u8::MAX as usize)
@@ -0,0 +1,261 @@
+use super::dyn_proof_plan_serializer::{DynProofPlanSerializer, ProofPlanSerializationError};
+use crate::{
+    base::{
+        database::{ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableRef},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, QueryError,
+            VerificationBuilder,
+        },
+        proof_plans::DynProofPlan,
+    },
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use serde::{Serialize, Serializer};
+
+/// A `DynProofPlan` that is added to the transcript in the format that the Solidity verifier reads.
+///
+/// The plan is serialized as its references, see [`DynProofPlanSerializer::serialize_references`],
+/// followed by the plan itself. A proof for an `EVMProofPlan` can only be verified with the same
+/// `EVMProofPlan`, whether in Rust or on the EVM.
+///
+/// Every plan can be serialized, but the Solidity verifier only supports filters and projections
+/// whose expressions are column references, literals, `=`, `AND`, `OR`, `NOT`, `+`, `-` and `*`.
+/// Proofs of any other plan can only be verified in Rust.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EVMProofPlan {
+    plan: DynProofPlan,
+    bytes: Vec<u8>,
+}
+
+impl EVMProofPlan {
+    /// Creates an `EVMProofPlan` whose literals are serialized as scalars of type `S`,
+    /// which has to be the scalar of the proof.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the plan can not be serialized.
+    pub fn try_new<S: Scalar>(plan: DynProofPlan) -> Result<Self, ProofPlanSerializationError> {
+        let references = DynProofPlanSerializer::<S>::try_new(
+            plan.get_table_references(),
+            plan.get_column_references(),
+        )?;
+        let plan_bytes = references
+            .clone()
+            .serialize_dyn_proof_plan(&plan)?
+            .into_bytes();
+        let mut bytes = references.serialize_references()?.into_bytes();
+        bytes.extend(plan_bytes);
+        Ok(Self { plan, bytes })
+    }
+
+    /// Returns the plan.
+    #[must_use]
+    pub fn inner(&self) -> &DynProofPlan {
+        &self.plan
+    }
+
+    /// Returns the serialized plan that the Solidity verifier reads.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Serialize for EVMProofPlan {
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+impl ProofPlan for EVMProofPlan {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        self.plan
+            .verifier_evaluate(builder, accessor, result, one_eval_map)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_intermediate_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_intermediate_result_fields()
+    }
+
+    fn finalize_result<S: Scalar>(
+        &self,
+        result: OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, QueryError> {
+        self.plan.finalize_result(result)
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.plan.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.plan.get_table_references()
+    }
+}
+
+impl ProverEvaluate for EVMProofPlan {
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        self.plan.first_round_evaluate(builder, alloc, table_map)
+    }
+
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        self.plan.final_round_evaluate(builder, alloc, table_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EVMProofPlan;
+    use crate::{
+        base::database::{
+            owned_table_utility::{bigint, boolean, owned_table},
+            ColumnRef, ColumnType, LiteralValue, OwnedTableTestAccessor,
+        },
+        evm_compatibility::dyn_proof_plan_serializer::{
+            constants::{BIGINT_TYPE_NUM, COLUMN_EXPR_NUM, FILTER_EXEC_NUM},
+            ProofPlanSerializationError,
+        },
+        proof_primitive::hyperkzg::{
+            BNScalar, HyperKZGCommitmentEvaluationProof, HyperKZGVerifierSetup,
+        },
+        sql::{
+            proof::VerifiableQueryResult,
+            proof_exprs::{
+                AliasedDynProofExpr, ColumnExpr, DynProofExpr, EqualsExpr, LiteralExpr, TableExpr,
+            },
+            proof_plans::{DynProofPlan, FilterExec},
+        },
+    };
+    use ark_std::test_rng;
+    use core::iter;
+    use itertools::Itertools;
+
+    fn filter_plan() -> DynProofPlan {
+        let table_ref = "namespace.table".parse().unwrap();
+        DynProofPlan::Filter(FilterExec::new(
+            vec![AliasedDynProofExpr {
+                expr: DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    "b".into(),
+                    ColumnType::BigInt,
+                ))),
+                alias: "b".into(),
+            }],
+            TableExpr { table_ref },
+            DynProofExpr::Equals(EqualsExpr::new(
+                Box::new(DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    "a".into(),
+                    ColumnType::Boolean,
+                )))),
+                Box::new(DynProofExpr::Literal(LiteralExpr::new(
+                    LiteralValue::Boolean(true),
+                ))),
+            )),
+        ))
+    }
+
+    #[test]
+    fn we_can_serialize_the_references_and_the_plan() {
+        let plan = EVMProofPlan::try_new::<BNScalar>(filter_plan()).unwrap();
+        let expected_prefix = iter::empty::<u8>()
+            .chain([1, 0, 0, 0, 0, 0, 0, 0, 15]) // table count, table name length
+            .chain(*b"namespace.table")
+            .chain([2]) // column count
+            .chain([0, 0, 0, 0, 0, 0, 0, 0, 1]) // table number, column name length
+            .chain(*b"b")
+            .chain([BIGINT_TYPE_NUM])
+            .chain([0, 0, 0, 0, 0, 0, 0, 0, 1])
+            .chain(*b"a")
+            .collect_vec();
+        assert!(plan.bytes().starts_with(&expected_prefix));
+        // The plan itself follows the type of the last column
+        assert_eq!(
+            plan.bytes()[expected_prefix.len() + 1..][..4],
+            [FILTER_EXEC_NUM, 0, 1, COLUMN_EXPR_NUM]
+        );
+        assert_eq!(plan.inner(), &filter_plan());
+
+        // The plan is added to the transcript as a byte array
+        let postcard_bytes = postcard::to_allocvec(&plan).unwrap();
+        assert_eq!(usize::from(postcard_bytes[0]), plan.bytes().len());
+        assert_eq!(&postcard_bytes[1..], plan.bytes());
+    }
+
+    #[test]
+    fn we_cannot_create_an_evm_proof_plan_with_too_many_columns() {
+        let table_ref = "namespace.table".parse().unwrap();
+        let plan = DynProofPlan::Filter(FilterExec::new(
+            (0..=u8::MAX)
+                .map(|i| AliasedDynProofExpr {
+                    expr: DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+                        table_ref,
+                        format!("column{i}").as_str().into(),
+                        ColumnType::BigInt,
+                    ))),
+                    alias: format!("column{i}").as_str().into(),
+                })
+                .collect(),
+            TableExpr { table_ref },
+            DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Boolean(true))),
+        ));
+        assert!(matches!(
+            EVMProofPlan::try_new::<BNScalar>(plan),
+            Err(ProofPlanSerializationError::TooManyColumns)
+        ));
+    }
+
+    #[test]
+    fn we_can_prove_and_verify_an_evm_proof_plan_with_hyperkzg() {
+        let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(8, &mut test_rng());
+        let accessor = OwnedTableTestAccessor::<HyperKZGCommitmentEvaluationProof>::new_from_table(
+            "namespace.table".parse().unwrap(),
+            owned_table([boolean("a", [true, false, true]), bigint("b", [1, 2, 3])]),
+            0,
+            prover_setup.as_slice(),
+        );
+        let plan = EVMProofPlan::try_new::<BNScalar>(filter_plan()).unwrap();
+        let result = VerifiableQueryResult::<HyperKZGCommitmentEvaluationProof>::new(
+            &plan,
+            &accessor,
+            &prover_setup.as_slice(),
+        );
+
+        // A proof for the plan is not a proof for the plan serialized differently
+        assert!(result
+            .clone()
+            .verify(plan.inner(), &accessor, &&verifier_setup)
+            .is_err());
+        let table = result
+            .verify(&plan, &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        assert_eq!(table, owned_table([bigint("b", [1, 3])]));
+    }
+}
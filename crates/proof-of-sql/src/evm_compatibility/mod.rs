@@ -1,4 +1,12 @@
+mod dyn_proof_plan_deserializer;
 mod dyn_proof_plan_serializer;
+#[cfg(test)]
+pub(crate) use dyn_proof_plan_serializer::constants;
+pub use dyn_proof_plan_serializer::ProofPlanSerializationError;
 mod primitive_serialize_ext;
 mod serialize_query_expr;
 pub use serialize_query_expr::serialize_query_expr;
+mod deserialize_proof_plan;
+pub use deserialize_proof_plan::deserialize_proof_plan;
+mod evm_proof_plan;
+pub use evm_proof_plan::EVMProofPlan;
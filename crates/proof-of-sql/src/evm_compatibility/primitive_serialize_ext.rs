@@ -27,6 +27,19 @@ pub trait PrimitiveSerializeExt<S: Scalar>: Sized {
         self.serialize_slice(&[value])
     }
 
+    /// Serializes a 64-bit unsigned integer as 8 bytes in big-endian form, which is how the EVM reads integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - An integer to be serialized.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The serialized result.
+    fn serialize_u64(self, value: u64) -> Self {
+        self.serialize_slice(&value.to_be_bytes())
+    }
+
     /// Serializes a scalar value. The scalar is serialized as a 256-bit, bytwise-big-endian integer.
     /// This is the format used by the EVM for representing integers.
     ///
@@ -73,6 +86,13 @@ mod tests {
         assert_eq!(result, vec![123]);
     }
 
+    #[test]
+    fn we_can_serialize_u64() {
+        let serializer = MockSerializer::<TestScalar>::new();
+        let result = serializer.serialize_u64(123 + (45 << 8)).into_inner();
+        assert_eq!(result, vec![0, 0, 0, 0, 0, 0, 45, 123]);
+    }
+
     #[test]
     fn we_can_serialize_scalar_that_requires_one_bytes() {
         let serializer = MockSerializer::<TestScalar>::new();
@@ -109,13 +109,10 @@ mod tests {
     }
 
     #[test]
-    fn we_cannot_generate_serialized_proof_plan_for_unsupported_plan() {
+    fn we_can_generate_serialized_proof_plan_for_empty_plan() {
         let plan = DynProofPlan::Empty(EmptyExec::new());
-        let result = serialize_query_expr::<TestScalar>(&QueryExpr::new(plan, vec![]));
-        assert!(matches!(
-            result,
-            Err(ProofPlanSerializationError::NotSupported)
-        ));
+        let bytes = serialize_query_expr::<TestScalar>(&QueryExpr::new(plan, vec![])).unwrap();
+        assert_eq!(bytes, vec![EMPTY_EXEC_NUM]);
     }
 
     #[test]
@@ -147,6 +144,8 @@ mod tests {
         let expected_bytes = iter::empty::<u8>()
             .chain([FILTER_EXEC_NUM, 0, 1]) // filter expr, table number, result count
             .chain([COLUMN_EXPR_NUM, 0]) // column expr, column b (#0)
+            .chain([0, 0, 0, 0, 0, 0, 0, 5]) // alias length
+            .chain(*b"alias") // alias
             .chain([EQUALS_EXPR_NUM]) // equals expr
             .chain([COLUMN_EXPR_NUM, 1]) // column expr, column a (#1)
             .chain([LITERAL_EXPR_NUM, BIGINT_TYPE_NUM]) // literal expr, literal type
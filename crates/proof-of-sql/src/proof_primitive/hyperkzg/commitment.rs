@@ -0,0 +1,252 @@
+//! Module containing the `HyperKZGCommitment` type and its implementation.
+//!
+//! A column is committed to as the KZG commitment of the univariate polynomial whose coefficients are the column,
+//! i.e. the column `[a_0, a_1, ..., a_{n-1}]` with offset `k` is committed to as
+//! `a_0 * tau^k * G + a_1 * tau^(k+1) * G + ... + a_{n-1} * tau^(k+n-1) * G`.
+//! In other words, the offset logically adds `k` zeros to the beginning of the column,
+//! so that commitments can be updated by adding the commitments of new rows.
+
+use super::{
+    commitment_evaluation_proof::SmallSetupSnafu, BNScalar, G1Projective, HyperKZGError,
+    HyperKZGPublicSetup,
+};
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    if_rayon, impl_serde_for_ark_serde_checked,
+    proof::Transcript,
+    slice_ops::slice_cast,
+};
+use alloc::vec::Vec;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bytemuck::TransparentWrapper;
+use core::ops::Mul;
+use derive_more::{AddAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use snafu::OptionExt;
+
+#[derive(
+    Debug,
+    Sub,
+    Eq,
+    PartialEq,
+    Neg,
+    Copy,
+    Clone,
+    Default,
+    AddAssign,
+    SubAssign,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
+/// The `HyperKZG` commitment type.
+pub struct HyperKZGCommitment(pub(crate) G1Projective);
+
+// Traits required for `HyperKZGCommitment` to impl `Commitment`.
+impl_serde_for_ark_serde_checked!(HyperKZGCommitment);
+impl Mul<HyperKZGCommitment> for BNScalar {
+    type Output = HyperKZGCommitment;
+    fn mul(self, rhs: HyperKZGCommitment) -> Self::Output {
+        HyperKZGCommitment(rhs.0 * self.0)
+    }
+}
+impl<'a> Mul<&'a HyperKZGCommitment> for BNScalar {
+    type Output = HyperKZGCommitment;
+    fn mul(self, rhs: &'a HyperKZGCommitment) -> Self::Output {
+        HyperKZGCommitment(rhs.0 * self.0)
+    }
+}
+
+/// Computes the commitment of `column` as if it was preceded by `offset` zeros.
+///
+/// This errors if the setup has fewer than `offset + column.len()` generators.
+pub(super) fn compute_commitment_impl<'a, T>(
+    column: &'a [T],
+    offset: usize,
+    setup: HyperKZGPublicSetup,
+) -> Result<HyperKZGCommitment, HyperKZGError>
+where
+    &'a T: Into<BNScalar>,
+    T: Sync,
+{
+    let required = offset + column.len();
+    let generators = setup.get(offset..required).context(SmallSetupSnafu {
+        actual: setup.len(),
+        required,
+    })?;
+    let scalars = slice_cast::<_, BNScalar>(column);
+    Ok(HyperKZGCommitment(G1Projective::msm_unchecked(
+        generators,
+        TransparentWrapper::peel_slice(&scalars),
+    )))
+}
+
+fn compute_commitment(
+    committable_column: &CommittableColumn,
+    offset: usize,
+    setup: HyperKZGPublicSetup,
+) -> Result<HyperKZGCommitment, HyperKZGError> {
+    match committable_column {
+        CommittableColumn::Scalar(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::TinyInt(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::SmallInt(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::Int(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::BigInt(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::Int128(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::VarChar(column) | CommittableColumn::Decimal75(_, _, column) => {
+            compute_commitment_impl(column, offset, setup)
+        }
+        CommittableColumn::Boolean(column) => compute_commitment_impl(column, offset, setup),
+        CommittableColumn::TimestampTZ(_, _, column) => {
+            compute_commitment_impl(column, offset, setup)
+        }
+        CommittableColumn::RangeCheckWord(column) => compute_commitment_impl(column, offset, setup),
+    }
+}
+
+impl HyperKZGCommitment {
+    /// Computes the commitments of the columns as if each was preceded by `offset` zeros.
+    ///
+    /// Unlike [`Commitment::compute_commitments`], this returns an error rather than panicking
+    /// if the setup has fewer generators than `offset` plus the length of the columns.
+    pub fn try_compute_commitments(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &HyperKZGPublicSetup<'_>,
+    ) -> Result<Vec<Self>, HyperKZGError> {
+        if_rayon!(committable_columns.par_iter(), committable_columns.iter())
+            .map(|column| compute_commitment(column, offset, setup))
+            .collect()
+    }
+}
+
+impl Commitment for HyperKZGCommitment {
+    type Scalar = BNScalar;
+    type PublicSetup<'a> = HyperKZGPublicSetup<'a>;
+
+    /// # Panics
+    /// Panics if the setup has fewer generators than `offset` plus the length of the columns.
+    /// Use [`HyperKZGCommitment::try_compute_commitments`] to get an error instead.
+    fn compute_commitments(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) -> Vec<Self> {
+        Self::try_compute_commitments(committable_columns, offset, setup)
+            .expect("the setup is too small for the columns")
+    }
+
+    fn append_to_transcript(&self, transcript: &mut impl Transcript) {
+        transcript.extend_canonical_serialize_as_le(&self.0.into_affine());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BNScalar, HyperKZGCommitment};
+    use crate::{
+        base::{
+            commitment::{Commitment, CommittableColumn, VecCommitmentExt},
+            database::Column,
+            proof::{Keccak256Transcript, Transcript},
+        },
+        proof_primitive::hyperkzg::{HyperKZGError, HyperKZGVerifierSetup},
+    };
+    use ark_ec::AffineRepr;
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn we_can_compute_commitments_with_an_offset() {
+        let (setup, _) = HyperKZGVerifierSetup::test_rand(8, &mut test_rng());
+        let column_a = [12i64, 34, 56];
+        let column_b = [true, false, true];
+        let commitments = Vec::<HyperKZGCommitment>::from_columns_with_offset(
+            [
+                Column::<BNScalar>::BigInt(&column_a),
+                Column::Boolean(&column_b),
+            ],
+            2,
+            &setup.as_slice(),
+        );
+        let expected_a = setup[2] * ark_bn254::Fr::from(12)
+            + setup[3] * ark_bn254::Fr::from(34)
+            + setup[4] * ark_bn254::Fr::from(56);
+        let expected_b = setup[2].into_group() + setup[4];
+        assert_eq!(
+            commitments,
+            vec![
+                HyperKZGCommitment(expected_a),
+                HyperKZGCommitment(expected_b)
+            ]
+        );
+
+        // Commitments are additive, so committing to rows separately gives the same commitment.
+        let mut commitment = Vec::<HyperKZGCommitment>::from_columns_with_offset(
+            [Column::<BNScalar>::BigInt(&column_a[..1])],
+            2,
+            &setup.as_slice(),
+        );
+        commitment
+            .try_append_rows_with_offset(
+                [Column::<BNScalar>::BigInt(&column_a[1..])],
+                3,
+                &setup.as_slice(),
+            )
+            .unwrap();
+        assert_eq!(commitment[0], commitments[0]);
+    }
+
+    #[test]
+    fn we_cannot_compute_commitments_with_a_setup_that_is_too_small() {
+        let (setup, _) = HyperKZGVerifierSetup::test_rand(4, &mut test_rng());
+        let column = [12i64, 34, 56];
+        assert_eq!(
+            HyperKZGCommitment::try_compute_commitments(
+                &[CommittableColumn::BigInt(&column)],
+                1,
+                &setup.as_slice(),
+            )
+            .unwrap()
+            .len(),
+            1
+        );
+        assert!(matches!(
+            HyperKZGCommitment::try_compute_commitments(
+                &[CommittableColumn::BigInt(&column)],
+                2,
+                &setup.as_slice(),
+            ),
+            Err(HyperKZGError::SmallSetup {
+                actual: 4,
+                required: 5
+            })
+        ));
+    }
+
+    #[test]
+    #[should_panic = "the setup is too small for the columns"]
+    fn computing_commitments_with_a_setup_that_is_too_small_panics() {
+        let (setup, _) = HyperKZGVerifierSetup::test_rand(2, &mut test_rng());
+        HyperKZGCommitment::compute_commitments(
+            &[CommittableColumn::BigInt(&[12, 34, 56])],
+            0,
+            &setup.as_slice(),
+        );
+    }
+
+    #[test]
+    fn we_can_append_different_hyperkzg_commitments_and_get_different_transcripts() {
+        let mut rng = test_rng();
+        let commitment1 = HyperKZGCommitment(ark_bn254::G1Projective::rand(&mut rng));
+        let commitment2 = BNScalar::from(2) * commitment1;
+
+        let mut transcript1 = Keccak256Transcript::new();
+        let mut transcript2 = Keccak256Transcript::new();
+
+        commitment1.append_to_transcript(&mut transcript1);
+        commitment2.append_to_transcript(&mut transcript2);
+
+        assert_ne!(transcript1.challenge_as_le(), transcript2.challenge_as_le());
+    }
+}
@@ -0,0 +1,305 @@
+use super::{
+    commitment::compute_commitment_impl, BNScalar, G1Affine, G1Projective, HyperKZGCommitment,
+    HyperKZGPublicSetup, HyperKZGVerifierSetup,
+};
+use crate::base::{
+    commitment::CommitmentEvaluationProof, impl_serde_for_ark_serde_checked, proof::Transcript,
+    scalar::Scalar,
+};
+use alloc::vec::Vec;
+use ark_bn254::Bn254;
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    CurveGroup, VariableBaseMSM,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bytemuck::TransparentWrapper;
+use core::iter;
+use num_traits::Zero;
+use snafu::{ensure, Snafu};
+
+/// The `CommitmentEvaluationProof` for the `HyperKZG` PCS.
+#[derive(Default, Clone, CanonicalSerialize, CanonicalDeserialize, PartialEq, Eq, Debug)]
+pub struct HyperKZGCommitmentEvaluationProof {
+    /// The commitments of the folded polynomials `f_1, ..., f_{nu-1}`.
+    /// `f_0` is the committed polynomial and `f_nu` is the constant evaluation, so neither is sent.
+    pub(crate) com: Vec<G1Affine>,
+    /// The evaluations of `f_0, ..., f_{nu-1}` at `r`, `-r` and `r^2`.
+    pub(crate) v: Vec<[BNScalar; 3]>,
+    /// The KZG witnesses of the batched polynomial at `r`, `-r` and `r^2`.
+    pub(crate) w: Vec<G1Affine>,
+}
+impl_serde_for_ark_serde_checked!(HyperKZGCommitmentEvaluationProof);
+
+/// The error type for the `HyperKZG` PCS.
+#[derive(Snafu, Debug)]
+pub enum HyperKZGError {
+    /// This error occurs when the generators offset is invalid.
+    #[snafu(display("invalid generators offset: {offset}"))]
+    InvalidGeneratorsOffset {
+        /// The invalid offset.
+        offset: u64,
+    },
+    /// This error occurs when the proof fails to verify.
+    #[snafu(display("verification error"))]
+    VerificationError,
+    /// This error occurs when the setup has too few generators for a column or a table.
+    #[snafu(
+        display(
+            "setup is too small: the setup is {actual}, but the proof requires a setup of size {required}"
+        ),
+        visibility(pub(super))
+    )]
+    SmallSetup {
+        /// The number of generators of the setup.
+        actual: usize,
+        /// The number of generators that are required.
+        required: usize,
+    },
+}
+
+/// Folds the coefficients of `f` with `x`, i.e. computes `(1 - x) * E + x * O`,
+/// where `f(X) = E(X^2) + X * O(X^2)`.
+fn fold(f: &[BNScalar], x: BNScalar) -> Vec<BNScalar> {
+    f.chunks(2)
+        .map(|pair| match *pair {
+            [even, odd] => even + x * (odd - even),
+            [even] => even - x * even,
+            _ => BNScalar::ZERO,
+        })
+        .collect()
+}
+
+/// Evaluates the polynomial with coefficients `f` at `u`.
+fn evaluate(f: &[BNScalar], u: BNScalar) -> BNScalar {
+    f.iter()
+        .rev()
+        .fold(BNScalar::ZERO, |acc, &coefficient| acc * u + coefficient)
+}
+
+/// Computes the coefficients of the quotient `(f(X) - f(u)) / (X - u)`.
+fn divide_by_linear(f: &[BNScalar], u: BNScalar) -> Vec<BNScalar> {
+    let mut quotient = f[1.min(f.len())..].to_vec();
+    let mut acc = BNScalar::ZERO;
+    for coefficient in quotient.iter_mut().rev() {
+        acc = *coefficient + u * acc;
+        *coefficient = acc;
+    }
+    quotient
+}
+
+/// Commits to `f`, which is never longer than the column the proof is for.
+///
+/// # Panics
+/// Panics if the setup is shorter than `f`, which `new` checks for the column beforehand.
+fn commit(f: &[BNScalar], setup: HyperKZGPublicSetup) -> G1Projective {
+    compute_commitment_impl(f, 0, setup)
+        .expect("the setup was checked to be long enough for the column")
+        .0
+}
+
+fn powers(base: BNScalar, count: usize) -> Vec<BNScalar> {
+    iter::successors(Some(BNScalar::ONE), |power| Some(*power * base))
+        .take(count)
+        .collect()
+}
+
+fn msm(bases: &[G1Affine], scalars: &[BNScalar]) -> G1Projective {
+    G1Projective::msm_unchecked(bases, TransparentWrapper::peel_slice(scalars))
+}
+
+impl CommitmentEvaluationProof for HyperKZGCommitmentEvaluationProof {
+    type Scalar = BNScalar;
+    type Commitment = HyperKZGCommitment;
+    type Error = HyperKZGError;
+    type ProverPublicSetup<'a> = HyperKZGPublicSetup<'a>;
+    type VerifierPublicSetup<'a> = &'a HyperKZGVerifierSetup;
+
+    /// Proves the evaluation of `a` at `b_point`.
+    ///
+    /// If `b_point` is empty, `a` has at most one entry, which is its own evaluation, so the proof
+    /// is empty.
+    ///
+    /// # Panics
+    /// Panics if `generators_offset` is nonzero, which is not supported, or if `a` is longer than the
+    /// setup. The verifier rejects such proofs with `InvalidGeneratorsOffset` and `SmallSetup`
+    /// respectively.
+    #[tracing::instrument(
+        name = "HyperKZGCommitmentEvaluationProof::new",
+        level = "debug",
+        skip_all
+    )]
+    fn new(
+        transcript: &mut impl Transcript,
+        a: &[Self::Scalar],
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        setup: &Self::ProverPublicSetup<'_>,
+    ) -> Self {
+        assert_eq!(
+            generators_offset, 0,
+            "nonzero generators offsets are not supported"
+        );
+        assert!(
+            a.len() <= setup.len(),
+            "the setup is too small for a column of length {}",
+            a.len()
+        );
+        let nu = b_point.len();
+        if nu == 0 {
+            return Self::default();
+        }
+
+        // f_0, ..., f_{nu-1}
+        let mut folds = vec![a.to_vec()];
+        for &x in &b_point[..nu - 1] {
+            let folded = fold(&folds[folds.len() - 1], x);
+            folds.push(folded);
+        }
+        let com = G1Projective::normalize_batch(
+            &folds[1..]
+                .iter()
+                .map(|polynomial| commit(polynomial, setup))
+                .collect::<Vec<_>>(),
+        );
+        transcript.extend_canonical_serialize_as_le(&com);
+
+        let r: BNScalar = transcript.scalar_challenge_as_be();
+        let points = [r, -r, r * r];
+        let evaluations = folds
+            .iter()
+            .map(|polynomial| points.map(|point| evaluate(polynomial, point)))
+            .collect::<Vec<_>>();
+        transcript.extend_scalars_as_be(evaluations.iter().flatten());
+
+        let q: BNScalar = transcript.scalar_challenge_as_be();
+        let mut batched = vec![BNScalar::ZERO; a.len()];
+        for (polynomial, q_power) in folds.iter().zip(powers(q, nu)) {
+            for (sum, &coefficient) in batched.iter_mut().zip(polynomial) {
+                *sum += q_power * coefficient;
+            }
+        }
+        let witnesses = G1Projective::normalize_batch(
+            &points.map(|point| commit(&divide_by_linear(&batched, point), setup)),
+        );
+        transcript.extend_canonical_serialize_as_le(&witnesses);
+        // The verifier draws a challenge to combine the openings, which the prover has to draw as well
+        // to keep the transcripts in sync.
+        let _: BNScalar = transcript.scalar_challenge_as_be();
+
+        Self {
+            com,
+            v: evaluations,
+            w: witnesses,
+        }
+    }
+
+    #[tracing::instrument(
+        name = "HyperKZGCommitmentEvaluationProof::verify_batched_proof",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proof(
+        &self,
+        transcript: &mut impl Transcript,
+        commit_batch: &[Self::Commitment],
+        batching_factors: &[Self::Scalar],
+        evaluations: &[Self::Scalar],
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        table_length: usize,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Result<(), Self::Error> {
+        ensure!(
+            generators_offset == 0,
+            InvalidGeneratorsOffsetSnafu {
+                offset: generators_offset
+            }
+        );
+        ensure!(
+            table_length <= setup.num_generators,
+            SmallSetupSnafu {
+                actual: setup.num_generators,
+                required: table_length
+            }
+        );
+        let commit: G1Projective = commit_batch
+            .iter()
+            .zip(batching_factors)
+            .map(|(c, f)| c.0 * f.0)
+            .sum();
+        let product: Self::Scalar = evaluations
+            .iter()
+            .zip(batching_factors)
+            .map(|(&e, &f)| e * f)
+            .sum();
+
+        let nu = b_point.len();
+        if nu == 0 {
+            // The committed polynomial is the constant `product`.
+            ensure!(
+                self.com.is_empty()
+                    && self.v.is_empty()
+                    && self.w.is_empty()
+                    && commit == setup.g1 * product.0,
+                VerificationSnafu
+            );
+            return Ok(());
+        }
+        ensure!(
+            self.com.len() == nu - 1 && self.v.len() == nu && self.w.len() == 3,
+            VerificationSnafu
+        );
+
+        transcript.extend_canonical_serialize_as_le(&self.com);
+        let r: BNScalar = transcript.scalar_challenge_as_be();
+        ensure!(!r.is_zero(), VerificationSnafu);
+        transcript.extend_scalars_as_be(self.v.iter().flatten());
+        let q: BNScalar = transcript.scalar_challenge_as_be();
+        transcript.extend_canonical_serialize_as_le(&self.w);
+        let d: BNScalar = transcript.scalar_challenge_as_be();
+
+        // Check that f_{j+1}(r^2) is the fold of f_j(r) and f_j(-r), where f_nu is the constant `product`.
+        let folded_evaluations = self.v[1..].iter().map(|v| v[2]).chain([product]);
+        for ((&[at_r, at_minus_r, _], &x), folded) in
+            self.v.iter().zip(b_point).zip(folded_evaluations)
+        {
+            ensure!(
+                BNScalar::TWO * r * folded
+                    == r * (BNScalar::ONE - x) * (at_r + at_minus_r) + x * (at_r - at_minus_r),
+                VerificationSnafu
+            );
+        }
+
+        // Check the openings of the batched polynomial `B = f_0 + q * f_1 + ... + q^(nu-1) * f_{nu-1}`
+        // at `u_k` all at once, i.e. that `tau * W_k = C_B - B(u_k) * G + u_k * W_k`, combined with powers of `d`.
+        let q_powers = powers(q, nu);
+        let batched_commit = commit + msm(&self.com, &q_powers[1..]);
+        let u = [r, -r, r * r];
+        let d_powers = powers(d, 3);
+        let batched_evaluation: BNScalar = (0..3)
+            .map(|k| {
+                d_powers[k]
+                    * self
+                        .v
+                        .iter()
+                        .zip(&q_powers)
+                        .map(|(v, &q_power)| q_power * v[k])
+                        .sum::<BNScalar>()
+            })
+            .sum();
+        let d_sum: BNScalar = d_powers.iter().copied().sum();
+        let d_u = d_powers
+            .iter()
+            .zip(u)
+            .map(|(&d_power, u)| d_power * u)
+            .collect::<Vec<_>>();
+        let lhs = batched_commit * d_sum.0 - setup.g1 * batched_evaluation.0 + msm(&self.w, &d_u);
+        let rhs = msm(&self.w, &d_powers);
+        ensure!(
+            Bn254::multi_pairing([lhs, -rhs], [setup.g2, setup.tau_g2]) == PairingOutput::zero(),
+            VerificationSnafu
+        );
+        Ok(())
+    }
+}
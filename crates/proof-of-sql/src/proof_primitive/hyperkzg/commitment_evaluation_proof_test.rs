@@ -0,0 +1,197 @@
+use super::{BNScalar, HyperKZGCommitmentEvaluationProof, HyperKZGError, HyperKZGVerifierSetup};
+use crate::base::{
+    commitment::{
+        commitment_evaluation_proof_test::*, CommitmentEvaluationProof, VecCommitmentExt,
+    },
+    database::Column,
+};
+use ark_std::{test_rng, UniformRand};
+use merlin::Transcript;
+
+#[test]
+fn test_simple_ipa() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(4, &mut test_rng());
+    test_simple_commitment_evaluation_proof::<HyperKZGCommitmentEvaluationProof>(
+        &prover_setup.as_slice(),
+        &&verifier_setup,
+    );
+}
+
+#[test]
+fn test_random_ipa_with_length_1() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(4, &mut test_rng());
+    test_commitment_evaluation_proof_with_length_1::<HyperKZGCommitmentEvaluationProof>(
+        &prover_setup.as_slice(),
+        &&verifier_setup,
+    );
+}
+
+#[test]
+fn test_random_ipa_with_various_lengths() {
+    let lengths = [128, 100, 64, 50, 32, 20, 16, 10, 8, 5, 4, 3, 2];
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(128, &mut test_rng());
+    for length in lengths {
+        test_random_commitment_evaluation_proof::<HyperKZGCommitmentEvaluationProof>(
+            length,
+            0,
+            &prover_setup.as_slice(),
+            &&verifier_setup,
+        );
+    }
+}
+
+#[test]
+#[should_panic = "nonzero generators offsets are not supported"]
+fn test_random_ipa_panics_with_a_nonzero_offset() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(128, &mut test_rng());
+    test_random_commitment_evaluation_proof::<HyperKZGCommitmentEvaluationProof>(
+        64,
+        1,
+        &prover_setup.as_slice(),
+        &&verifier_setup,
+    );
+}
+
+/// Proves the evaluation of a random column of length `length` and verifies it against the
+/// commitment to the column at the given offset.
+///
+/// The prover and verifier setups share `tau`, but may have different numbers of generators.
+fn prove_and_verify_random_column(
+    length: usize,
+    b_point_length: usize,
+    generators_offset: u64,
+    prover_setup_length: usize,
+    verifier_setup_length: usize,
+) -> Result<(), HyperKZGError> {
+    let mut rng = test_rng();
+    let (setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(
+        prover_setup_length.max(verifier_setup_length) + 8,
+        &mut rng,
+    );
+    let prover_setup = &setup[..prover_setup_length];
+    let verifier_setup = HyperKZGVerifierSetup {
+        num_generators: verifier_setup_length,
+        ..verifier_setup
+    };
+    let a = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(length)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(b_point_length)
+        .collect::<Vec<_>>();
+    let mut b = vec![BNScalar::default(); a.len()];
+    crate::base::polynomial::compute_evaluation_vector(&mut b, &b_point);
+    let product: BNScalar = a.iter().zip(&b).map(|(&a, &b)| a * b).sum();
+    let commits = Vec::from_columns_with_offset(
+        [Column::Scalar(&a)],
+        usize::try_from(generators_offset).unwrap(),
+        &setup.as_slice(),
+    );
+
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof =
+        HyperKZGCommitmentEvaluationProof::new(&mut transcript, &a, &b_point, 0, &prover_setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        generators_offset,
+        a.len(),
+        &&verifier_setup,
+    )
+}
+
+#[test]
+fn we_cannot_verify_a_proof_with_a_nonzero_generators_offset() {
+    assert!(prove_and_verify_random_column(10, 4, 0, 16, 16).is_ok());
+    assert!(matches!(
+        prove_and_verify_random_column(10, 4, 3, 16, 16),
+        Err(HyperKZGError::InvalidGeneratorsOffset { offset: 3 })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_for_a_column_that_is_longer_than_the_setup() {
+    assert!(prove_and_verify_random_column(16, 4, 0, 16, 16).is_ok());
+    assert!(matches!(
+        prove_and_verify_random_column(17, 5, 0, 32, 16),
+        Err(HyperKZGError::SmallSetup {
+            actual: 16,
+            required: 17
+        })
+    ));
+}
+
+#[test]
+#[should_panic = "the setup is too small for a column of length 17"]
+fn we_cannot_prove_the_evaluation_of_a_column_that_is_longer_than_the_setup() {
+    let _ = prove_and_verify_random_column(17, 5, 0, 16, 32);
+}
+
+#[test]
+fn we_can_prove_and_verify_the_evaluation_of_a_column_of_length_1_at_an_empty_point() {
+    assert!(prove_and_verify_random_column(1, 0, 0, 1, 1).is_ok());
+    assert!(prove_and_verify_random_column(0, 0, 0, 1, 1).is_ok());
+}
+
+#[test]
+fn we_cannot_verify_a_proof_with_a_different_setup() {
+    let mut rng = test_rng();
+    let (prover_setup, _) = HyperKZGVerifierSetup::test_rand(16, &mut rng);
+    let (_, verifier_setup) = HyperKZGVerifierSetup::test_rand(16, &mut rng);
+    let a = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(10)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(4)
+        .collect::<Vec<_>>();
+    let mut b = vec![BNScalar::default(); a.len()];
+    crate::base::polynomial::compute_evaluation_vector(&mut b, &b_point);
+    let product: BNScalar = a.iter().zip(&b).map(|(&a, &b)| a * b).sum();
+    let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, &prover_setup.as_slice());
+
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = HyperKZGCommitmentEvaluationProof::new(
+        &mut transcript,
+        &a,
+        &b_point,
+        0,
+        &prover_setup.as_slice(),
+    );
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let result = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        0,
+        a.len(),
+        &&verifier_setup,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn we_can_serialize_and_deserialize_hyperkzg_evaluation_proofs() {
+    let mut rng = test_rng();
+    let (prover_setup, _) = HyperKZGVerifierSetup::test_rand(32, &mut rng);
+    let a = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(30)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(5)
+        .collect::<Vec<_>>();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = HyperKZGCommitmentEvaluationProof::new(
+        &mut transcript,
+        &a,
+        &b_point,
+        0,
+        &prover_setup.as_slice(),
+    );
+    let encoded = postcard::to_allocvec(&proof).unwrap();
+    let decoded: HyperKZGCommitmentEvaluationProof = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(decoded, proof);
+}
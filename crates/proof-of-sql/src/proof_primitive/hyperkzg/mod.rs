@@ -0,0 +1,29 @@
+//! HyperKZG is the multilinear polynomial commitment scheme from Gemini (<https://eprint.iacr.org/2022/420.pdf>),
+//! built on top of KZG commitments to univariate polynomials over the BN254 curve.
+//!
+//! A column `a` of length at most `2^nu` is committed to as the KZG commitment of the univariate polynomial
+//! `f(X) = a_0 + a_1 X + ... + a_{n-1} X^{n-1}`. To open the multilinear extension of `a` at a point `x`,
+//! the prover repeatedly folds `f` with the coordinates of `x`, commits to the folded polynomials,
+//! and opens all of them at `r`, `-r` and `r^2` for a random `r`. The verifier checks that the openings are consistent
+//! with the folding and checks all openings at once with a single pairing equation.
+//!
+//! Verification therefore only needs a few scalar multiplications and two pairings,
+//! which makes it cheap to verify with the BN254 precompiles of the EVM.
+//!
+//! This implementation only runs on the CPU and only supports columns at offset `0` in evaluation proofs.
+
+use ark_bn254::{Fr as F, G1Affine, G1Projective, G2Affine};
+
+mod scalar;
+pub use scalar::BNScalar;
+
+mod public_setup;
+pub use public_setup::{HyperKZGPublicSetup, HyperKZGVerifierSetup};
+
+mod commitment;
+pub use commitment::HyperKZGCommitment;
+
+mod commitment_evaluation_proof;
+pub use commitment_evaluation_proof::{HyperKZGCommitmentEvaluationProof, HyperKZGError};
+#[cfg(test)]
+mod commitment_evaluation_proof_test;
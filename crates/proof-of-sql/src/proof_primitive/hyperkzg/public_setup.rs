@@ -0,0 +1,62 @@
+use super::{G1Affine, G2Affine};
+#[cfg(any(test, feature = "test"))]
+use super::{G1Projective, F};
+#[cfg(any(test, feature = "test"))]
+use alloc::vec::Vec;
+#[cfg(any(test, feature = "test"))]
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(any(test, feature = "test"))]
+use ark_std::{rand::Rng, UniformRand};
+
+/// The public setup of the prover, which is also the public setup needed to compute commitments.
+///
+/// This is the powers of a secret `tau` times the generator of G1, i.e. `[G, tau * G, tau^2 * G, ...]`.
+/// Columns, and therefore tables, can be at most as long as the setup.
+pub type HyperKZGPublicSetup<'a> = &'a [G1Affine];
+
+/// The public setup of the verifier.
+///
+/// This is the generator of G1, the generator `H` of G2 and `tau * H`, where `tau` is the same secret
+/// as in the [`HyperKZGPublicSetup`] it is used with, along with the number of generators of that setup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct HyperKZGVerifierSetup {
+    pub(crate) g1: G1Affine,
+    pub(crate) g2: G2Affine,
+    pub(crate) tau_g2: G2Affine,
+    pub(crate) num_generators: usize,
+}
+
+impl HyperKZGVerifierSetup {
+    /// Creates a verifier setup from the generators of G1 and G2, the generator of G2 times `tau`
+    /// and the number of generators of the matching [`HyperKZGPublicSetup`].
+    #[must_use]
+    pub fn new(g1: G1Affine, g2: G2Affine, tau_g2: G2Affine, num_generators: usize) -> Self {
+        Self {
+            g1,
+            g2,
+            tau_g2,
+            num_generators,
+        }
+    }
+
+    /// Creates a prover setup with `num_generators` generators and the matching verifier setup from a random `tau`.
+    ///
+    /// This is only for testing, since `tau` has to be unknown for the scheme to be sound.
+    /// Use the result of a trusted setup ceremony otherwise.
+    #[cfg(any(test, feature = "test"))]
+    pub fn test_rand<R: Rng + ?Sized>(num_generators: usize, rng: &mut R) -> (Vec<G1Affine>, Self) {
+        let tau = F::rand(rng);
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let powers = core::iter::successors(Some(g1.into_group()), |power: &G1Projective| {
+            Some(*power * tau)
+        })
+        .take(num_generators)
+        .collect::<Vec<_>>();
+        (
+            G1Projective::normalize_batch(&powers),
+            Self::new(g1, g2, (g2 * tau).into_affine(), num_generators),
+        )
+    }
+}
@@ -0,0 +1,31 @@
+use crate::base::scalar::{MontScalar, Scalar};
+
+/// The BN254 scalar type. (alias for `MontScalar<ark_bn254::FrConfig>`)
+pub type BNScalar = MontScalar<ark_bn254::FrConfig>;
+
+impl Scalar for BNScalar {
+    const MAX_SIGNED: Self = Self(ark_ff::MontFp!(
+        "10944121435919637611123202872628637544274182200208017171849102093287904247808"
+    ));
+    const ZERO: Self = Self(ark_ff::MontFp!("0"));
+    const ONE: Self = Self(ark_ff::MontFp!("1"));
+    const TWO: Self = Self(ark_ff::MontFp!("2"));
+    const TEN: Self = Self(ark_ff::MontFp!("10"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BNScalar;
+    use crate::base::scalar::Scalar;
+    use num_traits::{One, Zero};
+
+    #[test]
+    fn we_can_get_the_constants_of_the_bn254_scalar() {
+        assert_eq!(BNScalar::ZERO, BNScalar::zero());
+        assert_eq!(BNScalar::ONE, BNScalar::one());
+        assert_eq!(BNScalar::TWO, BNScalar::from(2));
+        assert_eq!(BNScalar::TEN, BNScalar::from(10));
+        // `MAX_SIGNED` is `(p - 1) / 2`, so twice of it is `-1`.
+        assert_eq!(BNScalar::MAX_SIGNED + BNScalar::MAX_SIGNED, -BNScalar::ONE);
+    }
+}
@@ -2,5 +2,7 @@
 pub mod dory;
 /// Central location for any code that requires the use of a dynamic matrix (for now, hyrax and dynamic dory).
 pub(super) mod dynamic_matrix_utils;
+/// HyperKZG is a multilinear commitment scheme over BN254, whose proofs are cheap to verify on Ethereum.
+pub mod hyperkzg;
 /// TODO: add docs
 pub(crate) mod sumcheck;
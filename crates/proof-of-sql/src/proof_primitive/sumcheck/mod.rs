@@ -1,6 +1,7 @@
 mod proof;
 #[cfg(test)]
 mod proof_test;
+pub(crate) use proof::Subclaim;
 pub use proof::SumcheckProof;
 
 mod prover_state;
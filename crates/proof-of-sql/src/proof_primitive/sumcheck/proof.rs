@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SumcheckProof<S: Scalar> {
-    pub(super) coefficients: Vec<S>,
+    pub(crate) coefficients: Vec<S>,
 }
 pub struct Subclaim<S: Scalar> {
     pub evaluation_point: Vec<S>,
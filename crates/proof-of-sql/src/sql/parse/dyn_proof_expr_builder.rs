@@ -1,7 +1,11 @@
 use super::ConversionError;
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue},
+        database::{
+            character_ident, code_point, length_ident, presence_ident, try_avg_column_type,
+            try_percentile_cont_column_type, ColumnRef, ColumnType, LikePattern,
+            LikePatternElement, LiteralValue,
+        },
         map::IndexMap,
         math::{
             decimal::{DecimalError, Precision},
@@ -11,18 +15,20 @@ use crate::{
     },
     sql::{
         parse::{
+            cast_column_type,
             dyn_proof_expr_builder::DecimalError::{InvalidPrecision, InvalidScale},
+            interval_error, interval_operands,
             ConversionError::DecimalConversionError,
         },
         proof_exprs::{ColumnExpr, DynProofExpr, ProofExpr},
     },
 };
-use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString, vec::Vec};
 use proof_of_sql_parser::{
-    intermediate_ast::{AggregationOperator, Expression, Literal},
+    intermediate_ast::{AggregationOperator, Expression, Literal, Percentile, UnaryOperator},
     posql_time::{PoSQLTimeUnit, PoSQLTimestampError},
 };
-use sqlparser::ast::{BinaryOperator, Ident, UnaryOperator};
+use sqlparser::ast::{BinaryOperator, Ident};
 
 /// Builder that enables building a `proofs::sql::proof_exprs::DynProofExpr` from
 /// a `proof_of_sql_parser::intermediate_ast::Expression`.
@@ -47,46 +53,109 @@ impl<'a> DynProofExprBuilder<'a> {
         }
     }
     /// Builds a `proofs::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression`
+    ///
+    /// This fails if the expression may be NULL, see [`Self::build_nullable`].
     pub fn build(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
+        match self.visit_expr(expr)? {
+            (expr, None) => Ok(expr),
+            (_, Some(_)) => Err(ConversionError::Unprovable {
+                error: format!("Expression {expr:?} may be NULL"),
+            }),
+        }
+    }
+
+    /// Builds a `proofs::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression`
+    /// along with its presence, i.e. a boolean expression that is `true` exactly where the expression is not NULL.
+    ///
+    /// The presence is `None` if the expression is never NULL.
+    /// Where the expression is NULL, the value of the expression is arbitrary.
+    pub fn build_nullable(
+        &self,
+        expr: &Expression,
+    ) -> Result<(DynProofExpr, Option<DynProofExpr>), ConversionError> {
         self.visit_expr(expr)
     }
+
+    /// Builds an aggregation along with the presence of the aggregated expression.
+    ///
+    /// Since NULLs are skipped by aggregations, the aggregation itself is never NULL.
+    pub(crate) fn build_aggregation(
+        &self,
+        op: AggregationOperator,
+        expr: &Expression,
+    ) -> Result<(DynProofExpr, Option<DynProofExpr>), ConversionError> {
+        self.visit_aggregate_expr(op, expr)
+    }
 }
 
+/// A pair of an expression and its presence, see [`DynProofExprBuilder::build_nullable`].
+type NullableExpr = (DynProofExpr, Option<DynProofExpr>);
+
 #[allow(clippy::match_wildcard_for_single_variants)]
 // Private interface
 impl DynProofExprBuilder<'_> {
-    fn visit_expr(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
+    fn visit_expr(&self, expr: &Expression) -> Result<NullableExpr, ConversionError> {
         match expr {
             Expression::Column(identifier) => self.visit_column((*identifier).into()),
-            Expression::Literal(lit) => self.visit_literal(lit),
+            Expression::Literal(lit) => Ok((DynProofExpr::new_literal(self.visit_literal(lit)?), None)),
             Expression::Binary { op, left, right } => {
                 self.visit_binary_expr(&(*op).into(), left, right)
             }
-            Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
-            Expression::Aggregation { op, expr } => self.visit_aggregate_expr(*op, expr),
+            Expression::Unary { op, expr } => self.visit_unary_expr(*op, expr),
+            Expression::Like { expr, pattern } => self.visit_like_expr(expr, pattern),
+            Expression::InList { expr, list } => self.visit_in_list_expr(expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(expr, low, high),
+            Expression::Case {
+                conditions,
+                else_result,
+            } => self.visit_case_expr(conditions, else_result.as_deref()),
+            Expression::Cast { expr, data_type } => {
+                let (expr, presence) = self.visit_expr(expr)?;
+                let to_type = cast_column_type(expr.data_type(), *data_type)?;
+                Ok((DynProofExpr::try_new_cast(expr, to_type)?, presence))
+            }
+            Expression::DateTrunc { field, expr } => {
+                let (expr, presence) = self.visit_expr(expr)?;
+                Ok((DynProofExpr::try_new_date_trunc(*field, expr)?, presence))
+            }
+            Expression::Extract { field, expr } => {
+                let (expr, presence) = self.visit_expr(expr)?;
+                Ok((DynProofExpr::try_new_extract(*field, expr)?, presence))
+            }
+            Expression::Aggregation { op, expr } => match self.visit_aggregate_expr(*op, expr)? {
+                (aggregation, None) => Ok((aggregation, None)),
+                (_, Some(_)) => Err(ConversionError::Unprovable {
+                    error: format!("Aggregation of {expr:?}, which may be NULL, is not supported at this location"),
+                }),
+            },
             _ => Err(ConversionError::Unprovable {
                 error: format!("Expression {expr:?} is not supported yet"),
             }),
         }
     }
 
-    fn visit_column(&self, identifier: Ident) -> Result<DynProofExpr, ConversionError> {
-        Ok(DynProofExpr::Column(ColumnExpr::new(
+    fn visit_column(&self, identifier: Ident) -> Result<NullableExpr, ConversionError> {
+        let presence = self
+            .column_mapping
+            .get(&presence_ident(&identifier))
+            .map(|column_ref| DynProofExpr::new_column(column_ref.clone()));
+        let column = DynProofExpr::Column(ColumnExpr::new(
             self.column_mapping
                 .get(&identifier)
                 .ok_or(ConversionError::MissingColumnWithoutTable {
                     identifier: Box::new(identifier),
                 })?
                 .clone(),
-        )))
+        ));
+        Ok((column, presence))
     }
 
     #[allow(clippy::unused_self)]
-    fn visit_literal(&self, lit: &Literal) -> Result<DynProofExpr, ConversionError> {
+    fn visit_literal(&self, lit: &Literal) -> Result<LiteralValue, ConversionError> {
         match lit {
-            Literal::Boolean(b) => Ok(DynProofExpr::new_literal(LiteralValue::Boolean(*b))),
-            Literal::BigInt(i) => Ok(DynProofExpr::new_literal(LiteralValue::BigInt(*i))),
-            Literal::Int128(i) => Ok(DynProofExpr::new_literal(LiteralValue::Int128(*i))),
+            Literal::Boolean(b) => Ok(LiteralValue::Boolean(*b)),
+            Literal::BigInt(i) => Ok(LiteralValue::BigInt(*i)),
+            Literal::Int128(i) => Ok(LiteralValue::Int128(*i)),
             Literal::Decimal(d) => {
                 let raw_scale = d.scale();
                 let scale = raw_scale.try_into().map_err(|_| InvalidScale {
@@ -98,15 +167,15 @@ impl DynProofExprBuilder<'_> {
                             error: d.precision().to_string(),
                         },
                     })?;
-                Ok(DynProofExpr::new_literal(LiteralValue::Decimal75(
+                Ok(LiteralValue::Decimal75(
                     precision,
                     scale,
                     I256::from_num_bigint(
                         &d.try_into_bigint_with_precision_and_scale(precision.value(), scale)?,
                     ),
-                )))
+                ))
             }
-            Literal::VarChar(s) => Ok(DynProofExpr::new_literal(LiteralValue::VarChar(s.clone()))),
+            Literal::VarChar(s) => Ok(LiteralValue::VarChar(s.clone())),
             Literal::Timestamp(its) => {
                 let timestamp = match its.timeunit() {
                     PoSQLTimeUnit::Nanosecond => {
@@ -122,12 +191,13 @@ impl DynProofExprBuilder<'_> {
                     PoSQLTimeUnit::Second => its.timestamp().timestamp(),
                 };
 
-                Ok(DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                Ok(LiteralValue::TimeStampTZ(
                     its.timeunit(),
                     its.timezone(),
                     timestamp,
-                )))
+                ))
             }
+            Literal::Interval { .. } => Err(interval_error()),
         }
     }
 
@@ -135,15 +205,211 @@ impl DynProofExprBuilder<'_> {
         &self,
         op: UnaryOperator,
         expr: &Expression,
-    ) -> Result<DynProofExpr, ConversionError> {
-        let expr = self.visit_expr(expr);
+    ) -> Result<NullableExpr, ConversionError> {
+        let (expr, presence) = self.visit_expr(expr)?;
         match op {
-            UnaryOperator::Not => DynProofExpr::try_new_not(expr?),
-            // Handle unsupported operators
-            _ => Err(ConversionError::UnsupportedOperation {
-                message: format!("{op:?}"),
-            }),
+            // NOT NULL is NULL
+            UnaryOperator::Not => Ok((DynProofExpr::try_new_not(expr)?, presence)),
+            UnaryOperator::IsNull => Ok((
+                presence.map_or_else(
+                    || Ok(DynProofExpr::new_literal(LiteralValue::Boolean(false))),
+                    DynProofExpr::try_new_not,
+                )?,
+                None,
+            )),
+            UnaryOperator::IsNotNull => Ok((
+                presence.unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true))),
+                None,
+            )),
+        }
+    }
+
+    /// `LIKE` is proven by comparing the length and character columns of a column with the
+    /// pattern, so only patterns without `%` or with a single `%` at the end are supported. Any
+    /// other pattern is [`ConversionError::Unprovable`].
+    ///
+    /// The character columns are trusted to match the strings of the column, see
+    /// [`character_columns`](crate::base::database::character_columns).
+    fn visit_like_expr(
+        &self,
+        expr: &Expression,
+        pattern: &str,
+    ) -> Result<NullableExpr, ConversionError> {
+        let (column, presence) = self.visit_expr(expr)?;
+        let unprovable = |reason: &str| ConversionError::Unprovable {
+            error: format!("{expr:?} LIKE '{pattern}' {reason}"),
+        };
+        let like_pattern = LikePattern::new(pattern);
+        let (prefix, is_open) = like_pattern
+            .as_prefix()
+            .ok_or_else(|| unprovable("has a % before the end of the pattern"))?;
+        // Without wildcards, LIKE is just equality
+        if !is_open
+            && prefix
+                .iter()
+                .all(|element| matches!(element, LikePatternElement::Character(_)))
+        {
+            let string = prefix
+                .iter()
+                .filter_map(|element| match element {
+                    LikePatternElement::Character(c) => Some(*c),
+                    _ => None,
+                })
+                .collect();
+            let literal = DynProofExpr::new_literal(LiteralValue::VarChar(string));
+            return Ok((DynProofExpr::try_new_equals(column, literal)?, presence));
+        }
+        let Expression::Column(identifier) = expr else {
+            return Err(unprovable("does not match a column"));
+        };
+        let identifier = Ident::from(*identifier);
+        let committed_column = |ident: Ident| {
+            self.column_mapping
+                .get(&ident)
+                .map(|column_ref| DynProofExpr::new_column(column_ref.clone()))
+                .ok_or_else(|| unprovable("needs more committed characters of the column"))
+        };
+        let length = committed_column(length_ident(&identifier))?;
+        let num_characters = DynProofExpr::new_literal(LiteralValue::Int(
+            i32::try_from(prefix.len()).map_err(|_| unprovable("has too many characters"))?,
+        ));
+        let length_matches = if is_open {
+            DynProofExpr::try_new_inequality(length, num_characters, false, false)?
+        } else {
+            DynProofExpr::try_new_equals(length, num_characters)?
+        };
+        let like = prefix
+            .iter()
+            .enumerate()
+            .filter_map(|(index, element)| match element {
+                LikePatternElement::Character(c) => Some((index, *c)),
+                _ => None,
+            })
+            .try_fold(length_matches, |like, (index, c)| {
+                let character = committed_column(character_ident(&identifier, index))?;
+                let code_point = DynProofExpr::new_literal(LiteralValue::Int(code_point(c)));
+                DynProofExpr::try_new_and(
+                    like,
+                    DynProofExpr::try_new_equals(character, code_point)?,
+                )
+            })?;
+        Ok((like, presence))
+    }
+
+    /// An `IN` list of literals is proven with a single [`DynProofExpr::InList`], while any other
+    /// list falls back to comparing the expression with every item.
+    ///
+    /// # Panics
+    /// Panics if a list with a non-literal item is empty, which is impossible.
+    fn visit_in_list_expr(
+        &self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> Result<NullableExpr, ConversionError> {
+        let (value, presence) = self.visit_expr(expr)?;
+        let literals = list
+            .iter()
+            .map(|item| match item {
+                Expression::Literal(literal) => self.visit_literal(literal).map(Some),
+                _ => Ok(None),
+            })
+            .collect::<Result<Option<Vec<_>>, _>>()?;
+        if let Some(literals) = literals {
+            return Ok((DynProofExpr::try_new_in_list(value, literals)?, presence));
         }
+        list.iter()
+            .map(|item| {
+                let (item, item_presence) = self.visit_expr(item)?;
+                Ok((
+                    DynProofExpr::try_new_equals(value.clone(), item)?,
+                    both_present(presence.clone(), item_presence)?,
+                ))
+            })
+            .reduce(|is_in_list, is_item| {
+                let (is_in_list, is_in_list_presence) = is_in_list?;
+                let (is_item, is_item_presence) = is_item?;
+                Ok((
+                    DynProofExpr::try_new_or(is_in_list.clone(), is_item.clone())?,
+                    or_presence(&is_in_list, is_in_list_presence, &is_item, is_item_presence)?,
+                ))
+            })
+            .expect("the list contains a non-literal item")
+    }
+
+    /// `expr BETWEEN low AND high` is proven as `expr >= low AND expr <= high`.
+    fn visit_between_expr(
+        &self,
+        expr: &Expression,
+        low: &Expression,
+        high: &Expression,
+    ) -> Result<NullableExpr, ConversionError> {
+        let (value, presence) = self.visit_expr(expr)?;
+        let (low, low_presence) = self.visit_expr(low)?;
+        let (high, high_presence) = self.visit_expr(high)?;
+        let is_above_low = DynProofExpr::try_new_inequality(value.clone(), low, false, false)?;
+        let is_above_low_presence = both_present(presence.clone(), low_presence)?;
+        let is_below_high = DynProofExpr::try_new_inequality(value, high, true, false)?;
+        let is_below_high_presence = both_present(presence, high_presence)?;
+        Ok((
+            DynProofExpr::try_new_and(is_above_low.clone(), is_below_high.clone())?,
+            and_presence(
+                &is_above_low,
+                is_above_low_presence,
+                &is_below_high,
+                is_below_high_presence,
+            )?,
+        ))
+    }
+
+    /// A `CASE` with several `WHEN` clauses is proven as nested `CASE`s with a single `WHEN` each.
+    ///
+    /// A condition that is NULL counts as `false` and a missing `ELSE` is NULL.
+    fn visit_case_expr(
+        &self,
+        conditions: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> Result<NullableExpr, ConversionError> {
+        let (_, last_result) =
+            conditions
+                .last()
+                .ok_or_else(|| ConversionError::InvalidExpression {
+                    expression: "CASE without WHEN clauses".to_string(),
+                })?;
+        let otherwise = match else_result {
+            Some(else_result) => self.visit_expr(else_result)?,
+            // The value is arbitrary where the result is NULL, so any value of a fitting type works
+            None => (
+                self.visit_expr(last_result)?.0,
+                Some(DynProofExpr::new_literal(LiteralValue::Boolean(false))),
+            ),
+        };
+        conditions.iter().rev().try_fold(
+            otherwise,
+            |(otherwise, otherwise_presence), (condition, result)| {
+                let condition = match self.visit_expr(condition)? {
+                    (condition, Some(condition_presence)) => {
+                        DynProofExpr::try_new_and(condition, condition_presence)?
+                    }
+                    (condition, None) => condition,
+                };
+                let (result, result_presence) = self.visit_expr(result)?;
+                let presence = match (result_presence, otherwise_presence) {
+                    (None, None) => None,
+                    (result_presence, otherwise_presence) => {
+                        let present = || DynProofExpr::new_literal(LiteralValue::Boolean(true));
+                        Some(DynProofExpr::try_new_case(
+                            condition.clone(),
+                            result_presence.unwrap_or_else(present),
+                            otherwise_presence.unwrap_or_else(present),
+                        )?)
+                    }
+                };
+                Ok((
+                    DynProofExpr::try_new_case(condition, result, otherwise)?,
+                    presence,
+                ))
+            },
+        )
     }
 
     fn visit_binary_expr(
@@ -151,56 +417,58 @@ impl DynProofExprBuilder<'_> {
         op: &BinaryOperator,
         left: &Expression,
         right: &Expression,
-    ) -> Result<DynProofExpr, ConversionError> {
+    ) -> Result<NullableExpr, ConversionError> {
+        if let Some((expr, value, field)) = interval_operands(op, left, right)? {
+            let (expr, presence) = self.visit_expr(expr)?;
+            return Ok((
+                DynProofExpr::try_new_add_interval(expr, value, field)?,
+                presence,
+            ));
+        }
+        let (left, left_presence) = self.visit_expr(left)?;
+        let (right, right_presence) = self.visit_expr(right)?;
         match op {
-            BinaryOperator::And => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_and(left?, right?)
-            }
-            BinaryOperator::Or => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_or(left?, right?)
-            }
-            BinaryOperator::Eq => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_equals(left?, right?)
-            }
-            BinaryOperator::GtEq => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_inequality(left?, right?, false)
-            }
-            BinaryOperator::LtEq => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_inequality(left?, right?, true)
-            }
-            BinaryOperator::Plus => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_add(left?, right?)
-            }
-            BinaryOperator::Minus => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_subtract(left?, right?)
-            }
-            BinaryOperator::Multiply => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_multiply(left?, right?)
-            }
-            BinaryOperator::Divide => Err(ConversionError::Unprovable {
-                error: format!("Binary operator {op:?} is not supported at this location"),
-            }),
+            BinaryOperator::And => Ok((
+                DynProofExpr::try_new_and(left.clone(), right.clone())?,
+                and_presence(&left, left_presence, &right, right_presence)?,
+            )),
+            BinaryOperator::Or => Ok((
+                DynProofExpr::try_new_or(left.clone(), right.clone())?,
+                or_presence(&left, left_presence, &right, right_presence)?,
+            )),
             _ => {
-                // Handle unsupported binary operations
-                Err(ConversionError::UnsupportedOperation {
-                    message: format!("{op:?}"),
-                })
+                let presence = both_present(left_presence, right_presence)?;
+                let right = match (op, &presence) {
+                    (BinaryOperator::Divide, Some(presence)) => {
+                        divisor_where_present(right, presence.clone())?
+                    }
+                    _ => right,
+                };
+                let expr = match op {
+                    BinaryOperator::Eq => DynProofExpr::try_new_equals(left, right),
+                    BinaryOperator::NotEq => DynProofExpr::try_new_not_equals(left, right),
+                    BinaryOperator::Lt => DynProofExpr::try_new_inequality(left, right, true, true),
+                    BinaryOperator::LtEq => {
+                        DynProofExpr::try_new_inequality(left, right, true, false)
+                    }
+                    BinaryOperator::Gt => {
+                        DynProofExpr::try_new_inequality(left, right, false, true)
+                    }
+                    BinaryOperator::GtEq => {
+                        DynProofExpr::try_new_inequality(left, right, false, false)
+                    }
+                    BinaryOperator::Plus => DynProofExpr::try_new_add(left, right),
+                    BinaryOperator::Minus => DynProofExpr::try_new_subtract(left, right),
+                    BinaryOperator::Multiply => DynProofExpr::try_new_multiply(left, right),
+                    BinaryOperator::Divide => DynProofExpr::try_new_divide(left, right),
+                    _ => {
+                        // Handle unsupported binary operations
+                        Err(ConversionError::UnsupportedOperation {
+                            message: format!("{op:?}"),
+                        })
+                    }
+                }?;
+                Ok((expr, presence))
             }
         }
     }
@@ -209,16 +477,41 @@ impl DynProofExprBuilder<'_> {
         &self,
         op: AggregationOperator,
         expr: &Expression,
-    ) -> Result<DynProofExpr, ConversionError> {
+    ) -> Result<NullableExpr, ConversionError> {
         if self.in_agg_scope {
             return Err(ConversionError::InvalidExpression {
                 expression: "nested aggregations are invalid".to_string(),
             });
         }
-        let expr = DynProofExprBuilder::new_agg(self.column_mapping).visit_expr(expr)?;
+        let (expr, presence) = match (op, expr) {
+            // COUNT(*) is the same as COUNT(1)
+            (AggregationOperator::Count, Expression::Wildcard) => {
+                (DynProofExpr::new_literal(LiteralValue::BigInt(1)), None)
+            }
+            _ => DynProofExprBuilder::new_agg(self.column_mapping).visit_expr(expr)?,
+        };
         match (op, expr.data_type().is_numeric()) {
-            (AggregationOperator::Count, _) | (AggregationOperator::Sum, true) => {
-                Ok(DynProofExpr::new_aggregate(op, expr))
+            (AggregationOperator::Count, _)
+            | (
+                AggregationOperator::Sum | AggregationOperator::Max | AggregationOperator::Min,
+                true,
+            ) => Ok((DynProofExpr::new_aggregate(op, expr), presence)),
+            (AggregationOperator::Avg, _) => {
+                try_avg_column_type(expr.data_type())?;
+                Ok((DynProofExpr::new_aggregate(op, expr), presence))
+            }
+            (AggregationOperator::Median, _) => {
+                try_percentile_cont_column_type(expr.data_type(), Percentile::MEDIAN)?;
+                Ok((DynProofExpr::new_aggregate(op, expr), presence))
+            }
+            (AggregationOperator::PercentileCont(percentile), _) => {
+                try_percentile_cont_column_type(expr.data_type(), percentile)?;
+                Ok((DynProofExpr::new_aggregate(op, expr), presence))
+            }
+            (AggregationOperator::PercentileDisc(_), true)
+                if expr.data_type() != ColumnType::Scalar =>
+            {
+                Ok((DynProofExpr::new_aggregate(op, expr), presence))
             }
             (AggregationOperator::Sum, false) => Err(ConversionError::InvalidExpression {
                 expression: format!(
@@ -231,3 +524,95 @@ impl DynProofExprBuilder<'_> {
         }
     }
 }
+
+/// The presence of an expression that is NULL if either of its operands is NULL.
+fn both_present(
+    left_presence: Option<DynProofExpr>,
+    right_presence: Option<DynProofExpr>,
+) -> Result<Option<DynProofExpr>, ConversionError> {
+    match (left_presence, right_presence) {
+        (Some(left_presence), Some(right_presence)) => {
+            DynProofExpr::try_new_and(left_presence, right_presence).map(Some)
+        }
+        (presence, None) | (None, presence) => Ok(presence),
+    }
+}
+
+/// The divisor of a quotient that is NULL where `presence` is `false`, with a nonzero value on those rows.
+///
+/// Dividing NULL by zero is NULL rather than a division by zero, which this makes sure of.
+/// Divisors that are not numeric are returned as they are, since they are rejected anyway.
+fn divisor_where_present(
+    divisor: DynProofExpr,
+    presence: DynProofExpr,
+) -> Result<DynProofExpr, ConversionError> {
+    let nonzero = match divisor.data_type() {
+        ColumnType::TinyInt => LiteralValue::TinyInt(1),
+        ColumnType::SmallInt => LiteralValue::SmallInt(1),
+        ColumnType::Int => LiteralValue::Int(1),
+        ColumnType::BigInt => LiteralValue::BigInt(1),
+        ColumnType::Int128 => LiteralValue::Int128(1),
+        ColumnType::Decimal75(precision, scale) => {
+            LiteralValue::Decimal75(precision, scale, I256::from(1))
+        }
+        _ => return Ok(divisor),
+    };
+    DynProofExpr::try_new_case(presence, divisor, DynProofExpr::new_literal(nonzero))
+}
+
+/// The presence of `left AND right` in three-valued logic.
+///
+/// The conjunction is known if both operands are known or if either operand is known to be `false`.
+fn and_presence(
+    left: &DynProofExpr,
+    left_presence: Option<DynProofExpr>,
+    right: &DynProofExpr,
+    right_presence: Option<DynProofExpr>,
+) -> Result<Option<DynProofExpr>, ConversionError> {
+    let left_is_false = DynProofExpr::try_new_not(left.clone())?;
+    let right_is_false = DynProofExpr::try_new_not(right.clone())?;
+    known_if_either_is(left_presence, left_is_false, right_presence, right_is_false)
+}
+
+/// The presence of `left OR right` in three-valued logic.
+///
+/// The disjunction is known if both operands are known or if either operand is known to be `true`.
+fn or_presence(
+    left: &DynProofExpr,
+    left_presence: Option<DynProofExpr>,
+    right: &DynProofExpr,
+    right_presence: Option<DynProofExpr>,
+) -> Result<Option<DynProofExpr>, ConversionError> {
+    known_if_either_is(left_presence, left.clone(), right_presence, right.clone())
+}
+
+/// The presence of a binary logical expression that is known if both operands are known or if
+/// either operand is known and `decisive`.
+fn known_if_either_is(
+    left_presence: Option<DynProofExpr>,
+    left_decisive: DynProofExpr,
+    right_presence: Option<DynProofExpr>,
+    right_decisive: DynProofExpr,
+) -> Result<Option<DynProofExpr>, ConversionError> {
+    match (left_presence, right_presence) {
+        (None, None) => Ok(None),
+        (Some(left_presence), None) => {
+            DynProofExpr::try_new_or(left_presence, right_decisive).map(Some)
+        }
+        (None, Some(right_presence)) => {
+            DynProofExpr::try_new_or(right_presence, left_decisive).map(Some)
+        }
+        (Some(left_presence), Some(right_presence)) => {
+            let both_known =
+                DynProofExpr::try_new_and(left_presence.clone(), right_presence.clone())?;
+            let left_known_and_decisive = DynProofExpr::try_new_and(left_presence, left_decisive)?;
+            let right_known_and_decisive =
+                DynProofExpr::try_new_and(right_presence, right_decisive)?;
+            DynProofExpr::try_new_or(
+                DynProofExpr::try_new_or(both_known, left_known_and_decisive)?,
+                right_known_and_decisive,
+            )
+            .map(Some)
+        }
+    }
+}
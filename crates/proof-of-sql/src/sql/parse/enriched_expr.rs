@@ -16,6 +16,8 @@ pub struct EnrichedExpr {
     pub residue_expression: AliasedResultExpr,
     /// The extracted provable expression plan if it exists.
     pub dyn_proof_expr: Option<DynProofExpr>,
+    /// The presence of the provable expression plan if it may be NULL.
+    pub presence_expr: Option<DynProofExpr>,
 }
 
 impl EnrichedExpr {
@@ -28,9 +30,9 @@ impl EnrichedExpr {
         // TODO: Using new_agg (ironically) disables aggregations in `QueryExpr` for now.
         // Re-enable aggregations when we add `GroupByExec` generalizations.
         let res_dyn_proof_expr =
-            DynProofExprBuilder::new_agg(column_mapping).build(&expression.expr);
+            DynProofExprBuilder::new_agg(column_mapping).build_nullable(&expression.expr);
         match res_dyn_proof_expr {
-            Ok(dyn_proof_expr) => {
+            Ok((dyn_proof_expr, presence_expr)) => {
                 let alias = expression.alias;
                 Self {
                     residue_expression: AliasedResultExpr {
@@ -38,11 +40,13 @@ impl EnrichedExpr {
                         alias,
                     },
                     dyn_proof_expr: Some(dyn_proof_expr),
+                    presence_expr,
                 }
             }
             Err(_) => Self {
                 residue_expression: expression,
                 dyn_proof_expr: None,
+                presence_expr: None,
             },
         }
     }
@@ -1,7 +1,7 @@
 use super::{where_expr_builder::WhereExprBuilder, ConversionError, EnrichedExpr};
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{presence_ident, ColumnRef, LiteralValue, TableRef},
         map::IndexMap,
     },
     sql::{
@@ -54,10 +54,23 @@ impl FilterExecBuilder {
         let mut has_nonprovable_column = false;
         for enriched_expr in columns {
             if let Some(plan) = &enriched_expr.dyn_proof_expr {
-                self.filter_result_expr_list.push(AliasedDynProofExpr {
-                    expr: plan.clone(),
-                    alias: enriched_expr.residue_expression.alias.into(),
-                });
+                let alias: Ident = enriched_expr.residue_expression.alias.into();
+                // A nullable column is followed by its presence column
+                if let Some(presence) = &enriched_expr.presence_expr {
+                    self.filter_result_expr_list.push(AliasedDynProofExpr {
+                        expr: plan.clone(),
+                        alias: alias.clone(),
+                    });
+                    self.filter_result_expr_list.push(AliasedDynProofExpr {
+                        expr: presence.clone(),
+                        alias: presence_ident(&alias),
+                    });
+                } else {
+                    self.filter_result_expr_list.push(AliasedDynProofExpr {
+                        expr: plan.clone(),
+                        alias,
+                    });
+                }
             } else {
                 has_nonprovable_column = true;
             }
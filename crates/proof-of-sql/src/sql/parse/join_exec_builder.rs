@@ -0,0 +1,612 @@
+use super::{ConversionError, ConversionResult, WhereExprBuilder};
+use crate::{
+    base::{
+        database::{
+            is_character_ident, is_presence_ident, ColumnField, ColumnRef, ColumnType,
+            SchemaAccessor, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+    },
+    sql::{
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{DynProofPlan, FilterExec, JoinExec, ProjectionExec, TableExec},
+    },
+};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal,
+        QualifiedColumn, SelectResultExpr, TableExpression,
+    },
+    Identifier, ResourceId,
+};
+use sqlparser::ast::Ident;
+
+/// Builder that turns a `FROM` clause containing joins into a [`DynProofPlan`] built out of [`JoinExec`]s.
+///
+/// Only the columns which are referenced by the query are passed through the joins.
+/// The conjuncts of the `WHERE` clause are pushed down to the tables they reference so that
+/// each of them has to reference columns of a single table.
+pub struct JoinExecBuilder<'a> {
+    schema_accessor: &'a dyn SchemaAccessor,
+    default_schema: Identifier,
+}
+
+/// The result of [`JoinExecBuilder::build`]
+pub struct JoinExecBuilderResult {
+    /// The provable part of the query
+    pub plan: DynProofPlan,
+    /// The result expressions in terms of the output columns of `plan`
+    pub result_exprs: Vec<AliasedResultExpr>,
+    /// The group by identifiers in terms of the output columns of `plan`
+    pub group_by: Vec<Ident>,
+}
+
+/// A `FROM` clause with all of its tables and join columns resolved
+enum JoinTree {
+    Table {
+        table_ref: TableRef,
+        schema: Vec<(Ident, ColumnType)>,
+    },
+    Join {
+        left: Box<JoinTree>,
+        right: Box<JoinTree>,
+        left_on: ColumnRef,
+        right_on: ColumnRef,
+    },
+}
+
+impl<'a> JoinExecBuilder<'a> {
+    pub fn new(schema_accessor: &'a dyn SchemaAccessor, default_schema: Identifier) -> Self {
+        Self {
+            schema_accessor,
+            default_schema,
+        }
+    }
+
+    /// Build the plan for `table_expr` along with the result expressions and the group by
+    /// identifiers rewritten in terms of the output columns of the plan.
+    ///
+    /// Only columns are supported as group by expressions.
+    pub fn build(
+        self,
+        table_expr: &TableExpression,
+        result_exprs: Vec<SelectResultExpr>,
+        where_expr: Option<Box<Expression>>,
+        mut group_by: Vec<Expression>,
+    ) -> ConversionResult<JoinExecBuilderResult> {
+        let tree = self.visit_table_expr(table_expr)?;
+        let mut result_exprs = result_exprs
+            .into_iter()
+            .map(|result_expr| match result_expr {
+                SelectResultExpr::ALL => tree.visible_columns(),
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => Ok(vec![aliased_expr]),
+            })
+            .collect::<ConversionResult<Vec<_>>>()?
+            .concat();
+        for aliased_expr in &mut result_exprs {
+            replace_count_wildcard(&mut aliased_expr.expr);
+        }
+        let where_conjuncts = where_expr
+            .map(|where_expr| conjuncts(*where_expr))
+            .unwrap_or_default();
+
+        // Name each referenced column after the first identifier referring to it
+        let mut output_names: IndexMap<ColumnRef, Identifier> = IndexMap::default();
+        for expr in result_exprs
+            .iter_mut()
+            .map(|aliased_expr| aliased_expr.expr.as_mut())
+            .chain(group_by.iter_mut())
+        {
+            try_rewrite_columns(expr, &mut |identifier| {
+                let column = tree.resolve(identifier)?;
+                Ok(*output_names.entry(column).or_insert(identifier))
+            })?;
+        }
+
+        // Push the conjuncts of the WHERE clause down to the tables
+        let mut table_filters: IndexMap<TableRef, Vec<Expression>> = IndexMap::default();
+        for mut conjunct in where_conjuncts {
+            let mut table_refs = Vec::new();
+            try_rewrite_columns(&mut conjunct, &mut |identifier| {
+                let column = tree.resolve(identifier)?;
+                if !table_refs.contains(&column.table_ref()) {
+                    table_refs.push(column.table_ref());
+                }
+                Identifier::try_from(column.column_id()).map_err(|e| {
+                    ConversionError::IdentifierConversionError {
+                        error: e.to_string(),
+                    }
+                })
+            })?;
+            match table_refs[..] {
+                [] => table_filters
+                    .entry(tree.first_table_ref())
+                    .or_default()
+                    .push(conjunct),
+                [table_ref] => table_filters.entry(table_ref).or_default().push(conjunct),
+                _ => Err(ConversionError::Unprovable {
+                    error: "WHERE clauses referencing both sides of a join are not supported"
+                        .to_string(),
+                })?,
+            }
+        }
+
+        let group_by = group_by
+            .into_iter()
+            .map(|expr| match expr {
+                Expression::Column(identifier) => Ok(identifier.into()),
+                _ => Err(ConversionError::Unprovable {
+                    error: "GROUP BY expressions other than columns in queries with joins"
+                        .to_string(),
+                }),
+            })
+            .collect::<ConversionResult<_>>()?;
+
+        let join_columns = tree.join_columns();
+        let (plan, _) = build_plan(&tree, &output_names, &join_columns, &table_filters)?;
+        Ok(JoinExecBuilderResult {
+            plan,
+            result_exprs,
+            group_by,
+        })
+    }
+}
+
+// Private interface
+impl JoinExecBuilder<'_> {
+    fn visit_table_expr(&self, table_expr: &TableExpression) -> ConversionResult<JoinTree> {
+        match table_expr {
+            TableExpression::Named { table, schema } => {
+                let table_ref = TableRef::new(ResourceId::new(
+                    schema.unwrap_or(self.default_schema),
+                    *table,
+                ));
+                let mut schema = self.schema_accessor.lookup_schema(table_ref);
+                if schema.is_empty() {
+                    Err(ConversionError::InvalidExpression {
+                        expression: format!("table '{table_ref}' does not exist"),
+                    })?;
+                }
+                if schema.iter().any(|(ident, _)| is_presence_ident(ident)) {
+                    Err(ConversionError::Unprovable {
+                        error: format!("joins of table '{table_ref}' with nullable columns"),
+                    })?;
+                }
+                // Character columns only serve to prove `LIKE`, which is not supported in joins
+                schema.retain(|(ident, _)| !is_character_ident(ident));
+                Ok(JoinTree::Table { table_ref, schema })
+            }
+            TableExpression::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+            } => {
+                let left = self.visit_table_expr(left)?;
+                let right = self.visit_table_expr(right)?;
+                // The join condition may list the columns in either order
+                let (left_on, right_on) = match (
+                    left.resolve_qualified(left_on)?,
+                    right.resolve_qualified(right_on)?,
+                ) {
+                    (Some(left_on), Some(right_on)) => (left_on, right_on),
+                    _ => (
+                        left.resolve_qualified(right_on)?
+                            .ok_or_else(|| missing_qualified_column(right_on))?,
+                        right
+                            .resolve_qualified(left_on)?
+                            .ok_or_else(|| missing_qualified_column(left_on))?,
+                    ),
+                };
+                check_join_column_types(*left_on.column_type(), *right_on.column_type())?;
+                Ok(JoinTree::Join {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    left_on,
+                    right_on,
+                })
+            }
+            TableExpression::Subquery { .. } => {
+                unreachable!("Subqueries are planned by SubqueryExecBuilder")
+            }
+        }
+    }
+}
+
+impl JoinTree {
+    /// All the columns of the tables in the tree along with the column they are equal to, if any.
+    ///
+    /// The right join column of a join is replaced by its left join column.
+    fn columns(&self) -> Vec<(ColumnRef, ColumnRef)> {
+        match self {
+            JoinTree::Table { table_ref, schema } => schema
+                .iter()
+                .map(|(column_id, column_type)| {
+                    let column = ColumnRef::new(*table_ref, column_id.clone(), *column_type);
+                    (column.clone(), column)
+                })
+                .collect(),
+            JoinTree::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+            } => left
+                .columns()
+                .into_iter()
+                .chain(right.columns().into_iter().map(|(column, canonical)| {
+                    if &canonical == right_on {
+                        (column, left_on.clone())
+                    } else {
+                        (column, canonical)
+                    }
+                }))
+                .collect(),
+        }
+    }
+
+    /// The join columns of all the joins in the tree
+    fn join_columns(&self) -> Vec<ColumnRef> {
+        match self {
+            JoinTree::Table { .. } => vec![],
+            JoinTree::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+            } => [
+                left.join_columns(),
+                right.join_columns(),
+                vec![left_on.clone(), right_on.clone()],
+            ]
+            .concat(),
+        }
+    }
+
+    fn first_table_ref(&self) -> TableRef {
+        match self {
+            JoinTree::Table { table_ref, .. } => *table_ref,
+            JoinTree::Join { left, .. } => left.first_table_ref(),
+        }
+    }
+
+    /// Resolve the column, which may be qualified by its table name, to the column it is equal to.
+    fn resolve_qualified(&self, column: &QualifiedColumn) -> ConversionResult<Option<ColumnRef>> {
+        let column_id: Ident = column.column.into();
+        let candidates = self
+            .columns()
+            .into_iter()
+            .filter(|(column_ref, _)| {
+                column_ref.column_id() == column_id
+                    && column.table.map_or(true, |table| {
+                        column_ref.table_ref().table_id() == table.into()
+                    })
+            })
+            .map(|(_, canonical)| canonical)
+            .collect::<IndexSet<_>>();
+        match candidates.len() {
+            0 | 1 => Ok(candidates.into_iter().next()),
+            _ => Err(ConversionError::InvalidExpression {
+                expression: format!("column '{column_id}' is ambiguous"),
+            }),
+        }
+    }
+
+    /// Resolve an unqualified column to the column it is equal to.
+    fn resolve(&self, identifier: Identifier) -> ConversionResult<ColumnRef> {
+        self.resolve_qualified(&QualifiedColumn {
+            table: None,
+            column: identifier,
+        })?
+        .ok_or_else(|| ConversionError::MissingColumnWithoutTable {
+            identifier: Box::new(identifier.into()),
+        })
+    }
+
+    /// The columns `SELECT *` expands to, i.e. all the columns other than the right join columns
+    fn visible_columns(&self) -> ConversionResult<Vec<AliasedResultExpr>> {
+        self.columns()
+            .into_iter()
+            .filter(|(column, canonical)| column == canonical)
+            .map(|(column, _)| {
+                let identifier = Identifier::try_from(column.column_id()).map_err(|e| {
+                    ConversionError::IdentifierConversionError {
+                        error: e.to_string(),
+                    }
+                })?;
+                Ok(AliasedResultExpr::new(
+                    Expression::Column(identifier),
+                    identifier,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Build the plan for `tree` along with the columns it outputs.
+///
+/// Only the columns named in `output_names` and the `join_columns` are output.
+///
+/// # Panics
+/// Panics if a table in `table_filters` has no filters, which can not happen since they are only
+/// added along with a filter.
+fn build_plan(
+    tree: &JoinTree,
+    output_names: &IndexMap<ColumnRef, Identifier>,
+    join_columns: &[ColumnRef],
+    table_filters: &IndexMap<TableRef, Vec<Expression>>,
+) -> ConversionResult<(DynProofPlan, Vec<ColumnRef>)> {
+    match tree {
+        JoinTree::Table { table_ref, schema } => {
+            let columns = schema
+                .iter()
+                .map(|(column_id, column_type)| {
+                    ColumnRef::new(*table_ref, column_id.clone(), *column_type)
+                })
+                .collect::<Vec<_>>();
+            let output_columns = columns
+                .iter()
+                .filter(|column| {
+                    output_names.contains_key(*column) || join_columns.contains(column)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            let plan = match table_filters.get(table_ref) {
+                Some(filters) => {
+                    let column_mapping = columns
+                        .into_iter()
+                        .map(|column| (column.column_id(), column))
+                        .collect::<IndexMap<_, _>>();
+                    let where_expr = filters
+                        .iter()
+                        .cloned()
+                        .reduce(|left, right| Expression::Binary {
+                            op: BinaryOperator::And,
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        })
+                        .map(Box::new);
+                    let where_clause = WhereExprBuilder::new(&column_mapping)
+                        .build(where_expr)?
+                        .expect("There is at least one filter");
+                    DynProofPlan::Filter(FilterExec::new(
+                        column_exprs(&output_columns),
+                        TableExpr {
+                            table_ref: *table_ref,
+                        },
+                        where_clause,
+                    ))
+                }
+                None if output_columns.len() == schema.len() => {
+                    DynProofPlan::Table(TableExec::new(
+                        *table_ref,
+                        output_columns
+                            .iter()
+                            .map(|column| {
+                                ColumnField::new(column.column_id(), *column.column_type())
+                            })
+                            .collect(),
+                    ))
+                }
+                None => DynProofPlan::Projection(ProjectionExec::new(
+                    column_exprs(&output_columns),
+                    TableExpr {
+                        table_ref: *table_ref,
+                    },
+                )),
+            };
+            Ok((plan, output_columns))
+        }
+        JoinTree::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+        } => {
+            let (left_plan, left_columns) =
+                build_plan(left, output_names, join_columns, table_filters)?;
+            let (right_plan, right_columns) =
+                build_plan(right, output_names, join_columns, table_filters)?;
+            let left_join_column_index = position(&left_columns, left_on);
+            let right_join_column_index = position(&right_columns, right_on);
+            let output_columns = left_columns
+                .into_iter()
+                .chain(
+                    right_columns
+                        .into_iter()
+                        .filter(|column| column != right_on),
+                )
+                .collect::<Vec<_>>();
+            // Columns which are only needed to compute the join get a name which can't clash with an identifier
+            let result_idents = output_columns
+                .iter()
+                .map(|column| {
+                    output_names.get(column).map_or_else(
+                        || Ident::new(format!("{}.{}", column.table_ref(), column.column_id())),
+                        |&identifier| identifier.into(),
+                    )
+                })
+                .collect();
+            let plan = DynProofPlan::Join(JoinExec::new(
+                Box::new(left_plan),
+                Box::new(right_plan),
+                left_join_column_index,
+                right_join_column_index,
+                result_idents,
+            ));
+            Ok((plan, output_columns))
+        }
+    }
+}
+
+/// Split an expression into the conjuncts of the top level `AND`s
+fn conjuncts(expr: Expression) -> Vec<Expression> {
+    match expr {
+        Expression::Binary {
+            op: BinaryOperator::And,
+            left,
+            right,
+        } => [conjuncts(*left), conjuncts(*right)].concat(),
+        expr => vec![expr],
+    }
+}
+
+/// Replace every column identifier in `expr` using `f`
+fn try_rewrite_columns(
+    expr: &mut Expression,
+    f: &mut impl FnMut(Identifier) -> ConversionResult<Identifier>,
+) -> ConversionResult<()> {
+    match expr {
+        Expression::Column(identifier) => *identifier = f(*identifier)?,
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. } => {
+            try_rewrite_columns(expr, f)?;
+        }
+        Expression::Binary { left, right, .. } => {
+            try_rewrite_columns(left, f)?;
+            try_rewrite_columns(right, f)?;
+        }
+        Expression::InList { expr, list } => {
+            try_rewrite_columns(expr, f)?;
+            for item in list {
+                try_rewrite_columns(item, f)?;
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            try_rewrite_columns(expr, f)?;
+            try_rewrite_columns(low, f)?;
+            try_rewrite_columns(high, f)?;
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            for (condition, result) in conditions {
+                try_rewrite_columns(condition, f)?;
+                try_rewrite_columns(result, f)?;
+            }
+            if let Some(else_result) = else_result {
+                try_rewrite_columns(else_result, f)?;
+            }
+        }
+        Expression::Literal(_) | Expression::Wildcard => {}
+        Expression::InSubquery { .. } | Expression::Subquery(_) => {
+            Err(ConversionError::Unprovable {
+                error: "subqueries in queries with joins".to_string(),
+            })?;
+        }
+        Expression::Window { .. } => Err(ConversionError::Unprovable {
+            error: "window functions in queries with joins".to_string(),
+        })?,
+    }
+    Ok(())
+}
+
+/// Replace `COUNT(*)` with `COUNT(1)` since wildcards can't be evaluated by postprocessing
+fn replace_count_wildcard(expr: &mut Expression) {
+    match expr {
+        Expression::Aggregation {
+            op: AggregationOperator::Count,
+            expr,
+        } if matches!(**expr, Expression::Wildcard) => {
+            **expr = Expression::Literal(Literal::BigInt(1));
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::InSubquery { expr, .. } => {
+            replace_count_wildcard(expr);
+        }
+        Expression::Binary { left, right, .. } => {
+            replace_count_wildcard(left);
+            replace_count_wildcard(right);
+        }
+        Expression::InList { expr, list } => {
+            replace_count_wildcard(expr);
+            list.iter_mut().for_each(replace_count_wildcard);
+        }
+        Expression::Between { expr, low, high } => {
+            replace_count_wildcard(expr);
+            replace_count_wildcard(low);
+            replace_count_wildcard(high);
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            for (condition, result) in conditions {
+                replace_count_wildcard(condition);
+                replace_count_wildcard(result);
+            }
+            if let Some(else_result) = else_result {
+                replace_count_wildcard(else_result);
+            }
+        }
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_)
+        | Expression::Window { .. } => {}
+    }
+}
+
+fn column_exprs(columns: &[ColumnRef]) -> Vec<AliasedDynProofExpr> {
+    columns
+        .iter()
+        .map(|column| AliasedDynProofExpr {
+            expr: DynProofExpr::new_column(column.clone()),
+            alias: column.column_id(),
+        })
+        .collect()
+}
+
+/// # Panics
+/// Panics if `column` is not in `columns`, which can not happen for the join columns.
+fn position(columns: &[ColumnRef], column: &ColumnRef) -> usize {
+    columns
+        .iter()
+        .position(|c| c == column)
+        .expect("Join columns are always output")
+}
+
+fn missing_qualified_column(column: &QualifiedColumn) -> ConversionError {
+    ConversionError::MissingColumnWithoutTable {
+        identifier: Box::new(match column.table {
+            Some(table) => Ident::new(format!("{table}.{}", column.column)),
+            None => column.column.into(),
+        }),
+    }
+}
+
+/// We can only join on columns of the same type whose values are small enough to be compared.
+pub(super) fn check_join_column_types(
+    left_type: ColumnType,
+    right_type: ColumnType,
+) -> ConversionResult<()> {
+    if left_type != right_type {
+        Err(ConversionError::DataTypeMismatch {
+            left_type: left_type.to_string(),
+            right_type: right_type.to_string(),
+        })?;
+    }
+    match left_type {
+        ColumnType::Boolean
+        | ColumnType::TinyInt
+        | ColumnType::SmallInt
+        | ColumnType::Int
+        | ColumnType::BigInt
+        | ColumnType::Int128
+        | ColumnType::TimestampTZ(_, _) => Ok(()),
+        _ => Err(ConversionError::Unprovable {
+            error: format!("joins on columns of type {left_type} are not supported"),
+        }),
+    }
+}
@@ -16,12 +16,24 @@ pub use query_expr::QueryExpr;
 mod filter_exec_builder;
 pub(crate) use filter_exec_builder::FilterExecBuilder;
 
+mod join_exec_builder;
+pub(crate) use join_exec_builder::JoinExecBuilder;
+
+mod subquery_exec_builder;
+pub(crate) use subquery_exec_builder::SubqueryExecBuilder;
+
+mod window_exec_builder;
+pub(crate) use window_exec_builder::WindowExecBuilder;
+
 /// TODO: add docs
 pub(crate) mod query_context;
 pub(crate) use query_context::QueryContext;
 
 mod query_context_builder;
-pub(crate) use query_context_builder::{type_check_binary_operation, QueryContextBuilder};
+pub(crate) use query_context_builder::{
+    cast_column_type, interval_error, interval_operands, type_check_binary_operation,
+    QueryContextBuilder,
+};
 
 mod dyn_proof_expr_builder;
 pub(crate) use dyn_proof_expr_builder::DynProofExprBuilder;
@@ -5,8 +5,8 @@ use crate::{
     },
     sql::{
         parse::{ConversionError, ConversionResult, DynProofExprBuilder, WhereExprBuilder},
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
-        proof_plans::GroupByExec,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{AggregateExec, GroupByExec},
     },
 };
 use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
@@ -25,7 +25,7 @@ pub struct QueryContext {
     in_result_scope: bool,
     has_visited_group_by: bool,
     order_by_exprs: Vec<OrderBy>,
-    group_by_exprs: Vec<Ident>,
+    group_by_exprs: Vec<Expression>,
     where_expr: Option<Box<Expression>>,
     result_column_set: IndexSet<Ident>,
     res_aliased_exprs: Vec<AliasedResultExpr>,
@@ -124,13 +124,15 @@ impl QueryContext {
         Ok(())
     }
 
-    pub fn set_group_by_exprs(&mut self, exprs: Vec<Ident>) {
+    pub fn set_group_by_exprs(&mut self, exprs: Vec<Expression>) {
         self.group_by_exprs = exprs;
 
         // Add the group by columns to the result column set
         // to ensure their integrity in the filter expression.
-        for group_column in &self.group_by_exprs {
-            self.result_column_set.insert(group_column.clone());
+        for group_expr in &self.group_by_exprs {
+            if let Expression::Column(group_column) = group_expr {
+                self.result_column_set.insert((*group_column).into());
+            }
         }
 
         self.has_visited_group_by = true;
@@ -149,7 +151,9 @@ impl QueryContext {
         // Result column references outside aggregation must appear in the group by
         self.group_by_exprs
             .iter()
-            .find(|group_column| *group_column == column)
+            .find(|group_expr| {
+                matches!(group_expr, Expression::Column(group_column) if Ident::from(*group_column) == *column)
+            })
             .map(|_| true)
             .ok_or(ConversionError::InvalidGroupByColumnRef {
                 column: column.to_string(),
@@ -213,7 +217,7 @@ impl QueryContext {
         &self.slice_expr
     }
 
-    pub fn get_group_by_exprs(&self) -> &[Ident] {
+    pub fn get_group_by_exprs(&self) -> &[Expression] {
         &self.group_by_exprs
     }
 
@@ -242,52 +246,51 @@ impl TryFrom<&QueryContext> for Option<GroupByExec> {
                 expression: "QueryContext has no table_ref".to_owned(),
             },
         )?;
-        let resource_id = table.table_ref.resource_id();
-        let group_by_exprs = value
-            .group_by_exprs
-            .iter()
-            .map(|expr| -> Result<ColumnExpr, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or(ConversionError::MissingColumn {
-                        identifier: Box::new((expr).clone()),
-                        resource_id: Box::new(resource_id),
-                    })
-                    .map(|column_ref| ColumnExpr::new(column_ref.clone()))
-            })
-            .collect::<Result<Vec<ColumnExpr>, ConversionError>>()?;
         // For a query to be provable the result columns must be of one of three kinds below:
-        // 1. Group by columns (it is mandatory to have all of them in the correct order)
-        // 2. Sum(expr) expressions (it is optional to have any)
+        // 1. Group by expressions (it is mandatory to have all of them in the correct order)
+        // 2. Sum(expr), Avg(expr), Max(expr) and Min(expr) expressions (it is optional to have any)
         // 3. count(*) with an alias (it is mandatory to have one and only one)
-        let num_group_by_columns = group_by_exprs.len();
+        let num_group_by_columns = value.group_by_exprs.len();
         let num_result_columns = value.res_aliased_exprs.len();
         if num_result_columns < num_group_by_columns + 1 {
             return Ok(None);
         }
-        let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns].to_vec();
-        let sum_expr_columns =
+        let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns];
+        let aggregate_expr_columns =
             &value.res_aliased_exprs[num_group_by_columns..num_result_columns - 1].to_vec();
-        // Check group by columns
-        let group_by_compliance = value
+        // Check group by expressions
+        let group_by_exprs = value
             .group_by_exprs
             .iter()
-            .zip(res_group_by_columns.iter())
-            .all(|(ident, res)| {
-                if let Expression::Column(res_ident) = *res.expr {
-                    Ident::from(res_ident) == *ident
+            .zip(res_group_by_columns)
+            .map(|(expr, res)| {
+                if *res.expr == *expr {
+                    DynProofExprBuilder::new(&value.column_mapping)
+                        .build(expr)
+                        .ok()
+                        .map(|dyn_proof_expr| AliasedDynProofExpr {
+                            alias: res.alias.into(),
+                            expr: dyn_proof_expr,
+                        })
                 } else {
-                    false
+                    None
                 }
-            });
+            })
+            .collect::<Option<Vec<AliasedDynProofExpr>>>();
 
-        // Check sums
-        let sum_expr = sum_expr_columns
+        // Check aggregations
+        let aggregate_exprs = aggregate_expr_columns
             .iter()
             .map(|res| {
                 if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
+                    op:
+                        AggregationOperator::Sum
+                        | AggregationOperator::Avg
+                        | AggregationOperator::Max
+                        | AggregationOperator::Min
+                        | AggregationOperator::Median
+                        | AggregationOperator::PercentileCont(_)
+                        | AggregationOperator::PercentileDisc(_),
                     ..
                 } = (*res.expr).clone()
                 {
@@ -315,15 +318,75 @@ impl TryFrom<&QueryContext> for Option<GroupByExec> {
             }
         );
 
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
+        let (Some(group_by_exprs), Some(aggregate_exprs), true) =
+            (group_by_exprs, aggregate_exprs, count_column_compliant)
+        else {
             return Ok(None);
-        }
+        };
         Ok(Some(GroupByExec::new(
             group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
+            aggregate_exprs,
             count_column.alias.into(),
             table,
             where_clause,
         )))
     }
 }
+
+/// Converts a `QueryContext` into a `Option<AggregateExec>`.
+///
+/// We use Some if the query is an aggregation without `GROUP BY` whose result columns
+/// are all `SUM`, `COUNT`, `AVG`, `MAX`, `MIN`, `MEDIAN`, `PERCENTILE_CONT` or `PERCENTILE_DISC`
+/// aggregations and None if it is not.
+/// We error out if the query is wrong
+impl TryFrom<&QueryContext> for Option<AggregateExec> {
+    type Error = ConversionError;
+
+    fn try_from(value: &QueryContext) -> Result<Option<AggregateExec>, Self::Error> {
+        if !value.group_by_exprs.is_empty() {
+            return Ok(None);
+        }
+        let where_clause = WhereExprBuilder::new(&value.column_mapping)
+            .build(value.where_expr.clone())?
+            .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+        let table = value.table.map(|table_ref| TableExpr { table_ref }).ok_or(
+            ConversionError::InvalidExpression {
+                expression: "QueryContext has no table_ref".to_owned(),
+            },
+        )?;
+        // NULLs are skipped by the aggregations, so each aggregation comes with its presence
+        let aggregate_exprs = value
+            .res_aliased_exprs
+            .iter()
+            .map(|res| match &*res.expr {
+                Expression::Aggregation {
+                    op:
+                        op @ (AggregationOperator::Sum
+                        | AggregationOperator::Count
+                        | AggregationOperator::Avg
+                        | AggregationOperator::Max
+                        | AggregationOperator::Min
+                        | AggregationOperator::Median
+                        | AggregationOperator::PercentileCont(_)
+                        | AggregationOperator::PercentileDisc(_)),
+                    expr,
+                } => DynProofExprBuilder::new(&value.column_mapping)
+                    .build_aggregation(*op, expr)
+                    .ok()
+                    .map(|(dyn_proof_expr, presence)| {
+                        (
+                            AliasedDynProofExpr {
+                                alias: res.alias.into(),
+                                expr: dyn_proof_expr,
+                            },
+                            presence,
+                        )
+                    }),
+                _ => None,
+            })
+            .collect::<Option<(Vec<AliasedDynProofExpr>, Vec<_>)>>();
+        Ok(aggregate_exprs.map(|(aggregate_exprs, presences)| {
+            AggregateExec::new_with_presences(aggregate_exprs, presences, table, where_clause)
+        }))
+    }
+}
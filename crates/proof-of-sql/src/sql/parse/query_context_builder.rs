@@ -1,23 +1,29 @@
-use super::{ConversionError, ConversionResult, QueryContext};
-use crate::base::{
-    database::{
-        try_add_subtract_column_types, try_multiply_column_types, ColumnRef, ColumnType,
-        SchemaAccessor, TableRef,
-    },
-    math::{
-        decimal::{DecimalError, Precision},
-        BigDecimalExt,
+use super::{query_expr::contains_aggregation, ConversionError, ConversionResult, QueryContext};
+use crate::{
+    base::{
+        database::{
+            character_ident, is_character_ident, is_presence_ident, length_ident,
+            presence_column_ref, try_add_subtract_column_types, try_avg_column_type,
+            try_case_column_types, try_multiply_column_types, try_percentile_cont_column_type,
+            ColumnRef, ColumnType, LikePattern, SchemaAccessor, TableRef,
+        },
+        math::{
+            decimal::{DecimalError, Precision},
+            BigDecimalExt,
+        },
     },
+    sql::proof_exprs::{AddIntervalExpr, CastExpr, DateTruncExpr, ExtractExpr},
 };
 use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use proof_of_sql_parser::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, Expression, Literal, OrderBy, SelectResultExpr,
-        Slice, TableExpression,
+        AggregationOperator, AliasedResultExpr, CastType, DateTimeField, Expression, Literal,
+        OrderBy, Percentile, SelectResultExpr, Slice, TableExpression, UnaryOperator,
     },
+    posql_time::PoSQLTimeZone,
     Identifier, ResourceId,
 };
-use sqlparser::ast::{BinaryOperator, UnaryOperator};
+use sqlparser::ast::BinaryOperator;
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
@@ -46,6 +52,10 @@ impl<'a> QueryContextBuilder<'a> {
                 self.context
                     .set_table_ref(TableRef::new(ResourceId::new(schema_identifier, table)));
             }
+            TableExpression::Join { .. } => unreachable!("Joins are planned by JoinExecBuilder"),
+            TableExpression::Subquery { .. } => {
+                unreachable!("Subqueries are planned by SubqueryExecBuilder")
+            }
         }
         self
     }
@@ -87,9 +97,17 @@ impl<'a> QueryContextBuilder<'a> {
         self
     }
 
-    pub fn visit_group_by_exprs(mut self, group_by_exprs: Vec<Ident>) -> ConversionResult<Self> {
-        for id in &group_by_exprs {
-            self.visit_column_identifier(id)?;
+    pub fn visit_group_by_exprs(
+        mut self,
+        group_by_exprs: Vec<Expression>,
+    ) -> ConversionResult<Self> {
+        for expr in &group_by_exprs {
+            if contains_aggregation(expr) {
+                return Err(ConversionError::InvalidExpression {
+                    expression: format!("aggregations in GROUP BY are not supported: {expr:?}"),
+                });
+            }
+            self.visit_expr(expr)?;
         }
         self.context.set_group_by_exprs(group_by_exprs);
         Ok(self)
@@ -115,7 +133,10 @@ impl<'a> QueryContextBuilder<'a> {
     }
 
     fn visit_select_all_expr(&mut self) -> ConversionResult<()> {
-        for (column_name, _) in self.lookup_schema() {
+        // Presence columns are part of the columns they belong to
+        for (column_name, _) in self.lookup_schema().into_iter().filter(|(column_name, _)| {
+            !is_presence_ident(column_name) && !is_character_ident(column_name)
+        }) {
             let column_identifier = Identifier::try_from(column_name).map_err(|e| {
                 ConversionError::IdentifierConversionError {
                     error: format!("Failed to convert Ident to Identifier: {e}"),
@@ -139,11 +160,38 @@ impl<'a> QueryContextBuilder<'a> {
             Expression::Wildcard => Ok(ColumnType::BigInt), // Since COUNT(*) = COUNT(1)
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::Column(_) => self.visit_column_expr(expr),
-            Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
+            Expression::Unary { op, expr } => self.visit_unary_expr(*op, expr),
             Expression::Binary { op, left, right } => {
                 self.visit_binary_expr(&(*op).into(), left, right)
             }
             Expression::Aggregation { op, expr } => self.visit_agg_expr(*op, expr),
+            Expression::Like { expr, pattern } => self.visit_like_expr(expr, pattern),
+            Expression::InList { expr, list } => self.visit_in_list_expr(expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(expr, low, high),
+            Expression::Case {
+                conditions,
+                else_result,
+            } => self.visit_case_expr(conditions, else_result.as_deref()),
+            Expression::Cast { expr, data_type } => {
+                let dtype = self.visit_expr(expr)?;
+                cast_column_type(dtype, *data_type)
+            }
+            Expression::DateTrunc { field, expr } => {
+                let dtype = self.visit_expr(expr)?;
+                DateTruncExpr::try_result_type(*field, dtype)
+            }
+            Expression::Extract { field, expr } => {
+                let dtype = self.visit_expr(expr)?;
+                ExtractExpr::try_result_type(*field, dtype)
+            }
+            Expression::InSubquery { .. } | Expression::Subquery(_) => {
+                Err(ConversionError::Unprovable {
+                    error: "subqueries in this position".to_string(),
+                })
+            }
+            Expression::Window { .. } => Err(ConversionError::Unprovable {
+                error: "window functions in this position".to_string(),
+            }),
         }
     }
 
@@ -164,6 +212,11 @@ impl<'a> QueryContextBuilder<'a> {
         left: &Expression,
         right: &Expression,
     ) -> ConversionResult<ColumnType> {
+        if let Some((expr, value, field)) = interval_operands(op, left, right)? {
+            let dtype = self.visit_expr(expr)?;
+            AddIntervalExpr::try_units(value, field, dtype)?;
+            return Ok(dtype);
+        }
         let left_dtype = self.visit_expr(left)?;
         let right_dtype = self.visit_expr(right)?;
         check_dtypes(left_dtype, right_dtype, op)?;
@@ -171,8 +224,11 @@ impl<'a> QueryContextBuilder<'a> {
             BinaryOperator::And
             | BinaryOperator::Or
             | BinaryOperator::Eq
+            | BinaryOperator::NotEq
             | BinaryOperator::GtEq
-            | BinaryOperator::LtEq => Ok(ColumnType::Boolean),
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::Lt => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Divide
             | BinaryOperator::Minus
@@ -202,11 +258,109 @@ impl<'a> QueryContextBuilder<'a> {
                 }
                 Ok(ColumnType::Boolean)
             }
-            // Handle unsupported operators
-            _ => Err(ConversionError::UnsupportedOperation {
-                message: format!("{op:?}"),
-            }),
+            UnaryOperator::IsNull | UnaryOperator::IsNotNull => {
+                self.visit_expr(expr)?;
+                Ok(ColumnType::Boolean)
+            }
+        }
+    }
+
+    fn visit_in_list_expr(
+        &mut self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        for item in list {
+            let item_dtype = self.visit_expr(item)?;
+            check_dtypes(dtype, item_dtype, &BinaryOperator::Eq)?;
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_between_expr(
+        &mut self,
+        expr: &Expression,
+        low: &Expression,
+        high: &Expression,
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        let low_dtype = self.visit_expr(low)?;
+        check_dtypes(dtype, low_dtype, &BinaryOperator::GtEq)?;
+        let high_dtype = self.visit_expr(high)?;
+        check_dtypes(dtype, high_dtype, &BinaryOperator::LtEq)?;
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_case_expr(
+        &mut self,
+        conditions: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> ConversionResult<ColumnType> {
+        let mut result_dtypes = Vec::with_capacity(conditions.len() + 1);
+        for (condition, result) in conditions {
+            let condition_dtype = self.visit_expr(condition)?;
+            if condition_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: condition_dtype,
+                });
+            }
+            result_dtypes.push(self.visit_expr(result)?);
+        }
+        if let Some(else_result) = else_result {
+            result_dtypes.push(self.visit_expr(else_result)?);
         }
+        result_dtypes
+            .into_iter()
+            .try_fold(None, |dtype, result_dtype| -> ConversionResult<_> {
+                Ok(Some(match dtype {
+                    None => result_dtype,
+                    Some(dtype) => try_case_column_types(dtype, result_dtype).map_err(|_| {
+                        ConversionError::DataTypeMismatch {
+                            left_type: dtype.to_string(),
+                            right_type: result_dtype.to_string(),
+                        }
+                    })?,
+                }))
+            })?
+            .ok_or_else(|| ConversionError::InvalidExpression {
+                expression: "CASE without WHEN clauses".to_string(),
+            })
+    }
+
+    fn visit_like_expr(
+        &mut self,
+        expr: &Expression,
+        pattern: &str,
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        if dtype != ColumnType::VarChar {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::VarChar,
+                actual: dtype,
+            });
+        }
+        // Matching a column against a prefix needs its length and character columns
+        if let (Expression::Column(identifier), Some((prefix, _))) =
+            (expr, LikePattern::new(pattern).as_prefix())
+        {
+            let column_name = Ident::from(*identifier);
+            let table_ref = *self.context.get_table_ref();
+            let character_idents = core::iter::once(length_ident(&column_name))
+                .chain((0..prefix.len()).map(|index| character_ident(&column_name, index)));
+            for ident in character_idents {
+                if self.schema_accessor.lookup_column(table_ref, ident.clone())
+                    == Some(ColumnType::Int)
+                {
+                    self.context.push_column_ref(
+                        ident.clone(),
+                        ColumnRef::new(table_ref, ident, ColumnType::Int),
+                    );
+                }
+            }
+        }
+        Ok(ColumnType::Boolean)
     }
 
     fn visit_agg_expr(
@@ -214,6 +368,13 @@ impl<'a> QueryContextBuilder<'a> {
         op: AggregationOperator,
         expr: &Expression,
     ) -> ConversionResult<ColumnType> {
+        if op == AggregationOperator::CountDistinct {
+            return Err(ConversionError::Unprovable {
+                error: "COUNT(DISTINCT ...) other than as the only result expression of a query \
+                    without GROUP BY or HAVING"
+                    .to_string(),
+            });
+        }
         self.context.set_in_agg_scope(true)?;
 
         let expr_dtype = self.visit_expr(expr)?;
@@ -228,11 +389,18 @@ impl<'a> QueryContextBuilder<'a> {
 
         self.context.set_in_agg_scope(false)?;
 
-        // Count aggregation always results in an integer type
-        if op == AggregationOperator::Count {
-            Ok(ColumnType::BigInt)
-        } else {
-            Ok(expr_dtype)
+        // Count aggregation always results in an integer type and averages in a decimal type
+        match op {
+            AggregationOperator::Count => Ok(ColumnType::BigInt),
+            AggregationOperator::Avg => Ok(try_avg_column_type(expr_dtype)?),
+            AggregationOperator::Median => Ok(try_percentile_cont_column_type(
+                expr_dtype,
+                Percentile::MEDIAN,
+            )?),
+            AggregationOperator::PercentileCont(percentile) => {
+                Ok(try_percentile_cont_column_type(expr_dtype, percentile)?)
+            }
+            _ => Ok(expr_dtype),
         }
     }
 
@@ -254,6 +422,7 @@ impl<'a> QueryContextBuilder<'a> {
                 ))
             }
             Literal::Timestamp(its) => Ok(ColumnType::TimestampTZ(its.timeunit(), its.timezone())),
+            Literal::Interval { .. } => Err(interval_error()),
         }
     }
 
@@ -270,7 +439,16 @@ impl<'a> QueryContextBuilder<'a> {
 
         let column = ColumnRef::new(*table_ref, column_name.clone(), column_type);
 
+        // Nullable columns come along with their presence column
+        let presence = presence_column_ref(&column);
+        let is_nullable = self
+            .schema_accessor
+            .lookup_column(*table_ref, presence.column_id())
+            == Some(ColumnType::Boolean);
         self.context.push_column_ref(column_name.clone(), column);
+        if is_nullable {
+            self.context.push_column_ref(presence.column_id(), presence);
+        }
 
         Ok(column_type)
     }
@@ -299,7 +477,7 @@ pub(crate) fn type_check_binary_operation(
                 (ColumnType::Boolean, ColumnType::Boolean)
             )
         }
-        BinaryOperator::Eq => {
+        BinaryOperator::Eq | BinaryOperator::NotEq => {
             matches!(
                 (left_dtype, right_dtype),
                 (ColumnType::VarChar, ColumnType::VarChar)
@@ -309,7 +487,7 @@ pub(crate) fn type_check_binary_operation(
                     | (ColumnType::Scalar, _)
             ) || (left_dtype.is_numeric() && right_dtype.is_numeric())
         }
-        BinaryOperator::GtEq | BinaryOperator::LtEq => {
+        BinaryOperator::GtEq | BinaryOperator::LtEq | BinaryOperator::Gt | BinaryOperator::Lt => {
             if left_dtype == ColumnType::VarChar || right_dtype == ColumnType::VarChar {
                 return false;
             }
@@ -324,11 +502,16 @@ pub(crate) fn type_check_binary_operation(
                     return false;
                 }
             }
+            // Proven timestamps, e.g. from `DATE_TRUNC`, are evaluated as scalars
             left_dtype.is_numeric() && right_dtype.is_numeric()
                 || matches!(
                     (left_dtype, right_dtype),
                     (ColumnType::Boolean, ColumnType::Boolean)
-                        | (ColumnType::TimestampTZ(_, _), ColumnType::TimestampTZ(_, _))
+                        | (
+                            ColumnType::TimestampTZ(_, _),
+                            ColumnType::TimestampTZ(_, _) | ColumnType::Scalar
+                        )
+                        | (ColumnType::Scalar, ColumnType::TimestampTZ(_, _))
                 )
         }
         BinaryOperator::Plus | BinaryOperator::Minus => {
@@ -343,6 +526,70 @@ pub(crate) fn type_check_binary_operation(
     }
 }
 
+/// Returns the column type a value of `from_dtype` has after a `CAST` to `cast_type`.
+///
+/// Timestamps keep the time zone of `from_dtype` if it is a timestamp and are in UTC otherwise.
+/// Since casts can't be postprocessed, this errors if the cast is not provable.
+pub(crate) fn cast_column_type(
+    from_dtype: ColumnType,
+    cast_type: CastType,
+) -> ConversionResult<ColumnType> {
+    let to_dtype = match cast_type {
+        CastType::TinyInt => ColumnType::TinyInt,
+        CastType::SmallInt => ColumnType::SmallInt,
+        CastType::Int => ColumnType::Int,
+        CastType::BigInt => ColumnType::BigInt,
+        CastType::Decimal { precision, scale } => ColumnType::Decimal75(
+            Precision::new(precision)?,
+            scale.try_into().map_err(|_| DecimalError::InvalidScale {
+                scale: scale.to_string(),
+            })?,
+        ),
+        CastType::VarChar => ColumnType::VarChar,
+        CastType::Timestamp(unit) => match from_dtype {
+            ColumnType::TimestampTZ(_, timezone) => ColumnType::TimestampTZ(unit, timezone),
+            _ => ColumnType::TimestampTZ(unit, PoSQLTimeZone::utc()),
+        },
+    };
+    if from_dtype == to_dtype || CastExpr::is_supported(from_dtype, to_dtype) {
+        Ok(to_dtype)
+    } else {
+        Err(ConversionError::DataTypeMismatch {
+            left_type: from_dtype.to_string(),
+            right_type: to_dtype.to_string(),
+        })
+    }
+}
+
+/// Splits `left op right` into an expression and an interval of `value` fields if it adds an
+/// interval to the expression or subtracts one from it, like in `ts - INTERVAL '1 day'`.
+///
+/// Subtracting an interval is adding the negated interval.
+pub(crate) fn interval_operands<'e>(
+    op: &BinaryOperator,
+    left: &'e Expression,
+    right: &'e Expression,
+) -> ConversionResult<Option<(&'e Expression, i64, DateTimeField)>> {
+    match (op, left, right) {
+        (BinaryOperator::Plus, expr, Expression::Literal(Literal::Interval { value, field }))
+        | (BinaryOperator::Plus, Expression::Literal(Literal::Interval { value, field }), expr) => {
+            Ok(Some((expr, *value, *field)))
+        }
+        (BinaryOperator::Minus, expr, Expression::Literal(Literal::Interval { value, field })) => {
+            let negated_value = value.checked_neg().ok_or_else(interval_error)?;
+            Ok(Some((expr, negated_value, *field)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The error for intervals that are used other than being added to or subtracted from timestamps
+pub(crate) fn interval_error() -> ConversionError {
+    ConversionError::InvalidExpression {
+        expression: "intervals can only be added to or subtracted from timestamps".to_string(),
+    }
+}
+
 fn check_dtypes(
     left_dtype: ColumnType,
     right_dtype: ColumnType,
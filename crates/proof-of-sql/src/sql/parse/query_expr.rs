@@ -1,17 +1,36 @@
-use super::{EnrichedExpr, FilterExecBuilder, QueryContextBuilder};
+use super::{
+    subquery_exec_builder::is_nestable, ConversionError, EnrichedExpr, FilterExecBuilder,
+    JoinExecBuilder, QueryContextBuilder, SubqueryExecBuilder, WindowExecBuilder,
+};
 use crate::{
-    base::database::SchemaAccessor,
+    base::{
+        database::{
+            is_presence_ident, presence_ident, ColumnField, ColumnRef, ColumnType, SchemaAccessor,
+        },
+        map::IndexMap,
+    },
     sql::{
         parse::ConversionResult,
         postprocessing::{
             GroupByPostprocessing, OrderByPostprocessing, OwnedTablePostprocessing,
             PostprocessingError, SelectPostprocessing, SlicePostprocessing,
         },
-        proof_plans::{DynProofPlan, GroupByExec},
+        proof::ProofPlan,
+        proof_plans::{
+            AggregateExec, DistinctExec, DynProofPlan, GroupByExec, IntersectExec, OrderByExec,
+            SliceExec, UnionExec,
+        },
+    },
+};
+use alloc::{boxed::Box, fmt, format, string::ToString, vec, vec::Vec};
+use core::iter;
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, Expression, OrderBy, OrderByDirection,
+        SelectResultExpr, SetExpression, SetOperator, Slice, TableExpression,
     },
+    Identifier, SelectStatement,
 };
-use alloc::{fmt, format, vec, vec::Vec};
-use proof_of_sql_parser::{intermediate_ast::SetExpression, Identifier, SelectStatement};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
@@ -41,6 +60,416 @@ pub fn convert_ident_to_identifier(ident: Ident) -> Result<Identifier, Postproce
     })
 }
 
+pub(super) fn contains_aggregation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Aggregation { .. } => true,
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::InSubquery { expr, .. } => contains_aggregation(expr),
+        Expression::Binary { left, right, .. } => {
+            contains_aggregation(left) || contains_aggregation(right)
+        }
+        Expression::InList { expr, list } => {
+            contains_aggregation(expr) || list.iter().any(contains_aggregation)
+        }
+        Expression::Between { expr, low, high } => {
+            contains_aggregation(expr) || contains_aggregation(low) || contains_aggregation(high)
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            conditions.iter().any(|(condition, result)| {
+                contains_aggregation(condition) || contains_aggregation(result)
+            }) || else_result.as_deref().is_some_and(contains_aggregation)
+        }
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_)
+        | Expression::Window { .. } => false,
+    }
+}
+
+/// Rewrite `SELECT COUNT(DISTINCT <expr>) AS <alias> FROM <from> WHERE <where>` into
+/// ```ignore
+///     SELECT COUNT(*) AS <alias>
+///     FROM (SELECT DISTINCT <expr> AS <alias> FROM <from> WHERE <where>) AS __count_distinct__
+/// ```
+/// so that the distinct values are proven by a [`DistinctExec`].
+///
+/// Any other use of `COUNT(DISTINCT ...)` is left as is and turns out to be unprovable. In
+/// particular, `COUNT(DISTINCT ...)` along with a `GROUP BY` would need the distinct pairs of the
+/// groups and the values, and a [`DistinctExec`] only proves that the rows of its result are
+/// unique if it has a single column.
+///
+/// # Panics
+/// Panics if `__count_distinct__` is not a valid identifier, which it is.
+fn rewrite_count_distinct(ast: SelectStatement) -> SelectStatement {
+    let SetExpression::Query {
+        result_exprs,
+        from,
+        where_expr,
+        group_by,
+        having,
+        distinct,
+    } = *ast.expr
+    else {
+        unreachable!("Set operations are planned separately")
+    };
+    let expr = match (
+        result_exprs.as_slice(),
+        group_by.is_empty() && having.is_none(),
+    ) {
+        ([SelectResultExpr::AliasedResultExpr(AliasedResultExpr { expr, alias })], true) => {
+            match &**expr {
+                Expression::Aggregation {
+                    op: AggregationOperator::CountDistinct,
+                    expr,
+                } => Some(SetExpression::Query {
+                    result_exprs: vec![SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+                        expr: Box::new(Expression::Aggregation {
+                            op: AggregationOperator::Count,
+                            expr: Box::new(Expression::Wildcard),
+                        }),
+                        alias: *alias,
+                    })],
+                    from: vec![Box::new(TableExpression::Subquery {
+                        query: Box::new(SelectStatement {
+                            expr: Box::new(SetExpression::Query {
+                                result_exprs: vec![SelectResultExpr::AliasedResultExpr(
+                                    AliasedResultExpr {
+                                        expr: expr.clone(),
+                                        alias: *alias,
+                                    },
+                                )],
+                                from: from.clone(),
+                                where_expr: where_expr.clone(),
+                                group_by: vec![],
+                                having: None,
+                                distinct: true,
+                            }),
+                            order_by: vec![],
+                            slice: None,
+                        }),
+                        alias: Identifier::try_new("__count_distinct__")
+                            .expect("__count_distinct__ is a valid identifier"),
+                    })],
+                    where_expr: None,
+                    group_by: vec![],
+                    having: None,
+                    distinct: false,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    SelectStatement {
+        expr: Box::new(expr.unwrap_or(SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+            distinct,
+        })),
+        order_by: ast.order_by,
+        slice: ast.slice,
+    }
+}
+
+/// Rewrite `SELECT <result_exprs> FROM <from> WHERE <where> GROUP BY <group_by> HAVING <having>` into
+/// ```ignore
+///     SELECT <result_exprs>
+///     FROM (
+///         SELECT <group_by>, <aggregation_0> AS __having_0__, ..., COUNT(*) AS __having_count__
+///         FROM <from> WHERE <where> GROUP BY <group_by>
+///     ) AS __having__
+///     WHERE <having>
+/// ```
+/// where the aggregations in `<result_exprs>` and `<having>` are replaced by the corresponding
+/// columns of the grouped table, so that the groups are filtered by a [`FilterExec`] on the
+/// output of a [`GroupByExec`].
+///
+/// The filter can only be proven if the uniqueness of the groups is proven, which a
+/// [`GroupByExec`] only does for a single group by column, see [`GroupByExec::is_nestable`].
+/// `HAVING` along with a `GROUP BY` on several columns is therefore unprovable.
+fn rewrite_having(ast: SelectStatement) -> ConversionResult<SelectStatement> {
+    if !matches!(
+        *ast.expr,
+        SetExpression::Query {
+            having: Some(_),
+            ..
+        }
+    ) {
+        return Ok(ast);
+    }
+    let SetExpression::Query {
+        mut result_exprs,
+        from,
+        where_expr,
+        group_by,
+        having: Some(mut having),
+        distinct,
+    } = *ast.expr
+    else {
+        unreachable!("The query has a HAVING clause")
+    };
+    if group_by.len() > 1 {
+        return Err(ConversionError::Unprovable {
+            error: "HAVING along with GROUP BY on several columns, whose groups are not proven to \
+                be unique"
+                .to_string(),
+        });
+    }
+    let keys = group_by
+        .iter()
+        .enumerate()
+        .map(|(i, key)| match key {
+            Expression::Column(column) => (key.clone(), *column),
+            _ => (key.clone(), having_identifier(format_args!("key_{i}"))),
+        })
+        .collect::<Vec<_>>();
+    let mut aggregations = Vec::new();
+    for result_expr in &mut result_exprs {
+        match result_expr {
+            SelectResultExpr::ALL => Err(ConversionError::Unprovable {
+                error: "HAVING along with *".to_string(),
+            })?,
+            SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                replace_aggregations(&mut aliased_expr.expr, &keys, &mut aggregations)?;
+            }
+        }
+    }
+    replace_aggregations(&mut having, &keys, &mut aggregations)?;
+    let grouped_result_exprs = keys
+        .into_iter()
+        .map(|(key, alias)| AliasedResultExpr::new(key, alias))
+        .chain(
+            aggregations
+                .into_iter()
+                .enumerate()
+                .map(|(i, aggregation)| AliasedResultExpr::new(aggregation, having_identifier(i))),
+        )
+        .chain(iter::once(AliasedResultExpr::new(
+            Expression::Aggregation {
+                op: AggregationOperator::Count,
+                expr: Box::new(Expression::Wildcard),
+            },
+            having_identifier("count"),
+        )))
+        .map(SelectResultExpr::AliasedResultExpr)
+        .collect();
+    Ok(SelectStatement {
+        expr: Box::new(SetExpression::Query {
+            result_exprs,
+            from: vec![Box::new(TableExpression::Subquery {
+                query: Box::new(SelectStatement {
+                    expr: Box::new(SetExpression::Query {
+                        result_exprs: grouped_result_exprs,
+                        from,
+                        where_expr,
+                        group_by,
+                        having: None,
+                        distinct: false,
+                    }),
+                    order_by: vec![],
+                    slice: None,
+                }),
+                alias: having_identifier(""),
+            })],
+            where_expr: Some(having),
+            group_by: vec![],
+            having: None,
+            distinct,
+        }),
+        order_by: ast.order_by,
+        slice: ast.slice,
+    })
+}
+
+/// Replace the group keys and the aggregations in `expr` by the columns of the grouped table built by
+/// [`rewrite_having`], adding the aggregations to `aggregations` unless they are already there.
+/// `COUNT` is replaced by the count column.
+fn replace_aggregations(
+    expr: &mut Expression,
+    keys: &[(Expression, Identifier)],
+    aggregations: &mut Vec<Expression>,
+) -> ConversionResult<()> {
+    if let Some((_, alias)) = keys.iter().find(|(key, _)| key == expr) {
+        *expr = Expression::Column(*alias);
+        return Ok(());
+    }
+    match expr {
+        Expression::Aggregation {
+            op: AggregationOperator::Count,
+            ..
+        } => *expr = Expression::Column(having_identifier("count")),
+        Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            ..
+        } => Err(ConversionError::Unprovable {
+            error: "COUNT(DISTINCT ...) along with HAVING".to_string(),
+        })?,
+        Expression::Aggregation { .. } => {
+            let index = aggregations
+                .iter()
+                .position(|aggregation| aggregation == expr)
+                .unwrap_or_else(|| {
+                    aggregations.push(expr.clone());
+                    aggregations.len() - 1
+                });
+            *expr = Expression::Column(having_identifier(index));
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::InSubquery { expr, .. } => replace_aggregations(expr, keys, aggregations)?,
+        Expression::Binary { left, right, .. } => {
+            replace_aggregations(left, keys, aggregations)?;
+            replace_aggregations(right, keys, aggregations)?;
+        }
+        Expression::InList { expr, list } => {
+            replace_aggregations(expr, keys, aggregations)?;
+            for item in list {
+                replace_aggregations(item, keys, aggregations)?;
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            replace_aggregations(expr, keys, aggregations)?;
+            replace_aggregations(low, keys, aggregations)?;
+            replace_aggregations(high, keys, aggregations)?;
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            for (condition, result) in conditions {
+                replace_aggregations(condition, keys, aggregations)?;
+                replace_aggregations(result, keys, aggregations)?;
+            }
+            if let Some(else_result) = else_result {
+                replace_aggregations(else_result, keys, aggregations)?;
+            }
+        }
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_) => {}
+        Expression::Window { .. } => Err(ConversionError::Unprovable {
+            error: "window functions along with HAVING".to_string(),
+        })?,
+    }
+    Ok(())
+}
+
+/// A name for the grouped table built by [`rewrite_having`] or one of its columns
+///
+/// # Panics
+/// Panics if the name is not a valid identifier, which it is.
+fn having_identifier(suffix: impl fmt::Display) -> Identifier {
+    let suffix = suffix.to_string();
+    let name = if suffix.is_empty() {
+        "__having__".to_string()
+    } else {
+        format!("__having_{suffix}__")
+    };
+    Identifier::try_new(name).expect("The name is a valid identifier")
+}
+
+/// The postprocessing steps ordering and slicing a result with the given columns,
+/// which must not order by nullable columns.
+fn order_by_and_slice_postprocessing(
+    fields: &[ColumnField],
+    order_by: Vec<OrderBy>,
+    slice: Option<Slice>,
+) -> ConversionResult<Vec<OwnedTablePostprocessing>> {
+    for by_expr in &order_by {
+        let by_ident: Ident = by_expr.expr.into();
+        if !fields.iter().any(|field| field.name() == by_ident) {
+            return Err(ConversionError::InvalidOrderBy {
+                alias: by_expr.expr.as_str().to_string(),
+            });
+        }
+        let presence = presence_ident(&by_ident);
+        if fields.iter().any(|field| field.name() == presence) {
+            return Err(ConversionError::Unprovable {
+                error: format!("ordering by the nullable column {}", by_expr.expr),
+            });
+        }
+    }
+    let mut postprocessing = vec![];
+    if !order_by.is_empty() {
+        postprocessing.push(OwnedTablePostprocessing::new_order_by(
+            OrderByPostprocessing::new(order_by),
+        ));
+    }
+    if let Some(slice) = slice {
+        postprocessing.push(OwnedTablePostprocessing::new_slice(
+            SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
+        ));
+    }
+    Ok(postprocessing)
+}
+
+/// The columns of the result of a set operation over `inputs`, which must have the same numbers
+/// and types of columns, none of which may be nullable.
+fn set_operation_fields(
+    operator: &str,
+    inputs: &[DynProofPlan],
+) -> ConversionResult<Vec<ColumnField>> {
+    let fields = inputs[0].get_column_result_fields();
+    for input in &inputs[1..] {
+        let input_fields = input.get_column_result_fields();
+        if input_fields.len() != fields.len() {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!(
+                    "{operator} of queries with {} and {} columns",
+                    fields.len(),
+                    input_fields.len()
+                ),
+            });
+        }
+        if let Some((field, input_field)) = fields
+            .iter()
+            .zip(&input_fields)
+            .find(|(field, input_field)| field.data_type() != input_field.data_type())
+        {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: field.data_type().to_string(),
+                right_type: input_field.data_type().to_string(),
+            });
+        }
+    }
+    if fields.iter().any(|field| is_presence_ident(&field.name())) {
+        return Err(ConversionError::Unprovable {
+            error: format!("{operator} of nullable columns"),
+        });
+    }
+    Ok(fields)
+}
+
+/// The columns of a GROUP BY clause for [`GroupByPostprocessing`], which only groups by columns
+fn group_by_columns(group_by: &[Expression]) -> ConversionResult<Vec<Ident>> {
+    group_by
+        .iter()
+        .map(|key| match key {
+            Expression::Column(column) => Ok((*column).into()),
+            _ => Err(ConversionError::Unprovable {
+                error:
+                    "GROUP BY expressions other than columns whose result requires postprocessing"
+                        .to_string(),
+            }),
+        })
+        .collect()
+}
+
 impl QueryExpr {
     /// Creates a new `QueryExpr` with the given `DynProofPlan` and `OwnedTablePostprocessing`.
     #[must_use]
@@ -52,25 +481,73 @@ impl QueryExpr {
     }
 
     /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`.
+    #[allow(clippy::too_many_lines)]
     pub fn try_new(
         ast: SelectStatement,
         default_schema: Ident,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
+        match *ast.expr {
+            SetExpression::Query { distinct: true, .. } => {
+                return Self::try_new_distinct(ast, default_schema, schema_accessor);
+            }
+            SetExpression::SetOperation { .. } => {
+                return Self::try_new_set_operation(ast, &default_schema, schema_accessor);
+            }
+            SetExpression::Query { .. } => {}
+        }
+        let ast = rewrite_having(rewrite_count_distinct(ast))?;
+        if SubqueryExecBuilder::has_subqueries(&ast) {
+            let mut builder = SubqueryExecBuilder::new(schema_accessor, default_schema.clone());
+            let ast = builder.build(ast)?;
+            let query = Self::try_new(ast, default_schema, &builder)?;
+            return Ok(Self {
+                proof_expr: builder.wrap_plan(query.proof_expr),
+                postprocessing: query.postprocessing,
+            });
+        }
+        if WindowExecBuilder::has_window_functions(&ast) {
+            return Self::try_new_window(ast, default_schema, schema_accessor);
+        }
         let context = match *ast.expr {
             SetExpression::Query {
                 result_exprs,
                 from,
                 where_expr,
                 group_by,
+                ..
+            } if matches!(from.as_slice(), [table_expr] if matches!(**table_expr, TableExpression::Join { .. })) =>
+            {
+                let join = JoinExecBuilder::new(
+                    schema_accessor,
+                    convert_ident_to_identifier(default_schema)?,
+                )
+                .build(&from[0], result_exprs, where_expr, group_by)?;
+                return Self::try_new_with_postprocessing(
+                    join.plan,
+                    join.result_exprs,
+                    join.group_by,
+                    ast.order_by,
+                    ast.slice,
+                );
+            }
+            SetExpression::Query {
+                result_exprs,
+                from,
+                where_expr,
+                group_by,
+                ..
             } => QueryContextBuilder::new(schema_accessor)
                 .visit_table_expr(&from, convert_ident_to_identifier(default_schema)?)
-                .visit_group_by_exprs(group_by.into_iter().map(Ident::from).collect())?
+                .visit_group_by_exprs(group_by)?
                 .visit_result_exprs(result_exprs)?
                 .visit_where_expr(where_expr)?
                 .visit_order_by_exprs(ast.order_by)
                 .visit_slice_expr(ast.slice)
                 .build()?,
+            SetExpression::SetOperation { .. } => {
+                unreachable!("Set operations are planned separately")
+            }
         };
         let result_aliased_exprs = context.get_aliased_result_exprs()?.to_vec();
         let group_by = context.get_group_by_exprs();
@@ -88,10 +565,34 @@ impl QueryExpr {
             ));
         }
         if context.has_agg() {
-            if let Some(group_by_expr) = Option::<GroupByExec>::try_from(&context)? {
-                Ok(Self {
-                    proof_expr: DynProofPlan::GroupBy(group_by_expr),
-                    postprocessing,
+            let plan = if let Some(aggregate_expr) = Option::<AggregateExec>::try_from(&context)? {
+                Some(DynProofPlan::Aggregate(aggregate_expr))
+            } else {
+                Option::<GroupByExec>::try_from(&context)?.map(DynProofPlan::GroupBy)
+            };
+            if let Some(plan) = plan {
+                // The result of a plan that is finalized after verification can not be ordered
+                // and sliced by another plan.
+                let ordered_plan = (is_nestable(&plan)
+                    && plan.get_intermediate_result_fields() == plan.get_column_result_fields())
+                .then(|| {
+                    try_prove_order_by_and_slice(
+                        &plan,
+                        &order_bys,
+                        context.get_slice_expr().as_ref(),
+                    )
+                })
+                .flatten();
+                Ok(match ordered_plan {
+                    // The ordering and the slice are proven, so no postprocessing is needed.
+                    Some(ordered_plan) => Self {
+                        proof_expr: ordered_plan,
+                        postprocessing: vec![],
+                    },
+                    None => Self {
+                        proof_expr: plan,
+                        postprocessing,
+                    },
                 })
             } else {
                 let raw_enriched_exprs = result_aliased_exprs
@@ -99,6 +600,7 @@ impl QueryExpr {
                     .map(|aliased_expr| EnrichedExpr {
                         residue_expression: aliased_expr.clone(),
                         dyn_proof_expr: None,
+                        presence_expr: None,
                     })
                     .collect::<Vec<_>>();
                 let filter = FilterExecBuilder::new(context.get_column_mapping())
@@ -107,8 +609,11 @@ impl QueryExpr {
                     .add_result_columns(&raw_enriched_exprs)
                     .build();
 
-                let group_by_postprocessing =
-                    GroupByPostprocessing::try_new(group_by.to_vec(), result_aliased_exprs)?;
+                check_no_nullable_columns(&context.get_column_mapping())?;
+                let group_by_postprocessing = GroupByPostprocessing::try_new(
+                    group_by_columns(group_by)?,
+                    result_aliased_exprs,
+                )?;
                 postprocessing.insert(
                     0,
                     OwnedTablePostprocessing::new_group_by(group_by_postprocessing.clone()),
@@ -153,10 +658,40 @@ impl QueryExpr {
                 .iter()
                 .any(|expr| expr.try_as_identifier().is_none())
             {
+                check_no_nullable_columns(&column_mapping)?;
                 postprocessing.insert(
                     0,
                     OwnedTablePostprocessing::new_select(SelectPostprocessing::new(select_exprs)),
                 );
+            } else if let Some(plan) = try_prove_order_by_and_slice(
+                &DynProofPlan::Filter(filter.clone()),
+                &order_bys,
+                context.get_slice_expr().as_ref(),
+            ) {
+                // The ordering and the slice are proven, so no postprocessing is needed.
+                return Ok(Self {
+                    proof_expr: plan,
+                    postprocessing: vec![],
+                });
+            }
+            let fields = filter.get_column_result_fields();
+            if let Some(order_by) = order_bys.iter().find(|order_by| {
+                let presence = presence_ident(&order_by.expr.into());
+                fields.iter().any(|field| field.name() == presence)
+            }) {
+                // Nullable columns can only be ordered by in an `OrderByExec`, which does not
+                // support `VARCHAR` columns
+                let ident: Ident = order_by.expr.into();
+                let is_varchar = fields
+                    .iter()
+                    .any(|field| field.name() == ident && field.data_type() == ColumnType::VarChar);
+                return Err(ConversionError::Unprovable {
+                    error: if is_varchar {
+                        format!("ordering by the nullable VARCHAR column {}", order_by.expr)
+                    } else {
+                        format!("ordering by the nullable column {}", order_by.expr)
+                    },
+                });
             }
             Ok(Self {
                 proof_expr: DynProofPlan::Filter(filter),
@@ -165,6 +700,295 @@ impl QueryExpr {
         }
     }
 
+    /// Plan `SELECT DISTINCT ...` as a [`DistinctExec`] over the plan of the query without `DISTINCT`.
+    ///
+    /// The ordering and the slice are applied to the distinct rows, so they are postprocessing steps.
+    fn try_new_distinct(
+        ast: SelectStatement,
+        default_schema: Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+            ..
+        } = *ast.expr
+        else {
+            unreachable!("Set operations are planned separately")
+        };
+        if !group_by.is_empty()
+            || having.is_some()
+            || result_exprs.iter().any(|result_expr| {
+                matches!(result_expr, SelectResultExpr::AliasedResultExpr(aliased_expr) if contains_aggregation(&aliased_expr.expr))
+            })
+        {
+            return Err(ConversionError::Unprovable {
+                error: "DISTINCT along with aggregations".to_string(),
+            });
+        }
+        let query = Self::try_new(
+            SelectStatement {
+                expr: Box::new(SetExpression::Query {
+                    result_exprs,
+                    from,
+                    where_expr,
+                    group_by,
+                    having: None,
+                    distinct: false,
+                }),
+                order_by: vec![],
+                slice: None,
+            },
+            default_schema,
+            schema_accessor,
+        )?;
+        if !query.postprocessing.is_empty() {
+            return Err(ConversionError::Unprovable {
+                error: "DISTINCT on results which require postprocessing".to_string(),
+            });
+        }
+        let postprocessing = order_by_and_slice_postprocessing(
+            &query.proof_expr.get_column_result_fields(),
+            ast.order_by,
+            ast.slice,
+        )?;
+        Ok(Self {
+            proof_expr: DynProofPlan::Distinct(DistinctExec::new(Box::new(query.proof_expr))),
+            postprocessing,
+        })
+    }
+
+    /// Plan `<left> UNION [ALL] <right>`, `<left> INTERSECT <right>` or `<left> EXCEPT <right>`.
+    ///
+    /// `UNION ALL` is planned as a [`UnionExec`], `UNION` as a [`DistinctExec`] over it and
+    /// `INTERSECT` and `EXCEPT` as an [`IntersectExec`]. Chains of unions are combined into a
+    /// single [`UnionExec`]. The columns of the result are named after those of the left operand.
+    ///
+    /// The ordering and the slice are applied to the combined rows, so they are postprocessing steps.
+    fn try_new_set_operation(
+        ast: SelectStatement,
+        default_schema: &Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let SetExpression::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } = *ast.expr
+        else {
+            unreachable!("The query is a set operation")
+        };
+        let operator = format!("{}{}", op, if all { " all" } else { "" }).to_uppercase();
+        let inputs = Self::try_new_set_operands(
+            op,
+            all,
+            [left, right],
+            &operator,
+            default_schema,
+            schema_accessor,
+        )?;
+        let fields = set_operation_fields(&operator, &inputs)?;
+        let proof_expr = match (op, all) {
+            (SetOperator::Union, true) => DynProofPlan::Union(UnionExec::new(inputs, fields)),
+            (SetOperator::Union, false) => DynProofPlan::Distinct(DistinctExec::new(Box::new(
+                DynProofPlan::Union(UnionExec::new(inputs, fields)),
+            ))),
+            (SetOperator::Intersect | SetOperator::Except, true) => {
+                return Err(ConversionError::Unprovable { error: operator });
+            }
+            (SetOperator::Intersect | SetOperator::Except, false) => {
+                if let Some(field) = fields
+                    .iter()
+                    .find(|field| !OrderByExec::is_supported_column_type(field.data_type()))
+                {
+                    return Err(ConversionError::Unprovable {
+                        error: format!("{operator} of columns of type {}", field.data_type()),
+                    });
+                }
+                let mut inputs = inputs.into_iter();
+                let (Some(left), Some(right)) = (inputs.next(), inputs.next()) else {
+                    unreachable!("INTERSECT and EXCEPT have exactly two operands")
+                };
+                DynProofPlan::Intersect(IntersectExec::new(
+                    Box::new(left),
+                    Box::new(right),
+                    op == SetOperator::Except,
+                ))
+            }
+        };
+        let postprocessing = order_by_and_slice_postprocessing(
+            &proof_expr.get_column_result_fields(),
+            ast.order_by,
+            ast.slice,
+        )?;
+        Ok(Self {
+            proof_expr,
+            postprocessing,
+        })
+    }
+
+    /// Plan the operands of a set operation, which have to be entirely provable.
+    ///
+    /// Operands which are unions themselves are replaced by their inputs if this does not change
+    /// the result, i.e. if `op` is a `UNION ALL` and the operand a `UNION ALL` or if `op` is a
+    /// `UNION` and the operand any union.
+    fn try_new_set_operands(
+        op: SetOperator,
+        all: bool,
+        operands: [Box<SetExpression>; 2],
+        operator: &str,
+        default_schema: &Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Vec<DynProofPlan>> {
+        let mut inputs = Vec::new();
+        for operand in operands {
+            let query = Self::try_new(
+                SelectStatement {
+                    expr: operand,
+                    order_by: vec![],
+                    slice: None,
+                },
+                default_schema.clone(),
+                schema_accessor,
+            )?;
+            if !query.postprocessing.is_empty() {
+                return Err(ConversionError::Unprovable {
+                    error: format!("{operator} of results which require postprocessing"),
+                });
+            }
+            match (op, all, query.proof_expr) {
+                (SetOperator::Union, _, DynProofPlan::Union(union_exec)) => {
+                    inputs.extend_from_slice(union_exec.inputs());
+                }
+                (SetOperator::Union, false, DynProofPlan::Distinct(distinct_exec))
+                    if matches!(distinct_exec.input(), DynProofPlan::Union(_)) =>
+                {
+                    let DynProofPlan::Union(union_exec) = distinct_exec.input() else {
+                        unreachable!("The input is a union")
+                    };
+                    inputs.extend_from_slice(union_exec.inputs());
+                }
+                (_, _, plan) => inputs.push(plan),
+            }
+        }
+        if !inputs.iter().all(is_nestable) {
+            return Err(ConversionError::Unprovable {
+                error: format!("{operator} of results which can only be proven at the top level"),
+            });
+        }
+        Ok(inputs)
+    }
+
+    /// Plan a query with window functions as the query
+    /// ```ignore
+    ///     SELECT <result_exprs> FROM __window__ ORDER BY ... LIMIT ...
+    /// ```
+    /// over the derived table `__window__` computing the window functions by [`WindowExec`]s.
+    ///
+    /// # Panics
+    /// Panics if `__window__` is not a valid identifier, which it is.
+    ///
+    /// [`WindowExec`]: crate::sql::proof_plans::WindowExec
+    fn try_new_window(
+        ast: SelectStatement,
+        default_schema: Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let window =
+            WindowExecBuilder::new(schema_accessor, default_schema.clone()).build(*ast.expr)?;
+        let mut builder = SubqueryExecBuilder::new(schema_accessor, default_schema.clone());
+        let alias = Identifier::try_new("__window__").expect("The name is a valid identifier");
+        builder.add_derived_table(alias, window.plan)?;
+        let query = Self::try_new(
+            SelectStatement {
+                expr: Box::new(SetExpression::Query {
+                    result_exprs: window
+                        .result_exprs
+                        .into_iter()
+                        .map(SelectResultExpr::AliasedResultExpr)
+                        .collect(),
+                    from: vec![Box::new(TableExpression::Named {
+                        table: alias,
+                        schema: None,
+                    })],
+                    where_expr: None,
+                    group_by: vec![],
+                    having: None,
+                    distinct: false,
+                }),
+                order_by: ast.order_by,
+                slice: ast.slice,
+            },
+            default_schema,
+            &builder,
+        )?;
+        Ok(Self {
+            proof_expr: builder.wrap_plan(query.proof_expr),
+            postprocessing: query.postprocessing,
+        })
+    }
+
+    /// Create a `QueryExpr` out of a `DynProofPlan` whose result columns are transformed into the
+    /// result of the query entirely by postprocessing.
+    fn try_new_with_postprocessing(
+        proof_expr: DynProofPlan,
+        result_aliased_exprs: Vec<AliasedResultExpr>,
+        group_by: Vec<Ident>,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+    ) -> ConversionResult<Self> {
+        for (i, aliased_expr) in result_aliased_exprs.iter().enumerate() {
+            if result_aliased_exprs[..i]
+                .iter()
+                .any(|other| other.alias == aliased_expr.alias)
+            {
+                return Err(ConversionError::DuplicateResultAlias {
+                    alias: aliased_expr.alias.to_string(),
+                });
+            }
+        }
+        if let Some(by_expr) = order_by.iter().find(|by_expr| {
+            !result_aliased_exprs
+                .iter()
+                .any(|aliased_expr| aliased_expr.alias == by_expr.expr)
+        }) {
+            return Err(ConversionError::InvalidOrderBy {
+                alias: by_expr.expr.as_str().to_string(),
+            });
+        }
+        let has_agg = !group_by.is_empty()
+            || result_aliased_exprs
+                .iter()
+                .any(|aliased_expr| contains_aggregation(&aliased_expr.expr));
+        // Group by postprocessing already evaluates the result expressions
+        let mut postprocessing = vec![if has_agg {
+            OwnedTablePostprocessing::new_group_by(GroupByPostprocessing::try_new(
+                group_by,
+                result_aliased_exprs,
+            )?)
+        } else {
+            OwnedTablePostprocessing::new_select(SelectPostprocessing::new(result_aliased_exprs))
+        }];
+        if !order_by.is_empty() {
+            postprocessing.push(OwnedTablePostprocessing::new_order_by(
+                OrderByPostprocessing::new(order_by),
+            ));
+        }
+        if let Some(slice) = slice {
+            postprocessing.push(OwnedTablePostprocessing::new_slice(
+                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
+            ));
+        }
+        Ok(Self {
+            proof_expr,
+            postprocessing,
+        })
+    }
+
     /// Immutable access to this query's provable filter expression.
     #[must_use]
     pub fn proof_expr(&self) -> &DynProofPlan {
@@ -177,3 +1001,88 @@ impl QueryExpr {
         &self.postprocessing
     }
 }
+
+/// Postprocessing does not track NULLs, so it must not involve nullable columns.
+fn check_no_nullable_columns(column_mapping: &IndexMap<Ident, ColumnRef>) -> ConversionResult<()> {
+    if column_mapping.keys().any(is_presence_ident) {
+        Err(ConversionError::Unprovable {
+            error: "postprocessing of nullable columns".to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Build a provable `ORDER BY ... LIMIT ...` on top of `plan` if possible, which has to be a plan
+/// that can be the input of other plans, such as a filter, an aggregation or a nestable
+/// `GROUP BY`.
+///
+/// Since the result of a slice of an unproven ordering is not attested, we only do this if
+/// there is both an ordering and a slice with a nonnegative offset, and the ordering only
+/// refers to result columns whose type can be ordered by in an [`OrderByExec`]. In particular,
+/// `VARCHAR` columns can not be ordered by in an [`OrderByExec`], so orderings by them are left
+/// to postprocessing.
+///
+/// As in postgres, NULLs come last in ascending and first in descending order, so nullable
+/// columns are ordered by their presence first. Since postprocessing can not do that, an ordering
+/// by a nullable column is proven even without a slice.
+fn try_prove_order_by_and_slice(
+    plan: &DynProofPlan,
+    order_bys: &[OrderBy],
+    slice: Option<&Slice>,
+) -> Option<DynProofPlan> {
+    if order_bys.is_empty() {
+        return None;
+    }
+    let fields = plan.get_column_result_fields();
+    let mut has_nullable_key = false;
+    let order_by = order_bys
+        .iter()
+        .map(|order_by| {
+            let ident: Ident = order_by.expr.into();
+            let index = fields
+                .iter()
+                .position(|field| field.name() == ident)
+                .filter(|&index| {
+                    OrderByExec::is_supported_column_type(fields[index].data_type())
+                })?;
+            let presence = presence_ident(&ident);
+            let presence_key = fields
+                .iter()
+                .position(|field| field.name() == presence)
+                .map(|presence_index| {
+                    let direction = match order_by.direction {
+                        OrderByDirection::Asc => OrderByDirection::Desc,
+                        OrderByDirection::Desc => OrderByDirection::Asc,
+                    };
+                    (presence_index, direction)
+                });
+            has_nullable_key |= presence_key.is_some();
+            Some(
+                presence_key
+                    .into_iter()
+                    .chain([(index, order_by.direction)]),
+            )
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    let order_by_plan = DynProofPlan::OrderBy(OrderByExec::new(Box::new(plan.clone()), order_by));
+    match slice {
+        Some(slice) => {
+            let skip = usize::try_from(slice.offset_value).ok()?;
+            let fetch = if slice.number_rows == u64::MAX {
+                None
+            } else {
+                Some(usize::try_from(slice.number_rows).ok()?)
+            };
+            Some(DynProofPlan::Slice(SliceExec::new(
+                Box::new(order_by_plan),
+                skip,
+                fetch,
+            )))
+        }
+        None => has_nullable_key.then_some(order_by_plan),
+    }
+}
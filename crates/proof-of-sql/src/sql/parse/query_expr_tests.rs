@@ -1,23 +1,25 @@
 use super::ConversionError;
 use crate::{
     base::{
-        database::{ColumnType, TableRef, TestSchemaAccessor},
+        database::{ColumnType, LiteralValue, TableRef, TestSchemaAccessor},
         map::{indexmap, IndexMap, IndexSet},
+        math::decimal::Precision,
     },
     sql::{
         parse::QueryExpr,
         postprocessing::{test_utility::*, PostprocessingError},
         proof_exprs::test_utility::*,
-        proof_plans::{test_utility::*, DynProofPlan},
+        proof_plans::{test_utility::*, DynProofPlan, WindowFunction},
     },
 };
 use itertools::Itertools;
 use proof_of_sql_parser::{
-    intermediate_ast::OrderByDirection::*,
+    intermediate_ast::{DateTimeField, OrderByDirection::*},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     sql::SelectStatementParser,
     utility::{
-        add as padd, aliased_expr, col, count, count_all, lit, max, min, mul as pmul, sub as psub,
-        sum,
+        add as padd, aliased_expr, col, count, count_all, div as pdiv, like as plike, lit, max,
+        min, mul as pmul, sub as psub, sum,
     },
 };
 use sqlparser::ast::Ident;
@@ -525,6 +527,43 @@ fn we_can_convert_an_ast_using_an_aliased_column() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_convert_an_ast_with_strict_comparisons_and_not_equals() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
+            "d".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a, a > b as is_greater from sxt_tab where b < 4 and c <> d",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                col_expr_plan(t, "a", &accessor),
+                aliased_plan(
+                    gt(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "is_greater",
+                ),
+            ],
+            tab(t),
+            and(
+                lt(column(t, "b", &accessor), const_bigint(4)),
+                not_equal(column(t, "c", &accessor), column(t, "d", &accessor)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_cannot_convert_an_ast_with_a_nonexistent_column() {
     let t = "sxt.sxt_tab".parse().unwrap();
@@ -917,21 +956,102 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![
-                col_expr_plan(t, "a", &accessor),
-                aliased_plan(
-                    and(
-                        column(t, "boolean", &accessor),
-                        gte(column(t, "a", &accessor), const_bigint(4)),
-                    ),
-                    "res",
+        slice_exec(
+            order_by_exec(
+                filter(
+                    vec![
+                        col_expr_plan(t, "a", &accessor),
+                        aliased_plan(
+                            and(
+                                column(t, "boolean", &accessor),
+                                gte(column(t, "a", &accessor), const_bigint(4)),
+                            ),
+                            "res",
+                        ),
+                    ],
+                    tab(t),
+                    equal(column(t, "a", &accessor), const_bigint(-3)),
                 ),
-            ],
+                vec![(0, Desc)],
+            ),
+            3,
+            Some(55),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_an_order_by_on_multiple_columns_with_a_limit() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
+            "c".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a, b as d, c from sxt_tab order by d, a desc limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            order_by_exec(
+                filter(
+                    vec![
+                        col_expr_plan(t, "a", &accessor),
+                        aliased_col_expr_plan(t, "b", "d", &accessor),
+                        col_expr_plan(t, "c", &accessor),
+                    ],
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![(1, Asc), (0, Desc)],
+            ),
+            0,
+            Some(2),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_do_not_prove_an_order_by_with_a_limit_on_a_varchar_column_or_with_a_negative_offset() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(t, "select a, c from sxt_tab order by c limit 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "c"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![orders(&["c"], &[Asc]), slice(Some(2), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
+    let ast = query_to_provable_ast(
+        t,
+        "select a, c from sxt_tab order by a limit 2 offset -1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "c"], &accessor),
             tab(t),
-            equal(column(t, "a", &accessor), const_bigint(-3)),
+            const_bool(true),
         ),
-        vec![orders(&["a"], &[Desc]), slice(Some(55), Some(3))],
+        vec![orders(&["a"], &[Asc]), slice(Some(2), Some(-1))],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -956,7 +1076,7 @@ fn we_can_do_provable_group_by() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(t),
@@ -984,7 +1104,7 @@ fn we_can_do_provable_group_by_without_sum() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![],
             "num_employee",
             tab(t),
@@ -995,6 +1115,39 @@ fn we_can_do_provable_group_by_without_sum() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_do_provable_group_by_with_max_and_min() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::Int,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select department, max(salary) as max_salary, sum(salary) as total_salary, min(bonus) as min_bonus, count(*) as num_employee from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![
+                max_expr(column(t, "salary", &accessor), "max_salary"),
+                sum_expr(column(t, "salary", &accessor), "total_salary"),
+                min_expr(column(t, "bonus", &accessor), "min_bonus"),
+            ],
+            "num_employee",
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_do_provable_group_by_with_two_group_by_columns() {
     let t = "sxt.employees".parse().unwrap();
@@ -1013,7 +1166,7 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["state", "department"], &accessor),
+            cols_expr_plan(t, &["state", "department"], &accessor),
             vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(t),
@@ -1024,6 +1177,69 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_prove_an_order_by_with_a_limit_on_a_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department order by total_salary desc, department limit 3 offset 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            order_by_exec(
+                group_by(
+                    cols_expr_plan(t, &["department"], &accessor),
+                    vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
+                    "num_employee",
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![(1, Desc), (0, Asc)],
+            ),
+            1,
+            Some(3),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_do_not_prove_an_order_by_with_a_limit_on_a_varchar_group_by_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "state".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select state, sum(salary) as total_salary, count(*) as num_employee from employees group by state order by state limit 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["state"], &accessor),
+            vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
+            "num_employee",
+            tab(t),
+            const_bool(true),
+        ),
+        vec![orders(&["state"], &[Asc]), slice(Some(3), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     let t = "sxt.employees".parse().unwrap();
@@ -1042,7 +1258,7 @@ fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![
                 sum_expr(column(t, "salary", &accessor), "total_salary"),
                 sum_expr(column(t, "tax", &accessor), "total_tax"),
@@ -1233,231 +1449,234 @@ fn we_can_have_aggregate_functions_without_a_group_by_clause() {
     let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
 
     let expected_ast = QueryExpr::new(
-        group_by(vec![], vec![], "__count__", tab(t), const_bool(true)),
+        aggregate(
+            vec![count_expr(column(t, "name", &accessor), "__count__")],
+            tab(t),
+            const_bool(true),
+        ),
         vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
+fn we_can_prove_sums_and_counts_without_a_group_by_clause() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
+            "bonus".into() => ColumnType::Int128,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select count(bonus) department from sxt.employees group by department",
+        "select count(*) as num_employees, sum(salary + bonus) as total from sxt.employees where salary >= 10",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["bonus", "department"], &accessor),
+        aggregate(
+            vec![
+                count_expr(const_bigint(1), "num_employees"),
+                sum_expr(
+                    add(
+                        column(t, "salary", &accessor),
+                        column(t, "bonus", &accessor),
+                    ),
+                    "total",
+                ),
+            ],
             tab(t),
-            const_bool(true),
+            gte(column(t, "salary", &accessor), const_bigint(10)),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count(col("bonus")), "department")],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
+fn we_can_prove_an_order_by_with_a_limit_on_an_aggregation_without_a_group_by_clause() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
+        "select count(*) as num_employees, sum(salary) as total from sxt.employees order by total limit 0",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["bonus", "department"], &accessor),
-            tab(t),
-            const_bool(true),
+        slice_exec(
+            order_by_exec(
+                aggregate(
+                    vec![
+                        count_expr(const_bigint(1), "num_employees"),
+                        sum_expr(column(t, "salary", &accessor), "total"),
+                    ],
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![(1, Asc)],
+            ),
+            0,
+            Some(0),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[
-                aliased_expr(col("department"), "department"),
-                aliased_expr(count(col("bonus")), "__count__"),
-                aliased_expr(count(col("department")), "dep"),
-            ],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
+fn we_can_prove_maxima_and_minima_without_a_group_by_clause() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
+            "bonus".into() => ColumnType::Int128,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select count(*) from sxt.employees where salary = 4 group by department",
+        "select max(salary) as max_salary, min(bonus) as min_bonus from sxt.employees where salary >= 10",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["department", "salary"], &accessor),
+        aggregate(
+            vec![
+                max_expr(column(t, "salary", &accessor), "max_salary"),
+                min_expr(column(t, "bonus", &accessor), "min_bonus"),
+            ],
             tab(t),
-            equal(column(t, "salary", &accessor), const_bigint(4)),
+            gte(column(t, "salary", &accessor), const_bigint(10)),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count_all(), "__count__")],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn aggregate_result_columns_cannot_reference_invalid_columns() {
+fn we_can_prove_averages_with_and_without_a_group_by_clause() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         t,
-        "select department, max(non_existent) from sxt.employees group by department",
+        "select avg(salary) as avg_salary, count(*) as num_employees from sxt.employees where salary >= 10",
         &accessor,
     );
-}
-
-#[test]
-fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
-    let t = "sxt.employees".parse().unwrap();
-    let accessor = schema_accessor_from_table_ref_with_schema(
-        t,
-        indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
-        },
+    let expected_ast = QueryExpr::new(
+        aggregate(
+            vec![
+                avg_expr(column(t, "salary", &accessor), "avg_salary"),
+                count_expr(const_bigint(1), "num_employees"),
+            ],
+            tab(t),
+            gte(column(t, "salary", &accessor), const_bigint(10)),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
+
     let ast = query_to_provable_ast(
         t,
-        "SELECT department as d1, department as d2 from employees group by department",
+        "select department, avg(salary) as avg_salary, count(*) as num_employees from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
+        group_by(
             cols_expr_plan(t, &["department"], &accessor),
+            vec![avg_expr(column(t, "salary", &accessor), "avg_salary")],
+            "num_employees",
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[
-                aliased_expr(col("department"), "d1"),
-                aliased_expr(col("department"), "d2"),
-            ],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
+fn we_cannot_convert_averages_that_are_not_proven() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "bonus".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
             "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
         },
     );
-    let query_text = "select salary d1, max(tax), salary d2, sum(bonus) sum_bonus, count(name) count_s from sxt.employees group by salary, bonus, salary";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
-    let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["bonus", "name", "salary", "tax"], &accessor),
-            tab(t),
-            const_bool(true),
-        ),
-        vec![group_by_postprocessing(
-            &["salary", "bonus", "salary"],
-            &[
-                aliased_expr(col("salary"), "d1"),
-                aliased_expr(max(col("tax")), "__max__"),
-                aliased_expr(col("salary"), "d2"),
-                aliased_expr(sum(col("bonus")), "sum_bonus"),
-                aliased_expr(count(col("name")), "count_s"),
-            ],
-        )],
-    );
-    assert_eq!(ast, expected_ast);
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select avg(salary) + 1 as total from sxt.employees")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnprovenAverage
+        })
+    ));
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select a from (select avg(salary) as a from sxt.employees) as s")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    invalid_query_to_provable_ast(t, "select avg(name) as a from sxt.employees", &accessor);
 }
 
 #[test]
-fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
+fn we_can_prove_percentiles_with_and_without_a_group_by_clause() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "a".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::Int128,
-            "b".into() => ColumnType::BigInt,
-            "h".into() => ColumnType::Int128,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    // TODO: add `a / b as a_div_b` result expr once polars properly
-    // supports decimal division without panicking in production
     let ast = query_to_provable_ast(
         t,
-        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        "select median(salary) as median_salary, percentile_disc(0.9) within group (order by salary) as p90 from sxt.employees where salary >= 10",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
+        aggregate(
             vec![
-                aliased_plan(
-                    add(column(t, "a", &accessor), column(t, "b", &accessor)),
-                    "__expr__",
-                ),
-                aliased_plan(multiply(const_bigint(2), column(t, "f", &accessor)), "f2"),
-                aliased_plan(
-                    subtract(const_bigint(-77), column(t, "h", &accessor)),
-                    "col",
-                ),
-                aliased_plan(
-                    add(column(t, "a", &accessor), column(t, "f", &accessor)),
-                    "af",
-                ),
+                median_expr(column(t, "salary", &accessor), "median_salary"),
+                percentile_disc_expr(9, 1, column(t, "salary", &accessor), "p90"),
             ],
             tab(t),
+            gte(column(t, "salary", &accessor), const_bigint(10)),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = query_to_provable_ast(
+        t,
+        "select department, percentile_cont(0.25) within group (order by salary) as p25, count(*) as num_employees from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![percentile_cont_expr(
+                25,
+                2,
+                column(t, "salary", &accessor),
+                "p25",
+            )],
+            "num_employees",
+            tab(t),
             const_bool(true),
         ),
         vec![],
@@ -1466,92 +1685,155 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
 }
 
 #[test]
-fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
-) {
+fn we_cannot_convert_percentiles_that_are_not_proven() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "c".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::BigInt,
-            "g".into() => ColumnType::BigInt,
-            "h".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select median(salary) + 1 as total from sxt.employees")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnprovenOrderStatistic
+        })
+    ));
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select a from (select median(salary) as a from sxt.employees) as s")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    invalid_query_to_provable_ast(t, "select median(name) as a from sxt.employees", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select percentile_disc(0.5) within group (order by name) as a from sxt.employees",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_do_not_prove_aggregations_of_aggregate_expressions_without_a_group_by_clause() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        "select sum(salary) + 1 as total from sxt.employees",
+        &accessor,
+    );
+    assert!(matches!(ast.proof_expr(), DynProofPlan::Filter(_)));
+}
+
+#[test]
+fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select count(bonus) department from sxt.employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            vec![
-                aliased_plan(
-                    multiply(
-                        add(const_bigint(2), column(t, "f", &accessor)),
-                        add(
-                            add(column(t, "c", &accessor), column(t, "g", &accessor)),
-                            multiply(const_bigint(2), column(t, "h", &accessor)),
-                        ),
-                    ),
-                    "__expr__",
-                ),
-                aliased_plan(
-                    multiply(
-                        add(
-                            add(
-                                multiply(
-                                    subtract(column(t, "h", &accessor), column(t, "g", &accessor)),
-                                    const_bigint(2),
-                                ),
-                                column(t, "c", &accessor),
-                            ),
-                            column(t, "g", &accessor),
-                        ),
-                        add(column(t, "f", &accessor), const_bigint(2)),
-                    ),
-                    "d",
-                ),
-            ],
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[aliased_expr(count(col("bonus")), "department")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_group_by_query_with_an_expression_key() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_id + 1 as d, count(*) as n from employees group by dept_id + 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![aliased_plan(
+                add(column(employees, "dept_id", &accessor), const_bigint(1)),
+                "d",
+            )],
+            vec![],
+            "n",
+            tab(employees),
+            const_bool(true),
+        ),
         vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+fn we_cannot_convert_unsupported_group_by_expression_queries() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // The expression key needs postprocessing since it is not the first result
+    invalid_query_to_provable_ast(
+        employees,
+        "select count(*) as n, dept_id + 1 as d from employees group by dept_id + 1",
+        &accessor,
+    );
+    // Aggregations can not be grouped by
+    invalid_query_to_provable_ast(
+        employees,
+        "select count(*) as n from employees group by sum(id)",
+        &accessor,
+    );
+}
+
+#[test]
+fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "c".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::BigInt,
-            "g".into() => ColumnType::BigInt,
-            "k".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["c", "f"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["c"],
+            &["department"],
             &[
-                aliased_expr(col("c"), "c"),
-                aliased_expr(
-                    sum(psub(padd(pmul(lit(2), col("f")), col("c")), lit(-7))),
-                    "d",
-                ),
+                aliased_expr(col("department"), "department"),
+                aliased_expr(count(col("bonus")), "__count__"),
+                aliased_expr(count(col("department")), "dep"),
             ],
         )],
     );
@@ -1559,491 +1841,935 @@ fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
 }
 
 #[test]
-fn we_cannot_use_non_grouped_columns_outside_agg() {
+fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
-    let identifier_not_in_agg_queries = vec![
-        "select salary from sxt.employees group by name",
-        "select sum(salary), salary from sxt.employees group by name",
-        "select min(salary) + salary from sxt.employees group by name",
-        "select 2 * salary, min(salary) from sxt.employees group by name",
-    ];
-
-    for query_text in &identifier_not_in_agg_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-        assert!(matches!(
-            result,
-            Err(ConversionError::PostprocessingError {
-                source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
-            })
-        ));
-    }
-
-    let invalid_group_by_queries = vec![
-        "select 2 * salary, min(salary) from sxt.employees",
-        "select sum(salary), salary from sxt.employees",
-        "select max(salary) + 2 * salary from sxt.employees",
-    ];
-
-    for query_text in &invalid_group_by_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-        assert!(matches!(
-            result,
-            Err(ConversionError::InvalidGroupByColumnRef { .. })
-        ));
-    }
+    let ast = query_to_provable_ast(
+        t,
+        "select count(*) from sxt.employees where salary = 4 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["department", "salary"], &accessor),
+            tab(t),
+            equal(column(t, "salary", &accessor), const_bigint(4)),
+        ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[aliased_expr(count_all(), "__count__")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn varchar_column_is_not_compatible_with_integer_column() {
+fn aggregate_result_columns_cannot_reference_invalid_columns() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
-
-    let bigint_to_varchar_queries = vec![
-        "select -123 * name from sxt.employees",
-        "select salary - name from sxt.employees",
-    ];
-
-    let varchar_to_bigint_queries = vec![
-        "select name from sxt.employees where 'abc' = salary",
-        "select name from sxt.employees where 'abc' != salary",
-    ];
-
-    for query_text in &bigint_to_varchar_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::DataTypeMismatch {
-                left_type: ColumnType::BigInt.to_string(),
-                right_type: ColumnType::VarChar.to_string(),
-            })
-        );
-    }
-
-    for query_text in &varchar_to_bigint_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::DataTypeMismatch {
-                left_type: ColumnType::VarChar.to_string(),
-                right_type: ColumnType::BigInt.to_string(),
-            })
-        );
-    }
-}
-
-#[test]
-fn arithmetic_operations_are_not_allowed_with_varchar_column() {
-    let t = "sxt.employees".parse().unwrap();
-    let accessor = schema_accessor_from_table_ref_with_schema(
+    invalid_query_to_provable_ast(
         t,
-        indexmap! {
-            "name".into() => ColumnType::VarChar,
-            "position".into() => ColumnType::VarChar,
-        },
-    );
-
-    let query_text = "select name - position from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-    assert_eq!(
-        result,
-        Err(ConversionError::DataTypeMismatch {
-            left_type: ColumnType::VarChar.to_string(),
-            right_type: ColumnType::VarChar.to_string(),
-        })
+        "select department, max(non_existent) from sxt.employees group by department",
+        &accessor,
     );
 }
 
 #[test]
-fn varchar_column_is_not_allowed_within_numeric_aggregations() {
+fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
-    let sum_query = "select sum(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(sum_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-    assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'sum'"
-    ));
-
-    let max_query = "select max(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(max_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-    assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'max'"
-    ));
-
-    let min_query = "select min(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(min_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-    assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'min'"
-    ));
-}
-
-#[test]
-fn group_by_with_bigint_column_is_valid() {
-    let t = "sxt.employees".parse().unwrap();
-    let accessor = schema_accessor_from_table_ref_with_schema(
+    let ast = query_to_provable_ast(
         t,
-        indexmap! {
-            "salary".into() => ColumnType::BigInt,
-        },
+        "SELECT department as d1, department as d2 from employees group by department",
+        &accessor,
     );
-    let query_text = "select salary from sxt.employees group by salary";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["salary"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["salary"],
-            &[aliased_expr(col("salary"), "salary")],
+            &["department"],
+            &[
+                aliased_expr(col("department"), "d1"),
+                aliased_expr(col("department"), "d2"),
+            ],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_decimal_column_is_valid() {
+fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "salary".into() => ColumnType::Int128,
+            "bonus".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
         },
     );
-    let query_text = "select salary from sxt.employees group by salary";
+    let query_text = "select salary d1, max(tax), salary d2, sum(bonus) sum_bonus, count(name) count_s from sxt.employees group by salary, bonus, salary";
 
     let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
 
-    let expected_query = QueryExpr::new(
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["salary"], &accessor),
+            cols_expr_plan(t, &["bonus", "name", "salary", "tax"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["salary"],
-            &[aliased_expr(col("salary"), "salary")],
+            &["salary", "bonus", "salary"],
+            &[
+                aliased_expr(col("salary"), "d1"),
+                aliased_expr(max(col("tax")), "__max__"),
+                aliased_expr(col("salary"), "d2"),
+                aliased_expr(sum(col("bonus")), "sum_bonus"),
+                aliased_expr(count(col("name")), "count_s"),
+            ],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_varchar_column_is_valid() {
+fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
+            "a".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::Int128,
+            "b".into() => ColumnType::BigInt,
+            "h".into() => ColumnType::Int128,
         },
     );
-    let query_text = "select name from sxt.employees group by name";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    // TODO: add `a / b as a_div_b` result expr once polars properly
+    // supports decimal division without panicking in production
+    let ast = query_to_provable_ast(
+        t,
+        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["name"], &accessor),
+            vec![
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "__expr__",
+                ),
+                aliased_plan(multiply(const_bigint(2), column(t, "f", &accessor)), "f2"),
+                aliased_plan(
+                    subtract(const_bigint(-77), column(t, "h", &accessor)),
+                    "col",
+                ),
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "f", &accessor)),
+                    "af",
+                ),
+            ],
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["name"],
-            &[aliased_expr(col("name"), "name")],
-        )],
+        vec![],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
+fn we_can_parse_a_provable_division_in_the_result_and_where_exprs() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
         },
     );
-    let query_text =
-        "select 2 * salary + sum(salary) - tax from sxt.employees group by salary, tax";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let ast = query_to_provable_ast(
+        t,
+        "select a / b as q, b from employees where a / 2 = 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["salary", "tax"], &accessor),
+            vec![
+                aliased_plan(
+                    divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "q",
+                ),
+                col_expr_plan(t, "b", &accessor),
+            ],
             tab(t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &["salary", "tax"],
-                &[aliased_expr(
-                    psub(
-                        padd(pmul(lit(2), col("salary")), sum(col("salary"))),
-                        col("tax"),
-                    ),
-                    "__expr__",
-                )],
+            equal(
+                divide(column(t, "a", &accessor), const_bigint(2)),
+                const_bigint(3),
             ),
-            select_expr(&[aliased_expr(
-                psub(
-                    padd(pmul(lit(2), col("salary")), col("__col_agg_0")),
-                    col("tax"),
-                ),
-                "__expr__",
-            )]),
-        ],
+        ),
+        vec![],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
+fn we_can_parse_provable_casts_in_the_result_and_where_exprs() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::Int128,
+            "a".into() => ColumnType::Int,
+            "d".into() => ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
         },
     );
-    let query_text = "select 7 + max(salary) as max_i, min(salary + 777 * bonus) * -5 as min_d from sxt.employees";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
+    let ast = query_to_provable_ast(
+        t,
+        "select cast(a as decimal(12, 2)) + d as s, cast(d as bigint) as b from employees where cast(d as int) = a",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["bonus", "salary"], &accessor),
-            tab(t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &[],
-                &[
-                    aliased_expr(padd(lit(7), max(col("salary"))), "max_i"),
-                    aliased_expr(
-                        pmul(
-                            min(padd(col("salary"), pmul(lit(777), col("bonus")))),
-                            lit(-5),
+            vec![
+                aliased_plan(
+                    add(
+                        cast(
+                            column(t, "a", &accessor),
+                            ColumnType::Decimal75(Precision::new(12).unwrap(), 2),
                         ),
-                        "min_d",
+                        column(t, "d", &accessor),
                     ),
-                ],
+                    "s",
+                ),
+                aliased_plan(cast(column(t, "d", &accessor), ColumnType::BigInt), "b"),
+            ],
+            tab(t),
+            equal(
+                cast(column(t, "d", &accessor), ColumnType::Int),
+                column(t, "a", &accessor),
             ),
-            select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "max_i"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "min_d"),
-            ]),
-        ],
+        ),
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_aggregation_always_have_integer_type() {
+fn we_cannot_parse_unsupported_casts() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
-            "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::Int128,
+            "a".into() => ColumnType::Int,
+            "b".into() => ColumnType::VarChar,
         },
     );
-    let query_text =
-        "select 7 + count(name) as cs, count(salary) * -5 as ci, count(tax) from sxt.employees";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+    invalid_query_to_provable_ast(t, "select cast(a as varchar) from employees", &accessor);
+    invalid_query_to_provable_ast(t, "select cast(b as int) from employees", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select cast(a as timestamp(3)) from employees",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select cast(a as decimal(80, 2)) from employees",
+        &accessor,
+    );
+}
 
+#[test]
+fn we_can_parse_provable_timestamp_functions_and_intervals() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select date_trunc('day', ts) as d, extract(hour from ts - interval '30 minutes') as h from employees where extract(year from interval '1 day' + ts) = 2024",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["name", "salary", "tax"], &accessor),
+            vec![
+                aliased_plan(
+                    date_trunc(DateTimeField::Day, column(t, "ts", &accessor)),
+                    "d",
+                ),
+                aliased_plan(
+                    extract(
+                        DateTimeField::Hour,
+                        add_interval(column(t, "ts", &accessor), -30, DateTimeField::Minute),
+                    ),
+                    "h",
+                ),
+            ],
             tab(t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &[],
-                &[
-                    aliased_expr(padd(lit(7), count(col("name"))), "cs"),
-                    aliased_expr(pmul(count(col("salary")), lit(-5)), "ci"),
-                    aliased_expr(count(col("tax")), "__count__"),
-                ],
+            equal(
+                extract(
+                    DateTimeField::Year,
+                    add_interval(column(t, "ts", &accessor), 1, DateTimeField::Day),
+                ),
+                const_bigint(2024),
             ),
-            select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "cs"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "ci"),
-                aliased_expr(col("__col_agg_2"), "__count__"),
-            ]),
-        ],
+        ),
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn select_wildcard_is_valid_with_group_by_exprs() {
-    let columns = [
-        "employee_name",
-        "base_salary",
-        "annual_bonus",
-        "manager_name",
-        "manager_salary",
-        "manager_bonus",
-        "department_name",
-        "department_budget",
-        "department_headcount",
-    ];
-    let sorted_columns = columns.iter().sorted().collect::<Vec<_>>();
-    let aliased_exprs = columns
-        .iter()
-        .map(|c| aliased_expr(col(c), c))
-        .collect::<Vec<_>>();
+fn we_cannot_parse_unprovable_timestamp_functions_and_intervals() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "employee_name".into() => ColumnType::VarChar,
-            "base_salary".into() => ColumnType::BigInt,
-            "annual_bonus".into() => ColumnType::Int128,
-            "manager_name".into() => ColumnType::VarChar,
-            "manager_salary".into() => ColumnType::BigInt,
-            "manager_bonus".into() => ColumnType::Int128,
-            "department_name".into() => ColumnType::VarChar,
-            "department_budget".into() => ColumnType::BigInt,
-            "department_headcount".into() => ColumnType::Int128,
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "a".into() => ColumnType::BigInt,
         },
     );
+    for query in [
+        "select date_trunc('quarter', ts) as q from employees",
+        "select extract(week from ts) as w from employees",
+        "select extract(year from a) as y from employees",
+        "select ts + interval '1 month' as m from employees",
+        "select ts + interval '1 millisecond' as m from employees",
+        "select interval '1 day' - ts as m from employees",
+        "select a + interval '1 day' as m from employees",
+        "select interval '1 day' as i from employees",
+    ] {
+        invalid_query_to_provable_ast(t, query, &accessor);
+    }
+}
 
-    let query_text = format!(
-        "SELECT * FROM {} GROUP BY {}",
-        "sxt.employees",
-        columns.join(", ")
+#[test]
+fn a_division_by_an_int128_is_done_in_postprocessing() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "h".into() => ColumnType::Int128,
+        },
     );
-
-    let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
-    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
-
+    let ast = query_to_provable_ast(t, "select a / h as q from employees", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
-            sorted_columns
-                .iter()
-                .map(|c| col_expr_plan(t, c, &accessor))
-                .collect(),
+            cols_expr_plan(t, &["a", "h"], &accessor),
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(&columns, &aliased_exprs)],
+        vec![select_expr(&[aliased_expr(pdiv(col("a"), col("h")), "q")])],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn nested_aggregations_are_not_supported() {
-    let supported_agg = ["max", "min", "sum", "count"];
+fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
+) {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::BigInt,
+            "g".into() => ColumnType::BigInt,
+            "h".into() => ColumnType::BigInt,
         },
     );
+    let ast = query_to_provable_ast(
+        t,
+        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    multiply(
+                        add(const_bigint(2), column(t, "f", &accessor)),
+                        add(
+                            add(column(t, "c", &accessor), column(t, "g", &accessor)),
+                            multiply(const_bigint(2), column(t, "h", &accessor)),
+                        ),
+                    ),
+                    "__expr__",
+                ),
+                aliased_plan(
+                    multiply(
+                        add(
+                            add(
+                                multiply(
+                                    subtract(column(t, "h", &accessor), column(t, "g", &accessor)),
+                                    const_bigint(2),
+                                ),
+                                column(t, "c", &accessor),
+                            ),
+                            column(t, "g", &accessor),
+                        ),
+                        add(column(t, "f", &accessor), const_bigint(2)),
+                    ),
+                    "d",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
 
-    for perm_aggs in supported_agg.iter().permutations(2) {
-        let query_text = format!(
-            "SELECT {}({}(salary)) FROM sxt.employees",
-            perm_aggs[0], perm_aggs[1]
-        );
-
-        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
-        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::InvalidExpression {
-                expression: "nested aggregations are not supported".to_string()
-            })
-        );
-    }
+#[test]
+fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "c".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::BigInt,
+            "g".into() => ColumnType::BigInt,
+            "k".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["c", "f"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["c"],
+            &[
+                aliased_expr(col("c"), "c"),
+                aliased_expr(
+                    sum(psub(padd(pmul(lit(2), col("f")), col("c")), lit(-7))),
+                    "d",
+                ),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn select_group_and_order_by_preserve_the_column_order_reference() {
-    const N: usize = 4;
+fn we_cannot_use_non_grouped_columns_outside_agg() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
             "name".into() => ColumnType::VarChar,
         },
     );
-    let base_cols: [&str; N] = ["salary", "department", "tax", "name"]; // sorted because of `select: [cols = ... ]`
-    let base_ordering = [Asc, Desc, Asc, Desc];
-    for (idx, perm_cols) in base_cols
-        .into_iter()
-        .permutations(N)
-        .collect::<IndexSet<_>>()
-        .into_iter()
-        .enumerate()
-    {
-        let perm_col_plans = perm_cols
-            .iter()
-            .sorted()
-            .map(|c| col_expr_plan(t, c, &accessor))
-            .collect();
+    let identifier_not_in_agg_queries = vec![
+        "select salary from sxt.employees group by name",
+        "select sum(salary), salary from sxt.employees group by name",
+        "select min(salary) + salary from sxt.employees group by name",
+        "select 2 * salary, min(salary) from sxt.employees group by name",
+    ];
+
+    for query_text in &identifier_not_in_agg_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+        assert!(matches!(
+            result,
+            Err(ConversionError::PostprocessingError {
+                source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
+            })
+        ));
+    }
+
+    let invalid_group_by_queries = vec![
+        "select 2 * salary, min(salary) from sxt.employees",
+        "select sum(salary), salary from sxt.employees",
+        "select max(salary) + 2 * salary from sxt.employees",
+    ];
+
+    for query_text in &invalid_group_by_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+        assert!(matches!(
+            result,
+            Err(ConversionError::InvalidGroupByColumnRef { .. })
+        ));
+    }
+}
+
+#[test]
+fn varchar_column_is_not_compatible_with_integer_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+
+    let bigint_to_varchar_queries = vec![
+        "select -123 * name from sxt.employees",
+        "select salary - name from sxt.employees",
+    ];
+
+    let varchar_to_bigint_queries = vec![
+        "select name from sxt.employees where 'abc' = salary",
+        "select name from sxt.employees where 'abc' != salary",
+    ];
+
+    for query_text in &bigint_to_varchar_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::DataTypeMismatch {
+                left_type: ColumnType::BigInt.to_string(),
+                right_type: ColumnType::VarChar.to_string(),
+            })
+        );
+    }
+
+    for query_text in &varchar_to_bigint_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::DataTypeMismatch {
+                left_type: ColumnType::VarChar.to_string(),
+                right_type: ColumnType::BigInt.to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn arithmetic_operations_are_not_allowed_with_varchar_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+            "position".into() => ColumnType::VarChar,
+        },
+    );
+
+    let query_text = "select name - position from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+    assert_eq!(
+        result,
+        Err(ConversionError::DataTypeMismatch {
+            left_type: ColumnType::VarChar.to_string(),
+            right_type: ColumnType::VarChar.to_string(),
+        })
+    );
+}
+
+#[test]
+fn varchar_column_is_not_allowed_within_numeric_aggregations() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let sum_query = "select sum(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(sum_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'sum'"
+    ));
+
+    let max_query = "select max(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(max_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'max'"
+    ));
+
+    let min_query = "select min(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(min_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'min'"
+    ));
+}
+
+#[test]
+fn group_by_with_bigint_column_is_valid() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text = "select salary from sxt.employees group by salary";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["salary"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["salary"],
+            &[aliased_expr(col("salary"), "salary")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_decimal_column_is_valid() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::Int128,
+        },
+    );
+    let query_text = "select salary from sxt.employees group by salary";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["salary"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["salary"],
+            &[aliased_expr(col("salary"), "salary")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_varchar_column_is_valid() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let query_text = "select name from sxt.employees group by name";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["name"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["name"],
+            &[aliased_expr(col("name"), "name")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text =
+        "select 2 * salary + sum(salary) - tax from sxt.employees group by salary, tax";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["salary", "tax"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["salary", "tax"],
+                &[aliased_expr(
+                    psub(
+                        padd(pmul(lit(2), col("salary")), sum(col("salary"))),
+                        col("tax"),
+                    ),
+                    "__expr__",
+                )],
+            ),
+            select_expr(&[aliased_expr(
+                psub(
+                    padd(pmul(lit(2), col("salary")), col("__col_agg_0")),
+                    col("tax"),
+                ),
+                "__expr__",
+            )]),
+        ],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::Int128,
+        },
+    );
+    let query_text = "select 7 + max(salary) as max_i, min(salary + 777 * bonus) * -5 as min_d from sxt.employees";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["bonus", "salary"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &[],
+                &[
+                    aliased_expr(padd(lit(7), max(col("salary"))), "max_i"),
+                    aliased_expr(
+                        pmul(
+                            min(padd(col("salary"), pmul(lit(777), col("bonus")))),
+                            lit(-5),
+                        ),
+                        "min_d",
+                    ),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(padd(lit(7), col("__col_agg_0")), "max_i"),
+                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "min_d"),
+            ]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn count_aggregation_always_have_integer_type() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::Int128,
+        },
+    );
+    let query_text =
+        "select 7 + count(name) as cs, count(salary) * -5 as ci, count(tax) from sxt.employees";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["name", "salary", "tax"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &[],
+                &[
+                    aliased_expr(padd(lit(7), count(col("name"))), "cs"),
+                    aliased_expr(pmul(count(col("salary")), lit(-5)), "ci"),
+                    aliased_expr(count(col("tax")), "__count__"),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(padd(lit(7), col("__col_agg_0")), "cs"),
+                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "ci"),
+                aliased_expr(col("__col_agg_2"), "__count__"),
+            ]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn select_wildcard_is_valid_with_group_by_exprs() {
+    let columns = [
+        "employee_name",
+        "base_salary",
+        "annual_bonus",
+        "manager_name",
+        "manager_salary",
+        "manager_bonus",
+        "department_name",
+        "department_budget",
+        "department_headcount",
+    ];
+    let sorted_columns = columns.iter().sorted().collect::<Vec<_>>();
+    let aliased_exprs = columns
+        .iter()
+        .map(|c| aliased_expr(col(c), c))
+        .collect::<Vec<_>>();
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "employee_name".into() => ColumnType::VarChar,
+            "base_salary".into() => ColumnType::BigInt,
+            "annual_bonus".into() => ColumnType::Int128,
+            "manager_name".into() => ColumnType::VarChar,
+            "manager_salary".into() => ColumnType::BigInt,
+            "manager_bonus".into() => ColumnType::Int128,
+            "department_name".into() => ColumnType::VarChar,
+            "department_budget".into() => ColumnType::BigInt,
+            "department_headcount".into() => ColumnType::Int128,
+        },
+    );
+
+    let query_text = format!(
+        "SELECT * FROM {} GROUP BY {}",
+        "sxt.employees",
+        columns.join(", ")
+    );
+
+    let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+    let ast = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            sorted_columns
+                .iter()
+                .map(|c| col_expr_plan(t, c, &accessor))
+                .collect(),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(&columns, &aliased_exprs)],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn nested_aggregations_are_not_supported() {
+    let supported_agg = ["max", "min", "sum", "count"];
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+        },
+    );
+
+    for perm_aggs in supported_agg.iter().permutations(2) {
+        let query_text = format!(
+            "SELECT {}({}(salary)) FROM sxt.employees",
+            perm_aggs[0], perm_aggs[1]
+        );
+
+        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+        let result = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::InvalidExpression {
+                expression: "nested aggregations are not supported".to_string()
+            })
+        );
+    }
+}
+
+#[test]
+fn select_group_and_order_by_preserve_the_column_order_reference() {
+    const N: usize = 4;
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let base_cols: [&str; N] = ["salary", "department", "tax", "name"]; // sorted because of `select: [cols = ... ]`
+    let base_ordering = [Asc, Desc, Asc, Desc];
+    for (idx, perm_cols) in base_cols
+        .into_iter()
+        .permutations(N)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .enumerate()
+    {
+        let perm_col_plans = perm_cols
+            .iter()
+            .sorted()
+            .map(|c| col_expr_plan(t, c, &accessor))
+            .collect();
         let aliased_perm_cols = perm_cols
             .iter()
             .map(|c| aliased_expr(col(c), c))
@@ -2067,84 +2793,1655 @@ fn select_group_and_order_by_preserve_the_column_order_reference() {
                 .join(", ")
         );
 
-        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
-        let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+        let query = QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor).unwrap();
+
+        let expected_query = QueryExpr::new(
+            filter(perm_col_plans, tab(t), const_bool(true)),
+            vec![
+                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
+                orders(&order_cols_vec, &ordering_vec),
+            ],
+        );
+        assert_eq!(query, expected_query);
+    }
+}
+
+///////////////////////////
+// Join
+///////////////////////////
+fn employees_and_departments_accessor() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let employees: TableRef = "sxt.employees".parse().unwrap();
+    let departments: TableRef = "sxt.departments".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        employees => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "dept_id".into() => ColumnType::BigInt,
+        },
+        departments => indexmap! {
+            "department_id".into() => ColumnType::BigInt,
+            "dept_name".into() => ColumnType::VarChar,
+            "budget".into() => ColumnType::Int128,
+        },
+    });
+    (employees, departments, accessor)
+}
+
+#[test]
+fn we_can_convert_a_join_selecting_columns_from_both_tables() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name, dept_name from employees join departments on employees.dept_id = departments.department_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join_exec(
+            projection(
+                cols_expr_plan(employees, &["name", "dept_id"], &accessor),
+                tab(employees),
+            ),
+            projection(
+                cols_expr_plan(departments, &["department_id", "dept_name"], &accessor),
+                tab(departments),
+            ),
+            1,
+            0,
+            &["name", "sxt.employees.dept_id", "dept_name"],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("name"), "name"),
+            aliased_expr(col("dept_name"), "dept_name"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_join_with_the_join_condition_in_either_order() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name, dept_name from employees inner join departments on department_id = dept_id",
+        &accessor,
+    );
+    let expected_ast = query_to_provable_ast(
+        employees,
+        "select name, dept_name from employees join departments on dept_id = department_id",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_join_selecting_all_columns() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select * from employees join departments on dept_id = department_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join_exec(
+            table_exec(
+                employees,
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("name", ColumnType::VarChar),
+                    column_field("dept_id", ColumnType::BigInt),
+                ],
+            ),
+            table_exec(
+                departments,
+                vec![
+                    column_field("department_id", ColumnType::BigInt),
+                    column_field("dept_name", ColumnType::VarChar),
+                    column_field("budget", ColumnType::Int128),
+                ],
+            ),
+            2,
+            0,
+            &["id", "name", "dept_id", "dept_name", "budget"],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("id"), "id"),
+            aliased_expr(col("name"), "name"),
+            aliased_expr(col("dept_id"), "dept_id"),
+            aliased_expr(col("dept_name"), "dept_name"),
+            aliased_expr(col("budget"), "budget"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_join_with_where_clauses_pushed_down_to_each_table() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name, budget from employees join departments on dept_id = department_id where id >= 3 and budget = 100",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join_exec(
+            filter(
+                cols_expr_plan(employees, &["name", "dept_id"], &accessor),
+                tab(employees),
+                gte(column(employees, "id", &accessor), const_bigint(3)),
+            ),
+            filter(
+                cols_expr_plan(departments, &["department_id", "budget"], &accessor),
+                tab(departments),
+                equal(column(departments, "budget", &accessor), const_bigint(100)),
+            ),
+            1,
+            0,
+            &["name", "sxt.employees.dept_id", "budget"],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("name"), "name"),
+            aliased_expr(col("budget"), "budget"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_join_with_group_by_order_by_and_slice() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_name, count(*) as num_employees from employees join departments on dept_id = department_id group by dept_name order by num_employees desc limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join_exec(
+            projection(
+                cols_expr_plan(employees, &["dept_id"], &accessor),
+                tab(employees),
+            ),
+            projection(
+                cols_expr_plan(departments, &["department_id", "dept_name"], &accessor),
+                tab(departments),
+            ),
+            0,
+            0,
+            &["sxt.employees.dept_id", "dept_name"],
+        ),
+        vec![
+            group_by_postprocessing(
+                &["dept_name"],
+                &[
+                    aliased_expr(col("dept_name"), "dept_name"),
+                    aliased_expr(count(lit(1)), "num_employees"),
+                ],
+            ),
+            orders(&["num_employees"], &[Desc]),
+            slice(Some(2), Some(0)),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_not_convert_a_join_with_an_ambiguous_column() {
+    let employees: TableRef = "sxt.employees".parse().unwrap();
+    let departments: TableRef = "sxt.departments".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        employees => indexmap! {
+            "name".into() => ColumnType::VarChar,
+            "dept_id".into() => ColumnType::BigInt,
+        },
+        departments => indexmap! {
+            "dept_id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    });
+    // The join columns are equal, so referencing them without a table is fine
+    query_to_provable_ast(
+        employees,
+        "select dept_id from employees join departments on employees.dept_id = departments.dept_id",
+        &accessor,
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select name from employees join departments on employees.dept_id = departments.dept_id")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, employees.schema_id(), &accessor),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_not_convert_a_join_with_a_where_clause_referencing_both_tables() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select name from employees join departments on dept_id = department_id where id = budget")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, employees.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { .. })
+    ));
+}
+
+#[test]
+fn we_can_not_convert_a_join_on_columns_of_different_types_or_missing_columns() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees join departments on name = department_id",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees join departments on dept_id = missing_id",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees join missing_table on dept_id = department_id",
+        &accessor,
+    );
+}
+
+/// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
+fn query_expr_for_test_table(sql_text: &str) -> QueryExpr {
+    let schema_accessor = schema_accessor_from_table_ref_with_schema(
+        "test.table".parse().unwrap(),
+        indexmap! {
+            "bigint_column".into() => ColumnType::BigInt,
+            "varchar_column".into() => ColumnType::VarChar,
+            "int128_column".into() => ColumnType::Int128,
+        },
+    );
+    let default_schema = "test".into();
+    let select_statement = SelectStatementParser::new().parse(sql_text).unwrap();
+    QueryExpr::try_new(select_statement, default_schema, &schema_accessor).unwrap()
+}
+
+/// Serializes and deserializes [`QueryExpr`] with flexbuffers and asserts that it remains the same.
+fn assert_query_expr_serializes_to_and_from_flex_buffers(query_expr: &QueryExpr) {
+    let serialized = flexbuffers::to_vec(query_expr).unwrap();
+    let deserialized: QueryExpr = flexbuffers::from_slice(serialized.as_slice()).unwrap();
+    assert_eq!(deserialized, *query_expr);
+}
+
+#[test]
+fn basic_query_expr_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table("select * from table");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_selected_columns_can_serialize_to_and_from_flex_buffers() {
+    let query_expr =
+        query_expr_for_test_table("select bigint_column, varchar_column, int128_column from table");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_aggregation_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table("select count(*) from table group by bigint_column");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_filters_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table(
+        "select * from table where bigint_column != 5 and varchar_column = 'example' or int128_column = 10",
+    );
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+fn nullable_schema_accessor(t: TableRef) -> TestSchemaAccessor {
+    schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "a$presence".into() => ColumnType::Boolean,
+            "b".into() => ColumnType::BigInt,
+            "b$presence".into() => ColumnType::Boolean,
+            "c".into() => ColumnType::BigInt,
+        },
+    )
+}
+
+#[test]
+fn we_can_convert_an_ast_with_nullable_columns() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select a, c from sxt_tab where b = 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "a$presence", "c"], &accessor),
+            tab(t),
+            and(
+                equal(column(t, "b", &accessor), const_bigint(3)),
+                column(t, "b$presence", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_nullable_result_columns_with_select_star() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select * from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "a$presence", "b", "b$presence", "c"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_division_by_a_nullable_column() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select c / b as q from sxt_tab", &accessor);
+    let b_presence = column(t, "b$presence", &accessor);
+    // The divisor is replaced where it is NULL, so that NULL rows never divide by zero
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    divide(
+                        column(t, "c", &accessor),
+                        case(
+                            b_presence.clone(),
+                            column(t, "b", &accessor),
+                            const_bigint(1),
+                        ),
+                    ),
+                    "q",
+                ),
+                aliased_plan(b_presence, "q$presence"),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_is_null_and_is_not_null() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select a is null as a_null, c is null as c_null from sxt_tab where b is not null",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(not(column(t, "a$presence", &accessor)), "a_null"),
+                aliased_plan(const_bool(false), "c_null"),
+            ],
+            tab(t),
+            column(t, "b$presence", &accessor),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_three_valued_logic() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select c from sxt_tab where a = 1 or c = 2 and b = 3",
+        &accessor,
+    );
+    let a_eq = equal(column(t, "a", &accessor), const_bigint(1));
+    let c_eq = equal(column(t, "c", &accessor), const_bigint(2));
+    let b_eq = equal(column(t, "b", &accessor), const_bigint(3));
+    let a_presence = column(t, "a$presence", &accessor);
+    let b_presence = column(t, "b$presence", &accessor);
+    // `c = 2 and b = 3` is known where `b` is not NULL or where `c = 2` is false
+    let and_presence = or(b_presence, not(c_eq.clone()));
+    let and_expr = and(c_eq, b_eq);
+    // `a = 1 or (...)` is known where both sides are known or a known side is true
+    let or_presence = or(
+        or(
+            and(a_presence.clone(), and_presence.clone()),
+            and(a_presence, a_eq.clone()),
+        ),
+        and(and_presence, and_expr.clone()),
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["c"], &accessor),
+            tab(t),
+            and(or(a_eq, and_expr), or_presence),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_an_order_by_on_a_nullable_column() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select c, a from sxt_tab order by a desc, c", &accessor);
+    let expected_ast = QueryExpr::new(
+        order_by_exec(
+            filter(
+                cols_expr_plan(t, &["c", "a", "a$presence"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            vec![(2, Asc), (1, Desc), (0, Asc)],
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_order_by_a_nullable_varchar_column() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
+            "c$presence".into() => ColumnType::Boolean,
+        },
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select a, c from sxt_tab order by c limit 2")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { error }) if error.contains("VARCHAR")
+    ));
+}
+
+#[test]
+fn we_cannot_postprocess_nullable_columns() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab group by a", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select sum(a) as s, c from sxt_tab group by c",
+        &accessor,
+    );
+}
+
+fn character_schema_accessor(t: TableRef) -> TestSchemaAccessor {
+    schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "s".into() => ColumnType::VarChar,
+            "s$length".into() => ColumnType::Int,
+            "s$char_0".into() => ColumnType::Int,
+            "s$char_1".into() => ColumnType::Int,
+            "b".into() => ColumnType::BigInt,
+        },
+    )
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_prefix_like() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = character_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select * from sxt_tab where s like 'a_%'", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["s", "b"], &accessor),
+            tab(t),
+            and(
+                gte(column(t, "s$length", &accessor), const_int(2)),
+                equal(column(t, "s$char_0", &accessor), const_int(97)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_like_patterns_of_a_fixed_length() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = character_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select s not like 'ab' as not_ab from sxt_tab where s like '_b'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(
+                not(equal(column(t, "s", &accessor), const_varchar("ab"))),
+                "not_ab",
+            )],
+            tab(t),
+            and(
+                equal(column(t, "s$length", &accessor), const_int(2)),
+                equal(column(t, "s$char_1", &accessor), const_int(98)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_prove_like_patterns_beyond_the_committed_characters() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = character_schema_accessor(t);
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where s like '%a'", &accessor);
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where s like 'a%b'", &accessor);
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where s like 'abc%'", &accessor);
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where b like 'a%'", &accessor);
+}
+
+#[test]
+fn we_can_postprocess_like_patterns_that_can_not_be_proven() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = character_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select s like '%b%' as has_b from sxt_tab where b = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["b", "s"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(1)),
+        ),
+        vec![select_expr(&[aliased_expr(
+            plike(col("s"), "%b%"),
+            "has_b",
+        )])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_in_list_of_literals() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(t, "select c from sxt_tab where a in (1, 2)", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["c"], &accessor),
+            tab(t),
+            and(
+                in_list(
+                    column(t, "a", &accessor),
+                    vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)],
+                ),
+                column(t, "a$presence", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_in_list_of_non_literals() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select c not in (3, c + 1) as c_out from sxt_tab",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(
+                not(or(
+                    equal(column(t, "c", &accessor), const_bigint(3)),
+                    equal(
+                        column(t, "c", &accessor),
+                        add(column(t, "c", &accessor), const_bigint(1)),
+                    ),
+                )),
+                "c_out",
+            )],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_between() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select c from sxt_tab where c between 1 and b",
+        &accessor,
+    );
+    let is_above_low = gte(column(t, "c", &accessor), const_bigint(1));
+    let is_below_high = lte(column(t, "c", &accessor), column(t, "b", &accessor));
+    // The conjunction is known where `b` is not NULL or where `c >= 1` is false
+    let presence = or(
+        column(t, "b$presence", &accessor),
+        not(is_above_low.clone()),
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["c"], &accessor),
+            tab(t),
+            and(and(is_above_low, is_below_high), presence),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_in_list_or_between_of_mismatched_types() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    invalid_query_to_provable_ast(t, "select c from sxt_tab where c in (1, 'x')", &accessor);
+    invalid_query_to_provable_ast(t, "select c from sxt_tab where c in (true)", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select c from sxt_tab where c between 'a' and 'b'",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_case_expression() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select case when c >= 1 then c when c = 0 then 5 else c + 1 end as d from sxt_tab",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(
+                case(
+                    gte(column(t, "c", &accessor), const_bigint(1)),
+                    column(t, "c", &accessor),
+                    case(
+                        equal(column(t, "c", &accessor), const_bigint(0)),
+                        const_bigint(5),
+                        add(column(t, "c", &accessor), const_bigint(1)),
+                    ),
+                ),
+                "d",
+            )],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_case_expression_on_nullable_columns() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    let ast = query_to_provable_ast(
+        t,
+        "select case when a = 1 then c end as d from sxt_tab",
+        &accessor,
+    );
+    // A NULL condition counts as false and the missing ELSE is NULL
+    let condition = and(
+        equal(column(t, "a", &accessor), const_bigint(1)),
+        column(t, "a$presence", &accessor),
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    case(
+                        condition.clone(),
+                        column(t, "c", &accessor),
+                        column(t, "c", &accessor),
+                    ),
+                    "d",
+                ),
+                aliased_plan(
+                    case(condition, const_bool(true), const_bool(false)),
+                    "d$presence",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_a_case_expression_of_mismatched_types() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = nullable_schema_accessor(t);
+    invalid_query_to_provable_ast(
+        t,
+        "select case when c = 1 then c else 'x' end as d from sxt_tab",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select case when c then c else 1 end as d from sxt_tab",
+        &accessor,
+    );
+}
+
+#[test]
+fn query_expr_with_order_and_limits_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table(
+        "select * from table order by int128_column desc limit 1 offset 1",
+    );
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn we_can_serialize_list_of_filters_from_query_expr() {
+    let query_expr = query_expr_for_test_table("select * from table");
+    let filter_execs = vec![query_expr.proof_expr()];
+    let serialized = flexbuffers::to_vec(&filter_execs).unwrap();
+    let deserialized: Vec<DynProofPlan> = flexbuffers::from_slice(serialized.as_slice()).unwrap();
+    let deserialized_as_ref: Vec<&DynProofPlan> = deserialized.iter().collect();
+    assert_eq!(filter_execs.len(), deserialized_as_ref.len());
+    assert_eq!(filter_execs[0], deserialized_as_ref[0]);
+}
+
+///////////////////////////
+// Subqueries
+///////////////////////////
+fn employees_exec(employees: TableRef) -> DynProofPlan {
+    table_exec(
+        employees,
+        vec![
+            column_field("id", ColumnType::BigInt),
+            column_field("name", ColumnType::VarChar),
+            column_field("dept_id", ColumnType::BigInt),
+        ],
+    )
+}
+
+#[test]
+fn we_can_convert_a_query_selecting_from_a_subquery() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select id from (select id, dept_id from employees where dept_id = 10) as s where id >= 2",
+        &accessor,
+    );
+    let s: TableRef = "sxt.s".parse().unwrap();
+    let s_accessor = schema_accessor_from_table_ref_with_schema(
+        s,
+        indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "dept_id".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            filter(
+                cols_expr_plan(employees, &["id", "dept_id"], &accessor),
+                tab(employees),
+                equal(column(employees, "dept_id", &accessor), const_bigint(10)),
+            ),
+            s,
+            filter(
+                cols_expr_plan(s, &["id"], &s_accessor),
+                tab(s),
+                gte(column(s, "id", &s_accessor), const_bigint(2)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_query_with_in_and_not_in_subqueries() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    for (operator, negated) in [("in", false), ("not in", true)] {
+        let ast = query_to_provable_ast(
+            employees,
+            &format!("select name from employees where id >= 2 and dept_id {operator} (select department_id from departments where dept_name = 'Sales')"),
+            &accessor,
+        );
+        let s: TableRef = "sxt.__subquery_0__".parse().unwrap();
+        let s_accessor = schema_accessor_from_table_ref_with_schema(
+            s,
+            indexmap! {
+                "id".into() => ColumnType::BigInt,
+                "name".into() => ColumnType::VarChar,
+                "dept_id".into() => ColumnType::BigInt,
+            },
+        );
+        let expected_ast = QueryExpr::new(
+            subquery_exec(
+                semi_join_exec(
+                    employees_exec(employees),
+                    filter(
+                        cols_expr_plan(departments, &["department_id"], &accessor),
+                        tab(departments),
+                        equal(
+                            column(departments, "dept_name", &accessor),
+                            const_varchar("Sales"),
+                        ),
+                    ),
+                    2,
+                    0,
+                    negated,
+                ),
+                s,
+                filter(
+                    cols_expr_plan(s, &["name"], &s_accessor),
+                    tab(s),
+                    gte(column(s, "id", &s_accessor), const_bigint(2)),
+                ),
+            ),
+            vec![],
+        );
+        assert_eq!(ast, expected_ast);
+    }
+}
+
+#[test]
+fn we_can_convert_a_query_with_a_scalar_subquery() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id = (select max(department_id) as m from departments)",
+        &accessor,
+    );
+    let max_department_id = query_to_provable_ast(
+        departments,
+        "select max(department_id) as m from departments",
+        &accessor,
+    );
+    let s: TableRef = "sxt.__subquery_0__".parse().unwrap();
+    let j: TableRef = "sxt.__subquery_1__".parse().unwrap();
+    // The maximum is NULL if there are no departments, so its presence is joined as well
+    let derived_accessor = TestSchemaAccessor::new(indexmap! {
+        s => indexmap! {
+            "m".into() => ColumnType::BigInt,
+            "m$presence".into() => ColumnType::Boolean,
+        },
+        j => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "dept_id".into() => ColumnType::BigInt,
+            "__subquery_0_key__".into() => ColumnType::BigInt,
+            "__subquery_0__".into() => ColumnType::BigInt,
+            "__subquery_0__$presence".into() => ColumnType::Boolean,
+        },
+    });
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            max_department_id.proof_expr().clone(),
+            s,
+            subquery_exec(
+                join_exec(
+                    projection(
+                        [
+                            cols_expr_plan(employees, &["id", "name", "dept_id"], &accessor),
+                            vec![aliased_plan(const_bigint(0), "__subquery_0_key__")],
+                        ]
+                        .concat(),
+                        tab(employees),
+                    ),
+                    projection(
+                        vec![
+                            aliased_plan(const_bigint(0), "__subquery_0_key__"),
+                            aliased_plan(column(s, "m", &derived_accessor), "__subquery_0__"),
+                            aliased_plan(
+                                column(s, "m$presence", &derived_accessor),
+                                "__subquery_0__$presence",
+                            ),
+                        ],
+                        tab(s),
+                    ),
+                    3,
+                    0,
+                    &[
+                        "id",
+                        "name",
+                        "dept_id",
+                        "__subquery_0_key__",
+                        "__subquery_0__",
+                        "__subquery_0__$presence",
+                    ],
+                ),
+                j,
+                filter(
+                    cols_expr_plan(j, &["name"], &derived_accessor),
+                    tab(j),
+                    and(
+                        equal(
+                            column(j, "dept_id", &derived_accessor),
+                            column(j, "__subquery_0__", &derived_accessor),
+                        ),
+                        column(j, "__subquery_0__$presence", &derived_accessor),
+                    ),
+                ),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_queries_with_unsupported_subqueries() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // IN subqueries have to be conjuncts of the WHERE clause on a column
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where id = 1 or dept_id in (select department_id from departments)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id + 1 in (select department_id from departments)",
+        &accessor,
+    );
+    // IN subqueries have to select a single column of the same type
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id in (select department_id, budget from departments)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id in (select dept_name from departments)",
+        &accessor,
+    );
+    // Scalar subqueries have to be aggregations without GROUP BY
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id = (select department_id from departments)",
+        &accessor,
+    );
+    // Subqueries can't be correlated
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees where dept_id in (select department_id from departments where budget = id)",
+        &accessor,
+    );
+    // Subqueries have to be entirely provable
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from (select id from employees order by id desc) as s",
+        &accessor,
+    );
+}
+
+///////////////////////////
+// Distinct
+///////////////////////////
+#[test]
+fn we_can_convert_a_distinct_query_with_order_by_and_slice() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select distinct dept_id, name from employees where id >= 2 order by name desc limit 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        distinct_exec(filter(
+            cols_expr_plan(employees, &["dept_id", "name"], &accessor),
+            tab(employees),
+            gte(column(employees, "id", &accessor), const_bigint(2)),
+        )),
+        vec![orders(&["name"], &[Desc]), slice(Some(3), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_a_count_distinct_query() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select count(distinct dept_id) as n from employees where id >= 2",
+        &accessor,
+    );
+    let d: TableRef = "sxt.__count_distinct__".parse().unwrap();
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            distinct_exec(filter(
+                vec![aliased_plan(column(employees, "dept_id", &accessor), "n")],
+                tab(employees),
+                gte(column(employees, "id", &accessor), const_bigint(2)),
+            )),
+            d,
+            aggregate(
+                vec![count_expr(const_bigint(1), "n")],
+                tab(d),
+                const_bool(true),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_unsupported_distinct_queries() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // DISTINCT along with aggregations
+    invalid_query_to_provable_ast(
+        employees,
+        "select distinct dept_id, count(*) as n from employees group by dept_id",
+        &accessor,
+    );
+    // COUNT(DISTINCT ...) along with other result expressions or GROUP BY
+    invalid_query_to_provable_ast(
+        employees,
+        "select count(distinct dept_id) as n, count(*) as m from employees",
+        &accessor,
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select dept_id, count(distinct name) as n from employees group by dept_id")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, employees.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { error }) if error.contains("without GROUP BY")
+    ));
+    // COUNT(DISTINCT ...) of values which can not be compared in the proof
+    invalid_query_to_provable_ast(
+        employees,
+        "select count(distinct name) as n from employees",
+        &accessor,
+    );
+    // DISTINCT on several columns in a subquery
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from (select distinct id, name from employees) as s",
+        &accessor,
+    );
+    // Ordering by a column which is not in the result
+    invalid_query_to_provable_ast(
+        employees,
+        "select distinct dept_id from employees order by id",
+        &accessor,
+    );
+}
+
+///////////////////////////
+// Having
+///////////////////////////
+#[test]
+fn we_can_convert_a_group_by_query_with_having() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_id, sum(id) as total from employees where id >= 2 group by dept_id having sum(id) >= 5 and count(*) >= 2",
+        &accessor,
+    );
+    let h: TableRef = "sxt.__having__".parse().unwrap();
+    let h_accessor = schema_accessor_from_table_ref_with_schema(
+        h,
+        indexmap! {
+            "dept_id".into() => ColumnType::BigInt,
+            "__having_0__".into() => ColumnType::BigInt,
+            "__having_count__".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            group_by(
+                cols_expr_plan(employees, &["dept_id"], &accessor),
+                vec![sum_expr(column(employees, "id", &accessor), "__having_0__")],
+                "__having_count__",
+                tab(employees),
+                gte(column(employees, "id", &accessor), const_bigint(2)),
+            ),
+            h,
+            filter(
+                vec![
+                    aliased_plan(column(h, "dept_id", &h_accessor), "dept_id"),
+                    aliased_plan(column(h, "__having_0__", &h_accessor), "total"),
+                ],
+                tab(h),
+                and(
+                    gte(column(h, "__having_0__", &h_accessor), const_bigint(5)),
+                    gte(column(h, "__having_count__", &h_accessor), const_bigint(2)),
+                ),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_aggregation_query_with_having() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select max(id) as m from employees having count(*) >= 2",
+        &accessor,
+    );
+    let h: TableRef = "sxt.__having__".parse().unwrap();
+    let h_accessor = schema_accessor_from_table_ref_with_schema(
+        h,
+        indexmap! {
+            "__having_0__".into() => ColumnType::BigInt,
+            "__having_0__$presence".into() => ColumnType::Boolean,
+            "__having_count__".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            aggregate(
+                vec![
+                    max_expr(column(employees, "id", &accessor), "__having_0__"),
+                    count_expr(const_bigint(1), "__having_count__"),
+                ],
+                tab(employees),
+                const_bool(true),
+            ),
+            h,
+            filter(
+                vec![
+                    aliased_plan(column(h, "__having_0__", &h_accessor), "m"),
+                    aliased_plan(
+                        column(h, "__having_0__$presence", &h_accessor),
+                        "m$presence",
+                    ),
+                ],
+                tab(h),
+                gte(column(h, "__having_count__", &h_accessor), const_bigint(2)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
 
-        let expected_query = QueryExpr::new(
-            filter(perm_col_plans, tab(t), const_bool(true)),
-            vec![
-                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
-                orders(&order_cols_vec, &ordering_vec),
-            ],
-        );
-        assert_eq!(query, expected_query);
-    }
+#[test]
+fn we_can_convert_a_query_with_having_and_an_expression_key() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_id + 1 as d from employees group by dept_id + 1 having dept_id + 1 >= 3",
+        &accessor,
+    );
+    let h: TableRef = "sxt.__having__".parse().unwrap();
+    let h_accessor = schema_accessor_from_table_ref_with_schema(
+        h,
+        indexmap! {
+            "__having_key_0__".into() => ColumnType::BigInt,
+            "__having_count__".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            group_by(
+                vec![aliased_plan(
+                    add(column(employees, "dept_id", &accessor), const_bigint(1)),
+                    "__having_key_0__",
+                )],
+                vec![],
+                "__having_count__",
+                tab(employees),
+                const_bool(true),
+            ),
+            h,
+            filter(
+                vec![aliased_plan(
+                    column(h, "__having_key_0__", &h_accessor),
+                    "d",
+                )],
+                tab(h),
+                gte(column(h, "__having_key_0__", &h_accessor), const_bigint(3)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
-/// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
-fn query_expr_for_test_table(sql_text: &str) -> QueryExpr {
-    let schema_accessor = schema_accessor_from_table_ref_with_schema(
-        "test.table".parse().unwrap(),
+#[test]
+fn we_cannot_convert_unsupported_having_queries() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // HAVING along with GROUP BY on several columns
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select dept_id, name, count(*) as n from employees group by dept_id, name having count(*) >= 2")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, employees.schema_id(), &accessor),
+        Err(ConversionError::Unprovable { error }) if error.contains("GROUP BY on several columns")
+    ));
+    // HAVING along with GROUP BY on a column which can not be compared in the proof
+    invalid_query_to_provable_ast(
+        employees,
+        "select name, count(*) as n from employees group by name having count(*) >= 2",
+        &accessor,
+    );
+    // HAVING on a column which is neither grouped nor aggregated
+    invalid_query_to_provable_ast(
+        employees,
+        "select dept_id, count(*) as n from employees group by dept_id having id >= 2",
+        &accessor,
+    );
+    // HAVING along with COUNT(DISTINCT ...) or DISTINCT
+    invalid_query_to_provable_ast(
+        employees,
+        "select dept_id, count(*) as n from employees group by dept_id having count(distinct id) >= 2",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select distinct dept_id from employees having dept_id >= 2",
+        &accessor,
+    );
+}
+
+///////////////////////////
+// Window Functions
+///////////////////////////
+#[test]
+fn we_can_convert_a_query_with_window_functions() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name, row_number() over (partition by dept_id order by id desc) as rn, sum(id) over (partition by dept_id) as s from employees where id >= 2 order by rn limit 3",
+        &accessor,
+    );
+    let w: TableRef = "sxt.__window__".parse().unwrap();
+    let w_accessor = schema_accessor_from_table_ref_with_schema(
+        w,
         indexmap! {
-            "bigint_column".into() => ColumnType::BigInt,
-            "varchar_column".into() => ColumnType::VarChar,
-            "int128_column".into() => ColumnType::Int128,
+            "name".into() => ColumnType::VarChar,
+            "dept_id".into() => ColumnType::BigInt,
+            "id".into() => ColumnType::BigInt,
+            "__window_1_arg__".into() => ColumnType::BigInt,
+            "__window_0__".into() => ColumnType::BigInt,
+            "__window_1__".into() => ColumnType::BigInt,
         },
     );
-    let default_schema = "test".into();
-    let select_statement = SelectStatementParser::new().parse(sql_text).unwrap();
-    QueryExpr::try_new(select_statement, default_schema, &schema_accessor).unwrap()
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            window_exec(
+                window_exec(
+                    filter(
+                        vec![
+                            col_expr_plan(employees, "name", &accessor),
+                            col_expr_plan(employees, "dept_id", &accessor),
+                            col_expr_plan(employees, "id", &accessor),
+                            aliased_plan(column(employees, "id", &accessor), "__window_1_arg__"),
+                        ],
+                        tab(employees),
+                        gte(column(employees, "id", &accessor), const_bigint(2)),
+                    ),
+                    vec![1],
+                    vec![(2, Desc)],
+                    vec![(WindowFunction::RowNumber, "__window_0__")],
+                ),
+                vec![1],
+                vec![],
+                vec![(WindowFunction::Sum(3), "__window_1__")],
+            ),
+            w,
+            slice_exec(
+                order_by_exec(
+                    filter(
+                        vec![
+                            col_expr_plan(w, "name", &w_accessor),
+                            aliased_plan(column(w, "__window_0__", &w_accessor), "rn"),
+                            aliased_plan(column(w, "__window_1__", &w_accessor), "s"),
+                        ],
+                        tab(w),
+                        const_bool(true),
+                    ),
+                    vec![(1, Asc)],
+                ),
+                0,
+                Some(3),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
-/// Serializes and deserializes [`QueryExpr`] with flexbuffers and asserts that it remains the same.
-fn assert_query_expr_serializes_to_and_from_flex_buffers(query_expr: &QueryExpr) {
-    let serialized = flexbuffers::to_vec(query_expr).unwrap();
-    let deserialized: QueryExpr = flexbuffers::from_slice(serialized.as_slice()).unwrap();
-    assert_eq!(deserialized, *query_expr);
+#[test]
+fn we_can_convert_a_query_with_a_window_function_in_an_expression_over_a_subquery() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select id, rank() over (order by dept_id) + 1 as r, rank() over (order by dept_id) as r0 from (select id, dept_id from employees) as e",
+        &accessor,
+    );
+    let e: TableRef = "sxt.e".parse().unwrap();
+    let e_accessor = schema_accessor_from_table_ref_with_schema(
+        e,
+        indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "dept_id".into() => ColumnType::BigInt,
+        },
+    );
+    let w: TableRef = "sxt.__window__".parse().unwrap();
+    let w_accessor = schema_accessor_from_table_ref_with_schema(
+        w,
+        indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "dept_id".into() => ColumnType::BigInt,
+            "__window_0__".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            filter(
+                cols_expr_plan(employees, &["id", "dept_id"], &accessor),
+                tab(employees),
+                const_bool(true),
+            ),
+            e,
+            subquery_exec(
+                window_exec(
+                    filter(
+                        cols_expr_plan(e, &["id", "dept_id"], &e_accessor),
+                        tab(e),
+                        const_bool(true),
+                    ),
+                    vec![],
+                    vec![(1, Asc)],
+                    vec![(WindowFunction::Rank, "__window_0__")],
+                ),
+                w,
+                filter(
+                    vec![
+                        col_expr_plan(w, "id", &w_accessor),
+                        aliased_plan(
+                            add(column(w, "__window_0__", &w_accessor), const_bigint(1)),
+                            "r",
+                        ),
+                        aliased_plan(column(w, "__window_0__", &w_accessor), "r0"),
+                    ],
+                    tab(w),
+                    const_bool(true),
+                ),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn basic_query_expr_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table("select * from table");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+fn we_cannot_convert_unsupported_queries_with_window_functions() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // Window functions along with aggregations
+    invalid_query_to_provable_ast(
+        employees,
+        "select dept_id, row_number() over (order by dept_id) as rn from employees group by dept_id",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select count(*) as n, rank() over (order by id) as r from employees",
+        &accessor,
+    );
+    // SELECT * along with window functions
+    invalid_query_to_provable_ast(
+        employees,
+        "select *, row_number() over (order by id) as rn from employees",
+        &accessor,
+    );
+    // Window functions outside of the result expressions
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from employees where row_number() over (order by id) <= 2",
+        &accessor,
+    );
+    // Partitioning or ordering by values which can not be compared in the proof
+    invalid_query_to_provable_ast(
+        employees,
+        "select id, row_number() over (partition by name) as rn from employees",
+        &accessor,
+    );
+    // Summing values which are not numeric
+    invalid_query_to_provable_ast(
+        employees,
+        "select id, sum(name) over (order by id) as s from employees",
+        &accessor,
+    );
+    // Window functions in queries with joins
+    invalid_query_to_provable_ast(
+        employees,
+        "select name, row_number() over (order by budget) as rn from employees join departments on employees.dept_id = departments.department_id",
+        &accessor,
+    );
 }
 
+///////////////////////////
+// Set operations
+///////////////////////////
 #[test]
-fn query_expr_with_selected_columns_can_serialize_to_and_from_flex_buffers() {
-    let query_expr =
-        query_expr_for_test_table("select bigint_column, varchar_column, int128_column from table");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+fn we_can_convert_a_chain_of_unions_with_order_by_and_slice() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_id from employees where id >= 2 union all select department_id as d from departments union all select id from employees order by dept_id desc limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        union_exec(
+            vec![
+                filter(
+                    cols_expr_plan(employees, &["dept_id"], &accessor),
+                    tab(employees),
+                    gte(column(employees, "id", &accessor), const_bigint(2)),
+                ),
+                filter(
+                    vec![aliased_plan(
+                        column(departments, "department_id", &accessor),
+                        "d",
+                    )],
+                    tab(departments),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(employees, &["id"], &accessor),
+                    tab(employees),
+                    const_bool(true),
+                ),
+            ],
+            vec![column_field("dept_id", ColumnType::BigInt)],
+        ),
+        vec![orders(&["dept_id"], &[Desc]), slice(Some(2), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn query_expr_with_aggregation_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table("select count(*) from table group by bigint_column");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+fn we_can_convert_a_chain_of_distinct_unions() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select name, dept_id from employees union all select dept_name, department_id from departments union select name, id from employees union select name, dept_id from employees where id = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        distinct_exec(union_exec(
+            vec![
+                filter(
+                    cols_expr_plan(employees, &["name", "dept_id"], &accessor),
+                    tab(employees),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(departments, &["dept_name", "department_id"], &accessor),
+                    tab(departments),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(employees, &["name", "id"], &accessor),
+                    tab(employees),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(employees, &["name", "dept_id"], &accessor),
+                    tab(employees),
+                    equal(column(employees, "id", &accessor), const_bigint(1)),
+                ),
+            ],
+            vec![
+                column_field("name", ColumnType::VarChar),
+                column_field("dept_id", ColumnType::BigInt),
+            ],
+        )),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn query_expr_with_filters_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table(
-        "select * from table where bigint_column != 5 and varchar_column = 'example' or int128_column = 10",
+fn we_can_convert_intersect_and_except() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select dept_id from employees except select department_id from departments intersect select id from employees where id <= 3 order by dept_id",
+        &accessor,
     );
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+    let expected_ast = QueryExpr::new(
+        intersect_exec(
+            filter(
+                cols_expr_plan(employees, &["dept_id"], &accessor),
+                tab(employees),
+                const_bool(true),
+            ),
+            intersect_exec(
+                filter(
+                    cols_expr_plan(departments, &["department_id"], &accessor),
+                    tab(departments),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(employees, &["id"], &accessor),
+                    tab(employees),
+                    lte(column(employees, "id", &accessor), const_bigint(3)),
+                ),
+                false,
+            ),
+            true,
+        ),
+        vec![orders(&["dept_id"], &[Asc])],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn query_expr_with_order_and_limits_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table(
-        "select * from table order by int128_column desc limit 1 offset 1",
+fn we_can_convert_a_set_operation_in_a_subquery() {
+    let (employees, departments, accessor) = employees_and_departments_accessor();
+    let ast = query_to_provable_ast(
+        employees,
+        "select count(*) as n from (select dept_id from employees intersect select department_id from departments) as s",
+        &accessor,
     );
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+    let s: TableRef = "sxt.s".parse().unwrap();
+    let expected_ast = QueryExpr::new(
+        subquery_exec(
+            intersect_exec(
+                filter(
+                    cols_expr_plan(employees, &["dept_id"], &accessor),
+                    tab(employees),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(departments, &["department_id"], &accessor),
+                    tab(departments),
+                    const_bool(true),
+                ),
+                false,
+            ),
+            s,
+            aggregate(
+                vec![count_expr(const_bigint(1), "n")],
+                tab(s),
+                const_bool(true),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_serialize_list_of_filters_from_query_expr() {
-    let query_expr = query_expr_for_test_table("select * from table");
-    let filter_execs = vec![query_expr.proof_expr()];
-    let serialized = flexbuffers::to_vec(&filter_execs).unwrap();
-    let deserialized: Vec<DynProofPlan> = flexbuffers::from_slice(serialized.as_slice()).unwrap();
-    let deserialized_as_ref: Vec<&DynProofPlan> = deserialized.iter().collect();
-    assert_eq!(filter_execs.len(), deserialized_as_ref.len());
-    assert_eq!(filter_execs[0], deserialized_as_ref[0]);
+fn we_cannot_convert_unsupported_set_operations() {
+    let (employees, _, accessor) = employees_and_departments_accessor();
+    // Different numbers of columns
+    invalid_query_to_provable_ast(
+        employees,
+        "select id, dept_id from employees union select department_id from departments",
+        &accessor,
+    );
+    // Different column types
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from employees union all select budget from departments",
+        &accessor,
+    );
+    // INTERSECT ALL and EXCEPT ALL
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from employees intersect all select department_id from departments",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from employees except all select department_id from departments",
+        &accessor,
+    );
+    // INTERSECT and EXCEPT of values which can not be compared in the proof
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees intersect select dept_name from departments",
+        &accessor,
+    );
+    // Operands which require postprocessing
+    invalid_query_to_provable_ast(
+        employees,
+        "select sum(id) + 1 as i from employees union select department_id from departments",
+        &accessor,
+    );
+    // Operands which can only be proven at the top level
+    invalid_query_to_provable_ast(
+        employees,
+        "select distinct name, id from employees union all select dept_name, department_id from departments",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        employees,
+        "select name from employees union select dept_name from departments union all select name from employees",
+        &accessor,
+    );
+    // Ordering by a column which is not in the result
+    invalid_query_to_provable_ast(
+        employees,
+        "select id from employees union select department_id from departments order by department_id",
+        &accessor,
+    );
 }
@@ -0,0 +1,672 @@
+use super::{join_exec_builder::check_join_column_types, ConversionError, ConversionResult};
+use crate::{
+    base::database::{
+        is_character_ident, is_presence_ident, presence_ident, ColumnField, ColumnType,
+        LiteralValue, SchemaAccessor, TableRef,
+    },
+    sql::{
+        parse::QueryExpr,
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{
+            DynProofPlan, JoinExec, ProjectionExec, SemiJoinExec, SubqueryExec, TableExec,
+        },
+    },
+};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::mem;
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, BinaryOperator, Expression, SelectResultExpr, SetExpression,
+        TableExpression, UnaryOperator, WindowFunction,
+    },
+    Identifier, ResourceId, SelectStatement,
+};
+use sqlparser::ast::Ident;
+
+/// Builder that turns the subqueries of a query into derived tables.
+///
+/// Each derived table is the result of a [`DynProofPlan`] which is exposed to the rest of the
+/// query by a [`SubqueryExec`]. The builder rewrites the query so that it only refers to the
+/// derived tables, and acts as the [`SchemaAccessor`] the rewritten query is planned with.
+///
+/// - A subquery in the `FROM` clause becomes a derived table named after its alias.
+/// - A conjunct `col [NOT] IN (<subquery>)` of the `WHERE` clause becomes a derived table which is
+///   the [`SemiJoinExec`] of the table the query selects from with the subquery.
+/// - A scalar subquery, which has to be an aggregation without `GROUP BY`, becomes a derived
+///   table which is the [`JoinExec`] of the table the query selects from with the single row of
+///   the subquery on a constant key. The subquery is replaced by the joined column.
+///
+/// Subqueries are uncorrelated, i.e. they can not refer to the columns of the enclosing query.
+pub struct SubqueryExecBuilder<'a> {
+    schema_accessor: &'a dyn SchemaAccessor,
+    default_schema: Ident,
+    /// The derived tables along with their plans, in the order they are defined in
+    derived_tables: Vec<(TableRef, DynProofPlan)>,
+}
+
+// Public interface
+impl<'a> SubqueryExecBuilder<'a> {
+    pub fn new(schema_accessor: &'a dyn SchemaAccessor, default_schema: Ident) -> Self {
+        Self {
+            schema_accessor,
+            default_schema,
+            derived_tables: Vec::new(),
+        }
+    }
+
+    /// Whether the query contains any subqueries
+    pub fn has_subqueries(ast: &SelectStatement) -> bool {
+        let SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            ..
+        } = &*ast.expr
+        else {
+            return false;
+        };
+        from.iter()
+            .any(|table_expr| table_expr_has_subqueries(table_expr))
+            || result_exprs.iter().any(|result_expr| match result_expr {
+                SelectResultExpr::ALL => false,
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                    has_subqueries(&aliased_expr.expr)
+                }
+            })
+            || where_expr.as_deref().is_some_and(has_subqueries)
+    }
+
+    /// Plan the subqueries of the query and rewrite it in terms of the derived tables.
+    #[allow(clippy::too_many_lines)]
+    pub fn build(&mut self, ast: SelectStatement) -> ConversionResult<SelectStatement> {
+        let SetExpression::Query {
+            mut result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+            distinct,
+        } = *ast.expr
+        else {
+            return Err(ConversionError::Unprovable {
+                error: "subqueries of set operations".to_string(),
+            });
+        };
+        let from = from
+            .into_iter()
+            .map(|table_expr| self.visit_table_expr(*table_expr).map(Box::new))
+            .collect::<ConversionResult<Vec<_>>>()?;
+
+        let mut where_conjuncts = where_expr
+            .map(|where_expr| conjuncts(*where_expr))
+            .unwrap_or_default();
+        let has_where_subqueries = where_conjuncts.iter().any(has_subqueries);
+        let has_result_subqueries = result_exprs.iter().any(|result_expr| {
+            matches!(result_expr, SelectResultExpr::AliasedResultExpr(aliased_expr) if has_subqueries(&aliased_expr.expr))
+        });
+        let from = if has_where_subqueries || has_result_subqueries {
+            let mut table_ref = match from.as_slice() {
+                [table_expr] => match **table_expr {
+                    TableExpression::Named { table, schema } => TableRef::new(ResourceId::new(
+                        schema.unwrap_or(self.default_schema()?),
+                        table,
+                    )),
+                    _ => Err(ConversionError::Unprovable {
+                        error: "subqueries in queries with joins".to_string(),
+                    })?,
+                },
+                _ => Err(ConversionError::Unprovable {
+                    error: "subqueries in queries without a single table".to_string(),
+                })?,
+            };
+            // The derived tables contain the columns added by the joins, which `*` must not expand to
+            result_exprs = self.expand_wildcard(result_exprs, table_ref)?;
+            // First restrict the table to the rows satisfying the IN subqueries
+            let mut remaining_conjuncts = Vec::with_capacity(where_conjuncts.len());
+            for conjunct in where_conjuncts {
+                match conjunct {
+                    Expression::InSubquery { expr, subquery } => {
+                        table_ref = self.semi_join(table_ref, &expr, *subquery, false)?;
+                    }
+                    Expression::Unary {
+                        op: UnaryOperator::Not,
+                        expr,
+                    } if matches!(*expr, Expression::InSubquery { .. }) => {
+                        let Expression::InSubquery { expr, subquery } = *expr else {
+                            unreachable!("The expression is an IN subquery")
+                        };
+                        table_ref = self.semi_join(table_ref, &expr, *subquery, true)?;
+                    }
+                    conjunct => remaining_conjuncts.push(conjunct),
+                }
+            }
+            where_conjuncts = remaining_conjuncts;
+            // Then join the results of the scalar subqueries
+            for expr in where_conjuncts
+                .iter_mut()
+                .chain(
+                    result_exprs
+                        .iter_mut()
+                        .filter_map(|result_expr| match result_expr {
+                            SelectResultExpr::ALL => None,
+                            SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                                Some(aliased_expr.expr.as_mut())
+                            }
+                        }),
+                )
+            {
+                self.replace_scalar_subqueries(expr, &mut table_ref)?;
+            }
+            vec![Box::new(TableExpression::Named {
+                table: identifier(&table_ref.table_id())?,
+                schema: Some(identifier(&table_ref.schema_id())?),
+            })]
+        } else {
+            from
+        };
+
+        let where_expr = where_conjuncts
+            .into_iter()
+            .reduce(|left, right| Expression::Binary {
+                op: BinaryOperator::And,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+            .map(Box::new);
+        Ok(SelectStatement {
+            expr: Box::new(SetExpression::Query {
+                result_exprs,
+                from,
+                where_expr,
+                group_by,
+                having,
+                distinct,
+            }),
+            order_by: ast.order_by,
+            slice: ast.slice,
+        })
+    }
+
+    /// Expose the derived tables to `plan`, which is the plan of the rewritten query.
+    pub fn wrap_plan(self, plan: DynProofPlan) -> DynProofPlan {
+        self.derived_tables
+            .into_iter()
+            .rev()
+            .fold(plan, |plan, (alias, input)| {
+                DynProofPlan::Subquery(SubqueryExec::new(Box::new(input), alias, Box::new(plan)))
+            })
+    }
+}
+
+impl SchemaAccessor for SubqueryExecBuilder<'_> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Ident) -> Option<ColumnType> {
+        match self.derived_table_fields(table_ref) {
+            Some(fields) => fields
+                .into_iter()
+                .find(|field| field.name() == column_id)
+                .map(|field| field.data_type()),
+            None => self.schema_accessor.lookup_column(table_ref, column_id),
+        }
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Ident, ColumnType)> {
+        match self.derived_table_fields(table_ref) {
+            Some(fields) => fields
+                .into_iter()
+                .map(|field| (field.name(), field.data_type()))
+                .collect(),
+            None => self.schema_accessor.lookup_schema(table_ref),
+        }
+    }
+}
+
+// Private interface
+impl SubqueryExecBuilder<'_> {
+    fn default_schema(&self) -> ConversionResult<Identifier> {
+        identifier(&self.default_schema)
+    }
+
+    fn derived_table_fields(&self, table_ref: TableRef) -> Option<Vec<ColumnField>> {
+        self.derived_tables
+            .iter()
+            .rev()
+            .find(|(alias, _)| *alias == table_ref)
+            .map(|(_, plan)| plan.get_column_result_fields())
+    }
+
+    /// Plan a subquery, which has to be entirely provable.
+    fn plan_subquery(&self, ast: SelectStatement) -> ConversionResult<DynProofPlan> {
+        let query = QueryExpr::try_new(ast, self.default_schema.clone(), self)?;
+        if !query.postprocessing().is_empty() {
+            Err(ConversionError::Unprovable {
+                error: "subqueries whose result requires postprocessing".to_string(),
+            })?;
+        }
+        if !is_nestable(query.proof_expr()) {
+            Err(ConversionError::Unprovable {
+                error:
+                    "subqueries with GROUP BY or DISTINCT on other than a single orderable column"
+                        .to_string(),
+            })?;
+        }
+        if query.proof_expr().get_intermediate_result_fields()
+            != query.proof_expr().get_column_result_fields()
+        {
+            Err(ConversionError::Unprovable {
+                error: "subqueries whose result is finalized after verification, such as AVG"
+                    .to_string(),
+            })?;
+        }
+        Ok(query.proof_expr().clone())
+    }
+
+    /// Add a derived table named `alias` which is the result of `plan`.
+    pub fn add_derived_table(
+        &mut self,
+        alias: Identifier,
+        plan: DynProofPlan,
+    ) -> ConversionResult<TableRef> {
+        let table_ref = TableRef::new(ResourceId::new(self.default_schema()?, alias));
+        if self.derived_table_fields(table_ref).is_some() {
+            Err(ConversionError::InvalidExpression {
+                expression: format!("the alias '{alias}' is used for more than one subquery"),
+            })?;
+        }
+        self.derived_tables.push((table_ref, plan));
+        Ok(table_ref)
+    }
+
+    /// A name for a derived table or column which can't clash with the aliases chosen by users
+    fn generated_identifier(&self, suffix: &str) -> ConversionResult<Identifier> {
+        Identifier::try_new(format!(
+            "__subquery_{}{suffix}__",
+            self.derived_tables.len()
+        ))
+        .map_err(|e| ConversionError::IdentifierConversionError {
+            error: e.to_string(),
+        })
+    }
+
+    fn visit_table_expr(
+        &mut self,
+        table_expr: TableExpression,
+    ) -> ConversionResult<TableExpression> {
+        Ok(match table_expr {
+            TableExpression::Named { .. } => table_expr,
+            TableExpression::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+            } => TableExpression::Join {
+                left: Box::new(self.visit_table_expr(*left)?),
+                right: Box::new(self.visit_table_expr(*right)?),
+                left_on,
+                right_on,
+            },
+            TableExpression::Subquery { query, alias } => {
+                let plan = self.plan_subquery(*query)?;
+                self.add_derived_table(alias, plan)?;
+                TableExpression::Named {
+                    table: alias,
+                    schema: None,
+                }
+            }
+        })
+    }
+
+    /// Replace `*` by the visible columns of the table
+    fn expand_wildcard(
+        &self,
+        result_exprs: Vec<SelectResultExpr>,
+        table_ref: TableRef,
+    ) -> ConversionResult<Vec<SelectResultExpr>> {
+        let mut expanded = Vec::with_capacity(result_exprs.len());
+        for result_expr in result_exprs {
+            match result_expr {
+                SelectResultExpr::ALL => {
+                    for (column_id, _) in self.lookup_schema(table_ref) {
+                        if is_presence_ident(&column_id) || is_character_ident(&column_id) {
+                            continue;
+                        }
+                        let column = identifier(&column_id)?;
+                        expanded.push(SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+                            Expression::Column(column),
+                            column,
+                        )));
+                    }
+                }
+                result_expr @ SelectResultExpr::AliasedResultExpr(_) => expanded.push(result_expr),
+            }
+        }
+        Ok(expanded)
+    }
+
+    /// The full schema of a table, i.e. including the presence and character columns
+    fn table_fields(&self, table_ref: TableRef) -> Vec<ColumnField> {
+        self.lookup_schema(table_ref)
+            .into_iter()
+            .map(|(column_id, column_type)| ColumnField::new(column_id, column_type))
+            .collect()
+    }
+
+    /// Restrict `table_ref` to the rows where `expr` is (or, if `negated` is set, is not) in the
+    /// result of `subquery` and return the resulting derived table.
+    fn semi_join(
+        &mut self,
+        table_ref: TableRef,
+        expr: &Expression,
+        subquery: SelectStatement,
+        negated: bool,
+    ) -> ConversionResult<TableRef> {
+        let &Expression::Column(column) = expr else {
+            Err(ConversionError::Unprovable {
+                error: "IN subqueries on expressions other than columns".to_string(),
+            })?
+        };
+        let column_id: Ident = column.into();
+        let fields = self.table_fields(table_ref);
+        let left_index = fields
+            .iter()
+            .position(|field| field.name() == column_id)
+            .ok_or_else(|| ConversionError::MissingColumnWithoutTable {
+                identifier: Box::new(column_id.clone()),
+            })?;
+        if fields
+            .iter()
+            .any(|field| field.name() == presence_ident(&column_id))
+        {
+            Err(ConversionError::Unprovable {
+                error: format!("IN subqueries on the nullable column {column_id}"),
+            })?;
+        }
+        let right = self.plan_subquery(subquery)?;
+        let right_fields = right.get_column_result_fields();
+        let [right_field] = right_fields.as_slice() else {
+            Err(ConversionError::Unprovable {
+                error: "IN subqueries which do not select exactly one non-nullable column"
+                    .to_string(),
+            })?
+        };
+        check_join_column_types(fields[left_index].data_type(), right_field.data_type())?;
+        let plan = DynProofPlan::SemiJoin(SemiJoinExec::new(
+            Box::new(DynProofPlan::Table(TableExec::new(table_ref, fields))),
+            Box::new(right),
+            left_index,
+            0,
+            negated,
+        ));
+        let alias = self.generated_identifier("")?;
+        self.add_derived_table(alias, plan)
+    }
+
+    /// Replace the scalar subqueries in `expr` by columns of a derived table joining `table_ref`
+    /// with their results, and replace `table_ref` by that derived table.
+    fn replace_scalar_subqueries(
+        &mut self,
+        expr: &mut Expression,
+        table_ref: &mut TableRef,
+    ) -> ConversionResult<()> {
+        match expr {
+            Expression::Subquery(subquery) => {
+                let subquery = mem::replace(subquery.as_mut(), empty_select_statement());
+                let (joined_table_ref, column) = self.scalar_join(*table_ref, subquery)?;
+                *table_ref = joined_table_ref;
+                *expr = Expression::Column(column);
+            }
+            Expression::InSubquery { .. } => Err(ConversionError::Unprovable {
+                error: "IN subqueries which are not conjuncts of the WHERE clause".to_string(),
+            })?,
+            Expression::Unary { expr, .. }
+            | Expression::Like { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::DateTrunc { expr, .. }
+            | Expression::Extract { expr, .. }
+            | Expression::Aggregation { expr, .. }
+            | Expression::Window {
+                function: WindowFunction::Sum(expr),
+                ..
+            } => {
+                self.replace_scalar_subqueries(expr, table_ref)?;
+            }
+            Expression::Binary { left, right, .. } => {
+                self.replace_scalar_subqueries(left, table_ref)?;
+                self.replace_scalar_subqueries(right, table_ref)?;
+            }
+            Expression::InList { expr, list } => {
+                self.replace_scalar_subqueries(expr, table_ref)?;
+                for item in list {
+                    self.replace_scalar_subqueries(item, table_ref)?;
+                }
+            }
+            Expression::Between { expr, low, high } => {
+                self.replace_scalar_subqueries(expr, table_ref)?;
+                self.replace_scalar_subqueries(low, table_ref)?;
+                self.replace_scalar_subqueries(high, table_ref)?;
+            }
+            Expression::Case {
+                conditions,
+                else_result,
+            } => {
+                for (condition, result) in conditions {
+                    self.replace_scalar_subqueries(condition, table_ref)?;
+                    self.replace_scalar_subqueries(result, table_ref)?;
+                }
+                if let Some(else_result) = else_result {
+                    self.replace_scalar_subqueries(else_result, table_ref)?;
+                }
+            }
+            Expression::Column(_)
+            | Expression::Literal(_)
+            | Expression::Wildcard
+            | Expression::Window { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Join `table_ref` with the single row of `subquery` and return the resulting derived table
+    /// along with the column holding the result of the subquery.
+    ///
+    /// Since [`JoinExec`] requires a join column, both sides get a constant key column.
+    /// If the result of the subquery can be NULL, like `MAX` over no rows, its presence is joined
+    /// as well.
+    fn scalar_join(
+        &mut self,
+        table_ref: TableRef,
+        subquery: SelectStatement,
+    ) -> ConversionResult<(TableRef, Identifier)> {
+        let subquery_plan = self.plan_subquery(subquery)?;
+        let subquery_fields = subquery_plan.get_column_result_fields();
+        let (Some(subquery_field), true) = (
+            single_nullable_field(&subquery_fields),
+            has_one_row(&subquery_plan),
+        ) else {
+            Err(ConversionError::Unprovable {
+                error: "scalar subqueries other than a single aggregation without GROUP BY"
+                    .to_string(),
+            })?
+        };
+        let subquery_field = subquery_field.clone();
+        let subquery_presence_field = subquery_fields.get(1).cloned();
+        let subquery_alias = self.generated_identifier("")?;
+        let key: Ident = self.generated_identifier("_key")?.into();
+        let subquery_table_ref = self.add_derived_table(subquery_alias, subquery_plan)?;
+        let column = subquery_alias;
+
+        let fields = self.table_fields(table_ref);
+        let constant_key = || AliasedDynProofExpr {
+            expr: DynProofExpr::new_literal(LiteralValue::BigInt(0)),
+            alias: key.clone(),
+        };
+        let left = DynProofPlan::Projection(ProjectionExec::new(
+            fields
+                .iter()
+                .map(|field| column_expr(table_ref, field))
+                .chain([constant_key()])
+                .collect(),
+            TableExpr { table_ref },
+        ));
+        let right = DynProofPlan::Projection(ProjectionExec::new(
+            [
+                constant_key(),
+                AliasedDynProofExpr {
+                    expr: column_expr(subquery_table_ref, &subquery_field).expr,
+                    alias: column.into(),
+                },
+            ]
+            .into_iter()
+            .chain(
+                subquery_presence_field
+                    .iter()
+                    .map(|presence_field| AliasedDynProofExpr {
+                        expr: column_expr(subquery_table_ref, presence_field).expr,
+                        alias: presence_ident(&column.into()),
+                    }),
+            )
+            .collect(),
+            TableExpr {
+                table_ref: subquery_table_ref,
+            },
+        ));
+        let plan = DynProofPlan::Join(JoinExec::new(
+            Box::new(left),
+            Box::new(right),
+            fields.len(),
+            0,
+            fields
+                .iter()
+                .map(ColumnField::name)
+                .chain([key.clone(), column.into()])
+                .chain(
+                    subquery_presence_field
+                        .is_some()
+                        .then(|| presence_ident(&column.into())),
+                )
+                .collect(),
+        ));
+        let alias = self.generated_identifier("")?;
+        Ok((self.add_derived_table(alias, plan)?, column))
+    }
+}
+
+/// Whether the plan can be the input of other plans
+pub(super) fn is_nestable(plan: &DynProofPlan) -> bool {
+    match plan {
+        DynProofPlan::GroupBy(group_by_exec) => group_by_exec.is_nestable(),
+        DynProofPlan::Distinct(distinct_exec) => distinct_exec.is_nestable(),
+        DynProofPlan::Subquery(subquery_exec) => is_nestable(subquery_exec.plan()),
+        _ => true,
+    }
+}
+
+/// The only column of `fields`, which may be followed by its presence
+fn single_nullable_field(fields: &[ColumnField]) -> Option<&ColumnField> {
+    match fields {
+        [field] => Some(field),
+        [field, presence_field] if presence_field.name() == presence_ident(&field.name()) => {
+            Some(field)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the result of the plan is known to have exactly one row
+fn has_one_row(plan: &DynProofPlan) -> bool {
+    match plan {
+        DynProofPlan::Aggregate(_) => true,
+        DynProofPlan::Subquery(subquery_exec) => has_one_row(subquery_exec.plan()),
+        _ => false,
+    }
+}
+
+fn column_expr(table_ref: TableRef, field: &ColumnField) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_column(crate::base::database::ColumnRef::new(
+            table_ref,
+            field.name(),
+            field.data_type(),
+        )),
+        alias: field.name(),
+    }
+}
+
+fn identifier(ident: &Ident) -> ConversionResult<Identifier> {
+    Identifier::try_from(ident.clone()).map_err(|e| ConversionError::IdentifierConversionError {
+        error: e.to_string(),
+    })
+}
+
+/// Placeholder for a subquery which has been moved out of the expression
+fn empty_select_statement() -> SelectStatement {
+    SelectStatement {
+        expr: Box::new(SetExpression::Query {
+            result_exprs: Vec::new(),
+            from: Vec::new(),
+            where_expr: None,
+            group_by: Vec::new(),
+            having: None,
+            distinct: false,
+        }),
+        order_by: Vec::new(),
+        slice: None,
+    }
+}
+
+/// Split an expression into the conjuncts of the top level `AND`s
+fn conjuncts(expr: Expression) -> Vec<Expression> {
+    match expr {
+        Expression::Binary {
+            op: BinaryOperator::And,
+            left,
+            right,
+        } => [conjuncts(*left), conjuncts(*right)].concat(),
+        expr => vec![expr],
+    }
+}
+
+fn table_expr_has_subqueries(table_expr: &TableExpression) -> bool {
+    match table_expr {
+        TableExpression::Named { .. } => false,
+        TableExpression::Join { left, right, .. } => {
+            table_expr_has_subqueries(left) || table_expr_has_subqueries(right)
+        }
+        TableExpression::Subquery { .. } => true,
+    }
+}
+
+fn has_subqueries(expr: &Expression) -> bool {
+    match expr {
+        Expression::InSubquery { .. } | Expression::Subquery(_) => true,
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::Window {
+            function: WindowFunction::Sum(expr),
+            ..
+        } => has_subqueries(expr),
+        Expression::Binary { left, right, .. } => has_subqueries(left) || has_subqueries(right),
+        Expression::InList { expr, list } => {
+            has_subqueries(expr) || list.iter().any(has_subqueries)
+        }
+        Expression::Between { expr, low, high } => {
+            has_subqueries(expr) || has_subqueries(low) || has_subqueries(high)
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            conditions
+                .iter()
+                .any(|(condition, result)| has_subqueries(condition) || has_subqueries(result))
+                || else_result.as_deref().is_some_and(has_subqueries)
+        }
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Window { .. } => false,
+    }
+}
@@ -30,10 +30,14 @@ impl<'a> WhereExprBuilder<'a> {
     ) -> Result<Option<DynProofExpr>, ConversionError> {
         where_expr
             .map(|where_expr| {
-                let expr_plan = self.builder.build(&where_expr)?;
+                let (expr_plan, presence) = self.builder.build_nullable(&where_expr)?;
                 // Ensure that the expression is a boolean expression
                 match expr_plan.data_type() {
-                    ColumnType::Boolean => Ok(expr_plan),
+                    // Rows where the where clause is NULL are not selected
+                    ColumnType::Boolean => match presence {
+                        Some(presence) => DynProofExpr::try_new_and(expr_plan, presence),
+                        None => Ok(expr_plan),
+                    },
                     _ => Err(ConversionError::NonbooleanWhereClause {
                         datatype: expr_plan.data_type(),
                     }),
@@ -151,6 +151,7 @@ fn we_can_directly_check_whether_bigint_columns_ge_int128() {
         ))),
         DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Int128(-12345))),
         false,
+        false,
     )
     .unwrap();
     assert_eq!(actual, expected);
@@ -173,6 +174,53 @@ fn we_can_directly_check_whether_bigint_columns_le_int128() {
         ))),
         DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Int128(-12345))),
         true,
+        false,
+    )
+    .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn we_can_directly_check_whether_bigint_columns_lt_int128() {
+    let column_mapping = get_column_mappings_for_testing();
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let expr_integer_to_integer = lt(col("bigint_column"), lit(-12345_i128));
+    let actual = builder
+        .build(Some(expr_integer_to_integer))
+        .unwrap()
+        .unwrap();
+    let expected = DynProofExpr::try_new_inequality(
+        DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+            "sxt.sxt_tab".parse().unwrap(),
+            "bigint_column".into(),
+            ColumnType::BigInt,
+        ))),
+        DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Int128(-12345))),
+        true,
+        true,
+    )
+    .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn we_can_directly_check_whether_bigint_columns_gt_int128() {
+    let column_mapping = get_column_mappings_for_testing();
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let expr_integer_to_integer = gt(col("bigint_column"), lit(-12345_i128));
+    let actual = builder
+        .build(Some(expr_integer_to_integer))
+        .unwrap()
+        .unwrap();
+    let expected = DynProofExpr::try_new_inequality(
+        DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+            "sxt.sxt_tab".parse().unwrap(),
+            "bigint_column".into(),
+            ColumnType::BigInt,
+        ))),
+        DynProofExpr::Literal(LiteralExpr::new(LiteralValue::Int128(-12345))),
+        false,
+        true,
     )
     .unwrap();
     assert_eq!(actual, expected);
@@ -258,6 +306,47 @@ fn we_can_check_varying_precision_eq_for_timestamp() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn we_can_compare_timestamp_columns_of_varying_precision() {
+    let column_mapping = get_column_mappings_for_testing();
+    for expr in [
+        lt(
+            col("timestamp_second_column"),
+            col("timestamp_nanosecond_column"),
+        ),
+        gt(
+            col("timestamp_millisecond_column"),
+            col("timestamp_microsecond_column"),
+        ),
+        not_equal(
+            col("timestamp_second_column"),
+            col("timestamp_microsecond_column"),
+        ),
+    ] {
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let result = builder.build(Some(expr));
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+fn we_can_check_whether_varchar_columns_are_not_equal() {
+    let column_mapping = get_column_mappings_for_testing();
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let expr = not_equal(col("varchar_column"), col("varchar_column"));
+    let actual = builder.build(Some(expr)).unwrap().unwrap();
+    let varchar_column = DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        "sxt.sxt_tab".parse().unwrap(),
+        "varchar_column".into(),
+        ColumnType::VarChar,
+    )));
+    let expected = DynProofExpr::try_new_not(
+        DynProofExpr::try_new_equals(varchar_column.clone(), varchar_column).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(actual, expected);
+}
+
 #[test]
 fn we_can_not_have_missing_column_as_where_clause() {
     let column_mapping = get_column_mappings_for_testing();
@@ -0,0 +1,384 @@
+use super::{query_expr::contains_aggregation, ConversionError, ConversionResult, QueryExpr};
+use crate::{
+    base::{
+        database::{is_presence_ident, ColumnField, SchemaAccessor},
+        map::IndexSet,
+    },
+    sql::{
+        proof::ProofPlan,
+        proof_plans::{self, DynProofPlan, OrderByExec, WindowExec},
+    },
+};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use core::fmt;
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, Expression, OrderBy, SelectResultExpr, SetExpression, WindowFunction,
+    },
+    Identifier, SelectStatement,
+};
+use sqlparser::ast::Ident;
+
+/// Builder that plans the window functions of a query as [`WindowExec`]s.
+///
+/// The query
+/// ```ignore
+///     SELECT <result_exprs> FROM <from> WHERE <where>
+/// ```
+/// is planned as the query
+/// ```ignore
+///     SELECT <referenced columns>, <summed expressions> FROM <from> WHERE <where>
+/// ```
+/// followed by a [`WindowExec`] for each distinct `OVER (PARTITION BY ... ORDER BY ...)` clause.
+/// Each window function in the result expressions is replaced by the column of its result, so
+/// that the result expressions can be evaluated over the output of the plan.
+///
+/// Window functions can not be combined with aggregations.
+pub struct WindowExecBuilder<'a> {
+    schema_accessor: &'a dyn SchemaAccessor,
+    default_schema: Ident,
+}
+
+/// The result of [`WindowExecBuilder::build`]
+pub struct WindowExecBuilderResult {
+    /// The provable part of the query
+    pub plan: DynProofPlan,
+    /// The result expressions in terms of the output columns of `plan`
+    pub result_exprs: Vec<AliasedResultExpr>,
+}
+
+/// A window function of the query along with the index of the column of its result
+struct WindowExpr {
+    function: WindowFunction,
+    partition_by: Vec<Identifier>,
+    order_by: Vec<OrderBy>,
+    index: usize,
+}
+
+impl WindowExpr {
+    /// The name of the column of the result
+    fn alias(&self) -> Identifier {
+        window_identifier(self.index)
+    }
+
+    /// The name of the column of the summed expression
+    fn argument_alias(&self) -> Identifier {
+        window_identifier(format_args!("{}_arg", self.index))
+    }
+}
+
+impl<'a> WindowExecBuilder<'a> {
+    pub fn new(schema_accessor: &'a dyn SchemaAccessor, default_schema: Ident) -> Self {
+        Self {
+            schema_accessor,
+            default_schema,
+        }
+    }
+
+    /// Whether the result expressions of the query contain any window functions
+    pub fn has_window_functions(ast: &SelectStatement) -> bool {
+        let SetExpression::Query { result_exprs, .. } = &*ast.expr else {
+            return false;
+        };
+        result_exprs.iter().any(|result_expr| {
+            matches!(result_expr, SelectResultExpr::AliasedResultExpr(aliased_expr) if has_window_functions(&aliased_expr.expr))
+        })
+    }
+
+    /// Build the plan computing the window functions of the query along with its result
+    /// expressions rewritten in terms of the output columns of the plan.
+    pub fn build(self, query: SetExpression) -> ConversionResult<WindowExecBuilderResult> {
+        let SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+            distinct,
+        } = query
+        else {
+            return Err(ConversionError::Unprovable {
+                error: "window functions in set operations".to_string(),
+            });
+        };
+        if !group_by.is_empty()
+            || having.is_some()
+            || distinct
+            || result_exprs.iter().any(|result_expr| {
+                matches!(result_expr, SelectResultExpr::AliasedResultExpr(aliased_expr) if contains_aggregation(&aliased_expr.expr))
+            })
+        {
+            return Err(ConversionError::Unprovable {
+                error: "window functions along with aggregations".to_string(),
+            });
+        }
+        let mut result_exprs = result_exprs
+            .into_iter()
+            .map(|result_expr| match result_expr {
+                SelectResultExpr::ALL => Err(ConversionError::Unprovable {
+                    error: "SELECT * along with window functions".to_string(),
+                }),
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => Ok(aliased_expr),
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+
+        let mut windows = Vec::new();
+        let mut columns = IndexSet::default();
+        for aliased_expr in &mut result_exprs {
+            replace_window_functions(&mut aliased_expr.expr, &mut windows, &mut columns)?;
+        }
+        for window in &windows {
+            columns.extend(window.partition_by.iter().copied());
+            columns.extend(window.order_by.iter().map(|by_expr| by_expr.expr));
+        }
+
+        // The query computing the referenced columns and the summed expressions
+        let input_exprs = columns
+            .iter()
+            .map(|&column| AliasedResultExpr::new(Expression::Column(column), column))
+            .chain(windows.iter().filter_map(|window| match &window.function {
+                WindowFunction::Sum(expr) => Some(AliasedResultExpr {
+                    expr: expr.clone(),
+                    alias: window.argument_alias(),
+                }),
+                WindowFunction::RowNumber | WindowFunction::Rank => None,
+            }))
+            .map(SelectResultExpr::AliasedResultExpr)
+            .collect();
+        let input = QueryExpr::try_new(
+            SelectStatement {
+                expr: Box::new(SetExpression::Query {
+                    result_exprs: input_exprs,
+                    from,
+                    where_expr,
+                    group_by: Vec::new(),
+                    having: None,
+                    distinct: false,
+                }),
+                order_by: Vec::new(),
+                slice: None,
+            },
+            self.default_schema,
+            self.schema_accessor,
+        )?;
+        if !input.postprocessing().is_empty() {
+            return Err(ConversionError::Unprovable {
+                error: "window functions over results which require postprocessing".to_string(),
+            });
+        }
+        let mut plan = input.proof_expr().clone();
+        let fields = plan.get_column_result_fields();
+        if fields.iter().any(|field| is_presence_ident(&field.name())) {
+            return Err(ConversionError::Unprovable {
+                error: "window functions over nullable columns".to_string(),
+            });
+        }
+
+        // A WindowExec for each distinct window, in the order they first appear
+        let mut remaining = windows;
+        while let Some(first) = remaining.first() {
+            let partition_by = first.partition_by.clone();
+            let order_by = first.order_by.clone();
+            let (current, rest): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|window| {
+                window.partition_by == partition_by && window.order_by == order_by
+            });
+            remaining = rest;
+            plan = window_plan(plan, &fields, &partition_by, &order_by, current)?;
+        }
+
+        Ok(WindowExecBuilderResult { plan, result_exprs })
+    }
+}
+
+/// Plan the window functions sharing a window as a [`WindowExec`] over `input`.
+///
+/// `fields` are the columns the query computes before any window function, which remain the
+/// first columns of every [`WindowExec`].
+fn window_plan(
+    input: DynProofPlan,
+    fields: &[ColumnField],
+    partition_by: &[Identifier],
+    order_by: &[OrderBy],
+    windows: Vec<WindowExpr>,
+) -> ConversionResult<DynProofPlan> {
+    let key_index = |column: Identifier| {
+        let index = column_index(fields, column);
+        let data_type = fields[index].data_type();
+        if OrderByExec::is_supported_column_type(data_type) {
+            Ok(index)
+        } else {
+            Err(ConversionError::Unprovable {
+                error: format!(
+                    "window functions partitioned or ordered by {column} of type {data_type}"
+                ),
+            })
+        }
+    };
+    let partition_by = partition_by
+        .iter()
+        .map(|&column| key_index(column))
+        .collect::<ConversionResult<Vec<_>>>()?;
+    let order_by = order_by
+        .iter()
+        .map(|by_expr| Ok((key_index(by_expr.expr)?, by_expr.direction)))
+        .collect::<ConversionResult<Vec<_>>>()?;
+    let functions = windows
+        .into_iter()
+        .map(|window| {
+            let function = match window.function {
+                WindowFunction::RowNumber => proof_plans::WindowFunction::RowNumber,
+                WindowFunction::Rank => proof_plans::WindowFunction::Rank,
+                WindowFunction::Sum(_) => {
+                    let index = column_index(fields, window.argument_alias());
+                    let data_type = fields[index].data_type();
+                    if !data_type.is_numeric() {
+                        return Err(ConversionError::InvalidExpression {
+                            expression: format!("SUM of {data_type} over a window"),
+                        });
+                    }
+                    proof_plans::WindowFunction::Sum(index)
+                }
+            };
+            Ok((function, window.alias().into()))
+        })
+        .collect::<ConversionResult<Vec<_>>>()?;
+    Ok(DynProofPlan::Window(WindowExec::new(
+        Box::new(input),
+        partition_by,
+        order_by,
+        functions,
+    )))
+}
+
+/// The index of the column named `column`, which is selected by the input of the windows
+///
+/// # Panics
+/// Panics if there is no such column, which can not happen.
+fn column_index(fields: &[ColumnField], column: Identifier) -> usize {
+    let name: Ident = column.into();
+    fields
+        .iter()
+        .position(|field| field.name() == name)
+        .expect("The column is selected by the input of the windows")
+}
+
+/// A name for a column computed by the window functions
+///
+/// # Panics
+/// Panics if the name is not a valid identifier, which it is.
+fn window_identifier(suffix: impl fmt::Display) -> Identifier {
+    Identifier::try_new(format!("__window_{suffix}__")).expect("The name is a valid identifier")
+}
+
+/// Replace the window functions in `expr` by the columns of their results, adding them to
+/// `windows` unless they are already there, and collect the columns `expr` otherwise refers to
+fn replace_window_functions(
+    expr: &mut Expression,
+    windows: &mut Vec<WindowExpr>,
+    columns: &mut IndexSet<Identifier>,
+) -> ConversionResult<()> {
+    match expr {
+        Expression::Window {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            let index = windows
+                .iter()
+                .position(|window| {
+                    window.function == *function
+                        && window.partition_by == *partition_by
+                        && window.order_by == *order_by
+                })
+                .unwrap_or_else(|| {
+                    windows.push(WindowExpr {
+                        function: function.clone(),
+                        partition_by: partition_by.clone(),
+                        order_by: order_by.clone(),
+                        index: windows.len(),
+                    });
+                    windows.len() - 1
+                });
+            *expr = Expression::Column(windows[index].alias());
+        }
+        Expression::Column(identifier) => {
+            columns.insert(*identifier);
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. } => {
+            replace_window_functions(expr, windows, columns)?;
+        }
+        Expression::Binary { left, right, .. } => {
+            replace_window_functions(left, windows, columns)?;
+            replace_window_functions(right, windows, columns)?;
+        }
+        Expression::InList { expr, list } => {
+            replace_window_functions(expr, windows, columns)?;
+            for item in list {
+                replace_window_functions(item, windows, columns)?;
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            replace_window_functions(expr, windows, columns)?;
+            replace_window_functions(low, windows, columns)?;
+            replace_window_functions(high, windows, columns)?;
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            for (condition, result) in conditions {
+                replace_window_functions(condition, windows, columns)?;
+                replace_window_functions(result, windows, columns)?;
+            }
+            if let Some(else_result) = else_result {
+                replace_window_functions(else_result, windows, columns)?;
+            }
+        }
+        Expression::Literal(_) | Expression::Wildcard => {}
+        Expression::InSubquery { .. } | Expression::Subquery(_) => {
+            Err(ConversionError::Unprovable {
+                error: "subqueries along with window functions".to_string(),
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn has_window_functions(expr: &Expression) -> bool {
+    match expr {
+        Expression::Window { .. } => true,
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::InSubquery { expr, .. } => has_window_functions(expr),
+        Expression::Binary { left, right, .. } => {
+            has_window_functions(left) || has_window_functions(right)
+        }
+        Expression::InList { expr, list } => {
+            has_window_functions(expr) || list.iter().any(has_window_functions)
+        }
+        Expression::Between { expr, low, high } => {
+            has_window_functions(expr) || has_window_functions(low) || has_window_functions(high)
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            conditions.iter().any(|(condition, result)| {
+                has_window_functions(condition) || has_window_functions(result)
+            }) || else_result.as_deref().is_some_and(has_window_functions)
+        }
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_) => false,
+    }
+}
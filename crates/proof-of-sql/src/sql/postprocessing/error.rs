@@ -53,6 +53,14 @@ pub enum PostprocessingError {
         /// The underlying source error
         source: crate::base::database::OwnedColumnError,
     },
+    /// Averages are only supported when they are proven
+    #[snafu(display("AVG is only supported in aggregations that are proven"))]
+    UnprovenAverage,
+    /// Percentiles are only supported when they are proven
+    #[snafu(display(
+        "MEDIAN, PERCENTILE_CONT and PERCENTILE_DISC are only supported in aggregations that are proven"
+    ))]
+    UnprovenOrderStatistic,
     /// Nested aggregation in `GROUP BY` clause
     #[snafu(display("Nested aggregation in `GROUP BY` clause: {error}"))]
     NestedAggregationInGroupByClause {
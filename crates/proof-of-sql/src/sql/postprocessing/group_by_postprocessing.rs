@@ -34,12 +34,43 @@ pub struct GroupByPostprocessing {
 /// Otherwise we need two layers of aggregation functions to be nested.
 fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => false,
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_)
+        | Expression::Window { .. } => false,
         Expression::Aggregation { expr, .. } => is_agg || contains_nested_aggregation(expr, true),
         Expression::Binary { left, right, .. } => {
             contains_nested_aggregation(left, is_agg) || contains_nested_aggregation(right, is_agg)
         }
-        Expression::Unary { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::InSubquery { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::InList { expr, list } => {
+            contains_nested_aggregation(expr, is_agg)
+                || list
+                    .iter()
+                    .any(|item| contains_nested_aggregation(item, is_agg))
+        }
+        Expression::Between { expr, low, high } => {
+            contains_nested_aggregation(expr, is_agg)
+                || contains_nested_aggregation(low, is_agg)
+                || contains_nested_aggregation(high, is_agg)
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            conditions.iter().any(|(condition, result)| {
+                contains_nested_aggregation(condition, is_agg)
+                    || contains_nested_aggregation(result, is_agg)
+            }) || else_result
+                .as_deref()
+                .is_some_and(|else_result| contains_nested_aggregation(else_result, is_agg))
+        }
     }
 }
 
@@ -47,16 +78,50 @@ fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
 fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
     match expr {
         Expression::Column(identifier) => IndexSet::from_iter([(*identifier).into()]),
-        Expression::Literal(_) | Expression::Aggregation { .. } | Expression::Wildcard => {
-            IndexSet::default()
-        }
+        Expression::Literal(_)
+        | Expression::Aggregation { .. }
+        | Expression::Wildcard
+        | Expression::Subquery(_)
+        | Expression::Window { .. } => IndexSet::default(),
         Expression::Binary { left, right, .. } => {
             let mut left_identifiers = get_free_identifiers_from_expr(left);
             let right_identifiers = get_free_identifiers_from_expr(right);
             left_identifiers.extend(right_identifiers);
             left_identifiers
         }
-        Expression::Unary { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Unary { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::InSubquery { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::InList { expr, list } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            for item in list {
+                identifiers.extend(get_free_identifiers_from_expr(item));
+            }
+            identifiers
+        }
+        Expression::Between { expr, low, high } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            identifiers.extend(get_free_identifiers_from_expr(low));
+            identifiers.extend(get_free_identifiers_from_expr(high));
+            identifiers
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            let mut identifiers = IndexSet::default();
+            for (condition, result) in conditions {
+                identifiers.extend(get_free_identifiers_from_expr(condition));
+                identifiers.extend(get_free_identifiers_from_expr(result));
+            }
+            if let Some(else_result) = else_result {
+                identifiers.extend(get_free_identifiers_from_expr(else_result));
+            }
+            identifiers
+        }
     }
 }
 
@@ -69,12 +134,19 @@ fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
 ///
 /// Will panic if the key for an aggregation expression cannot be parsed as a valid identifier
 /// or if there are issues retrieving an identifier from the map.
+#[allow(clippy::too_many_lines)]
 fn get_aggregate_and_remainder_expressions(
     expr: Expression,
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Ident>,
 ) -> Result<Expression, PostprocessingError> {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => Ok(expr),
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        // Subqueries and window functions are planned before postprocessing
+        | Expression::Subquery(_)
+        | Expression::InSubquery { .. }
+        | Expression::Window { .. } => Ok(expr),
         Expression::Aggregation { op, expr } => {
             let key = (op, (*expr));
             if let Some(ident) = aggregation_expr_map.get(&key) {
@@ -118,6 +190,80 @@ fn get_aggregate_and_remainder_expressions(
                 expr: Box::new(remainder?),
             })
         }
+        Expression::Like { expr, pattern } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            Ok(Expression::Like {
+                expr: Box::new(remainder?),
+                pattern,
+            })
+        }
+        Expression::Cast { expr, data_type } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            Ok(Expression::Cast {
+                expr: Box::new(remainder?),
+                data_type,
+            })
+        }
+        Expression::DateTrunc { field, expr } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            Ok(Expression::DateTrunc {
+                field,
+                expr: Box::new(remainder?),
+            })
+        }
+        Expression::Extract { field, expr } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            Ok(Expression::Extract {
+                field,
+                expr: Box::new(remainder?),
+            })
+        }
+        Expression::InList { expr, list } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            let list_remainder = list
+                .into_iter()
+                .map(|item| get_aggregate_and_remainder_expressions(item, aggregation_expr_map))
+                .collect::<Result<_, _>>();
+            Ok(Expression::InList {
+                expr: Box::new(remainder?),
+                list: list_remainder?,
+            })
+        }
+        Expression::Between { expr, low, high } => {
+            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            let low_remainder = get_aggregate_and_remainder_expressions(*low, aggregation_expr_map);
+            let high_remainder =
+                get_aggregate_and_remainder_expressions(*high, aggregation_expr_map);
+            Ok(Expression::Between {
+                expr: Box::new(remainder?),
+                low: Box::new(low_remainder?),
+                high: Box::new(high_remainder?),
+            })
+        }
+        Expression::Case {
+            conditions,
+            else_result,
+        } => {
+            let conditions_remainder = conditions
+                .into_iter()
+                .map(|(condition, result)| {
+                    Ok((
+                        get_aggregate_and_remainder_expressions(condition, aggregation_expr_map)?,
+                        get_aggregate_and_remainder_expressions(result, aggregation_expr_map)?,
+                    ))
+                })
+                .collect::<Result<_, PostprocessingError>>();
+            let else_remainder = else_result
+                .map(|else_result| {
+                    get_aggregate_and_remainder_expressions(*else_result, aggregation_expr_map)
+                        .map(Box::new)
+                })
+                .transpose();
+            Ok(Expression::Case {
+                conditions: conditions_remainder?,
+                else_result: else_remainder?,
+            })
+        }
     }
 }
 
@@ -178,6 +324,24 @@ impl GroupByPostprocessing {
                 )
             })
             .collect::<PostprocessingResult<Vec<AliasedResultExpr>>>()?;
+        // Averages are finalized from proven sums and counts, so they are never postprocessed
+        if aggregation_expr_map
+            .keys()
+            .any(|(op, _)| *op == AggregationOperator::Avg)
+        {
+            return Err(PostprocessingError::UnprovenAverage);
+        }
+        // Percentiles are proven as order statistics, which are not postprocessed either
+        if aggregation_expr_map.keys().any(|(op, _)| {
+            matches!(
+                op,
+                AggregationOperator::Median
+                    | AggregationOperator::PercentileCont(_)
+                    | AggregationOperator::PercentileDisc(_)
+            )
+        }) {
+            return Err(PostprocessingError::UnprovenOrderStatistic);
+        }
         let group_by_identifiers = Vec::from_iter(IndexSet::from_iter(by_ids));
         Ok(Self {
             remainder_exprs,
@@ -27,6 +27,11 @@ fn we_cannot_have_invalid_group_bys() {
         res,
         Err(PostprocessingError::NestedAggregationInGroupByClause { .. })
     ));
+
+    // Averages are only finalized from proven sums and counts
+    let expr = add(avg(col("b")), lit(1));
+    let res = GroupByPostprocessing::try_new(vec!["a".into()], vec![aliased_expr(expr, "res")]);
+    assert!(matches!(res, Err(PostprocessingError::UnprovenAverage)));
 }
 
 #[test]
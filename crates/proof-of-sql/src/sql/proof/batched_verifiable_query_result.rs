@@ -0,0 +1,102 @@
+use super::{ProofPlan, QueryData, QueryError, QueryProof};
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{CommitmentAccessor, DataAccessor, OwnedTable},
+    },
+    utils::log,
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The results of several sql queries along with a single proof that all of them are valid.
+///
+/// This works like [`VerifiableQueryResult`](super::VerifiableQueryResult), except that the queries
+/// share one transcript, one sumcheck and one evaluation proof, which is much cheaper to produce,
+/// send and verify than one proof per query.
+///
+/// The queries have to be verified together, in the same order as they were proven.
+///
+/// The queries are given as [`ProofPlan`]s, such as the [`proof_expr`] of a [`QueryExpr`], and the
+/// verified results are those of the plans. Any postprocessing of a [`QueryExpr`], such as an
+/// ordering or a slice that is not proven, is left to the caller, who has to apply it to each
+/// result, e.g. with [`apply_postprocessing_steps`].
+///
+/// [`proof_expr`]: crate::sql::parse::QueryExpr::proof_expr
+/// [`QueryExpr`]: crate::sql::parse::QueryExpr
+/// [`apply_postprocessing_steps`]: crate::sql::postprocessing::apply_postprocessing_steps
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BatchedVerifiableQueryResult<CP: CommitmentEvaluationProof> {
+    /// The results of the queries in intermediate form, in the same order as the queries.
+    pub(super) results: Vec<OwnedTable<CP::Scalar>>,
+    /// The proof that all the query results are valid.
+    pub(super) proof: QueryProof<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> BatchedVerifiableQueryResult<CP> {
+    /// Form a `BatchedVerifiableQueryResult` from several query expressions.
+    ///
+    /// This function both computes the results of the queries and constructs a single proof of
+    /// the validity of all of them.
+    #[tracing::instrument(name = "BatchedVerifiableQueryResult::new", level = "info", skip_all)]
+    pub fn new<P: ProofPlan + Serialize>(
+        exprs: &[&P],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        log::log_memory_usage("Start");
+
+        let (proof, results) = QueryProof::new_batch(exprs, accessor, setup);
+
+        log::log_memory_usage("End");
+
+        Self { results, proof }
+    }
+
+    /// Verify a `BatchedVerifiableQueryResult`. Upon success, this function returns the finalized
+    /// form of every query result, in the same order as `exprs`.
+    ///
+    /// The verification fails as a whole if any of the results is invalid.
+    ///
+    /// Note: This does NOT apply any postprocessing to the results!
+    #[tracing::instrument(
+        name = "BatchedVerifiableQueryResult::verify",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify<P: ProofPlan + Serialize>(
+        self,
+        exprs: &[&P],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, QueryError> {
+        log::log_memory_usage("Start");
+
+        let query_data = self
+            .proof
+            .verify_batch(exprs, accessor, self.results, setup)?;
+        let mut finalized = Vec::with_capacity(query_data.len());
+        for (
+            expr,
+            QueryData {
+                table,
+                verification_hash,
+            },
+        ) in exprs.iter().zip(query_data)
+        {
+            let table = table.try_coerce_with_fields(expr.get_intermediate_result_fields())?;
+            finalized.push(QueryData {
+                table: expr.finalize_result(table)?,
+                verification_hash,
+            });
+        }
+
+        log::log_memory_usage("End");
+
+        Ok(finalized)
+    }
+}
@@ -0,0 +1,175 @@
+use super::{BatchedVerifiableQueryResult, QueryError, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::naive_evaluation_proof::NaiveEvaluationProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::{parse::QueryExpr, proof_plans::DynProofPlan},
+};
+
+fn accessor() -> OwnedTableTestAccessor<'static, NaiveEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            bigint("c", [-2, 2, 1, 0, 1]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.other".parse().unwrap(),
+        owned_table([
+            boolean("d", [true, false, true]),
+            varchar("e", ["x", "y", "z"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn query(sql: &str, accessor: &OwnedTableTestAccessor<NaiveEvaluationProof>) -> DynProofPlan {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), accessor)
+        .unwrap()
+        .proof_expr()
+        .clone()
+}
+
+#[test]
+fn we_can_prove_and_verify_a_batch_of_queries() {
+    let accessor = accessor();
+    let plans = [
+        query("SELECT a, b FROM table WHERE c >= 0", &accessor),
+        query(
+            "SELECT a, sum(2 * b + 1) as f, count(*) as g FROM table WHERE c >= 0 group by a",
+            &accessor,
+        ),
+        query("SELECT e FROM other WHERE d", &accessor),
+    ];
+    let exprs = plans.iter().collect::<Vec<_>>();
+
+    let verifiable_results =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &());
+    let query_data = verifiable_results.verify(&exprs, &accessor, &()).unwrap();
+
+    assert_eq!(query_data.len(), 3);
+    assert_eq!(
+        query_data[0].table,
+        owned_table([bigint("a", [1, 2, 3, 2]), bigint("b", [0, 4, 2, 3])])
+    );
+    assert_eq!(
+        query_data[1].table,
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            bigint("f", [1, 16, 5]),
+            bigint("g", [1, 2, 1]),
+        ])
+    );
+    assert_eq!(query_data[2].table, owned_table([varchar("e", ["x", "z"])]));
+    assert!(query_data
+        .iter()
+        .all(|data| data.verification_hash == query_data[0].verification_hash));
+}
+
+#[test]
+fn a_batch_of_one_query_verifies_like_a_single_query() {
+    let accessor = accessor();
+    let plan = query("SELECT a, b FROM table WHERE c >= 0", &accessor);
+
+    let batched_data =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&[&plan], &accessor, &())
+            .verify(&[&plan], &accessor, &())
+            .unwrap();
+    let single_data = VerifiableQueryResult::<NaiveEvaluationProof>::new(&plan, &accessor, &())
+        .verify(&plan, &accessor, &())
+        .unwrap();
+
+    assert_eq!(batched_data.len(), 1);
+    assert_eq!(batched_data[0].table, single_data.table);
+    assert_eq!(
+        batched_data[0].verification_hash,
+        single_data.verification_hash
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_batch_with_a_tampered_result() {
+    let accessor = accessor();
+    let plans = [
+        query("SELECT a, b FROM table WHERE c >= 0", &accessor),
+        query("SELECT e FROM other WHERE d", &accessor),
+    ];
+    let exprs = plans.iter().collect::<Vec<_>>();
+
+    let mut verifiable_results =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &());
+    verifiable_results.results[1] = owned_table([varchar("e", ["x", "y"])]);
+    assert!(verifiable_results.verify(&exprs, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_a_batch_against_different_queries() {
+    let accessor = accessor();
+    let plans = [
+        query("SELECT a, b FROM table WHERE c >= 0", &accessor),
+        query("SELECT e FROM other WHERE d", &accessor),
+    ];
+    let exprs = plans.iter().collect::<Vec<_>>();
+    let reversed_exprs = plans.iter().rev().collect::<Vec<_>>();
+
+    // Missing query
+    assert!(
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &())
+            .verify(&exprs[..1], &accessor, &())
+            .is_err()
+    );
+    // Wrong order
+    assert!(
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &())
+            .verify(&reversed_exprs, &accessor, &())
+            .is_err()
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_batch_that_divides_by_zero() {
+    let accessor = accessor();
+    let plans = [
+        query("SELECT e FROM other WHERE d", &accessor),
+        query("SELECT a / b as q FROM table WHERE c >= 0", &accessor),
+    ];
+    let exprs = plans.iter().collect::<Vec<_>>();
+
+    let verifiable_results =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &());
+    assert_eq!(verifiable_results.proof.division_by_zero_counts, [1]);
+    assert!(matches!(
+        verifiable_results.verify(&exprs, &accessor, &()),
+        Err(QueryError::DivisionByZero)
+    ));
+
+    // The count is proven, so it can not be hidden
+    let mut tampered_results =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&exprs, &accessor, &());
+    tampered_results.proof.division_by_zero_counts = vec![0];
+    assert!(matches!(
+        tampered_results.verify(&exprs, &accessor, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+}
+
+#[test]
+fn we_can_verify_a_batch_whose_zero_divisors_are_filtered_out() {
+    let accessor = accessor();
+    let plan = query("SELECT a / b as q FROM table WHERE b <> 0", &accessor);
+
+    let query_data =
+        BatchedVerifiableQueryResult::<NaiveEvaluationProof>::new(&[&plan], &accessor, &())
+            .verify(&[&plan], &accessor, &())
+            .unwrap();
+    assert_eq!(
+        query_data[0].table,
+        owned_table([bigint("q", [1_i64, 0, 1, 0])])
+    );
+}
@@ -14,6 +14,10 @@ use alloc::{boxed::Box, vec::Vec};
 pub struct FinalRoundBuilder<'a, S: Scalar> {
     num_sumcheck_variables: usize,
     bit_distributions: Vec<BitDistribution>,
+    division_by_zero_counts: Vec<usize>,
+    /// The indicators of the rows with a zero divisor that are yet to be checked by a plan,
+    /// see [`Self::take_division_by_zero_indicators`].
+    division_by_zero_indicators: Vec<&'a [bool]>,
     commitment_descriptor: Vec<CommittableColumn<'a>>,
     pcs_proof_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
     sumcheck_subpolynomials: Vec<SumcheckSubpolynomial<'a, S>>,
@@ -32,6 +36,8 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         Self {
             num_sumcheck_variables,
             bit_distributions: Vec::new(),
+            division_by_zero_counts: Vec::new(),
+            division_by_zero_indicators: Vec::new(),
             commitment_descriptor: Vec::new(),
             pcs_proof_mles: Vec::new(),
             sumcheck_subpolynomials: Vec::new(),
@@ -57,6 +63,28 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         self.bit_distributions.push(dist);
     }
 
+    /// Produce the number of rows with a division by zero that a plan has checked.
+    ///
+    /// The verifier reports a division by zero if any of these counts is nonzero.
+    pub fn produce_division_by_zero_count(&mut self, count: usize) {
+        self.division_by_zero_counts.push(count);
+    }
+
+    /// Produce the indicator of the rows on which an expression divides by zero.
+    ///
+    /// Expressions are evaluated on every row, including the ones that the plan discards,
+    /// so the plan that evaluates the expression has to take the indicator
+    /// with [`Self::take_division_by_zero_indicators`] and check it on the rows it keeps.
+    pub fn produce_division_by_zero_indicator(&mut self, indicator: &'a [bool]) {
+        self.division_by_zero_indicators.push(indicator);
+    }
+
+    /// Take the indicators of the rows with a zero divisor that were produced
+    /// since they were last taken.
+    pub fn take_division_by_zero_indicators(&mut self) -> Vec<&'a [bool]> {
+        core::mem::take(&mut self.division_by_zero_indicators)
+    }
+
     /// Produce an anchored MLE that we can reference in sumcheck.
     ///
     /// An anchored MLE is an MLE where the verifier has access to the commitment.
@@ -141,6 +169,10 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         &self.bit_distributions
     }
 
+    pub fn division_by_zero_counts(&self) -> &[usize] {
+        &self.division_by_zero_counts
+    }
+
     /// Pops a challenge off the stack of post-result challenges.
     ///
     /// These challenges are used in creation of the constraints in the proof.
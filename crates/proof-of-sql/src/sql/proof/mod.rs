@@ -35,6 +35,8 @@ pub(crate) use proof_plan::{HonestProver, ProverEvaluate, ProverHonestyMarker};
 
 mod query_proof;
 use query_proof::QueryProof;
+#[cfg(test)]
+mod query_proof_sol_test;
 #[cfg(all(test, feature = "blitzar"))]
 mod query_proof_test;
 
@@ -51,6 +53,11 @@ pub use verifiable_query_result::VerifiableQueryResult;
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test;
 
+mod batched_verifiable_query_result;
+pub use batched_verifiable_query_result::BatchedVerifiableQueryResult;
+#[cfg(test)]
+mod batched_verifiable_query_result_test;
+
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test_utility;
 #[cfg(all(test, feature = "blitzar"))]
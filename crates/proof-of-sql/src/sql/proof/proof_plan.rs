@@ -1,4 +1,4 @@
-use super::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder};
+use super::{FinalRoundBuilder, FirstRoundBuilder, QueryError, VerificationBuilder};
 use crate::base::{
     database::{ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableRef},
     map::{IndexMap, IndexSet},
@@ -24,6 +24,23 @@ pub trait ProofPlan: Debug + Send + Sync + ProverEvaluate {
     /// Return all the result column fields
     fn get_column_result_fields(&self) -> Vec<ColumnField>;
 
+    /// Return the fields of the result in the intermediate form that is proven.
+    ///
+    /// This is the same as [`ProofPlan::get_column_result_fields`] unless the plan has to
+    /// finalize its result with [`ProofPlan::finalize_result`].
+    fn get_intermediate_result_fields(&self) -> Vec<ColumnField> {
+        self.get_column_result_fields()
+    }
+
+    /// Turn the verified result in intermediate form into its final form, e.g. by dividing
+    /// proven sums by proven counts to get averages.
+    fn finalize_result<S: Scalar>(
+        &self,
+        result: OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, QueryError> {
+        Ok(result)
+    }
+
     /// Return all the columns referenced in the Query
     fn get_column_references(&self) -> IndexSet<ColumnRef>;
 
@@ -36,7 +53,7 @@ pub trait ProverEvaluate {
     /// Evaluate the query, modify `FirstRoundBuilder` and return the result.
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S>;
@@ -1,6 +1,6 @@
 use super::{
     make_sumcheck_state::make_sumcheck_prover_state, FinalRoundBuilder, FirstRoundBuilder,
-    ProofPlan, QueryData, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
+    ProofPlan, QueryData, QueryError, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
     VerificationBuilder,
 };
 use crate::{
@@ -13,14 +13,14 @@ use crate::{
         },
         map::{IndexMap, IndexSet},
         math::log2_up,
-        polynomial::compute_evaluation_vector,
+        polynomial::{compute_evaluation_vector, MultilinearExtension},
         proof::{Keccak256Transcript, ProofError, Transcript},
         scalar::Scalar,
     },
-    proof_primitive::sumcheck::SumcheckProof,
+    proof_primitive::sumcheck::{Subclaim, SumcheckProof},
     utils::log,
 };
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp;
 use num_traits::Zero;
@@ -55,12 +55,20 @@ fn get_index_range<'a>(
 pub(super) struct QueryProof<CP: CommitmentEvaluationProof> {
     /// Bit distributions
     pub bit_distributions: Vec<BitDistribution>,
+    /// The number of rows with a division by zero found by each check
+    pub division_by_zero_counts: Vec<usize>,
     /// One evaluation lengths
     pub one_evaluation_lengths: Vec<usize>,
+    /// Rho evaluation lengths
+    pub rho_evaluation_lengths: Vec<usize>,
+    /// Commitments to the intermediate MLEs produced in the first round
+    pub first_round_commitments: Vec<CP::Commitment>,
     /// Commitments
     pub commitments: Vec<CP::Commitment>,
     /// Sumcheck Proof
     pub sumcheck_proof: SumcheckProof<CP::Scalar>,
+    /// MLEs produced in the first round used in sumcheck
+    pub first_round_pcs_proof_evaluations: Vec<CP::Scalar>,
     /// MLEs used in sumcheck except for the result columns
     pub pcs_proof_evaluations: Vec<CP::Scalar>,
     /// Inner product proof of the MLEs' evaluations
@@ -73,45 +81,60 @@ pub(super) struct QueryProof<CP: CommitmentEvaluationProof> {
 
 impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     /// Create a new `QueryProof`.
+    ///
+    /// # Panics
+    ///
+    /// Will not panic, since a batch of one plan always has exactly one result.
     #[tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)]
     pub fn new(
         expr: &(impl ProofPlan + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup<'_>,
     ) -> (Self, OwnedTable<CP::Scalar>) {
+        let (proof, mut results) = Self::new_batch(&[expr], accessor, setup);
+        let result = results.pop().expect("There is exactly one result per plan");
+        (proof, result)
+    }
+
+    /// Create a new `QueryProof` for several plans at once.
+    ///
+    /// The plans share one transcript, one sumcheck and one evaluation proof.
+    /// The results are returned in the same order as the plans, in the intermediate form of the
+    /// plans, so finalizing them and applying any postprocessing is left to the caller.
+    #[tracing::instrument(name = "QueryProof::new_batch", level = "debug", skip_all)]
+    pub fn new_batch<P: ProofPlan + Serialize>(
+        exprs: &[&P],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> (Self, Vec<OwnedTable<CP::Scalar>>) {
         log::log_memory_usage("Start");
 
-        let (min_row_num, max_row_num) = get_index_range(accessor, &expr.get_table_references());
+        let table_refs = get_table_references(exprs);
+        let (min_row_num, max_row_num) = get_index_range(accessor, &table_refs);
         let initial_range_length = max_row_num - min_row_num;
         let alloc = Bump::new();
 
-        let total_col_refs = expr.get_column_references();
-        let table_map: IndexMap<TableRef, Table<CP::Scalar>> = expr
-            .get_table_references()
-            .into_iter()
-            .map(|table_ref| {
-                let col_refs: IndexSet<ColumnRef> = total_col_refs
-                    .iter()
-                    .filter(|col_ref| col_ref.table_ref() == table_ref)
-                    .cloned()
-                    .collect();
-                (table_ref, accessor.get_table(table_ref, &col_refs))
-            })
-            .collect();
+        let total_col_refs = get_column_references(exprs);
+        let table_map = get_table_map(accessor, table_refs, &total_col_refs);
 
-        // Prover First Round: Evaluate the query && get the right number of post result challenges
+        // Prover First Round: Evaluate the queries && get the right number of post result challenges
         let mut first_round_builder = FirstRoundBuilder::new();
-        let query_result = expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map);
-        let owned_table_result = OwnedTable::from(&query_result);
-        let provable_result = query_result.into();
+        let owned_table_results: Vec<OwnedTable<CP::Scalar>> = exprs
+            .iter()
+            .map(|expr| {
+                let query_result =
+                    expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map);
+                OwnedTable::from(&query_result)
+            })
+            .collect();
         let one_evaluation_lengths = first_round_builder.one_evaluation_lengths();
+        let rho_evaluation_lengths = first_round_builder.rho_evaluation_lengths();
 
-        let range_length = one_evaluation_lengths
-            .iter()
-            .copied()
-            .chain(core::iter::once(initial_range_length))
-            .max()
-            .expect("Will always have at least one element"); // safe to unwrap because we have at least one element
+        let range_length = get_range_length(
+            initial_range_length,
+            one_evaluation_lengths,
+            rho_evaluation_lengths,
+        );
 
         let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
         assert!(num_sumcheck_variables > 0);
@@ -119,14 +142,20 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript = make_transcript(
-            expr,
-            &owned_table_result,
+            exprs,
+            &owned_table_results,
             range_length,
             min_row_num,
             one_evaluation_lengths,
+            rho_evaluation_lengths,
             post_result_challenge_count,
         );
 
+        // commit to any intermediate MLEs produced in the first round
+        let first_round_commitments =
+            first_round_builder.commit_intermediate_mles(min_row_num, setup);
+        extend_transcript_with_commitments(&mut transcript, &first_round_commitments, &[]);
+
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
         // the prover after the prover sends the result, but before the prover
@@ -143,7 +172,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             builder.produce_anchored_mle(accessor.get_column(col_ref));
         }
 
-        expr.final_round_evaluate(&mut builder, &alloc, &table_map);
+        for expr in exprs {
+            expr.final_round_evaluate(&mut builder, &alloc, &table_map);
+        }
 
         let num_sumcheck_variables = builder.num_sumcheck_variables();
 
@@ -156,6 +187,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &commitments,
             builder.bit_distributions(),
         );
+        transcript.extend_serialize_as_le(builder.division_by_zero_counts());
 
         // construct the sumcheck polynomial
         let subpolynomial_constraint_count = builder.num_sumcheck_subpolynomials();
@@ -174,41 +206,26 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let mut evaluation_point = vec![Zero::zero(); state.num_vars];
         let sumcheck_proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, state);
 
-        // evaluate the MLEs used in sumcheck except for the result columns
-        let mut evaluation_vec = vec![Zero::zero(); range_length];
-        compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
-        let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
-
-        // commit to the MLE evaluations
-        transcript.extend_canonical_serialize_as_le(&pcs_proof_evaluations);
-
-        // fold together the pre result MLEs -- this will form the input to an inner product proof
-        // of their evaluations (fold in this context means create a random linear combination)
-        let random_scalars: Vec<_> =
-            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
-                .take(pcs_proof_evaluations.len())
-                .collect();
-
-        assert_eq!(random_scalars.len(), builder.pcs_proof_mles().len());
-        let mut folded_mle = vec![Zero::zero(); range_length];
-        for (multiplier, evaluator) in random_scalars.iter().zip(builder.pcs_proof_mles().iter()) {
-            evaluator.mul_add(&mut folded_mle, multiplier);
-        }
-
-        // finally, form the inner product proof of the MLEs' evaluations
-        let evaluation_proof = CP::new(
-            &mut transcript,
-            &folded_mle,
-            &evaluation_point,
-            min_row_num as u64,
-            setup,
-        );
+        let (first_round_pcs_proof_evaluations, pcs_proof_evaluations, evaluation_proof) =
+            prove_pcs_proof_evaluations(
+                &mut transcript,
+                &first_round_builder,
+                &builder,
+                &evaluation_point,
+                range_length,
+                min_row_num,
+                setup,
+            );
 
         let proof = Self {
             bit_distributions: builder.bit_distributions().to_vec(),
+            division_by_zero_counts: builder.division_by_zero_counts().to_vec(),
             one_evaluation_lengths: one_evaluation_lengths.to_vec(),
+            rho_evaluation_lengths: rho_evaluation_lengths.to_vec(),
+            first_round_commitments,
             commitments,
             sumcheck_proof,
+            first_round_pcs_proof_evaluations,
             pcs_proof_evaluations,
             evaluation_proof,
             range_length,
@@ -218,11 +235,15 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         log::log_memory_usage("End");
 
-        (proof, provable_result)
+        (proof, owned_table_results)
     }
 
     #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
     /// Verify a `QueryProof`. Note: This does NOT transform the result!
+    ///
+    /// # Panics
+    ///
+    /// Will not panic, since a successfully verified batch of one plan always has exactly one result.
     pub fn verify(
         self,
         expr: &(impl ProofPlan + Serialize),
@@ -230,38 +251,53 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         result: OwnedTable<CP::Scalar>,
         setup: &CP::VerifierPublicSetup<'_>,
     ) -> QueryResult<CP::Scalar> {
+        let mut query_data = self.verify_batch(&[expr], accessor, vec![result], setup)?;
+        Ok(query_data
+            .pop()
+            .expect("There is exactly one result per plan"))
+    }
+
+    #[tracing::instrument(name = "QueryProof::verify_batch", level = "debug", skip_all, err)]
+    /// Verify a `QueryProof` of several plans, given one result per plan.
+    /// Note: This does NOT transform the results!
+    pub fn verify_batch<P: ProofPlan + Serialize>(
+        self,
+        exprs: &[&P],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: Vec<OwnedTable<CP::Scalar>>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, QueryError> {
         log::log_memory_usage("Start");
 
-        let table_refs = expr.get_table_references();
+        if results.len() != exprs.len() {
+            Err(ProofError::VerificationError {
+                error: "number of results does not match number of plans",
+            })?;
+        }
+
+        let table_refs = get_table_references(exprs);
         let (min_row_num, _) = get_index_range(accessor, &table_refs);
         let num_sumcheck_variables = cmp::max(log2_up(self.range_length), 1);
         assert!(num_sumcheck_variables > 0);
 
-        // validate bit decompositions
-        for dist in &self.bit_distributions {
-            if !dist.is_valid() {
-                Err(ProofError::VerificationError {
-                    error: "invalid bit distributions",
-                })?;
-            } else if !dist.is_within_acceptable_range() {
-                Err(ProofError::VerificationError {
-                    error: "bit distribution outside of acceptable range",
-                })?;
-            }
-        }
+        validate_bit_distributions(&self.bit_distributions)?;
 
-        let column_references = expr.get_column_references();
+        let column_references = get_column_references(exprs);
 
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript = make_transcript(
-            expr,
-            &result,
+            exprs,
+            &results,
             self.range_length,
             min_row_num,
             &self.one_evaluation_lengths,
+            &self.rho_evaluation_lengths,
             self.post_result_challenge_count,
         );
 
+        // add the first round commitments to the transcript
+        extend_transcript_with_commitments(&mut transcript, &self.first_round_commitments, &[]);
+
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
         // the prover after the prover sends the result, but before the prover
@@ -278,6 +314,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &self.commitments,
             &self.bit_distributions,
         );
+        transcript.extend_serialize_as_le(&self.division_by_zero_counts);
 
         // draw the random scalars for sumcheck
         let num_random_scalars = num_sumcheck_variables + self.subpolynomial_constraint_count;
@@ -297,16 +334,82 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &Zero::zero(),
         )?;
 
-        // commit to mle evaluations
-        transcript.extend_canonical_serialize_as_le(&self.pcs_proof_evaluations);
+        // pass over the provable AST to fill in the verification builder
+        let (mut builder, one_eval_map) = self.make_verification_builder(
+            accessor,
+            &table_refs,
+            min_row_num,
+            &subclaim,
+            &sumcheck_random_scalars,
+            post_result_challenges,
+        );
 
-        // draw the random scalars for the evaluation proof
-        // (i.e. the folding/random linear combination of the pcs_proof_mles)
-        let evaluation_random_scalars: Vec<_> =
-            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
-                .take(self.pcs_proof_evaluations.len())
-                .collect();
+        let evaluation_accessor: IndexMap<_, _> = column_references
+            .iter()
+            .map(|col| Ok((col.clone(), builder.try_consume_mle_evaluation()?)))
+            .collect::<Result<_, ProofError>>()?;
+
+        verify_results(
+            exprs,
+            &results,
+            &mut builder,
+            &evaluation_accessor,
+            &one_eval_map,
+            &subclaim.evaluation_point,
+        )?;
+
+        // perform the evaluation check of the sumcheck polynomial
+        let sumcheck_evaluation = builder
+            .try_sumcheck_evaluation()
+            .map_err(ProofError::from)?;
+        if sumcheck_evaluation != subclaim.expected_evaluation {
+            Err(ProofError::VerificationError {
+                error: "sumcheck evaluation check failed",
+            })?;
+        }
+
+        // finally, check the MLE evaluations with the inner product proof
+        self.verify_pcs_proof_evaluations(
+            &mut transcript,
+            accessor,
+            &column_references,
+            &subclaim.evaluation_point,
+            min_row_num,
+            setup,
+        )?;
+
+        // the counts are proven, so a division by zero can only be reported once the proof is verified
+        if self.division_by_zero_counts.iter().any(|&count| count != 0) {
+            Err(QueryError::DivisionByZero)?;
+        }
+
+        let verification_hash = transcript.challenge_as_le();
 
+        log::log_memory_usage("End");
+
+        Ok(results
+            .into_iter()
+            .map(|table| QueryData {
+                table,
+                verification_hash,
+            })
+            .collect())
+    }
+
+    /// Creates the verification builder from the sumcheck subclaim along with the evaluations of
+    /// the one columns of the referenced tables.
+    fn make_verification_builder<'a>(
+        &'a self,
+        accessor: &impl MetadataAccessor,
+        table_refs: &IndexSet<TableRef>,
+        min_row_num: usize,
+        subclaim: &Subclaim<CP::Scalar>,
+        sumcheck_random_scalars: &SumcheckRandomScalars<'a, CP::Scalar>,
+        post_result_challenges: Vec<CP::Scalar>,
+    ) -> (
+        VerificationBuilder<'a, CP::Scalar>,
+        IndexMap<TableRef, CP::Scalar>,
+    ) {
         // Always prepend input lengths to the one evaluation lengths
         let table_length_map = table_refs
             .iter()
@@ -318,118 +421,307 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             .chain(self.one_evaluation_lengths.iter())
             .copied();
 
-        // pass over the provable AST to fill in the verification builder
         let sumcheck_evaluations = SumcheckMleEvaluations::new(
             self.range_length,
             one_evaluation_lengths,
+            self.rho_evaluation_lengths.iter().copied(),
             &subclaim.evaluation_point,
-            &sumcheck_random_scalars,
+            sumcheck_random_scalars,
+            &self.first_round_pcs_proof_evaluations,
             &self.pcs_proof_evaluations,
         );
         let one_eval_map: IndexMap<TableRef, CP::Scalar> = table_length_map
             .iter()
             .map(|(table_ref, length)| (**table_ref, sumcheck_evaluations.one_evaluations[length]))
             .collect();
-        let mut builder = VerificationBuilder::new(
+        let builder = VerificationBuilder::new(
             min_row_num,
             sumcheck_evaluations,
             &self.bit_distributions,
+            &self.division_by_zero_counts,
             sumcheck_random_scalars.subpolynomial_multipliers,
             post_result_challenges,
             self.one_evaluation_lengths.clone(),
+            self.rho_evaluation_lengths.clone(),
             subclaim.max_multiplicands,
         );
+        (builder, one_eval_map)
+    }
+
+    /// Verifies the evaluations of the MLEs of both rounds and of the referenced columns at the
+    /// evaluation point with the evaluation proof of their random linear combination.
+    fn verify_pcs_proof_evaluations(
+        &self,
+        transcript: &mut impl Transcript,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        column_references: &IndexSet<ColumnRef>,
+        evaluation_point: &[CP::Scalar],
+        min_row_num: usize,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<(), ProofError> {
+        // commit to mle evaluations
+        transcript.extend_canonical_serialize_as_le(&self.first_round_pcs_proof_evaluations);
+        transcript.extend_canonical_serialize_as_le(&self.pcs_proof_evaluations);
+
+        // draw the random scalars for the evaluation proof
+        // (i.e. the folding/random linear combination of the pcs_proof_mles)
+        let evaluation_random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(
+                    self.first_round_pcs_proof_evaluations.len() + self.pcs_proof_evaluations.len(),
+                )
+                .collect();
 
-        let pcs_proof_commitments: Vec<_> = column_references
+        let pcs_proof_commitments: Vec<_> = self
+            .first_round_commitments
             .iter()
-            .map(|col| accessor.get_commitment(col.clone()))
+            .cloned()
+            .chain(
+                column_references
+                    .iter()
+                    .map(|col| accessor.get_commitment(col.clone())),
+            )
             .chain(self.commitments.iter().cloned())
             .collect();
-        let evaluation_accessor: IndexMap<_, _> = column_references
-            .into_iter()
-            .map(|col| Ok((col, builder.try_consume_mle_evaluation()?)))
-            .collect::<Result<_, ProofError>>()?;
-
-        let verifier_evaluations = expr.verifier_evaluate(
-            &mut builder,
-            &evaluation_accessor,
-            Some(&result),
-            &one_eval_map,
-        )?;
-        // compute the evaluation of the result MLEs
-        let result_evaluations = result.mle_evaluations(&subclaim.evaluation_point);
-        // check the evaluation of the result MLEs
-        if verifier_evaluations.column_evals() != result_evaluations {
-            Err(ProofError::VerificationError {
-                error: "result evaluation check failed",
-            })?;
-        }
-
-        // perform the evaluation check of the sumcheck polynomial
-        if builder.sumcheck_evaluation() != subclaim.expected_evaluation {
-            Err(ProofError::VerificationError {
-                error: "sumcheck evaluation check failed",
-            })?;
-        }
-
-        // finally, check the MLE evaluations with the inner product proof
+        let pcs_proof_evaluations: Vec<_> = self
+            .first_round_pcs_proof_evaluations
+            .iter()
+            .chain(&self.pcs_proof_evaluations)
+            .copied()
+            .collect();
         self.evaluation_proof
             .verify_batched_proof(
-                &mut transcript,
+                transcript,
                 &pcs_proof_commitments,
                 &evaluation_random_scalars,
-                &self.pcs_proof_evaluations,
-                &subclaim.evaluation_point,
+                &pcs_proof_evaluations,
+                evaluation_point,
                 min_row_num as u64,
                 self.range_length,
                 setup,
             )
             .map_err(|_e| ProofError::VerificationError {
                 error: "Inner product proof of MLE evaluations failed",
+            })
+    }
+}
+
+/// Returns the tables referenced by any of the plans, in order of first reference.
+fn get_table_references<P: ProofPlan>(exprs: &[&P]) -> IndexSet<TableRef> {
+    exprs
+        .iter()
+        .flat_map(|expr| expr.get_table_references())
+        .collect()
+}
+
+/// Returns the columns referenced by any of the plans, in order of first reference.
+fn get_column_references<P: ProofPlan>(exprs: &[&P]) -> IndexSet<ColumnRef> {
+    exprs
+        .iter()
+        .flat_map(|expr| expr.get_column_references())
+        .collect()
+}
+
+/// Validates the bit distributions claimed by the prover.
+///
+/// Only the range of comparisons is checked here, since it is the widest one accepted.
+/// The narrower range of every other sign proof is checked by `verifier_evaluate_sign`.
+fn validate_bit_distributions(bit_distributions: &[BitDistribution]) -> Result<(), ProofError> {
+    for dist in bit_distributions {
+        if !dist.is_valid() {
+            Err(ProofError::VerificationError {
+                error: "invalid bit distributions",
+            })?;
+        } else if !dist.is_within_acceptable_comparison_range() {
+            Err(ProofError::VerificationError {
+                error: "bit distribution outside of acceptable range",
             })?;
+        }
+    }
+    Ok(())
+}
 
-        let verification_hash = transcript.challenge_as_le();
+/// Passes over the plans to fill in the verification builder, checking the evaluations of the
+/// result MLEs of each plan at the evaluation point.
+fn verify_results<S: Scalar, P: ProofPlan>(
+    exprs: &[&P],
+    results: &[OwnedTable<S>],
+    builder: &mut VerificationBuilder<S>,
+    evaluation_accessor: &IndexMap<ColumnRef, S>,
+    one_eval_map: &IndexMap<TableRef, S>,
+    evaluation_point: &[S],
+) -> Result<(), ProofError> {
+    for (expr, result) in exprs.iter().zip(results) {
+        let verifier_evaluations =
+            expr.verifier_evaluate(builder, evaluation_accessor, Some(result), one_eval_map)?;
+        // compute the evaluation of the result MLEs
+        let result_evaluations = result.mle_evaluations(evaluation_point);
+        // check the evaluation of the result MLEs
+        if verifier_evaluations.column_evals() != result_evaluations {
+            Err(ProofError::VerificationError {
+                error: "result evaluation check failed",
+            })?;
+        }
+    }
+    Ok(())
+}
 
-        log::log_memory_usage("End");
+/// Evaluates the MLEs of both rounds at the evaluation point and proves these evaluations with a
+/// single evaluation proof of their random linear combination.
+fn prove_pcs_proof_evaluations<'a, CP: CommitmentEvaluationProof>(
+    transcript: &mut impl Transcript,
+    first_round_builder: &FirstRoundBuilder<'a, CP::Scalar>,
+    builder: &FinalRoundBuilder<'a, CP::Scalar>,
+    evaluation_point: &[CP::Scalar],
+    range_length: usize,
+    min_row_num: usize,
+    setup: &CP::ProverPublicSetup<'_>,
+) -> (Vec<CP::Scalar>, Vec<CP::Scalar>, CP) {
+    // evaluate the MLEs used in sumcheck except for the result columns
+    let mut evaluation_vec = vec![Zero::zero(); range_length];
+    compute_evaluation_vector(&mut evaluation_vec, evaluation_point);
+    let first_round_pcs_proof_evaluations =
+        first_round_builder.evaluate_pcs_proof_mles(&evaluation_vec);
+    let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
+
+    // commit to the MLE evaluations
+    transcript.extend_canonical_serialize_as_le(&first_round_pcs_proof_evaluations);
+    transcript.extend_canonical_serialize_as_le(&pcs_proof_evaluations);
+
+    // fold together the pre result MLEs -- this will form the input to an inner product proof
+    // of their evaluations (fold in this context means create a random linear combination)
+    let random_scalars: Vec<_> = core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+        .take(first_round_pcs_proof_evaluations.len() + pcs_proof_evaluations.len())
+        .collect();
+    let folded_mle = fold_pcs_proof_mles(
+        &random_scalars,
+        first_round_builder.pcs_proof_mles(),
+        builder.pcs_proof_mles(),
+        range_length,
+    );
+
+    // finally, form the inner product proof of the MLEs' evaluations
+    let evaluation_proof = CP::new(
+        transcript,
+        &folded_mle,
+        evaluation_point,
+        min_row_num as u64,
+        setup,
+    );
+    (
+        first_round_pcs_proof_evaluations,
+        pcs_proof_evaluations,
+        evaluation_proof,
+    )
+}
 
-        Ok(QueryData {
-            table: result,
-            verification_hash,
+/// Returns the tables the prover needs, each with the referenced columns of it.
+fn get_table_map<'a, S: Scalar>(
+    accessor: &'a impl DataAccessor<S>,
+    table_refs: IndexSet<TableRef>,
+    column_refs: &IndexSet<ColumnRef>,
+) -> IndexMap<TableRef, Table<'a, S>> {
+    table_refs
+        .into_iter()
+        .map(|table_ref| {
+            let col_refs: IndexSet<ColumnRef> = column_refs
+                .iter()
+                .filter(|col_ref| col_ref.table_ref() == table_ref)
+                .cloned()
+                .collect();
+            (table_ref, accessor.get_table(table_ref, &col_refs))
         })
+        .collect()
+}
+
+/// Returns the length of the range of generators, which covers the referenced rows of the tables
+/// and every one and rho evaluation length, and is never empty, even if all the tables are.
+fn get_range_length(
+    initial_range_length: usize,
+    one_evaluation_lengths: &[usize],
+    rho_evaluation_lengths: &[usize],
+) -> usize {
+    one_evaluation_lengths
+        .iter()
+        .chain(rho_evaluation_lengths)
+        .copied()
+        .fold(initial_range_length.max(1), usize::max)
+}
+
+/// Folds the MLEs of both rounds the evaluation proof is for into their random linear combination
+/// with `random_scalars`.
+///
+/// # Panics
+/// Panics if there are not as many MLEs as random scalars.
+fn fold_pcs_proof_mles<S: Scalar>(
+    random_scalars: &[S],
+    first_round_mles: &[Box<dyn MultilinearExtension<S> + '_>],
+    final_round_mles: &[Box<dyn MultilinearExtension<S> + '_>],
+    range_length: usize,
+) -> Vec<S> {
+    assert_eq!(
+        random_scalars.len(),
+        first_round_mles.len() + final_round_mles.len()
+    );
+    let mut folded_mle = vec![Zero::zero(); range_length];
+    for (multiplier, evaluator) in random_scalars
+        .iter()
+        .zip(first_round_mles.iter().chain(final_round_mles))
+    {
+        evaluator.mul_add(&mut folded_mle, multiplier);
     }
+    folded_mle
 }
 
 /// Constructs a transcript for the proof process.
 ///
 /// This function initializes a transcript and extends it with various elements
-/// such as the result table columns, the proof plan expression, the range length,
+/// such as the result table columns and the proof plan expression of each query, the range length,
 /// the minimum row number, and the one evaluation lengths.
 ///
+/// If there is more than one plan, the number of plans and the number of columns and rows of
+/// each result come first, so that the data of one plan can not be taken for that of another.
+/// A single plan has nothing to separate, so its transcript is the same as without batching,
+/// which is what the Solidity verifier reproduces.
+///
 /// # Arguments
 ///
-/// * `expr` - The proof plan expression.
-/// * `result` - The result table containing the query result.
+/// * `exprs` - The proof plan expressions.
+/// * `results` - The result tables containing the query results, one per expression.
 /// * `range_length` - The length of the range of generators used.
 /// * `min_row_num` - The minimum row number in the index range of the tables referenced by the query.
 /// * `one_evaluation_lengths` - The lengths of the one evaluations.
+/// * `rho_evaluation_lengths` - The lengths of the rho evaluations.
+/// * `post_result_challenge_count` - The number of post-result challenges.
 ///
 /// # Returns
 ///
 /// A transcript initialized with the provided data.
-fn make_transcript<S: Scalar, T: Transcript>(
-    expr: &(impl ProofPlan + Serialize),
-    result: &OwnedTable<S>,
+fn make_transcript<S: Scalar, T: Transcript, P: ProofPlan + Serialize>(
+    exprs: &[&P],
+    results: &[OwnedTable<S>],
     range_length: usize,
     min_row_num: usize,
     one_evaluation_lengths: &[usize],
+    rho_evaluation_lengths: &[usize],
     post_result_challenge_count: usize,
 ) -> T {
     let mut transcript = T::new();
-    extend_transcript_with_owned_table(&mut transcript, result);
-    transcript.extend_serialize_as_le(expr);
+    if exprs.len() != 1 {
+        transcript.extend_serialize_as_le(&exprs.len());
+        for result in results {
+            transcript.extend_serialize_as_le(&result.num_columns());
+            transcript.extend_serialize_as_le(&result.num_rows());
+        }
+    }
+    for (expr, result) in exprs.iter().zip(results) {
+        extend_transcript_with_owned_table(&mut transcript, result);
+        transcript.extend_serialize_as_le(expr);
+    }
     transcript.extend_serialize_as_le(&range_length);
     transcript.extend_serialize_as_le(&min_row_num);
     transcript.extend_serialize_as_le(one_evaluation_lengths);
+    transcript.extend_serialize_as_le(rho_evaluation_lengths);
     transcript.extend_serialize_as_le(&post_result_challenge_count);
     transcript
 }
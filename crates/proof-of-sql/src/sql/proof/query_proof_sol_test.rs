@@ -0,0 +1,427 @@
+use super::QueryProof;
+use crate::{
+    base::database::{
+        owned_table_utility::{bigint, boolean, int, int128, owned_table, smallint, tinyint},
+        ColumnRef, ColumnType, CommitmentAccessor, LiteralValue, MetadataAccessor, OwnedColumn,
+        OwnedTable, OwnedTableTestAccessor, TableRef,
+    },
+    evm_compatibility::{
+        constants::{
+            BIGINT_TYPE_NUM, BOOLEAN_TYPE_NUM, INT128_TYPE_NUM, INT_TYPE_NUM, SCALAR_TYPE_NUM,
+            SMALLINT_TYPE_NUM, TINYINT_TYPE_NUM,
+        },
+        EVMProofPlan,
+    },
+    proof_primitive::hyperkzg::{
+        BNScalar, HyperKZGCommitmentEvaluationProof, HyperKZGVerifierSetup,
+    },
+    sql::{
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{AggregateExec, DynProofPlan, FilterExec, ProjectionExec},
+    },
+    tests::{ForgeScript, ForgeScriptError},
+};
+use alloy_sol_types::{
+    private::primitives::{Bytes, B256, I256, U256},
+    sol,
+};
+use ark_bn254::{Fq, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::test_rng;
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+
+sol! {
+    struct G1Point {
+        uint256 x;
+        uint256 y;
+    }
+    struct G2Point {
+        uint256 xImaginary;
+        uint256 xReal;
+        uint256 yImaginary;
+        uint256 yReal;
+    }
+    struct HyperKZGProof {
+        G1Point[] com;
+        uint256[3][] v;
+        G1Point[] w;
+    }
+    struct VerifierSetup {
+        G1Point g1;
+        G2Point g2;
+        G2Point tauG2;
+        uint256 numGenerators;
+    }
+    struct ResultColumn {
+        string name;
+        uint8 columnType;
+        int256[] values;
+    }
+    struct SolQueryProof {
+        uint64[] oneEvaluationLengths;
+        G1Point[] commitments;
+        uint256[] sumcheckCoefficients;
+        uint256[] pcsProofEvaluations;
+        HyperKZGProof evaluationProof;
+        uint64 rangeLength;
+        uint64 subpolynomialConstraintCount;
+        uint64 postResultChallengeCount;
+    }
+    struct QueryProofCase {
+        bytes plan;
+        ResultColumn[] result;
+        SolQueryProof proof;
+        uint256[] tableLengths;
+        G1Point[] columnCommitments;
+        VerifierSetup setup;
+        bytes32 expectedVerificationHash;
+    }
+}
+
+fn scalar_to_u256(scalar: BNScalar) -> U256 {
+    U256::from_limbs(scalar.into())
+}
+
+fn fq_to_u256(fq: Fq) -> U256 {
+    U256::from_limbs(fq.into_bigint().0)
+}
+
+/// The point at infinity is `(0, 0)` on the EVM.
+fn g1_to_sol(point: G1Affine) -> G1Point {
+    let (x, y) = point.xy().map_or((U256::ZERO, U256::ZERO), |(x, y)| {
+        (fq_to_u256(x), fq_to_u256(y))
+    });
+    G1Point { x, y }
+}
+
+fn g2_to_sol(point: G2Affine) -> G2Point {
+    let (x, y) = point
+        .xy()
+        .expect("the setup does not contain the point at infinity");
+    G2Point {
+        xImaginary: fq_to_u256(x.c1),
+        xReal: fq_to_u256(x.c0),
+        yImaginary: fq_to_u256(y.c1),
+        yReal: fq_to_u256(y.c0),
+    }
+}
+
+fn i256_column<T: Copy + Into<i128>>(values: &[T]) -> Vec<I256> {
+    values
+        .iter()
+        .map(|&value| I256::try_from(value.into()).unwrap())
+        .collect()
+}
+
+fn result_to_sol(result: &OwnedTable<BNScalar>) -> Vec<ResultColumn> {
+    result
+        .inner_table()
+        .iter()
+        .map(|(name, column)| {
+            let (column_type, values) = match column {
+                OwnedColumn::Boolean(values) => (
+                    BOOLEAN_TYPE_NUM,
+                    i256_column(&values.iter().map(|&b| u8::from(b)).collect::<Vec<_>>()),
+                ),
+                OwnedColumn::TinyInt(values) => (TINYINT_TYPE_NUM, i256_column(values)),
+                OwnedColumn::SmallInt(values) => (SMALLINT_TYPE_NUM, i256_column(values)),
+                OwnedColumn::Int(values) => (INT_TYPE_NUM, i256_column(values)),
+                OwnedColumn::BigInt(values) => (BIGINT_TYPE_NUM, i256_column(values)),
+                OwnedColumn::Int128(values) => (INT128_TYPE_NUM, i256_column(values)),
+                OwnedColumn::Scalar(values) => (
+                    SCALAR_TYPE_NUM,
+                    values
+                        .iter()
+                        .map(|&value| I256::try_from(scalar_to_u256(value)).unwrap())
+                        .collect(),
+                ),
+                other => panic!(
+                    "the Solidity verifier does not support {:?}",
+                    other.column_type()
+                ),
+            };
+            ResultColumn {
+                name: name.value.clone(),
+                columnType: column_type,
+                values,
+            }
+        })
+        .collect()
+}
+
+fn proof_to_sol(proof: &QueryProof<HyperKZGCommitmentEvaluationProof>) -> SolQueryProof {
+    // The supported plans never produce these parts of a proof.
+    assert!(proof.bit_distributions.is_empty());
+    assert!(proof.division_by_zero_counts.is_empty());
+    assert!(proof.rho_evaluation_lengths.is_empty());
+    assert!(proof.first_round_commitments.is_empty());
+    assert!(proof.first_round_pcs_proof_evaluations.is_empty());
+    SolQueryProof {
+        oneEvaluationLengths: proof
+            .one_evaluation_lengths
+            .iter()
+            .map(|&length| length as u64)
+            .collect(),
+        commitments: proof
+            .commitments
+            .iter()
+            .map(|commitment| g1_to_sol(commitment.0.into_affine()))
+            .collect(),
+        sumcheckCoefficients: proof
+            .sumcheck_proof
+            .coefficients
+            .iter()
+            .copied()
+            .map(scalar_to_u256)
+            .collect(),
+        pcsProofEvaluations: proof
+            .pcs_proof_evaluations
+            .iter()
+            .copied()
+            .map(scalar_to_u256)
+            .collect(),
+        evaluationProof: HyperKZGProof {
+            com: proof
+                .evaluation_proof
+                .com
+                .iter()
+                .copied()
+                .map(g1_to_sol)
+                .collect(),
+            v: proof
+                .evaluation_proof
+                .v
+                .iter()
+                .map(|evaluations| evaluations.map(scalar_to_u256))
+                .collect(),
+            w: proof
+                .evaluation_proof
+                .w
+                .iter()
+                .copied()
+                .map(g1_to_sol)
+                .collect(),
+        },
+        rangeLength: proof.range_length as u64,
+        subpolynomialConstraintCount: proof.subpolynomial_constraint_count as u64,
+        postResultChallengeCount: proof.post_result_challenge_count as u64,
+    }
+}
+
+fn setup_to_sol(setup: &HyperKZGVerifierSetup) -> VerifierSetup {
+    VerifierSetup {
+        g1: g1_to_sol(setup.g1),
+        g2: g2_to_sol(setup.g2),
+        tauG2: g2_to_sol(setup.tau_g2),
+        numGenerators: U256::from(setup.num_generators),
+    }
+}
+
+/// Proves `plan` in Rust, checks that Rust accepts the proof and returns the case for the Solidity verifier.
+fn query_proof_case(plan: DynProofPlan) -> QueryProofCase {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(16, &mut test_rng());
+    let table_ref: TableRef = "namespace.table".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<HyperKZGCommitmentEvaluationProof>::new_from_table(
+        table_ref,
+        owned_table([
+            boolean("a", [true, false, true, true, false, true, false]),
+            bigint("b", [1, 2, 3, -4, 5, i64::MAX, i64::MIN]),
+            tinyint("c", [-1, 2, -3, 4, 5, i8::MIN, i8::MAX]),
+            smallint("d", [10_i16, -20, 30, 40, 50, 60, 70]),
+            int("e", [100, 200, -300, 400, 500, 600, 700]),
+            int128("f", [7, 8, 9, -10, 11, i128::MAX, i128::MIN]),
+        ]),
+        0,
+        prover_setup.as_slice(),
+    );
+    let plan = EVMProofPlan::try_new::<BNScalar>(plan).unwrap();
+    let (proof, result) = QueryProof::<HyperKZGCommitmentEvaluationProof>::new(
+        &plan,
+        &accessor,
+        &prover_setup.as_slice(),
+    );
+    let sol_proof = proof_to_sol(&proof);
+    let verification_hash = proof
+        .verify(&plan, &accessor, result.clone(), &&verifier_setup)
+        .unwrap()
+        .verification_hash;
+    QueryProofCase {
+        plan: Bytes::copy_from_slice(plan.bytes()),
+        result: result_to_sol(&result),
+        proof: sol_proof,
+        tableLengths: plan
+            .get_table_references()
+            .into_iter()
+            .map(|table_ref| U256::from(accessor.get_length(table_ref)))
+            .collect(),
+        columnCommitments: plan
+            .get_column_references()
+            .into_iter()
+            .map(|column_ref| g1_to_sol(accessor.get_commitment(column_ref).0.into_affine()))
+            .collect(),
+        setup: setup_to_sol(&verifier_setup),
+        expectedVerificationHash: B256::from(verification_hash),
+    }
+}
+
+fn column(name: &str, column_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::new_column(ColumnRef::new(
+        "namespace.table".parse().unwrap(),
+        name.into(),
+        column_type,
+    ))
+}
+
+fn aliased(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr,
+        alias: alias.into(),
+    }
+}
+
+/// `SELECT b, c, d * e AS de, f FROM namespace.table WHERE a OR b = 3 AND NOT c = 5`
+fn filter_plan() -> DynProofPlan {
+    let selection = DynProofExpr::try_new_or(
+        column("a", ColumnType::Boolean),
+        DynProofExpr::try_new_and(
+            DynProofExpr::try_new_equals(
+                column("b", ColumnType::BigInt),
+                DynProofExpr::new_literal(LiteralValue::BigInt(3)),
+            )
+            .unwrap(),
+            DynProofExpr::try_new_not(
+                DynProofExpr::try_new_equals(
+                    column("c", ColumnType::TinyInt),
+                    DynProofExpr::new_literal(LiteralValue::TinyInt(5)),
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    DynProofPlan::Filter(FilterExec::new(
+        vec![
+            aliased(column("b", ColumnType::BigInt), "b"),
+            aliased(column("c", ColumnType::TinyInt), "c"),
+            aliased(
+                DynProofExpr::try_new_multiply(
+                    column("d", ColumnType::SmallInt),
+                    column("e", ColumnType::Int),
+                )
+                .unwrap(),
+                "de",
+            ),
+            aliased(column("f", ColumnType::Int128), "f"),
+        ],
+        TableExpr {
+            table_ref: "namespace.table".parse().unwrap(),
+        },
+        selection,
+    ))
+}
+
+/// `SELECT a, NOT a AS not_a, d + e AS d_plus_e, e - 7 AS e_minus_seven FROM namespace.table`
+fn projection_plan() -> DynProofPlan {
+    DynProofPlan::Projection(ProjectionExec::new(
+        vec![
+            aliased(column("a", ColumnType::Boolean), "a"),
+            aliased(
+                DynProofExpr::try_new_not(column("a", ColumnType::Boolean)).unwrap(),
+                "not_a",
+            ),
+            aliased(
+                DynProofExpr::try_new_add(
+                    column("d", ColumnType::SmallInt),
+                    column("e", ColumnType::Int),
+                )
+                .unwrap(),
+                "d_plus_e",
+            ),
+            aliased(
+                DynProofExpr::try_new_subtract(
+                    column("e", ColumnType::Int),
+                    DynProofExpr::new_literal(LiteralValue::Int(7)),
+                )
+                .unwrap(),
+                "e_minus_seven",
+            ),
+        ],
+        TableExpr {
+            table_ref: "namespace.table".parse().unwrap(),
+        },
+    ))
+}
+
+/// `SELECT SUM(e) AS sum_e FROM namespace.table WHERE a`, which only Rust can verify
+fn aggregate_plan() -> DynProofPlan {
+    DynProofPlan::Aggregate(AggregateExec::new(
+        vec![aliased(
+            DynProofExpr::new_aggregate(AggregationOperator::Sum, column("e", ColumnType::Int)),
+            "sum_e",
+        )],
+        TableExpr {
+            table_ref: "namespace.table".parse().unwrap(),
+        },
+        column("a", ColumnType::Boolean),
+    ))
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_can_verify_query_proofs_in_solidity() {
+    for plan in [filter_plan(), projection_plan()] {
+        let query_proof_case = query_proof_case(plan);
+        ForgeScript::new(
+            "./sol_src/tests/QueryProofTest.t.sol",
+            "rustTestWeCanVerifyQueryProofsFromRust",
+        )
+        .arg(&query_proof_case)
+        .execute()
+        .unwrap();
+
+        // A different result is rejected
+        let mut tampered_case = query_proof_case.clone();
+        tampered_case.result[0].values[0] += I256::ONE;
+        assert!(matches!(
+            ForgeScript::new(
+                "./sol_src/tests/QueryProofTest.t.sol",
+                "rustTestWeCanVerifyQueryProofsFromRust",
+            )
+            .arg(&tampered_case)
+            .execute(),
+            Err(ForgeScriptError::SolidityError { .. })
+        ));
+
+        // A different proof is rejected
+        let mut tampered_case = query_proof_case;
+        tampered_case.proof.pcsProofEvaluations[0] += U256::from(1);
+        assert!(matches!(
+            ForgeScript::new(
+                "./sol_src/tests/QueryProofTest.t.sol",
+                "rustTestWeCanVerifyQueryProofsFromRust",
+            )
+            .arg(&tampered_case)
+            .execute(),
+            Err(ForgeScriptError::SolidityError { .. })
+        ));
+    }
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_cannot_verify_unsupported_plans_in_solidity() {
+    let query_proof_case = query_proof_case(aggregate_plan());
+    assert!(matches!(
+        ForgeScript::new(
+            "./sol_src/tests/QueryProofTest.t.sol",
+            "rustTestWeCanVerifyQueryProofsFromRust",
+        )
+        .arg(&query_proof_case)
+        .execute(),
+        Err(ForgeScriptError::SolidityError { .. })
+    ));
+}
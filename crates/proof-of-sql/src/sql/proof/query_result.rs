@@ -19,6 +19,11 @@ pub enum QueryError {
     /// Decoding errors other than overflow and invalid string.
     #[snafu(display("Miscellaneous decoding error"))]
     MiscellaneousDecodingError,
+    /// The query divided by zero on a row that it keeps.
+    /// This does not mean that the verification failed.
+    /// This just means that the database could not respond with a result.
+    #[snafu(display("Division by zero"))]
+    DivisionByZero,
     /// Miscellaneous evaluation error.
     #[snafu(display("Miscellaneous evaluation error"))]
     MiscellaneousEvaluationError,
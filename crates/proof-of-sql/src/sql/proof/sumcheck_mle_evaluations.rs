@@ -2,7 +2,8 @@ use super::SumcheckRandomScalars;
 use crate::base::{
     map::{IndexMap, IndexSet},
     polynomial::{
-        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
+        compute_rho_eval, compute_truncated_lagrange_basis_inner_product,
+        compute_truncated_lagrange_basis_sum,
     },
     scalar::Scalar,
 };
@@ -17,6 +18,10 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     ///     `x_i = 1` if `i < length;`
     ///         = 0, otherwise
     pub one_evaluations: IndexMap<usize, S>,
+    /// The evaluation (at the random point generated by sumcheck) of an MLE `{x_i}` where
+    ///     `x_i = i` if `i < length;`
+    ///         = 0, otherwise
+    pub rho_evaluations: IndexMap<usize, S>,
     /// The evaluation (at the random point generated by sumcheck) of the MLE formed from all ones with length 1.
     pub singleton_one_evaluation: S,
     /// The evaluation (at the random point generated by sumcheck) of the MLE formed from entrywise random scalars.
@@ -24,6 +29,9 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     /// This is used within sumcheck to establish that a given expression
     /// is zero across all entries.
     pub random_evaluation: S,
+    /// The evaluations (at the random point generated by sumcheck) of the mles committed to in the first round,
+    /// i.e. before the post-result challenges are drawn. These are batched together with the rest of the mles.
+    pub first_round_pcs_proof_evaluations: &'a [S],
     /// The evaluations (at the random point generated by sumcheck) of the mles that are evaluated by the inner product argument. These are batched together and checked by a single IPA.
     pub pcs_proof_evaluations: &'a [S],
 }
@@ -38,12 +46,16 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
     /// # Inputs
     /// - `evaluation_point` - the point, outputted by sumcheck, at which to evaluate the MLEs
     /// - `sumcheck_random_scalars` - the random scalars used to batch the evaluations that are proven via IPA
+    /// - `first_round_pcs_proof_evaluations` - the evaluations of the MLEs committed to in the first round
     /// - `pcs_proof_evaluations` - the evaluations of the MLEs that are proven via IPA
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         range_length: usize,
         one_evaluation_lengths: impl IntoIterator<Item = usize>,
+        rho_evaluation_lengths: impl IntoIterator<Item = usize>,
         evaluation_point: &[S],
         sumcheck_random_scalars: &SumcheckRandomScalars<S>,
+        first_round_pcs_proof_evaluations: &'a [S],
         pcs_proof_evaluations: &'a [S],
     ) -> Self {
         assert_eq!(
@@ -67,12 +79,20 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
                 )
             })
             .collect();
+        let unique_rho_evaluation_lengths: IndexSet<usize> =
+            rho_evaluation_lengths.into_iter().collect();
+        let rho_evaluations = unique_rho_evaluation_lengths
+            .iter()
+            .map(|&length| (length, compute_rho_eval(length, evaluation_point)))
+            .collect();
         let singleton_one_evaluation = compute_truncated_lagrange_basis_sum(1, evaluation_point);
         Self {
             num_sumcheck_variables: evaluation_point.len(),
             one_evaluations,
+            rho_evaluations,
             singleton_one_evaluation,
             random_evaluation,
+            first_round_pcs_proof_evaluations,
             pcs_proof_evaluations,
         }
     }
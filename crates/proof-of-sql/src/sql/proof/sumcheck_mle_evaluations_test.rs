@@ -16,8 +16,10 @@ fn we_can_track_the_evaluation_of_mles_used_within_sumcheck() {
     let evals = SumcheckMleEvaluations::new(
         3,
         [3, 3],
+        [2],
         &evaluation_point,
         &sumcheck_random_scalars,
+        &[],
         &pcs_proof_evaluations,
     );
     let expected_eval = (Curve25519Scalar::one() - evaluation_point[0])
@@ -42,4 +44,10 @@ fn we_can_track_the_evaluation_of_mles_used_within_sumcheck() {
         *evals.one_evaluations.values().next().unwrap(),
         expected_eval
     );
+
+    let expected_eval = (evaluation_point[0]) * (Curve25519Scalar::one() - evaluation_point[1]);
+    assert_eq!(
+        *evals.rho_evaluations.values().next().unwrap(),
+        expected_eval
+    );
 }
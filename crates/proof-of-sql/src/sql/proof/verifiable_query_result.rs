@@ -2,15 +2,11 @@ use super::{ProofPlan, QueryData, QueryProof, QueryResult};
 use crate::{
     base::{
         commitment::CommitmentEvaluationProof,
-        database::{
-            ColumnField, ColumnType, CommitmentAccessor, DataAccessor, OwnedColumn, OwnedTable,
-        },
+        database::{CommitmentAccessor, DataAccessor, OwnedTable},
         proof::ProofError,
-        scalar::Scalar,
     },
     utils::log,
 };
-use alloc::vec;
 use serde::{Deserialize, Serialize};
 
 /// The result of an sql query along with a proof that the query is valid. The
@@ -93,18 +89,6 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
         // a query must have at least one result column; if not, it should
         // have been rejected at the parsing stage.
 
-        // handle the empty case
-        let table_refs = expr.get_table_references();
-        if table_refs
-            .into_iter()
-            .all(|table_ref| accessor.get_length(table_ref) == 0)
-        {
-            return VerifiableQueryResult {
-                result: None,
-                proof: None,
-            };
-        }
-
         let (proof, res) = QueryProof::new(expr, accessor, setup);
 
         log::log_memory_usage("End");
@@ -121,7 +105,7 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
     /// Note: a verified result can still respresent an error (e.g. overflow), but it is a verified
     /// error.
     ///
-    /// Note: This does NOT transform the result!
+    /// Note: This does NOT apply any postprocessing to the result!
     #[tracing::instrument(name = "VerifiableQueryResult::verify", level = "info", skip_all)]
     pub fn verify(
         self,
@@ -137,54 +121,15 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
                     table,
                     verification_hash,
                 } = proof.verify(expr, accessor, result, setup)?;
+                let table = table.try_coerce_with_fields(expr.get_intermediate_result_fields())?;
                 Ok(QueryData {
-                    table: table.try_coerce_with_fields(expr.get_column_result_fields())?,
+                    table: expr.finalize_result(table)?,
                     verification_hash,
                 })
             }
-            (None, None)
-                if expr
-                    .get_table_references()
-                    .into_iter()
-                    .all(|table_ref| accessor.get_length(table_ref) == 0) =>
-            {
-                let result_fields = expr.get_column_result_fields();
-                make_empty_query_result(&result_fields)
-            }
             _ => Err(ProofError::VerificationError {
                 error: "Proof does not match result: at least one is missing",
             })?,
         }
     }
 }
-
-fn make_empty_query_result<S: Scalar>(result_fields: &[ColumnField]) -> QueryResult<S> {
-    let table = OwnedTable::try_new(
-        result_fields
-            .iter()
-            .map(|field| {
-                (
-                    field.name(),
-                    match field.data_type() {
-                        ColumnType::Boolean => OwnedColumn::Boolean(vec![]),
-                        ColumnType::TinyInt => OwnedColumn::TinyInt(vec![]),
-                        ColumnType::SmallInt => OwnedColumn::SmallInt(vec![]),
-                        ColumnType::Int => OwnedColumn::Int(vec![]),
-                        ColumnType::BigInt => OwnedColumn::BigInt(vec![]),
-                        ColumnType::Int128 => OwnedColumn::Int128(vec![]),
-                        ColumnType::Decimal75(precision, scale) => {
-                            OwnedColumn::Decimal75(precision, scale, vec![])
-                        }
-                        ColumnType::Scalar => OwnedColumn::Scalar(vec![]),
-                        ColumnType::VarChar => OwnedColumn::VarChar(vec![]),
-                        ColumnType::TimestampTZ(tu, tz) => OwnedColumn::TimestampTZ(tu, tz, vec![]),
-                    },
-                )
-            })
-            .collect(),
-    )?;
-    Ok(QueryData {
-        table,
-        verification_hash: Default::default(),
-    })
-}
@@ -19,7 +19,7 @@ use serde::Serialize;
 /// Will panic if:
 /// - The verification of `res` does not succeed, causing the assertion `assert!(res.verify(...).is_ok())` to fail.
 /// - `res.proof` is `None`, causing `res.proof.as_ref().unwrap()` to panic.
-/// - Attempting to modify `pcs_proof_evaluations` or `commitments` if `res_p.proof` is `None`, leading to a panic on `unwrap()`.
+/// - Attempting to modify `pcs_proof_evaluations`, `commitments` or their first round counterparts if `res_p.proof` is `None`, leading to a panic on `unwrap()`.
 /// - `fake_accessor.update_offset` fails, causing a panic if it is designed to do so in the implementation.
 pub fn exercise_verification(
     res: &VerifiableQueryResult<InnerProductProof>,
@@ -43,12 +43,28 @@ pub fn exercise_verification(
     assert!(res_p.verify(expr, accessor, &()).is_err());
 
     // try changing MLE evaluations
+    for i in 0..proof.first_round_pcs_proof_evaluations.len() {
+        let mut res_p = res.clone();
+        res_p
+            .proof
+            .as_mut()
+            .unwrap()
+            .first_round_pcs_proof_evaluations[i] += Curve25519Scalar::one();
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+    }
     for i in 0..proof.pcs_proof_evaluations.len() {
         let mut res_p = res.clone();
         res_p.proof.as_mut().unwrap().pcs_proof_evaluations[i] += Curve25519Scalar::one();
         assert!(res_p.verify(expr, accessor, &()).is_err());
     }
 
+    // try changing the division by zero counts
+    for i in 0..proof.division_by_zero_counts.len() {
+        let mut res_p = res.clone();
+        res_p.proof.as_mut().unwrap().division_by_zero_counts[i] += 1;
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+    }
+
     // try changing intermediate commitments
     let commit_p = RistrettoPoint::compute_commitments(
         &[CommittableColumn::BigInt(&[
@@ -59,6 +75,12 @@ pub fn exercise_verification(
         &(),
     )[0];
 
+    for i in 0..proof.first_round_commitments.len() {
+        let mut res_p = res.clone();
+        res_p.proof.as_mut().unwrap().first_round_commitments[i] = commit_p;
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+    }
+
     for i in 0..proof.commitments.len() {
         let mut res_p = res.clone();
         res_p.proof.as_mut().unwrap().commitments[i] = commit_p;
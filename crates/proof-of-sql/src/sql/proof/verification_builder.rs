@@ -10,7 +10,13 @@ pub struct VerificationBuilder<'a, S: Scalar> {
     subpolynomial_multipliers: &'a [S],
     sumcheck_evaluation: S,
     bit_distributions: &'a [BitDistribution],
+    division_by_zero_counts: &'a [usize],
+    /// The evaluations of the indicators of the rows with a zero divisor that are yet to be
+    /// checked by a plan, see [`Self::take_division_by_zero_indicator_evaluations`].
+    division_by_zero_indicator_evaluations: Vec<S>,
     consumed_one_evaluations: usize,
+    consumed_rho_evaluations: usize,
+    consumed_first_round_pcs_proof_mles: usize,
     consumed_pcs_proof_mles: usize,
     produced_subpolynomials: usize,
     /// The challenges used in creation of the constraints in the proof.
@@ -22,6 +28,7 @@ pub struct VerificationBuilder<'a, S: Scalar> {
     /// challenge is the last entry in the vector.
     post_result_challenges: Vec<S>,
     one_evaluation_length_queue: Vec<usize>,
+    rho_evaluation_length_queue: Vec<usize>,
     subpolynomial_max_multiplicands: usize,
 }
 
@@ -30,26 +37,34 @@ impl<'a, S: Scalar> VerificationBuilder<'a, S> {
         clippy::missing_panics_doc,
         reason = "The only possible panic is from the assertion comparing lengths, which is clear from context."
     )]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         generator_offset: usize,
         mle_evaluations: SumcheckMleEvaluations<'a, S>,
         bit_distributions: &'a [BitDistribution],
+        division_by_zero_counts: &'a [usize],
         subpolynomial_multipliers: &'a [S],
         post_result_challenges: Vec<S>,
         one_evaluation_length_queue: Vec<usize>,
+        rho_evaluation_length_queue: Vec<usize>,
         subpolynomial_max_multiplicands: usize,
     ) -> Self {
         Self {
             mle_evaluations,
             generator_offset,
             bit_distributions,
+            division_by_zero_counts,
+            division_by_zero_indicator_evaluations: Vec::new(),
             subpolynomial_multipliers,
             sumcheck_evaluation: S::zero(),
             consumed_one_evaluations: 0,
+            consumed_rho_evaluations: 0,
+            consumed_first_round_pcs_proof_mles: 0,
             consumed_pcs_proof_mles: 0,
             produced_subpolynomials: 0,
             post_result_challenges,
             one_evaluation_length_queue,
+            rho_evaluation_length_queue,
             subpolynomial_max_multiplicands,
         }
     }
@@ -73,6 +88,25 @@ impl<'a, S: Scalar> VerificationBuilder<'a, S> {
             .expect("One evaluation not found"))
     }
 
+    /// Consume the evaluation of a rho evaluation
+    ///
+    /// # Panics
+    /// It should never panic, as the length of the rho evaluation is guaranteed to be present
+    pub fn try_consume_rho_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.consumed_rho_evaluations;
+        let length = self
+            .rho_evaluation_length_queue
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewRhoLengths)?;
+        self.consumed_rho_evaluations += 1;
+        Ok(*self
+            .mle_evaluations
+            .rho_evaluations
+            .get(&length)
+            .expect("Rho evaluation not found"))
+    }
+
     pub fn generator_offset(&self) -> usize {
         self.generator_offset
     }
@@ -100,6 +134,27 @@ impl<'a, S: Scalar> VerificationBuilder<'a, S> {
             .collect()
     }
 
+    /// Consume the evaluation of an MLE committed to in the first round, i.e. before the post-result challenges
+    pub fn try_consume_first_round_mle_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.consumed_first_round_pcs_proof_mles;
+        self.consumed_first_round_pcs_proof_mles += 1;
+        self.mle_evaluations
+            .first_round_pcs_proof_evaluations
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewMLEEvaluations)
+    }
+
+    /// Consume multiple first round MLE evaluations
+    pub fn try_consume_first_round_mle_evaluations(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<S>, ProofSizeMismatch> {
+        iter::repeat_with(|| self.try_consume_first_round_mle_evaluation())
+            .take(count)
+            .collect()
+    }
+
     /// Consume a bit distribution that describes which bits are constant
     /// and which bits varying in a column of data
     pub fn try_consume_bit_distribution(&mut self) -> Result<BitDistribution, ProofSizeMismatch> {
@@ -112,6 +167,30 @@ impl<'a, S: Scalar> VerificationBuilder<'a, S> {
         Ok(res)
     }
 
+    /// Consume the number of rows with a division by zero that a plan has checked
+    pub fn try_consume_division_by_zero_count(&mut self) -> Result<usize, ProofSizeMismatch> {
+        let (&res, rest) = self
+            .division_by_zero_counts
+            .split_first()
+            .ok_or(ProofSizeMismatch::TooFewDivisionByZeroCounts)?;
+        self.division_by_zero_counts = rest;
+        Ok(res)
+    }
+
+    /// Produce the evaluation of the indicator of the rows on which an expression divides by zero
+    ///
+    /// This is the counterpart of
+    /// [`FinalRoundBuilder::produce_division_by_zero_indicator`](super::FinalRoundBuilder::produce_division_by_zero_indicator).
+    pub fn produce_division_by_zero_indicator_evaluation(&mut self, eval: S) {
+        self.division_by_zero_indicator_evaluations.push(eval);
+    }
+
+    /// Take the evaluations of the indicators of the rows with a zero divisor that were produced
+    /// since they were last taken
+    pub fn take_division_by_zero_indicator_evaluations(&mut self) -> Vec<S> {
+        core::mem::take(&mut self.division_by_zero_indicator_evaluations)
+    }
+
     /// Produce the evaluation of a subpolynomial used in sumcheck
     pub fn try_produce_sumcheck_subpolynomial_evaluation(
         &mut self,
@@ -142,20 +221,26 @@ impl<'a, S: Scalar> VerificationBuilder<'a, S> {
         Ok(())
     }
 
-    #[allow(
-        clippy::missing_panics_doc,
-        reason = "The panic condition is clear due to the assertion that checks if the computation is completed."
-    )]
     /// Get the evaluation of the sumcheck polynomial at its randomly selected point
-    pub fn sumcheck_evaluation(&self) -> S {
-        assert!(self.completed());
-        self.sumcheck_evaluation
+    ///
+    /// Fails if the proof has elements that the proof plan did not consume, such as bit
+    /// distributions or division by zero counts.
+    pub fn try_sumcheck_evaluation(&self) -> Result<S, ProofSizeMismatch> {
+        if self.completed() {
+            Ok(self.sumcheck_evaluation)
+        } else {
+            Err(ProofSizeMismatch::UnconsumedProofElements)
+        }
     }
 
     /// Check that the verification builder is completely built up
     fn completed(&self) -> bool {
         self.bit_distributions.is_empty()
+            && self.division_by_zero_counts.is_empty()
+            && self.division_by_zero_indicator_evaluations.is_empty()
             && self.produced_subpolynomials == self.subpolynomial_multipliers.len()
+            && self.consumed_first_round_pcs_proof_mles
+                == self.mle_evaluations.first_round_pcs_proof_evaluations.len()
             && self.consumed_pcs_proof_mles == self.mle_evaluations.pcs_proof_evaluations.len()
             && self.post_result_challenges.is_empty()
     }
@@ -1,5 +1,8 @@
 use super::{SumcheckMleEvaluations, VerificationBuilder};
-use crate::{base::scalar::Curve25519Scalar, sql::proof::SumcheckSubpolynomialType};
+use crate::{
+    base::{proof::ProofSizeMismatch, scalar::Curve25519Scalar},
+    sql::proof::SumcheckSubpolynomialType,
+};
 use num_traits::Zero;
 
 #[test]
@@ -13,11 +16,45 @@ fn an_empty_sumcheck_polynomial_evaluates_to_zero() {
         mle_evaluations,
         &[][..],
         &[][..],
+        &[][..],
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        0,
+    );
+    assert_eq!(
+        builder.try_sumcheck_evaluation().unwrap(),
+        Curve25519Scalar::zero()
+    );
+}
+
+#[test]
+fn we_cannot_get_the_sumcheck_evaluation_with_an_unconsumed_division_by_zero_count() {
+    let mle_evaluations = SumcheckMleEvaluations {
+        num_sumcheck_variables: 1,
+        ..Default::default()
+    };
+    let mut builder = VerificationBuilder::<Curve25519Scalar>::new(
+        0,
+        mle_evaluations,
+        &[][..],
+        &[0, 0][..],
+        &[][..],
+        Vec::new(),
         Vec::new(),
         Vec::new(),
         0,
     );
-    assert_eq!(builder.sumcheck_evaluation(), Curve25519Scalar::zero());
+    assert_eq!(builder.try_consume_division_by_zero_count().unwrap(), 0);
+    assert!(matches!(
+        builder.try_sumcheck_evaluation(),
+        Err(ProofSizeMismatch::UnconsumedProofElements)
+    ));
+    assert_eq!(builder.try_consume_division_by_zero_count().unwrap(), 0);
+    assert_eq!(
+        builder.try_sumcheck_evaluation().unwrap(),
+        Curve25519Scalar::zero()
+    );
 }
 
 #[test]
@@ -34,9 +71,11 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         0,
         mle_evaluations,
         &[][..],
+        &[][..],
         &subpolynomial_multipliers,
         Vec::new(),
         Vec::new(),
+        Vec::new(),
         1,
     );
     builder
@@ -55,7 +94,10 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         .unwrap();
     let expected_sumcheck_evaluation = subpolynomial_multipliers[0] * Curve25519Scalar::from(2u64)
         + subpolynomial_multipliers[1] * Curve25519Scalar::from(3u64);
-    assert_eq!(builder.sumcheck_evaluation(), expected_sumcheck_evaluation);
+    assert_eq!(
+        builder.try_sumcheck_evaluation().unwrap(),
+        expected_sumcheck_evaluation
+    );
 }
 
 #[test]
@@ -65,12 +107,14 @@ fn we_can_consume_post_result_challenges_in_proof_builder() {
         SumcheckMleEvaluations::default(),
         &[][..],
         &[][..],
+        &[][..],
         vec![
             Curve25519Scalar::from(123),
             Curve25519Scalar::from(456),
             Curve25519Scalar::from(789),
         ],
         Vec::new(),
+        Vec::new(),
         0,
     );
     assert_eq!(
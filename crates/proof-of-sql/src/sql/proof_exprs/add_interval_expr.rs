@@ -0,0 +1,118 @@
+use super::{
+    timestamp_util::{check_timestamp, interval_units},
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{FinalRoundBuilder, VerificationBuilder},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, format};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::DateTimeField;
+use serde::{Deserialize, Serialize};
+
+/// Provable `expr + INTERVAL '...'` or `expr - INTERVAL '...'` of timestamps
+///
+/// Only intervals of a fixed length are supported, which are a constant number of time units
+/// of the timestamps. So the result is linear in the timestamps and needs no proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddIntervalExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    /// The number of time units to add
+    pub(crate) units: i64,
+}
+
+impl AddIntervalExpr {
+    /// Create an expression that adds `units` time units to the timestamps `expr`
+    pub fn new(expr: Box<DynProofExpr>, units: i64) -> Self {
+        Self { expr, units }
+    }
+
+    /// The number of time units in an interval of `value` fields that is added to values of
+    /// `from_type`
+    pub(crate) fn try_units(
+        value: i64,
+        field: DateTimeField,
+        from_type: ColumnType,
+    ) -> ConversionResult<i64> {
+        let time_unit = check_timestamp("Interval arithmetic", from_type)?;
+        interval_units(value, field, time_unit).ok_or_else(|| ConversionError::Unprovable {
+            error: format!("INTERVAL '{value} {field}' is not a whole number of {time_unit}"),
+        })
+    }
+}
+
+impl ProofExpr for AddIntervalExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[tracing::instrument(name = "AddIntervalExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let res = add_units(alloc, &column, self.units);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "AddIntervalExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let res = add_units(alloc, &column, self.units);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self.expr.verifier_evaluate(builder, accessor, one_eval)?;
+        Ok(eval + S::from(self.units) * one_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Adds `units` to every timestamp of `column`
+fn add_units<'a, S: Scalar>(alloc: &'a Bump, column: &Column<'a, S>, units: i64) -> Column<'a, S> {
+    let units = S::from(units);
+    Column::Scalar(
+        alloc.alloc_slice_fill_iter(
+            column
+                .to_scalar_with_scaling(0)
+                .into_iter()
+                .map(|timestamp| timestamp + units),
+        ),
+    )
+}
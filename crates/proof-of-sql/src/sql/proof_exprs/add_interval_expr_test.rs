@@ -0,0 +1,164 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+// select a + interval '1 day' as a, a - interval '90 minute' as b, a + interval '2000 microsecond' as c from sxt.t
+#[test]
+fn we_can_prove_adding_and_subtracting_intervals() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        utc,
+        [1_718_900_000_123, -1, 0],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                add_interval(column(t, "a", &accessor), 1, DateTimeField::Day),
+                "a",
+            ),
+            aliased_plan(
+                add_interval(column(t, "a", &accessor), -90, DateTimeField::Minute),
+                "b",
+            ),
+            aliased_plan(
+                add_interval(column(t, "a", &accessor), 2_000, DateTimeField::Microsecond),
+                "c",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_986_400_123, 86_399_999, 86_400_000],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_894_600_123, -5_400_001, -5_400_000],
+        ),
+        timestamptz(
+            "c",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_900_000_125, 1, 2],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where a + interval '1 week' <= '2024-06-20 00:00:00'
+#[test]
+fn we_can_prove_a_filter_on_a_timestamp_plus_an_interval() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        utc,
+        [1_718_236_799, 1_718_236_800, 1_718_236_801],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        lte(
+            add_interval(column(t, "a", &accessor), 1, DateTimeField::Week),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                utc,
+                1_718_841_600,
+            )),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        utc,
+        [1_718_236_799, 1_718_236_800],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_add_intervals_that_are_not_a_whole_number_of_time_units() {
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1]),
+        timestamptz("b", PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::utc(), [1]),
+        bigint("c", [1_i64]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (name, value, field) in [
+        ("a", 1, DateTimeField::Month),
+        ("a", 1, DateTimeField::Year),
+        ("a", 1, DateTimeField::Quarter),
+        ("a", 1, DateTimeField::Millisecond),
+        ("b", i64::MAX, DateTimeField::Second),
+    ] {
+        assert!(matches!(
+            DynProofExpr::try_new_add_interval(column(t, name, &accessor), value, field),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+    assert!(matches!(
+        DynProofExpr::try_new_add_interval(column(t, "c", &accessor), 1, DateTimeField::Day),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_interval_addition_using_result_evaluate() {
+    let alloc = Bump::new();
+    let timezone = PoSQLTimeZone::new(3_600);
+    let data = table([borrowed_timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        timezone,
+        [1_718_900_000, -1],
+        &alloc,
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let expr = add_interval(column(t, "a", &accessor), -2, DateTimeField::Hour);
+    assert_eq!(
+        expr.data_type(),
+        ColumnType::TimestampTZ(PoSQLTimeUnit::Second, timezone)
+    );
+    assert_eq!(
+        expr.result_evaluate(&alloc, &data),
+        Column::Scalar(&[1_718_892_800_i64, -7_201].map(TestScalar::from))
+    );
+}
@@ -16,9 +16,9 @@ use serde::{Deserialize, Serialize};
 /// Provable numerical `+` / `-` expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddSubtractExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
-    is_subtract: bool,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+    pub(crate) is_subtract: bool,
 }
 
 impl AddSubtractExpr {
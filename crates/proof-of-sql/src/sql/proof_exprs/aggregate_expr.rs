@@ -1,7 +1,10 @@
 use super::{DynProofExpr, ProofExpr};
 use crate::{
     base::{
-        database::{Column, ColumnRef, ColumnType, Table},
+        database::{
+            try_avg_column_type, try_percentile_cont_column_type, Column, ColumnRef, ColumnType,
+            Table,
+        },
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
@@ -11,7 +14,7 @@ use crate::{
 };
 use alloc::boxed::Box;
 use bumpalo::Bump;
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, Percentile};
 use serde::{Deserialize, Serialize};
 
 /// Provable aggregate expression
@@ -19,8 +22,8 @@ use serde::{Deserialize, Serialize};
 /// Currently it doesn't do much since aggregation logic is implemented elsewhere
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AggregateExpr {
-    op: AggregationOperator,
-    expr: Box<DynProofExpr>,
+    pub(crate) op: AggregationOperator,
+    pub(crate) expr: Box<DynProofExpr>,
 }
 
 impl AggregateExpr {
@@ -28,15 +31,39 @@ impl AggregateExpr {
     pub fn new(op: AggregationOperator, expr: Box<DynProofExpr>) -> Self {
         Self { op, expr }
     }
+
+    /// Get the aggregation operator
+    pub fn op(&self) -> AggregationOperator {
+        self.op
+    }
+
+    /// Get the expression being aggregated
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
 }
 
 impl ProofExpr for AggregateExpr {
     // Remove the count method
     fn data_type(&self) -> ColumnType {
         match self.op {
-            AggregationOperator::Count => ColumnType::BigInt,
-            AggregationOperator::Sum => self.expr.data_type(),
-            _ => todo!("Aggregation operator not supported here yet"),
+            AggregationOperator::Count | AggregationOperator::CountDistinct => ColumnType::BigInt,
+            AggregationOperator::Sum
+            | AggregationOperator::Max
+            | AggregationOperator::Min
+            | AggregationOperator::PercentileDisc(_) => self.expr.data_type(),
+            AggregationOperator::Avg => {
+                try_avg_column_type(self.expr.data_type()).expect("Failed to average column type")
+            }
+            AggregationOperator::Median => {
+                try_percentile_cont_column_type(self.expr.data_type(), Percentile::MEDIAN)
+                    .expect("Failed to interpolate column type")
+            }
+            AggregationOperator::PercentileCont(percentile) => {
+                try_percentile_cont_column_type(self.expr.data_type(), percentile)
+                    .expect("Failed to interpolate column type")
+            }
+            AggregationOperator::First => todo!("Aggregation operator not supported here yet"),
         }
     }
 
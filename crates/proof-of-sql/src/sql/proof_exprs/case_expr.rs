@@ -0,0 +1,299 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{try_case_column_types, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{
+            final_round_evaluate_branch_division_by_zero, verify_branch_division_by_zero,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable `CASE WHEN condition THEN then_result ELSE else_result END` expression
+///
+/// The result is proven to be `condition * then_result + (1 - condition) * else_result`, where
+/// both branches are scaled to the scale of the result.
+///
+/// A division by zero in a branch only counts on the rows on which the branch is taken,
+/// so that `CASE` can guard against it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr {
+    pub(crate) condition: Box<DynProofExpr>,
+    pub(crate) then_result: Box<DynProofExpr>,
+    pub(crate) else_result: Box<DynProofExpr>,
+}
+
+impl CaseExpr {
+    /// Create a new `CASE` expression
+    pub fn new(
+        condition: Box<DynProofExpr>,
+        then_result: Box<DynProofExpr>,
+        else_result: Box<DynProofExpr>,
+    ) -> Self {
+        Self {
+            condition,
+            then_result,
+            else_result,
+        }
+    }
+
+    /// The scalars of a branch, scaled to the scale of the result
+    fn scaled_scalars<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        branch: &DynProofExpr,
+        column: Column<'a, S>,
+    ) -> &'a [S] {
+        let result_scale = self.data_type().scale().unwrap_or(0);
+        let branch_scale = branch.data_type().scale().unwrap_or(0);
+        alloc.alloc_slice_copy(&column.to_scalar_with_scaling(result_scale - branch_scale))
+    }
+
+    /// The scale factor of a branch, see [`Self::scaled_scalars`]
+    ///
+    /// # Panics
+    /// Panics if the branch has a larger scale than the result, which type checking rules out.
+    fn scale_factor<S: Scalar>(&self, branch: &DynProofExpr) -> S {
+        let result_scale = self.data_type().scale().unwrap_or(0);
+        let branch_scale = branch.data_type().scale().unwrap_or(0);
+        S::pow10(
+            u8::try_from(result_scale - branch_scale).expect("the result has the largest scale"),
+        )
+    }
+
+    /// Picks the entries of the result from the columns of the branches along with their scaled
+    /// scalars, see [`Self::scaled_scalars`].
+    ///
+    /// Returns the result along with its scalars.
+    fn result_column<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        condition: &'a [bool],
+        (then_column, then_scalars): (Column<'a, S>, &'a [S]),
+        (else_column, else_scalars): (Column<'a, S>, &'a [S]),
+    ) -> (Column<'a, S>, &'a [S]) {
+        let n = condition.len();
+        let scalars: &'a [S] = alloc.alloc_slice_fill_with(n, |i| {
+            if condition[i] {
+                then_scalars[i]
+            } else {
+                else_scalars[i]
+            }
+        });
+        let column = match (self.data_type(), then_column, else_column) {
+            (ColumnType::Boolean, Column::Boolean(then_values), Column::Boolean(else_values)) => {
+                Column::Boolean(alloc.alloc_slice_fill_with(n, |i| {
+                    if condition[i] {
+                        then_values[i]
+                    } else {
+                        else_values[i]
+                    }
+                }))
+            }
+            (
+                ColumnType::VarChar,
+                Column::VarChar((then_values, _)),
+                Column::VarChar((else_values, _)),
+            ) => Column::VarChar((
+                alloc.alloc_slice_fill_with(n, |i| {
+                    if condition[i] {
+                        then_values[i]
+                    } else {
+                        else_values[i]
+                    }
+                }),
+                scalars,
+            )),
+            (
+                ColumnType::TimestampTZ(tu, tz),
+                Column::TimestampTZ(_, _, then_values),
+                Column::TimestampTZ(_, _, else_values),
+            ) => Column::TimestampTZ(
+                tu,
+                tz,
+                alloc.alloc_slice_fill_with(n, |i| {
+                    if condition[i] {
+                        then_values[i]
+                    } else {
+                        else_values[i]
+                    }
+                }),
+            ),
+            (ColumnType::TinyInt, _, _) => Column::TinyInt(integers(alloc, scalars)),
+            (ColumnType::SmallInt, _, _) => Column::SmallInt(integers(alloc, scalars)),
+            (ColumnType::Int, _, _) => Column::Int(integers(alloc, scalars)),
+            (ColumnType::BigInt, _, _) => Column::BigInt(integers(alloc, scalars)),
+            (ColumnType::Int128, _, _) => Column::Int128(integers(alloc, scalars)),
+            (ColumnType::Decimal75(precision, scale), _, _) => {
+                Column::Decimal75(precision, scale, scalars)
+            }
+            _ => Column::Scalar(scalars),
+        };
+        (column, scalars)
+    }
+}
+
+/// Converts the scalars of an integer result back to integers
+///
+/// # Panics
+/// Panics if a scalar does not fit into `T`, which can't happen since every entry is taken from
+/// a branch whose type fits into the result type.
+fn integers<'a, S: Scalar + TryInto<T>, T: Copy>(alloc: &'a Bump, scalars: &[S]) -> &'a [T] {
+    alloc.alloc_slice_fill_with(scalars.len(), |i| {
+        scalars[i]
+            .try_into()
+            .unwrap_or_else(|_| panic!("entry fits into the result type"))
+    })
+}
+
+impl ProofExpr for CaseExpr {
+    fn data_type(&self) -> ColumnType {
+        try_case_column_types(self.then_result.data_type(), self.else_result.data_type())
+            .expect("Failed to pick between column types")
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let condition = self
+            .condition
+            .result_evaluate(alloc, table)
+            .as_boolean()
+            .expect("condition is not boolean");
+        let then_column = self.then_result.result_evaluate(alloc, table);
+        let else_column = self.else_result.result_evaluate(alloc, table);
+        let then_scalars = self.scaled_scalars(alloc, &self.then_result, then_column);
+        let else_scalars = self.scaled_scalars(alloc, &self.else_result, else_column);
+        self.result_column(
+            alloc,
+            condition,
+            (then_column, then_scalars),
+            (else_column, else_scalars),
+        )
+        .0
+    }
+
+    #[tracing::instrument(name = "CaseExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let condition = self
+            .condition
+            .prover_evaluate(builder, alloc, table)
+            .as_boolean()
+            .expect("condition is not boolean");
+        let condition_indicators = builder.take_division_by_zero_indicators();
+        let then_column = self.then_result.prover_evaluate(builder, alloc, table);
+        let then_indicators = builder.take_division_by_zero_indicators();
+        let else_column = self.else_result.prover_evaluate(builder, alloc, table);
+        let else_indicators = builder.take_division_by_zero_indicators();
+        for indicator in condition_indicators {
+            builder.produce_division_by_zero_indicator(indicator);
+        }
+        let then_scalars = self.scaled_scalars(alloc, &self.then_result, then_column);
+        let else_scalars = self.scaled_scalars(alloc, &self.else_result, else_column);
+        let (res, scalars) = self.result_column(
+            alloc,
+            condition,
+            (then_column, then_scalars),
+            (else_column, else_scalars),
+        );
+
+        // result
+        builder.produce_intermediate_mle(scalars);
+
+        // subpolynomial: result - else - condition * then + condition * else
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(scalars)]),
+                (-S::one(), vec![Box::new(else_scalars)]),
+                (-S::one(), vec![Box::new(condition), Box::new(then_scalars)]),
+                (S::one(), vec![Box::new(condition), Box::new(else_scalars)]),
+            ],
+        );
+
+        // divisions by zero only count in the branch that is taken
+        final_round_evaluate_branch_division_by_zero(
+            builder,
+            alloc,
+            &then_indicators,
+            condition,
+            true,
+        );
+        final_round_evaluate_branch_division_by_zero(
+            builder,
+            alloc,
+            &else_indicators,
+            condition,
+            false,
+        );
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let condition = self
+            .condition
+            .verifier_evaluate(builder, accessor, one_eval)?;
+        let condition_indicator_evals = builder.take_division_by_zero_indicator_evaluations();
+        let then_eval = self
+            .then_result
+            .verifier_evaluate(builder, accessor, one_eval)?
+            * self.scale_factor::<S>(&self.then_result);
+        let then_indicator_evals = builder.take_division_by_zero_indicator_evaluations();
+        let else_eval = self
+            .else_result
+            .verifier_evaluate(builder, accessor, one_eval)?
+            * self.scale_factor::<S>(&self.else_result);
+        let else_indicator_evals = builder.take_division_by_zero_indicator_evaluations();
+        for indicator_eval in condition_indicator_evals {
+            builder.produce_division_by_zero_indicator_evaluation(indicator_eval);
+        }
+
+        // result
+        let result_eval = builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: result - else - condition * then + condition * else
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            result_eval - else_eval - condition * then_eval + condition * else_eval,
+            2,
+        )?;
+
+        // divisions by zero only count in the branch that is taken
+        verify_branch_division_by_zero(builder, &then_indicator_evals, condition, true)?;
+        verify_branch_division_by_zero(builder, &else_indicator_evals, condition, false)?;
+
+        Ok(result_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.condition.get_column_references(columns);
+        self.then_result.get_column_references(columns);
+        self.else_result.get_column_references(columns);
+    }
+}
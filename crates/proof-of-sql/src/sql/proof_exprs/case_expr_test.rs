@@ -0,0 +1,271 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTable, OwnedTableTestAccessor,
+            Table, TableTestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+#[test]
+fn we_can_prove_a_case_query_with_no_rows() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), int("b", [0; 0]), bigint("c", [0; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case(
+                gte(column(t, "a", &accessor), const_bigint(0)),
+                column(t, "b", &accessor),
+                column(t, "c", &accessor),
+            ),
+            "r",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("r", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_case_query_with_integer_branches_of_different_types() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, -2, 3, 0, -5]),
+        int("b", [10, 20, 30, 40, 50]),
+        bigint("c", [-100, -200, -300, -400, -500]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case(
+                gte(column(t, "a", &accessor), const_bigint(0)),
+                column(t, "b", &accessor),
+                column(t, "c", &accessor),
+            ),
+            "r",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("r", [10, -200, 30, 40, -500])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_case_query_with_branches_of_different_scales() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        boolean("a", [true, false, true, false]),
+        decimal75("b", 10, 2, [125, -50, 300, 0]),
+        bigint("c", [1, 2, 3, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case(
+                column(t, "a", &accessor),
+                column(t, "b", &accessor),
+                column(t, "c", &accessor),
+            ),
+            "r",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("r", 21, 2, [125, 200, 300, 400])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_nested_case_query_on_strings() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        varchar("b", ["ab", "cd", "ef", "gh", "ij"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    // CASE WHEN a <= 2 THEN b WHEN a = 4 THEN 'four' ELSE 'other' END
+    let ast = filter(
+        vec![aliased_plan(
+            case(
+                lte(column(t, "a", &accessor), const_bigint(2)),
+                column(t, "b", &accessor),
+                case(
+                    equal(column(t, "a", &accessor), const_bigint(4)),
+                    const_varchar("four"),
+                    const_varchar("other"),
+                ),
+            ),
+            "r",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("r", ["ab", "cd", "other", "four", "other"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_query_filtering_by_a_boolean_case() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        boolean("b", [true, false, true, false, true]),
+        boolean("c", [false, true, true, false, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        case(
+            gte(column(t, "a", &accessor), const_bigint(3)),
+            column(t, "b", &accessor),
+            column(t, "c", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2, 3, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_a_case_expr_with_mismatched_types() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([boolean("a", [true]), bigint("b", [1]), varchar("c", ["1"])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert!(DynProofExpr::try_new_case(
+        column(t, "a", &accessor),
+        column(t, "b", &accessor),
+        column(t, "c", &accessor),
+    )
+    .is_err());
+    assert!(DynProofExpr::try_new_case(
+        column(t, "b", &accessor),
+        column(t, "b", &accessor),
+        column(t, "b", &accessor),
+    )
+    .is_err());
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            bigint("b", dist.sample_iter(&mut rng).take(n)),
+            int(
+                "c",
+                dist.sample_iter(&mut rng)
+                    .take(n)
+                    .map(|v| i32::try_from(v).unwrap()),
+            ),
+        ]);
+
+        // Generate random value to compare with
+        let threshold = dist.sample(&mut rng);
+
+        // Create and verify proof
+        let t = "sxt.t".parse().unwrap();
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t,
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![
+                aliased_plan(column(t, "a", &accessor), "a"),
+                aliased_plan(
+                    case(
+                        lte(column(t, "a", &accessor), const_bigint(threshold)),
+                        column(t, "b", &accessor),
+                        column(t, "c", &accessor),
+                    ),
+                    "r",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_a, expected_r): (Vec<_>, Vec<_>) = multizip((
+            data["a"].i64_iter(),
+            data["b"].i64_iter(),
+            data["c"].i32_iter(),
+        ))
+        .map(|(a, b, c)| (*a, if *a <= threshold { *b } else { i64::from(*c) }))
+        .multiunzip();
+        let expected_result = owned_table([bigint("a", expected_a), bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(121);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_case_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data: Table<Curve25519Scalar> = table([
+        borrowed_boolean("a", [true, false, false, true], &alloc),
+        borrowed_smallint("b", [1_i16, 2, 3, 4], &alloc),
+        borrowed_int("c", [-1, -2, -3, -4], &alloc),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = TableTestAccessor::<InnerProductProof>::new_from_table(t, data.clone(), 0, ());
+    let case_expr: DynProofExpr = case(
+        column(t, "a", &accessor),
+        column(t, "b", &accessor),
+        column(t, "c", &accessor),
+    );
+    let res = case_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Int(&[1, -2, -3, 4]);
+    assert_eq!(res, expected_res);
+}
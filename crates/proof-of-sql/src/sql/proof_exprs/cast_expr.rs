@@ -0,0 +1,332 @@
+use super::{
+    divide_and_modulo_expr::{
+        divide_and_modulo_scalars, prover_evaluate_abs, verifier_evaluate_abs,
+    },
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+
+/// The largest number of decimal digits a value can be shifted by
+const MAX_SCALE_SHIFT: u8 = 75;
+/// The bit size of the values supported by the sign gadget
+const SIGN_GADGET_BITS: u32 = 128;
+
+/// Provable `CAST` between numeric types or between timestamps of different time units
+///
+/// All of these types are treated as scaled integers, where integers have a scale of 0 and
+/// timestamps the number of decimal digits of their time unit. Let `a` be the value to cast and
+/// `k` the difference between the scale of the target type and the scale of `a`.
+/// 1. If `k >= 0`, the result is `a * 10^k`, which needs no proof.
+/// 2. If `k < 0`, the result is `a / 10^-k` rounded toward zero. The prover commits to the quotient
+///    `q` and the remainder `r` and we prove that `q * 10^-k + r = a`, `|r| < 10^-k` and that `r` is
+///    either zero or has the same sign as `a`.
+///
+/// If the target type can not hold all the values of the source type, e.g. in `CAST(a AS TINYINT)`
+/// for an `INT` column `a`, we also prove that the result is within the range of the target type.
+/// The inequalities are proven with the sign gadget, which bounds the source types of such casts.
+/// Since expressions are also evaluated on rows that are later filtered out, a value out of range
+/// fails the verification even if its row is not part of the result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastExpr {
+    pub(crate) from_expr: Box<DynProofExpr>,
+    pub(crate) to_type: ColumnType,
+}
+
+impl CastExpr {
+    /// Create a `CAST` of `from_expr` to `to_type`
+    pub fn new(from_expr: Box<DynProofExpr>, to_type: ColumnType) -> Self {
+        Self { from_expr, to_type }
+    }
+
+    /// Returns true if casting `from_type` to `to_type` is provable
+    pub(crate) fn is_supported(from_type: ColumnType, to_type: ColumnType) -> bool {
+        let Some(shift) = scale_shift(from_type, to_type) else {
+            return false;
+        };
+        let limit = BigInt::one() << SIGN_GADGET_BITS;
+        let (_, to_max) = value_range(to_type);
+        let scaled_max_abs = scaled_max_abs_value(from_type, shift);
+        shift.unsigned_abs() <= u16::from(MAX_SCALE_SHIFT)
+            && (shift >= 0 || max_abs_value(from_type) < limit)
+            && (scaled_max_abs <= to_max || scaled_max_abs + to_max < limit)
+    }
+
+    /// # Panics
+    /// Panics if the cast is not supported, which `DynProofExpr::try_new_cast` rules out.
+    fn scale_shift(&self) -> i16 {
+        scale_shift(self.from_expr.data_type(), self.to_type).expect("Cast is supported")
+    }
+
+    /// Returns true if some values of the source type are out of the range of the target type
+    fn needs_range_check(&self) -> bool {
+        scaled_max_abs_value(self.from_expr.data_type(), self.scale_shift())
+            > value_range(self.to_type).1
+    }
+
+    /// The range of the target type as scalars
+    ///
+    /// # Panics
+    /// Panics if the range does not fit into the scalar field, which it does for supported casts.
+    fn scalar_range<S: Scalar>(&self) -> (S, S) {
+        let (min, max) = value_range(self.to_type);
+        (
+            S::try_from(min).expect("The range fits into a scalar"),
+            S::try_from(max).expect("The range fits into a scalar"),
+        )
+    }
+}
+
+/// The difference between the scales of the types if a value of `from_type` can be cast to `to_type`
+fn scale_shift(from_type: ColumnType, to_type: ColumnType) -> Option<i16> {
+    let is_scaled_integer = |column_type: ColumnType| {
+        column_type.is_integer() || matches!(column_type, ColumnType::Decimal75(_, _))
+    };
+    let is_compatible = matches!(
+        (from_type, to_type),
+        (ColumnType::TimestampTZ(_, _), ColumnType::TimestampTZ(_, _))
+    ) || (is_scaled_integer(from_type) && is_scaled_integer(to_type));
+    if is_compatible {
+        Some(i16::from(to_type.scale()?) - i16::from(from_type.scale()?))
+    } else {
+        None
+    }
+}
+
+/// The smallest and the largest value of a type supported by casts
+///
+/// # Panics
+/// Panics if the type is not supported by casts.
+fn value_range(column_type: ColumnType) -> (BigInt, BigInt) {
+    match column_type {
+        ColumnType::Decimal75(precision, _) => {
+            let max = BigInt::from(10).pow(u32::from(precision.value())) - BigInt::one();
+            (-max.clone(), max)
+        }
+        ColumnType::TinyInt
+        | ColumnType::SmallInt
+        | ColumnType::Int
+        | ColumnType::BigInt
+        | ColumnType::Int128
+        | ColumnType::TimestampTZ(_, _) => {
+            let min = -(BigInt::one() << (column_type.bit_size() - 1));
+            (min.clone(), -min - 1)
+        }
+        _ => panic!("Casts do not support {column_type}"),
+    }
+}
+
+/// The largest absolute value of a type supported by casts
+fn max_abs_value(column_type: ColumnType) -> BigInt {
+    -value_range(column_type).0
+}
+
+/// The largest absolute value of a type supported by casts after shifting its scale by `shift`
+fn scaled_max_abs_value(column_type: ColumnType, shift: i16) -> BigInt {
+    let scale_factor = BigInt::from(10).pow(u32::from(shift.unsigned_abs()));
+    if shift >= 0 {
+        max_abs_value(column_type) * scale_factor
+    } else {
+        max_abs_value(column_type) / scale_factor
+    }
+}
+
+impl ProofExpr for CastExpr {
+    fn data_type(&self) -> ColumnType {
+        self.to_type
+    }
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let from_column: Column<'a, S> = self.from_expr.result_evaluate(alloc, table);
+        let shift = self.scale_shift();
+        if shift >= 0 {
+            Column::Scalar(alloc.alloc_slice_copy(&from_column.to_scalar_with_scaling(shift as i8)))
+        } else {
+            let from_values = from_column.to_scalar_with_scaling(0);
+            let divisor = alloc
+                .alloc_slice_fill_copy(from_values.len(), S::pow10(shift.unsigned_abs() as u8));
+            Column::Scalar(divide_and_modulo_scalars(alloc, &from_values, divisor).0)
+        }
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.cast_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let from_column: Column<'a, S> = self.from_expr.prover_evaluate(builder, alloc, table);
+        let from_values: &'a [S] = alloc.alloc_slice_copy(&from_column.to_scalar_with_scaling(0));
+        let shift = self.scale_shift();
+        let num_rows = table.num_rows();
+        let result: &'a [S] = if shift >= 0 {
+            let scale_factor = S::pow10(shift as u8);
+            alloc.alloc_slice_fill_with(num_rows, |i| from_values[i] * scale_factor)
+        } else {
+            let scale_factor = S::pow10(shift.unsigned_abs() as u8);
+            let divisor: &'a [S] = alloc.alloc_slice_fill_copy(num_rows, scale_factor);
+
+            // quotient and remainder
+            let (quotient, remainder) = divide_and_modulo_scalars(alloc, from_values, divisor);
+            builder.produce_intermediate_mle(quotient);
+            builder.produce_intermediate_mle(remainder);
+
+            // subpolynomial: quotient * divisor + remainder - from_value
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (scale_factor, vec![Box::new(quotient)]),
+                    (S::one(), vec![Box::new(remainder)]),
+                    (-S::one(), vec![Box::new(from_values)]),
+                ],
+            );
+
+            // sign(from_value) and abs_remainder, i.e. the remainder with that sign removed
+            let from_sign = prover_evaluate_sign(
+                builder,
+                alloc,
+                from_values,
+                #[cfg(test)]
+                false,
+            );
+            let abs_remainder = prover_evaluate_abs(builder, alloc, remainder, from_sign);
+
+            // sign(abs_remainder) == 0 and sign(divisor - 1 - abs_remainder) == 0
+            let gap: &'a [S] =
+                alloc.alloc_slice_fill_with(num_rows, |i| scale_factor - S::ONE - abs_remainder[i]);
+            prover_evaluate_sign(
+                builder,
+                alloc,
+                abs_remainder,
+                #[cfg(test)]
+                false,
+            );
+            prover_evaluate_sign(
+                builder,
+                alloc,
+                gap,
+                #[cfg(test)]
+                false,
+            );
+            quotient
+        };
+
+        // sign(result - min) == 0 and sign(max - result) == 0
+        if self.needs_range_check() {
+            let (min, max): (S, S) = self.scalar_range();
+            let above_min: &'a [S] = alloc.alloc_slice_fill_with(num_rows, |i| result[i] - min);
+            let below_max: &'a [S] = alloc.alloc_slice_fill_with(num_rows, |i| max - result[i]);
+            prover_evaluate_sign(
+                builder,
+                alloc,
+                above_min,
+                #[cfg(test)]
+                false,
+            );
+            prover_evaluate_sign(
+                builder,
+                alloc,
+                below_max,
+                #[cfg(test)]
+                false,
+            );
+        }
+
+        log::log_memory_usage("End");
+
+        Column::Scalar(result)
+    }
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let from_value = self
+            .from_expr
+            .verifier_evaluate(builder, accessor, one_eval)?;
+        let shift = self.scale_shift();
+        let result = if shift >= 0 {
+            from_value * S::pow10(shift as u8)
+        } else {
+            let divisor = S::pow10(shift.unsigned_abs() as u8);
+
+            // quotient and remainder
+            let quotient = builder.try_consume_mle_evaluation()?;
+            let remainder = builder.try_consume_mle_evaluation()?;
+
+            // subpolynomial: quotient * divisor + remainder - from_value
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                quotient * divisor + remainder - from_value,
+                1,
+            )?;
+
+            // sign(from_value) and abs_remainder
+            let from_sign = verifier_evaluate_sign(builder, from_value, one_eval)?;
+            let abs_remainder = verifier_evaluate_abs(builder, remainder, from_sign)?;
+
+            // sign(abs_remainder) == 0 and sign(divisor - 1 - abs_remainder) == 0
+            let gap = (divisor - S::ONE) * one_eval - abs_remainder;
+            let abs_remainder_sign = verifier_evaluate_sign(builder, abs_remainder, one_eval)?;
+            let gap_sign = verifier_evaluate_sign(builder, gap, one_eval)?;
+            if !abs_remainder_sign.is_zero() || !gap_sign.is_zero() {
+                return Err(ProofError::VerificationError {
+                    error: "remainder is out of range",
+                });
+            }
+            quotient
+        };
+
+        // sign(result - min) == 0 and sign(max - result) == 0
+        if self.needs_range_check() {
+            let (min, max): (S, S) = self.scalar_range();
+            let above_min_sign =
+                verifier_evaluate_sign(builder, result - min * one_eval, one_eval)?;
+            let below_max_sign =
+                verifier_evaluate_sign(builder, max * one_eval - result, one_eval)?;
+            if !above_min_sign.is_zero() || !below_max_sign.is_zero() {
+                return Err(ProofError::VerificationError {
+                    error: "cast value is out of range",
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.from_expr.get_column_references(columns);
+    }
+}
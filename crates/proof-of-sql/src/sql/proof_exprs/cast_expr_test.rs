@@ -0,0 +1,219 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+// select cast(a as bigint) as a, cast(b as decimal(12, 2)) as b, cast(c as decimal(7, 4)) as c from sxt.t
+#[test]
+fn we_can_prove_widening_casts() {
+    let data = owned_table([
+        tinyint("a", [i8::MIN, -1, 0, i8::MAX]),
+        int("b", [i32::MIN, -5, 0, 12]),
+        decimal75("c", 3, 1, [-999_i64, -15, 0, 7]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(cast(column(t, "a", &accessor), ColumnType::BigInt), "a"),
+            aliased_plan(cast(column(t, "b", &accessor), decimal_type(12, 2)), "b"),
+            aliased_plan(cast(column(t, "c", &accessor), decimal_type(7, 4)), "c"),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-128_i64, -1, 0, 127]),
+        decimal75("b", 12, 2, [i64::from(i32::MIN) * 100, -500, 0, 1200]),
+        decimal75("c", 7, 4, [-999_000_i64, -15_000, 0, 7000]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as int) as a, cast(a as decimal(4, 1)) as b from sxt.t
+#[test]
+fn we_can_prove_casts_of_decimals_to_a_smaller_scale_rounding_toward_zero() {
+    let data = owned_table([decimal75("a", 6, 2, [1234_i64, -1234, 99, -99, 0, -100])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(cast(column(t, "a", &accessor), ColumnType::Int), "a"),
+            aliased_plan(cast(column(t, "a", &accessor), decimal_type(4, 1)), "b"),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("a", [12, -12, 0, 0, 0, -1]),
+        decimal75("b", 4, 1, [123_i64, -123, 9, -9, 0, -10]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as timestamp(0)) as a, cast(a as timestamp(6)) as b from sxt.t
+#[test]
+fn we_can_prove_casts_between_time_units() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        PoSQLTimeZone::utc(),
+        [1_625_072_400_123, -1_500, 0],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                cast(
+                    column(t, "a", &accessor),
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+                ),
+                "a",
+            ),
+            aliased_plan(
+                cast(
+                    column(t, "a", &accessor),
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Microsecond, PoSQLTimeZone::utc()),
+                ),
+                "b",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_625_072_400, -1, 0],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Microsecond,
+            PoSQLTimeZone::utc(),
+            [1_625_072_400_123_000, -1_500_000, 0],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as tinyint) as a from sxt.t where a <= 100
+#[test]
+fn we_can_prove_a_narrowing_cast_of_values_in_range() {
+    let data = owned_table([int("a", [-128, 5, 100, 127])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            cast(column(t, "a", &accessor), ColumnType::TinyInt),
+            "a",
+        )],
+        tab(t),
+        lte(column(t, "a", &accessor), const_int(100)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([tinyint("a", [-128_i8, 5, 100])]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as tinyint) as a from sxt.t where a <= 100
+#[test]
+fn we_cannot_prove_a_narrowing_cast_of_values_out_of_range_even_if_filtered_out() {
+    let data = owned_table([int("a", [-128, 5, 100, 128])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![aliased_plan(
+            cast(column(t, "a", &accessor), ColumnType::TinyInt),
+            "a",
+        )],
+        tab(t),
+        lte(column(t, "a", &accessor), const_int(100)),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_cast_unsupported_types() {
+    let data = owned_table([
+        bigint("a", [1_i64]),
+        varchar("b", ["1"]),
+        boolean("c", [true]),
+        timestamptz("d", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (name, to_type) in [
+        ("a", ColumnType::VarChar),
+        ("a", ColumnType::Boolean),
+        ("b", ColumnType::BigInt),
+        ("c", ColumnType::Int),
+        ("d", ColumnType::BigInt),
+        (
+            "a",
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        ),
+    ] {
+        assert!(matches!(
+            DynProofExpr::try_new_cast(column(t, name, &accessor), to_type),
+            Err(ConversionError::DataTypeMismatch { .. })
+        ));
+    }
+}
+
+#[test]
+fn casting_to_the_same_type_is_a_no_op() {
+    let data = owned_table([bigint("a", [1_i64])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert_eq!(
+        DynProofExpr::try_new_cast(column(t, "a", &accessor), ColumnType::BigInt).unwrap(),
+        column(t, "a", &accessor)
+    );
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_cast_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_decimal75("a", 5, 2, [1999, -1999, 50, 0], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let downscale = cast(column(t, "a", &accessor), ColumnType::SmallInt);
+    let upscale = cast(column(t, "a", &accessor), decimal_type(10, 4));
+    assert_eq!(downscale.data_type(), ColumnType::SmallInt);
+    assert_eq!(
+        downscale.result_evaluate(&alloc, &data),
+        Column::Scalar(&[19, -19, 0, 0].map(TestScalar::from))
+    );
+    assert_eq!(
+        upscale.result_evaluate(&alloc, &data),
+        Column::Scalar(&[199_900, -199_900, 5000, 0].map(TestScalar::from))
+    );
+}
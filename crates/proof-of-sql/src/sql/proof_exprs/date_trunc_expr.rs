@@ -0,0 +1,148 @@
+use super::{
+    timestamp_util::{
+        check_timestamp, date_trunc, is_date_trunc_supported, ColumnArithmetic,
+        EvaluationArithmetic,
+    },
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{FinalRoundBuilder, VerificationBuilder},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, format};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+use serde::{Deserialize, Serialize};
+
+/// Provable `DATE_TRUNC('field', expr)`, the start of the year, month, week, day, hour, ... that a
+/// timestamp is in, where years, months, weeks and days start in the time zone of the timestamp
+///
+/// The timestamp is shifted to local time and divided into fields, see
+/// [`timestamp_util`](super::timestamp_util). Weeks start on Monday.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateTruncExpr {
+    pub(crate) field: DateTimeField,
+    pub(crate) expr: Box<DynProofExpr>,
+}
+
+impl DateTruncExpr {
+    /// Create a `DATE_TRUNC` of the timestamps `expr` to `field`
+    pub fn new(field: DateTimeField, expr: Box<DynProofExpr>) -> Self {
+        Self { field, expr }
+    }
+
+    /// The type of `DATE_TRUNC` to `field` of values of `from_type`, which is `from_type` itself
+    pub(crate) fn try_result_type(
+        field: DateTimeField,
+        from_type: ColumnType,
+    ) -> ConversionResult<ColumnType> {
+        check_timestamp("DATE_TRUNC", from_type)?;
+        if is_date_trunc_supported(field) {
+            Ok(from_type)
+        } else {
+            Err(ConversionError::Unprovable {
+                error: format!("DATE_TRUNC to {field} is not supported"),
+            })
+        }
+    }
+
+    /// The time unit and the time zone of the timestamps
+    ///
+    /// # Panics
+    /// Panics if `expr` is not a timestamp, which `DynProofExpr::try_new_date_trunc` rules out.
+    fn time_unit_and_zone(&self) -> (PoSQLTimeUnit, PoSQLTimeZone) {
+        let ColumnType::TimestampTZ(time_unit, timezone) = self.expr.data_type() else {
+            panic!("DATE_TRUNC truncates timestamps");
+        };
+        (time_unit, timezone)
+    }
+}
+
+impl ProofExpr for DateTruncExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let timestamps = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        Column::Scalar(
+            date_trunc(
+                &mut ColumnArithmetic::new(alloc),
+                timestamps,
+                self.field,
+                time_unit,
+                timezone,
+            )
+            .expect("Computing columns does not fail"),
+        )
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.date_trunc_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let timestamps = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        let result = date_trunc(
+            &mut ColumnArithmetic::new_prover(alloc, builder),
+            timestamps,
+            self.field,
+            time_unit,
+            timezone,
+        )
+        .expect("Proving columns does not fail");
+
+        log::log_memory_usage("End");
+
+        Column::Scalar(result)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let timestamps = self.expr.verifier_evaluate(builder, accessor, one_eval)?;
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        date_trunc(
+            &mut EvaluationArithmetic::new(builder, one_eval),
+            timestamps,
+            self.field,
+            time_unit,
+            timezone,
+        )
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
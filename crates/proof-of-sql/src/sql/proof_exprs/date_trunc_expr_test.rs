@@ -0,0 +1,367 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+const TIMESTAMPS: [i64; 8] = [
+    0,               // 1970-01-01 00:00:00, a Thursday
+    -1,              // 1969-12-31 23:59:59
+    951_868_799,     // 2000-02-29 23:59:59
+    1_709_251_199,   // 2024-02-29 23:59:59
+    4_107_542_400,   // 2100-03-01 00:00:00, 2100 is not a leap year
+    -11_676_096_001, // 1599-12-31 23:59:59
+    253_402_300_799, // 9999-12-31 23:59:59
+    1_718_900_000,   // 2024-06-20 16:13:20
+];
+
+// select date_trunc('year', a) as year, date_trunc('month', a) as month, ..., date_trunc('minute', a) as minute from sxt.t
+#[test]
+fn we_can_prove_date_trunc_to_calendar_and_fixed_length_fields() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz("a", PoSQLTimeUnit::Second, utc, TIMESTAMPS)]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let fields = [
+        ("year", DateTimeField::Year),
+        ("month", DateTimeField::Month),
+        ("week", DateTimeField::Week),
+        ("day", DateTimeField::Day),
+        ("hour", DateTimeField::Hour),
+        ("minute", DateTimeField::Minute),
+    ];
+    let ast = projection(
+        fields
+            .iter()
+            .map(|&(name, field)| aliased_plan(date_trunc(field, column(t, "a", &accessor)), name))
+            .collect(),
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "year",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                0,
+                -31_536_000,
+                946_684_800,
+                1_704_067_200,
+                4_102_444_800,
+                -11_707_632_000,
+                253_370_764_800,
+                1_704_067_200,
+            ],
+        ),
+        timestamptz(
+            "month",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                0,
+                -2_678_400,
+                949_363_200,
+                1_706_745_600,
+                4_107_542_400,
+                -11_678_774_400,
+                253_399_622_400,
+                1_717_200_000,
+            ],
+        ),
+        timestamptz(
+            "week",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                -259_200,
+                -259_200,
+                951_696_000,
+                1_708_905_600,
+                4_107_542_400,
+                -11_676_528_000,
+                253_401_868_800,
+                1_718_582_400,
+            ],
+        ),
+        timestamptz(
+            "day",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                0,
+                -86_400,
+                951_782_400,
+                1_709_164_800,
+                4_107_542_400,
+                -11_676_182_400,
+                253_402_214_400,
+                1_718_841_600,
+            ],
+        ),
+        timestamptz(
+            "hour",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                0,
+                -3_600,
+                951_865_200,
+                1_709_247_600,
+                4_107_542_400,
+                -11_676_099_600,
+                253_402_297_200,
+                1_718_899_200,
+            ],
+        ),
+        timestamptz(
+            "minute",
+            PoSQLTimeUnit::Second,
+            utc,
+            [
+                0,
+                -60,
+                951_868_740,
+                1_709_251_140,
+                4_107_542_400,
+                -11_676_096_060,
+                253_402_300_740,
+                1_718_899_980,
+            ],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select date_trunc('day', a) as a, date_trunc('month', b) as b from sxt.t
+#[test]
+fn we_can_prove_date_trunc_in_the_time_zone_of_the_timestamps() {
+    let ist = PoSQLTimeZone::new(19_800);
+    let pst = PoSQLTimeZone::new(-28_800);
+    let timestamps = [
+        0,
+        -1,
+        951_868_799,
+        1_709_251_199,
+        4_107_542_400,
+        -11_676_096_001,
+        1_718_900_000,
+    ];
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, ist, timestamps),
+        timestamptz("b", PoSQLTimeUnit::Second, pst, timestamps),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                date_trunc(DateTimeField::Day, column(t, "a", &accessor)),
+                "a",
+            ),
+            aliased_plan(
+                date_trunc(DateTimeField::Month, column(t, "b", &accessor)),
+                "b",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            ist,
+            [
+                -19_800,
+                -19_800,
+                951_849_000,
+                1_709_231_400,
+                4_107_522_600,
+                -11_676_115_800,
+                1_718_821_800,
+            ],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Second,
+            pst,
+            [
+                -2_649_600,
+                -2_649_600,
+                949_392_000,
+                1_706_774_400,
+                4_105_152_000,
+                -11_678_745_600,
+                1_717_228_800,
+            ],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select date_trunc('second', a) as a, date_trunc('microsecond', a) as b, date_trunc('day', a) as c from sxt.t
+#[test]
+fn we_can_prove_date_trunc_of_timestamps_with_fractional_seconds() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        utc,
+        [1_718_900_000_123, -1, 0],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                date_trunc(DateTimeField::Second, column(t, "a", &accessor)),
+                "a",
+            ),
+            aliased_plan(
+                date_trunc(DateTimeField::Microsecond, column(t, "a", &accessor)),
+                "b",
+            ),
+            aliased_plan(
+                date_trunc(DateTimeField::Day, column(t, "a", &accessor)),
+                "c",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_900_000_000, -1_000, 0],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_900_000_123, -1, 0],
+        ),
+        timestamptz(
+            "c",
+            PoSQLTimeUnit::Millisecond,
+            utc,
+            [1_718_841_600_000, -86_400_000, 0],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where date_trunc('day', a) = '2024-06-20 00:00:00'
+#[test]
+fn we_can_prove_a_filter_on_date_trunc() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        utc,
+        [
+            1_718_841_599,
+            1_718_841_600,
+            1_718_900_000,
+            1_718_927_999,
+            1_718_928_000,
+        ],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(
+            date_trunc(DateTimeField::Day, column(t, "a", &accessor)),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                utc,
+                1_718_841_600,
+            )),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        utc,
+        [1_718_841_600, 1_718_900_000, 1_718_927_999],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_date_trunc_to_unsupported_fields_or_non_timestamps() {
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1]),
+        bigint("b", [1_i64]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for field in [
+        DateTimeField::Quarter,
+        DateTimeField::DayOfWeek,
+        DateTimeField::DayOfYear,
+    ] {
+        assert!(matches!(
+            DynProofExpr::try_new_date_trunc(field, column(t, "a", &accessor)),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+    assert!(matches!(
+        DynProofExpr::try_new_date_trunc(DateTimeField::Day, column(t, "b", &accessor)),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_date_trunc_using_result_evaluate() {
+    let alloc = Bump::new();
+    let timezone = PoSQLTimeZone::new(3_600);
+    let data = table([borrowed_timestamptz(
+        "a",
+        PoSQLTimeUnit::Microsecond,
+        timezone,
+        [1_718_900_000_000_001, -1],
+        &alloc,
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let expr = date_trunc(DateTimeField::Hour, column(t, "a", &accessor));
+    assert_eq!(
+        expr.data_type(),
+        ColumnType::TimestampTZ(PoSQLTimeUnit::Microsecond, timezone)
+    );
+    assert_eq!(
+        expr.result_evaluate(&alloc, &data),
+        Column::Scalar(&[1_718_899_200_000_000_i64, -3_600_000_000].map(TestScalar::from))
+    );
+}
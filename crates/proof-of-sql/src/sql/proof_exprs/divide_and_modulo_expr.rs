@@ -0,0 +1,384 @@
+use super::{prover_evaluate_equals_zero, verifier_evaluate_equals_zero, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{try_divide_column_types, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+
+/// The largest precision of a scaled dividend. Any such value is at most `2^128` in absolute value,
+/// which is the range supported by the sign gadget.
+const MAX_DIVIDEND_PRECISION: u8 = 38;
+/// The largest precision of a divisor. This keeps `|q * b| < 2^128 * 10^37` well below half of the
+/// field modulus, so that `q * b + r = a` holds over the integers and not just in the field.
+const MAX_DIVISOR_PRECISION: u8 = 37;
+
+/// Provable numerical `/` and `%` expression
+///
+/// Let `a` be the dividend, scaled to the scale of the quotient, and `b` the divisor.
+/// The prover commits to the quotient `q` and the remainder `r` and we prove that
+/// 1. `q * b + r = a`,
+/// 2. `|r| < |b|` and `r` is either zero or has the same sign as `a`,
+///
+/// which uniquely determines `q` as `a / b` rounded toward zero and `r` as `a % b`.
+/// The inequalities are proven with the sign gadget, so all of `a`, `b` and `q` must lie within
+/// its range.
+///
+/// Division by zero is total: if `b = 0` then `q = 0` and `r = a`. This is necessary
+/// since expressions are also evaluated on rows that are later filtered out.
+/// The rows with a zero divisor are reported to the plan evaluating the expression, which proves
+/// that there are none among the rows it keeps. Otherwise, verifying the result fails with
+/// [`QueryError::DivisionByZero`](crate::sql::proof::QueryError::DivisionByZero).
+/// Quotients that do not fit into the result type, e.g. `i64::MIN / -1`, surface as
+/// [`QueryError::Overflow`](crate::sql::proof::QueryError::Overflow) when the result is verified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DivideAndModuloExpr {
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+    pub(crate) is_modulo: bool,
+}
+
+impl DivideAndModuloExpr {
+    /// Create numerical `/` or `%` expression
+    pub fn new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>, is_modulo: bool) -> Self {
+        Self {
+            lhs,
+            rhs,
+            is_modulo,
+        }
+    }
+
+    /// Returns the result type of `lhs / rhs` (or `lhs % rhs` if `is_modulo` is true)
+    /// if the operation is provable, and `None` otherwise.
+    pub(crate) fn try_result_type(
+        lhs: ColumnType,
+        rhs: ColumnType,
+        is_modulo: bool,
+    ) -> Option<ColumnType> {
+        let result_type = if is_modulo {
+            lhs.max_integer_type(&rhs)?
+        } else {
+            try_divide_column_types(lhs, rhs).ok()?
+        };
+        let rhs_precision = rhs.precision_value()?;
+        let scale_shift = Self::scale_shift(lhs, rhs, result_type);
+        let dividend_precision = i16::from(lhs.precision_value()?) + i16::from(scale_shift);
+        (scale_shift >= 0
+            && (lhs.is_integer() || dividend_precision <= i16::from(MAX_DIVIDEND_PRECISION))
+            && rhs_precision <= MAX_DIVISOR_PRECISION)
+            .then_some(result_type)
+    }
+
+    /// The power of ten the dividend has to be scaled by so that the quotient has the result scale.
+    fn scale_shift(lhs: ColumnType, rhs: ColumnType, result_type: ColumnType) -> i8 {
+        result_type.scale().unwrap_or(0) - lhs.scale().unwrap_or(0) + rhs.scale().unwrap_or(0)
+    }
+
+    fn dividend_scale_shift(&self) -> i8 {
+        Self::scale_shift(self.lhs.data_type(), self.rhs.data_type(), self.data_type())
+    }
+}
+
+/// Compute the quotients and remainders of the division of `lhs` by `rhs`,
+/// rounding toward zero. Division by zero yields a quotient of zero and a remainder of `lhs`.
+///
+/// # Panics
+/// Panics if a quotient or remainder does not fit into the scalar field, which can not happen
+/// since both are bounded by the dividend.
+pub(super) fn divide_and_modulo_scalars<'a, S: Scalar>(
+    alloc: &'a Bump,
+    lhs: &[S],
+    rhs: &[S],
+) -> (&'a [S], &'a [S]) {
+    let (quotients, remainders): (Vec<_>, Vec<_>) = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(&a, &b)| {
+            if b.is_zero() {
+                (S::ZERO, a)
+            } else {
+                let a: BigInt = a.into();
+                let b: BigInt = b.into();
+                (
+                    S::try_from(&a / &b).expect("Quotient is bounded by the dividend"),
+                    S::try_from(a % b).expect("Remainder is bounded by the dividend"),
+                )
+            }
+        })
+        .unzip();
+    (
+        alloc.alloc_slice_copy(&quotients),
+        alloc.alloc_slice_copy(&remainders),
+    )
+}
+
+/// Commit to `abs = value * (1 - 2 * sign)` and prove that it is computed correctly.
+pub(super) fn prover_evaluate_abs<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    values: &'a [S],
+    signs: &'a [bool],
+) -> &'a [S] {
+    let abs: &'a [S] =
+        alloc.alloc_slice_fill_with(
+            values.len(),
+            |i| if signs[i] { -values[i] } else { values[i] },
+        );
+    builder.produce_intermediate_mle(abs);
+
+    // subpolynomial: abs - value + 2 * value * sign
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(abs)]),
+            (-S::one(), vec![Box::new(values)]),
+            (S::TWO, vec![Box::new(values), Box::new(signs)]),
+        ],
+    );
+    abs
+}
+
+/// The counterpart of `prover_evaluate_abs`, returning the evaluation of `abs`.
+pub(super) fn verifier_evaluate_abs<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    value_eval: S,
+    sign_eval: S,
+) -> Result<S, ProofError> {
+    let abs_eval = builder.try_consume_mle_evaluation()?;
+
+    // subpolynomial: abs - value + 2 * value * sign
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        abs_eval - value_eval + S::TWO * value_eval * sign_eval,
+        2,
+    )?;
+    Ok(abs_eval)
+}
+
+impl ProofExpr for DivideAndModuloExpr {
+    fn data_type(&self) -> ColumnType {
+        Self::try_result_type(self.lhs.data_type(), self.rhs.data_type(), self.is_modulo)
+            .expect("Failed to divide column types")
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.result_evaluate(alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
+        let dividend = lhs_column.to_scalar_with_scaling(self.dividend_scale_shift());
+        let divisor = rhs_column.to_scalar_with_scaling(0);
+        let (quotient, remainder) = divide_and_modulo_scalars(alloc, &dividend, &divisor);
+        Column::Scalar(if self.is_modulo { remainder } else { quotient })
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.divide_and_modulo_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.prover_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.prover_evaluate(builder, alloc, table);
+        let scale_shift = self.dividend_scale_shift();
+        let dividend: &'a [S] =
+            alloc.alloc_slice_copy(&lhs_column.to_scalar_with_scaling(scale_shift));
+        let divisor: &'a [S] = alloc.alloc_slice_copy(&rhs_column.to_scalar_with_scaling(0));
+        let scale_factor = S::pow10(scale_shift.unsigned_abs());
+
+        // quotient and remainder
+        let (quotient, remainder) = divide_and_modulo_scalars(alloc, dividend, divisor);
+        builder.produce_intermediate_mle(quotient);
+        builder.produce_intermediate_mle(remainder);
+
+        // subpolynomial: quotient * divisor + remainder - dividend
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(quotient), Box::new(divisor)]),
+                (S::one(), vec![Box::new(remainder)]),
+                (-scale_factor, vec![Box::new(lhs_column)]),
+            ],
+        );
+
+        // divisor == 0
+        let divisor_is_zero =
+            prover_evaluate_equals_zero(table.num_rows(), builder, alloc, divisor);
+        builder.produce_division_by_zero_indicator(divisor_is_zero);
+        let divisor_is_not_zero: &'a [bool] =
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| !divisor_is_zero[i]);
+
+        // subpolynomial: divisor_is_zero * quotient
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(
+                S::one(),
+                vec![Box::new(divisor_is_zero), Box::new(quotient)],
+            )],
+        );
+
+        // sign(dividend), sign(divisor) and a range check of the quotient
+        let dividend_sign = prover_evaluate_sign(
+            builder,
+            alloc,
+            dividend,
+            #[cfg(test)]
+            false,
+        );
+        let divisor_sign = prover_evaluate_sign(
+            builder,
+            alloc,
+            divisor,
+            #[cfg(test)]
+            false,
+        );
+        prover_evaluate_sign(
+            builder,
+            alloc,
+            quotient,
+            #[cfg(test)]
+            false,
+        );
+
+        // abs_remainder, i.e. the remainder with the sign of the dividend removed, and abs_divisor
+        let abs_remainder = prover_evaluate_abs(builder, alloc, remainder, dividend_sign);
+        let abs_divisor = prover_evaluate_abs(builder, alloc, divisor, divisor_sign);
+
+        // gap = (1 - divisor_is_zero) * (abs_divisor - abs_remainder - 1)
+        let gap: &'a [S] = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            if divisor_is_zero[i] {
+                S::ZERO
+            } else {
+                abs_divisor[i] - abs_remainder[i] - S::ONE
+            }
+        });
+        builder.produce_intermediate_mle(gap);
+
+        // subpolynomial: gap - abs_divisor + abs_remainder - divisor_is_zero * abs_remainder + divisor_is_not_zero
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(gap)]),
+                (-S::one(), vec![Box::new(abs_divisor)]),
+                (S::one(), vec![Box::new(abs_remainder)]),
+                (
+                    -S::one(),
+                    vec![Box::new(divisor_is_zero), Box::new(abs_remainder)],
+                ),
+                (S::one(), vec![Box::new(divisor_is_not_zero)]),
+            ],
+        );
+
+        // sign(abs_remainder) == 0 and sign(gap) == 0
+        prover_evaluate_sign(
+            builder,
+            alloc,
+            abs_remainder,
+            #[cfg(test)]
+            false,
+        );
+        prover_evaluate_sign(
+            builder,
+            alloc,
+            gap,
+            #[cfg(test)]
+            false,
+        );
+
+        let res = Column::Scalar(if self.is_modulo { remainder } else { quotient });
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let lhs = self.lhs.verifier_evaluate(builder, accessor, one_eval)?;
+        let divisor = self.rhs.verifier_evaluate(builder, accessor, one_eval)?;
+        let dividend = lhs * S::pow10(self.dividend_scale_shift().unsigned_abs());
+
+        // quotient and remainder
+        let quotient = builder.try_consume_mle_evaluation()?;
+        let remainder = builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: quotient * divisor + remainder - dividend
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            quotient * divisor + remainder - dividend,
+            2,
+        )?;
+
+        // divisor == 0
+        let divisor_is_zero = verifier_evaluate_equals_zero(builder, divisor, one_eval)?;
+        builder.produce_division_by_zero_indicator_evaluation(divisor_is_zero);
+        let divisor_is_not_zero = one_eval - divisor_is_zero;
+
+        // subpolynomial: divisor_is_zero * quotient
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            divisor_is_zero * quotient,
+            2,
+        )?;
+
+        // sign(dividend), sign(divisor) and a range check of the quotient
+        let dividend_sign = verifier_evaluate_sign(builder, dividend, one_eval)?;
+        let divisor_sign = verifier_evaluate_sign(builder, divisor, one_eval)?;
+        verifier_evaluate_sign(builder, quotient, one_eval)?;
+
+        // abs_remainder and abs_divisor
+        let abs_remainder = verifier_evaluate_abs(builder, remainder, dividend_sign)?;
+        let abs_divisor = verifier_evaluate_abs(builder, divisor, divisor_sign)?;
+
+        // gap
+        let gap = builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: gap - abs_divisor + abs_remainder - divisor_is_zero * abs_remainder + divisor_is_not_zero
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            gap - abs_divisor + abs_remainder - divisor_is_zero * abs_remainder
+                + divisor_is_not_zero,
+            2,
+        )?;
+
+        // sign(abs_remainder) == 0 and sign(gap) == 0
+        let abs_remainder_sign = verifier_evaluate_sign(builder, abs_remainder, one_eval)?;
+        let gap_sign = verifier_evaluate_sign(builder, gap, one_eval)?;
+        if !abs_remainder_sign.is_zero() || !gap_sign.is_zero() {
+            return Err(ProofError::VerificationError {
+                error: "remainder is out of range",
+            });
+        }
+
+        Ok(if self.is_modulo { remainder } else { quotient })
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+}
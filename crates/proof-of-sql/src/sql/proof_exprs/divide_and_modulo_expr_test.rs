@@ -0,0 +1,310 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+
+// select a / b as q, a % b as r, c from sxt.t
+#[test]
+fn we_can_prove_integer_division_and_modulo_rounding_toward_zero() {
+    let data = owned_table([
+        bigint("a", [7_i64, -7, 7, -7, 0, 5, i64::MIN, 123]),
+        int("b", [2_i32, 2, -2, -2, 3, 7, 1, -1000]),
+        varchar("c", ["a", "b", "c", "d", "e", "f", "g", "h"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                modulo(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "r",
+            ),
+            col_expr_plan(t, "c", &accessor),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("q", [3_i64, -3, -3, 3, 0, 0, i64::MIN, 0]),
+        bigint("r", [1_i64, -1, 1, -1, 0, 5, 0, 123]),
+        varchar("c", ["a", "b", "c", "d", "e", "f", "g", "h"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / 4, a % 4 from sxt.t where a / 4 = 2
+#[test]
+fn we_can_prove_a_division_in_a_where_clause() {
+    let data = owned_table([smallint("a", [8_i16, 9, 10, 11, 12, -8, -9])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(divide(column(t, "a", &accessor), const_smallint(4)), "q"),
+            aliased_plan(modulo(column(t, "a", &accessor), const_smallint(4)), "r"),
+        ],
+        tab(t),
+        equal(
+            divide(column(t, "a", &accessor), const_smallint(4)),
+            const_smallint(2),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        smallint("q", [2_i16, 2, 2, 2]),
+        smallint("r", [0_i16, 1, 2, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t
+#[test]
+fn we_can_prove_a_decimal_division() {
+    let data = owned_table([
+        decimal75("a", 4, 1, [100_i64, -25, 10, 1]),
+        decimal75("b", 3, 2, [300_i64, 200, -3, 7]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![aliased_plan(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "q",
+        )],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // 10.0 / 3.00, -2.5 / 2.00, 1.0 / -0.03 and 0.1 / 0.07 with a scale of 6
+    let expected_res = owned_table([decimal75(
+        "q",
+        11,
+        6,
+        [3_333_333_i64, -1_250_000, -33_333_333, 1_428_571],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q, a % b as r from sxt.t
+#[test]
+fn we_cannot_prove_a_division_by_zero() {
+    let data = owned_table([bigint("a", [5_i64, -3, 4]), bigint("b", [0_i64, 2, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                modulo(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "r",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::DivisionByZero)
+    ));
+}
+
+// select a / b as q from sxt.t where b <> 0
+#[test]
+fn we_can_prove_a_division_whose_zero_divisors_are_filtered_out() {
+    let data = owned_table([
+        bigint("a", [5_i64, -3, 4, 9]),
+        bigint("b", [0_i64, 2, 0, -4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "q",
+        )],
+        tab(t),
+        not(equal(column(t, "b", &accessor), const_bigint(0))),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("q", [-1_i64, -2])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where a / b > 0
+#[test]
+fn we_cannot_prove_a_division_by_zero_in_a_where_clause() {
+    let data = owned_table([bigint("a", [5_i64, -3, 4]), bigint("b", [1_i64, 2, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gt(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            const_bigint(0),
+        ),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::DivisionByZero)
+    ));
+}
+
+// select case when b = 0 then 0 else a / b end as q, case when b <> 0 then a % b else a end as r from sxt.t
+#[test]
+fn we_can_guard_a_division_against_zero_divisors_with_a_case() {
+    let data = owned_table([bigint("a", [5_i64, -3, 4]), bigint("b", [0_i64, 2, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let b_is_zero = equal(column(t, "b", &accessor), const_bigint(0));
+    let ast = projection(
+        vec![
+            aliased_plan(
+                case(
+                    b_is_zero.clone(),
+                    const_bigint(0),
+                    divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                ),
+                "q",
+            ),
+            aliased_plan(
+                case(
+                    not(b_is_zero),
+                    modulo(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    column(t, "a", &accessor),
+                ),
+                "r",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("q", [0_i64, -1, 0]), bigint("r", [5_i64, -1, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+// select sum(a / b) as s from sxt.t where b <> 0
+#[test]
+fn we_can_aggregate_a_division_whose_zero_divisors_are_filtered_out() {
+    let data = owned_table([
+        bigint("a", [5_i64, -3, 4, 9]),
+        bigint("b", [0_i64, 2, 0, -4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = aggregate(
+        vec![sum_expr(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "s",
+        )],
+        tab(t),
+        not(equal(column(t, "b", &accessor), const_bigint(0))),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("s", [-3_i64])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as c from sxt.t
+#[test]
+fn result_expr_can_overflow() {
+    let data = owned_table([bigint("a", [i64::MIN, 4]), bigint("b", [-1_i64, 2])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: DynProofPlan = projection(
+        vec![aliased_plan(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "c",
+        )],
+        tab(t),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::Overflow)
+    ));
+}
+
+#[test]
+fn we_cannot_divide_or_take_the_modulo_of_unsupported_types() {
+    let data = owned_table([
+        decimal75("a", 2, 1, [1_i16]),
+        int128("b", [1_i128]),
+        varchar("c", ["1"]),
+        bigint("d", [1_i64]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    // Modulo is only supported for integers
+    assert!(matches!(
+        DynProofExpr::try_new_modulo(column(t, "a", &accessor), column(t, "d", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    // The divisor is too wide for the field
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(t, "d", &accessor), column(t, "b", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(t, "c", &accessor), column(t, "d", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_division_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_int("a", [7, -7, 9, 4], &alloc),
+        borrowed_tinyint("b", [2_i8, 2, 0, -3], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let quotient_expr = divide(column(t, "a", &accessor), column(t, "b", &accessor));
+    let remainder_expr = modulo(column(t, "a", &accessor), column(t, "b", &accessor));
+    assert_eq!(quotient_expr.data_type(), ColumnType::Int);
+    assert_eq!(
+        quotient_expr.result_evaluate(&alloc, &data),
+        Column::Scalar(&[3, -3, 0, -1].map(TestScalar::from))
+    );
+    assert_eq!(
+        remainder_expr.result_evaluate(&alloc, &data),
+        Column::Scalar(&[1, -1, 9, 1].map(TestScalar::from))
+    );
+}
@@ -1,10 +1,11 @@
 use super::{
-    AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr,
-    MultiplyExpr, NotExpr, OrExpr, ProofExpr,
+    AddIntervalExpr, AddSubtractExpr, AggregateExpr, AndExpr, CaseExpr, CastExpr, ColumnExpr,
+    DateTruncExpr, DivideAndModuloExpr, EqualsExpr, ExtractExpr, InListExpr, InequalityExpr,
+    LiteralExpr, MultiplyExpr, NotExpr, OrExpr, ProofExpr,
 };
 use crate::{
     base::{
-        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        database::{try_case_column_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
@@ -14,10 +15,10 @@ use crate::{
         proof::{FinalRoundBuilder, VerificationBuilder},
     },
 };
-use alloc::{boxed::Box, string::ToString};
+use alloc::{boxed::Box, string::ToString, vec::Vec};
 use bumpalo::Bump;
 use core::fmt::Debug;
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, DateTimeField};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::BinaryOperator;
 
@@ -39,10 +40,24 @@ pub enum DynProofExpr {
     Equals(EqualsExpr),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr),
+    /// Provable AST expression for an `IN` expression with a list of literals
+    InList(InListExpr),
+    /// Provable `CASE WHEN ... THEN ... ELSE ... END` expression
+    Case(CaseExpr),
     /// Provable numeric `+` / `-` expression
     AddSubtract(AddSubtractExpr),
     /// Provable numeric `*` expression
     Multiply(MultiplyExpr),
+    /// Provable numeric `/` / `%` expression
+    DivideAndModulo(DivideAndModuloExpr),
+    /// Provable `CAST` expression
+    Cast(CastExpr),
+    /// Provable `DATE_TRUNC` expression
+    DateTrunc(DateTruncExpr),
+    /// Provable `EXTRACT` expression
+    Extract(ExtractExpr),
+    /// Provable timestamp `+` / `-` interval expression
+    AddInterval(AddIntervalExpr),
     /// Provable aggregate expression
     Aggregate(AggregateExpr),
 }
@@ -85,11 +100,56 @@ impl DynProofExpr {
             })
         }
     }
-    /// Create a new inequality expression
+    /// Create a new `IN` expression
+    pub fn try_new_in_list(expr: DynProofExpr, list: Vec<LiteralValue>) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        if let Some(item) = list.iter().find(|item| {
+            !type_check_binary_operation(datatype, item.column_type(), &BinaryOperator::Eq)
+        }) {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: datatype.to_string(),
+                right_type: item.column_type().to_string(),
+            })
+        } else {
+            Ok(Self::InList(InListExpr::new(Box::new(expr), list)))
+        }
+    }
+    /// Create a new `CASE WHEN condition THEN then_result ELSE else_result END` expression
+    pub fn try_new_case(
+        condition: DynProofExpr,
+        then_result: DynProofExpr,
+        else_result: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        condition.check_data_type(ColumnType::Boolean)?;
+        let then_datatype = then_result.data_type();
+        let else_datatype = else_result.data_type();
+        if type_check_binary_operation(then_datatype, else_datatype, &BinaryOperator::Eq)
+            && try_case_column_types(then_datatype, else_datatype).is_ok()
+        {
+            Ok(Self::Case(CaseExpr::new(
+                Box::new(condition),
+                Box::new(then_result),
+                Box::new(else_result),
+            )))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: then_datatype.to_string(),
+                right_type: else_datatype.to_string(),
+            })
+        }
+    }
+    /// Create a new not equals expression, which is the negation of an equals expression and so
+    /// needs no additional proof
+    pub fn try_new_not_equals(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        Self::try_new_not(Self::try_new_equals(lhs, rhs)?)
+    }
+    /// Create a new inequality expression, which is `lhs < rhs` if `is_lt` and `lhs > rhs`
+    /// otherwise, or `lhs <= rhs` and `lhs >= rhs` respectively if it is not strict
     pub fn try_new_inequality(
         lhs: DynProofExpr,
         rhs: DynProofExpr,
-        is_lte: bool,
+        is_lt: bool,
+        is_strict: bool,
     ) -> ConversionResult<Self> {
         let lhs_datatype = lhs.data_type();
         let rhs_datatype = rhs.data_type();
@@ -97,7 +157,8 @@ impl DynProofExpr {
             Ok(Self::Inequality(InequalityExpr::new(
                 Box::new(lhs),
                 Box::new(rhs),
-                is_lte,
+                is_lt,
+                is_strict,
             )))
         } else {
             Err(ConversionError::DataTypeMismatch {
@@ -160,6 +221,77 @@ impl DynProofExpr {
         }
     }
 
+    /// Create a new divide expression
+    pub fn try_new_divide(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        Self::try_new_divide_and_modulo(lhs, rhs, false)
+    }
+
+    /// Create a new modulo expression
+    pub fn try_new_modulo(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        Self::try_new_divide_and_modulo(lhs, rhs, true)
+    }
+
+    fn try_new_divide_and_modulo(
+        lhs: DynProofExpr,
+        rhs: DynProofExpr,
+        is_modulo: bool,
+    ) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        if DivideAndModuloExpr::try_result_type(lhs_datatype, rhs_datatype, is_modulo).is_some() {
+            Ok(Self::DivideAndModulo(DivideAndModuloExpr::new(
+                Box::new(lhs),
+                Box::new(rhs),
+                is_modulo,
+            )))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: lhs_datatype.to_string(),
+                right_type: rhs_datatype.to_string(),
+            })
+        }
+    }
+
+    /// Create a new `CAST` expression, which is `expr` itself if it already has the type
+    pub fn try_new_cast(expr: DynProofExpr, to_type: ColumnType) -> ConversionResult<Self> {
+        let from_type = expr.data_type();
+        if from_type == to_type {
+            Ok(expr)
+        } else if CastExpr::is_supported(from_type, to_type) {
+            Ok(Self::Cast(CastExpr::new(Box::new(expr), to_type)))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: from_type.to_string(),
+                right_type: to_type.to_string(),
+            })
+        }
+    }
+
+    /// Create a new `DATE_TRUNC` expression
+    pub fn try_new_date_trunc(field: DateTimeField, expr: DynProofExpr) -> ConversionResult<Self> {
+        DateTruncExpr::try_result_type(field, expr.data_type())?;
+        Ok(Self::DateTrunc(DateTruncExpr::new(field, Box::new(expr))))
+    }
+
+    /// Create a new `EXTRACT` expression
+    pub fn try_new_extract(field: DateTimeField, expr: DynProofExpr) -> ConversionResult<Self> {
+        ExtractExpr::try_result_type(field, expr.data_type())?;
+        Ok(Self::Extract(ExtractExpr::new(field, Box::new(expr))))
+    }
+
+    /// Create a new expression adding an interval of `value` fields to the timestamps `expr`
+    pub fn try_new_add_interval(
+        expr: DynProofExpr,
+        value: i64,
+        field: DateTimeField,
+    ) -> ConversionResult<Self> {
+        let units = AddIntervalExpr::try_units(value, field, expr.data_type())?;
+        Ok(Self::AddInterval(AddIntervalExpr::new(
+            Box::new(expr),
+            units,
+        )))
+    }
+
     /// Create a new aggregate expression
     pub fn new_aggregate(op: AggregationOperator, expr: DynProofExpr) -> Self {
         Self::Aggregate(AggregateExpr::new(op, Box::new(expr)))
@@ -316,6 +316,33 @@ fn we_can_prove_an_equality_query_with_a_string_comparison() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_equality_and_inequality_of_two_string_columns() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        varchar("c", ["t", "ghi", "", "abc"]),
+        varchar("d", ["t", "gh", "", "ABC"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (where_clause, expected) in [
+        (
+            equal(column(t, "c", &accessor), column(t, "d", &accessor)),
+            [1, 3],
+        ),
+        (
+            not_equal(column(t, "c", &accessor), column(t, "d", &accessor)),
+            [2, 4],
+        ),
+    ] {
+        let ast = filter(cols_expr_plan(t, &["a"], &accessor), tab(t), where_clause);
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, owned_table([bigint("a", expected)]));
+    }
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
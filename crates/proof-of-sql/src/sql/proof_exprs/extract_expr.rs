@@ -0,0 +1,148 @@
+use super::{
+    timestamp_util::{
+        check_timestamp, extract, is_extract_supported, ColumnArithmetic, EvaluationArithmetic,
+    },
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{FinalRoundBuilder, VerificationBuilder},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, format};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+use serde::{Deserialize, Serialize};
+
+/// Provable `EXTRACT(field FROM expr)`, a field such as the year, the month or the hour of a
+/// timestamp in the time zone of the timestamp, as a `BIGINT`
+///
+/// The timestamp is shifted to local time and divided into fields, see
+/// [`timestamp_util`](super::timestamp_util). `DOW` counts days from Sunday as 0, and `SECOND` is
+/// the whole number of seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractExpr {
+    pub(crate) field: DateTimeField,
+    pub(crate) expr: Box<DynProofExpr>,
+}
+
+impl ExtractExpr {
+    /// Create an `EXTRACT` of `field` from the timestamps `expr`
+    pub fn new(field: DateTimeField, expr: Box<DynProofExpr>) -> Self {
+        Self { field, expr }
+    }
+
+    /// The type of `EXTRACT` of `field` from values of `from_type`, which is `BIGINT`
+    pub(crate) fn try_result_type(
+        field: DateTimeField,
+        from_type: ColumnType,
+    ) -> ConversionResult<ColumnType> {
+        check_timestamp("EXTRACT", from_type)?;
+        if is_extract_supported(field) {
+            Ok(ColumnType::BigInt)
+        } else {
+            Err(ConversionError::Unprovable {
+                error: format!("EXTRACT of {field} is not supported"),
+            })
+        }
+    }
+
+    /// The time unit and the time zone of the timestamps
+    ///
+    /// # Panics
+    /// Panics if `expr` is not a timestamp, which `DynProofExpr::try_new_extract` rules out.
+    fn time_unit_and_zone(&self) -> (PoSQLTimeUnit, PoSQLTimeZone) {
+        let ColumnType::TimestampTZ(time_unit, timezone) = self.expr.data_type() else {
+            panic!("EXTRACT takes fields of timestamps");
+        };
+        (time_unit, timezone)
+    }
+}
+
+impl ProofExpr for ExtractExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::BigInt
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let timestamps = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        Column::Scalar(
+            extract(
+                &mut ColumnArithmetic::new(alloc),
+                timestamps,
+                self.field,
+                time_unit,
+                timezone,
+            )
+            .expect("Computing columns does not fail"),
+        )
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.extract_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let timestamps = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        let result = extract(
+            &mut ColumnArithmetic::new_prover(alloc, builder),
+            timestamps,
+            self.field,
+            time_unit,
+            timezone,
+        )
+        .expect("Proving columns does not fail");
+
+        log::log_memory_usage("End");
+
+        Column::Scalar(result)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let timestamps = self.expr.verifier_evaluate(builder, accessor, one_eval)?;
+        let (time_unit, timezone) = self.time_unit_and_zone();
+        extract(
+            &mut EvaluationArithmetic::new(builder, one_eval),
+            timestamps,
+            self.field,
+            time_unit,
+            timezone,
+        )
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+// select extract(year from a) as year, ..., extract(second from a) as second from sxt.t
+#[test]
+fn we_can_prove_extract_of_all_supported_fields() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [
+            0,               // 1970-01-01 00:00:00, a Thursday
+            -1,              // 1969-12-31 23:59:59
+            951_868_799,     // 2000-02-29 23:59:59
+            1_709_251_199,   // 2024-02-29 23:59:59
+            4_107_542_400,   // 2100-03-01 00:00:00, 2100 is not a leap year
+            -11_676_096_001, // 1599-12-31 23:59:59
+            253_402_300_799, // 9999-12-31 23:59:59
+            1_718_900_000,   // 2024-06-20 16:13:20
+        ],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let fields = [
+        ("year", DateTimeField::Year),
+        ("quarter", DateTimeField::Quarter),
+        ("month", DateTimeField::Month),
+        ("day", DateTimeField::Day),
+        ("dow", DateTimeField::DayOfWeek),
+        ("doy", DateTimeField::DayOfYear),
+        ("hour", DateTimeField::Hour),
+        ("minute", DateTimeField::Minute),
+        ("second", DateTimeField::Second),
+    ];
+    let ast = projection(
+        fields
+            .iter()
+            .map(|&(name, field)| aliased_plan(extract(field, column(t, "a", &accessor)), name))
+            .collect(),
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("year", [1970, 1969, 2000, 2024, 2100, 1599, 9999, 2024]),
+        bigint("quarter", [1, 4, 1, 1, 1, 4, 4, 2]),
+        bigint("month", [1, 12, 2, 2, 3, 12, 12, 6]),
+        bigint("day", [1, 31, 29, 29, 1, 31, 31, 20]),
+        bigint("dow", [4, 3, 2, 4, 1, 5, 5, 4]),
+        bigint("doy", [1, 365, 60, 60, 60, 365, 365, 172]),
+        bigint("hour", [0, 23, 23, 23, 0, 23, 23, 16]),
+        bigint("minute", [0, 59, 59, 59, 0, 59, 59, 13]),
+        bigint("second", [0, 59, 59, 59, 0, 59, 59, 20]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select extract(year from a) as year, ..., extract(dow from a) as dow from sxt.t
+#[test]
+fn we_can_prove_extract_in_the_time_zone_of_the_timestamps() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::new(-28_800),
+        [
+            0,
+            -1,
+            951_868_799,
+            1_709_251_199,
+            4_107_542_400,
+            -11_676_096_001,
+            1_718_900_000,
+        ],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let fields = [
+        ("year", DateTimeField::Year),
+        ("month", DateTimeField::Month),
+        ("day", DateTimeField::Day),
+        ("hour", DateTimeField::Hour),
+        ("dow", DateTimeField::DayOfWeek),
+    ];
+    let ast = projection(
+        fields
+            .iter()
+            .map(|&(name, field)| aliased_plan(extract(field, column(t, "a", &accessor)), name))
+            .collect(),
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("year", [1969, 1969, 2000, 2024, 2100, 1599, 2024]),
+        bigint("month", [12, 12, 2, 2, 2, 12, 6]),
+        bigint("day", [31, 31, 29, 29, 28, 31, 20]),
+        bigint("hour", [16, 15, 15, 15, 16, 15, 8]),
+        bigint("dow", [3, 3, 2, 4, 0, 5, 4]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select extract(second from a) as s, extract(millisecond from a) as ms, ... from sxt.t
+#[test]
+fn we_can_prove_extract_of_fractional_seconds() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Nanosecond,
+        PoSQLTimeZone::utc(),
+        [1_718_900_000_123_456_789, -1],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let fields = [
+        ("s", DateTimeField::Second),
+        ("ms", DateTimeField::Millisecond),
+        ("us", DateTimeField::Microsecond),
+        ("ns", DateTimeField::Nanosecond),
+    ];
+    let ast = projection(
+        fields
+            .iter()
+            .map(|&(name, field)| aliased_plan(extract(field, column(t, "a", &accessor)), name))
+            .collect(),
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("s", [20, 59]),
+        bigint("ms", [20_123, 59_999]),
+        bigint("us", [20_123_456, 59_999_999]),
+        bigint("ns", [20_123_456_789_i64, 59_999_999_999]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where extract(year from a) = 2024
+#[test]
+fn we_can_prove_a_filter_on_extract() {
+    let utc = PoSQLTimeZone::utc();
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        utc,
+        [
+            1_704_067_199_999,
+            1_704_067_200_000,
+            1_718_900_000_000,
+            1_735_689_600_000,
+        ],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(
+            extract(DateTimeField::Year, column(t, "a", &accessor)),
+            const_bigint(2024),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        utc,
+        [1_704_067_200_000, 1_718_900_000_000],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_extract_unsupported_fields_or_from_non_timestamps() {
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1]),
+        varchar("b", ["2024-06-20"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_extract(DateTimeField::Week, column(t, "a", &accessor)),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_extract(DateTimeField::Year, column(t, "b", &accessor)),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_extract_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::new(19_800),
+        [1_718_900_000, -1],
+        &alloc,
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let expr = extract(DateTimeField::Minute, column(t, "a", &accessor));
+    assert_eq!(expr.data_type(), ColumnType::BigInt);
+    assert_eq!(
+        expr.result_evaluate(&alloc, &data),
+        Column::Scalar(&[43, 29].map(TestScalar::from))
+    );
+}
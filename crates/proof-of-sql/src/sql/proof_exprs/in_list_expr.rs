@@ -0,0 +1,197 @@
+use super::{
+    prover_evaluate_equals_zero, result_evaluate_equals_zero, verifier_evaluate_equals_zero,
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::iter;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for `expr IN (list...)` where the list consists of literals.
+///
+/// Rather than proving `expr = item` for every item and combining the results with `OR`s, the
+/// product `(expr - item_0) * (expr - item_1) * ...` is committed to and proven equal to its
+/// expansion as a polynomial in `expr`. The expression is then in the list exactly where the
+/// product is zero.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InListExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    pub(crate) list: Vec<LiteralValue>,
+}
+
+impl InListExpr {
+    /// Create a new `IN` expression
+    pub fn new(expr: Box<DynProofExpr>, list: Vec<LiteralValue>) -> Self {
+        Self { expr, list }
+    }
+
+    /// The factors `a * expr - b` of the product, given as `(a, b)`, where `a` and `b` rescale
+    /// `expr` and the item to a common scale.
+    ///
+    /// # Panics
+    /// Panics if two scales differ by more than 255, which is impossible for `i8` scales.
+    fn factors<S: Scalar>(&self) -> Vec<(S, S)> {
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        self.list
+            .iter()
+            .map(|item| {
+                let item_scale = item.column_type().scale().unwrap_or(0);
+                let scale_difference = i16::from(expr_scale) - i16::from(item_scale);
+                let scale_factor = S::pow10(
+                    u8::try_from(scale_difference.unsigned_abs())
+                        .expect("scales differ by at most 255"),
+                );
+                if scale_difference >= 0 {
+                    (S::one(), item.to_scalar::<S>() * scale_factor)
+                } else {
+                    (scale_factor, item.to_scalar())
+                }
+            })
+            .collect()
+    }
+
+    /// The coefficients of the product as a polynomial in `expr`, starting with the constant one.
+    fn coefficients<S: Scalar>(&self) -> Vec<S> {
+        self.factors::<S>()
+            .into_iter()
+            .fold(vec![S::one()], |coefficients, (a, b)| {
+                // (c_0 + c_1 x + ...) * (a x - b)
+                iter::once(S::zero())
+                    .chain(coefficients.iter().copied())
+                    .zip(coefficients.iter().copied().chain(iter::once(S::zero())))
+                    .map(|(shifted, unshifted)| a * shifted - b * unshifted)
+                    .collect()
+            })
+    }
+
+    fn product<'a, S: Scalar>(&self, alloc: &'a Bump, expr_column: &[S]) -> &'a [S] {
+        let factors = self.factors::<S>();
+        alloc.alloc_slice_fill_with(expr_column.len(), |i| {
+            factors
+                .iter()
+                .map(|(a, b)| *a * expr_column[i] - *b)
+                .product()
+        })
+    }
+}
+
+impl ProofExpr for InListExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "InListExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let expr_column = self
+            .expr
+            .result_evaluate(alloc, table)
+            .to_scalar_with_scaling(0);
+        let product = self.product(alloc, &expr_column);
+        Column::Boolean(result_evaluate_equals_zero(
+            table.num_rows(),
+            alloc,
+            product,
+        ))
+    }
+
+    #[tracing::instrument(name = "InListExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column: &'a [S] = alloc.alloc_slice_copy(
+            &self
+                .expr
+                .prover_evaluate(builder, alloc, table)
+                .to_scalar_with_scaling(0),
+        );
+
+        // product
+        let product = self.product(alloc, expr_column);
+        builder.produce_intermediate_mle(product);
+
+        // subpolynomial: product - (c_0 + c_1 * expr + c_2 * expr * expr + ...)
+        let ones: &'a [bool] = alloc.alloc_slice_fill_copy(table.num_rows(), true);
+        let terms =
+            iter::once((S::one(), vec![Box::new(product) as Box<_>]))
+                .chain(self.coefficients::<S>().into_iter().enumerate().map(
+                    |(power, coefficient)| {
+                        let multiplicands = if power == 0 {
+                            vec![Box::new(ones) as Box<_>]
+                        } else {
+                            iter::repeat_with(|| Box::new(expr_column) as Box<_>)
+                                .take(power)
+                                .collect()
+                        };
+                        (-coefficient, multiplicands)
+                    },
+                ))
+                .collect();
+        builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+
+        let res = Column::Boolean(prover_evaluate_equals_zero(
+            table.num_rows(),
+            builder,
+            alloc,
+            product,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        one_eval: S,
+    ) -> Result<S, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor, one_eval)?;
+
+        // product
+        let product_eval = builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: product - (c_0 + c_1 * expr + c_2 * expr * expr + ...)
+        let mut coefficients = self.coefficients::<S>().into_iter();
+        let constant = coefficients.next().unwrap_or(S::zero()) * one_eval;
+        let (polynomial_eval, _) = coefficients.fold(
+            (constant, expr_eval),
+            |(polynomial_eval, power_eval), coefficient| {
+                (
+                    polynomial_eval + coefficient * power_eval,
+                    power_eval * expr_eval,
+                )
+            },
+        );
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            product_eval - polynomial_eval,
+            self.list.len().max(1),
+        )?;
+
+        verifier_evaluate_equals_zero(builder, product_eval, one_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, LiteralValue, OwnedTable,
+            OwnedTableTestAccessor, Table, TableTestAccessor,
+        },
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+#[test]
+fn we_can_prove_an_in_list_query_with_no_rows() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        in_list(
+            column(t, "a", &accessor),
+            vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", [""; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_multiple_rows() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, -2, 3, 4, 2, 0]),
+        varchar("b", ["a", "b", "c", "d", "e", "f"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        in_list(
+            column(t, "a", &accessor),
+            vec![
+                LiteralValue::BigInt(2),
+                LiteralValue::Int(-2),
+                LiteralValue::SmallInt(0),
+            ],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [-2, 2, 0]), varchar("b", ["b", "e", "f"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_not_in_list_query_on_strings() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        varchar("b", ["ab", "cd", "ef", "cd"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        not(in_list(
+            column(t, "b", &accessor),
+            vec![
+                LiteralValue::VarChar("cd".to_string()),
+                LiteralValue::VarChar("gh".to_string()),
+            ],
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_items_of_different_scales() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 10, 1, [15, 20, 25, 30, -5]),
+        bigint("b", [1, 2, 3, 4, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    // a IN (2, 2.50, -0.5)
+    let ast = filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        in_list(
+            column(t, "a", &accessor),
+            vec![
+                LiteralValue::BigInt(2),
+                LiteralValue::Decimal75(Precision::new(3).unwrap(), 2, 250.into()),
+                LiteralValue::Decimal75(Precision::new(1).unwrap(), 1, (-5).into()),
+            ],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [2, 3, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_an_empty_list() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1, 2, 3])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        in_list(column(t, "a", &accessor), vec![]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_an_in_list_expr_with_mismatched_types() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1, 2, 3])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert!(DynProofExpr::try_new_in_list(
+        column(t, "a", &accessor),
+        vec![
+            LiteralValue::BigInt(1),
+            LiteralValue::VarChar("1".to_string())
+        ],
+    )
+    .is_err());
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            varchar(
+                "b",
+                dist.sample_iter(&mut rng).take(n).map(|v| format!("s{v}")),
+            ),
+        ]);
+
+        // Generate random values to filter by
+        let list_len = Uniform::new(1, 5).sample(&mut rng);
+        let list: Vec<i64> = dist.sample_iter(&mut rng).take(list_len).collect();
+
+        // Create and verify proof
+        let t = "sxt.t".parse().unwrap();
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t,
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            in_list(
+                column(t, "a", &accessor),
+                list.iter().copied().map(LiteralValue::BigInt).collect(),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_a, expected_b): (Vec<_>, Vec<_>) =
+            multizip((data["a"].i64_iter(), data["b"].string_iter()))
+                .filter(|(a, _)| list.contains(a))
+                .map(|(a, b)| (*a, b.clone()))
+                .multiunzip();
+        let expected_result = owned_table([bigint("a", expected_a), varchar("b", expected_b)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(121);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_in_list_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data: Table<Curve25519Scalar> = table([
+        borrowed_bigint("a", [1, 2, 3, 4], &alloc),
+        borrowed_varchar("b", ["t", "ghi", "jj", "f"], &alloc),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = TableTestAccessor::<InnerProductProof>::new_from_table(t, data.clone(), 0, ());
+    let in_list_expr: DynProofExpr = in_list(
+        column(t, "b", &accessor),
+        vec![
+            LiteralValue::VarChar("f".to_string()),
+            LiteralValue::VarChar("ghi".to_string()),
+        ],
+    );
+    let res = in_list_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, true, false, true]);
+    assert_eq!(res, expected_res);
+}
@@ -1,8 +1,4 @@
-use super::{
-    prover_evaluate_equals_zero, prover_evaluate_or, result_evaluate_equals_zero,
-    result_evaluate_or, scale_and_add_subtract_eval, scale_and_subtract,
-    verifier_evaluate_equals_zero, verifier_evaluate_or, DynProofExpr, ProofExpr,
-};
+use super::{scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -12,7 +8,9 @@ use crate::{
     },
     sql::{
         proof::{FinalRoundBuilder, VerificationBuilder},
-        proof_gadgets::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign},
+        proof_gadgets::{
+            prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_comparison_sign,
+        },
     },
     utils::log,
 };
@@ -20,27 +18,74 @@ use alloc::boxed::Box;
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
-/// Provable AST expression for an inequality expression
+/// Provable AST expression for an inequality expression, i.e. `<`, `<=`, `>` or `>=`
+///
+/// Since the sign of `0` can not be proven, `a < b` is proven as the sign of the odd, and so nonzero,
+/// value `2 * (a - b) + 1`. Each comparison is then a single sign decomposition, which `a < b` shares
+/// with its negation `a >= b` and `a > b` shares with its negation `a <= b`.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct InequalityExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
-    is_lte: bool,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+    pub(crate) is_lt: bool,
+    pub(crate) is_strict: bool,
     #[cfg(test)]
     pub(crate) treat_column_of_zeros_as_negative: bool,
 }
 
 impl InequalityExpr {
-    /// Create a new less than or equal expression
-    pub fn new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>, is_lte: bool) -> Self {
+    /// Create a new inequality expression, which is `lhs < rhs` if `is_lt` and `lhs > rhs` otherwise,
+    /// or `lhs <= rhs` and `lhs >= rhs` respectively if it is not strict
+    pub fn new(
+        lhs: Box<DynProofExpr>,
+        rhs: Box<DynProofExpr>,
+        is_lt: bool,
+        is_strict: bool,
+    ) -> Self {
         Self {
             lhs,
             rhs,
-            is_lte,
+            is_lt,
+            is_strict,
             #[cfg(test)]
             treat_column_of_zeros_as_negative: false,
         }
     }
+
+    /// Whether the sign decomposition is of `2 * (lhs - rhs) + 1` rather than `2 * (rhs - lhs) + 1`
+    fn is_lhs_minus_rhs(&self) -> bool {
+        self.is_lt == self.is_strict
+    }
+
+    /// Compute `2 * diff + 1`, where `diff` is `lhs - rhs` or `rhs - lhs` at the larger scale
+    ///
+    /// # Panics
+    /// Panics if the columns can not be scaled and subtracted, which the type check prevents.
+    fn odd_diff<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        lhs_column: Column<'a, S>,
+        rhs_column: Column<'a, S>,
+    ) -> &'a [S] {
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        let diff = if self.is_lhs_minus_rhs() {
+            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
+        } else {
+            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
+        }
+        .expect("Failed to scale and subtract");
+        alloc.alloc_slice_fill_with(diff.len(), |i| diff[i] + diff[i] + S::ONE)
+    }
+
+    /// The result of the comparison from the sign of `2 * diff + 1`, i.e. whether `diff < 0`
+    fn result_from_sign<'a>(&self, alloc: &'a Bump, sign: &'a [bool]) -> &'a [bool] {
+        if self.is_strict {
+            sign
+        } else {
+            alloc.alloc_slice_fill_with(sign.len(), |i| !sign[i])
+        }
+    }
 }
 
 impl ProofExpr for InequalityExpr {
@@ -58,25 +103,12 @@ impl ProofExpr for InequalityExpr {
 
         let lhs_column = self.lhs.result_evaluate(alloc, table);
         let rhs_column = self.rhs.result_evaluate(alloc, table);
-        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
-        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let table_length = table.num_rows();
-        let diff = if self.is_lte {
-            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
-                .expect("Failed to scale and subtract")
-        } else {
-            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
-                .expect("Failed to scale and subtract")
-        };
+        let odd_diff = self.odd_diff(alloc, lhs_column, rhs_column);
 
-        // diff == 0
-        let equals_zero = result_evaluate_equals_zero(table_length, alloc, diff);
+        // sign(2 * diff + 1) == -1
+        let sign = result_evaluate_sign(table.num_rows(), alloc, odd_diff);
 
-        // sign(diff) == -1
-        let sign = result_evaluate_sign(table_length, alloc, diff);
-
-        // (diff == 0) || (sign(diff) == -1)
-        let res = Column::Boolean(result_evaluate_or(table_length, alloc, equals_zero, sign));
+        let res = Column::Boolean(self.result_from_sign(alloc, sign));
 
         log::log_memory_usage("End");
 
@@ -94,30 +126,18 @@ impl ProofExpr for InequalityExpr {
 
         let lhs_column = self.lhs.prover_evaluate(builder, alloc, table);
         let rhs_column = self.rhs.prover_evaluate(builder, alloc, table);
-        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
-        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let diff = if self.is_lte {
-            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
-                .expect("Failed to scale and subtract")
-        } else {
-            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
-                .expect("Failed to scale and subtract")
-        };
-
-        // diff == 0
-        let equals_zero = prover_evaluate_equals_zero(table.num_rows(), builder, alloc, diff);
+        let odd_diff = self.odd_diff(alloc, lhs_column, rhs_column);
 
-        // sign(diff) == -1
+        // sign(2 * diff + 1) == -1
         let sign = prover_evaluate_sign(
             builder,
             alloc,
-            diff,
+            odd_diff,
             #[cfg(test)]
             self.treat_column_of_zeros_as_negative,
         );
 
-        // (diff == 0) || (sign(diff) == -1)
-        let res = Column::Boolean(prover_evaluate_or(builder, alloc, equals_zero, sign));
+        let res = Column::Boolean(self.result_from_sign(alloc, sign));
 
         log::log_memory_usage("End");
 
@@ -134,20 +154,21 @@ impl ProofExpr for InequalityExpr {
         let rhs_eval = self.rhs.verifier_evaluate(builder, accessor, one_eval)?;
         let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
         let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let diff_eval = if self.is_lte {
+        let diff_eval = if self.is_lhs_minus_rhs() {
             scale_and_add_subtract_eval(lhs_eval, rhs_eval, lhs_scale, rhs_scale, true)
         } else {
             scale_and_add_subtract_eval(rhs_eval, lhs_eval, rhs_scale, lhs_scale, true)
         };
 
-        // diff == 0
-        let equals_zero = verifier_evaluate_equals_zero(builder, diff_eval, one_eval)?;
+        // sign(2 * diff + 1) == -1
+        let sign_eval =
+            verifier_evaluate_comparison_sign(builder, diff_eval + diff_eval + one_eval, one_eval)?;
 
-        // sign(diff) == -1
-        let sign = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
-
-        // (diff == 0) || (sign(diff) == -1)
-        verifier_evaluate_or(builder, &equals_zero, &sign)
+        Ok(if self.is_strict {
+            sign_eval
+        } else {
+            one_eval - sign_eval
+        })
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
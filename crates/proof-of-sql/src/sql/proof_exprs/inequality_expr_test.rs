@@ -160,6 +160,85 @@ fn we_can_compare_columns_with_extreme_values() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_compare_columns_whose_doubled_difference_is_extreme() {
+    let max_38 = Curve25519Scalar::pow10(38) - Curve25519Scalar::ONE;
+    // After scaling, `decimal_c` and `decimal_d` differ by `4.4 * 10^38`, which is more than `2^128`
+    let big_38 = Curve25519Scalar::from(4) * Curve25519Scalar::pow10(37);
+    let data = owned_table([
+        bigint("bigint_a", [i64::MIN, i64::MAX, i64::MIN]),
+        bigint("bigint_b", [i64::MAX, i64::MIN, i64::MIN]),
+        int128("int128_a", [i128::MIN, i128::MAX, i128::MAX]),
+        int128("int128_b", [i128::MAX, i128::MIN, i128::MAX]),
+        decimal75("decimal_a", 38, 0, [-max_38, max_38, max_38]),
+        decimal75("decimal_b", 38, 0, [max_38, -max_38, max_38]),
+        decimal75(
+            "decimal_c",
+            38,
+            1,
+            [-big_38, big_38, Curve25519Scalar::ZERO],
+        ),
+        decimal75(
+            "decimal_d",
+            38,
+            0,
+            [big_38, -big_38, Curve25519Scalar::ZERO],
+        ),
+        bigint("c", [1_i64, 2, 3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (lhs, rhs) in [
+        ("bigint_a", "bigint_b"),
+        ("int128_a", "int128_b"),
+        ("decimal_a", "decimal_b"),
+        ("decimal_c", "decimal_d"),
+    ] {
+        for (where_clause, expected) in [
+            (
+                lt(column(t, lhs, &accessor), column(t, rhs, &accessor)),
+                vec![1_i64],
+            ),
+            (
+                lte(column(t, lhs, &accessor), column(t, rhs, &accessor)),
+                vec![1, 3],
+            ),
+            (
+                gt(column(t, lhs, &accessor), column(t, rhs, &accessor)),
+                vec![2],
+            ),
+            (
+                gte(column(t, lhs, &accessor), column(t, rhs, &accessor)),
+                vec![2, 3],
+            ),
+        ] {
+            let ast = filter(cols_expr_plan(t, &["c"], &accessor), tab(t), where_clause);
+            let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+            exercise_verification(&verifiable_res, &ast, &accessor, t);
+            let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+            assert_eq!(res, owned_table([bigint("c", expected)]));
+        }
+    }
+}
+
+#[test]
+fn we_cannot_compare_columns_whose_scaled_difference_is_out_of_range() {
+    let max_38 = Curve25519Scalar::pow10(38) - Curve25519Scalar::ONE;
+    let data = owned_table([
+        decimal75("a", 38, 0, [max_38]),
+        decimal75("b", 38, 1, [-max_38]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        lt(column(t, "b", &accessor), column(t, "a", &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
 #[test]
 fn we_can_compare_columns_with_small_decimal_values_without_scale() {
     let scalar_pos = Curve25519Scalar::pow10(38) - Curve25519Scalar::ONE;
@@ -300,6 +379,7 @@ fn we_cannot_compare_columns_filtering_on_extreme_decimal_values() {
         DynProofExpr::try_new_inequality(
             column(t, "e", &accessor),
             const_scalar::<Curve25519Scalar, _>(Curve25519Scalar::ONE),
+            false,
             false
         ),
         Err(ConversionError::DataTypeMismatch { .. })
@@ -476,7 +556,7 @@ fn we_can_compare_a_constant_column_of_zeros() {
 }
 
 #[test]
-fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
+fn we_cannot_prove_a_dishonest_sign_when_comparing_equal_values() {
     let data = owned_table([bigint("a", [0_i64, 0, 0]), bigint("b", [1_i64, 2, 3])]);
     let t = "sxt.t".parse().unwrap();
     let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
@@ -491,10 +571,81 @@ fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
         }
     }
     let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
-    exercise_verification(&verifiable_res, &ast, &accessor, t);
-    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
-    let expected = owned_table([bigint("b", [1_i64, 2, 3])]);
-    assert_eq!(res, expected);
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_can_compare_columns_with_strict_inequalities() {
+    let data = owned_table([
+        bigint("a", [-1_i64, 2, 3, 5, 2]),
+        bigint("b", [2_i64, 2, 2, 2, 2]),
+        bigint("c", [1_i64, 2, 3, 4, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (where_clause, expected) in [
+        (
+            lt(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![1_i64],
+        ),
+        (
+            gt(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![3, 4],
+        ),
+        (lt(column(t, "b", &accessor), const_bigint(2)), vec![]),
+        (gt(column(t, "b", &accessor), const_bigint(2)), vec![]),
+    ] {
+        let ast = filter(cols_expr_plan(t, &["c"], &accessor), tab(t), where_clause);
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, owned_table([bigint("c", expected)]));
+    }
+}
+
+#[test]
+fn we_can_compare_timestamp_columns_with_different_units() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            vec![1, 2, 3, -1],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            vec![1500, 2000, 2500, -999],
+        ),
+        bigint("c", [1_i64, 2, 3, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for (where_clause, expected) in [
+        (
+            lt(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![1_i64, 4],
+        ),
+        (
+            lte(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![1, 2, 4],
+        ),
+        (
+            gt(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![3],
+        ),
+        (
+            gte(column(t, "a", &accessor), column(t, "b", &accessor)),
+            vec![2, 3],
+        ),
+    ] {
+        let ast = filter(cols_expr_plan(t, &["c"], &accessor), tab(t), where_clause);
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, owned_table([bigint("c", expected)]));
+    }
 }
 
 fn test_random_tables_with_given_offset(offset: usize) {
@@ -593,3 +744,21 @@ fn we_can_compute_the_correct_output_of_a_gte_inequality_expr_using_result_evalu
     let expected_res = Column::Boolean(&[false, true, true]);
     assert_eq!(res, expected_res);
 }
+
+#[test]
+fn we_can_compute_the_correct_output_of_strict_inequality_exprs_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [-1, 9, 1, 3], &alloc),
+        borrowed_bigint("b", [1, 2, 3, 3], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data.clone(), 0);
+    let lt_expr = lt(column(t, "a", &accessor), column(t, "b", &accessor));
+    let res = lt_expr.result_evaluate(&alloc, &data);
+    assert_eq!(res, Column::Boolean(&[true, false, true, false]));
+    let gt_expr = gt(column(t, "a", &accessor), column(t, "b", &accessor));
+    let res = gt_expr.result_evaluate(&alloc, &data);
+    assert_eq!(res, Column::Boolean(&[false, true, false, false]));
+}
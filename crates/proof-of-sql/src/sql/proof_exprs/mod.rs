@@ -16,10 +16,37 @@ mod aggregate_expr;
 pub(crate) use aggregate_expr::AggregateExpr;
 
 mod multiply_expr;
-use multiply_expr::MultiplyExpr;
+pub(crate) use multiply_expr::MultiplyExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod multiply_expr_test;
 
+mod divide_and_modulo_expr;
+pub(crate) use divide_and_modulo_expr::DivideAndModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod divide_and_modulo_expr_test;
+
+mod cast_expr;
+pub(crate) use cast_expr::CastExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod cast_expr_test;
+
+mod timestamp_util;
+
+mod date_trunc_expr;
+pub(crate) use date_trunc_expr::DateTruncExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod date_trunc_expr_test;
+
+mod extract_expr;
+pub(crate) use extract_expr::ExtractExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod extract_expr_test;
+
+mod add_interval_expr;
+pub(crate) use add_interval_expr::AddIntervalExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod add_interval_expr_test;
+
 mod dyn_proof_expr;
 pub(crate) use dyn_proof_expr::DynProofExpr;
 
@@ -34,17 +61,17 @@ pub(crate) use and_expr::AndExpr;
 mod and_expr_test;
 
 mod inequality_expr;
-use inequality_expr::InequalityExpr;
+pub(crate) use inequality_expr::InequalityExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod inequality_expr_test;
 
 mod or_expr;
-use or_expr::{prover_evaluate_or, result_evaluate_or, verifier_evaluate_or, OrExpr};
+pub(crate) use or_expr::OrExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod or_expr_test;
 
 mod not_expr;
-use not_expr::NotExpr;
+pub(crate) use not_expr::NotExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod not_expr_test;
 
@@ -64,6 +91,16 @@ use equals_expr::{
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
+mod case_expr;
+pub(crate) use case_expr::CaseExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod case_expr_test;
+
+mod in_list_expr;
+pub(crate) use in_list_expr::InListExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_expr_test;
+
 mod table_expr;
 pub(crate) use table_expr::TableExpr;
 
@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 /// Provable logical NOT expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NotExpr {
-    expr: Box<DynProofExpr>,
+    pub(crate) expr: Box<DynProofExpr>,
 }
 
 impl NotExpr {
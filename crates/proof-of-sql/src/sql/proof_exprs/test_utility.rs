@@ -1,10 +1,10 @@
 use super::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr};
 use crate::base::{
-    database::{ColumnRef, LiteralValue, SchemaAccessor, TableRef},
+    database::{ColumnRef, ColumnType, LiteralValue, OwnedColumn, SchemaAccessor, TableRef},
     math::{decimal::Precision, i256::I256},
     scalar::Scalar,
 };
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, DateTimeField, Percentile};
 use sqlparser::ast::Ident;
 
 /// # Panics
@@ -33,18 +33,39 @@ pub fn equal(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
     DynProofExpr::try_new_equals(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_not_equals()` returns an error.
+pub fn not_equal(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_not_equals(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_inequality()` returns an error.
+pub fn lt(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_inequality(left, right, true, true).unwrap()
+}
+
 /// # Panics
 /// Panics if:
 /// - `DynProofExpr::try_new_inequality()` returns an error.
 pub fn lte(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
-    DynProofExpr::try_new_inequality(left, right, true).unwrap()
+    DynProofExpr::try_new_inequality(left, right, true, false).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_inequality()` returns an error.
+pub fn gt(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_inequality(left, right, false, true).unwrap()
 }
 
 /// # Panics
 /// Panics if:
 /// - `DynProofExpr::try_new_inequality()` returns an error.
 pub fn gte(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
-    DynProofExpr::try_new_inequality(left, right, false).unwrap()
+    DynProofExpr::try_new_inequality(left, right, false, false).unwrap()
 }
 
 /// # Panics
@@ -89,6 +110,66 @@ pub fn multiply(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
     DynProofExpr::try_new_multiply(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_divide()` returns an error.
+pub fn divide(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_divide(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_modulo()` returns an error.
+pub fn modulo(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_modulo(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_cast()` returns an error.
+pub fn cast(expr: DynProofExpr, to_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::try_new_cast(expr, to_type).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_date_trunc()` returns an error.
+pub fn date_trunc(field: DateTimeField, expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_date_trunc(field, expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_extract()` returns an error.
+pub fn extract(field: DateTimeField, expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_extract(field, expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_add_interval()` returns an error.
+pub fn add_interval(expr: DynProofExpr, value: i64, field: DateTimeField) -> DynProofExpr {
+    DynProofExpr::try_new_add_interval(expr, value, field).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in_list()` returns an error.
+pub fn in_list(expr: DynProofExpr, list: Vec<LiteralValue>) -> DynProofExpr {
+    DynProofExpr::try_new_in_list(expr, list).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_case()` returns an error.
+pub fn case(
+    condition: DynProofExpr,
+    then_result: DynProofExpr,
+    else_result: DynProofExpr,
+) -> DynProofExpr {
+    DynProofExpr::try_new_case(condition, then_result, else_result).unwrap()
+}
+
 pub fn const_bool(val: bool) -> DynProofExpr {
     DynProofExpr::new_literal(LiteralValue::Boolean(val))
 }
@@ -216,3 +297,84 @@ pub fn sum_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
         alias: alias.into(),
     }
 }
+
+pub fn max_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Max, expr),
+        alias: alias.into(),
+    }
+}
+
+pub fn min_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Min, expr),
+        alias: alias.into(),
+    }
+}
+
+pub fn avg_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Avg, expr),
+        alias: alias.into(),
+    }
+}
+
+pub fn median_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Median, expr),
+        alias: alias.into(),
+    }
+}
+
+/// # Panics
+/// Panics if `numerator / 10^scale` is not a valid percentile.
+pub fn percentile_cont_expr(
+    numerator: u64,
+    scale: u8,
+    expr: DynProofExpr,
+    alias: &str,
+) -> AliasedDynProofExpr {
+    let percentile = Percentile::try_new(numerator, scale).unwrap();
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::PercentileCont(percentile), expr),
+        alias: alias.into(),
+    }
+}
+
+/// # Panics
+/// Panics if `numerator / 10^scale` is not a valid percentile.
+pub fn percentile_disc_expr(
+    numerator: u64,
+    scale: u8,
+    expr: DynProofExpr,
+    alias: &str,
+) -> AliasedDynProofExpr {
+    let percentile = Percentile::try_new(numerator, scale).unwrap();
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::PercentileDisc(percentile), expr),
+        alias: alias.into(),
+    }
+}
+
+pub fn count_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Count, expr),
+        alias: alias.into(),
+    }
+}
+
+/// # Panics
+/// Panics if `precision` is not a valid decimal precision.
+pub fn decimal_type(precision: u8, scale: i8) -> ColumnType {
+    ColumnType::Decimal75(Precision::new(precision).unwrap(), scale)
+}
+
+/// # Panics
+/// Panics if `precision` is not a valid decimal precision.
+pub fn decimals<S: Scalar>(precision: u8, scale: i8, values: &[i64]) -> OwnedColumn<S> {
+    OwnedColumn::Decimal75(
+        Precision::new(precision).unwrap(),
+        scale,
+        values.iter().copied().map(S::from).collect(),
+    )
+}
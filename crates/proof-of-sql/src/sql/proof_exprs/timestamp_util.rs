@@ -0,0 +1,587 @@
+//! Arithmetic on the local time of timestamps, which `DATE_TRUNC` and `EXTRACT` are built from.
+//!
+//! The arithmetic is written once against [`TimestampArithmetic`], which is implemented to compute
+//! the results, to prove them and to verify them. All values are integers, and the only operations
+//! that are not linear are floor divisions by constants, the sign of a value and products.
+//! A floor division `q = floor(a / c)` is proven by committing to `q` and proving `0 <= a - q * c < c`
+//! with the sign gadget.
+//!
+//! Dates follow the proleptic Gregorian calendar. The year of a day is committed to and proven by
+//! bounding the day between the first days of that year and the next one, while the month and the
+//! day of the month are computed from the day of the year with the usual formulas for years that
+//! start on March 1, which puts the leap day at the end of the year.
+use crate::{
+    base::{database::ColumnType, proof::ProofError, scalar::Scalar},
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, verifier_evaluate_sign},
+    },
+};
+use alloc::{boxed::Box, format, vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    intermediate_ast::DateTimeField,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+/// The number of nanoseconds in a second
+const NANOSECONDS_PER_SECOND: i128 = 1_000_000_000;
+/// The number of seconds in a day
+const SECONDS_PER_DAY: i128 = 86_400;
+/// The number of days from 0001-01-01 to 1970-01-01
+const DAYS_BEFORE_EPOCH: i128 = 719_162;
+/// The number of days in 400 years, after which the Gregorian calendar repeats
+const DAYS_PER_400_YEARS: i128 = 146_097;
+/// The number of days in January and February of a year that is not a leap year
+const DAYS_BEFORE_MARCH: i128 = 59;
+/// The number of days from the Monday before 1970-01-01, a Thursday, to 1970-01-01
+const DAYS_SINCE_MONDAY_AT_EPOCH: i128 = 3;
+
+/// Integer arithmetic on columns, or on their evaluations
+pub(super) trait TimestampArithmetic {
+    /// A column of integers, or its evaluation
+    type Value: Copy;
+
+    /// `sum(coefficient * value) + constant`
+    fn linear(&mut self, terms: &[(i128, Self::Value)], constant: i128) -> Self::Value;
+
+    /// The integers `f(value)`, which are committed to and have to be constrained by the caller
+    fn witness(
+        &mut self,
+        value: Self::Value,
+        f: impl Fn(i128) -> i128,
+    ) -> Result<Self::Value, ProofError>;
+
+    /// `1` where the value is negative and `0` elsewhere
+    fn is_negative(&mut self, value: Self::Value) -> Result<Self::Value, ProofError>;
+
+    /// Fails unless the value is non-negative
+    fn check_non_negative(&mut self, value: Self::Value) -> Result<(), ProofError>;
+
+    /// `lhs * rhs`
+    fn multiply(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, ProofError>;
+}
+
+/// Checks that `operation` is applied to timestamps and returns their time unit
+pub(super) fn check_timestamp(
+    operation: &str,
+    column_type: ColumnType,
+) -> ConversionResult<PoSQLTimeUnit> {
+    match column_type {
+        ColumnType::TimestampTZ(time_unit, _) => Ok(time_unit),
+        _ => Err(ConversionError::InvalidExpression {
+            expression: format!("{operation} requires a timestamp, but found {column_type}"),
+        }),
+    }
+}
+
+/// Converts an integer scalar to an `i128`
+///
+/// # Panics
+/// Panics if the scalar is out of range, which values derived from timestamps never are.
+fn scalar_to_i128<S: Scalar>(value: S) -> i128 {
+    let Ok(value) = value.try_into() else {
+        panic!("Values derived from timestamps fit into an i128");
+    };
+    value
+}
+
+/// Computes columns, and proves them if a builder is given
+pub(super) struct ColumnArithmetic<'a, 'b, S: Scalar> {
+    alloc: &'a Bump,
+    builder: Option<&'b mut FinalRoundBuilder<'a, S>>,
+}
+
+impl<'a, 'b, S: Scalar> ColumnArithmetic<'a, 'b, S> {
+    /// Computes columns without proving them
+    pub(super) fn new(alloc: &'a Bump) -> Self {
+        Self {
+            alloc,
+            builder: None,
+        }
+    }
+
+    /// Computes columns and proves them with `builder`
+    pub(super) fn new_prover(alloc: &'a Bump, builder: &'b mut FinalRoundBuilder<'a, S>) -> Self {
+        Self {
+            alloc,
+            builder: Some(builder),
+        }
+    }
+
+    fn map(&self, value: &[S], f: impl Fn(i128) -> i128) -> &'a [S] {
+        self.alloc
+            .alloc_slice_fill_iter(value.iter().map(|&v| S::from(f(scalar_to_i128(v)))))
+    }
+
+    fn prove_sign(&mut self, value: &'a [S]) -> Option<&'a [bool]> {
+        self.builder.as_deref_mut().map(|builder| {
+            prover_evaluate_sign(
+                builder,
+                self.alloc,
+                value,
+                #[cfg(test)]
+                false,
+            )
+        })
+    }
+}
+
+impl<'a, S: Scalar + 'a> TimestampArithmetic for ColumnArithmetic<'a, '_, S> {
+    type Value = &'a [S];
+
+    fn linear(&mut self, terms: &[(i128, &'a [S])], constant: i128) -> &'a [S] {
+        let length = terms.first().map_or(0, |(_, value)| value.len());
+        self.alloc.alloc_slice_fill_with(length, |i| {
+            terms
+                .iter()
+                .fold(S::from(constant), |sum, &(coefficient, value)| {
+                    sum + S::from(coefficient) * value[i]
+                })
+        })
+    }
+
+    fn witness(&mut self, value: &'a [S], f: impl Fn(i128) -> i128) -> Result<&'a [S], ProofError> {
+        let result = self.map(value, f);
+        if let Some(builder) = self.builder.as_deref_mut() {
+            builder.produce_intermediate_mle(result);
+        }
+        Ok(result)
+    }
+
+    fn is_negative(&mut self, value: &'a [S]) -> Result<&'a [S], ProofError> {
+        Ok(match self.prove_sign(value) {
+            Some(signs) => self
+                .alloc
+                .alloc_slice_fill_iter(signs.iter().map(|&sign| S::from(sign))),
+            None => self.map(value, |v| i128::from(v < 0)),
+        })
+    }
+
+    fn check_non_negative(&mut self, value: &'a [S]) -> Result<(), ProofError> {
+        self.prove_sign(value);
+        Ok(())
+    }
+
+    fn multiply(&mut self, lhs: &'a [S], rhs: &'a [S]) -> Result<&'a [S], ProofError> {
+        let result: &'a [S] = self
+            .alloc
+            .alloc_slice_fill_with(lhs.len(), |i| lhs[i] * rhs[i]);
+        if let Some(builder) = self.builder.as_deref_mut() {
+            builder.produce_intermediate_mle(result);
+
+            // subpolynomial: result - lhs * rhs
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(result)]),
+                    (-S::one(), vec![Box::new(lhs), Box::new(rhs)]),
+                ],
+            );
+        }
+        Ok(result)
+    }
+}
+
+/// Verifies the evaluations of the columns that [`ColumnArithmetic`] proves
+pub(super) struct EvaluationArithmetic<'b, 'c, S: Scalar> {
+    builder: &'b mut VerificationBuilder<'c, S>,
+    one_eval: S,
+}
+
+impl<'b, 'c, S: Scalar> EvaluationArithmetic<'b, 'c, S> {
+    /// Verifies evaluations with `builder`
+    pub(super) fn new(builder: &'b mut VerificationBuilder<'c, S>, one_eval: S) -> Self {
+        Self { builder, one_eval }
+    }
+}
+
+impl<S: Scalar> TimestampArithmetic for EvaluationArithmetic<'_, '_, S> {
+    type Value = S;
+
+    fn linear(&mut self, terms: &[(i128, S)], constant: i128) -> S {
+        terms.iter().fold(
+            S::from(constant) * self.one_eval,
+            |sum, &(coefficient, value)| sum + S::from(coefficient) * value,
+        )
+    }
+
+    fn witness(&mut self, _value: S, _f: impl Fn(i128) -> i128) -> Result<S, ProofError> {
+        Ok(self.builder.try_consume_mle_evaluation()?)
+    }
+
+    fn is_negative(&mut self, value: S) -> Result<S, ProofError> {
+        verifier_evaluate_sign(self.builder, value, self.one_eval)
+    }
+
+    fn check_non_negative(&mut self, value: S) -> Result<(), ProofError> {
+        if verifier_evaluate_sign(self.builder, value, self.one_eval)?.is_zero() {
+            Ok(())
+        } else {
+            Err(ProofError::VerificationError {
+                error: "timestamp arithmetic is invalid",
+            })
+        }
+    }
+
+    fn multiply(&mut self, lhs: S, rhs: S) -> Result<S, ProofError> {
+        let result = self.builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: result - lhs * rhs
+        self.builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            result - lhs * rhs,
+            2,
+        )?;
+        Ok(result)
+    }
+}
+
+/// `floor(value / divisor)` for a positive `divisor`
+fn floor_div<A: TimestampArithmetic>(
+    a: &mut A,
+    value: A::Value,
+    divisor: i128,
+) -> Result<A::Value, ProofError> {
+    if divisor == 1 {
+        return Ok(value);
+    }
+    let quotient = a.witness(value, |v| v.div_euclid(divisor))?;
+
+    // 0 <= value - quotient * divisor <= divisor - 1
+    let remainder = a.linear(&[(1, value), (-divisor, quotient)], 0);
+    let gap = a.linear(&[(-1, remainder)], divisor - 1);
+    a.check_non_negative(remainder)?;
+    a.check_non_negative(gap)?;
+    Ok(quotient)
+}
+
+/// The length of a fixed length field in nanoseconds
+///
+/// # Panics
+/// Panics if the length of the field depends on the date.
+fn field_nanoseconds(field: DateTimeField) -> i128 {
+    match field {
+        DateTimeField::Week => 7 * SECONDS_PER_DAY * NANOSECONDS_PER_SECOND,
+        DateTimeField::Day => SECONDS_PER_DAY * NANOSECONDS_PER_SECOND,
+        DateTimeField::Hour => 3_600 * NANOSECONDS_PER_SECOND,
+        DateTimeField::Minute => 60 * NANOSECONDS_PER_SECOND,
+        DateTimeField::Second => NANOSECONDS_PER_SECOND,
+        DateTimeField::Millisecond => 1_000_000,
+        DateTimeField::Microsecond => 1_000,
+        DateTimeField::Nanosecond => 1,
+        _ => panic!("{field} does not have a fixed length"),
+    }
+}
+
+/// The number of time units in a fixed length field if the field is at least as long as the
+/// time unit, e.g. 86,400 for days of timestamps in seconds, and `None` otherwise
+///
+/// # Panics
+/// Panics if the length of the field depends on the date.
+pub(super) fn units_per_field(field: DateTimeField, time_unit: PoSQLTimeUnit) -> Option<i128> {
+    let unit_nanoseconds = unit_nanoseconds(time_unit);
+    let field_nanoseconds = field_nanoseconds(field);
+    (field_nanoseconds >= unit_nanoseconds).then(|| field_nanoseconds / unit_nanoseconds)
+}
+
+/// The length of a time unit in nanoseconds
+fn unit_nanoseconds(time_unit: PoSQLTimeUnit) -> i128 {
+    match time_unit {
+        PoSQLTimeUnit::Second => NANOSECONDS_PER_SECOND,
+        PoSQLTimeUnit::Millisecond => 1_000_000,
+        PoSQLTimeUnit::Microsecond => 1_000,
+        PoSQLTimeUnit::Nanosecond => 1,
+    }
+}
+
+/// The number of time units in a day
+fn units_per_day(time_unit: PoSQLTimeUnit) -> i128 {
+    SECONDS_PER_DAY * NANOSECONDS_PER_SECOND / unit_nanoseconds(time_unit)
+}
+
+/// The offset of the time zone in time units
+fn offset_units(time_unit: PoSQLTimeUnit, timezone: PoSQLTimeZone) -> i128 {
+    i128::from(timezone.offset()) * (NANOSECONDS_PER_SECOND / unit_nanoseconds(time_unit))
+}
+
+/// The number of whole fields, e.g. hours, since 1970-01-01 00:00:00 in local time
+fn count_fields<A: TimestampArithmetic>(
+    a: &mut A,
+    local: A::Value,
+    field: DateTimeField,
+    time_unit: PoSQLTimeUnit,
+) -> Result<A::Value, ProofError> {
+    if let Some(units) = units_per_field(field, time_unit) {
+        floor_div(a, local, units)
+    } else {
+        let fields_per_unit = unit_nanoseconds(time_unit) / field_nanoseconds(field);
+        Ok(a.linear(&[(fields_per_unit, local)], 0))
+    }
+}
+
+/// `value mod modulus` for a positive `modulus`
+fn modulo<A: TimestampArithmetic>(
+    a: &mut A,
+    value: A::Value,
+    modulus: i128,
+) -> Result<A::Value, ProofError> {
+    let quotient = floor_div(a, value, modulus)?;
+    Ok(a.linear(&[(1, value), (-modulus, quotient)], 0))
+}
+
+/// The number of days in the years of a 400 year cycle before its year `year_of_cycle`
+fn days_before_year<A: TimestampArithmetic>(
+    a: &mut A,
+    year_of_cycle: A::Value,
+) -> Result<A::Value, ProofError> {
+    let leap_years = floor_div(a, year_of_cycle, 4)?;
+    let centuries = floor_div(a, year_of_cycle, 100)?;
+    let leap_centuries = floor_div(a, year_of_cycle, 400)?;
+    Ok(a.linear(
+        &[
+            (365, year_of_cycle),
+            (1, leap_years),
+            (-1, centuries),
+            (1, leap_centuries),
+        ],
+        0,
+    ))
+}
+
+/// [`days_before_year`] on integers
+fn days_before_year_of_cycle(year_of_cycle: i128) -> i128 {
+    365 * year_of_cycle + year_of_cycle / 4 - year_of_cycle / 100 + year_of_cycle / 400
+}
+
+/// The year of a 400 year cycle that its day `day_of_cycle` is in
+fn year_of_cycle(day_of_cycle: i128) -> i128 {
+    let mut year = day_of_cycle / 366;
+    while days_before_year_of_cycle(year + 1) <= day_of_cycle {
+        year += 1;
+    }
+    year
+}
+
+/// A date split into its year and the day of that year
+struct YearAndDay<V> {
+    year: V,
+    /// The zero-based day of the year
+    day_of_year: V,
+    /// `1` if the year is a leap year and `0` otherwise
+    is_leap_year: V,
+}
+
+/// The date of a day counted from 1970-01-01
+fn year_and_day<A: TimestampArithmetic>(
+    a: &mut A,
+    days: A::Value,
+) -> Result<YearAndDay<A::Value>, ProofError> {
+    let days_since_year_one = a.linear(&[(1, days)], DAYS_BEFORE_EPOCH);
+    let cycle = floor_div(a, days_since_year_one, DAYS_PER_400_YEARS)?;
+    let day_of_cycle = a.linear(&[(1, days_since_year_one), (-DAYS_PER_400_YEARS, cycle)], 0);
+
+    // The year is the unique `y` with days_before_year(y) <= day_of_cycle < days_before_year(y + 1)
+    let year_of_cycle = a.witness(day_of_cycle, year_of_cycle)?;
+    let next_year_of_cycle = a.linear(&[(1, year_of_cycle)], 1);
+    let year_start = days_before_year(a, year_of_cycle)?;
+    let next_year_start = days_before_year(a, next_year_of_cycle)?;
+    let day_of_year = a.linear(&[(1, day_of_cycle), (-1, year_start)], 0);
+    let days_left = a.linear(&[(1, next_year_start), (-1, day_of_cycle)], -1);
+    a.check_non_negative(day_of_year)?;
+    a.check_non_negative(days_left)?;
+
+    Ok(YearAndDay {
+        year: a.linear(&[(400, cycle), (1, year_of_cycle)], 1),
+        day_of_year,
+        is_leap_year: a.linear(&[(1, next_year_start), (-1, year_start)], -365),
+    })
+}
+
+/// The month and the day of the month of a date
+fn month_and_day<A: TimestampArithmetic>(
+    a: &mut A,
+    date: &YearAndDay<A::Value>,
+) -> Result<(A::Value, A::Value), ProofError> {
+    // Count the days from March 1, where January and February belong to the previous year
+    let days_since_march = a.linear(
+        &[(1, date.day_of_year), (-1, date.is_leap_year)],
+        -DAYS_BEFORE_MARCH,
+    );
+    let is_january_or_february = a.is_negative(days_since_march)?;
+    let leap_january_or_february = a.multiply(is_january_or_february, date.is_leap_year)?;
+    let day_of_march_year = a.linear(
+        &[
+            (1, days_since_march),
+            (365, is_january_or_february),
+            (1, leap_january_or_february),
+        ],
+        0,
+    );
+
+    // Months from March on alternate between 31 and 30 days, except for February at the end
+    let shifted_day = a.linear(&[(5, day_of_march_year)], 2);
+    let month_of_march_year = floor_div(a, shifted_day, 153)?;
+    let shifted_month = a.linear(&[(153, month_of_march_year)], 2);
+    let month_start = floor_div(a, shifted_month, 5)?;
+    let day = a.linear(&[(1, day_of_march_year), (-1, month_start)], 1);
+    let month = a.linear(
+        &[(1, month_of_march_year), (-12, is_january_or_february)],
+        3,
+    );
+    Ok((month, day))
+}
+
+/// Returns true if the length of `field` does not depend on the date
+fn has_fixed_length(field: DateTimeField) -> bool {
+    matches!(
+        field,
+        DateTimeField::Week
+            | DateTimeField::Day
+            | DateTimeField::Hour
+            | DateTimeField::Minute
+            | DateTimeField::Second
+            | DateTimeField::Millisecond
+            | DateTimeField::Microsecond
+            | DateTimeField::Nanosecond
+    )
+}
+
+/// The number of time units in an interval of `value` fields, if the length of the field is
+/// fixed and the interval is a whole number of time units that fits into an `i64`
+pub(super) fn interval_units(
+    value: i64,
+    field: DateTimeField,
+    time_unit: PoSQLTimeUnit,
+) -> Option<i64> {
+    if !has_fixed_length(field) {
+        return None;
+    }
+    let nanoseconds = i128::from(value) * field_nanoseconds(field);
+    let unit_nanoseconds = unit_nanoseconds(time_unit);
+    if nanoseconds % unit_nanoseconds == 0 {
+        i64::try_from(nanoseconds / unit_nanoseconds).ok()
+    } else {
+        None
+    }
+}
+
+/// Returns true if `DATE_TRUNC` to `field` is supported
+pub(super) fn is_date_trunc_supported(field: DateTimeField) -> bool {
+    !matches!(
+        field,
+        DateTimeField::Quarter | DateTimeField::DayOfWeek | DateTimeField::DayOfYear
+    )
+}
+
+/// Returns true if `EXTRACT` of `field` is supported
+pub(super) fn is_extract_supported(field: DateTimeField) -> bool {
+    !matches!(field, DateTimeField::Week)
+}
+
+/// `DATE_TRUNC(field, timestamps)` of timestamps in `time_unit`, truncated in `timezone`
+///
+/// # Panics
+/// Panics if the field is not supported, see [`is_date_trunc_supported`].
+pub(super) fn date_trunc<A: TimestampArithmetic>(
+    a: &mut A,
+    timestamps: A::Value,
+    field: DateTimeField,
+    time_unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+) -> Result<A::Value, ProofError> {
+    let offset = offset_units(time_unit, timezone);
+    let local = a.linear(&[(1, timestamps)], offset);
+    match field {
+        DateTimeField::Year | DateTimeField::Month => {
+            let day_units = units_per_day(time_unit);
+            let days = floor_div(a, local, day_units)?;
+            let date = year_and_day(a, days)?;
+            let start_day = if field == DateTimeField::Year {
+                a.linear(&[(1, days), (-1, date.day_of_year)], 0)
+            } else {
+                let (_, day) = month_and_day(a, &date)?;
+                a.linear(&[(1, days), (-1, day)], 1)
+            };
+            Ok(a.linear(&[(day_units, start_day)], -offset))
+        }
+        DateTimeField::Quarter | DateTimeField::DayOfWeek | DateTimeField::DayOfYear => {
+            panic!("DATE_TRUNC does not support {field}")
+        }
+        _ => {
+            let Some(period) = units_per_field(field, time_unit) else {
+                return Ok(timestamps);
+            };
+            // Weeks start on Monday
+            let shift = if field == DateTimeField::Week {
+                DAYS_SINCE_MONDAY_AT_EPOCH * units_per_day(time_unit)
+            } else {
+                0
+            };
+            let shifted = a.linear(&[(1, local)], shift);
+            let periods = floor_div(a, shifted, period)?;
+            Ok(a.linear(&[(period, periods)], -shift - offset))
+        }
+    }
+}
+
+/// `EXTRACT(field FROM timestamps)` of timestamps in `time_unit`, in local time of `timezone`
+///
+/// `SECOND` is the whole number of seconds, while `MILLISECOND`, `MICROSECOND` and `NANOSECOND`
+/// include the seconds, e.g. `EXTRACT(MILLISECOND FROM ts)` is `12345` at `12:34:12.345`.
+///
+/// # Panics
+/// Panics if the field is not supported, see [`is_extract_supported`].
+pub(super) fn extract<A: TimestampArithmetic>(
+    a: &mut A,
+    timestamps: A::Value,
+    field: DateTimeField,
+    time_unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+) -> Result<A::Value, ProofError> {
+    let local = a.linear(&[(1, timestamps)], offset_units(time_unit, timezone));
+    match field {
+        DateTimeField::Hour => {
+            let hours = count_fields(a, local, field, time_unit)?;
+            modulo(a, hours, 24)
+        }
+        DateTimeField::Minute | DateTimeField::Second => {
+            let count = count_fields(a, local, field, time_unit)?;
+            modulo(a, count, 60)
+        }
+        DateTimeField::Millisecond | DateTimeField::Microsecond | DateTimeField::Nanosecond => {
+            let count = count_fields(a, local, field, time_unit)?;
+            modulo(
+                a,
+                count,
+                field_nanoseconds(DateTimeField::Minute) / field_nanoseconds(field),
+            )
+        }
+        DateTimeField::Week => panic!("EXTRACT does not support {field}"),
+        _ => {
+            let days = floor_div(a, local, units_per_day(time_unit))?;
+            if field == DateTimeField::DayOfWeek {
+                // 1970-01-01 is a Thursday, and Sunday is 0
+                let shifted = a.linear(&[(1, days)], 4);
+                return modulo(a, shifted, 7);
+            }
+            let date = year_and_day(a, days)?;
+            match field {
+                DateTimeField::Year => Ok(date.year),
+                DateTimeField::DayOfYear => Ok(a.linear(&[(1, date.day_of_year)], 1)),
+                _ => {
+                    let (month, day) = month_and_day(a, &date)?;
+                    match field {
+                        DateTimeField::Month => Ok(month),
+                        DateTimeField::Day => Ok(day),
+                        _ => {
+                            let shifted = a.linear(&[(1, month)], 2);
+                            floor_div(a, shifted, 3)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
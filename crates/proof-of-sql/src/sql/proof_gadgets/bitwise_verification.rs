@@ -15,7 +15,7 @@ pub fn verify_constant_sign_decomposition<S: Scalar>(
 ) -> Result<(), ProofError> {
     assert!(
         dist.is_valid()
-            && dist.is_within_acceptable_range()
+            && dist.is_within_acceptable_comparison_range()
             && dist.num_varying_bits() == bit_evals.len()
             && !dist.has_varying_sign_bit()
     );
@@ -49,7 +49,7 @@ pub fn verify_constant_abs_decomposition<S: Scalar>(
 ) -> Result<(), ProofError> {
     assert!(
         dist.is_valid()
-            && dist.is_within_acceptable_range()
+            && dist.is_within_acceptable_comparison_range()
             && dist.num_varying_bits() == 1
             && dist.has_varying_sign_bit()
     );
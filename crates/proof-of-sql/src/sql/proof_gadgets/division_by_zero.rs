@@ -0,0 +1,160 @@
+use crate::{
+    base::{proof::ProofError, scalar::Scalar},
+    sql::proof::{
+        FinalRoundBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+
+/// Prove the number of rows with a division by zero.
+///
+/// Each indicator comes with the selection of the rows on which it counts, or `None` if it
+/// counts on every row. Let `m` be the number of indicators. We prove that
+/// ```text
+/// sum(selection_0 * indicator_0 + ... + selection_(m-1) * indicator_(m-1)) = count
+/// ```
+/// by proving that the sum of the left hand side minus `count` times the column that is one on
+/// the first row only is zero. The verifier reports a nonzero count as
+/// [`QueryError::DivisionByZero`](crate::sql::proof::QueryError::DivisionByZero).
+///
+/// Nothing is proven if there are no indicators.
+///
+/// This must be paired with [`verify_division_by_zero`] on the verifier side.
+pub(crate) fn final_round_evaluate_division_by_zero<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    indicators: &[(&'a [bool], Option<&'a [bool]>)],
+) {
+    if indicators.is_empty() {
+        return;
+    }
+    let count: usize = indicators
+        .iter()
+        .map(|(indicator, selection)| {
+            (0..indicator.len())
+                .filter(|&i| indicator[i] && selection.map_or(true, |selection| selection[i]))
+                .count()
+        })
+        .sum();
+    builder.produce_division_by_zero_count(count);
+    let singleton: &'a [bool] = alloc.alloc_slice_fill_copy(1, true);
+
+    // subpolynomial: selection_0 * indicator_0 + ... + selection_(m-1) * indicator_(m-1) - count * singleton
+    let terms: Vec<SumcheckSubpolynomialTerm<'a, S>> = indicators
+        .iter()
+        .map(|&(indicator, selection)| match selection {
+            Some(selection) => (
+                S::one(),
+                vec![Box::new(selection) as Box<_>, Box::new(indicator) as Box<_>],
+            ),
+            None => (S::one(), vec![Box::new(indicator) as Box<_>]),
+        })
+        .chain([(
+            -S::from([count as u64, 0, 0, 0]),
+            vec![Box::new(singleton) as Box<_>],
+        )])
+        .collect();
+    builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::ZeroSum, terms);
+}
+
+/// Verify the number of rows with a division by zero, see [`final_round_evaluate_division_by_zero`].
+///
+/// `indicator_evals` contains the evaluation of each indicator along with the evaluation of its
+/// selection, if any.
+pub(crate) fn verify_division_by_zero<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    indicator_evals: &[(S, Option<S>)],
+) -> Result<(), ProofError> {
+    if indicator_evals.is_empty() {
+        return Ok(());
+    }
+    let count = builder.try_consume_division_by_zero_count()?;
+    let singleton_one_eval = builder.mle_evaluations.singleton_one_evaluation;
+    let degree = if indicator_evals
+        .iter()
+        .any(|(_, selection_eval)| selection_eval.is_some())
+    {
+        2
+    } else {
+        1
+    };
+
+    // subpolynomial: selection_0 * indicator_0 + ... + selection_(m-1) * indicator_(m-1) - count * singleton
+    let sum_eval: S = indicator_evals
+        .iter()
+        .map(|&(indicator_eval, selection_eval)| {
+            selection_eval.map_or(indicator_eval, |selection_eval| {
+                selection_eval * indicator_eval
+            })
+        })
+        .sum();
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        sum_eval - S::from([count as u64, 0, 0, 0]) * singleton_one_eval,
+        degree,
+    )?;
+    Ok(())
+}
+
+/// Restrict the indicators of the rows with a zero divisor in a branch of a `CASE` to the rows
+/// on which the branch is taken, i.e. where `condition` is `is_then_branch`, and produce them.
+///
+/// This way, a `CASE` can guard a division against a zero divisor.
+///
+/// This must be paired with [`verify_branch_division_by_zero`] on the verifier side.
+pub(crate) fn final_round_evaluate_branch_division_by_zero<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    indicators: &[&'a [bool]],
+    condition: &'a [bool],
+    is_then_branch: bool,
+) {
+    for &indicator in indicators {
+        let branch_indicator: &'a [bool] = alloc.alloc_slice_fill_with(indicator.len(), |i| {
+            indicator[i] && condition[i] == is_then_branch
+        });
+        builder.produce_intermediate_mle(branch_indicator);
+
+        // subpolynomial: branch_indicator - indicator * condition
+        // or, for the else branch: branch_indicator - indicator + indicator * condition
+        let mut terms: Vec<SumcheckSubpolynomialTerm<'a, S>> =
+            vec![(S::one(), vec![Box::new(branch_indicator)])];
+        if is_then_branch {
+            terms.push((-S::one(), vec![Box::new(indicator), Box::new(condition)]));
+        } else {
+            terms.push((-S::one(), vec![Box::new(indicator)]));
+            terms.push((S::one(), vec![Box::new(indicator), Box::new(condition)]));
+        }
+        builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+        builder.produce_division_by_zero_indicator(branch_indicator);
+    }
+}
+
+/// The counterpart of [`final_round_evaluate_branch_division_by_zero`].
+pub(crate) fn verify_branch_division_by_zero<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    indicator_evals: &[S],
+    condition_eval: S,
+    is_then_branch: bool,
+) -> Result<(), ProofError> {
+    for &indicator_eval in indicator_evals {
+        let branch_indicator_eval = builder.try_consume_mle_evaluation()?;
+
+        // subpolynomial: branch_indicator - indicator * condition
+        // or, for the else branch: branch_indicator - indicator + indicator * condition
+        let branch_eval = if is_then_branch {
+            indicator_eval * condition_eval
+        } else {
+            indicator_eval - indicator_eval * condition_eval
+        };
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            branch_indicator_eval - branch_eval,
+            2,
+        )?;
+        builder.produce_division_by_zero_indicator_evaluation(branch_indicator_eval);
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use super::{prover_evaluate_sign, verifier_evaluate_sign};
+use crate::{
+    base::{proof::ProofError, scalar::Scalar},
+    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+
+/// Compute the witness for [`final_round_evaluate_extremum`].
+///
+/// `group_indexes` contains the index of the group of each row, or `None` if the row is not selected,
+/// and `extrema` contains the extremum of each group. The witness marks, for each group, the first
+/// row of the group that attains the extremum.
+///
+/// # Panics
+/// Panics if some group does not attain its extremum.
+pub(crate) fn compute_extremum_witness<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &[S],
+    group_indexes: &[Option<usize>],
+    extrema: &[S],
+) -> &'a [bool] {
+    let mut is_attained = vec![false; extrema.len()];
+    let witness = alloc.alloc_slice_fill_iter(column.iter().zip(group_indexes).map(
+        |(value, group_index)| match group_index {
+            Some(j) if !is_attained[*j] && *value == extrema[*j] => {
+                is_attained[*j] = true;
+                true
+            }
+            _ => false,
+        },
+    ));
+    assert!(
+        is_attained.iter().all(|&b| b),
+        "every group should attain its extremum"
+    );
+    witness
+}
+
+/// Prove that `extremum` is the maximum (if `MAX` is set) or minimum of `column` on the selected rows.
+///
+/// Here `extremum` is the column that assigns to each selected row the extremum of its group
+/// and `0` to all other rows. It is up to the caller to prove that `extremum` is constant on each group.
+///
+/// We prove that `diff = selection * ±(extremum - column)` is nonnegative, i.e. that `extremum` bounds
+/// `column` on the selected rows. We then commit to the `witness` column computed by
+/// [`compute_extremum_witness`] and prove that it is boolean, only marks selected rows and
+/// only marks rows where `column = extremum`. Finally, the caller must prove that the
+/// `witness` marks at least one row of each group.
+///
+/// # Panics
+/// Panics if `extremum` does not bound `column` on the selected rows.
+pub(crate) fn final_round_evaluate_extremum<'a, S: Scalar, const MAX: bool>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    selection: &'a [bool],
+    column: &'a [S],
+    extremum: &'a [S],
+    witness: &'a [bool],
+) {
+    let sign = if MAX { S::one() } else { -S::one() };
+    // diff = selection * ±(extremum - column)
+    let diff: &[S] = alloc.alloc_slice_fill_with(column.len(), |i| {
+        if selection[i] {
+            sign * (extremum[i] - column[i])
+        } else {
+            S::zero()
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+    // diff - ±(selection * extremum - selection * column) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (-sign, vec![Box::new(selection), Box::new(extremum)]),
+            (sign, vec![Box::new(selection), Box::new(column)]),
+        ],
+    );
+    let signs = prover_evaluate_sign(
+        builder,
+        alloc,
+        diff,
+        #[cfg(test)]
+        false,
+    );
+    assert!(
+        signs.iter().all(|&s| !s),
+        "extremum does not bound the column"
+    );
+
+    builder.produce_intermediate_mle(witness);
+    // witness * witness - witness = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(witness), Box::new(witness)]),
+            (-S::one(), vec![Box::new(witness)]),
+        ],
+    );
+    // witness - witness * selection = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(witness)]),
+            (-S::one(), vec![Box::new(witness), Box::new(selection)]),
+        ],
+    );
+    // witness * extremum - witness * column = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(witness), Box::new(extremum)]),
+            (-S::one(), vec![Box::new(witness), Box::new(column)]),
+        ],
+    );
+}
+
+/// Verify that a column is the maximum (if `MAX` is set) or minimum of another column
+/// on the selected rows and return the evaluation of the witness.
+///
+/// See [`final_round_evaluate_extremum`].
+pub(crate) fn verify_extremum<S: Scalar, const MAX: bool>(
+    builder: &mut VerificationBuilder<S>,
+    selection_eval: S,
+    column_eval: S,
+    extremum_eval: S,
+    one_eval: S,
+) -> Result<S, ProofError> {
+    let sign = if MAX { S::one() } else { -S::one() };
+    let diff_eval = builder.try_consume_mle_evaluation()?;
+    // diff - ±(selection * extremum - selection * column) = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        diff_eval - sign * selection_eval * (extremum_eval - column_eval),
+        2,
+    )?;
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if !sign_eval.is_zero() {
+        Err(ProofError::VerificationError {
+            error: "extremum does not bound the column",
+        })?;
+    }
+
+    let witness_eval = builder.try_consume_mle_evaluation()?;
+    // witness * witness - witness = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        witness_eval * witness_eval - witness_eval,
+        2,
+    )?;
+    // witness - witness * selection = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        witness_eval - witness_eval * selection_eval,
+        2,
+    )?;
+    // witness * extremum - witness * column = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        witness_eval * (extremum_eval - column_eval),
+        2,
+    )?;
+    Ok(witness_eval)
+}
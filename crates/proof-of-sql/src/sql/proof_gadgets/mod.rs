@@ -3,8 +3,37 @@ mod bitwise_verification;
 use bitwise_verification::{verify_constant_abs_decomposition, verify_constant_sign_decomposition};
 #[cfg(test)]
 mod bitwise_verification_test;
+mod division_by_zero;
+pub(crate) use division_by_zero::{
+    final_round_evaluate_branch_division_by_zero, final_round_evaluate_division_by_zero,
+    verify_branch_division_by_zero, verify_division_by_zero,
+};
+mod extremum;
+pub(crate) use extremum::{
+    compute_extremum_witness, final_round_evaluate_extremum, verify_extremum,
+};
+mod order_statistic;
+pub(crate) use order_statistic::{
+    count_by_group, final_round_evaluate_order_statistic, final_round_evaluate_rank,
+    verify_order_statistic, verify_rank, Rank,
+};
+mod monotonic;
+#[allow(unused_imports)]
+pub(crate) use monotonic::{
+    final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+};
 mod sign_expr;
-pub(crate) use sign_expr::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign};
+pub(crate) use sign_expr::{
+    prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_comparison_sign,
+    verifier_evaluate_sign,
+};
+mod shift;
+pub(crate) use shift::{final_round_evaluate_shift, first_round_evaluate_shift, verify_shift};
+mod sorted;
+pub(crate) use sorted::{
+    final_round_evaluate_sorted, final_round_evaluate_sorted_with_ties,
+    first_round_evaluate_sorted, verify_sorted, verify_sorted_with_ties,
+};
 pub mod range_check;
 #[cfg(all(test, feature = "blitzar"))]
 pub mod range_check_test;
@@ -0,0 +1,222 @@
+use super::{prover_evaluate_sign, verifier_evaluate_sign};
+use crate::{
+    base::{proof::ProofError, scalar::Scalar, slice_ops},
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+
+/// Compute the shifted column `0, column[0], ..., column[n-1]` and commit to it in the first round.
+///
+/// This must be paired with [`final_round_evaluate_monotonic`] on the prover side
+/// and [`verify_monotonic`] on the verifier side.
+///
+/// Note: the shifted column is committed before the post-result challenges are drawn
+/// since it takes part in a multiset argument with the original column.
+pub(crate) fn first_round_evaluate_monotonic<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    column: &'a [S],
+) {
+    let shifted_column = alloc.alloc_slice_fill_with(column.len() + 1, |i| {
+        if i == 0 {
+            S::zero()
+        } else {
+            column[i - 1]
+        }
+    });
+    builder.produce_intermediate_mle(shifted_column as &[_]);
+    builder.produce_rho_evaluation_length(column.len() + 1);
+    builder.produce_one_evaluation_length(column.len() + 1);
+}
+
+/// Prove that `column` is monotonic.
+///
+/// If `STRICT` is set, consecutive entries must differ, and if `ASC` is set,
+/// the column must be increasing rather than decreasing.
+///
+/// Let `n` be the length of `column` and `shifted_column` be the column `0, column[0], ..., column[n-1]`
+/// committed to in [`first_round_evaluate_monotonic`]. We first prove that the shifted column is
+/// actually a shift of `column` by proving the multiset equality
+/// ```text
+/// {(i + 1, column[i]) : 0 <= i < n} = {(i, shifted_column[i]) : 1 <= i <= n}
+/// ```
+/// We then prove that the sign of `diff = ±(column - shifted_column) - STRICT` is nonnegative
+/// on the indices `1..n-1`.
+///
+/// # Panics
+/// Panics if `column` is not monotonic in the requested order.
+pub(crate) fn final_round_evaluate_monotonic<'a, S: Scalar, const STRICT: bool, const ASC: bool>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    column: &'a [S],
+) {
+    let num_rows = column.len();
+    let shifted_column: &[S] =
+        alloc.alloc_slice_fill_with(
+            num_rows + 1,
+            |i| {
+                if i == 0 {
+                    S::zero()
+                } else {
+                    column[i - 1]
+                }
+            },
+        );
+    let rho: &[S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| S::from([i as u64, 0, 0, 0]));
+    let chi_n: &[bool] = alloc.alloc_slice_fill_copy(num_rows, true);
+    let chi_n_plus_1: &[bool] = alloc.alloc_slice_fill_copy(num_rows + 1, true);
+    let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+
+    // c_star = 1 / (1 + alpha * (column + beta * (rho + 1)))
+    let c_star: &mut [S] = alloc.alloc_slice_fill_with(num_rows, |i| {
+        S::one() + alpha * (column[i] + beta * (rho[i] + S::one()))
+    });
+    slice_ops::batch_inversion(c_star);
+    let c_star: &[S] = c_star;
+    // d_star = 1 / (1 + alpha * (shifted_column + beta * rho))
+    let d_star: &mut [S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+        S::one() + alpha * (shifted_column[i] + beta * rho[i])
+    });
+    slice_ops::batch_inversion(d_star);
+    let d_star: &[S] = d_star;
+    builder.produce_intermediate_mle(c_star);
+    builder.produce_intermediate_mle(d_star);
+
+    // c_star + alpha * c_star * column + alpha * beta * c_star * rho + alpha * beta * c_star - chi_n = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one() + alpha * beta, vec![Box::new(c_star)]),
+            (alpha, vec![Box::new(c_star), Box::new(column)]),
+            (alpha * beta, vec![Box::new(c_star), Box::new(rho)]),
+            (-S::one(), vec![Box::new(chi_n)]),
+        ],
+    );
+    // d_star + alpha * d_star * shifted_column + alpha * beta * d_star * rho - chi_(n+1) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(d_star)]),
+            (alpha, vec![Box::new(d_star), Box::new(shifted_column)]),
+            (alpha * beta, vec![Box::new(d_star), Box::new(rho)]),
+            (-S::one(), vec![Box::new(chi_n_plus_1)]),
+        ],
+    );
+    // sum c_star - d_star + d_star * chi_1 = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(c_star)]),
+            (-S::one(), vec![Box::new(d_star)]),
+            (S::one(), vec![Box::new(d_star), Box::new(chi_1)]),
+        ],
+    );
+
+    // diff = ±(column - shifted_column) - STRICT on the indices 1..n-1 and 0 elsewhere
+    let strict_offset = if STRICT { S::one() } else { S::zero() };
+    let diff: &[S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+        if i == 0 || i >= num_rows {
+            S::zero()
+        } else if ASC {
+            column[i] - shifted_column[i] - strict_offset
+        } else {
+            shifted_column[i] - column[i] - strict_offset
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+    let sign = if ASC { S::one() } else { -S::one() };
+    // Note that `chi_n - chi_1 * chi_n` is the indicator of the indices 1..n-1, even if n = 0.
+    // diff - (±(column - shifted_column) - STRICT) * (chi_n - chi_1 * chi_n) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (-sign, vec![Box::new(column)]),
+            (sign, vec![Box::new(column), Box::new(chi_1)]),
+            (sign, vec![Box::new(shifted_column), Box::new(chi_n)]),
+            (
+                -sign,
+                vec![Box::new(shifted_column), Box::new(chi_1), Box::new(chi_n)],
+            ),
+            (strict_offset, vec![Box::new(chi_n)]),
+            (-strict_offset, vec![Box::new(chi_1), Box::new(chi_n)]),
+        ],
+    );
+    let signs = prover_evaluate_sign(
+        builder,
+        alloc,
+        diff,
+        #[cfg(test)]
+        false,
+    );
+    assert!(
+        signs.iter().all(|&s| !s),
+        "column is not monotonic in the requested order"
+    );
+}
+
+/// Verify that a column is monotonic.
+///
+/// See [`final_round_evaluate_monotonic`].
+pub(crate) fn verify_monotonic<S: Scalar, const STRICT: bool, const ASC: bool>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_eval: S,
+    chi_n_eval: S,
+) -> Result<(), ProofError> {
+    // First round
+    let shifted_column_eval = builder.try_consume_first_round_mle_evaluation()?;
+    let rho_eval = builder.try_consume_rho_evaluation()?;
+    let chi_n_plus_1_eval = builder.try_consume_one_evaluation()?;
+    let singleton_one_eval = builder.mle_evaluations.singleton_one_evaluation;
+
+    // Final round
+    let c_star_eval = builder.try_consume_mle_evaluation()?;
+    let d_star_eval = builder.try_consume_mle_evaluation()?;
+    // c_star + alpha * c_star * column + alpha * beta * c_star * rho + alpha * beta * c_star - chi_n = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        c_star_eval * (S::one() + alpha * (column_eval + beta * (rho_eval + S::one())))
+            - chi_n_eval,
+        2,
+    )?;
+    // d_star + alpha * d_star * shifted_column + alpha * beta * d_star * rho - chi_(n+1) = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        d_star_eval * (S::one() + alpha * (shifted_column_eval + beta * rho_eval))
+            - chi_n_plus_1_eval,
+        2,
+    )?;
+    // sum c_star - d_star + d_star * chi_1 = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        c_star_eval - d_star_eval + d_star_eval * singleton_one_eval,
+        2,
+    )?;
+
+    let diff_eval = builder.try_consume_mle_evaluation()?;
+    let strict_offset = if STRICT { S::one() } else { S::zero() };
+    let sign = if ASC { S::one() } else { -S::one() };
+    // diff - (±(column - shifted_column) - STRICT) * (chi_n - chi_1 * chi_n) = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        diff_eval - sign * column_eval
+            + sign * column_eval * singleton_one_eval
+            + sign * shifted_column_eval * chi_n_eval * (S::one() - singleton_one_eval)
+            + strict_offset * chi_n_eval * (S::one() - singleton_one_eval),
+        3,
+    )?;
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, chi_n_plus_1_eval)?;
+    if !sign_eval.is_zero() {
+        Err(ProofError::VerificationError {
+            error: "column is not monotonic",
+        })?;
+    }
+    Ok(())
+}
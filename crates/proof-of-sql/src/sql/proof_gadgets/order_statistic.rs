@@ -0,0 +1,231 @@
+use super::{prover_evaluate_sign, verifier_evaluate_sign};
+use crate::{
+    base::{proof::ProofError, scalar::Scalar},
+    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+
+/// The rank, counting from `0`, of an order statistic among the `n` values of a group,
+/// which is `floor((numerator * n + offset) / denominator)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Rank {
+    numerator: u64,
+    denominator: u64,
+    offset: i64,
+}
+
+impl Rank {
+    /// Create the rank `floor((numerator * n + offset) / denominator)`
+    ///
+    /// # Panics
+    /// Panics if `denominator` is zero.
+    pub(crate) fn new(numerator: u64, denominator: u64, offset: i64) -> Self {
+        assert!(denominator > 0, "the denominator of a rank is positive");
+        Self {
+            numerator,
+            denominator,
+            offset,
+        }
+    }
+
+    /// The rank among `count` values
+    ///
+    /// # Panics
+    /// Panics if the rank is negative, which it is not for nonempty groups and the ranks of percentiles.
+    pub(crate) fn of(self, count: i64) -> usize {
+        let rank = (i128::from(self.numerator) * i128::from(count) + i128::from(self.offset))
+            .div_euclid(i128::from(self.denominator));
+        usize::try_from(rank).expect("ranks of nonempty groups are nonnegative")
+    }
+
+    /// The evaluations of `numerator * count + offset * ones - denominator * less_count`
+    /// and `denominator * less_or_equal_count - numerator * count - (offset + 1) * ones`,
+    /// which are both nonnegative exactly if `less_count <= rank < less_or_equal_count`
+    fn slacks<S: Scalar>(self, count: S, ones: S, less_count: S, less_or_equal_count: S) -> (S, S) {
+        let numerator = S::from(i128::from(self.numerator));
+        let denominator = S::from(i128::from(self.denominator));
+        let offset = S::from(self.offset);
+        (
+            numerator * count + offset * ones - denominator * less_count,
+            denominator * less_or_equal_count - numerator * count - (offset + S::one()) * ones,
+        )
+    }
+}
+
+/// Count, for each of the `num_groups` groups, the rows where `indicator` is set.
+///
+/// `group_indexes` contains the index of the group of each row, or `None` if the row is not selected.
+///
+/// # Panics
+/// Panics if a group index is out of bounds.
+pub(crate) fn count_by_group<'a>(
+    alloc: &'a Bump,
+    indicator: &[bool],
+    group_indexes: &[Option<usize>],
+    num_groups: usize,
+) -> &'a [i64] {
+    let counts = alloc.alloc_slice_fill_copy(num_groups, 0_i64);
+    for (&is_set, group_index) in indicator.iter().zip(group_indexes) {
+        if let (true, Some(j)) = (is_set, group_index) {
+            counts[*j] += 1;
+        }
+    }
+    counts
+}
+
+/// Prove, for each selected row, whether `column` is less than, and whether it is at most,
+/// the order statistic of the group of the row.
+///
+/// Here `order_statistic` is the column that assigns to each selected row the order statistic of its
+/// group and `0` to all other rows. It is up to the caller to prove that `order_statistic` is constant
+/// on each group.
+///
+/// Since the values are integers, we commit to `diff = selection * (column - order_statistic)` and prove
+/// that `less = sign(diff)` and `less_or_equal = sign(diff - selection)`, which are not set on the rows
+/// that are not selected. It is up to the caller to count these per group and to prove with
+/// [`final_round_evaluate_rank`] that the counting argument pins down the order statistic.
+///
+/// Returns `less` and `less_or_equal`.
+pub(crate) fn final_round_evaluate_order_statistic<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    selection: &'a [bool],
+    column: &'a [S],
+    order_statistic: &'a [S],
+) -> (&'a [bool], &'a [bool]) {
+    // diff = selection * (column - order_statistic)
+    let diff: &[S] = alloc.alloc_slice_fill_with(column.len(), |i| {
+        if selection[i] {
+            column[i] - order_statistic[i]
+        } else {
+            S::zero()
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+    // diff - selection * column + selection * order_statistic = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (-S::one(), vec![Box::new(selection), Box::new(column)]),
+            (
+                S::one(),
+                vec![Box::new(selection), Box::new(order_statistic)],
+            ),
+        ],
+    );
+    let diff_minus_selection: &[S] = alloc.alloc_slice_fill_with(diff.len(), |i| {
+        if selection[i] {
+            diff[i] - S::one()
+        } else {
+            S::zero()
+        }
+    });
+    let less = prover_evaluate_sign(
+        builder,
+        alloc,
+        diff,
+        #[cfg(test)]
+        false,
+    );
+    let less_or_equal = prover_evaluate_sign(
+        builder,
+        alloc,
+        diff_minus_selection,
+        #[cfg(test)]
+        false,
+    );
+    (less, less_or_equal)
+}
+
+/// Verify the proof of [`final_round_evaluate_order_statistic`] and return the evaluations of
+/// `less` and `less_or_equal`.
+pub(crate) fn verify_order_statistic<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    selection_eval: S,
+    column_eval: S,
+    order_statistic_eval: S,
+    one_eval: S,
+) -> Result<(S, S), ProofError> {
+    let diff_eval = builder.try_consume_mle_evaluation()?;
+    // diff - selection * column + selection * order_statistic = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        diff_eval - selection_eval * column_eval + selection_eval * order_statistic_eval,
+        2,
+    )?;
+    let less_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    let less_or_equal_eval = verifier_evaluate_sign(builder, diff_eval - selection_eval, one_eval)?;
+    Ok((less_eval, less_or_equal_eval))
+}
+
+/// Prove that the order statistics of some groups have the given `rank`.
+///
+/// `counts` contains the number of rows of each group, and `less_counts` and `less_or_equal_counts`
+/// the numbers of rows less than, respectively at most, the order statistic of the group. The caller
+/// must prove these counts, e.g. by summing the columns returned by
+/// [`final_round_evaluate_order_statistic`].
+/// `ones` weighs the offset of the rank and is usually all ones, but may be unset for empty groups.
+///
+/// A value is the order statistic of a given rank exactly if `less_count <= rank < less_or_equal_count`.
+/// Writing the rank as `floor((numerator * count + offset) / denominator)`, this is the case exactly if
+/// the two slacks of [`Rank`] are nonnegative, which we prove with the sign gadget.
+///
+/// # Panics
+/// Panics if some order statistic does not have the given rank.
+pub(crate) fn final_round_evaluate_rank<'a, S: Scalar + 'a>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    rank: Rank,
+    (counts, ones): (&[i64], &[bool]),
+    (less_counts, less_or_equal_counts): (&[i64], &[i64]),
+) {
+    let slacks = |i: usize| {
+        rank.slacks(
+            S::from(counts[i]),
+            S::from(ones[i]),
+            S::from(less_counts[i]),
+            S::from(less_or_equal_counts[i]),
+        )
+    };
+    let lower_slack: &[S] = alloc.alloc_slice_fill_with(counts.len(), |i| slacks(i).0);
+    let upper_slack: &[S] = alloc.alloc_slice_fill_with(counts.len(), |i| slacks(i).1);
+    for slack in [lower_slack, upper_slack] {
+        let signs = prover_evaluate_sign(
+            builder,
+            alloc,
+            slack,
+            #[cfg(test)]
+            false,
+        );
+        assert!(
+            signs.iter().all(|&s| !s),
+            "order statistic does not have its rank"
+        );
+    }
+}
+
+/// Verify the proof of [`final_round_evaluate_rank`].
+pub(crate) fn verify_rank<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    rank: Rank,
+    (count_eval, offset_weight_eval): (S, S),
+    (less_count_eval, less_or_equal_count_eval): (S, S),
+    one_eval: S,
+) -> Result<(), ProofError> {
+    let (lower_slack_eval, upper_slack_eval) = rank.slacks(
+        count_eval,
+        offset_weight_eval,
+        less_count_eval,
+        less_or_equal_count_eval,
+    );
+    for slack_eval in [lower_slack_eval, upper_slack_eval] {
+        if !verifier_evaluate_sign(builder, slack_eval, one_eval)?.is_zero() {
+            Err(ProofError::VerificationError {
+                error: "order statistic does not have its rank",
+            })?;
+        }
+    }
+    Ok(())
+}
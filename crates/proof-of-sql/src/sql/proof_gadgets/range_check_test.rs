@@ -22,7 +22,7 @@ impl ProverEvaluate for RangeCheckTestPlan {
     #[doc = " Evaluate the query, modify `FirstRoundBuilder` and return the result."]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         _alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
@@ -0,0 +1,174 @@
+use crate::{
+    base::{proof::ProofError, scalar::Scalar, slice_ops},
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialTerm,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_plans::{fold_columns, fold_vals},
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+
+/// Shift a column by one row, i.e. compute `0, column[0], ..., column[n-1]`.
+fn shift<'a, S: Scalar>(alloc: &'a Bump, column: &[S]) -> &'a [S] {
+    alloc.alloc_slice_fill_with(column.len() + 1, |i| {
+        if i == 0 {
+            S::zero()
+        } else {
+            column[i - 1]
+        }
+    })
+}
+
+/// Compute the shifted columns `0, column[0], ..., column[n-1]` and commit to them in the first round.
+///
+/// This must be paired with [`final_round_evaluate_shift`] on the prover side
+/// and [`verify_shift`] on the verifier side.
+///
+/// Note: the shifted columns are committed before the post-result challenges are drawn
+/// since they take part in a multiset argument with the original columns.
+pub(crate) fn first_round_evaluate_shift<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    columns: &[&'a [S]],
+    num_rows: usize,
+) {
+    for column in columns {
+        builder.produce_intermediate_mle(shift(alloc, column));
+    }
+    builder.produce_rho_evaluation_length(num_rows + 1);
+    builder.produce_one_evaluation_length(num_rows + 1);
+}
+
+/// Prove that the columns committed to in [`first_round_evaluate_shift`] are the shifts of `columns`.
+///
+/// Let `n` be the number of rows, `m` the number of columns and `shifted_j` be the shift of the
+/// `j`th column. We prove the multiset equality
+/// ```text
+/// {(i + 1, column_0[i], ..., column_(m-1)[i]) : 0 <= i < n} = {(i, shifted_0[i], ..., shifted_(m-1)[i]) : 1 <= i <= n}
+/// ```
+/// Since the indices on each side are distinct, this means that `shifted_j[i] = column_j[i - 1]`
+/// for `1 <= i <= n`. Note that `shifted_j[0]` is not constrained.
+///
+/// Returns the shifted columns.
+pub(crate) fn final_round_evaluate_shift<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[&'a [S]],
+    num_rows: usize,
+) -> Vec<&'a [S]> {
+    let shifted_columns = columns
+        .iter()
+        .map(|column| shift(alloc, column))
+        .collect::<Vec<_>>();
+    let rho: &[S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| S::from([i as u64, 0, 0, 0]));
+    let chi_n: &[bool] = alloc.alloc_slice_fill_copy(num_rows, true);
+    let chi_n_plus_1: &[bool] = alloc.alloc_slice_fill_copy(num_rows + 1, true);
+    let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+    let beta_m = (0..columns.len()).fold(S::one(), |acc, _| acc * beta);
+
+    // c_star = 1 / (1 + alpha * (fold(columns) + beta^m * (rho + 1)))
+    let c_star: &mut [S] = alloc.alloc_slice_fill_copy(num_rows, S::zero());
+    fold_columns(c_star, alpha, beta, columns);
+    c_star
+        .iter_mut()
+        .zip(rho)
+        .for_each(|(c, r)| *c += S::one() + alpha * beta_m * (*r + S::one()));
+    slice_ops::batch_inversion(c_star);
+    let c_star: &[S] = c_star;
+    // d_star = 1 / (1 + alpha * (fold(shifted_columns) + beta^m * rho))
+    let d_star: &mut [S] = alloc.alloc_slice_fill_copy(num_rows + 1, S::zero());
+    fold_columns(d_star, alpha, beta, &shifted_columns);
+    d_star
+        .iter_mut()
+        .zip(rho)
+        .for_each(|(d, r)| *d += S::one() + alpha * beta_m * *r);
+    slice_ops::batch_inversion(d_star);
+    let d_star: &[S] = d_star;
+    builder.produce_intermediate_mle(c_star);
+    builder.produce_intermediate_mle(d_star);
+
+    // c_star + alpha * c_star * fold(columns) + alpha * beta^m * c_star * rho + alpha * beta^m * c_star - chi_n = 0
+    let mut c_star_terms: Vec<SumcheckSubpolynomialTerm<S>> =
+        vec![(S::one() + alpha * beta_m, vec![Box::new(c_star)])];
+    let mut power = alpha;
+    for column in columns {
+        c_star_terms.push((power, vec![Box::new(c_star), Box::new(*column)]));
+        power *= beta;
+    }
+    c_star_terms.push((alpha * beta_m, vec![Box::new(c_star), Box::new(rho)]));
+    c_star_terms.push((-S::one(), vec![Box::new(chi_n)]));
+    builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, c_star_terms);
+    // d_star + alpha * d_star * fold(shifted_columns) + alpha * beta^m * d_star * rho - chi_(n+1) = 0
+    let mut d_star_terms: Vec<SumcheckSubpolynomialTerm<S>> =
+        vec![(S::one(), vec![Box::new(d_star)])];
+    let mut power = alpha;
+    for shifted_column in &shifted_columns {
+        d_star_terms.push((power, vec![Box::new(d_star), Box::new(*shifted_column)]));
+        power *= beta;
+    }
+    d_star_terms.push((alpha * beta_m, vec![Box::new(d_star), Box::new(rho)]));
+    d_star_terms.push((-S::one(), vec![Box::new(chi_n_plus_1)]));
+    builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, d_star_terms);
+    // sum c_star - d_star + d_star * chi_1 = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(c_star)]),
+            (-S::one(), vec![Box::new(d_star)]),
+            (S::one(), vec![Box::new(d_star), Box::new(chi_1)]),
+        ],
+    );
+    shifted_columns
+}
+
+/// Verify that some columns committed to in the first round are the shifts of the given columns.
+///
+/// See [`final_round_evaluate_shift`]. Returns the evaluations of the shifted columns
+/// along with the evaluation of `chi_(n+1)`.
+pub(crate) fn verify_shift<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_evals: &[S],
+    chi_n_eval: S,
+) -> Result<(Vec<S>, S), ProofError> {
+    // First round
+    let shifted_column_evals =
+        builder.try_consume_first_round_mle_evaluations(column_evals.len())?;
+    let rho_eval = builder.try_consume_rho_evaluation()?;
+    let chi_n_plus_1_eval = builder.try_consume_one_evaluation()?;
+    let singleton_one_eval = builder.mle_evaluations.singleton_one_evaluation;
+    let beta_m = (0..column_evals.len()).fold(S::one(), |acc, _| acc * beta);
+
+    // Final round
+    let c_star_eval = builder.try_consume_mle_evaluation()?;
+    let d_star_eval = builder.try_consume_mle_evaluation()?;
+    // c_star + alpha * c_star * fold(columns) + alpha * beta^m * c_star * rho + alpha * beta^m * c_star - chi_n = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        c_star_eval
+            * (S::one() + alpha * (fold_vals(beta, column_evals) + beta_m * (rho_eval + S::one())))
+            - chi_n_eval,
+        2,
+    )?;
+    // d_star + alpha * d_star * fold(shifted_columns) + alpha * beta^m * d_star * rho - chi_(n+1) = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        d_star_eval
+            * (S::one() + alpha * (fold_vals(beta, &shifted_column_evals) + beta_m * rho_eval))
+            - chi_n_plus_1_eval,
+        2,
+    )?;
+    // sum c_star - d_star + d_star * chi_1 = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        c_star_eval - d_star_eval + d_star_eval * singleton_one_eval,
+        2,
+    )?;
+    Ok((shifted_column_evals, chi_n_plus_1_eval))
+}
@@ -107,6 +107,38 @@ pub fn verifier_evaluate_sign<S: Scalar>(
 ) -> Result<S, ProofError> {
     // bit_distribution
     let dist = builder.try_consume_bit_distribution()?;
+    if !dist.is_within_acceptable_range() {
+        return Err(ProofError::VerificationError {
+            error: "bit distribution outside of acceptable range",
+        });
+    }
+    verifier_evaluate_sign_of_distribution(builder, &dist, eval, one_eval)
+}
+
+/// Verify the sign decomposition of the values a comparison takes the sign of.
+///
+/// This is [`verifier_evaluate_sign`] with the wider range of
+/// [`BitDistribution::is_within_acceptable_comparison_range`].
+///
+/// # Panics
+/// Panics if `bit_evals.last()` is `None`.
+pub fn verifier_evaluate_comparison_sign<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    eval: S,
+    one_eval: S,
+) -> Result<S, ProofError> {
+    let dist = builder.try_consume_bit_distribution()?;
+    verifier_evaluate_sign_of_distribution(builder, &dist, eval, one_eval)
+}
+
+/// # Panics
+/// Panics if `bit_evals.last()` is `None`.
+fn verifier_evaluate_sign_of_distribution<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    dist: &BitDistribution,
+    eval: S,
+    one_eval: S,
+) -> Result<S, ProofError> {
     let num_varying_bits = dist.num_varying_bits();
 
     // extract evaluations and commitmens of the multilinear extensions for the varying
@@ -122,14 +154,14 @@ pub fn verifier_evaluate_sign<S: Scalar>(
 
     // handle the special case of the sign bit being constant
     if !dist.has_varying_sign_bit() {
-        return verifier_const_sign_evaluate(&dist, eval, one_eval, &bit_evals);
+        return verifier_const_sign_evaluate(dist, eval, one_eval, &bit_evals);
     }
 
     // handle the special case of the absolute part being constant
     if dist.num_varying_bits() == 1 {
-        verify_constant_abs_decomposition(&dist, eval, one_eval, bit_evals[0])?;
+        verify_constant_abs_decomposition(dist, eval, one_eval, bit_evals[0])?;
     } else {
-        verify_bit_decomposition(builder, eval, one_eval, &bit_evals, &dist)?;
+        verify_bit_decomposition(builder, eval, one_eval, &bit_evals, dist)?;
     }
 
     Ok(*bit_evals.last().unwrap())
@@ -1,12 +1,18 @@
-use super::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign};
+use super::{
+    prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_comparison_sign,
+    verifier_evaluate_sign,
+};
 use crate::{
-    base::{bit::BitDistribution, polynomial::MultilinearExtension, scalar::Curve25519Scalar},
+    base::{
+        bit::BitDistribution, polynomial::MultilinearExtension, proof::ProofError,
+        scalar::Curve25519Scalar,
+    },
     sql::proof::{
         FinalRoundBuilder, SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
     },
 };
 use bumpalo::Bump;
-use num_traits::Zero;
+use num_traits::{One, Zero};
 
 #[test]
 fn prover_evaluation_generates_the_bit_distribution_of_a_constant_column() {
@@ -43,9 +49,11 @@ fn we_can_verify_a_constant_decomposition() {
     let sumcheck_evaluations = SumcheckMleEvaluations::new(
         data.len(),
         [data.len()],
+        [],
         &evaluation_point,
         &sumcheck_random_scalars,
         &[],
+        &[],
     );
     let one_evals = sumcheck_evaluations.one_evaluations.clone();
     let one_eval = one_evals.values().next().unwrap();
@@ -55,6 +63,8 @@ fn we_can_verify_a_constant_decomposition() {
         sumcheck_evaluations,
         &dists,
         &[],
+        &[],
+        Vec::new(),
         Vec::new(),
         Vec::new(),
         3,
@@ -75,9 +85,11 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
     let sumcheck_evaluations = SumcheckMleEvaluations::new(
         data.len(),
         [data.len()],
+        [],
         &evaluation_point,
         &sumcheck_random_scalars,
         &[],
+        &[],
     );
     let one_evals = sumcheck_evaluations.one_evaluations.clone();
     let one_eval = one_evals.values().next().unwrap();
@@ -87,6 +99,8 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
         sumcheck_evaluations,
         &dists,
         &[],
+        &[],
+        Vec::new(),
         Vec::new(),
         Vec::new(),
         3,
@@ -95,6 +109,81 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
     assert!(verifier_evaluate_sign(&mut builder, data_eval, *one_eval).is_err());
 }
 
+/// Verifies the sign of a constant column with the given bit distribution.
+fn verify_the_sign_of_a_constant_column(
+    data: &[Curve25519Scalar],
+    dist: BitDistribution,
+    verifier_evaluate: fn(
+        &mut VerificationBuilder<Curve25519Scalar>,
+        Curve25519Scalar,
+        Curve25519Scalar,
+    ) -> Result<Curve25519Scalar, ProofError>,
+) -> Result<Curve25519Scalar, ProofError> {
+    let dists = [dist];
+    let scalars = [Curve25519Scalar::from(97), Curve25519Scalar::from(3432)];
+    let sumcheck_random_scalars = SumcheckRandomScalars::new(&scalars, data.len(), 2);
+    let evaluation_point = [Curve25519Scalar::from(324), Curve25519Scalar::from(97)];
+    let sumcheck_evaluations = SumcheckMleEvaluations::new(
+        data.len(),
+        [data.len()],
+        [],
+        &evaluation_point,
+        &sumcheck_random_scalars,
+        &[],
+        &[],
+    );
+    let one_evals = sumcheck_evaluations.one_evaluations.clone();
+    let one_eval = one_evals.values().next().unwrap();
+
+    let mut builder = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        3,
+    );
+    let data_eval = data.evaluate_at_point(&evaluation_point);
+    verifier_evaluate(&mut builder, data_eval, *one_eval)
+}
+
+#[test]
+fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
+    let data = [Curve25519Scalar::zero(); 3];
+    let dist = BitDistribution::new::<Curve25519Scalar, _>(&data);
+    let mut negative_dist = dist.clone();
+    negative_dist.or_all[3] = 1 << 63;
+    assert_eq!(
+        verify_the_sign_of_a_constant_column(&data, dist, verifier_evaluate_sign).unwrap(),
+        Curve25519Scalar::zero()
+    );
+    assert_ne!(
+        verify_the_sign_of_a_constant_column(&data, negative_dist, verifier_evaluate_sign).unwrap(),
+        Curve25519Scalar::zero()
+    );
+}
+
+#[test]
+fn only_the_sign_of_a_comparison_can_be_proven_beyond_the_sum_of_two_128_bit_integers() {
+    let sum = Curve25519Scalar::from(i128::MIN) + Curve25519Scalar::from(i128::MIN);
+    let data = [-(sum + sum) + Curve25519Scalar::one(); 3];
+    let dist = BitDistribution::new::<Curve25519Scalar, _>(&data);
+    assert!(matches!(
+        verify_the_sign_of_a_constant_column(&data, dist.clone(), verifier_evaluate_sign),
+        Err(ProofError::VerificationError {
+            error: "bit distribution outside of acceptable range"
+        })
+    ));
+    assert_eq!(
+        verify_the_sign_of_a_constant_column(&data, dist, verifier_evaluate_comparison_sign)
+            .unwrap(),
+        Curve25519Scalar::zero()
+    );
+}
+
 #[test]
 fn we_can_compute_the_correct_sign_of_scalars_using_result_evaluate_sign_for_a_constant() {
     let data: &[Curve25519Scalar] = &[(-123).into(), (-123).into()];
@@ -0,0 +1,347 @@
+use super::{
+    final_round_evaluate_shift, first_round_evaluate_shift, prover_evaluate_sign,
+    verifier_evaluate_sign, verify_shift,
+};
+use crate::{
+    base::{proof::ProofError, scalar::Scalar, slice_ops},
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+
+/// Get `1` for ascending and `-1` for descending order.
+fn direction_sign<S: Scalar>(direction: OrderByDirection) -> S {
+    match direction {
+        OrderByDirection::Asc => S::one(),
+        OrderByDirection::Desc => -S::one(),
+    }
+}
+
+/// Compute the shifted columns `0, column[0], ..., column[n-1]` and commit to them in the first round.
+///
+/// This must be paired with [`final_round_evaluate_sorted`] on the prover side
+/// and [`verify_sorted`] on the verifier side.
+pub(crate) fn first_round_evaluate_sorted<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    columns: &[&'a [S]],
+    num_rows: usize,
+) {
+    if columns.is_empty() {
+        return;
+    }
+    first_round_evaluate_shift(builder, alloc, columns, num_rows);
+}
+
+/// Prove that the rows of `columns` are sorted lexicographically in the given directions.
+///
+/// Let `n` be the number of rows and `shifted_j` be the shift of the `j`th column committed to
+/// in [`first_round_evaluate_sorted`], which is proven by [`final_round_evaluate_shift`].
+/// On the indices `1..n-1` we then let `diff_j = ±(column_j - shifted_j)` and
+/// `eq_j = eq_(j-1) * (diff_j == 0)` be the indicator of the rows which are equal to the previous row
+/// on the first `j + 1` columns, where `eq_(-1) = chi_n`. The latter is proven by committing to
+/// `inv_j`, the inverse of `diff_j` where it is nonzero, and proving
+/// ```text
+/// eq_j - eq_(j-1) + eq_(j-1) * diff_j * inv_j = 0
+/// eq_j * diff_j = 0
+/// ```
+/// Finally, we prove that `eq_(j-1) * diff_j` is nonnegative for every `j`.
+///
+/// # Panics
+/// Panics if the rows are not sorted in the requested order.
+pub(crate) fn final_round_evaluate_sorted<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[&'a [S]],
+    directions: &[OrderByDirection],
+    num_rows: usize,
+) {
+    prove_sorted(
+        builder, alloc, alpha, beta, columns, directions, num_rows, false,
+    );
+}
+
+/// Prove that the rows of `columns` are sorted as in [`final_round_evaluate_sorted`], and return
+/// the proven indicators `eq_0, ..., eq_(m-1)` of the rows which are equal to the previous row on
+/// the first `1, ..., m` columns.
+///
+/// Note that `eq_j` is `0` outside of the indices `0..n-1` and `1` at the index `0`.
+/// The last indicator `eq_(m-1)` is committed to in addition to the ones committed to by
+/// [`final_round_evaluate_sorted`].
+///
+/// This must be paired with [`verify_sorted_with_ties`] on the verifier side.
+///
+/// # Panics
+/// Panics if the rows are not sorted in the requested order.
+pub(crate) fn final_round_evaluate_sorted_with_ties<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[&'a [S]],
+    directions: &[OrderByDirection],
+    num_rows: usize,
+) -> Vec<&'a [S]> {
+    prove_sorted(
+        builder, alloc, alpha, beta, columns, directions, num_rows, true,
+    )
+}
+
+/// See [`final_round_evaluate_sorted`] and [`final_round_evaluate_sorted_with_ties`].
+///
+/// # Panics
+/// Panics if the rows are not sorted in the requested order.
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+fn prove_sorted<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[&'a [S]],
+    directions: &[OrderByDirection],
+    num_rows: usize,
+    with_ties: bool,
+) -> Vec<&'a [S]> {
+    if columns.is_empty() {
+        return Vec::new();
+    }
+    let num_columns = columns.len();
+    let shifted_columns =
+        final_round_evaluate_shift(builder, alloc, alpha, beta, columns, num_rows);
+    let chi_n: &[bool] = alloc.alloc_slice_fill_copy(num_rows, true);
+    let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+
+    let mut ties = Vec::with_capacity(num_columns);
+    let mut eq_prev: &[S] = alloc.alloc_slice_fill_copy(num_rows, S::one());
+    for (j, ((column, shifted_column), direction)) in columns
+        .iter()
+        .copied()
+        .zip(shifted_columns.iter().copied())
+        .zip(directions)
+        .enumerate()
+    {
+        let sign = direction_sign::<S>(*direction);
+        // diff = ±(column - shifted_column) on the indices 1..n-1 and 0 elsewhere
+        let diff: &[S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+            if i == 0 || i >= num_rows {
+                S::zero()
+            } else {
+                sign * (column[i] - shifted_column[i])
+            }
+        });
+        builder.produce_intermediate_mle(diff);
+        // Note that `chi_n - chi_1 * chi_n` is the indicator of the indices 1..n-1, even if n = 0.
+        // diff - ±(column - shifted_column) * (chi_n - chi_1 * chi_n) = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(diff)]),
+                (-sign, vec![Box::new(column)]),
+                (sign, vec![Box::new(column), Box::new(chi_1)]),
+                (sign, vec![Box::new(shifted_column), Box::new(chi_n)]),
+                (
+                    -sign,
+                    vec![Box::new(shifted_column), Box::new(chi_1), Box::new(chi_n)],
+                ),
+            ],
+        );
+
+        // masked_diff = eq_(j-1) * diff, which is simply diff for the first column
+        let masked_diff = if j == 0 {
+            diff
+        } else {
+            let masked_diff: &[S] = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+                if i < num_rows {
+                    eq_prev[i] * diff[i]
+                } else {
+                    S::zero()
+                }
+            });
+            builder.produce_intermediate_mle(masked_diff);
+            // masked_diff - eq_(j-1) * diff = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(masked_diff)]),
+                    (-S::one(), vec![Box::new(eq_prev), Box::new(diff)]),
+                ],
+            );
+            masked_diff
+        };
+        let signs = prover_evaluate_sign(
+            builder,
+            alloc,
+            masked_diff,
+            #[cfg(test)]
+            false,
+        );
+        assert!(
+            signs.iter().all(|&s| !s),
+            "columns are not sorted in the requested order"
+        );
+
+        if j + 1 < num_columns || with_ties {
+            let inv: &mut [S] = alloc.alloc_slice_copy(&diff[..num_rows]);
+            slice_ops::batch_inversion(inv);
+            let inv: &[S] = inv;
+            let eq: &[S] = alloc.alloc_slice_fill_with(num_rows, |i| {
+                if diff[i].is_zero() {
+                    eq_prev[i]
+                } else {
+                    S::zero()
+                }
+            });
+            builder.produce_intermediate_mle(inv);
+            builder.produce_intermediate_mle(eq);
+            // eq_j - eq_(j-1) + eq_(j-1) * diff * inv = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(eq)]),
+                    (-S::one(), vec![Box::new(eq_prev)]),
+                    (
+                        S::one(),
+                        vec![Box::new(eq_prev), Box::new(diff), Box::new(inv)],
+                    ),
+                ],
+            );
+            // eq_j * diff = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![(S::one(), vec![Box::new(eq), Box::new(diff)])],
+            );
+            ties.push(eq);
+            eq_prev = eq;
+        }
+    }
+    ties
+}
+
+/// Verify that the rows of some columns are sorted lexicographically in the given directions.
+///
+/// See [`final_round_evaluate_sorted`].
+pub(crate) fn verify_sorted<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_evals: &[S],
+    directions: &[OrderByDirection],
+    chi_n_eval: S,
+) -> Result<(), ProofError> {
+    verify_sorted_impl(
+        builder,
+        alpha,
+        beta,
+        column_evals,
+        directions,
+        chi_n_eval,
+        false,
+    )?;
+    Ok(())
+}
+
+/// Verify that the rows of some columns are sorted lexicographically in the given directions,
+/// returning the evaluations of the indicators of the rows which are equal to the previous row.
+///
+/// See [`final_round_evaluate_sorted_with_ties`].
+pub(crate) fn verify_sorted_with_ties<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_evals: &[S],
+    directions: &[OrderByDirection],
+    chi_n_eval: S,
+) -> Result<Vec<S>, ProofError> {
+    verify_sorted_impl(
+        builder,
+        alpha,
+        beta,
+        column_evals,
+        directions,
+        chi_n_eval,
+        true,
+    )
+}
+
+/// See [`verify_sorted`] and [`verify_sorted_with_ties`].
+fn verify_sorted_impl<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_evals: &[S],
+    directions: &[OrderByDirection],
+    chi_n_eval: S,
+    with_ties: bool,
+) -> Result<Vec<S>, ProofError> {
+    if column_evals.is_empty() {
+        return Ok(Vec::new());
+    }
+    let num_columns = column_evals.len();
+    let (shifted_column_evals, chi_n_plus_1_eval) =
+        verify_shift(builder, alpha, beta, column_evals, chi_n_eval)?;
+    let singleton_one_eval = builder.mle_evaluations.singleton_one_evaluation;
+
+    let mut tie_evals = Vec::with_capacity(num_columns);
+    let mut eq_prev_eval = chi_n_eval;
+    for (j, ((column_eval, shifted_column_eval), direction)) in column_evals
+        .iter()
+        .zip(&shifted_column_evals)
+        .zip(directions)
+        .enumerate()
+    {
+        let sign = direction_sign::<S>(*direction);
+        let diff_eval = builder.try_consume_mle_evaluation()?;
+        // diff - ±(column - shifted_column) * (chi_n - chi_1 * chi_n) = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            diff_eval - sign * *column_eval
+                + sign * *column_eval * singleton_one_eval
+                + sign * *shifted_column_eval * chi_n_eval * (S::one() - singleton_one_eval),
+            3,
+        )?;
+
+        let masked_diff_eval = if j == 0 {
+            diff_eval
+        } else {
+            let masked_diff_eval = builder.try_consume_mle_evaluation()?;
+            // masked_diff - eq_(j-1) * diff = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                masked_diff_eval - eq_prev_eval * diff_eval,
+                2,
+            )?;
+            masked_diff_eval
+        };
+        let sign_eval = verifier_evaluate_sign(builder, masked_diff_eval, chi_n_plus_1_eval)?;
+        if !sign_eval.is_zero() {
+            Err(ProofError::VerificationError {
+                error: "columns are not sorted",
+            })?;
+        }
+
+        if j + 1 < num_columns || with_ties {
+            let inv_eval = builder.try_consume_mle_evaluation()?;
+            let eq_eval = builder.try_consume_mle_evaluation()?;
+            // eq_j - eq_(j-1) + eq_(j-1) * diff * inv = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                eq_eval - eq_prev_eval + eq_prev_eval * diff_eval * inv_eval,
+                3,
+            )?;
+            // eq_j * diff = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                eq_eval * diff_eval,
+                2,
+            )?;
+            tie_evals.push(eq_eval);
+            eq_prev_eval = eq_eval;
+        }
+    }
+    Ok(tie_evals)
+}
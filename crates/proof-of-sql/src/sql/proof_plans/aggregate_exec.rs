@@ -0,0 +1,1079 @@
+use super::{
+    avg_count_ident, avg_sum_type, count_presence, finalize_avg, finalize_percentile_cont,
+    interpolated_percentile, order_statistic_ranks, percentile_high_ident,
+};
+use crate::{
+    base::{
+        database::{
+            presence_ident, Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, QueryError,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            compute_extremum_witness, final_round_evaluate_division_by_zero,
+            final_round_evaluate_extremum, final_round_evaluate_order_statistic,
+            final_round_evaluate_rank, verify_division_by_zero, verify_extremum,
+            verify_order_statistic, verify_rank, Rank,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <aggregate_expr1> as <alias1>, ..., <aggregate_exprN> as <aliasN>
+///     FROM <table>
+///     WHERE <where_clause>
+/// ```
+/// where each aggregate expression is one of `SUM(<expr>)`, `COUNT(<expr>)`, `AVG(<expr>)`,
+/// `MAX(<expr>)`, `MIN(<expr>)`, `MEDIAN(<expr>)`, `PERCENTILE_CONT(<p>) WITHIN GROUP (ORDER BY <expr>)`
+/// or `PERCENTILE_DISC(<p>) WITHIN GROUP (ORDER BY <expr>)`.
+///
+/// The result always consists of exactly one row, even if no rows are selected.
+/// In that case all aggregations but `SUM` and `COUNT` are NULL, so each of them is followed by its
+/// presence in the result. For `MAX`, `MIN` and `PERCENTILE_DISC` it is proven to be whether the
+/// selection is nonempty, while for `AVG`, `MEDIAN` and `PERCENTILE_CONT` it is whether the proven
+/// count is nonzero.
+/// Since the verifier checks that the result has one row, it can also be the input of other plans
+/// unless there is an `AVG`, `MEDIAN` or `PERCENTILE_CONT`.
+///
+/// `AVG(<expr>)` is proven as `SUM(<expr>)` followed by `COUNT(<expr>)`, which make up the
+/// intermediate form of the result. The average is only computed from them when the verified
+/// result is finalized.
+///
+/// Percentiles are proven as order statistics: a value has rank `k` exactly if fewer than `k + 1`
+/// selected values are less than it and more than `k` are at most it, which the counting argument
+/// of [`final_round_evaluate_rank`] checks. `PERCENTILE_CONT(<p>)` and `MEDIAN` are proven as the two
+/// order statistics they interpolate between followed by `COUNT(<expr>)`, and are likewise only
+/// computed when the verified result is finalized.
+///
+/// Each aggregated expression may come with its presence, in which case the rows where it is NULL
+/// are skipped by the aggregation. In particular, aggregations over only NULLs behave like
+/// aggregations over no rows.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct AggregateExec {
+    pub(crate) aggregate_exprs: Vec<AliasedDynProofExpr>,
+    pub(crate) presences: Vec<Option<DynProofExpr>>,
+    pub(crate) table: TableExpr,
+    pub(crate) where_clause: DynProofExpr,
+}
+
+impl AggregateExec {
+    /// Creates a new aggregate expression.
+    ///
+    /// # Panics
+    /// Panics if any of the `aggregate_exprs` is not a `SUM`, `COUNT`, `AVG`, `MAX`, `MIN`, `MEDIAN`,
+    /// `PERCENTILE_CONT` or `PERCENTILE_DISC` aggregation.
+    pub fn new(
+        aggregate_exprs: Vec<AliasedDynProofExpr>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        let presences = vec![None; aggregate_exprs.len()];
+        Self::new_with_presences(aggregate_exprs, presences, table, where_clause)
+    }
+
+    /// Creates a new aggregate expression whose aggregated expressions may be NULL.
+    ///
+    /// `presences` contains the presence of each aggregated expression, or `None` if it is never NULL.
+    ///
+    /// # Panics
+    /// Panics if any of the `aggregate_exprs` is not a `SUM`, `COUNT`, `AVG`, `MAX`, `MIN`, `MEDIAN`,
+    /// `PERCENTILE_CONT` or `PERCENTILE_DISC` aggregation, if there is not exactly one presence per aggregation or if a presence is not boolean.
+    pub fn new_with_presences(
+        aggregate_exprs: Vec<AliasedDynProofExpr>,
+        presences: Vec<Option<DynProofExpr>>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        assert_eq!(
+            aggregate_exprs.len(),
+            presences.len(),
+            "AggregateExec needs exactly one presence per aggregation"
+        );
+        assert!(
+            presences
+                .iter()
+                .flatten()
+                .all(|presence| presence.data_type() == ColumnType::Boolean),
+            "AggregateExec presences must be boolean"
+        );
+        for aliased_expr in &aggregate_exprs {
+            assert!(
+                matches!(
+                    &aliased_expr.expr,
+                    DynProofExpr::Aggregate(aggregate)
+                        if matches!(
+                            aggregate.op(),
+                            AggregationOperator::Sum
+                                | AggregationOperator::Count
+                                | AggregationOperator::Avg
+                                | AggregationOperator::Max
+                                | AggregationOperator::Min
+                                | AggregationOperator::Median
+                                | AggregationOperator::PercentileCont(_)
+                                | AggregationOperator::PercentileDisc(_)
+                        )
+                ),
+                "AggregateExec only supports SUM, COUNT, AVG, MAX, MIN, MEDIAN, PERCENTILE_CONT and PERCENTILE_DISC"
+            );
+        }
+        Self {
+            aggregate_exprs,
+            presences,
+            table,
+            where_clause,
+        }
+    }
+
+    /// The aggregations that are proven, which make up the intermediate form of the result.
+    ///
+    /// Each `AVG` is proven as a `SUM` followed by a `COUNT`, and each `MEDIAN` or `PERCENTILE_CONT` as
+    /// two order statistics followed by a `COUNT`.
+    fn proven_aggregates(&self) -> impl Iterator<Item = ProvenAggregate<'_>> {
+        self.aggregate_exprs
+            .iter()
+            .zip(&self.presences)
+            .flat_map(|(aliased_expr, presence)| {
+                let DynProofExpr::Aggregate(aggregate) = &aliased_expr.expr else {
+                    unreachable!("AggregateExec only contains aggregations")
+                };
+                let proven_aggregate = |op, alias, data_type| ProvenAggregate {
+                    op,
+                    expr: aggregate.expr(),
+                    presence: presence.as_ref(),
+                    alias,
+                    data_type,
+                    rank: None,
+                    is_nullable: false,
+                };
+                let order_statistic = |alias, rank| ProvenAggregate {
+                    rank: Some(rank),
+                    ..proven_aggregate(aggregate.op(), alias, aggregate.expr().data_type())
+                };
+                let nullable_order_statistic = |alias, rank| ProvenAggregate {
+                    is_nullable: true,
+                    ..order_statistic(alias, rank)
+                };
+                match aggregate.op() {
+                    AggregationOperator::Avg => vec![
+                        proven_aggregate(
+                            AggregationOperator::Sum,
+                            aliased_expr.alias.clone(),
+                            avg_sum_type(aggregate.expr().data_type()),
+                        ),
+                        proven_aggregate(
+                            AggregationOperator::Count,
+                            avg_count_ident(&aliased_expr.alias),
+                            ColumnType::BigInt,
+                        ),
+                    ],
+                    AggregationOperator::Median | AggregationOperator::PercentileCont(_) => {
+                        let ranks = order_statistic_ranks(aggregate.op());
+                        vec![
+                            order_statistic(aliased_expr.alias.clone(), ranks[0]),
+                            order_statistic(percentile_high_ident(&aliased_expr.alias), ranks[1]),
+                            proven_aggregate(
+                                AggregationOperator::Count,
+                                avg_count_ident(&aliased_expr.alias),
+                                ColumnType::BigInt,
+                            ),
+                        ]
+                    }
+                    AggregationOperator::PercentileDisc(_) => order_statistic_ranks(aggregate.op())
+                        .into_iter()
+                        .map(|rank| nullable_order_statistic(aliased_expr.alias.clone(), rank))
+                        .collect(),
+                    op => vec![ProvenAggregate {
+                        is_nullable: is_null_without_rows(op),
+                        ..proven_aggregate(op, aliased_expr.alias.clone(), aggregate.data_type())
+                    }],
+                }
+            })
+    }
+
+    /// Whether there is an `AVG` aggregation, so that the result has to be finalized
+    pub fn has_avg(&self) -> bool {
+        self.aggregate_exprs.iter().any(|aliased_expr| {
+            matches!(&aliased_expr.expr, DynProofExpr::Aggregate(aggregate) if aggregate.op() == AggregationOperator::Avg)
+        })
+    }
+
+    /// Whether there is a `MEDIAN` or `PERCENTILE_CONT` aggregation, so that the result has to be finalized
+    fn has_interpolated_percentile(&self) -> bool {
+        self.aggregate_exprs.iter().any(|aliased_expr| {
+            matches!(&aliased_expr.expr, DynProofExpr::Aggregate(aggregate) if interpolated_percentile(aggregate.op()).is_some())
+        })
+    }
+
+    /// Whether there is a `MAX`, `MIN` or order statistic aggregation over an expression that is never NULL.
+    ///
+    /// These share the proof that the selection is nonempty.
+    fn has_nonempty_checks_without_presence(&self) -> bool {
+        self.proven_aggregates()
+            .any(|aggregate| aggregate.needs_nonempty_check() && aggregate.presence.is_none())
+    }
+}
+
+/// An aggregation proven by an [`AggregateExec`], which is one column of the intermediate result
+struct ProvenAggregate<'a> {
+    op: AggregationOperator,
+    expr: &'a DynProofExpr,
+    presence: Option<&'a DynProofExpr>,
+    alias: Ident,
+    data_type: ColumnType,
+    /// The rank of the aggregation if it is an order statistic
+    rank: Option<Rank>,
+    /// Whether the aggregation is followed by its presence, which is whether its selection is
+    /// nonempty
+    is_nullable: bool,
+}
+
+/// Whether the aggregation is NULL over no rows, in which case it is followed by its presence in
+/// the result
+fn is_null_without_rows(op: AggregationOperator) -> bool {
+    !matches!(op, AggregationOperator::Sum | AggregationOperator::Count)
+}
+
+impl ProvenAggregate<'_> {
+    /// Whether the proof of the aggregation relies on whether its selection is nonempty
+    fn needs_nonempty_check(&self) -> bool {
+        matches!(self.op, AggregationOperator::Max | AggregationOperator::Min)
+            || self.rank.is_some()
+    }
+}
+
+impl ProofPlan for AggregateExec {
+    #[allow(clippy::too_many_lines)]
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_one_eval = *one_eval_map
+            .get(&self.table.table_ref)
+            .expect("One eval not found");
+        // 1. selection
+        let where_eval = self
+            .where_clause
+            .verifier_evaluate(builder, accessor, input_one_eval)?;
+        let where_clause_division_by_zero_evals =
+            builder.take_division_by_zero_indicator_evaluations();
+        // 2. columns and their presences
+        let mut input_evals = Vec::with_capacity(self.aggregate_exprs.len());
+        let mut presence_evals = Vec::with_capacity(self.aggregate_exprs.len());
+        for aggregate in self.proven_aggregates() {
+            input_evals.push(match aggregate.op {
+                AggregationOperator::Count => None,
+                _ => Some(
+                    aggregate
+                        .expr
+                        .verifier_evaluate(builder, accessor, input_one_eval)?,
+                ),
+            });
+            presence_evals.push(
+                aggregate
+                    .presence
+                    .map(|presence| presence.verifier_evaluate(builder, accessor, input_one_eval))
+                    .transpose()?,
+            );
+        }
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_evals = where_clause_division_by_zero_evals
+            .into_iter()
+            .map(|eval| (eval, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicator_evaluations()
+                    .into_iter()
+                    .map(|eval| (eval, Some(where_eval))),
+            )
+            .collect::<Vec<_>>();
+        verify_division_by_zero(builder, &division_by_zero_evals)?;
+        // 3. aggregated columns
+        let aggregate_evals =
+            builder.try_consume_mle_evaluations(self.proven_aggregates().count())?;
+        let output_one_eval = builder.try_consume_one_evaluation()?;
+
+        if output_one_eval != builder.mle_evaluations.singleton_one_evaluation
+            || result.is_some_and(|table| table.num_rows() != 1)
+        {
+            Err(ProofError::VerificationError {
+                error: "Result of aggregation does not have exactly one row.",
+            })?;
+        }
+
+        // The selection of each aggregation skips the rows where its input is NULL
+        let selection_evals = presence_evals
+            .iter()
+            .map(|presence_eval| match presence_eval {
+                Some(presence_eval) => {
+                    let selection_eval = builder.try_consume_mle_evaluation()?;
+                    // selection - sel * presence = 0
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        selection_eval - where_eval * *presence_eval,
+                        2,
+                    )?;
+                    Ok(selection_eval)
+                }
+                None => Ok(where_eval),
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?;
+
+        // sum sel * expr - aggregate = 0 for sums and sum sel - aggregate = 0 for counts
+        for (((aggregate, input_eval), aggregate_eval), selection_eval) in self
+            .proven_aggregates()
+            .zip(&input_evals)
+            .zip(&aggregate_evals)
+            .zip(&selection_evals)
+        {
+            match (aggregate.op, input_eval) {
+                (AggregationOperator::Sum, Some(input_eval)) => builder
+                    .try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        *selection_eval * *input_eval - *aggregate_eval,
+                        2,
+                    )?,
+                (AggregationOperator::Count, _) => builder
+                    .try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        *selection_eval - *aggregate_eval,
+                        1,
+                    )?,
+                _ => (),
+            }
+        }
+
+        // 4. Prove extrema and order statistics
+        let shared_nonempty_evals = self
+            .has_nonempty_checks_without_presence()
+            .then(|| verify_is_nonempty(builder, where_eval))
+            .transpose()?;
+        // The presences of the aggregations that are NULL over no rows
+        let mut result_presence_evals = vec![None; aggregate_evals.len()];
+        for (
+            ((((aggregate, input_eval), presence_eval), selection_eval), aggregate_eval),
+            result_presence_eval,
+        ) in self
+            .proven_aggregates()
+            .zip(&input_evals)
+            .zip(&presence_evals)
+            .zip(&selection_evals)
+            .zip(&aggregate_evals)
+            .zip(&mut result_presence_evals)
+        {
+            let (true, Some(input_eval)) = (aggregate.needs_nonempty_check(), *input_eval) else {
+                continue;
+            };
+            let (count_eval, is_nonempty_eval) = match (presence_eval, shared_nonempty_evals) {
+                (None, Some(nonempty_evals)) => nonempty_evals,
+                _ => verify_is_nonempty(builder, *selection_eval)?,
+            };
+            if aggregate.is_nullable {
+                *result_presence_eval = Some(is_nonempty_eval);
+            }
+            // The only entry of the aggregated column
+            let extremum = *aggregate_eval
+                * output_one_eval.inv().ok_or(ProofError::VerificationError {
+                    error: "Evaluation point is degenerate.",
+                })?;
+            if let Some(rank) = aggregate.rank {
+                let (less_eval, less_or_equal_eval) = verify_order_statistic(
+                    builder,
+                    *selection_eval,
+                    input_eval,
+                    extremum * *selection_eval,
+                    input_one_eval,
+                )?;
+                let less_count_eval = builder.try_consume_mle_evaluation()?;
+                let less_or_equal_count_eval = builder.try_consume_mle_evaluation()?;
+                // sum less - less_count = 0 and sum less_or_equal - less_or_equal_count = 0
+                for (indicator_eval, count_eval) in [
+                    (less_eval, less_count_eval),
+                    (less_or_equal_eval, less_or_equal_count_eval),
+                ] {
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        indicator_eval - count_eval,
+                        1,
+                    )?;
+                }
+                verify_rank(
+                    builder,
+                    rank,
+                    (count_eval, is_nonempty_eval),
+                    (less_count_eval, less_or_equal_count_eval),
+                    output_one_eval,
+                )?;
+                // order_statistic - order_statistic * is_nonempty = 0
+                builder.try_produce_sumcheck_subpolynomial_evaluation(
+                    SumcheckSubpolynomialType::Identity,
+                    extremum * (output_one_eval - is_nonempty_eval),
+                    1,
+                )?;
+                continue;
+            }
+            let witness_eval = if aggregate.op == AggregationOperator::Max {
+                verify_extremum::<_, true>(
+                    builder,
+                    *selection_eval,
+                    input_eval,
+                    extremum * *selection_eval,
+                    input_one_eval,
+                )?
+            } else {
+                verify_extremum::<_, false>(
+                    builder,
+                    *selection_eval,
+                    input_eval,
+                    extremum * *selection_eval,
+                    input_one_eval,
+                )?
+            };
+            // sum witness - is_nonempty = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::ZeroSum,
+                witness_eval - is_nonempty_eval,
+                1,
+            )?;
+            // extremum - extremum * is_nonempty = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                extremum * (output_one_eval - is_nonempty_eval),
+                1,
+            )?;
+        }
+
+        let column_evals = aggregate_evals
+            .into_iter()
+            .zip(result_presence_evals)
+            .flat_map(|(aggregate_eval, presence_eval)| {
+                core::iter::once(aggregate_eval).chain(presence_eval)
+            })
+            .collect();
+        Ok(TableEvaluation::new(column_evals, output_one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aggregate_exprs
+            .iter()
+            .flat_map(|aliased_expr| {
+                let field =
+                    ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type());
+                let presence_field = matches!(
+                    &aliased_expr.expr,
+                    DynProofExpr::Aggregate(aggregate) if is_null_without_rows(aggregate.op())
+                )
+                .then(|| {
+                    ColumnField::new(presence_ident(&aliased_expr.alias), ColumnType::Boolean)
+                });
+                core::iter::once(field).chain(presence_field)
+            })
+            .collect()
+    }
+
+    fn get_intermediate_result_fields(&self) -> Vec<ColumnField> {
+        self.proven_aggregates()
+            .flat_map(|aggregate| {
+                let presence_field = aggregate.is_nullable.then(|| {
+                    ColumnField::new(presence_ident(&aggregate.alias), ColumnType::Boolean)
+                });
+                core::iter::once(ColumnField::new(aggregate.alias, aggregate.data_type))
+                    .chain(presence_field)
+            })
+            .collect()
+    }
+
+    fn finalize_result<S: Scalar>(
+        &self,
+        result: OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, QueryError> {
+        if !self.has_avg() && !self.has_interpolated_percentile() {
+            return Ok(result);
+        }
+        let mut columns = result.into_inner();
+        let finalized_columns = self
+            .aggregate_exprs
+            .iter()
+            .map(|aliased_expr| {
+                let column = columns
+                    .swap_remove(&aliased_expr.alias)
+                    .expect("The result has the intermediate fields");
+                let presence_alias = presence_ident(&aliased_expr.alias);
+                let (column, presence) = match &aliased_expr.expr {
+                    DynProofExpr::Aggregate(aggregate)
+                        if aggregate.op() == AggregationOperator::Avg =>
+                    {
+                        let counts = columns
+                            .get(&avg_count_ident(&aliased_expr.alias))
+                            .expect("The result has the intermediate fields");
+                        (
+                            finalize_avg(column, counts, aggregate.data_type())?,
+                            Some(count_presence(counts)),
+                        )
+                    }
+                    DynProofExpr::Aggregate(aggregate) => {
+                        match interpolated_percentile(aggregate.op()) {
+                            Some(percentile) => {
+                                let counts = columns
+                                    .get(&avg_count_ident(&aliased_expr.alias))
+                                    .expect("The result has the intermediate fields");
+                                (
+                                    finalize_percentile_cont(
+                                        &column,
+                                        columns
+                                            .get(&percentile_high_ident(&aliased_expr.alias))
+                                            .expect("The result has the intermediate fields"),
+                                        counts,
+                                        percentile,
+                                        aggregate.data_type(),
+                                    )?,
+                                    Some(count_presence(counts)),
+                                )
+                            }
+                            None => (column, columns.swap_remove(&presence_alias)),
+                        }
+                    }
+                    _ => (column, None),
+                };
+                Ok(core::iter::once((aliased_expr.alias.clone(), column))
+                    .chain(presence.map(|presence| (presence_alias, presence))))
+            })
+            .collect::<Result<Vec<_>, QueryError>>()?;
+        Ok(OwnedTable::try_from_iter(
+            finalized_columns.into_iter().flatten(),
+        )?)
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut columns = IndexSet::default();
+
+        for aliased_expr in &self.aggregate_exprs {
+            aliased_expr.expr.get_column_references(&mut columns);
+        }
+        for presence in self.presences.iter().flatten() {
+            presence.get_column_references(&mut columns);
+        }
+
+        self.where_clause.get_column_references(&mut columns);
+
+        columns
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        IndexSet::from_iter([self.table.table_ref])
+    }
+}
+
+impl ProverEvaluate for AggregateExec {
+    #[tracing::instrument(
+        name = "AggregateExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        // 1. selection
+        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns and their presences
+        let aggregate_columns = self
+            .proven_aggregates()
+            .map(|aggregate| match aggregate.op {
+                AggregationOperator::Count => None,
+                _ => Some(aggregate.expr.result_evaluate(alloc, table)),
+            })
+            .collect::<Vec<_>>();
+        let selections = self
+            .proven_aggregates()
+            .map(|aggregate| {
+                aggregate.presence.map_or(selection, |presence| {
+                    let presence = presence
+                        .result_evaluate(alloc, table)
+                        .as_boolean()
+                        .expect("presence is not boolean");
+                    select_present(alloc, selection, presence)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // 3. aggregated columns
+        let res = self.aggregated_table(alloc, &aggregate_columns, &selections);
+        builder.produce_one_evaluation_length(1);
+
+        log::log_memory_usage("End");
+
+        self.result_table(alloc, res, &selections)
+    }
+
+    #[tracing::instrument(
+        name = "AggregateExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    #[allow(clippy::too_many_lines)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        // 1. selection
+        let selection_column: Column<'a, S> =
+            self.where_clause.prover_evaluate(builder, alloc, table);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let where_clause_division_by_zero_indicators = builder.take_division_by_zero_indicators();
+
+        // 2. columns and their presences
+        let mut aggregate_columns = Vec::with_capacity(self.aggregate_exprs.len());
+        let mut presence_columns = Vec::with_capacity(self.aggregate_exprs.len());
+        for aggregate in self.proven_aggregates() {
+            aggregate_columns.push(match aggregate.op {
+                AggregationOperator::Count => None,
+                _ => Some(aggregate.expr.prover_evaluate(builder, alloc, table)),
+            });
+            presence_columns.push(aggregate.presence.map(|presence| {
+                presence
+                    .prover_evaluate(builder, alloc, table)
+                    .as_boolean()
+                    .expect("presence is not boolean")
+            }));
+        }
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_indicators = where_clause_division_by_zero_indicators
+            .into_iter()
+            .map(|indicator| (indicator, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicators()
+                    .into_iter()
+                    .map(|indicator| (indicator, Some(selection))),
+            )
+            .collect::<Vec<_>>();
+        final_round_evaluate_division_by_zero(builder, alloc, &division_by_zero_indicators);
+        let selections = presence_columns
+            .iter()
+            .map(|presence| {
+                presence.map_or(selection, |presence| {
+                    select_present(alloc, selection, presence)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // 3. aggregated columns
+        let res = self.aggregated_table(alloc, &aggregate_columns, &selections);
+        for column in res.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
+
+        // The selection of each aggregation skips the rows where its input is NULL
+        for (presence, aggregate_selection) in presence_columns.iter().zip(&selections) {
+            let Some(presence) = presence else {
+                continue;
+            };
+            builder.produce_intermediate_mle(*aggregate_selection);
+            // selection - sel * presence = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(*aggregate_selection)]),
+                    (-S::one(), vec![Box::new(selection), Box::new(*presence)]),
+                ],
+            );
+        }
+
+        // sum sel * expr - aggregate = 0 for sums and sum sel - aggregate = 0 for counts
+        for (((aggregate, aggregate_column), result_column), aggregate_selection) in self
+            .proven_aggregates()
+            .zip(&aggregate_columns)
+            .zip(res.columns())
+            .zip(&selections)
+        {
+            let input_multiplicands: Vec<Box<dyn MultilinearExtension<S>>> =
+                match (aggregate.op, aggregate_column) {
+                    (AggregationOperator::Sum, Some(column)) => {
+                        vec![Box::new(*aggregate_selection), Box::new(*column)]
+                    }
+                    (AggregationOperator::Count, _) => vec![Box::new(*aggregate_selection)],
+                    _ => continue,
+                };
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (S::one(), input_multiplicands),
+                    (-S::one(), vec![Box::new(*result_column)]),
+                ],
+            );
+        }
+
+        // 4. Prove extrema and order statistics
+        let shared_nonempty = self
+            .has_nonempty_checks_without_presence()
+            .then(|| prove_is_nonempty(builder, alloc, selection));
+        let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+        for ((((aggregate, aggregate_column), presence), aggregate_selection), result_column) in
+            self.proven_aggregates()
+                .zip(aggregate_columns)
+                .zip(&presence_columns)
+                .zip(&selections)
+                .zip(res.columns())
+        {
+            let (true, Some(column)) = (aggregate.needs_nonempty_check(), aggregate_column) else {
+                continue;
+            };
+            let aggregate_selection: &[bool] = aggregate_selection;
+            let (count, is_nonempty) = match (presence, shared_nonempty) {
+                (None, Some(nonempty)) => nonempty,
+                _ => prove_is_nonempty(builder, alloc, aggregate_selection),
+            };
+            if let Some(rank) = aggregate.rank {
+                prove_order_statistic(
+                    builder,
+                    alloc,
+                    rank,
+                    aggregate_selection,
+                    &column,
+                    result_column,
+                    (count, is_nonempty),
+                );
+                continue;
+            }
+            let group_indexes = aggregate_selection
+                .iter()
+                .map(|&b| b.then_some(0))
+                .collect::<Vec<_>>();
+            let column: &[S] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+            let extremum_value = result_column.scalar_at(0).expect("result has one row");
+            let extremum: &[S] = alloc.alloc_slice_fill_with(aggregate_selection.len(), |i| {
+                if aggregate_selection[i] {
+                    extremum_value
+                } else {
+                    S::zero()
+                }
+            });
+            let extrema: &[S] = if is_nonempty[0] {
+                &[extremum_value]
+            } else {
+                &[]
+            };
+            let witness = compute_extremum_witness(alloc, column, &group_indexes, extrema);
+            if aggregate.op == AggregationOperator::Max {
+                final_round_evaluate_extremum::<_, true>(
+                    builder,
+                    alloc,
+                    aggregate_selection,
+                    column,
+                    extremum,
+                    witness,
+                );
+            } else {
+                final_round_evaluate_extremum::<_, false>(
+                    builder,
+                    alloc,
+                    aggregate_selection,
+                    column,
+                    extremum,
+                    witness,
+                );
+            }
+            // sum witness - is_nonempty = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (S::one(), vec![Box::new(witness)]),
+                    (-S::one(), vec![Box::new(is_nonempty)]),
+                ],
+            );
+            // extremum - extremum * is_nonempty = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (extremum_value, vec![Box::new(chi_1)]),
+                    (-extremum_value, vec![Box::new(is_nonempty)]),
+                ],
+            );
+        }
+
+        log::log_memory_usage("End");
+
+        self.result_table(alloc, res, &selections)
+    }
+}
+
+/// The rows of `selection` where `presence` is also `true`.
+fn select_present<'a>(alloc: &'a Bump, selection: &[bool], presence: &[bool]) -> &'a [bool] {
+    alloc.alloc_slice_fill_with(selection.len(), |i| selection[i] && presence[i])
+}
+
+/// Prove that the single row `is_nonempty` column is `1` exactly when `selection` is nonempty.
+///
+/// Returns the single row `count` column of `selection` along with `is_nonempty`.
+///
+/// # Panics
+/// Panics if the number of selected rows does not fit in an `i64`.
+fn prove_is_nonempty<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    selection: &'a [bool],
+) -> (&'a [i64], &'a [bool]) {
+    let count = selection.iter().filter(|&&b| b).count();
+    let count_column: &[i64] =
+        alloc.alloc_slice_fill_copy(1, i64::try_from(count).expect("count fits in i64"));
+    let is_nonempty: &[bool] = alloc.alloc_slice_fill_copy(1, count > 0);
+    builder.produce_intermediate_mle(count_column);
+    builder.produce_intermediate_mle(is_nonempty);
+    // sum sel - count = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(selection)]),
+            (-S::one(), vec![Box::new(count_column)]),
+        ],
+    );
+    // is_nonempty * is_nonempty - is_nonempty = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(is_nonempty), Box::new(is_nonempty)]),
+            (-S::one(), vec![Box::new(is_nonempty)]),
+        ],
+    );
+    // count - count * is_nonempty = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(count_column)]),
+            (
+                -S::one(),
+                vec![Box::new(count_column), Box::new(is_nonempty)],
+            ),
+        ],
+    );
+    (count_column, is_nonempty)
+}
+
+/// Verify the proof of [`prove_is_nonempty`] and return the evaluations of `count` and `is_nonempty`.
+fn verify_is_nonempty<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    selection_eval: S,
+) -> Result<(S, S), ProofError> {
+    let count_eval = builder.try_consume_mle_evaluation()?;
+    let is_nonempty_eval = builder.try_consume_mle_evaluation()?;
+    // sum sel - count = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        selection_eval - count_eval,
+        1,
+    )?;
+    // is_nonempty * is_nonempty - is_nonempty = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        is_nonempty_eval * is_nonempty_eval - is_nonempty_eval,
+        2,
+    )?;
+    // count - count * is_nonempty = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        count_eval - count_eval * is_nonempty_eval,
+        2,
+    )?;
+    Ok((count_eval, is_nonempty_eval))
+}
+
+/// Prove that the single entry of `result_column` is the order statistic of the given `rank` among
+/// the values of `column` in `selection`, or `0` if `selection` is empty.
+///
+/// `count` and `is_nonempty` are the single row columns proven by [`prove_is_nonempty`].
+///
+/// # Panics
+/// Panics if the result column is empty or the counts do not fit in an `i64`.
+fn prove_order_statistic<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    rank: Rank,
+    selection: &'a [bool],
+    column: &Column<'a, S>,
+    result_column: &Column<'a, S>,
+    (count, is_nonempty): (&'a [i64], &'a [bool]),
+) {
+    let column: &[S] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+    let order_statistic_value = result_column.scalar_at(0).expect("result has one row");
+    let order_statistic: &[S] = alloc.alloc_slice_fill_with(selection.len(), |i| {
+        if selection[i] {
+            order_statistic_value
+        } else {
+            S::zero()
+        }
+    });
+    let (less, less_or_equal) =
+        final_round_evaluate_order_statistic(builder, alloc, selection, column, order_statistic);
+    let count_set = |indicator: &[bool]| {
+        let count = indicator.iter().filter(|&&b| b).count();
+        &*alloc.alloc_slice_fill_copy(1, i64::try_from(count).expect("count fits in i64"))
+    };
+    let less_count = count_set(less);
+    let less_or_equal_count = count_set(less_or_equal);
+    // sum less - less_count = 0 and sum less_or_equal - less_or_equal_count = 0
+    for (indicator, indicator_count) in [(less, less_count), (less_or_equal, less_or_equal_count)] {
+        builder.produce_intermediate_mle(indicator_count);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(indicator)]),
+                (-S::one(), vec![Box::new(indicator_count)]),
+            ],
+        );
+    }
+    final_round_evaluate_rank(
+        builder,
+        alloc,
+        rank,
+        (count, is_nonempty),
+        (less_count, less_or_equal_count),
+    );
+    // order_statistic - order_statistic * is_nonempty = 0
+    let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (order_statistic_value, vec![Box::new(chi_1)]),
+            (-order_statistic_value, vec![Box::new(is_nonempty)]),
+        ],
+    );
+}
+
+impl AggregateExec {
+    /// Compute the single row result of the aggregation.
+    ///
+    /// `aggregate_columns` contains the aggregated column of each proven aggregation for sums, maxima
+    /// and minima and `None` for counts.
+    /// `selections` contains the rows each aggregation is over.
+    /// If no rows are selected, maxima, minima and order statistics are `0`.
+    ///
+    /// # Panics
+    /// Panics if an aggregated column is shorter than its selection.
+    fn aggregated_table<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        aggregate_columns: &[Option<Column<'a, S>>],
+        selections: &[&[bool]],
+    ) -> Table<'a, S> {
+        let columns = self
+            .proven_aggregates()
+            .zip(aggregate_columns)
+            .zip(selections)
+            .map(
+                |((aggregate, aggregate_column), selection)| match aggregate_column {
+                    Some(column) => {
+                        let values = selection
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, &b)| b)
+                            .map(|(i, _)| column.scalar_at(i).expect("index is in bounds"));
+                        let value = match (aggregate.op, aggregate.rank) {
+                            (_, Some(rank)) => {
+                                let mut values = values.collect::<Vec<_>>();
+                                let count = i64::try_from(values.len()).expect("count fits in i64");
+                                (count > 0).then(|| {
+                                    *values
+                                        .select_nth_unstable_by(
+                                            rank.of(count),
+                                            ScalarExt::signed_cmp,
+                                        )
+                                        .1
+                                })
+                            }
+                            (AggregationOperator::Max, _) => values.max_by(ScalarExt::signed_cmp),
+                            (AggregationOperator::Min, _) => values.min_by(ScalarExt::signed_cmp),
+                            _ => Some(values.sum()),
+                        };
+                        Column::Scalar(alloc.alloc_slice_fill_copy(1, value.unwrap_or(S::ZERO)))
+                    }
+                    None => {
+                        let count = selection.iter().filter(|&&b| b).count();
+                        Column::BigInt(alloc.alloc_slice_fill_copy(
+                            1,
+                            i64::try_from(count).expect("count fits in i64"),
+                        ))
+                    }
+                },
+            );
+        Table::<'a, S>::try_new_with_options(
+            self.proven_aggregates()
+                .map(|aggregate| aggregate.alias)
+                .zip(columns)
+                .collect(),
+            TableOptions::new(Some(1)),
+        )
+        .expect("Failed to create table from column references")
+    }
+
+    /// The result of the aggregation, in which each aggregation that is NULL over no rows is
+    /// followed by its presence.
+    ///
+    /// `aggregated_table` is the table computed by [`Self::aggregated_table`] from `selections`.
+    ///
+    /// # Panics
+    /// Panics if the result does not have exactly one row, which it does by construction.
+    fn result_table<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        aggregated_table: Table<'a, S>,
+        selections: &[&[bool]],
+    ) -> Table<'a, S> {
+        let columns = self
+            .proven_aggregates()
+            .zip(aggregated_table.into_inner())
+            .zip(selections)
+            .flat_map(|((aggregate, (alias, column)), selection)| {
+                let presence = aggregate.is_nullable.then(|| {
+                    let is_nonempty = selection.iter().any(|&b| b);
+                    (
+                        presence_ident(&alias),
+                        Column::Boolean(alloc.alloc_slice_fill_copy(1, is_nonempty)),
+                    )
+                });
+                core::iter::once((alias, column)).chain(presence)
+            })
+            .collect();
+        Table::<'a, S>::try_new_with_options(columns, TableOptions::new(Some(1)))
+            .expect("Failed to create table from column references")
+    }
+}
@@ -0,0 +1,393 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+
+/// `select sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99`
+#[test]
+fn we_can_prove_a_simple_aggregation_with_bigint_columns() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            count_expr(const_bigint(1), "__count__"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("sum_c", [101 + 102 + 103 + 104]),
+        bigint("__count__", [4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select count(a) as count_a, sum(a * 2 + 1) as sum_a, sum(d) as sum_d, count(*) as c from sxt.t`
+#[test]
+fn we_can_prove_an_aggregation_with_expressions_in_any_order() {
+    let data = owned_table([
+        int("a", [1, -2, 3, 4]),
+        decimal75("d", 10, 2, [100, 250, -75, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            count_expr(column(t, "a", &accessor), "count_a"),
+            sum_expr(
+                add(
+                    multiply(column(t, "a", &accessor), const_bigint(2)),
+                    const_bigint(1),
+                ),
+                "sum_a",
+            ),
+            sum_expr(column(t, "d", &accessor), "sum_d"),
+            count_expr(const_bigint(1), "c"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("count_a", [4]),
+        bigint("sum_a", [(1 - 2 + 3 + 4) * 2 + 4]),
+        decimal75("sum_d", 10, 2, [275]),
+        bigint("c", [4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 1`
+#[test]
+fn we_can_prove_an_aggregation_without_selected_rows() {
+    let data = owned_table([bigint("b", [99, 0]), bigint("c", [101, 102])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            count_expr(const_bigint(1), "__count__"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("sum_c", [0]), bigint("__count__", [0])]);
+    assert_eq!(res, expected);
+}
+
+/// `select sum(c) as sum_c, count(*) as __count__ from sxt.t`
+#[test]
+fn we_can_prove_an_aggregation_on_an_empty_table() {
+    let data = owned_table([bigint("c", [0_i64; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            count_expr(const_bigint(1), "__count__"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("sum_c", [0]), bigint("__count__", [0])]);
+    assert_eq!(res, expected);
+}
+
+/// `select max(c) as max_c, min(c) as min_c, sum(c) as sum_c, min(d) as min_d from sxt.t where b = 99`
+#[test]
+fn we_can_prove_an_aggregation_with_max_and_min() {
+    let data = owned_table([
+        bigint("b", [99, 99, 0, 99, 99]),
+        bigint("c", [-5, 102, 1000, 104, 102]),
+        decimal75("d", 10, 2, [100, -250, -999, 75, -250]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            max_expr(column(t, "c", &accessor), "max_c"),
+            min_expr(column(t, "c", &accessor), "min_c"),
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            min_expr(column(t, "d", &accessor), "min_d"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("max_c", [104]),
+        presence("max_c", [true]),
+        bigint("min_c", [-5]),
+        presence("min_c", [true]),
+        bigint("sum_c", [-5 + 102 + 104 + 102]),
+        decimal75("min_d", 10, 2, [-250]),
+        presence("min_d", [true]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select max(c) as max_c, min(c) as min_c from sxt.t where b = 1`
+#[test]
+fn we_can_prove_an_aggregation_with_max_and_min_without_selected_rows() {
+    let data = owned_table([bigint("b", [99, 0]), bigint("c", [101, -102])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            max_expr(column(t, "c", &accessor), "max_c"),
+            min_expr(column(t, "c", &accessor), "min_c"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // `max_c` and `min_c` are NULL
+    let expected = owned_table([
+        bigint("max_c", [0]),
+        presence("max_c", [false]),
+        bigint("min_c", [0]),
+        presence("min_c", [false]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select count(c) as count_c, sum(c) as sum_c, max(c) as max_c, min(d) as min_d, count(*) as n from sxt.t where b = 99`
+/// where `c` and `d` are nullable
+#[test]
+fn we_can_prove_an_aggregation_that_skips_nulls() {
+    let data = owned_table([
+        bigint("b", [99, 99, 0, 99, 99]),
+        bigint("c", [-5, 0, 1000, 104, 0]),
+        presence("c", [true, false, true, true, false]),
+        bigint("d", [0, 0, -7, 0, 0]),
+        presence("d", [false, false, true, false, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let c_presence = Some(column(t, "c$presence", &accessor));
+    let expr = aggregate_with_presences(
+        vec![
+            count_expr(column(t, "c", &accessor), "count_c"),
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            max_expr(column(t, "c", &accessor), "max_c"),
+            min_expr(column(t, "d", &accessor), "min_d"),
+            count_expr(const_bigint(1), "n"),
+        ],
+        vec![
+            c_presence.clone(),
+            c_presence.clone(),
+            c_presence,
+            Some(column(t, "d$presence", &accessor)),
+            None,
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // `min_d` has no value to aggregate, so it is NULL
+    let expected = owned_table([
+        bigint("count_c", [2]),
+        bigint("sum_c", [-5 + 104]),
+        bigint("max_c", [104]),
+        presence("max_c", [true]),
+        bigint("min_d", [0]),
+        presence("min_d", [false]),
+        bigint("n", [4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select avg(c) as avg_c, avg(d) as avg_d, count(*) as n from sxt.t where b = 99`
+/// where `c` is nullable
+#[test]
+fn we_can_prove_an_aggregation_with_averages() {
+    let data = owned_table([
+        bigint("b", [99, 99, 0, 99, 99]),
+        bigint("c", [1, 0, 1000, 2, 2]),
+        presence("c", [true, false, true, true, true]),
+        decimal75("d", 10, 2, [-100, -250, -999, 75, -2]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate_with_presences(
+        vec![
+            avg_expr(column(t, "c", &accessor), "avg_c"),
+            avg_expr(column(t, "d", &accessor), "avg_d"),
+            count_expr(const_bigint(1), "n"),
+        ],
+        vec![Some(column(t, "c$presence", &accessor)), None, None],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // `5 / 3` is rounded to the 6 digits after the decimal point of the average
+    let expected = owned_table([
+        decimal75("avg_c", 25, 6, [1_666_667]),
+        presence("avg_c", [true]),
+        decimal75("avg_d", 14, 6, [-692_500]),
+        presence("avg_d", [true]),
+        bigint("n", [4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select avg(c) as avg_c from sxt.t where b = 1`
+#[test]
+fn we_can_prove_an_average_without_selected_rows() {
+    let data = owned_table([bigint("b", [99, 0]), bigint("c", [101, -102])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![avg_expr(column(t, "c", &accessor), "avg_c")],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // `avg_c` is NULL
+    let expected = owned_table([decimal75("avg_c", 25, 6, [0]), presence("avg_c", [false])]);
+    assert_eq!(res, expected);
+}
+
+/// `select median(a) as median_a, percentile_cont(0.25) within group (order by a) as p25_a,
+/// percentile_disc(0.9) within group (order by a) as p90_a, count(*) as n from sxt.t where b = 99`
+#[test]
+fn we_can_prove_an_aggregation_with_percentiles() {
+    let data = owned_table([
+        bigint("a", [5, -3, 8, 1, 4, 2, 9]),
+        bigint("b", [99, 99, 99, 0, 99, 99, 99]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            median_expr(column(t, "a", &accessor), "median_a"),
+            percentile_cont_expr(25, 2, column(t, "a", &accessor), "p25_a"),
+            percentile_disc_expr(9, 1, column(t, "a", &accessor), "p90_a"),
+            count_expr(const_bigint(1), "n"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // The selected values are -3, 2, 4, 5, 8 and 9
+    let expected = owned_table([
+        decimal75("median_a", 20, 1, [45]),
+        presence("median_a", [true]),
+        decimal75("p25_a", 21, 2, [250]),
+        presence("p25_a", [true]),
+        bigint("p90_a", [9]),
+        presence("p90_a", [true]),
+        bigint("n", [6]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select median(a) as median_a, percentile_disc(0.5) within group (order by a) as p50_a
+/// from sxt.t where b = 1`
+#[test]
+fn we_can_prove_percentiles_without_selected_rows() {
+    let data = owned_table([bigint("a", [101, -102]), bigint("b", [99, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = aggregate(
+        vec![
+            median_expr(column(t, "a", &accessor), "median_a"),
+            percentile_disc_expr(5, 1, column(t, "a", &accessor), "p50_a"),
+        ],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // Both percentiles are NULL
+    let expected = owned_table([
+        decimal75("median_a", 20, 1, [0]),
+        presence("median_a", [false]),
+        bigint("p50_a", [0]),
+        presence("p50_a", [false]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select m, s from (select max(a) as m, sum(a) as s from sxt.t where a <= 3) as agg`
+#[test]
+fn we_can_prove_an_aggregation_as_the_input_of_another_plan() {
+    let data = owned_table([bigint("a", [1, 5, -2, 3, 4])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let alias = "sxt.agg".parse().unwrap();
+    let mut alias_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    alias_accessor.add_table(
+        alias,
+        owned_table([bigint("m", [0; 0]), bigint("s", [0; 0])]),
+        0,
+    );
+    let expr = subquery_exec(
+        aggregate(
+            vec![
+                max_expr(column(t, "a", &accessor), "m"),
+                sum_expr(column(t, "a", &accessor), "s"),
+            ],
+            tab(t),
+            lte(column(t, "a", &accessor), const_bigint(3)),
+        ),
+        alias,
+        projection(
+            cols_expr_plan(alias, &["m", "s"], &alias_accessor),
+            tab(alias),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("m", [3]), bigint("s", [1 - 2 + 3])]);
+    assert_eq!(res, expected);
+}
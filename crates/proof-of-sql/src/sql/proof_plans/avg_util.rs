@@ -0,0 +1,98 @@
+//! `AVG` aggregations are proven as a sum along with a count. The average itself is only computed
+//! from the verified sum and count when the result is finalized after verification.
+use crate::{
+    base::{
+        database::{ColumnType, OwnedColumn},
+        math::decimal::Precision,
+        scalar::Scalar,
+    },
+    sql::proof::QueryError,
+};
+use alloc::{format, vec::Vec};
+use num_bigint::BigInt;
+use num_traits::Signed;
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the alias of an average to get the name of the count it is divided by.
+///
+/// Since `$` can not appear in an identifier of a query, the count can not collide with a result column.
+const AVG_COUNT_SUFFIX: &str = "$count";
+
+/// The name of the count the average named `alias` is divided by.
+pub(super) fn avg_count_ident(alias: &Ident) -> Ident {
+    Ident::new(format!("{}{AVG_COUNT_SUFFIX}", alias.value))
+}
+
+/// The type of the sum that is proven for an average over values of type `column_type`.
+///
+/// The sum keeps the scale of the values, and is only bounded by the field.
+///
+/// # Panics
+/// Panics if the largest precision is not supported, which it is.
+pub(super) fn avg_sum_type(column_type: ColumnType) -> ColumnType {
+    ColumnType::Decimal75(
+        Precision::new(75).expect("75 is a supported precision"),
+        column_type.scale().unwrap_or(0),
+    )
+}
+
+/// Whether each of the proven `counts` is nonzero, which is the presence of an aggregation over
+/// the counted rows that is NULL over no rows.
+///
+/// # Panics
+/// Panics if `counts` is not a `BIGINT` column.
+pub(super) fn count_presence<S: Scalar>(counts: &OwnedColumn<S>) -> OwnedColumn<S> {
+    let OwnedColumn::BigInt(counts) = counts else {
+        panic!("counts are BIGINT columns");
+    };
+    OwnedColumn::Boolean(counts.iter().map(|&count| count != 0).collect())
+}
+
+/// Divide the proven `sums` by the proven `counts`, rounding half away from zero to the scale of
+/// `avg_type`. Averages over no rows are NULL, so they are `0` here and their presence is given
+/// by [`count_presence`].
+///
+/// Returns [`QueryError::Overflow`] if an average does not fit into `avg_type`.
+///
+/// # Panics
+/// Panics if `sums` is not a decimal column, `counts` is not a `BIGINT` column or `avg_type`
+/// is not a decimal type whose scale is at least the scale of the sums.
+pub(super) fn finalize_avg<S: Scalar>(
+    sums: OwnedColumn<S>,
+    counts: &OwnedColumn<S>,
+    avg_type: ColumnType,
+) -> Result<OwnedColumn<S>, QueryError> {
+    let (OwnedColumn::Decimal75(_, sum_scale, sums), OwnedColumn::BigInt(counts)) = (sums, counts)
+    else {
+        panic!("averages are proven as decimal sums and BIGINT counts");
+    };
+    let ColumnType::Decimal75(precision, avg_scale) = avg_type else {
+        panic!("averages are decimals");
+    };
+    let scale_factor = BigInt::from(10)
+        .pow(u32::try_from(avg_scale - sum_scale).expect("averages do not lose scale"));
+    let bound = BigInt::from(10).pow(u32::from(precision.value()));
+    let averages = sums
+        .into_iter()
+        .zip(counts)
+        .map(|(sum, &count)| {
+            if count == 0 {
+                return Ok(S::ZERO);
+            }
+            let dividend: BigInt = Into::<BigInt>::into(sum) * &scale_factor;
+            let divisor = BigInt::from(count);
+            // Counts are positive, so round half away from zero by truncating `(2 * |a| + b) / (2 * b)`
+            let magnitude: BigInt = (dividend.abs() * 2 + &divisor) / (divisor * 2);
+            let average = if dividend.is_negative() {
+                -magnitude
+            } else {
+                magnitude
+            };
+            if average.abs() >= bound {
+                return Err(QueryError::Overflow);
+            }
+            S::try_from(average).map_err(|_| QueryError::Overflow)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(OwnedColumn::Decimal75(precision, avg_scale, averages))
+}
@@ -0,0 +1,75 @@
+use super::{avg_count_ident, avg_sum_type, count_presence, finalize_avg};
+use crate::{
+    base::{
+        database::{ColumnType, OwnedColumn},
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        proof::QueryError,
+        proof_exprs::test_utility::{decimal_type, decimals},
+    },
+};
+use sqlparser::ast::Ident;
+
+fn sums(scale: i8, values: &[i64]) -> OwnedColumn<TestScalar> {
+    decimals(75, scale, values)
+}
+
+#[test]
+fn we_can_name_the_count_of_an_average() {
+    assert_eq!(
+        avg_count_ident(&Ident::new("avg_a")),
+        Ident::new("avg_a$count")
+    );
+}
+
+#[test]
+fn we_can_get_the_presence_of_an_average_from_its_count() {
+    assert_eq!(
+        count_presence(&OwnedColumn::<TestScalar>::BigInt(vec![3, 0, 1])),
+        OwnedColumn::Boolean(vec![true, false, true])
+    );
+}
+
+#[test]
+fn we_can_get_the_type_of_the_sum_of_an_average() {
+    assert_eq!(avg_sum_type(ColumnType::Int), decimal_type(75, 0));
+    assert_eq!(avg_sum_type(decimal_type(10, 3)), decimal_type(75, 3));
+}
+
+#[test]
+fn we_can_finalize_averages_rounding_half_away_from_zero() {
+    let counts = OwnedColumn::BigInt(vec![3, 3, 2, 2, 4, 1]);
+    let averages =
+        finalize_avg(sums(0, &[5, -5, 1, -1, 0, 7]), &counts, decimal_type(10, 0)).unwrap();
+    assert_eq!(averages, decimals(10, 0, &[2, -2, 1, -1, 0, 7]));
+}
+
+#[test]
+fn we_can_finalize_averages_to_a_larger_scale() {
+    let counts = OwnedColumn::BigInt(vec![3, 3, 8]);
+    let averages = finalize_avg(sums(2, &[5, -5, 1]), &counts, decimal_type(20, 6)).unwrap();
+    assert_eq!(averages, decimals(20, 6, &[16_667, -16_667, 1_250]));
+}
+
+#[test]
+fn we_can_finalize_averages_over_no_rows_to_zero() {
+    let counts = OwnedColumn::BigInt(vec![0, 2]);
+    let averages = finalize_avg(sums(0, &[0, 4]), &counts, decimal_type(10, 6)).unwrap();
+    assert_eq!(averages, decimals(10, 6, &[0, 2_000_000]));
+}
+
+#[test]
+fn we_cannot_finalize_averages_that_do_not_fit_into_their_type() {
+    let counts = OwnedColumn::BigInt(vec![1]);
+    assert!(matches!(
+        finalize_avg(sums(0, &[1_000]), &counts, decimal_type(9, 6)),
+        Err(QueryError::Overflow)
+    ));
+    assert!(matches!(
+        finalize_avg(sums(0, &[-1_000]), &counts, decimal_type(9, 6)),
+        Err(QueryError::Overflow)
+    ));
+    let averages = finalize_avg(sums(0, &[999]), &counts, decimal_type(9, 6)).unwrap();
+    assert_eq!(averages, decimals(9, 6, &[999_000_000]));
+}
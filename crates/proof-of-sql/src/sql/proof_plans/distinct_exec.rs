@@ -0,0 +1,308 @@
+use super::{
+    join_exec::{prove_lookup, verify_lookup},
+    DynProofPlan, OrderByExec,
+};
+use crate::{
+    base::{
+        database::{
+            group_by_util::{aggregate_columns, AggregatedColumns},
+            order_by_util::compare_indexes_by_owned_columns,
+            ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT DISTINCT * FROM (<ProofPlan>)
+/// ```
+///
+/// The result consists of the distinct rows of the input in ascending order,
+/// i.e. it is a `GROUP BY` on all columns of the input without any aggregates.
+///
+/// Let `I` be the input and `O` the output. The prover commits to `O`, the number of times each
+/// row of `O` occurs in `I` and the inverses of these counts. We then prove that
+/// 1. `O` covers `I`, i.e. the rows of `I` are a lookup into the rows of `O` with multiplicities the counts.
+/// 2. Every row of `O` occurs in `I`, i.e. `count * inverse - 1 = 0`.
+/// 3. The rows of `O` are unique. If the input has a single column of a type whose values can be
+///     compared in the proof, that column is proven to be strictly increasing. Otherwise the
+///     verifier checks that the rows of the result are strictly increasing, which means that the
+///     plan can only be used at the top level.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct DistinctExec {
+    pub(crate) input: Box<DynProofPlan>,
+}
+
+impl DistinctExec {
+    /// Creates a new distinct execution plan.
+    ///
+    /// # Panics
+    /// Panics if the input has no columns.
+    pub fn new(input: Box<DynProofPlan>) -> Self {
+        assert!(
+            !input.get_column_result_fields().is_empty(),
+            "The input of a distinct must have columns"
+        );
+        Self { input }
+    }
+
+    /// The plan whose duplicate rows are removed
+    pub fn input(&self) -> &DynProofPlan {
+        &self.input
+    }
+
+    /// Whether the uniqueness of the rows is proven, so that the plan can be the input of other plans
+    pub fn is_nestable(&self) -> bool {
+        match self.input.get_column_result_fields().as_slice() {
+            [field] => OrderByExec::is_supported_column_type(field.data_type()),
+            _ => false,
+        }
+    }
+}
+
+impl ProofPlan for DistinctExec
+where
+    DistinctExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+
+        // First round
+        let output_column_evals =
+            builder.try_consume_first_round_mle_evaluations(input_eval.column_evals().len())?;
+        let [count_eval, count_inverse_eval] = builder
+            .try_consume_first_round_mle_evaluations(2)?
+            .try_into()
+            .expect("We consumed exactly 2 evaluations");
+        let output_one_eval = builder.try_consume_one_evaluation()?;
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        // 1. The output covers the input
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            input_eval.column_evals(),
+            input_eval.one_eval(),
+            &output_column_evals,
+            output_one_eval,
+            count_eval,
+        )?;
+
+        // 2. Every row of the output occurs in the input
+        // count * inverse - 1 = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            count_eval * count_inverse_eval - output_one_eval,
+            2,
+        )?;
+
+        // 3. The rows of the output are unique
+        if self.is_nestable() {
+            verify_monotonic::<S, true, true>(
+                builder,
+                alpha,
+                beta,
+                output_column_evals[0],
+                output_one_eval,
+            )?;
+        } else {
+            let table = result.ok_or(ProofError::UnsupportedQueryPlan {
+                error: "DistinctExec on several columns currently only supported at top level of query plan.",
+            })?;
+            let columns = table.inner_table().values().collect::<Vec<_>>();
+            if (1..table.num_rows())
+                .any(|i| compare_indexes_by_owned_columns(&columns, i - 1, i).is_ge())
+            {
+                Err(ProofError::VerificationError {
+                    error: "Result of distinct not ordered as expected.",
+                })?;
+            }
+        }
+
+        Ok(TableEvaluation::new(output_column_evals, output_one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for DistinctExec {
+    #[tracing::instrument(name = "DistinctExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &input);
+
+        for column in witness.output.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.produce_intermediate_mle(witness.counts);
+        builder.produce_intermediate_mle(witness.count_inverses);
+        builder.produce_one_evaluation_length(witness.output.num_rows());
+        if self.is_nestable() {
+            first_round_evaluate_monotonic(builder, alloc, witness.first_output_column(alloc));
+        }
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+
+    #[tracing::instrument(name = "DistinctExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &input);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 1. The output covers the input
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &input.columns().copied().collect::<Vec<_>>(),
+            &witness.output.columns().copied().collect::<Vec<_>>(),
+            witness.counts,
+        );
+
+        // 2. Every row of the output occurs in the input
+        // count * inverse - 1 = 0
+        let output_ones = alloc.alloc_slice_fill_copy(witness.output.num_rows(), true);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (
+                    S::one(),
+                    vec![Box::new(witness.counts), Box::new(witness.count_inverses)],
+                ),
+                (-S::one(), vec![Box::new(output_ones as &[_])]),
+            ],
+        );
+
+        // 3. The rows of the output are unique
+        if self.is_nestable() {
+            final_round_evaluate_monotonic::<S, true, true>(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                witness.first_output_column(alloc),
+            );
+        }
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+}
+
+/// The witness committed to by the prover of a [`DistinctExec`]
+struct DistinctWitness<'a, S: Scalar> {
+    output: Table<'a, S>,
+    counts: &'a [i64],
+    count_inverses: &'a [S],
+}
+
+impl<'a, S: Scalar> DistinctWitness<'a, S> {
+    /// The first column of the output as scalars
+    ///
+    /// # Panics
+    /// Panics if the output has no columns.
+    fn first_output_column(&self, alloc: &'a Bump) -> &'a [S] {
+        let column = self
+            .output
+            .column(0)
+            .expect("The output of a distinct has columns");
+        alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0))
+    }
+}
+
+impl DistinctExec {
+    /// Compute the distinct rows along with all the witness columns needed to prove them.
+    ///
+    /// # Panics
+    /// Panics if the input columns do not have the same length.
+    fn compute_witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        input: &Table<'a, S>,
+    ) -> DistinctWitness<'a, S> {
+        let input_columns = input.columns().copied().collect::<Vec<_>>();
+        let selection = alloc.alloc_slice_fill_copy(input.num_rows(), true);
+        let AggregatedColumns {
+            group_by_columns,
+            count_column,
+            ..
+        } = aggregate_columns(alloc, &input_columns, &[], &[], &[], selection)
+            .expect("columns should be aggregatable");
+        let output = Table::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(group_by_columns),
+            TableOptions::new(Some(count_column.len())),
+        )
+        .expect("Failed to create table from iterator");
+        let count_inverses: &mut [S] =
+            alloc.alloc_slice_fill_iter(count_column.iter().map(|&count| S::from(count)));
+        slice_ops::batch_inversion(count_inverses);
+        DistinctWitness {
+            output,
+            counts: count_column,
+            count_inverses,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, ColumnRef, ColumnType, TableRef},
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr},
+    },
+};
+
+/// `select distinct user_id, kind from sxt.events where amount <= 60`
+#[test]
+fn we_can_prove_a_distinct_on_several_columns() {
+    let (accessor, t) = accessor_with_events::<InnerProductProof>(());
+    let ast = distinct_exec(filter(
+        cols_expr_plan(t, &["user_id", "kind"], &accessor),
+        tab(t),
+        lte(column(t, "amount", &accessor), const_bigint(60)),
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("user_id", [1_i64, 1, 2, 3, 3]),
+        varchar("kind", ["click", "view", "view", "click", "view"]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select distinct kind from sxt.events`
+#[test]
+fn we_can_prove_a_distinct_on_a_varchar_column() {
+    let (accessor, t) = accessor_with_events::<InnerProductProof>(());
+    let ast = distinct_exec(projection(cols_expr_plan(t, &["kind"], &accessor), tab(t)));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([varchar("kind", ["click", "view"])]);
+    assert_eq!(res, expected);
+}
+
+/// `select distinct user_id from sxt.events where amount >= 100`
+#[test]
+fn we_can_prove_a_distinct_with_an_empty_input() {
+    let (accessor, t) = accessor_with_events::<InnerProductProof>(());
+    let ast = distinct_exec(filter(
+        cols_expr_plan(t, &["user_id"], &accessor),
+        tab(t),
+        gte(column(t, "amount", &accessor), const_bigint(100)),
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("user_id", [0_i64; 0])]);
+    assert_eq!(res, expected);
+}
+
+/// `select count(*) as n from (select distinct user_id from sxt.events) as d`
+#[test]
+fn we_can_prove_a_count_over_a_distinct_on_one_column() {
+    let (accessor, t) = accessor_with_events::<InnerProductProof>(());
+    let alias: TableRef = "sxt.d".parse().unwrap();
+    let ast = subquery_exec(
+        distinct_exec(projection(
+            cols_expr_plan(t, &["user_id"], &accessor),
+            tab(t),
+        )),
+        alias,
+        aggregate(
+            vec![count_expr(
+                DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+                    alias,
+                    "user_id".into(),
+                    ColumnType::BigInt,
+                ))),
+                "n",
+            )],
+            tab(alias),
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("n", [4_i64])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_a_distinct_on_several_columns_which_is_not_at_the_top_level() {
+    let (accessor, t) = accessor_with_events::<InnerProductProof>(());
+    let alias: TableRef = "sxt.d".parse().unwrap();
+    let ast = subquery_exec(
+        distinct_exec(projection(
+            cols_expr_plan(t, &["user_id", "kind"], &accessor),
+            tab(t),
+        )),
+        alias,
+        aggregate(
+            vec![count_expr(const_bigint(1), "n")],
+            tab(alias),
+            const_bool(true),
+        ),
+    );
+    let res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
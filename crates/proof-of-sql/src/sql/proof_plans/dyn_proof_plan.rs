@@ -1,4 +1,8 @@
-use super::{EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, TableExec, UnionExec};
+use super::{
+    AggregateExec, DistinctExec, EmptyExec, FilterExec, GroupByExec, IntersectExec, JoinExec,
+    OrderByExec, ProjectionExec, SemiJoinExec, SliceExec, SubqueryExec, TableExec, UnionExec,
+    WindowExec,
+};
 use crate::{
     base::{
         database::{ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableRef},
@@ -7,7 +11,8 @@ use crate::{
         scalar::Scalar,
     },
     sql::proof::{
-        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, QueryError,
+        VerificationBuilder,
     },
 };
 use alloc::vec::Vec;
@@ -30,13 +35,20 @@ pub enum DynProofPlan {
     /// Provable expressions for queries of the form
     /// ```ignore
     ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
-    ///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
+    ///         <aggregate_expr1> as <alias1>, ..., <aggregate_exprN> as <aliasN>,
     ///         COUNT(*) as count_alias
     ///     FROM <table>
     ///     WHERE <where_clause>
     ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
     /// ```
     GroupBy(GroupByExec),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT SUM(<expr1>) as <alias1>, ..., MAX(<exprN>) as <aliasN>
+    ///     FROM <table>
+    ///     WHERE <where_clause>
+    /// ```
+    Aggregate(AggregateExec),
     /// Provable expressions for queries of the form, where the result is sent in a dense form
     /// ```ignore
     ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
@@ -49,6 +61,11 @@ pub enum DynProofPlan {
     Slice(SliceExec),
     /// `ProofPlan` for queries of the form
     /// ```ignore
+    ///     <ProofPlan> ORDER BY <col1> [ASC|DESC], ..., <colN> [ASC|DESC]
+    /// ```
+    OrderBy(OrderByExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
     ///     <ProofPlan>
     ///     UNION ALL
     ///     <ProofPlan>
@@ -57,4 +74,39 @@ pub enum DynProofPlan {
     ///     <ProofPlan>
     /// ```
     Union(UnionExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> INNER JOIN <ProofPlan>
+    ///     ON col1 = col2
+    /// ```
+    Join(JoinExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> WHERE col1 [NOT] IN (<ProofPlan>)
+    /// ```
+    SemiJoin(SemiJoinExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> FROM (<ProofPlan>) AS <alias>
+    /// ```
+    Subquery(SubqueryExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT DISTINCT * FROM (<ProofPlan>)
+    /// ```
+    Distinct(DistinctExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT *, <function> OVER (PARTITION BY ... ORDER BY ...) AS <alias>, ... FROM (<ProofPlan>)
+    /// ```
+    Window(WindowExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> INTERSECT <ProofPlan>
+    /// ```
+    /// or
+    /// ```ignore
+    ///     <ProofPlan> EXCEPT <ProofPlan>
+    /// ```
+    Intersect(IntersectExec),
 }
@@ -16,6 +16,7 @@ use crate::{
             ProverHonestyMarker, SumcheckSubpolynomialType, VerificationBuilder,
         },
         proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{final_round_evaluate_division_by_zero, verify_division_by_zero},
     },
     utils::log,
 };
@@ -75,6 +76,8 @@ where
         let selection_eval =
             self.where_clause
                 .verifier_evaluate(builder, accessor, input_one_eval)?;
+        let where_clause_division_by_zero_evals =
+            builder.take_division_by_zero_indicator_evaluations();
         // 2. columns
         let columns_evals = Vec::from_iter(
             self.aliased_results
@@ -86,6 +89,18 @@ where
                 })
                 .collect::<Result<Vec<_>, _>>()?,
         );
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_evals = where_clause_division_by_zero_evals
+            .into_iter()
+            .map(|eval| (eval, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicator_evaluations()
+                    .into_iter()
+                    .map(|eval| (eval, Some(selection_eval))),
+            )
+            .collect::<Vec<_>>();
+        verify_division_by_zero(builder, &division_by_zero_evals)?;
         // 3. filtered_columns
         let filtered_columns_evals =
             builder.try_consume_mle_evaluations(self.aliased_results.len())?;
@@ -145,7 +160,7 @@ impl ProverEvaluate for FilterExec {
     #[tracing::instrument(name = "FilterExec::first_round_evaluate", level = "debug", skip_all)]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
@@ -206,6 +221,7 @@ impl ProverEvaluate for FilterExec {
             .as_boolean()
             .expect("selection is not boolean");
         let output_length = selection.iter().filter(|b| **b).count();
+        let where_clause_division_by_zero_indicators = builder.take_division_by_zero_indicators();
 
         // 2. columns
         let columns: Vec<_> = self
@@ -213,6 +229,18 @@ impl ProverEvaluate for FilterExec {
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect();
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_indicators = where_clause_division_by_zero_indicators
+            .into_iter()
+            .map(|indicator| (indicator, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicators()
+                    .into_iter()
+                    .map(|indicator| (indicator, Some(selection))),
+            )
+            .collect::<Vec<_>>();
+        final_round_evaluate_division_by_zero(builder, alloc, &division_by_zero_indicators);
         // Compute filtered_columns
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
         // 3. Produce MLEs
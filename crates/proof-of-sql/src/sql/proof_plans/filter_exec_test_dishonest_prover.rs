@@ -18,6 +18,7 @@ use crate::{
             test_utility::{cols_expr_plan, column, const_int128, equal, tab},
             ProofExpr,
         },
+        proof_gadgets::final_round_evaluate_division_by_zero,
     },
     utils::log,
 };
@@ -37,7 +38,7 @@ impl ProverEvaluate for DishonestFilterExec {
     )]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
@@ -101,12 +102,25 @@ impl ProverEvaluate for DishonestFilterExec {
             .as_boolean()
             .expect("selection is not boolean");
         let output_length = selection.iter().filter(|b| **b).count();
+        let where_clause_division_by_zero_indicators = builder.take_division_by_zero_indicators();
         // 2. columns
         let columns: Vec<_> = self
             .aliased_results
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect();
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_indicators = where_clause_division_by_zero_indicators
+            .into_iter()
+            .map(|indicator| (indicator, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicators()
+                    .into_iter()
+                    .map(|indicator| (indicator, Some(selection))),
+            )
+            .collect::<Vec<_>>();
+        final_round_evaluate_division_by_zero(builder, alloc, &division_by_zero_indicators);
         // Compute filtered_columns
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
         let filtered_columns = tamper_column(alloc, filtered_columns);
@@ -1,8 +1,14 @@
-use super::{fold_columns, fold_vals};
+use super::{
+    avg_sum_type, finalize_avg, finalize_percentile_cont, fold_columns, fold_vals,
+    interpolated_percentile, order_statistic_ranks, percentile_high_ident, OrderByExec,
+};
 use crate::{
     base::{
         database::{
-            group_by_util::{aggregate_columns, AggregatedColumns},
+            group_by_util::{
+                aggregate_columns, group_indexes, order_statistics_by_group_indexes,
+                AggregatedColumns,
+            },
             order_by_util::compare_indexes_by_owned_columns,
             Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
             TableRef,
@@ -14,10 +20,17 @@ use crate::{
     },
     sql::{
         proof::{
-            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, QueryError,
             SumcheckSubpolynomialType, VerificationBuilder,
         },
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            compute_extremum_witness, count_by_group, final_round_evaluate_division_by_zero,
+            final_round_evaluate_extremum, final_round_evaluate_monotonic,
+            final_round_evaluate_order_statistic, final_round_evaluate_rank,
+            first_round_evaluate_monotonic, verify_division_by_zero, verify_extremum,
+            verify_monotonic, verify_order_statistic, verify_rank, Rank,
+        },
     },
     utils::log,
 };
@@ -25,50 +38,236 @@ use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
 use core::iter;
 use num_traits::{One, Zero};
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, Percentile};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
 /// Provable expressions for queries of the form
 /// ```ignore
-///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
-///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
+///     SELECT <group_by_expr1>.expr as <group_by_expr1>.alias, ..., <group_by_exprM>.expr as <group_by_exprM>.alias,
+///         <aggregate_expr1>.expr as <aggregate_expr1>.alias, ..., <aggregate_exprN>.expr as <aggregate_exprN>.alias,
 ///         COUNT(*) as count_alias
 ///     FROM <table>
 ///     WHERE <where_clause>
-///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
+///     GROUP BY <group_by_expr1>.expr, ..., <group_by_exprM>.expr
 /// ```
+/// where each aggregate expression is one of `SUM(<expr>)`, `AVG(<expr>)`, `MAX(<expr>)`, `MIN(<expr>)`,
+/// `MEDIAN(<expr>)`, `PERCENTILE_CONT(<p>) WITHIN GROUP (ORDER BY <expr>)` or
+/// `PERCENTILE_DISC(<p>) WITHIN GROUP (ORDER BY <expr>)`.
+///
+/// `AVG(<expr>)` is proven as `SUM(<expr>)`, which along with the count of the group makes up the
+/// intermediate form of the result. The average is only computed when the verified result is
+/// finalized, so such a plan can not be the input of other plans.
+///
+/// Percentiles are proven as order statistics of their groups, which like extrema are folded with the
+/// `group_by` columns. The numbers of values of each group that are less than, respectively at most,
+/// the order statistic are proven like sums, and the counting argument of [`final_round_evaluate_rank`]
+/// checks that the order statistic has its rank. `PERCENTILE_CONT(<p>)` and `MEDIAN` are proven as the
+/// two order statistics they interpolate between and, just like `AVG`, are only computed when the
+/// verified result is finalized.
 ///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+///
+/// The groups of the result have to be unique. If there is a single `group_by` expression of a type
+/// whose values can be compared in the proof, that column is proven to be strictly increasing.
+/// Otherwise the verifier checks that the groups of the result are strictly increasing, which
+/// means that the plan can only be used at the top level.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GroupByExec {
-    pub(super) group_by_exprs: Vec<ColumnExpr>,
-    pub(super) sum_expr: Vec<AliasedDynProofExpr>,
-    pub(super) count_alias: Ident,
-    pub(super) table: TableExpr,
-    pub(super) where_clause: DynProofExpr,
+    pub(crate) group_by_exprs: Vec<AliasedDynProofExpr>,
+    pub(crate) aggregate_exprs: Vec<AliasedDynProofExpr>,
+    pub(crate) count_alias: Ident,
+    pub(crate) table: TableExpr,
+    pub(crate) where_clause: DynProofExpr,
+}
+
+/// The kind of aggregation performed by an aggregate expression of a [`GroupByExec`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AggregateKind {
+    Sum,
+    Max,
+    Min,
+    OrderStatistic(Rank),
 }
 
 impl GroupByExec {
     /// Creates a new `group_by` expression.
+    ///
+    /// # Panics
+    /// Panics if an aggregate expression is a `COUNT`, `COUNT(DISTINCT ...)` or `FIRST` aggregation.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr>,
-        sum_expr: Vec<AliasedDynProofExpr>,
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        aggregate_exprs: Vec<AliasedDynProofExpr>,
         count_alias: Ident,
         table: TableExpr,
         where_clause: DynProofExpr,
     ) -> Self {
+        assert!(
+            aggregate_exprs.iter().all(|aliased_expr| !matches!(
+                &aliased_expr.expr,
+                DynProofExpr::Aggregate(aggregate) if matches!(
+                    aggregate.op(),
+                    AggregationOperator::Count
+                        | AggregationOperator::CountDistinct
+                        | AggregationOperator::First
+                )
+            )),
+            "Group by aggregation is not supported"
+        );
         Self {
             group_by_exprs,
-            sum_expr,
+            aggregate_exprs,
             count_alias,
             table,
             where_clause,
         }
     }
+
+    /// Whether the uniqueness of the groups is proven, so that the plan can be the input of other plans
+    pub fn is_nestable(&self) -> bool {
+        match self.group_by_exprs.as_slice() {
+            [aliased_expr] => OrderByExec::is_supported_column_type(aliased_expr.expr.data_type()),
+            _ => false,
+        }
+    }
+
+    /// The kinds of the aggregated columns of the intermediate result along with the index of the
+    /// aggregate expression each of them aggregates. Percentiles are order statistics, of which
+    /// `MEDIAN` and `PERCENTILE_CONT` have two, and `SUM` and `AVG` are summed, as is an aggregate
+    /// expression which isn't an aggregation.
+    ///
+    /// # Panics
+    /// Panics if an aggregate expression is an aggregation that [`Self::new`] rejects.
+    fn aggregate_kinds(&self) -> impl Iterator<Item = (usize, AggregateKind)> + '_ {
+        self.aggregate_exprs
+            .iter()
+            .enumerate()
+            .flat_map(|(index, aliased_expr)| {
+                let kinds = match &aliased_expr.expr {
+                    DynProofExpr::Aggregate(aggregate) => match aggregate.op() {
+                        AggregationOperator::Sum | AggregationOperator::Avg => {
+                            vec![AggregateKind::Sum]
+                        }
+                        AggregationOperator::Max => vec![AggregateKind::Max],
+                        AggregationOperator::Min => vec![AggregateKind::Min],
+                        op @ (AggregationOperator::Median
+                        | AggregationOperator::PercentileCont(_)
+                        | AggregationOperator::PercentileDisc(_)) => order_statistic_ranks(op)
+                            .into_iter()
+                            .map(AggregateKind::OrderStatistic)
+                            .collect(),
+                        AggregationOperator::Count
+                        | AggregationOperator::CountDistinct
+                        | AggregationOperator::First => {
+                            unreachable!("Group by aggregation is not supported")
+                        }
+                    },
+                    _ => vec![AggregateKind::Sum],
+                };
+                kinds.into_iter().map(move |kind| (index, kind))
+            })
+    }
+
+    /// The aliases and percentiles of the `MEDIAN` and `PERCENTILE_CONT` aggregate expressions
+    fn interpolated_percentiles(&self) -> impl Iterator<Item = (&Ident, Percentile)> + '_ {
+        self.aggregate_exprs
+            .iter()
+            .filter_map(|aliased_expr| match &aliased_expr.expr {
+                DynProofExpr::Aggregate(aggregate) => interpolated_percentile(aggregate.op())
+                    .map(|percentile| (&aliased_expr.alias, percentile)),
+                _ => None,
+            })
+    }
+
+    /// The first `group_by` column of the result as scalars
+    ///
+    /// # Panics
+    /// Panics if the result has no columns.
+    fn first_group_by_column<'a, S: Scalar>(alloc: &'a Bump, res: &Table<'a, S>) -> &'a [S] {
+        let column = res.column(0).expect("The result of a group by has columns");
+        alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0))
+    }
+
+    /// Aggregate the columns and return the `group_by` columns, the aggregated columns
+    /// in the order of [`Self::aggregate_kinds`] and the count column.
+    ///
+    /// # Panics
+    /// Panics if the columns do not have the same length.
+    fn aggregate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        group_by_columns: &[Column<'a, S>],
+        aggregate_expr_columns: &[Column<'a, S>],
+        selection: &[bool],
+    ) -> (Vec<Column<'a, S>>, Vec<&'a [S]>, &'a [i64]) {
+        let columns_of_kind = |kind| {
+            self.aggregate_kinds()
+                .filter(|(_, k)| *k == kind)
+                .map(|(index, _)| aggregate_expr_columns[index])
+                .collect::<Vec<_>>()
+        };
+        let AggregatedColumns {
+            group_by_columns: group_by_result_columns,
+            sum_columns,
+            max_columns,
+            min_columns,
+            count_column,
+        } = aggregate_columns(
+            alloc,
+            group_by_columns,
+            &columns_of_kind(AggregateKind::Sum),
+            &columns_of_kind(AggregateKind::Max),
+            &columns_of_kind(AggregateKind::Min),
+            selection,
+        )
+        .expect("columns should be aggregatable");
+        // Groups are never empty, so every group has an extremum
+        let unwrap_extrema = |columns: Vec<&'a [Option<S>]>| {
+            columns.into_iter().map(|column| {
+                alloc.alloc_slice_fill_iter(
+                    column
+                        .iter()
+                        .map(|value| value.expect("groups are not empty")),
+                ) as &[_]
+            })
+        };
+        let mut sum_columns = sum_columns.into_iter();
+        let mut max_columns = unwrap_extrema(max_columns);
+        let mut min_columns = unwrap_extrema(min_columns);
+        let group_indexes = group_indexes(group_by_columns, selection);
+        let aggregate_result_columns = self
+            .aggregate_kinds()
+            .map(|(index, kind)| {
+                match kind {
+                    AggregateKind::Sum => sum_columns.next(),
+                    AggregateKind::Max => max_columns.next(),
+                    AggregateKind::Min => min_columns.next(),
+                    AggregateKind::OrderStatistic(rank) => {
+                        let ranks = count_column
+                            .iter()
+                            .map(|&count| rank.of(count))
+                            .collect::<Vec<_>>();
+                        Some(order_statistics_by_group_indexes(
+                            alloc,
+                            &aggregate_expr_columns[index].to_scalar_with_scaling(0),
+                            &group_indexes,
+                            &ranks,
+                        ))
+                    }
+                }
+                .expect("there is one aggregated column per aggregate kind")
+            })
+            .collect();
+        (
+            group_by_result_columns,
+            aggregate_result_columns,
+            count_column,
+        )
+    }
 }
 
 impl ProofPlan for GroupByExec {
-    #[allow(unused_variables)]
+    #[allow(unused_variables, clippy::too_many_lines)]
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut VerificationBuilder<S>,
@@ -83,14 +282,20 @@ impl ProofPlan for GroupByExec {
         let where_eval = self
             .where_clause
             .verifier_evaluate(builder, accessor, input_one_eval)?;
+        let where_clause_division_by_zero_evals =
+            builder.take_division_by_zero_indicator_evaluations();
         // 2. columns
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor, input_one_eval))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, accessor, input_one_eval)
+            })
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
-            .sum_expr
+            .aggregate_exprs
             .iter()
             .map(|aliased_expr| {
                 aliased_expr
@@ -98,41 +303,130 @@ impl ProofPlan for GroupByExec {
                     .verifier_evaluate(builder, accessor, input_one_eval)
             })
             .collect::<Result<Vec<_>, _>>()?;
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_evals = where_clause_division_by_zero_evals
+            .into_iter()
+            .map(|eval| (eval, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicator_evaluations()
+                    .into_iter()
+                    .map(|eval| (eval, Some(where_eval))),
+            )
+            .collect::<Vec<_>>();
+        verify_division_by_zero(builder, &division_by_zero_evals)?;
         // 3. filtered_columns
         let group_by_result_columns_evals =
-            builder.try_consume_mle_evaluations(self.group_by_exprs.len())?;
-        let sum_result_columns_evals = builder.try_consume_mle_evaluations(self.sum_expr.len())?;
-        let count_column_eval = builder.try_consume_mle_evaluation()?;
+            builder.try_consume_first_round_mle_evaluations(self.group_by_exprs.len())?;
+        let aggregate_result_columns_evals =
+            builder.try_consume_first_round_mle_evaluations(self.aggregate_kinds().count())?;
+        let count_column_eval = builder.try_consume_first_round_mle_evaluation()?;
 
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
         let output_one_eval = builder.try_consume_one_evaluation()?;
 
-        verify_group_by(
+        // The extrema and order statistics are part of the group, so they are folded with the
+        // `group_by` columns. The counts of the order statistics are summed.
+        let (mut sum_in_evals, mut sum_out_evals) = (Vec::new(), Vec::new());
+        let mut g_in_evals = group_by_evals.clone();
+        let mut g_out_evals = group_by_result_columns_evals.clone();
+        let mut extrema = Vec::new();
+        let mut order_statistics = Vec::new();
+        for ((index, kind), aggregate_result_eval) in
+            self.aggregate_kinds().zip(&aggregate_result_columns_evals)
+        {
+            let aggregate_eval = aggregate_evals[index];
+            if kind == AggregateKind::Sum {
+                sum_in_evals.push(aggregate_eval);
+                sum_out_evals.push(*aggregate_result_eval);
+                continue;
+            }
+            let extremum_in_eval = builder.try_consume_mle_evaluation()?;
+            g_in_evals.push(extremum_in_eval);
+            g_out_evals.push(*aggregate_result_eval);
+            if let AggregateKind::OrderStatistic(rank) = kind {
+                let (less_eval, less_or_equal_eval) = verify_order_statistic(
+                    builder,
+                    where_eval,
+                    aggregate_eval,
+                    extremum_in_eval,
+                    input_one_eval,
+                )?;
+                let less_count_eval = builder.try_consume_mle_evaluation()?;
+                let less_or_equal_count_eval = builder.try_consume_mle_evaluation()?;
+                sum_in_evals.extend([less_eval, less_or_equal_eval]);
+                sum_out_evals.extend([less_count_eval, less_or_equal_count_eval]);
+                order_statistics.push((rank, less_count_eval, less_or_equal_count_eval));
+            } else {
+                extrema.push((kind, aggregate_eval, extremum_in_eval));
+            }
+        }
+
+        let (g_in_star_eval, g_out_star_eval) = verify_group_by(
             builder,
             alpha,
             beta,
             input_one_eval,
             output_one_eval,
-            (group_by_evals, aggregate_evals, where_eval),
-            (
-                group_by_result_columns_evals.clone(),
-                sum_result_columns_evals.clone(),
-                count_column_eval,
-            ),
+            (g_in_evals, sum_in_evals, where_eval),
+            (g_out_evals, sum_out_evals, count_column_eval),
         )?;
+        for (kind, aggregate_eval, extremum_in_eval) in extrema {
+            let witness_eval = if kind == AggregateKind::Max {
+                verify_extremum::<_, true>(
+                    builder,
+                    where_eval,
+                    aggregate_eval,
+                    extremum_in_eval,
+                    input_one_eval,
+                )?
+            } else {
+                verify_extremum::<_, false>(
+                    builder,
+                    where_eval,
+                    aggregate_eval,
+                    extremum_in_eval,
+                    input_one_eval,
+                )?
+            };
+            // sum g_in_star * witness - g_out_star = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::ZeroSum,
+                g_in_star_eval * witness_eval - g_out_star_eval,
+                2,
+            )?;
+        }
+        for (rank, less_count_eval, less_or_equal_count_eval) in order_statistics {
+            verify_rank(
+                builder,
+                rank,
+                (count_column_eval, output_one_eval),
+                (less_count_eval, less_or_equal_count_eval),
+                output_one_eval,
+            )?;
+        }
         match result {
+            _ if self.is_nestable() => {
+                verify_monotonic::<S, true, true>(
+                    builder,
+                    alpha,
+                    beta,
+                    group_by_result_columns_evals[0],
+                    output_one_eval,
+                )?;
+            }
             Some(table) => {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError {
                         error: "Result does not all correct group by columns.",
                     })?;
-                if (0..table.num_rows() - 1)
-                    .any(|i| compare_indexes_by_owned_columns(&cols, i, i + 1).is_ge())
+                if (1..table.num_rows())
+                    .any(|i| compare_indexes_by_owned_columns(&cols, i - 1, i).is_ge())
                 {
                     Err(ProofError::VerificationError {
                         error: "Result of group by not ordered as expected.",
@@ -148,20 +442,19 @@ impl ProofPlan for GroupByExec {
 
         let column_evals = group_by_result_columns_evals
             .into_iter()
-            .chain(sum_result_columns_evals)
+            .chain(aggregate_result_columns_evals)
             .chain(iter::once(count_column_eval))
             .collect::<Vec<_>>();
         Ok(TableEvaluation::new(column_evals, output_one_eval))
     }
 
-    #[allow(clippy::redundant_closure_for_method_calls)]
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
+            .chain(&self.aggregate_exprs)
+            .map(|aliased_expr| {
                 ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
-            }))
+            })
             .chain(iter::once(ColumnField::new(
                 self.count_alias.clone(),
                 ColumnType::BigInt,
@@ -169,13 +462,109 @@ impl ProofPlan for GroupByExec {
             .collect()
     }
 
+    fn get_intermediate_result_fields(&self) -> Vec<ColumnField> {
+        let group_by_fields = self.group_by_exprs.iter().map(|aliased_expr| {
+            ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+        });
+        let mut previous_index = None;
+        let aggregate_fields = self.aggregate_kinds().map(move |(index, kind)| {
+            let aliased_expr = &self.aggregate_exprs[index];
+            // The second order statistic of an aggregate expression is the one interpolated to
+            let alias = if previous_index.replace(index) == Some(index) {
+                percentile_high_ident(&aliased_expr.alias)
+            } else {
+                aliased_expr.alias.clone()
+            };
+            let data_type = match (&aliased_expr.expr, kind) {
+                (DynProofExpr::Aggregate(aggregate), AggregateKind::OrderStatistic(_)) => {
+                    aggregate.expr().data_type()
+                }
+                (DynProofExpr::Aggregate(aggregate), _)
+                    if aggregate.op() == AggregationOperator::Avg =>
+                {
+                    avg_sum_type(aggregate.expr().data_type())
+                }
+                (expr, _) => expr.data_type(),
+            };
+            ColumnField::new(alias, data_type)
+        });
+        group_by_fields
+            .chain(aggregate_fields)
+            .chain(iter::once(ColumnField::new(
+                self.count_alias.clone(),
+                ColumnType::BigInt,
+            )))
+            .collect()
+    }
+
+    fn finalize_result<S: Scalar>(
+        &self,
+        result: OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, QueryError> {
+        let avg_aggregates = self
+            .aggregate_exprs
+            .iter()
+            .filter_map(|aliased_expr| match &aliased_expr.expr {
+                DynProofExpr::Aggregate(aggregate)
+                    if aggregate.op() == AggregationOperator::Avg =>
+                {
+                    Some((&aliased_expr.alias, aggregate.data_type()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let percentile_aggregates = self.interpolated_percentiles().collect::<Vec<_>>();
+        if avg_aggregates.is_empty() && percentile_aggregates.is_empty() {
+            return Ok(result);
+        }
+        let mut columns = result.into_inner();
+        let counts = columns
+            .get(&self.count_alias)
+            .expect("The result has the intermediate fields")
+            .clone();
+        let highs = percentile_aggregates
+            .iter()
+            .map(|(alias, _)| {
+                columns
+                    .shift_remove(&percentile_high_ident(alias))
+                    .expect("The result has the intermediate fields")
+            })
+            .collect::<Vec<_>>();
+        let columns = columns
+            .into_iter()
+            .map(|(name, column)| {
+                let column = match (
+                    avg_aggregates.iter().find(|(alias, _)| **alias == name),
+                    percentile_aggregates
+                        .iter()
+                        .position(|(alias, _)| **alias == name),
+                ) {
+                    (Some((_, avg_type)), _) => finalize_avg(column, &counts, *avg_type)?,
+                    (None, Some(i)) => {
+                        let (alias, percentile) = percentile_aggregates[i];
+                        let cont_type = self
+                            .aggregate_exprs
+                            .iter()
+                            .find(|aliased_expr| aliased_expr.alias == *alias)
+                            .expect("percentiles are aggregate expressions")
+                            .expr
+                            .data_type();
+                        finalize_percentile_cont(
+                            &column, &highs[i], &counts, percentile, cont_type,
+                        )?
+                    }
+                    (None, None) => column,
+                };
+                Ok((name, column))
+            })
+            .collect::<Result<Vec<_>, QueryError>>()?;
+        Ok(OwnedTable::try_from_iter(columns)?)
+    }
+
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         let mut columns = IndexSet::default();
 
-        for col in &self.group_by_exprs {
-            columns.insert(col.get_column_reference());
-        }
-        for aliased_expr in &self.sum_expr {
+        for aliased_expr in self.group_by_exprs.iter().chain(&self.aggregate_exprs) {
             aliased_expr.expr.get_column_references(&mut columns);
         }
 
@@ -193,7 +582,7 @@ impl ProverEvaluate for GroupByExec {
     #[tracing::instrument(name = "GroupByExec::first_round_evaluate", level = "debug", skip_all)]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
@@ -213,36 +602,45 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
             .collect::<Vec<_>>();
-        let sum_columns = self
-            .sum_expr
+        let aggregate_columns = self
+            .aggregate_exprs
             .iter()
             .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
             .collect::<Vec<_>>();
         // Compute filtered_columns
-        let AggregatedColumns {
-            group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
-            count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
-        let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
+        let (group_by_result_columns, aggregate_result_columns, count_column) =
+            self.aggregate(alloc, &group_by_columns, &aggregate_columns, selection);
+        let aggregate_result_columns_iter = aggregate_result_columns
+            .iter()
+            .map(|col| Column::Scalar(col));
         let res = Table::<'a, S>::try_from_iter(
-            self.get_column_result_fields()
+            self.get_intermediate_result_fields()
                 .into_iter()
                 .map(|field| field.name())
                 .zip(
                     group_by_result_columns
                         .into_iter()
-                        .chain(sum_result_columns_iter)
+                        .chain(aggregate_result_columns_iter)
                         .chain(iter::once(Column::BigInt(count_column))),
                 ),
         )
         .expect("Failed to create table from column references");
+        // The result columns are committed before the challenges are drawn
+        // so that the plan can be the input of other plans
+        for column in res.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
         builder.request_post_result_challenges(2);
         builder.produce_one_evaluation_length(count_column.len());
+        if self.is_nestable() {
+            first_round_evaluate_monotonic(
+                builder,
+                alloc,
+                Self::first_group_by_column(alloc, &res),
+            );
+        }
 
         log::log_memory_usage("End");
 
@@ -250,7 +648,7 @@ impl ProverEvaluate for GroupByExec {
     }
 
     #[tracing::instrument(name = "GroupByExec::final_round_evaluate", level = "debug", skip_all)]
-    #[allow(unused_variables)]
+    #[allow(unused_variables, clippy::too_many_lines)]
     fn final_round_evaluate<'a, S: Scalar>(
         &self,
         builder: &mut FinalRoundBuilder<'a, S>,
@@ -268,58 +666,171 @@ impl ProverEvaluate for GroupByExec {
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        let where_clause_division_by_zero_indicators = builder.take_division_by_zero_indicators();
 
         // 2. columns
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.prover_evaluate(builder, alloc, table))
+            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect::<Vec<_>>();
-        let sum_columns = self
-            .sum_expr
+        let aggregate_columns = self
+            .aggregate_exprs
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect::<Vec<_>>();
+        // divisions by zero in the where clause or on the selected rows
+        let division_by_zero_indicators = where_clause_division_by_zero_indicators
+            .into_iter()
+            .map(|indicator| (indicator, None))
+            .chain(
+                builder
+                    .take_division_by_zero_indicators()
+                    .into_iter()
+                    .map(|indicator| (indicator, Some(selection))),
+            )
+            .collect::<Vec<_>>();
+        final_round_evaluate_division_by_zero(builder, alloc, &division_by_zero_indicators);
         // 3. Compute filtered_columns
-        let AggregatedColumns {
-            group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
-            count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+        let (group_by_result_columns, aggregate_result_columns, count_column) =
+            self.aggregate(alloc, &group_by_columns, &aggregate_columns, selection);
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
         // 4. Tally results
-        let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
+        let aggregate_result_columns_iter = aggregate_result_columns
+            .iter()
+            .map(|col| Column::Scalar(col));
         let columns = group_by_result_columns
             .clone()
             .into_iter()
-            .chain(sum_result_columns_iter)
+            .chain(aggregate_result_columns_iter)
             .chain(iter::once(Column::BigInt(count_column)));
         let res = Table::<'a, S>::try_from_iter(
-            self.get_column_result_fields()
+            self.get_intermediate_result_fields()
                 .into_iter()
                 .map(|field| field.name())
                 .zip(columns.clone()),
         )
         .expect("Failed to create table from column references");
-        // 5. Produce MLEs
-        for column in columns {
-            builder.produce_intermediate_mle(column);
+        // The extrema and order statistics are part of the group, so they are folded with the
+        // `group_by` columns. `extremum_in` assigns to each selected row the extremum or order
+        // statistic of its group. The counts of the order statistics are summed.
+        let group_indexes = group_indexes(&group_by_columns, selection);
+        let (mut sum_in, mut sum_out) = (Vec::new(), Vec::new());
+        let mut g_in = group_by_columns.clone();
+        let mut g_out = group_by_result_columns.clone();
+        let mut extrema = Vec::new();
+        let mut order_statistics = Vec::new();
+        for ((index, kind), aggregate_result_column) in
+            self.aggregate_kinds().zip(aggregate_result_columns)
+        {
+            let aggregate_column = aggregate_columns[index];
+            if kind == AggregateKind::Sum {
+                sum_in.push(aggregate_column);
+                sum_out.push(aggregate_result_column);
+                continue;
+            }
+            let extremum_in: &[S] = alloc.alloc_slice_fill_iter(group_indexes.iter().map(
+                |group_index| match group_index {
+                    Some(j) => aggregate_result_column[*j],
+                    None => S::zero(),
+                },
+            ));
+            builder.produce_intermediate_mle(extremum_in);
+            g_in.push(Column::Scalar(extremum_in));
+            g_out.push(Column::Scalar(aggregate_result_column));
+            if let AggregateKind::OrderStatistic(rank) = kind {
+                let column: &[S] =
+                    alloc.alloc_slice_copy(&aggregate_column.to_scalar_with_scaling(0));
+                let (less, less_or_equal) = final_round_evaluate_order_statistic(
+                    builder,
+                    alloc,
+                    selection,
+                    column,
+                    extremum_in,
+                );
+                let num_groups = count_column.len();
+                let less_counts = count_by_group(alloc, less, &group_indexes, num_groups);
+                let less_or_equal_counts =
+                    count_by_group(alloc, less_or_equal, &group_indexes, num_groups);
+                for (indicator, counts) in
+                    [(less, less_counts), (less_or_equal, less_or_equal_counts)]
+                {
+                    builder.produce_intermediate_mle(counts);
+                    sum_in.push(Column::Boolean(indicator));
+                    sum_out.push(alloc.alloc_slice_fill_iter(counts.iter().map(|&c| S::from(c))));
+                }
+                order_statistics.push((rank, less_counts, less_or_equal_counts));
+            } else {
+                extrema.push((kind, aggregate_column, extremum_in, aggregate_result_column));
+            }
         }
-        // 6. Prove group by
-        prove_group_by(
+        // 5. Prove group by
+        let (g_in_star, g_out_star) = prove_group_by(
             builder,
             alloc,
             alpha,
             beta,
-            (&group_by_columns, &sum_columns, selection),
-            (&group_by_result_columns, &sum_result_columns, count_column),
+            (&g_in, &sum_in, selection),
+            (&g_out, &sum_out, count_column),
             table.num_rows(),
         );
+        // 6. Prove extrema
+        for (kind, aggregate_column, extremum_in, aggregate_result_column) in extrema {
+            let column: &[S] = alloc.alloc_slice_copy(&aggregate_column.to_scalar_with_scaling(0));
+            let witness =
+                compute_extremum_witness(alloc, column, &group_indexes, aggregate_result_column);
+            if kind == AggregateKind::Max {
+                final_round_evaluate_extremum::<_, true>(
+                    builder,
+                    alloc,
+                    selection,
+                    column,
+                    extremum_in,
+                    witness,
+                );
+            } else {
+                final_round_evaluate_extremum::<_, false>(
+                    builder,
+                    alloc,
+                    selection,
+                    column,
+                    extremum_in,
+                    witness,
+                );
+            }
+            // sum g_in_star * witness - g_out_star = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (S::one(), vec![Box::new(g_in_star), Box::new(witness)]),
+                    (-S::one(), vec![Box::new(g_out_star)]),
+                ],
+            );
+        }
+        // 7. Prove the ranks of the order statistics
+        let output_ones: &[bool] = alloc.alloc_slice_fill_copy(count_column.len(), true);
+        for (rank, less_counts, less_or_equal_counts) in order_statistics {
+            final_round_evaluate_rank(
+                builder,
+                alloc,
+                rank,
+                (count_column, output_ones),
+                (less_counts, less_or_equal_counts),
+            );
+        }
+        // 8. Prove the uniqueness of the groups
+        if self.is_nestable() {
+            final_round_evaluate_monotonic::<S, true, true>(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                Self::first_group_by_column(alloc, &res),
+            );
+        }
 
         log::log_memory_usage("End");
 
@@ -327,7 +838,7 @@ impl ProverEvaluate for GroupByExec {
     }
 }
 
-#[allow(clippy::unnecessary_wraps)]
+/// Verify the group by and return the evaluations of `g_in_star` and `g_out_star`.
 fn verify_group_by<S: Scalar>(
     builder: &mut VerificationBuilder<S>,
     alpha: S,
@@ -336,7 +847,7 @@ fn verify_group_by<S: Scalar>(
     output_one_eval: S,
     (g_in_evals, sum_in_evals, sel_in_eval): (Vec<S>, Vec<S>, S),
     (g_out_evals, sum_out_evals, count_out_eval): (Vec<S>, Vec<S>, S),
-) -> Result<(), ProofError> {
+) -> Result<(S, S), ProofError> {
     // g_in_fold = alpha * sum beta^j * g_in[j]
     let g_in_fold_eval = alpha * fold_vals(beta, &g_in_evals);
     // g_out_fold = alpha * sum beta^j * g_out[j]
@@ -370,13 +881,14 @@ fn verify_group_by<S: Scalar>(
         2,
     )?;
 
-    Ok(())
+    Ok((g_in_star_eval, g_out_star_eval))
 }
 
 #[allow(
     clippy::missing_panics_doc,
     reason = "alpha is guaranteed to not be zero in this context"
 )]
+/// Prove the group by and return `g_in_star` and `g_out_star`.
 pub fn prove_group_by<'a, S: Scalar>(
     builder: &mut FinalRoundBuilder<'a, S>,
     alloc: &'a Bump,
@@ -385,7 +897,7 @@ pub fn prove_group_by<'a, S: Scalar>(
     (g_in, sum_in, sel_in): (&[Column<S>], &[Column<S>], &'a [bool]),
     (g_out, sum_out, count_out): (&[Column<S>], &[&'a [S]], &'a [i64]),
     n: usize,
-) {
+) -> (&'a [S], &'a [S]) {
     let m = count_out.len();
     let input_ones = alloc.alloc_slice_fill_copy(n, true);
     let output_ones = alloc.alloc_slice_fill_copy(m, true);
@@ -464,4 +976,5 @@ pub fn prove_group_by<'a, S: Scalar>(
             (-S::one(), vec![Box::new(output_ones as &[_])]),
         ],
     );
+    (g_in_star, g_out_star)
 }
@@ -23,7 +23,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = group_by(
-        cols_expr(t, &["a"], &accessor),
+        cols_expr_plan(t, &["a"], &accessor),
         vec![sum_expr(column(t, "c", &accessor), "sum_c")],
         "__count__",
         tab(t),
@@ -52,7 +52,7 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = group_by(
-        cols_expr(t, &["a"], &accessor),
+        cols_expr_plan(t, &["a"], &accessor),
         vec![sum_expr(
             add(
                 multiply(column(t, "c", &accessor), const_bigint(2)),
@@ -75,6 +75,174 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+/// `select a, avg(c) as avg_c, count(*) as __count__ from sxt.t where b = 99 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_averages() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 1]),
+        bigint("b", [99, 99, 99, 99, 0, 99]),
+        bigint("c", [101, -102, -104, 104, 105, 102]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![avg_expr(column(t, "c", &accessor), "avg_c")],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        decimal75("avg_c", 25, 6, [102_333_333, -103_000_000]),
+        bigint("__count__", [3, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, median(c) as median_c, sum(c) as sum_c,
+/// percentile_cont(0.25) within group (order by c) as p25_c,
+/// percentile_disc(0.5) within group (order by c) as p50_c, count(*) as __count__
+/// from sxt.t where b = 99 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_percentiles() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 1, 1]),
+        bigint("b", [99, 99, 99, 99, 0, 99, 99]),
+        bigint("c", [10, -4, 7, 3, 6, 20, -1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![
+            median_expr(column(t, "c", &accessor), "median_c"),
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            percentile_cont_expr(25, 2, column(t, "c", &accessor), "p25_c"),
+            percentile_disc_expr(5, 1, column(t, "c", &accessor), "p50_c"),
+        ],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // The groups are -1, 3, 10, 20 and -4, 7
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        decimal75("median_c", 20, 1, [65, 15]),
+        bigint("sum_c", [32, 3]),
+        decimal75("p25_c", 21, 2, [200, -125]),
+        bigint("p50_c", [3, -4]),
+        bigint("__count__", [4, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a + b as a_plus_b, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a + b`
+#[test]
+fn we_can_prove_a_group_by_on_an_expression() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 3]),
+        bigint("b", [2, 1, 3, 0, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        vec![aliased_plan(
+            add(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "a_plus_b",
+        )],
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a_plus_b", [1, 3, 5]),
+        bigint("sum_c", [104, 101 + 102 + 105, 103]),
+        bigint("__count__", [1, 3, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, max(c) as max_c, sum(c) as sum_c, min(c - b) as min_c, count(*) as __count__ from sxt.t where b <> 0 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_max_and_min() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3, 3]),
+        bigint("b", [99, 98, 99, 99, 0, 1, 1]),
+        bigint("c", [104, -102, 104, 104, 105, -7, -7]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![
+            max_expr(column(t, "c", &accessor), "max_c"),
+            sum_expr(column(t, "c", &accessor), "sum_c"),
+            min_expr(
+                subtract(column(t, "c", &accessor), column(t, "b", &accessor)),
+                "min_c",
+            ),
+        ],
+        "__count__",
+        tab(t),
+        not(equal(column(t, "b", &accessor), const_bigint(0))),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("max_c", [104, 104, -7]),
+        bigint("sum_c", [208, 2, -14]),
+        bigint("min_c", [5, -200, -8]),
+        bigint("__count__", [2, 2, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select max(c) as max_c, min(c) as min_c, count(*) as __count__ from sxt.t where b = 99`
+#[test]
+fn we_can_prove_a_group_by_with_max_and_min_without_group_by_columns() {
+    let data = owned_table([bigint("b", [99, 99, 0, 99]), int("c", [3, -1, 100, 2])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        vec![],
+        vec![
+            max_expr(column(t, "c", &accessor), "max_c"),
+            min_expr(column(t, "c", &accessor), "min_c"),
+        ],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        int("max_c", [3]),
+        int("min_c", [-1]),
+        bigint("__count__", [3]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 #[allow(clippy::too_many_lines)]
 #[test]
 fn we_can_prove_a_complex_group_by_query_with_many_columns() {
@@ -160,7 +328,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
     //  GROUP BY scalar_group, int128_group, bigint_group
     let expr = group_by(
-        cols_expr(
+        cols_expr_plan(
             t,
             &["scalar_group", "int128_group", "bigint_group"],
             &accessor,
@@ -230,3 +398,19 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+#[should_panic(expected = "Group by aggregation is not supported")]
+fn we_cannot_create_a_group_by_with_a_count_of_an_expression() {
+    let data = owned_table([bigint("a", [1, 2]), bigint("b", [3, 4])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![count_expr(column(t, "b", &accessor), "count_b")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+}
@@ -0,0 +1,544 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter},
+    join_exec::{prove_lookup, verify_lookup},
+    DynProofPlan, OrderByExec,
+};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes, filter_util::filter_columns,
+            order_by_util::compare_indexes_by_columns, union_util::column_union, Column,
+            ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialTerm, SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_sorted_with_ties, first_round_evaluate_sorted,
+            verify_sorted_with_ties,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> INTERSECT <ProofPlan>
+/// ```
+/// or, if `negated` is set,
+/// ```ignore
+///     <ProofPlan> EXCEPT <ProofPlan>
+/// ```
+///
+/// The result consists of the distinct rows of the left input which do (or, if `negated` is set,
+/// do not) occur in the right input, in ascending order. The columns are those of the left input.
+///
+/// Let `L` and `R` be the inputs and `O` the output. The prover commits to `O` and the sorted list `U`
+/// of the unique rows of `L` and `R` along with how often each of them occurs in `L` and `R`,
+/// whether it occurs in `L` and `R` at all, the inverses of its counts and the selection `s` of
+/// the rows of `U` which make up `O`. All of these are committed to before any challenge is drawn.
+/// We then prove that
+/// 1. `O` is the filter of `U` by `s`.
+/// 2. The counts in `L` and `R` of each row of `U` are the number of its occurrences in `L` and `R`
+///     by proving the multiset equalities of the rows of `L` and `R` and the rows of `U`
+///     with multiplicities the counts.
+/// 3. A row of `U` occurs in `L` if and only if its count in `L` is nonzero, i.e.
+///     `occurs - count * inverse = 0` and `count - count * occurs = 0`, and likewise for `R`.
+/// 4. `s = occurs in L * occurs in R` (or `occurs in L * (1 - occurs in R)` if `negated` is set).
+/// 5. The rows of `U` are strictly increasing, i.e. they are sorted and none of them is equal to
+///     the previous one, so that the counts are unique.
+///
+/// Since the ordering is proven using sign decompositions, the columns can only be of the types
+/// supported by an [`OrderByExec`].
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct IntersectExec {
+    pub(crate) left: Box<DynProofPlan>,
+    pub(crate) right: Box<DynProofPlan>,
+    pub(crate) negated: bool,
+}
+
+impl IntersectExec {
+    /// Creates a new intersect execution plan, which is an except if `negated` is set.
+    ///
+    /// # Panics
+    /// Panics if the inputs have no columns, different column types
+    /// or columns of types not supported by an [`OrderByExec`].
+    pub fn new(left: Box<DynProofPlan>, right: Box<DynProofPlan>, negated: bool) -> Self {
+        let left_fields = left.get_column_result_fields();
+        let right_fields = right.get_column_result_fields();
+        assert!(
+            !left_fields.is_empty(),
+            "The inputs of an intersect must have columns"
+        );
+        assert!(
+            left_fields.len() == right_fields.len()
+                && left_fields
+                    .iter()
+                    .zip(&right_fields)
+                    .all(|(left_field, right_field)| {
+                        left_field.data_type() == right_field.data_type()
+                    }),
+            "The inputs of an intersect must have the same column types"
+        );
+        assert!(
+            left_fields
+                .iter()
+                .all(|field| OrderByExec::is_supported_column_type(field.data_type())),
+            "Intersect column type is not supported"
+        );
+        Self {
+            left,
+            right,
+            negated,
+        }
+    }
+}
+
+impl ProofPlan for IntersectExec
+where
+    IntersectExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let left_eval = self
+            .left
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let right_eval = self
+            .right
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let num_columns = left_eval.column_evals().len();
+
+        // First round
+        let output_column_evals = builder.try_consume_first_round_mle_evaluations(num_columns)?;
+        let unique_row_evals = builder.try_consume_first_round_mle_evaluations(num_columns)?;
+        let [left_count_eval, right_count_eval, left_occurs_eval, right_occurs_eval, left_count_inverse_eval, right_count_inverse_eval, selection_eval] =
+            builder
+                .try_consume_first_round_mle_evaluations(7)?
+                .try_into()
+                .expect("We consumed exactly 7 evaluations");
+        let output_one_eval = builder.try_consume_one_evaluation()?;
+        let unique_row_one_eval = builder.try_consume_one_evaluation()?;
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        // 1. The output is the filter of the unique rows
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            unique_row_one_eval,
+            output_one_eval,
+            &unique_row_evals,
+            selection_eval,
+            &output_column_evals,
+        )?;
+
+        // 2. The counts in the inputs
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            left_eval.column_evals(),
+            left_eval.one_eval(),
+            &unique_row_evals,
+            unique_row_one_eval,
+            left_count_eval,
+        )?;
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            right_eval.column_evals(),
+            right_eval.one_eval(),
+            &unique_row_evals,
+            unique_row_one_eval,
+            right_count_eval,
+        )?;
+
+        // 3. A row occurs in an input if and only if its count is nonzero
+        for (count_eval, occurs_eval, count_inverse_eval) in [
+            (left_count_eval, left_occurs_eval, left_count_inverse_eval),
+            (
+                right_count_eval,
+                right_occurs_eval,
+                right_count_inverse_eval,
+            ),
+        ] {
+            // occurs - count * inverse = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                occurs_eval - count_eval * count_inverse_eval,
+                2,
+            )?;
+            // count - count * occurs = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                count_eval - count_eval * occurs_eval,
+                2,
+            )?;
+        }
+
+        // 4. The selection is whether the row occurs in the left input and (not) in the right input
+        // s - occurs_left * occurs_right = 0 or s - occurs_left + occurs_left * occurs_right = 0
+        let selection_constraint_eval = if self.negated {
+            selection_eval - left_occurs_eval + left_occurs_eval * right_occurs_eval
+        } else {
+            selection_eval - left_occurs_eval * right_occurs_eval
+        };
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            selection_constraint_eval,
+            2,
+        )?;
+
+        // 5. The unique rows are strictly increasing
+        let tie_evals = verify_sorted_with_ties(
+            builder,
+            alpha,
+            beta,
+            &unique_row_evals,
+            &vec![OrderByDirection::Asc; num_columns],
+            unique_row_one_eval,
+        )?;
+        let singleton_one_eval = builder.mle_evaluations.singleton_one_evaluation;
+        // eq_(m-1) - chi_1 * chi_n = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            tie_evals[num_columns - 1] - singleton_one_eval * unique_row_one_eval,
+            2,
+        )?;
+
+        Ok(TableEvaluation::new(output_column_evals, output_one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.left.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut column_refs = self.left.get_column_references();
+        column_refs.extend(self.right.get_column_references());
+        column_refs
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        let mut table_refs = self.left.get_table_references();
+        table_refs.extend(self.right.get_table_references());
+        table_refs
+    }
+}
+
+impl ProverEvaluate for IntersectExec {
+    #[tracing::instrument(
+        name = "IntersectExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.first_round_evaluate(builder, alloc, table_map);
+        let right = self.right.first_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+
+        for column in witness.output.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
+        for column in &witness.unique_rows {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.produce_intermediate_mle(witness.left_counts);
+        builder.produce_intermediate_mle(witness.right_counts);
+        builder.produce_intermediate_mle(witness.left_occurs);
+        builder.produce_intermediate_mle(witness.right_occurs);
+        builder.produce_intermediate_mle(witness.left_count_inverses);
+        builder.produce_intermediate_mle(witness.right_count_inverses);
+        builder.produce_intermediate_mle(witness.selection);
+        builder.produce_one_evaluation_length(witness.output.num_rows());
+        builder.produce_one_evaluation_length(witness.num_unique_rows());
+        first_round_evaluate_sorted(
+            builder,
+            alloc,
+            &witness.unique_row_scalars,
+            witness.num_unique_rows(),
+        );
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+
+    #[tracing::instrument(
+        name = "IntersectExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    #[allow(clippy::too_many_lines)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.final_round_evaluate(builder, alloc, table_map);
+        let right = self.right.final_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let num_unique_rows = witness.num_unique_rows();
+
+        let left_columns = left.columns().copied().collect::<Vec<_>>();
+        let right_columns = right.columns().copied().collect::<Vec<_>>();
+        let output_columns = witness.output.columns().copied().collect::<Vec<_>>();
+
+        // 1. The output is the filter of the unique rows
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &witness.unique_rows,
+            witness.selection,
+            &output_columns,
+            num_unique_rows,
+            witness.output.num_rows(),
+        );
+
+        // 2. The counts in the inputs
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &left_columns,
+            &witness.unique_rows,
+            witness.left_counts,
+        );
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &right_columns,
+            &witness.unique_rows,
+            witness.right_counts,
+        );
+
+        // 3. A row occurs in an input if and only if its count is nonzero
+        for (counts, occurs, count_inverses) in [
+            (
+                witness.left_counts,
+                witness.left_occurs,
+                witness.left_count_inverses,
+            ),
+            (
+                witness.right_counts,
+                witness.right_occurs,
+                witness.right_count_inverses,
+            ),
+        ] {
+            // occurs - count * inverse = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(occurs)]),
+                    (-S::one(), vec![Box::new(counts), Box::new(count_inverses)]),
+                ],
+            );
+            // count - count * occurs = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(counts)]),
+                    (-S::one(), vec![Box::new(counts), Box::new(occurs)]),
+                ],
+            );
+        }
+
+        // 4. The selection is whether the row occurs in the left input and (not) in the right input
+        // s - occurs_left * occurs_right = 0 or s - occurs_left + occurs_left * occurs_right = 0
+        let mut selection_terms: Vec<SumcheckSubpolynomialTerm<S>> = vec![
+            (S::one(), vec![Box::new(witness.selection)]),
+            (
+                if self.negated { S::one() } else { -S::one() },
+                vec![
+                    Box::new(witness.left_occurs),
+                    Box::new(witness.right_occurs),
+                ],
+            ),
+        ];
+        if self.negated {
+            selection_terms.push((-S::one(), vec![Box::new(witness.left_occurs)]));
+        }
+        builder
+            .produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, selection_terms);
+
+        // 5. The unique rows are strictly increasing
+        let ties = final_round_evaluate_sorted_with_ties(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &witness.unique_row_scalars,
+            &vec![OrderByDirection::Asc; witness.unique_rows.len()],
+            num_unique_rows,
+        );
+        let chi_n: &[bool] = alloc.alloc_slice_fill_copy(num_unique_rows, true);
+        let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+        // eq_(m-1) - chi_1 * chi_n = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(ties[ties.len() - 1])]),
+                (-S::one(), vec![Box::new(chi_1), Box::new(chi_n)]),
+            ],
+        );
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+}
+
+/// The witness committed to by the prover of an [`IntersectExec`]
+struct IntersectWitness<'a, S: Scalar> {
+    output: Table<'a, S>,
+    unique_rows: Vec<Column<'a, S>>,
+    unique_row_scalars: Vec<&'a [S]>,
+    left_counts: &'a [i64],
+    right_counts: &'a [i64],
+    left_occurs: &'a [bool],
+    right_occurs: &'a [bool],
+    left_count_inverses: &'a [S],
+    right_count_inverses: &'a [S],
+    selection: &'a [bool],
+}
+
+impl<S: Scalar> IntersectWitness<'_, S> {
+    fn num_unique_rows(&self) -> usize {
+        self.left_counts.len()
+    }
+}
+
+impl IntersectExec {
+    /// Compute the intersection along with all the witness columns needed to prove it.
+    ///
+    /// # Panics
+    /// Panics if the inputs have different column types.
+    fn compute_witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        left: &Table<'a, S>,
+        right: &Table<'a, S>,
+    ) -> IntersectWitness<'a, S> {
+        let fields = self.get_column_result_fields();
+        let columns = left
+            .columns()
+            .zip(right.columns())
+            .zip(&fields)
+            .map(|((left_column, right_column), field)| {
+                column_union(&[left_column, right_column], alloc, field.data_type())
+                    .expect("The inputs have the same column types")
+            })
+            .collect::<Vec<_>>();
+
+        // Sort the rows of both inputs and count the occurrences of each unique row on both sides
+        let mut indexes = (0..left.num_rows() + right.num_rows()).collect::<Vec<_>>();
+        indexes.sort_unstable_by(|&i, &j| compare_indexes_by_columns(&columns, i, j));
+        let mut unique_indexes: Vec<usize> = Vec::new();
+        let mut counts: Vec<(i64, i64)> = Vec::new();
+        for index in indexes {
+            if unique_indexes.last().map_or(true, |&last| {
+                compare_indexes_by_columns(&columns, last, index) != Ordering::Equal
+            }) {
+                unique_indexes.push(index);
+                counts.push((0, 0));
+            }
+            let (left_count, right_count) = counts.last_mut().expect("A count was pushed");
+            if index < left.num_rows() {
+                *left_count += 1;
+            } else {
+                *right_count += 1;
+            }
+        }
+        let unique_rows = columns
+            .iter()
+            .map(|column| {
+                apply_column_to_indexes(column, alloc, &unique_indexes)
+                    .expect("The indexes are in bounds")
+            })
+            .collect::<Vec<_>>();
+        let unique_row_scalars = unique_rows
+            .iter()
+            .map(|column| alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0)) as &[_])
+            .collect();
+
+        let left_counts: &[i64] = alloc.alloc_slice_fill_iter(counts.iter().map(|count| count.0));
+        let right_counts: &[i64] = alloc.alloc_slice_fill_iter(counts.iter().map(|count| count.1));
+        let left_occurs: &[bool] =
+            alloc.alloc_slice_fill_iter(left_counts.iter().map(|&count| count > 0));
+        let right_occurs: &[bool] =
+            alloc.alloc_slice_fill_iter(right_counts.iter().map(|&count| count > 0));
+        let inverses = |counts: &[i64]| {
+            let inverses: &mut [S] =
+                alloc.alloc_slice_fill_iter(counts.iter().map(|&count| S::from(count)));
+            slice_ops::batch_inversion(inverses);
+            inverses as &[_]
+        };
+        let selection: &[bool] = alloc.alloc_slice_fill_iter(
+            left_occurs
+                .iter()
+                .zip(right_occurs)
+                .map(|(&left, &right)| left && right != self.negated),
+        );
+        let (output_columns, output_length) = filter_columns(alloc, &unique_rows, selection);
+        let output = Table::try_from_iter_with_options(
+            fields
+                .into_iter()
+                .map(|field| field.name())
+                .zip(output_columns),
+            TableOptions::new(Some(output_length)),
+        )
+        .expect("Failed to create table from iterator");
+
+        IntersectWitness {
+            output,
+            unique_rows,
+            unique_row_scalars,
+            left_counts,
+            right_counts,
+            left_occurs,
+            right_occurs,
+            left_count_inverses: inverses(left_counts),
+            right_count_inverses: inverses(right_counts),
+            selection,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use super::test_utility::*;
+use crate::{
+    base::database::{owned_table_utility::*, ColumnType},
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+/// `select customer, item from sxt.orders intersect select customer, item from sxt.returns`
+#[test]
+fn we_can_prove_an_intersect_on_several_columns() {
+    let (accessor, orders_ref, returns_ref) =
+        accessor_with_orders_and_returns::<InnerProductProof>(());
+    let ast = intersect_exec(
+        projection(
+            cols_expr_plan(orders_ref, &["customer", "item"], &accessor),
+            tab(orders_ref),
+        ),
+        table_exec(
+            returns_ref,
+            vec![
+                column_field("customer", ColumnType::BigInt),
+                column_field("item", ColumnType::Int),
+            ],
+        ),
+        false,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, orders_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("customer", [1_i64, 3]), int("item", [20, 40])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select customer, item from sxt.orders except select customer, item from sxt.returns`
+#[test]
+fn we_can_prove_an_except_on_several_columns() {
+    let (accessor, orders_ref, returns_ref) =
+        accessor_with_orders_and_returns::<InnerProductProof>(());
+    let ast = intersect_exec(
+        projection(
+            cols_expr_plan(orders_ref, &["customer", "item"], &accessor),
+            tab(orders_ref),
+        ),
+        projection(
+            cols_expr_plan(returns_ref, &["customer", "item"], &accessor),
+            tab(returns_ref),
+        ),
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, orders_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("customer", [-1_i64, 2, 3]),
+        int("item", [30, 10, 10]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select item from sxt.returns except select item from sxt.orders where customer = 3`
+#[test]
+fn we_can_prove_an_except_over_a_filter() {
+    let (accessor, orders_ref, returns_ref) =
+        accessor_with_orders_and_returns::<InnerProductProof>(());
+    let ast = intersect_exec(
+        projection(
+            cols_expr_plan(returns_ref, &["item"], &accessor),
+            tab(returns_ref),
+        ),
+        filter(
+            cols_expr_plan(orders_ref, &["item"], &accessor),
+            tab(orders_ref),
+            equal(column(orders_ref, "customer", &accessor), const_bigint(3)),
+        ),
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, orders_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([int("item", [20])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_intersect_with_an_empty_input() {
+    let (accessor, orders_ref, returns_ref) =
+        accessor_with_orders_and_returns::<InnerProductProof>(());
+    let ast = intersect_exec(
+        filter(
+            cols_expr_plan(orders_ref, &["customer", "item"], &accessor),
+            tab(orders_ref),
+            equal(column(orders_ref, "customer", &accessor), const_bigint(4)),
+        ),
+        projection(
+            cols_expr_plan(returns_ref, &["customer", "item"], &accessor),
+            tab(returns_ref),
+        ),
+        false,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, orders_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("customer", [0_i64; 0]), int("item", [0_i32; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+#[should_panic(expected = "The inputs of an intersect must have the same column types")]
+fn we_cannot_create_an_intersect_with_different_column_types() {
+    let (accessor, orders_ref, returns_ref) =
+        accessor_with_orders_and_returns::<InnerProductProof>(());
+    intersect_exec(
+        projection(
+            cols_expr_plan(orders_ref, &["customer"], &accessor),
+            tab(orders_ref),
+        ),
+        projection(
+            cols_expr_plan(returns_ref, &["item"], &accessor),
+            tab(returns_ref),
+        ),
+        false,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Intersect column type is not supported")]
+fn we_cannot_create_an_intersect_on_a_varchar_column() {
+    let (accessor, orders_ref, _) = accessor_with_orders_and_returns::<InnerProductProof>(());
+    let input = || {
+        projection(
+            cols_expr_plan(orders_ref, &["note"], &accessor),
+            tab(orders_ref),
+        )
+    };
+    intersect_exec(input(), input(), false);
+}
@@ -0,0 +1,596 @@
+use super::{fold_columns, fold_vals, DynProofPlan};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_column_by_index, join_util::equi_join_indexes, Column, ColumnField,
+            ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use bumpalo::Bump;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> INNER JOIN <ProofPlan>
+///     ON col1 = col2
+/// ```
+///
+/// The result consists of the columns of the left input followed by the columns of the right
+/// input other than its join column, which always coincides with the join column of the left input.
+///
+/// Let `L` and `R` be the inputs and `O` the output. The prover commits to `O`, the row indexes
+/// `left_row_index` and `right_row_index` of `L` and `R` each output row comes from, the number of
+/// times each row of `L` and `R` is used, and the sorted list of unique join keys along with how
+/// often each of them occurs in `L` and `R`. All of these are committed to before any challenge is drawn.
+/// We then prove that
+/// 1. `(left_row_index, O_L)` is a lookup into `(row index of L, L)` with the claimed multiplicities
+///     and similarly for the right side. Since the output join column is shared this
+///     also proves that the join keys of both sides agree.
+/// 2. `left_row_index * 2^64 + right_row_index` is strictly increasing so no pair of rows occurs twice.
+/// 3. The multiplicity of each row of `L` is the number of occurrences of its join key in `R`.
+///     This is done by proving the multiset equality between `(join key, multiplicity)` of `L` and
+///     `(unique key, count in R)` with multiplicities the count in `L`, along with the multiset
+///     equality of the join keys of `R` and the unique keys with multiplicities the count in `R`.
+///     The unique keys are proven to be strictly increasing.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct JoinExec {
+    pub(crate) left: Box<DynProofPlan>,
+    pub(crate) right: Box<DynProofPlan>,
+    pub(crate) left_join_column_index: usize,
+    pub(crate) right_join_column_index: usize,
+    pub(crate) result_idents: Vec<Ident>,
+}
+
+impl JoinExec {
+    /// Creates a new join execution plan.
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds
+    /// or if the number of result identifiers is incorrect.
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_index: usize,
+        right_join_column_index: usize,
+        result_idents: Vec<Ident>,
+    ) -> Self {
+        let num_left_columns = left.get_column_result_fields().len();
+        let num_right_columns = right.get_column_result_fields().len();
+        assert!(
+            left_join_column_index < num_left_columns,
+            "Left join column index out of bounds"
+        );
+        assert!(
+            right_join_column_index < num_right_columns,
+            "Right join column index out of bounds"
+        );
+        assert_eq!(
+            result_idents.len(),
+            num_left_columns + num_right_columns - 1,
+            "The number of result identifiers must match the number of output columns"
+        );
+        Self {
+            left,
+            right,
+            left_join_column_index,
+            right_join_column_index,
+            result_idents,
+        }
+    }
+}
+
+impl ProofPlan for JoinExec
+where
+    JoinExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let left_eval = self
+            .left
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let right_eval = self
+            .right
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let num_left_columns = left_eval.column_evals().len();
+
+        // First round
+        let output_column_evals =
+            builder.try_consume_first_round_mle_evaluations(self.result_idents.len())?;
+        let [left_row_index_eval, right_row_index_eval, left_multiplicity_eval, right_multiplicity_eval, unique_key_eval, unique_key_left_count_eval, unique_key_right_count_eval] =
+            builder
+                .try_consume_first_round_mle_evaluations(7)?
+                .try_into()
+                .expect("We consumed exactly 7 evaluations");
+        let output_one_eval = builder.try_consume_one_evaluation()?;
+        let unique_key_one_eval = builder.try_consume_one_evaluation()?;
+        let left_rho_eval = builder.try_consume_rho_evaluation()?;
+        let right_rho_eval = builder.try_consume_rho_evaluation()?;
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        let output_left_evals = &output_column_evals[..num_left_columns];
+        let output_right_evals = right_output_evals(
+            &output_column_evals[num_left_columns..],
+            output_column_evals[self.left_join_column_index],
+            self.right_join_column_index,
+        );
+
+        // 1. Lookups of the output rows into the inputs
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[&[left_row_index_eval], output_left_evals].concat(),
+            output_one_eval,
+            &[&[left_rho_eval], left_eval.column_evals()].concat(),
+            left_eval.one_eval(),
+            left_multiplicity_eval,
+        )?;
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[&[right_row_index_eval], output_right_evals.as_slice()].concat(),
+            output_one_eval,
+            &[&[right_rho_eval], right_eval.column_evals()].concat(),
+            right_eval.one_eval(),
+            right_multiplicity_eval,
+        )?;
+
+        // 2. No pair of rows occurs twice
+        verify_monotonic::<S, true, true>(
+            builder,
+            alpha,
+            beta,
+            row_index_pair_key(left_row_index_eval, right_row_index_eval),
+            output_one_eval,
+        )?;
+
+        // 3. The multiplicities are the number of matching rows in the other input
+        let left_join_column_eval = left_eval.column_evals()[self.left_join_column_index];
+        let right_join_column_eval = right_eval.column_evals()[self.right_join_column_index];
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[left_join_column_eval, left_multiplicity_eval],
+            left_eval.one_eval(),
+            &[unique_key_eval, unique_key_right_count_eval],
+            unique_key_one_eval,
+            unique_key_left_count_eval,
+        )?;
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[right_join_column_eval],
+            right_eval.one_eval(),
+            &[unique_key_eval],
+            unique_key_one_eval,
+            unique_key_right_count_eval,
+        )?;
+        verify_monotonic::<S, true, true>(
+            builder,
+            alpha,
+            beta,
+            unique_key_eval,
+            unique_key_one_eval,
+        )?;
+
+        Ok(TableEvaluation::new(output_column_evals, output_one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let left_fields = self.left.get_column_result_fields();
+        let right_fields = self.right.get_column_result_fields();
+        left_fields
+            .into_iter()
+            .chain(
+                right_fields
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != self.right_join_column_index)
+                    .map(|(_, field)| field),
+            )
+            .zip(self.result_idents.iter())
+            .map(|(field, ident)| ColumnField::new(ident.clone(), field.data_type()))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut column_refs = self.left.get_column_references();
+        column_refs.extend(self.right.get_column_references());
+        column_refs
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        let mut table_refs = self.left.get_table_references();
+        table_refs.extend(self.right.get_table_references());
+        table_refs
+    }
+}
+
+impl ProverEvaluate for JoinExec {
+    #[tracing::instrument(name = "JoinExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.first_round_evaluate(builder, alloc, table_map);
+        let right = self.right.first_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+
+        for column in witness.output.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.produce_intermediate_mle(witness.left_row_indexes);
+        builder.produce_intermediate_mle(witness.right_row_indexes);
+        builder.produce_intermediate_mle(witness.left_multiplicities);
+        builder.produce_intermediate_mle(witness.right_multiplicities);
+        builder.produce_intermediate_mle(witness.unique_keys);
+        builder.produce_intermediate_mle(witness.unique_key_left_counts);
+        builder.produce_intermediate_mle(witness.unique_key_right_counts);
+        builder.produce_one_evaluation_length(witness.output.num_rows());
+        builder.produce_one_evaluation_length(witness.unique_keys.len());
+        builder.produce_rho_evaluation_length(left.num_rows());
+        builder.produce_rho_evaluation_length(right.num_rows());
+        first_round_evaluate_monotonic(builder, alloc, witness.row_index_pair_keys);
+        first_round_evaluate_monotonic(builder, alloc, witness.unique_keys);
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+
+    #[tracing::instrument(name = "JoinExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.final_round_evaluate(builder, alloc, table_map);
+        let right = self.right.final_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let output_columns = witness.output.columns().copied().collect::<Vec<_>>();
+        let left_columns = left.columns().copied().collect::<Vec<_>>();
+        let right_columns = right.columns().copied().collect::<Vec<_>>();
+        let num_left_columns = left_columns.len();
+        let output_right_columns = right_output_evals(
+            &output_columns[num_left_columns..],
+            output_columns[self.left_join_column_index],
+            self.right_join_column_index,
+        );
+        let left_rho: &[S] =
+            alloc.alloc_slice_fill_with(left.num_rows(), |i| S::from([i as u64, 0, 0, 0]));
+        let right_rho: &[S] =
+            alloc.alloc_slice_fill_with(right.num_rows(), |i| S::from([i as u64, 0, 0, 0]));
+
+        // 1. Lookups of the output rows into the inputs
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[
+                &[Column::BigInt(witness.left_row_indexes)],
+                &output_columns[..num_left_columns],
+            ]
+            .concat(),
+            &[&[Column::Scalar(left_rho)], left_columns.as_slice()].concat(),
+            witness.left_multiplicities,
+        );
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[
+                &[Column::BigInt(witness.right_row_indexes)],
+                output_right_columns.as_slice(),
+            ]
+            .concat(),
+            &[&[Column::Scalar(right_rho)], right_columns.as_slice()].concat(),
+            witness.right_multiplicities,
+        );
+
+        // 2. No pair of rows occurs twice
+        final_round_evaluate_monotonic::<S, true, true>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.row_index_pair_keys,
+        );
+
+        // 3. The multiplicities are the number of matching rows in the other input
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[
+                left_columns[self.left_join_column_index],
+                Column::BigInt(witness.left_multiplicities),
+            ],
+            &[
+                Column::Scalar(witness.unique_keys),
+                Column::BigInt(witness.unique_key_right_counts),
+            ],
+            witness.unique_key_left_counts,
+        );
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[right_columns[self.right_join_column_index]],
+            &[Column::Scalar(witness.unique_keys)],
+            witness.unique_key_right_counts,
+        );
+        final_round_evaluate_monotonic::<S, true, true>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.unique_keys,
+        );
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+}
+
+/// The witness committed to by the prover of a [`JoinExec`]
+struct JoinWitness<'a, S: Scalar> {
+    output: Table<'a, S>,
+    left_row_indexes: &'a [i64],
+    right_row_indexes: &'a [i64],
+    left_multiplicities: &'a [i64],
+    right_multiplicities: &'a [i64],
+    unique_keys: &'a [S],
+    unique_key_left_counts: &'a [i64],
+    unique_key_right_counts: &'a [i64],
+    row_index_pair_keys: &'a [S],
+}
+
+impl JoinExec {
+    /// Compute the join along with all the witness columns needed to prove it.
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds.
+    fn compute_witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        left: &Table<'a, S>,
+        right: &Table<'a, S>,
+    ) -> JoinWitness<'a, S> {
+        let left_keys = left
+            .column(self.left_join_column_index)
+            .expect("Left join column index out of bounds")
+            .to_scalar_with_scaling(0);
+        let right_keys = right
+            .column(self.right_join_column_index)
+            .expect("Right join column index out of bounds")
+            .to_scalar_with_scaling(0);
+        let (left_indexes, right_indexes): (Vec<usize>, Vec<usize>) =
+            equi_join_indexes(&left_keys, &right_keys)
+                .into_iter()
+                .unzip();
+        let output = Table::try_from_iter_with_options(
+            self.result_idents.iter().cloned().zip(
+                left.columns()
+                    .map(|column| filter_column_by_index(alloc, column, &left_indexes))
+                    .chain(
+                        right
+                            .columns()
+                            .enumerate()
+                            .filter(|(i, _)| *i != self.right_join_column_index)
+                            .map(|(_, column)| {
+                                filter_column_by_index(alloc, column, &right_indexes)
+                            }),
+                    ),
+            ),
+            TableOptions::new(Some(left_indexes.len())),
+        )
+        .expect("Failed to create table from iterator");
+
+        // Count the occurrences of each key on both sides
+        let mut key_counts: BTreeMap<S, (i64, i64)> = BTreeMap::new();
+        for key in &left_keys {
+            key_counts.entry(*key).or_default().0 += 1;
+        }
+        for key in &right_keys {
+            key_counts.entry(*key).or_default().1 += 1;
+        }
+        let mut unique_keys = key_counts.keys().copied().collect::<Vec<_>>();
+        unique_keys.sort_unstable_by(ScalarExt::signed_cmp);
+
+        let to_i64 = |i: &usize| i64::try_from(*i).expect("Row index does not fit in i64");
+        let left_row_indexes: &[i64] = alloc.alloc_slice_fill_iter(left_indexes.iter().map(to_i64));
+        let right_row_indexes: &[i64] =
+            alloc.alloc_slice_fill_iter(right_indexes.iter().map(to_i64));
+        JoinWitness {
+            output,
+            left_row_indexes,
+            right_row_indexes,
+            left_multiplicities: alloc
+                .alloc_slice_fill_iter(left_keys.iter().map(|key| key_counts[key].1)),
+            right_multiplicities: alloc
+                .alloc_slice_fill_iter(right_keys.iter().map(|key| key_counts[key].0)),
+            unique_key_left_counts: alloc
+                .alloc_slice_fill_iter(unique_keys.iter().map(|key| key_counts[key].0)),
+            unique_key_right_counts: alloc
+                .alloc_slice_fill_iter(unique_keys.iter().map(|key| key_counts[key].1)),
+            unique_keys: alloc.alloc_slice_copy(&unique_keys),
+            row_index_pair_keys: alloc.alloc_slice_fill_iter(
+                left_row_indexes
+                    .iter()
+                    .zip(right_row_indexes)
+                    .map(|(&l, &r)| row_index_pair_key(S::from(l), S::from(r))),
+            ),
+        }
+    }
+}
+
+/// Encode a pair of row indexes as `left * 2^64 + right` so that the encoding is
+/// injective and respects the lexicographic order of the pairs.
+fn row_index_pair_key<S: Scalar>(left: S, right: S) -> S {
+    left * S::from([0, 1, 0, 0]) + right
+}
+
+/// Insert the output join column, shared by both sides, into the right side of the output
+/// at the position of the right join column.
+fn right_output_evals<T: Clone>(
+    right_output_evals: &[T],
+    join_column_eval: T,
+    right_join_column_index: usize,
+) -> Vec<T> {
+    let mut res = right_output_evals.to_vec();
+    res.insert(right_join_column_index, join_column_eval);
+    res
+}
+
+/// Prove that every row of `lookup_columns` is a row of `table_columns` where
+/// the `i`th row of the table is used `multiplicities[i]` times.
+///
+/// This is done by proving
+/// ```text
+/// sum_j 1 / (1 + alpha * fold(lookup_j)) = sum_i multiplicities[i] / (1 + alpha * fold(table_i))
+/// ```
+pub(super) fn prove_lookup<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    lookup_columns: &[Column<'a, S>],
+    table_columns: &[Column<'a, S>],
+    multiplicities: &'a [i64],
+) {
+    let lookup_star = prove_star(builder, alloc, alpha, beta, lookup_columns);
+    let table_star = prove_star(builder, alloc, alpha, beta, table_columns);
+    // sum lookup_star - multiplicities * table_star = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(lookup_star)]),
+            (
+                -S::one(),
+                vec![Box::new(multiplicities), Box::new(table_star)],
+            ),
+        ],
+    );
+}
+
+/// Produce `star = 1 / (1 + alpha * fold(columns))` along with the constraint
+/// `star + fold * star - chi = 0`.
+///
+/// # Panics
+/// Panics if `columns` is empty.
+fn prove_star<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[Column<'a, S>],
+) -> &'a [S] {
+    let length = columns[0].len();
+    let ones = alloc.alloc_slice_fill_copy(length, true);
+    let fold = alloc.alloc_slice_fill_copy(length, Zero::zero());
+    fold_columns(fold, alpha, beta, columns);
+    let star = alloc.alloc_slice_copy(fold);
+    slice_ops::add_const::<S, S>(star, One::one());
+    slice_ops::batch_inversion(star);
+    builder.produce_intermediate_mle(star as &[_]);
+    // star + fold * star - chi = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(star as &[_])]),
+            (
+                S::one(),
+                vec![Box::new(star as &[_]), Box::new(fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(ones as &[_])]),
+        ],
+    );
+    star
+}
+
+/// Verify the lookup proven by [`prove_lookup`].
+#[allow(clippy::too_many_arguments)]
+pub(super) fn verify_lookup<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    lookup_evals: &[S],
+    lookup_one_eval: S,
+    table_evals: &[S],
+    table_one_eval: S,
+    multiplicity_eval: S,
+) -> Result<(), ProofError> {
+    let lookup_star_eval = verify_star(builder, alpha, beta, lookup_evals, lookup_one_eval)?;
+    let table_star_eval = verify_star(builder, alpha, beta, table_evals, table_one_eval)?;
+    // sum lookup_star - multiplicities * table_star = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        lookup_star_eval - multiplicity_eval * table_star_eval,
+        2,
+    )?;
+    Ok(())
+}
+
+/// Verify the star column produced by [`prove_star`].
+fn verify_star<S: Scalar>(
+    builder: &mut VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    evals: &[S],
+    one_eval: S,
+) -> Result<S, ProofError> {
+    let fold_eval = alpha * fold_vals(beta, evals);
+    let star_eval = builder.try_consume_mle_evaluation()?;
+    // star + fold * star - chi = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        star_eval + fold_eval * star_eval - one_eval,
+        2,
+    )?;
+    Ok(star_eval)
+}
@@ -0,0 +1,197 @@
+use super::test_utility::*;
+use crate::{
+    base::database::{
+        owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef, TestAccessor,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_join_exec() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = join_exec(
+        table_exec(
+            employees_ref,
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+                column_field("dept_id", ColumnType::BigInt),
+            ],
+        ),
+        table_exec(
+            departments_ref,
+            vec![
+                column_field("dept_id", ColumnType::BigInt),
+                column_field("dept_name", ColumnType::VarChar),
+            ],
+        ),
+        2,
+        0,
+        &["id", "name", "dept_id", "dept_name"],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [1_i64, 2, 2, 3, 5, 5]),
+        varchar("name", ["Ann", "Bob", "Bob", "Cat", "Eve", "Eve"]),
+        bigint("dept_id", [10_i64, 20, 20, 10, 20, 20]),
+        varchar(
+            "dept_name",
+            ["Legal", "Sales", "Support", "Legal", "Sales", "Support"],
+        ),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_join_exec_of_filtered_inputs() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = join_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["dept_id", "name"], &accessor),
+            tab(employees_ref),
+            gte(column(employees_ref, "id", &accessor), const_bigint(3)),
+        ),
+        projection(
+            cols_expr_plan(departments_ref, &["dept_name", "dept_id"], &accessor),
+            tab(departments_ref),
+        ),
+        0,
+        1,
+        &["dept_id", "name", "dept_name"],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("dept_id", [10_i64, 20, 20]),
+        varchar("name", ["Cat", "Eve", "Eve"]),
+        varchar("dept_name", ["Legal", "Sales", "Support"]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_join_exec_without_matches() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = join_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["dept_id"], &accessor),
+            tab(employees_ref),
+            equal(
+                column(employees_ref, "dept_id", &accessor),
+                const_bigint(40),
+            ),
+        ),
+        table_exec(
+            departments_ref,
+            vec![
+                column_field("dept_id", ColumnType::BigInt),
+                column_field("dept_name", ColumnType::VarChar),
+            ],
+        ),
+        0,
+        0,
+        &["dept_id", "dept_name"],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("dept_id", [0_i64; 0]), varchar("dept_name", [""; 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_join_exec_of_empty_tables() {
+    let t0: TableRef = "sxt.t0".parse().unwrap();
+    let t1: TableRef = "sxt.t1".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t0, owned_table([bigint("a", [0_i64; 0])]), 0);
+    accessor.add_table(
+        t1,
+        owned_table([int("b", [0_i32; 0]), bigint("a", [0_i64; 0])]),
+        0,
+    );
+    let ast = join_exec(
+        table_exec(t0, vec![column_field("a", ColumnType::BigInt)]),
+        table_exec(
+            t1,
+            vec![
+                column_field("b", ColumnType::Int),
+                column_field("a", ColumnType::BigInt),
+            ],
+        ),
+        0,
+        1,
+        &["a", "b"],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t0);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [0_i64; 0]), int("b", [0_i32; 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_join_exec_on_negative_keys_with_a_slice() {
+    let t0: TableRef = "sxt.t0".parse().unwrap();
+    let t1: TableRef = "sxt.t1".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t0,
+        owned_table([
+            int("a", [-3_i32, 5, -3, i32::MIN, i32::MAX]),
+            smallint("c", [1_i16, 2, 3, 4, 5]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        t1,
+        owned_table([
+            int("a", [i32::MAX, -3, i32::MIN, 7]),
+            boolean("d", [true, false, true, false]),
+        ]),
+        0,
+    );
+    let ast = slice_exec(
+        join_exec(
+            table_exec(
+                t0,
+                vec![
+                    column_field("a", ColumnType::Int),
+                    column_field("c", ColumnType::SmallInt),
+                ],
+            ),
+            table_exec(
+                t1,
+                vec![
+                    column_field("a", ColumnType::Int),
+                    column_field("d", ColumnType::Boolean),
+                ],
+            ),
+            0,
+            0,
+            &["a", "c", "d"],
+        ),
+        1,
+        Some(3),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t1);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        int("a", [-3_i32, i32::MIN, i32::MAX]),
+        smallint("c", [3_i16, 4, 5]),
+        boolean("d", [false, true, true]),
+    ]);
+    assert_eq!(res, expected);
+}
@@ -29,22 +29,74 @@ pub(crate) use fold_util::{fold_columns, fold_vals};
 #[cfg(test)]
 mod fold_util_test;
 
+mod avg_util;
+use avg_util::{avg_count_ident, avg_sum_type, count_presence, finalize_avg};
+#[cfg(test)]
+mod avg_util_test;
+
+mod order_statistic_util;
+use order_statistic_util::{
+    finalize_percentile_cont, interpolated_percentile, order_statistic_ranks, percentile_high_ident,
+};
+#[cfg(test)]
+mod order_statistic_util_test;
+
 mod group_by_exec;
 pub(crate) use group_by_exec::GroupByExec;
 
 #[cfg(all(test, feature = "blitzar"))]
 mod group_by_exec_test;
 
+mod aggregate_exec;
+pub(crate) use aggregate_exec::AggregateExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod aggregate_exec_test;
+
 mod slice_exec;
 pub(crate) use slice_exec::SliceExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod slice_exec_test;
 
+mod order_by_exec;
+pub(crate) use order_by_exec::OrderByExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod order_by_exec_test;
+
 mod union_exec;
 pub(crate) use union_exec::UnionExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod union_exec_test;
 
+mod join_exec;
+pub(crate) use join_exec::JoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod join_exec_test;
+
+mod semi_join_exec;
+pub(crate) use semi_join_exec::SemiJoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod semi_join_exec_test;
+
+mod subquery_exec;
+pub(crate) use subquery_exec::SubqueryExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod subquery_exec_test;
+
+mod distinct_exec;
+pub(crate) use distinct_exec::DistinctExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod distinct_exec_test;
+
+mod intersect_exec;
+pub(crate) use intersect_exec::IntersectExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod intersect_exec_test;
+
+mod window_exec;
+pub(crate) use window_exec::{WindowExec, WindowFunction};
+#[cfg(all(test, feature = "blitzar"))]
+mod window_exec_test;
+
 mod dyn_proof_plan;
 pub use dyn_proof_plan::DynProofPlan;
 
@@ -0,0 +1,283 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes, order_by_util::compare_indexes_by_columns_with_direction,
+            Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::permutation::Permutation,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{final_round_evaluate_sorted, first_round_evaluate_sorted, verify_sorted},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> ORDER BY <col1> [ASC|DESC], ..., <colN> [ASC|DESC]
+/// ```
+///
+/// Let `I` be the input and `O` the output. The prover commits to `O` before any challenge is drawn.
+/// We then prove that
+/// 1. `O` is a permutation of `I`, i.e. the multisets of rows of `I` and `O` agree.
+/// 2. The rows of `O` are sorted lexicographically on the order by columns in the given directions.
+///
+/// Since the ordering is proven using sign decompositions, only integer, boolean, decimal and
+/// timestamp columns can be ordered by. In particular, `VarChar` columns are not supported.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct OrderByExec {
+    pub(crate) input: Box<DynProofPlan>,
+    pub(crate) order_by: Vec<(usize, OrderByDirection)>,
+}
+
+impl OrderByExec {
+    /// Creates a new order by execution plan.
+    ///
+    /// # Panics
+    /// Panics if an order by column index is out of bounds
+    /// or if an order by column has an unsupported type.
+    pub fn new(input: Box<DynProofPlan>, order_by: Vec<(usize, OrderByDirection)>) -> Self {
+        let fields = input.get_column_result_fields();
+        assert!(
+            order_by.iter().all(|(index, _)| *index < fields.len()),
+            "Order by column index out of bounds"
+        );
+        assert!(
+            order_by
+                .iter()
+                .all(|(index, _)| Self::is_supported_column_type(fields[*index].data_type())),
+            "Order by column type is not supported"
+        );
+        Self { input, order_by }
+    }
+
+    /// Whether a column of the given type can be ordered by in an [`OrderByExec`].
+    pub(crate) fn is_supported_column_type(column_type: ColumnType) -> bool {
+        matches!(
+            column_type,
+            ColumnType::Boolean
+                | ColumnType::TinyInt
+                | ColumnType::SmallInt
+                | ColumnType::Int
+                | ColumnType::BigInt
+                | ColumnType::Int128
+                | ColumnType::Decimal75(_, _)
+                | ColumnType::TimestampTZ(_, _)
+        )
+    }
+
+    fn directions(&self) -> Vec<OrderByDirection> {
+        self.order_by
+            .iter()
+            .map(|(_, direction)| *direction)
+            .collect()
+    }
+
+    /// Sort the columns of the input, returning the sorted columns
+    /// and the sorted order by columns as scalars.
+    ///
+    /// # Panics
+    /// Panics if the permutation is out of bounds, which can not happen.
+    fn sort<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        input: &Table<'a, S>,
+    ) -> (Vec<Column<'a, S>>, Vec<&'a [S]>) {
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let order_by_pairs = self
+            .order_by
+            .iter()
+            .map(|(index, direction)| (columns[*index], *direction))
+            .collect::<Vec<_>>();
+        let permutation = Permutation::unchecked_new_from_cmp(input.num_rows(), |&a, &b| {
+            compare_indexes_by_columns_with_direction(&order_by_pairs, a, b).then(a.cmp(&b))
+        });
+        let sorted_columns = columns
+            .iter()
+            .map(|column| {
+                apply_column_to_indexes(column, alloc, permutation.as_slice())
+                    .expect("Indexes can not be out of bounds")
+            })
+            .collect::<Vec<_>>();
+        let sorted_order_by_columns = self
+            .order_by
+            .iter()
+            .map(|(index, _)| {
+                alloc.alloc_slice_copy(&sorted_columns[*index].to_scalar_with_scaling(0)) as &[_]
+            })
+            .collect();
+        (sorted_columns, sorted_order_by_columns)
+    }
+}
+
+impl ProofPlan for OrderByExec
+where
+    OrderByExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let column_evals = input_eval.column_evals();
+        let one_eval = input_eval.one_eval();
+
+        // First round
+        let output_column_evals =
+            builder.try_consume_first_round_mle_evaluations(column_evals.len())?;
+        let order_by_evals = self
+            .order_by
+            .iter()
+            .map(|(index, _)| output_column_evals[*index])
+            .collect::<Vec<_>>();
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        // 1. The output is a permutation of the input
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            one_eval,
+            one_eval,
+            column_evals,
+            one_eval,
+            &output_column_evals,
+        )?;
+        // 2. The output is sorted
+        verify_sorted(
+            builder,
+            alpha,
+            beta,
+            &order_by_evals,
+            &self.directions(),
+            one_eval,
+        )?;
+
+        Ok(TableEvaluation::new(output_column_evals, one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for OrderByExec {
+    #[tracing::instrument(name = "OrderByExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let (sorted_columns, sorted_order_by_columns) = self.sort(alloc, &input);
+
+        for column in &sorted_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        first_round_evaluate_sorted(builder, alloc, &sorted_order_by_columns, num_rows);
+        builder.request_post_result_challenges(2);
+
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(sorted_columns),
+            TableOptions::new(Some(num_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "OrderByExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let (sorted_columns, sorted_order_by_columns) = self.sort(alloc, &input);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 1. The output is a permutation of the input
+        let selection = alloc.alloc_slice_fill_copy(num_rows, true);
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            selection,
+            &sorted_columns,
+            num_rows,
+            num_rows,
+        );
+        // 2. The output is sorted
+        final_round_evaluate_sorted(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &sorted_order_by_columns,
+            &self.directions(),
+            num_rows,
+        );
+
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(sorted_columns),
+            TableOptions::new(Some(num_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
@@ -0,0 +1,154 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, table_utility::*, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        map::indexmap,
+    },
+    sql::{
+        proof::{exercise_verification, FirstRoundBuilder, ProverEvaluate, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+
+#[test]
+fn we_can_prove_an_order_by_exec_on_a_single_column() {
+    let data = owned_table([
+        bigint("a", [3_i64, -1, 2, 5, 2]),
+        varchar("b", ["3", "-1", "2a", "5", "2b"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = order_by_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![(0, OrderByDirection::Asc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-1_i64, 2, 2, 3, 5]),
+        varchar("b", ["-1", "2a", "2b", "3", "5"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_order_by_exec_on_multiple_columns_and_directions() {
+    let data = owned_table([
+        int("a", [1, 2, 1, 2, 1, 2]),
+        boolean("b", [true, false, false, true, true, false]),
+        bigint("c", [10_i64, 20, 30, 40, 50, 60]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = order_by_exec(
+        projection(cols_expr_plan(t, &["a", "b", "c"], &accessor), tab(t)),
+        vec![
+            (0, OrderByDirection::Desc),
+            (1, OrderByDirection::Asc),
+            (2, OrderByDirection::Desc),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("a", [2, 2, 2, 1, 1, 1]),
+        boolean("b", [false, false, true, false, true, true]),
+        bigint("c", [60_i64, 20, 40, 30, 50, 10]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_top_k_query_using_a_slice_of_an_order_by_exec() {
+    let data = owned_table([
+        bigint("a", [7_i64, 3, 9, 1, 8, 4]),
+        varchar("b", ["7", "3", "9", "1", "8", "4"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let where_clause = not(equal(column(t, "a", &accessor), const_bigint(8)));
+    let ast = slice_exec(
+        order_by_exec(
+            filter(
+                cols_expr_plan(t, &["a", "b"], &accessor),
+                tab(t),
+                where_clause,
+            ),
+            vec![(0, OrderByDirection::Desc)],
+        ),
+        0,
+        Some(3),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [9_i64, 7, 4]), varchar("b", ["9", "7", "4"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_order_by_exec_with_no_rows() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3]), int128("b", [1_i128, 2, 3])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let where_clause = equal(column(t, "a", &accessor), const_bigint(4));
+    let ast = order_by_exec(
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            where_clause,
+        ),
+        vec![(1, OrderByDirection::Asc), (0, OrderByDirection::Desc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), int128("b", [0_i128; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_correct_result_from_an_order_by_exec_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [2_i64, 1, 2, 1], &alloc),
+        borrowed_bigint("b", [1_i64, 2, 3, 4], &alloc),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let table_map = indexmap! {
+        t => data.clone()
+    };
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = order_by_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![(0, OrderByDirection::Asc), (1, OrderByDirection::Desc)],
+    );
+    let first_round_builder = &mut FirstRoundBuilder::new();
+    let res = ast.first_round_evaluate(first_round_builder, &alloc, &table_map);
+    let expected = table([
+        borrowed_bigint("a", [1_i64, 1, 2, 2], &alloc),
+        borrowed_bigint("b", [4_i64, 2, 3, 1], &alloc),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[should_panic(expected = "Order by column type is not supported")]
+fn we_cannot_create_an_order_by_exec_on_a_varchar_column() {
+    let data = owned_table([bigint("a", [1_i64, 2]), varchar("b", ["1", "2"])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    order_by_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![(1, OrderByDirection::Asc)],
+    );
+}
@@ -0,0 +1,137 @@
+//! `MEDIAN`, `PERCENTILE_CONT` and `PERCENTILE_DISC` aggregations are proven as order statistics, i.e.
+//! as the values of a given rank among the aggregated values. `PERCENTILE_CONT`, and so `MEDIAN`, is
+//! proven as the two order statistics it interpolates between along with a count. The interpolation
+//! itself is only computed from the verified order statistics when the result is finalized after
+//! verification.
+use crate::{
+    base::{
+        database::{ColumnType, OwnedColumn},
+        scalar::Scalar,
+    },
+    sql::{proof::QueryError, proof_gadgets::Rank},
+};
+use alloc::{format, vec, vec::Vec};
+use num_bigint::BigInt;
+use num_traits::Signed;
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, Percentile};
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the alias of a continuous percentile to get the name of the larger order
+/// statistic it interpolates to.
+///
+/// Since `$` can not appear in an identifier of a query, the name can not collide with a result column.
+const PERCENTILE_HIGH_SUFFIX: &str = "$high";
+
+/// The name of the larger order statistic the continuous percentile named `alias` interpolates to.
+pub(super) fn percentile_high_ident(alias: &Ident) -> Ident {
+    Ident::new(format!("{}{PERCENTILE_HIGH_SUFFIX}", alias.value))
+}
+
+/// The percentile that `op` interpolates, if `op` is `MEDIAN` or `PERCENTILE_CONT`.
+pub(super) fn interpolated_percentile(op: AggregationOperator) -> Option<Percentile> {
+    match op {
+        AggregationOperator::Median => Some(Percentile::MEDIAN),
+        AggregationOperator::PercentileCont(percentile) => Some(percentile),
+        _ => None,
+    }
+}
+
+/// The ranks of the order statistics proven for `op`, which are empty if `op` is not a percentile.
+///
+/// Among `n` values, counting ranks from `0`, `PERCENTILE_DISC(p)` is the value of rank
+/// `ceil(p * n) - 1`, or of rank `0` if `p = 0`. `PERCENTILE_CONT(p)` interpolates between the values
+/// of ranks `floor(p * (n - 1))` and `ceil(p * (n - 1))`.
+///
+/// # Panics
+/// Panics if the scale of a percentile is not supported, which it is.
+pub(super) fn order_statistic_ranks(op: AggregationOperator) -> Vec<Rank> {
+    let rank = |percentile: Percentile, offset: fn(i64, i64) -> i64| {
+        let numerator = percentile.numerator();
+        let denominator = 10_u64.pow(u32::from(percentile.scale()));
+        let signed = |value: u64| {
+            i64::try_from(value)
+                .expect("percentiles have at most 18 digits after the decimal point")
+        };
+        Rank::new(
+            numerator,
+            denominator,
+            offset(signed(numerator), signed(denominator)),
+        )
+    };
+    if let AggregationOperator::PercentileDisc(percentile) = op {
+        // ceil(a * n / b) - 1 = floor((a * n - 1) / b)
+        return vec![rank(percentile, |a, _| -i64::from(a > 0))];
+    }
+    // floor(a * (n - 1) / b) and ceil(a * (n - 1) / b) = floor((a * (n - 1) + b - 1) / b)
+    interpolated_percentile(op)
+        .map(|percentile| {
+            vec![
+                rank(percentile, |a, _| -a),
+                rank(percentile, |a, b| b - 1 - a),
+            ]
+        })
+        .unwrap_or_default()
+}
+
+/// Interpolate between the proven order statistics `lows` and `highs` to the `percentile` of groups of
+/// `counts` values, giving a column of type `cont_type`. Percentiles over no values are NULL, so they
+/// are `0` here and their presence is given by [`super::count_presence`], just like averages.
+///
+/// For `percentile = a / b` and `n` values, the result is `low + t * (high - low) / b` where
+/// `t = a * (n - 1) mod b`. Since `b` is a power of ten, this is exact at the scale of `cont_type`.
+///
+/// Returns [`QueryError::Overflow`] if a percentile does not fit into `cont_type`.
+///
+/// # Panics
+/// Panics if `lows` and `highs` are not numeric columns, `counts` is not a `BIGINT` column or
+/// `cont_type` is not a decimal type.
+pub(super) fn finalize_percentile_cont<S: Scalar>(
+    lows: &OwnedColumn<S>,
+    highs: &OwnedColumn<S>,
+    counts: &OwnedColumn<S>,
+    percentile: Percentile,
+    cont_type: ColumnType,
+) -> Result<OwnedColumn<S>, QueryError> {
+    let OwnedColumn::BigInt(counts) = counts else {
+        panic!("continuous percentiles are proven along with BIGINT counts");
+    };
+    let ColumnType::Decimal75(precision, cont_scale) = cont_type else {
+        panic!("continuous percentiles are decimals");
+    };
+    let numerator = BigInt::from(percentile.numerator());
+    let denominator = BigInt::from(10).pow(u32::from(percentile.scale()));
+    let bound = BigInt::from(10).pow(u32::from(precision.value()));
+    let percentiles = numeric_values(lows)
+        .into_iter()
+        .zip(numeric_values(highs))
+        .zip(counts)
+        .map(|((low, high), &count)| {
+            if count == 0 {
+                return Ok(S::ZERO);
+            }
+            let fraction = (&numerator * BigInt::from(count - 1)) % &denominator;
+            let value: BigInt = low.clone() * &denominator + fraction * (high - low);
+            if value.abs() >= bound {
+                return Err(QueryError::Overflow);
+            }
+            S::try_from(value).map_err(|_| QueryError::Overflow)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(OwnedColumn::Decimal75(precision, cont_scale, percentiles))
+}
+
+/// The values of a numeric column as integers at the scale of the column
+///
+/// # Panics
+/// Panics if the column is not numeric.
+fn numeric_values<S: Scalar>(column: &OwnedColumn<S>) -> Vec<BigInt> {
+    match column {
+        OwnedColumn::TinyInt(values) => values.iter().map(|&v| BigInt::from(v)).collect(),
+        OwnedColumn::SmallInt(values) => values.iter().map(|&v| BigInt::from(v)).collect(),
+        OwnedColumn::Int(values) => values.iter().map(|&v| BigInt::from(v)).collect(),
+        OwnedColumn::BigInt(values) => values.iter().map(|&v| BigInt::from(v)).collect(),
+        OwnedColumn::Int128(values) => values.iter().map(|&v| BigInt::from(v)).collect(),
+        OwnedColumn::Decimal75(_, _, values) => values.iter().map(|&v| v.into()).collect(),
+        _ => panic!("continuous percentiles are only proven over numeric columns"),
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    finalize_percentile_cont, interpolated_percentile, order_statistic_ranks, percentile_high_ident,
+};
+use crate::{
+    base::{database::OwnedColumn, scalar::test_scalar::TestScalar},
+    sql::{
+        proof::QueryError,
+        proof_exprs::test_utility::{decimal_type, decimals},
+    },
+};
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, Percentile};
+use sqlparser::ast::Ident;
+
+fn ranks_of(op: AggregationOperator, count: i64) -> Vec<usize> {
+    order_statistic_ranks(op)
+        .into_iter()
+        .map(|rank| rank.of(count))
+        .collect()
+}
+
+#[test]
+fn we_can_name_the_high_order_statistic_of_a_percentile() {
+    assert_eq!(
+        percentile_high_ident(&Ident::new("median_a")),
+        Ident::new("median_a$high")
+    );
+}
+
+#[test]
+fn we_can_get_the_interpolated_percentile_of_an_aggregation() {
+    let quartile = Percentile::try_new(25, 2).unwrap();
+    assert_eq!(
+        interpolated_percentile(AggregationOperator::Median),
+        Some(Percentile::MEDIAN)
+    );
+    assert_eq!(
+        interpolated_percentile(AggregationOperator::PercentileCont(quartile)),
+        Some(quartile)
+    );
+    assert_eq!(
+        interpolated_percentile(AggregationOperator::PercentileDisc(quartile)),
+        None
+    );
+    assert_eq!(interpolated_percentile(AggregationOperator::Avg), None);
+}
+
+#[test]
+fn we_can_get_the_ranks_of_discrete_percentiles() {
+    let disc = |numerator, scale| {
+        AggregationOperator::PercentileDisc(Percentile::try_new(numerator, scale).unwrap())
+    };
+    assert_eq!(ranks_of(disc(25, 2), 4), vec![0]);
+    assert_eq!(ranks_of(disc(25, 2), 5), vec![1]);
+    assert_eq!(ranks_of(disc(0, 0), 1), vec![0]);
+    assert_eq!(ranks_of(disc(0, 0), 7), vec![0]);
+    assert_eq!(ranks_of(disc(1, 0), 1), vec![0]);
+    assert_eq!(ranks_of(disc(1, 0), 7), vec![6]);
+    assert_eq!(ranks_of(disc(5, 1), 7), vec![3]);
+}
+
+#[test]
+fn we_can_get_the_ranks_of_continuous_percentiles() {
+    let cont = |numerator, scale| {
+        AggregationOperator::PercentileCont(Percentile::try_new(numerator, scale).unwrap())
+    };
+    assert_eq!(ranks_of(AggregationOperator::Median, 1), vec![0, 0]);
+    assert_eq!(ranks_of(AggregationOperator::Median, 4), vec![1, 2]);
+    assert_eq!(ranks_of(AggregationOperator::Median, 5), vec![2, 2]);
+    assert_eq!(ranks_of(cont(25, 2), 4), vec![0, 1]);
+    assert_eq!(ranks_of(cont(25, 2), 5), vec![1, 1]);
+    assert_eq!(ranks_of(cont(0, 0), 7), vec![0, 0]);
+    assert_eq!(ranks_of(cont(1, 0), 7), vec![6, 6]);
+}
+
+#[test]
+fn we_have_no_ranks_for_other_aggregations() {
+    assert!(order_statistic_ranks(AggregationOperator::Sum).is_empty());
+    assert!(order_statistic_ranks(AggregationOperator::Max).is_empty());
+}
+
+#[test]
+fn we_can_finalize_continuous_percentiles() {
+    let lows = OwnedColumn::<TestScalar>::Int(vec![2, 3, -4, 0]);
+    let highs = OwnedColumn::<TestScalar>::Int(vec![3, 3, -1, 0]);
+    let counts = OwnedColumn::BigInt(vec![4, 5, 4, 0]);
+    let medians = finalize_percentile_cont(
+        &lows,
+        &highs,
+        &counts,
+        Percentile::MEDIAN,
+        decimal_type(11, 1),
+    )
+    .unwrap();
+    assert_eq!(medians, decimals(11, 1, &[25, 30, -25, 0]));
+    let quartiles = finalize_percentile_cont(
+        &lows,
+        &highs,
+        &counts,
+        Percentile::try_new(25, 2).unwrap(),
+        decimal_type(12, 2),
+    )
+    .unwrap();
+    assert_eq!(quartiles, decimals(12, 2, &[275, 300, -175, 0]));
+}
+
+#[test]
+fn we_can_finalize_continuous_percentiles_of_decimals() {
+    let lows = decimals::<TestScalar>(5, 2, &[-150, 100]);
+    let highs = decimals(5, 2, &[250, 100]);
+    let counts = OwnedColumn::BigInt(vec![2, 3]);
+    let medians = finalize_percentile_cont(
+        &lows,
+        &highs,
+        &counts,
+        Percentile::MEDIAN,
+        decimal_type(6, 3),
+    )
+    .unwrap();
+    assert_eq!(medians, decimals(6, 3, &[500, 1000]));
+}
+
+#[test]
+fn we_cannot_finalize_continuous_percentiles_that_overflow() {
+    let lows = OwnedColumn::<TestScalar>::BigInt(vec![99]);
+    let highs = OwnedColumn::<TestScalar>::BigInt(vec![100]);
+    let counts = OwnedColumn::BigInt(vec![2]);
+    assert!(matches!(
+        finalize_percentile_cont(
+            &lows,
+            &highs,
+            &counts,
+            Percentile::MEDIAN,
+            decimal_type(2, 1)
+        ),
+        Err(QueryError::Overflow)
+    ));
+}
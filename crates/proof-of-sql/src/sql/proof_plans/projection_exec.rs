@@ -12,6 +12,7 @@ use crate::{
             FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
         },
         proof_exprs::{AliasedDynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{final_round_evaluate_division_by_zero, verify_division_by_zero},
     },
     utils::log,
 };
@@ -25,8 +26,8 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ProjectionExec {
-    pub(super) aliased_results: Vec<AliasedDynProofExpr>,
-    pub(super) table: TableExpr,
+    pub(crate) aliased_results: Vec<AliasedDynProofExpr>,
+    pub(crate) table: TableExpr,
 }
 
 impl ProjectionExec {
@@ -60,6 +61,13 @@ impl ProofPlan for ProjectionExec {
                     .verifier_evaluate(builder, accessor, one_eval)
             })
             .collect::<Result<Vec<_>, _>>()?;
+        // divisions by zero
+        let division_by_zero_evals = builder
+            .take_division_by_zero_indicator_evaluations()
+            .into_iter()
+            .map(|eval| (eval, None))
+            .collect::<Vec<_>>();
+        verify_division_by_zero(builder, &division_by_zero_evals)?;
         let column_evals = builder.try_consume_mle_evaluations(self.aliased_results.len())?;
         Ok(TableEvaluation::new(column_evals, one_eval))
     }
@@ -94,7 +102,7 @@ impl ProverEvaluate for ProjectionExec {
     )]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        _builder: &mut FirstRoundBuilder,
+        _builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
@@ -147,6 +155,13 @@ impl ProverEvaluate for ProjectionExec {
             TableOptions::new(Some(table.num_rows())),
         )
         .expect("Failed to create table from iterator");
+        // divisions by zero
+        let division_by_zero_indicators = builder
+            .take_division_by_zero_indicators()
+            .into_iter()
+            .map(|indicator| (indicator, None))
+            .collect::<Vec<_>>();
+        final_round_evaluate_division_by_zero(builder, alloc, &division_by_zero_indicators);
         // 2. Produce MLEs
         for column in res.columns().copied() {
             builder.produce_intermediate_mle(column);
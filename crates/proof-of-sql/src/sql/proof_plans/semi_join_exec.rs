@@ -0,0 +1,422 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter},
+    join_exec::{prove_lookup, verify_lookup},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_columns, Column, ColumnField, ColumnRef, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> WHERE col1 [NOT] IN (<ProofPlan>)
+/// ```
+///
+/// The result consists of the rows of the left input whose join key does (or, if `negated` is set,
+/// does not) occur in the join column of the right input. The columns are those of the left input.
+///
+/// Let `L` and `R` be the inputs and `O` the output. The prover commits to `O`, the selection `s`
+/// of the rows of `L` whose key occurs in `R`, and the sorted list of unique join keys of `L` and `R`
+/// along with how often each of them occurs in `L` and `R`, whether it occurs in `R` at all and the
+/// inverse of its count in `R`. All of these are committed to before any challenge is drawn.
+/// We then prove that
+/// 1. `O` is the filter of `L` by `s` (or `1 - s` if `negated` is set).
+/// 2. `(join key, s)` of `L` is a lookup into `(unique key, occurs in R)` so that `s` is the same
+///     for all rows with the same key. The unique keys are proven to be strictly increasing.
+/// 3. The count in `R` of each unique key is the number of its occurrences in `R` by proving the
+///     multiset equality of the join keys of `R` and the unique keys with multiplicities the count in `R`.
+/// 4. A unique key occurs in `R` if and only if its count in `R` is nonzero, i.e.
+///     `occurs - count * inverse = 0` and `count - count * occurs = 0`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SemiJoinExec {
+    pub(crate) left: Box<DynProofPlan>,
+    pub(crate) right: Box<DynProofPlan>,
+    pub(crate) left_join_column_index: usize,
+    pub(crate) right_join_column_index: usize,
+    pub(crate) negated: bool,
+}
+
+impl SemiJoinExec {
+    /// Creates a new semi join execution plan, which is an anti join if `negated` is set.
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds.
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_index: usize,
+        right_join_column_index: usize,
+        negated: bool,
+    ) -> Self {
+        assert!(
+            left_join_column_index < left.get_column_result_fields().len(),
+            "Left join column index out of bounds"
+        );
+        assert!(
+            right_join_column_index < right.get_column_result_fields().len(),
+            "Right join column index out of bounds"
+        );
+        Self {
+            left,
+            right,
+            left_join_column_index,
+            right_join_column_index,
+            negated,
+        }
+    }
+}
+
+impl ProofPlan for SemiJoinExec
+where
+    SemiJoinExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let left_eval = self
+            .left
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let right_eval = self
+            .right
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+
+        // First round
+        let output_column_evals =
+            builder.try_consume_first_round_mle_evaluations(left_eval.column_evals().len())?;
+        let [selection_eval, unique_key_eval, unique_key_left_count_eval, unique_key_right_count_eval, unique_key_occurs_eval, unique_key_right_count_inverse_eval] =
+            builder
+                .try_consume_first_round_mle_evaluations(6)?
+                .try_into()
+                .expect("We consumed exactly 6 evaluations");
+        let output_one_eval = builder.try_consume_one_evaluation()?;
+        let unique_key_one_eval = builder.try_consume_one_evaluation()?;
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        // 1. The output is the filter of the left input
+        let filter_selection_eval = if self.negated {
+            left_eval.one_eval() - selection_eval
+        } else {
+            selection_eval
+        };
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            left_eval.one_eval(),
+            output_one_eval,
+            left_eval.column_evals(),
+            filter_selection_eval,
+            &output_column_evals,
+        )?;
+
+        // 2. The selection is whether the key occurs in the right input
+        let left_join_column_eval = left_eval.column_evals()[self.left_join_column_index];
+        let right_join_column_eval = right_eval.column_evals()[self.right_join_column_index];
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[left_join_column_eval, selection_eval],
+            left_eval.one_eval(),
+            &[unique_key_eval, unique_key_occurs_eval],
+            unique_key_one_eval,
+            unique_key_left_count_eval,
+        )?;
+        verify_monotonic::<S, true, true>(
+            builder,
+            alpha,
+            beta,
+            unique_key_eval,
+            unique_key_one_eval,
+        )?;
+
+        // 3. The counts in the right input
+        verify_lookup(
+            builder,
+            alpha,
+            beta,
+            &[right_join_column_eval],
+            right_eval.one_eval(),
+            &[unique_key_eval],
+            unique_key_one_eval,
+            unique_key_right_count_eval,
+        )?;
+
+        // 4. A key occurs in the right input if and only if its count is nonzero
+        // occurs - count * inverse = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            unique_key_occurs_eval
+                - unique_key_right_count_eval * unique_key_right_count_inverse_eval,
+            2,
+        )?;
+        // count - count * occurs = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            unique_key_right_count_eval - unique_key_right_count_eval * unique_key_occurs_eval,
+            2,
+        )?;
+
+        Ok(TableEvaluation::new(output_column_evals, output_one_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.left.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut column_refs = self.left.get_column_references();
+        column_refs.extend(self.right.get_column_references());
+        column_refs
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        let mut table_refs = self.left.get_table_references();
+        table_refs.extend(self.right.get_table_references());
+        table_refs
+    }
+}
+
+impl ProverEvaluate for SemiJoinExec {
+    #[tracing::instrument(name = "SemiJoinExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.first_round_evaluate(builder, alloc, table_map);
+        let right = self.right.first_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+
+        for column in witness.output.columns() {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.produce_intermediate_mle(witness.selection);
+        builder.produce_intermediate_mle(witness.unique_keys);
+        builder.produce_intermediate_mle(witness.unique_key_left_counts);
+        builder.produce_intermediate_mle(witness.unique_key_right_counts);
+        builder.produce_intermediate_mle(witness.unique_key_occurs);
+        builder.produce_intermediate_mle(witness.unique_key_right_count_inverses);
+        builder.produce_one_evaluation_length(witness.output.num_rows());
+        builder.produce_one_evaluation_length(witness.unique_keys.len());
+        first_round_evaluate_monotonic(builder, alloc, witness.unique_keys);
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+
+    #[tracing::instrument(name = "SemiJoinExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let left = self.left.final_round_evaluate(builder, alloc, table_map);
+        let right = self.right.final_round_evaluate(builder, alloc, table_map);
+        let witness = self.compute_witness(alloc, &left, &right);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let left_columns = left.columns().copied().collect::<Vec<_>>();
+        let right_columns = right.columns().copied().collect::<Vec<_>>();
+        let output_columns = witness.output.columns().copied().collect::<Vec<_>>();
+
+        // 1. The output is the filter of the left input
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &left_columns,
+            witness.filter_selection,
+            &output_columns,
+            left.num_rows(),
+            witness.output.num_rows(),
+        );
+
+        // 2. The selection is whether the key occurs in the right input
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[
+                left_columns[self.left_join_column_index],
+                Column::Boolean(witness.selection),
+            ],
+            &[
+                Column::Scalar(witness.unique_keys),
+                Column::Boolean(witness.unique_key_occurs),
+            ],
+            witness.unique_key_left_counts,
+        );
+        final_round_evaluate_monotonic::<S, true, true>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.unique_keys,
+        );
+
+        // 3. The counts in the right input
+        prove_lookup(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[right_columns[self.right_join_column_index]],
+            &[Column::Scalar(witness.unique_keys)],
+            witness.unique_key_right_counts,
+        );
+
+        // 4. A key occurs in the right input if and only if its count is nonzero
+        // occurs - count * inverse = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(witness.unique_key_occurs)]),
+                (
+                    -S::one(),
+                    vec![
+                        Box::new(witness.unique_key_right_counts),
+                        Box::new(witness.unique_key_right_count_inverses),
+                    ],
+                ),
+            ],
+        );
+        // count - count * occurs = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(witness.unique_key_right_counts)]),
+                (
+                    -S::one(),
+                    vec![
+                        Box::new(witness.unique_key_right_counts),
+                        Box::new(witness.unique_key_occurs),
+                    ],
+                ),
+            ],
+        );
+
+        log::log_memory_usage("End");
+
+        witness.output
+    }
+}
+
+/// The witness committed to by the prover of a [`SemiJoinExec`]
+struct SemiJoinWitness<'a, S: Scalar> {
+    output: Table<'a, S>,
+    selection: &'a [bool],
+    filter_selection: &'a [bool],
+    unique_keys: &'a [S],
+    unique_key_left_counts: &'a [i64],
+    unique_key_right_counts: &'a [i64],
+    unique_key_occurs: &'a [bool],
+    unique_key_right_count_inverses: &'a [S],
+}
+
+impl SemiJoinExec {
+    /// Compute the semi join along with all the witness columns needed to prove it.
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds.
+    fn compute_witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        left: &Table<'a, S>,
+        right: &Table<'a, S>,
+    ) -> SemiJoinWitness<'a, S> {
+        let left_keys = left
+            .column(self.left_join_column_index)
+            .expect("Left join column index out of bounds")
+            .to_scalar_with_scaling(0);
+        let right_keys = right
+            .column(self.right_join_column_index)
+            .expect("Right join column index out of bounds")
+            .to_scalar_with_scaling(0);
+
+        // Count the occurrences of each key on both sides
+        let mut key_counts: BTreeMap<S, (i64, i64)> = BTreeMap::new();
+        for key in &left_keys {
+            key_counts.entry(*key).or_default().0 += 1;
+        }
+        for key in &right_keys {
+            key_counts.entry(*key).or_default().1 += 1;
+        }
+        let mut unique_keys = key_counts.keys().copied().collect::<Vec<_>>();
+        unique_keys.sort_unstable_by(ScalarExt::signed_cmp);
+
+        let selection: &[bool] =
+            alloc.alloc_slice_fill_iter(left_keys.iter().map(|key| key_counts[key].1 > 0));
+        let filter_selection: &[bool] =
+            alloc.alloc_slice_fill_iter(selection.iter().map(|&s| s != self.negated));
+        let left_columns = left.columns().copied().collect::<Vec<_>>();
+        let (output_columns, output_length) =
+            filter_columns(alloc, &left_columns, filter_selection);
+        let output = Table::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(output_columns),
+            TableOptions::new(Some(output_length)),
+        )
+        .expect("Failed to create table from iterator");
+
+        let unique_key_right_counts: &[i64] =
+            alloc.alloc_slice_fill_iter(unique_keys.iter().map(|key| key_counts[key].1));
+        let unique_key_right_count_inverses: &mut [S] = alloc
+            .alloc_slice_fill_iter(unique_key_right_counts.iter().map(|&count| S::from(count)));
+        slice_ops::batch_inversion(unique_key_right_count_inverses);
+        SemiJoinWitness {
+            output,
+            selection,
+            filter_selection,
+            unique_key_left_counts: alloc
+                .alloc_slice_fill_iter(unique_keys.iter().map(|key| key_counts[key].0)),
+            unique_key_right_counts,
+            unique_key_occurs: alloc
+                .alloc_slice_fill_iter(unique_key_right_counts.iter().map(|&count| count > 0)),
+            unique_key_right_count_inverses,
+            unique_keys: alloc.alloc_slice_copy(&unique_keys),
+        }
+    }
+}
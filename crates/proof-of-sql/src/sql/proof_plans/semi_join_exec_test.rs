@@ -0,0 +1,148 @@
+use super::test_utility::*;
+use crate::{
+    base::database::{
+        owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef, TestAccessor,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+fn employees_exec(employees_ref: TableRef) -> super::DynProofPlan {
+    table_exec(
+        employees_ref,
+        vec![
+            column_field("id", ColumnType::BigInt),
+            column_field("name", ColumnType::VarChar),
+            column_field("dept_id", ColumnType::BigInt),
+        ],
+    )
+}
+
+/// `select * from sxt.employees where dept_id in (select dept_id from sxt.departments)`
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_semi_join_exec() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = semi_join_exec(
+        employees_exec(employees_ref),
+        projection(
+            cols_expr_plan(departments_ref, &["dept_id"], &accessor),
+            tab(departments_ref),
+        ),
+        2,
+        0,
+        false,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [1_i64, 2, 3, 5]),
+        varchar("name", ["Ann", "Bob", "Cat", "Eve"]),
+        bigint("dept_id", [10_i64, 20, 10, 20]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select * from sxt.employees where dept_id not in (select dept_id from sxt.departments)`
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_an_anti_join_exec() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = semi_join_exec(
+        employees_exec(employees_ref),
+        projection(
+            cols_expr_plan(departments_ref, &["dept_id"], &accessor),
+            tab(departments_ref),
+        ),
+        2,
+        0,
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [4_i64, 6]),
+        varchar("name", ["Dan", "Fay"]),
+        bigint("dept_id", [40_i64, -10]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select * from sxt.employees where dept_id in (select dept_id from sxt.departments where dept_id >= 100)`
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_semi_join_exec_with_an_empty_right_input() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let ast = semi_join_exec(
+        employees_exec(employees_ref),
+        filter(
+            cols_expr_plan(departments_ref, &["dept_id"], &accessor),
+            tab(departments_ref),
+            gte(
+                column(departments_ref, "dept_id", &accessor),
+                const_bigint(100),
+            ),
+        ),
+        2,
+        0,
+        false,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [0_i64; 0]),
+        varchar("name", [""; 0]),
+        bigint("dept_id", [0_i64; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select id from (select * from sxt.employees where dept_id in (select dept_id from sxt.departments)) as s where id >= 2`
+#[test]
+fn we_can_prove_a_filter_over_a_semi_join_exec() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.s".parse().unwrap();
+    let semi_join = semi_join_exec(
+        employees_exec(employees_ref),
+        table_exec(
+            departments_ref,
+            vec![
+                column_field("dept_id", ColumnType::BigInt),
+                column_field("dept_name", ColumnType::VarChar),
+            ],
+        ),
+        2,
+        0,
+        false,
+    );
+    let mut alias_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    alias_accessor.add_table(
+        alias,
+        owned_table([bigint("id", [0_i64; 0]), bigint("dept_id", [0_i64; 0])]),
+        0,
+    );
+    let ast = subquery_exec(
+        semi_join,
+        alias,
+        filter(
+            cols_expr_plan(alias, &["id"], &alias_accessor),
+            tab(alias),
+            gte(column(alias, "id", &alias_accessor), const_bigint(2)),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("id", [2_i64, 3, 5])]);
+    assert_eq!(res, expected);
+}
@@ -0,0 +1,164 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableRef},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, QueryError,
+        VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> FROM (<ProofPlan>) AS <alias>
+/// ```
+/// The result of `input` is exposed to `plan` as a table named `alias`.
+/// Since the result of `input` is proven, `plan` can treat it exactly like a committed table.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SubqueryExec {
+    pub(crate) input: Box<DynProofPlan>,
+    pub(crate) alias: TableRef,
+    pub(crate) plan: Box<DynProofPlan>,
+}
+
+impl SubqueryExec {
+    /// Creates a new subquery execution plan.
+    pub fn new(input: Box<DynProofPlan>, alias: TableRef, plan: Box<DynProofPlan>) -> Self {
+        Self { input, alias, plan }
+    }
+
+    /// The plan which uses the derived table
+    pub fn plan(&self) -> &DynProofPlan {
+        &self.plan
+    }
+
+    /// The column references of `alias` that can be used by `plan`
+    fn alias_column_references(&self) -> impl Iterator<Item = ColumnRef> + '_ {
+        self.input
+            .get_column_result_fields()
+            .into_iter()
+            .map(|field| ColumnRef::new(self.alias, field.name(), field.data_type()))
+    }
+}
+
+impl ProofPlan for SubqueryExec
+where
+    SubqueryExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let plan_accessor = accessor
+            .clone()
+            .into_iter()
+            .chain(
+                self.alias_column_references()
+                    .zip(input_eval.column_evals().iter().copied()),
+            )
+            .collect::<IndexMap<_, _>>();
+        let mut plan_one_eval_map = one_eval_map.clone();
+        plan_one_eval_map.insert(self.alias, input_eval.one_eval());
+        self.plan
+            .verifier_evaluate(builder, &plan_accessor, result, &plan_one_eval_map)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_intermediate_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_intermediate_result_fields()
+    }
+
+    fn finalize_result<S: Scalar>(
+        &self,
+        result: OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, QueryError> {
+        self.plan.finalize_result(result)
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input
+            .get_column_references()
+            .into_iter()
+            .chain(
+                self.plan
+                    .get_column_references()
+                    .into_iter()
+                    .filter(|column_ref| column_ref.table_ref() != self.alias),
+            )
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input
+            .get_table_references()
+            .into_iter()
+            .chain(
+                self.plan
+                    .get_table_references()
+                    .into_iter()
+                    .filter(|table_ref| *table_ref != self.alias),
+            )
+            .collect()
+    }
+}
+
+impl ProverEvaluate for SubqueryExec {
+    #[tracing::instrument(name = "SubqueryExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let mut plan_table_map = table_map.clone();
+        plan_table_map.insert(self.alias, input);
+        let res = self
+            .plan
+            .first_round_evaluate(builder, alloc, &plan_table_map);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "SubqueryExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let mut plan_table_map = table_map.clone();
+        plan_table_map.insert(self.alias, input);
+        let res = self
+            .plan
+            .final_round_evaluate(builder, alloc, &plan_table_map);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
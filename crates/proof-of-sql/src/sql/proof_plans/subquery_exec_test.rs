@@ -0,0 +1,257 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{owned_table_utility::*, ColumnRef, ColumnType, TableRef},
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+/// A column of a derived table, which can't be looked up in the accessor
+fn alias_column(alias: TableRef, name: &str, column_type: ColumnType) -> ColumnExpr {
+    ColumnExpr::new(ColumnRef::new(alias, name.into(), column_type))
+}
+
+/// `select id, salary from (select id, salary from sxt.employees where salary >= 150) as s where id <= 4`
+#[test]
+fn we_can_prove_a_filter_over_a_filtered_subquery() {
+    let (accessor, employees_ref, _) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.s".parse().unwrap();
+    let ast = subquery_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["id", "salary"], &accessor),
+            tab(employees_ref),
+            gte(
+                column(employees_ref, "salary", &accessor),
+                const_bigint(150),
+            ),
+        ),
+        alias,
+        filter(
+            vec![
+                aliased_plan(
+                    DynProofExpr::Column(alias_column(alias, "id", ColumnType::BigInt)),
+                    "id",
+                ),
+                aliased_plan(
+                    DynProofExpr::Column(alias_column(alias, "salary", ColumnType::BigInt)),
+                    "salary",
+                ),
+            ],
+            tab(alias),
+            lte(
+                DynProofExpr::Column(alias_column(alias, "id", ColumnType::BigInt)),
+                const_bigint(4),
+            ),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [2_i64, 3, 4]),
+        bigint("salary", [250_i64, 175, 300]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select dept_id, sum(salary) as total, count(*) as __count__
+/// from (select salary, dept_id from sxt.employees where id >= 2) as s group by dept_id`
+#[test]
+fn we_can_prove_a_group_by_over_a_subquery() {
+    let (accessor, employees_ref, _) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.s".parse().unwrap();
+    let ast = subquery_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["salary", "dept_id"], &accessor),
+            tab(employees_ref),
+            gte(column(employees_ref, "id", &accessor), const_bigint(2)),
+        ),
+        alias,
+        group_by(
+            vec![aliased_plan(
+                DynProofExpr::Column(alias_column(alias, "dept_id", ColumnType::BigInt)),
+                "dept_id",
+            )],
+            vec![sum_expr(
+                DynProofExpr::Column(alias_column(alias, "salary", ColumnType::BigInt)),
+                "total",
+            )],
+            "__count__",
+            tab(alias),
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("dept_id", [-10_i64, 10, 20, 40]),
+        bigint("total", [220_i64, 175, 250 + 50, 300]),
+        bigint("__count__", [1_i64, 1, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select * from (select id, dept_id from sxt.employees where salary >= 150) as s
+/// join sxt.departments on s.dept_id = departments.dept_id`
+#[test]
+fn we_can_prove_a_join_of_a_subquery_with_a_table() {
+    let (accessor, employees_ref, departments_ref) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.s".parse().unwrap();
+    let ast = subquery_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["id", "dept_id"], &accessor),
+            tab(employees_ref),
+            gte(
+                column(employees_ref, "salary", &accessor),
+                const_bigint(150),
+            ),
+        ),
+        alias,
+        join_exec(
+            table_exec(
+                alias,
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("dept_id", ColumnType::BigInt),
+                ],
+            ),
+            table_exec(
+                departments_ref,
+                vec![
+                    column_field("dept_id", ColumnType::BigInt),
+                    column_field("dept_name", ColumnType::VarChar),
+                ],
+            ),
+            1,
+            0,
+            &["id", "dept_id", "dept_name"],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    exercise_verification(&verifiable_res, &ast, &accessor, departments_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("id", [2_i64, 2, 3]),
+        bigint("dept_id", [20_i64, 20, 10]),
+        varchar("dept_name", ["Sales", "Support", "Legal"]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_subquery_with_an_empty_result() {
+    let (accessor, employees_ref, _) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.s".parse().unwrap();
+    let ast = subquery_exec(
+        filter(
+            cols_expr_plan(employees_ref, &["id"], &accessor),
+            tab(employees_ref),
+            gte(
+                column(employees_ref, "salary", &accessor),
+                const_bigint(1000),
+            ),
+        ),
+        alias,
+        aggregate(
+            vec![count_expr(
+                DynProofExpr::Column(alias_column(alias, "id", ColumnType::BigInt)),
+                "n",
+            )],
+            tab(alias),
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("n", [0_i64])]);
+    assert_eq!(res, expected);
+}
+
+/// `select dept_id, total from (select dept_id, sum(salary) as total, count(*) as __count__
+/// from sxt.employees group by dept_id) as g where total >= 300`
+#[test]
+fn we_can_prove_a_filter_over_a_group_by_on_one_column() {
+    let (accessor, employees_ref, _) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.g".parse().unwrap();
+    let ast = subquery_exec(
+        group_by(
+            cols_expr_plan(employees_ref, &["dept_id"], &accessor),
+            vec![sum_expr(
+                column(employees_ref, "salary", &accessor),
+                "total",
+            )],
+            "__count__",
+            tab(employees_ref),
+            const_bool(true),
+        ),
+        alias,
+        filter(
+            vec![
+                aliased_plan(
+                    DynProofExpr::Column(alias_column(alias, "dept_id", ColumnType::BigInt)),
+                    "dept_id",
+                ),
+                aliased_plan(
+                    DynProofExpr::Column(alias_column(alias, "total", ColumnType::BigInt)),
+                    "total",
+                ),
+            ],
+            tab(alias),
+            gte(
+                DynProofExpr::Column(alias_column(alias, "total", ColumnType::BigInt)),
+                const_bigint(300),
+            ),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, employees_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("dept_id", [20_i64, 40]),
+        bigint("total", [250_i64 + 50, 300]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_a_group_by_on_several_columns_which_is_not_at_the_top_level() {
+    let (accessor, employees_ref, _) =
+        accessor_with_employees_and_departments::<InnerProductProof>(());
+    let alias: TableRef = "sxt.g".parse().unwrap();
+    let ast = subquery_exec(
+        group_by(
+            cols_expr_plan(employees_ref, &["dept_id", "salary"], &accessor),
+            vec![],
+            "__count__",
+            tab(employees_ref),
+            const_bool(true),
+        ),
+        alias,
+        aggregate(
+            vec![count_expr(const_bigint(1), "n")],
+            tab(alias),
+            const_bool(true),
+        ),
+    );
+    let res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
@@ -1,11 +1,18 @@
 use super::{
-    DynProofPlan, EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, TableExec,
-    UnionExec,
+    AggregateExec, DistinctExec, DynProofPlan, EmptyExec, FilterExec, GroupByExec, IntersectExec,
+    JoinExec, OrderByExec, ProjectionExec, SemiJoinExec, SliceExec, SubqueryExec, TableExec,
+    UnionExec, WindowExec, WindowFunction,
+};
+#[cfg(all(test, feature = "blitzar"))]
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
 };
 use crate::{
     base::database::{ColumnField, ColumnType, TableRef},
-    sql::proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+    sql::proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
 };
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
 
 pub fn column_field(name: &str, column_type: ColumnType) -> ColumnField {
     ColumnField::new(name.into(), column_type)
@@ -35,25 +42,184 @@ pub fn filter(
 ///
 /// Will panic if `count_alias` cannot be parsed as a valid identifier.
 pub fn group_by(
-    group_by_exprs: Vec<ColumnExpr>,
-    sum_expr: Vec<AliasedDynProofExpr>,
+    group_by_exprs: Vec<AliasedDynProofExpr>,
+    aggregate_exprs: Vec<AliasedDynProofExpr>,
     count_alias: &str,
     table: TableExpr,
     where_clause: DynProofExpr,
 ) -> DynProofPlan {
     DynProofPlan::GroupBy(GroupByExec::new(
         group_by_exprs,
-        sum_expr,
+        aggregate_exprs,
         count_alias.into(),
         table,
         where_clause,
     ))
 }
 
+pub fn aggregate(
+    aggregate_exprs: Vec<AliasedDynProofExpr>,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::Aggregate(AggregateExec::new(aggregate_exprs, table, where_clause))
+}
+
+#[cfg(all(test, feature = "blitzar"))]
+pub fn aggregate_with_presences(
+    aggregate_exprs: Vec<AliasedDynProofExpr>,
+    presences: Vec<Option<DynProofExpr>>,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::Aggregate(AggregateExec::new_with_presences(
+        aggregate_exprs,
+        presences,
+        table,
+        where_clause,
+    ))
+}
+
 pub fn slice_exec(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> DynProofPlan {
     DynProofPlan::Slice(SliceExec::new(Box::new(input), skip, fetch))
 }
 
+pub fn order_by_exec(
+    input: DynProofPlan,
+    order_by: Vec<(usize, OrderByDirection)>,
+) -> DynProofPlan {
+    DynProofPlan::OrderBy(OrderByExec::new(Box::new(input), order_by))
+}
+
 pub fn union_exec(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> DynProofPlan {
     DynProofPlan::Union(UnionExec::new(inputs, schema))
 }
+
+pub fn join_exec(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_index: usize,
+    right_join_column_index: usize,
+    result_idents: &[&str],
+) -> DynProofPlan {
+    DynProofPlan::Join(JoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_index,
+        right_join_column_index,
+        result_idents.iter().map(|&ident| ident.into()).collect(),
+    ))
+}
+
+pub fn semi_join_exec(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_index: usize,
+    right_join_column_index: usize,
+    negated: bool,
+) -> DynProofPlan {
+    DynProofPlan::SemiJoin(SemiJoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_index,
+        right_join_column_index,
+        negated,
+    ))
+}
+
+pub fn subquery_exec(input: DynProofPlan, alias: TableRef, plan: DynProofPlan) -> DynProofPlan {
+    DynProofPlan::Subquery(SubqueryExec::new(Box::new(input), alias, Box::new(plan)))
+}
+
+pub fn distinct_exec(input: DynProofPlan) -> DynProofPlan {
+    DynProofPlan::Distinct(DistinctExec::new(Box::new(input)))
+}
+
+pub fn window_exec(
+    input: DynProofPlan,
+    partition_by: Vec<usize>,
+    order_by: Vec<(usize, OrderByDirection)>,
+    functions: Vec<(WindowFunction, &str)>,
+) -> DynProofPlan {
+    DynProofPlan::Window(WindowExec::new(
+        Box::new(input),
+        partition_by,
+        order_by,
+        functions
+            .into_iter()
+            .map(|(function, alias)| (function, alias.into()))
+            .collect(),
+    ))
+}
+
+pub fn intersect_exec(left: DynProofPlan, right: DynProofPlan, negated: bool) -> DynProofPlan {
+    DynProofPlan::Intersect(IntersectExec::new(Box::new(left), Box::new(right), negated))
+}
+
+/// An accessor with an `sxt.employees` table and an `sxt.departments` table that can be joined
+/// on `dept_id`.
+///
+/// Some employees match no department, one matches two, and one department has no employees.
+#[cfg(all(test, feature = "blitzar"))]
+pub fn accessor_with_employees_and_departments<CP: CommitmentEvaluationProof>(
+    setup: CP::ProverPublicSetup<'_>,
+) -> (OwnedTableTestAccessor<'_, CP>, TableRef, TableRef) {
+    let employees = owned_table([
+        bigint("id", [1_i64, 2, 3, 4, 5, 6]),
+        varchar("name", ["Ann", "Bob", "Cat", "Dan", "Eve", "Fay"]),
+        bigint("salary", [100_i64, 250, 175, 300, 50, 220]),
+        bigint("dept_id", [10_i64, 20, 10, 40, 20, -10]),
+    ]);
+    let departments = owned_table([
+        bigint("dept_id", [20_i64, 10, 30, 20]),
+        varchar("dept_name", ["Sales", "Legal", "Ops", "Support"]),
+    ]);
+    let employees_ref: TableRef = "sxt.employees".parse().unwrap();
+    let departments_ref: TableRef = "sxt.departments".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(setup);
+    accessor.add_table(employees_ref, employees, 0);
+    accessor.add_table(departments_ref, departments, 0);
+    (accessor, employees_ref, departments_ref)
+}
+
+/// An accessor with an `sxt.events` table with repeated `user_id` and `kind` values.
+#[cfg(all(test, feature = "blitzar"))]
+pub fn accessor_with_events<CP: CommitmentEvaluationProof>(
+    setup: CP::ProverPublicSetup<'_>,
+) -> (OwnedTableTestAccessor<'_, CP>, TableRef) {
+    let events = owned_table([
+        bigint("user_id", [3_i64, 1, 3, 2, 1, 3, -4]),
+        varchar(
+            "kind",
+            ["view", "click", "view", "view", "view", "click", "view"],
+        ),
+        bigint("amount", [10_i64, 20, 30, 40, 50, 60, 70]),
+    ]);
+    let events_ref: TableRef = "sxt.events".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(setup);
+    accessor.add_table(events_ref, events, 0);
+    (accessor, events_ref)
+}
+
+/// An accessor with an `sxt.orders` table and an `sxt.returns` table that share the `customer`
+/// and `item` columns.
+#[cfg(all(test, feature = "blitzar"))]
+pub fn accessor_with_orders_and_returns<CP: CommitmentEvaluationProof>(
+    setup: CP::ProverPublicSetup<'_>,
+) -> (OwnedTableTestAccessor<'_, CP>, TableRef, TableRef) {
+    let orders = owned_table([
+        bigint("customer", [3_i64, 1, 3, 2, -1, 3, 1]),
+        int("item", [10, 20, 10, 10, 30, 40, 20]),
+        varchar("note", ["a", "b", "c", "d", "e", "f", "g"]),
+    ]);
+    let returns = owned_table([
+        bigint("customer", [1_i64, 3, 5, 1, -1]),
+        int("item", [20, 40, 10, 20, 20]),
+    ]);
+    let orders_ref: TableRef = "sxt.orders".parse().unwrap();
+    let returns_ref: TableRef = "sxt.returns".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(setup);
+    accessor.add_table(orders_ref, orders, 0);
+    accessor.add_table(returns_ref, returns, 0);
+    (accessor, orders_ref, returns_ref)
+}
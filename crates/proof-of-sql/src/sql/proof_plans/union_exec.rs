@@ -32,8 +32,8 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct UnionExec {
-    pub(super) inputs: Vec<DynProofPlan>,
-    pub(super) schema: Vec<ColumnField>,
+    pub(crate) inputs: Vec<DynProofPlan>,
+    pub(crate) schema: Vec<ColumnField>,
 }
 
 impl UnionExec {
@@ -41,6 +41,11 @@ impl UnionExec {
     pub fn new(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> Self {
         Self { inputs, schema }
     }
+
+    /// The plans whose results are combined
+    pub fn inputs(&self) -> &[DynProofPlan] {
+        &self.inputs
+    }
 }
 
 impl ProofPlan for UnionExec
@@ -108,7 +113,7 @@ impl ProverEvaluate for UnionExec {
     #[tracing::instrument(name = "UnionExec::first_round_evaluate", level = "debug", skip_all)]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        builder: &mut FirstRoundBuilder,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
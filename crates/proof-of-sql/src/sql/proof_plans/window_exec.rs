@@ -0,0 +1,695 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter},
+    DynProofPlan, OrderByExec,
+};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes, order_by_util::compare_indexes_by_columns_with_direction,
+            Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::permutation::Permutation,
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialTerm, SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_shift, final_round_evaluate_sorted_with_ties,
+            first_round_evaluate_shift, first_round_evaluate_sorted, verify_shift,
+            verify_sorted_with_ties,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::iter;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// A window function computed by a [`WindowExec`]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum WindowFunction {
+    /// `ROW_NUMBER()`, the position of the row within its partition, starting at 1
+    RowNumber,
+    /// `RANK()`, one more than the number of rows of the partition ordered before the row
+    Rank,
+    /// `SUM(<col>)` of the input column with the given index, over the rows of the partition
+    /// up to the row and its peers
+    Sum(usize),
+}
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT *, <function1> OVER (PARTITION BY <col1>, ..., <colM> ORDER BY <col1> [ASC|DESC], ..., <colN> [ASC|DESC]) AS <alias1>,
+///         ..., <functionK> OVER (...) AS <aliasK>
+///     FROM (<ProofPlan>)
+/// ```
+/// where each window function is one of `ROW_NUMBER()`, `RANK()` or `SUM(<col>)`. As in postgres,
+/// the peers of a row are the rows of its partition which agree with it on the order by columns,
+/// and the sum of a row is taken over the rows of its partition up to its last peer.
+///
+/// The result consists of the rows of the input ordered by the partition columns in ascending
+/// order and then by the order by columns, followed by a column for each window function.
+///
+/// Let `I` be the input and `O` the output without the window columns. The prover commits to `O`,
+/// the window columns and the running sums before any challenge is drawn. We then prove that
+/// 1. `O` is a permutation of `I`.
+/// 2. The rows of `O` are sorted on the partition and order by columns, which provides the
+///     indicators `same_partition` and `same_peers` of the rows which are in the same partition
+///     as the previous row and additionally peers of it.
+/// 3. The window columns are computed from the rows before them, i.e. with `shifted` the shift of
+///     a column by one row, which is proven by a multiset argument, and `row_number` committed to
+///     if there is a `ROW_NUMBER()` or `RANK()`,
+/// ```text
+/// row_number = same_partition * shifted(row_number) + 1
+/// rank = same_peers * shifted(rank) + (1 - same_peers) * row_number
+/// running_sum = same_partition * shifted(running_sum) + column
+/// shifted(sum) = same_peers * sum + (1 - same_peers) * shifted(running_sum)
+/// ```
+/// where the last identity propagates the running sum of the last peer back to the other peers.
+///
+/// Since the ordering is proven using sign decompositions, the partition and order by columns
+/// can only be of the types supported by an [`OrderByExec`].
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct WindowExec {
+    pub(crate) input: Box<DynProofPlan>,
+    pub(crate) partition_by: Vec<usize>,
+    pub(crate) order_by: Vec<(usize, OrderByDirection)>,
+    pub(crate) functions: Vec<(WindowFunction, Ident)>,
+}
+
+impl WindowExec {
+    /// Creates a new window execution plan.
+    ///
+    /// # Panics
+    /// Panics if a column index is out of bounds, if a partition or order by column has a type
+    /// which can not be ordered by, or if a summed column is not numeric.
+    pub fn new(
+        input: Box<DynProofPlan>,
+        partition_by: Vec<usize>,
+        order_by: Vec<(usize, OrderByDirection)>,
+        functions: Vec<(WindowFunction, Ident)>,
+    ) -> Self {
+        let fields = input.get_column_result_fields();
+        assert!(
+            partition_by
+                .iter()
+                .chain(order_by.iter().map(|(index, _)| index))
+                .all(|&index| index < fields.len()
+                    && OrderByExec::is_supported_column_type(fields[index].data_type())),
+            "Window partition or order by column is out of bounds or not supported"
+        );
+        assert!(
+            functions.iter().all(|(function, _)| match function {
+                WindowFunction::Sum(index) =>
+                    *index < fields.len() && fields[*index].data_type().is_numeric(),
+                WindowFunction::RowNumber | WindowFunction::Rank => true,
+            }),
+            "Window sum column is out of bounds or not numeric"
+        );
+        Self {
+            input,
+            partition_by,
+            order_by,
+            functions,
+        }
+    }
+
+    /// The partition columns in ascending order followed by the order by columns
+    fn keys(&self) -> impl Iterator<Item = (usize, OrderByDirection)> + '_ {
+        self.partition_by
+            .iter()
+            .map(|&index| (index, OrderByDirection::Asc))
+            .chain(self.order_by.iter().copied())
+    }
+
+    fn directions(&self) -> Vec<OrderByDirection> {
+        self.keys().map(|(_, direction)| direction).collect()
+    }
+
+    /// Whether the row numbers are committed to, which is the case if they are needed
+    fn has_row_numbers(&self) -> bool {
+        self.functions.iter().any(|(function, _)| {
+            matches!(function, WindowFunction::RowNumber | WindowFunction::Rank)
+        })
+    }
+
+    /// The number of committed columns besides the output columns of the input
+    fn num_witness_columns(&self) -> usize {
+        usize::from(self.has_row_numbers())
+            + self
+                .functions
+                .iter()
+                .map(|(function, _)| match function {
+                    WindowFunction::RowNumber => 0,
+                    WindowFunction::Rank => 1,
+                    WindowFunction::Sum(_) => 2,
+                })
+                .sum::<usize>()
+    }
+}
+
+impl ProofPlan for WindowExec
+where
+    WindowExec: ProverEvaluate,
+{
+    #[allow(clippy::too_many_lines)]
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        one_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, one_eval_map)?;
+        let column_evals = input_eval.column_evals();
+        let one_eval = input_eval.one_eval();
+
+        // First round
+        let output_column_evals =
+            builder.try_consume_first_round_mle_evaluations(column_evals.len())?;
+        let witness_evals =
+            builder.try_consume_first_round_mle_evaluations(self.num_witness_columns())?;
+        let key_evals = self
+            .keys()
+            .map(|(index, _)| output_column_evals[index])
+            .collect::<Vec<_>>();
+
+        // Final round
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        // 1. The output is a permutation of the input
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            one_eval,
+            one_eval,
+            column_evals,
+            one_eval,
+            &output_column_evals,
+        )?;
+        // 2. The output is sorted
+        let tie_evals = verify_sorted_with_ties(
+            builder,
+            alpha,
+            beta,
+            &key_evals,
+            &self.directions(),
+            one_eval,
+        )?;
+        let eq_eval = |num_keys: usize| {
+            num_keys
+                .checked_sub(1)
+                .map_or(one_eval, |index| tie_evals[index])
+        };
+        let eq_partition_eval = eq_eval(self.partition_by.len());
+        let eq_peers_eval = eq_eval(key_evals.len());
+        // 3. The window columns are computed from the rows before them
+        let shifted_evals = if witness_evals.is_empty() {
+            Vec::new()
+        } else {
+            verify_shift(builder, alpha, beta, &witness_evals, one_eval)?.0
+        };
+        // chi_1 * chi_n, which along with eq_partition and eq_peers makes up
+        // same_partition = eq_partition - chi_1 * chi_n and same_peers = eq_peers - chi_1 * chi_n
+        let first_eval = builder.mle_evaluations.singleton_one_evaluation * one_eval;
+        let mut witness = witness_evals.iter().zip(&shifted_evals);
+        let row_number_eval = if self.has_row_numbers() {
+            let (&row_number_eval, &shifted_row_number_eval) =
+                witness.next().ok_or(ProofError::VerificationError {
+                    error: "missing row numbers",
+                })?;
+            // row_number - same_partition * shifted(row_number) - chi_n = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                row_number_eval
+                    - (eq_partition_eval - first_eval) * shifted_row_number_eval
+                    - one_eval,
+                3,
+            )?;
+            row_number_eval
+        } else {
+            S::zero()
+        };
+        let mut window_column_evals = Vec::with_capacity(self.functions.len());
+        for (function, _) in &self.functions {
+            let mut next_eval = || {
+                witness.next().ok_or(ProofError::VerificationError {
+                    error: "missing window columns",
+                })
+            };
+            let window_column_eval = match function {
+                WindowFunction::RowNumber => row_number_eval,
+                WindowFunction::Rank => {
+                    let (&rank_eval, &shifted_rank_eval) = next_eval()?;
+                    // rank - same_peers * shifted(rank) - (1 - same_peers) * row_number = 0
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        rank_eval - (eq_peers_eval - first_eval) * shifted_rank_eval
+                            + (eq_peers_eval - first_eval - S::one()) * row_number_eval,
+                        3,
+                    )?;
+                    rank_eval
+                }
+                WindowFunction::Sum(index) => {
+                    let (&running_sum_eval, &shifted_running_sum_eval) = next_eval()?;
+                    let (&sum_eval, &shifted_sum_eval) = next_eval()?;
+                    // running_sum - same_partition * shifted(running_sum) - column = 0
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        running_sum_eval
+                            - (eq_partition_eval - first_eval) * shifted_running_sum_eval
+                            - output_column_evals[*index],
+                        3,
+                    )?;
+                    // shifted(sum) - same_peers * sum - (1 - same_peers) * shifted(running_sum) = 0
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        shifted_sum_eval - (eq_peers_eval - first_eval) * sum_eval
+                            + (eq_peers_eval - first_eval - S::one()) * shifted_running_sum_eval,
+                        3,
+                    )?;
+                    sum_eval
+                }
+            };
+            window_column_evals.push(window_column_eval);
+        }
+
+        Ok(TableEvaluation::new(
+            output_column_evals
+                .into_iter()
+                .chain(window_column_evals)
+                .collect(),
+            one_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let input_fields = self.input.get_column_result_fields();
+        let window_fields = self
+            .functions
+            .iter()
+            .map(|(function, alias)| {
+                let data_type = match function {
+                    WindowFunction::RowNumber | WindowFunction::Rank => ColumnType::BigInt,
+                    WindowFunction::Sum(index) => input_fields[*index].data_type(),
+                };
+                ColumnField::new(alias.clone(), data_type)
+            })
+            .collect::<Vec<_>>();
+        input_fields.into_iter().chain(window_fields).collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for WindowExec {
+    #[tracing::instrument(name = "WindowExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let witness = self.compute_witness(alloc, &input);
+        let witness_columns = witness.witness_columns(alloc);
+
+        for column in &witness.sorted_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        for column in &witness_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        first_round_evaluate_sorted(builder, alloc, &witness.key_columns, num_rows);
+        if !witness_columns.is_empty() {
+            first_round_evaluate_shift(builder, alloc, &witness_columns, num_rows);
+        }
+        builder.request_post_result_challenges(2);
+
+        let res = self.output_table(&witness, num_rows);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "WindowExec::final_round_evaluate", level = "debug", skip_all)]
+    #[allow(clippy::too_many_lines)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let witness = self.compute_witness(alloc, &input);
+        let witness_columns = witness.witness_columns(alloc);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 1. The output is a permutation of the input
+        let selection = alloc.alloc_slice_fill_copy(num_rows, true);
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            selection,
+            &witness.sorted_columns,
+            num_rows,
+            num_rows,
+        );
+        // 2. The output is sorted
+        let ties = final_round_evaluate_sorted_with_ties(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &witness.key_columns,
+            &self.directions(),
+            num_rows,
+        );
+        let chi_n: &[S] = alloc.alloc_slice_fill_copy(num_rows, S::one());
+        let chi_1: &[bool] = alloc.alloc_slice_fill_copy(1, true);
+        let eq = |num_keys: usize| num_keys.checked_sub(1).map_or(chi_n, |index| ties[index]);
+        let eq_partition = eq(self.partition_by.len());
+        let eq_peers = eq(witness.key_columns.len());
+        // 3. The window columns are computed from the rows before them
+        let shifted_columns = if witness_columns.is_empty() {
+            Vec::new()
+        } else {
+            final_round_evaluate_shift(builder, alloc, alpha, beta, &witness_columns, num_rows)
+        };
+        // Note that `same = eq - chi_1 * chi_n` for both eq_partition and eq_peers.
+        // column * same * shifted_column
+        let same_times = |coefficient: S, eq: &'a [S], column: &'a [S]| {
+            [
+                (
+                    coefficient,
+                    vec![
+                        Box::new(eq) as Box<dyn MultilinearExtension<S>>,
+                        Box::new(column),
+                    ],
+                ),
+                (
+                    -coefficient,
+                    vec![
+                        Box::new(chi_1) as Box<dyn MultilinearExtension<S>>,
+                        Box::new(chi_n),
+                        Box::new(column),
+                    ],
+                ),
+            ]
+        };
+        let mut witness_iter = witness_columns.iter().copied().zip(shifted_columns);
+        let row_numbers = if self.has_row_numbers() {
+            let (row_numbers, shifted_row_numbers) = witness_iter
+                .next()
+                .expect("row numbers are committed to if needed");
+            // row_number - same_partition * shifted(row_number) - chi_n = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                iter::once((
+                    S::one(),
+                    vec![Box::new(row_numbers) as Box<dyn MultilinearExtension<S>>],
+                ))
+                .chain(same_times(-S::one(), eq_partition, shifted_row_numbers))
+                .chain([(
+                    -S::one(),
+                    vec![Box::new(chi_n) as Box<dyn MultilinearExtension<S>>],
+                )])
+                .collect::<Vec<SumcheckSubpolynomialTerm<S>>>(),
+            );
+            row_numbers
+        } else {
+            &[]
+        };
+        for window_columns in &witness.window_columns {
+            match window_columns {
+                WindowColumns::RowNumber => {}
+                WindowColumns::Rank(_) => {
+                    let (ranks, shifted_ranks) =
+                        witness_iter.next().expect("ranks are committed to");
+                    // rank - same_peers * shifted(rank) - (1 - same_peers) * row_number = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        iter::once((
+                            S::one(),
+                            vec![Box::new(ranks) as Box<dyn MultilinearExtension<S>>],
+                        ))
+                        .chain(same_times(-S::one(), eq_peers, shifted_ranks))
+                        .chain([(
+                            -S::one(),
+                            vec![Box::new(row_numbers) as Box<dyn MultilinearExtension<S>>],
+                        )])
+                        .chain(same_times(S::one(), eq_peers, row_numbers))
+                        .collect::<Vec<SumcheckSubpolynomialTerm<S>>>(),
+                    );
+                }
+                WindowColumns::Sum { values, .. } => {
+                    let (running_sums, shifted_running_sums) =
+                        witness_iter.next().expect("running sums are committed to");
+                    let (sums, shifted_sums) = witness_iter.next().expect("sums are committed to");
+                    // running_sum - same_partition * shifted(running_sum) - column = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        iter::once((
+                            S::one(),
+                            vec![Box::new(running_sums) as Box<dyn MultilinearExtension<S>>],
+                        ))
+                        .chain(same_times(-S::one(), eq_partition, shifted_running_sums))
+                        .chain([(
+                            -S::one(),
+                            vec![Box::new(*values) as Box<dyn MultilinearExtension<S>>],
+                        )])
+                        .collect::<Vec<SumcheckSubpolynomialTerm<S>>>(),
+                    );
+                    // shifted(sum) - same_peers * sum - (1 - same_peers) * shifted(running_sum) = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        iter::once((S::one(), vec![Box::new(shifted_sums) as Box<dyn MultilinearExtension<S>>]))
+                            .chain(same_times(-S::one(), eq_peers, sums))
+                            .chain([(-S::one(), vec![Box::new(shifted_running_sums) as Box<dyn MultilinearExtension<S>>])])
+                            .chain(same_times(S::one(), eq_peers, shifted_running_sums))
+                            .collect::<Vec<SumcheckSubpolynomialTerm<S>>>(),
+                    );
+                }
+            }
+        }
+
+        let res = self.output_table(&witness, num_rows);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
+
+/// The columns computed for a window function, besides the row numbers
+enum WindowColumns<'a, S: Scalar> {
+    RowNumber,
+    Rank(&'a [i64]),
+    Sum {
+        /// The summed column, in the order of the output
+        values: &'a [S],
+        running_sums: &'a [S],
+        sums: &'a [S],
+    },
+}
+
+/// The sorted input along with the columns computed for the window functions
+struct WindowWitness<'a, S: Scalar> {
+    sorted_columns: Vec<Column<'a, S>>,
+    /// The partition and order by columns of the output
+    key_columns: Vec<&'a [S]>,
+    row_numbers: Option<&'a [i64]>,
+    window_columns: Vec<WindowColumns<'a, S>>,
+}
+
+impl<'a, S: Scalar> WindowWitness<'a, S> {
+    /// The committed columns besides the output columns of the input, in the order they are committed to
+    fn witness_columns(&self, alloc: &'a Bump) -> Vec<&'a [S]> {
+        let to_scalars = |column: &[i64]| {
+            alloc.alloc_slice_fill_iter(column.iter().map(|&v| S::from(v))) as &[_]
+        };
+        self.row_numbers
+            .map(to_scalars)
+            .into_iter()
+            .chain(
+                self.window_columns
+                    .iter()
+                    .flat_map(|window_columns| match window_columns {
+                        WindowColumns::RowNumber => vec![],
+                        WindowColumns::Rank(ranks) => vec![to_scalars(ranks)],
+                        WindowColumns::Sum {
+                            running_sums, sums, ..
+                        } => vec![*running_sums, *sums],
+                    }),
+            )
+            .collect()
+    }
+}
+
+impl WindowExec {
+    /// Sort the input and compute the window functions over it
+    ///
+    /// # Panics
+    /// Panics if the permutation is out of bounds, which can not happen.
+    fn compute_witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        input: &Table<'a, S>,
+    ) -> WindowWitness<'a, S> {
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let keys = self
+            .keys()
+            .map(|(index, direction)| (columns[index], direction))
+            .collect::<Vec<_>>();
+        // Break ties by the index so that the sort is deterministic
+        let permutation = Permutation::unchecked_new_from_cmp(num_rows, |&a, &b| {
+            compare_indexes_by_columns_with_direction(&keys, a, b).then(a.cmp(&b))
+        });
+        let sorted_columns = columns
+            .iter()
+            .map(|column| {
+                apply_column_to_indexes(column, alloc, permutation.as_slice())
+                    .expect("Indexes can not be out of bounds")
+            })
+            .collect::<Vec<_>>();
+        let to_scalars = |column: &Column<'a, S>| {
+            alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0)) as &[_]
+        };
+        let key_columns: Vec<&'a [S]> = self
+            .keys()
+            .map(|(index, _)| to_scalars(&sorted_columns[index]))
+            .collect();
+
+        // Whether a row starts a new group, i.e. differs from the previous row on the first keys
+        let starts_group = |num_keys: usize| {
+            (0..num_rows)
+                .map(|i| i == 0 || key_columns[..num_keys].iter().any(|c| c[i] != c[i - 1]))
+                .collect::<Vec<_>>()
+        };
+        let new_partitions = starts_group(self.partition_by.len());
+        let new_peers = starts_group(key_columns.len());
+
+        let row_numbers: Option<&'a [i64]> = self.has_row_numbers().then(|| {
+            let mut row_number: i64 = 0;
+            alloc.alloc_slice_fill_iter(new_partitions.iter().map(|&new_partition| {
+                row_number = if new_partition { 1 } else { row_number + 1 };
+                row_number
+            })) as &[_]
+        });
+        let window_columns = self
+            .functions
+            .iter()
+            .map(|(function, _)| match function {
+                WindowFunction::RowNumber => WindowColumns::RowNumber,
+                WindowFunction::Rank => {
+                    let row_numbers = row_numbers.expect("row numbers are computed for ranks");
+                    let mut rank = 0;
+                    WindowColumns::Rank(alloc.alloc_slice_fill_iter((0..num_rows).map(|i| {
+                        if new_peers[i] {
+                            rank = row_numbers[i];
+                        }
+                        rank
+                    })))
+                }
+                WindowFunction::Sum(index) => {
+                    let values = to_scalars(&sorted_columns[*index]);
+                    let mut running_sum = S::zero();
+                    let running_sums: &[S] = alloc.alloc_slice_fill_iter((0..num_rows).map(|i| {
+                        if new_partitions[i] {
+                            running_sum = S::zero();
+                        }
+                        running_sum += values[i];
+                        running_sum
+                    }));
+                    // The sum of a row is the running sum of its last peer
+                    let sums = alloc.alloc_slice_fill_copy(num_rows, S::zero());
+                    for i in (0..num_rows).rev() {
+                        sums[i] = if i + 1 == num_rows || new_peers[i + 1] {
+                            running_sums[i]
+                        } else {
+                            sums[i + 1]
+                        };
+                    }
+                    WindowColumns::Sum {
+                        values,
+                        running_sums,
+                        sums,
+                    }
+                }
+            })
+            .collect();
+
+        WindowWitness {
+            sorted_columns,
+            key_columns,
+            row_numbers,
+            window_columns,
+        }
+    }
+
+    /// The sorted input followed by the window columns
+    ///
+    /// # Panics
+    /// Panics if the columns do not match the result fields, which can not happen.
+    fn output_table<'a, S: Scalar>(
+        &self,
+        witness: &WindowWitness<'a, S>,
+        num_rows: usize,
+    ) -> Table<'a, S> {
+        let window_columns =
+            witness
+                .window_columns
+                .iter()
+                .map(|window_columns| match window_columns {
+                    WindowColumns::RowNumber => Column::BigInt(
+                        witness
+                            .row_numbers
+                            .expect("row numbers are computed for row numbers"),
+                    ),
+                    WindowColumns::Rank(ranks) => Column::BigInt(ranks),
+                    WindowColumns::Sum { sums, .. } => Column::Scalar(sums),
+                });
+        Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(witness.sorted_columns.iter().copied().chain(window_columns)),
+            TableOptions::new(Some(num_rows)),
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
@@ -0,0 +1,193 @@
+use super::{test_utility::*, WindowFunction};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor},
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+
+#[test]
+fn we_can_prove_window_functions_over_partitions_with_ties() {
+    let data = owned_table([
+        int("p", [1, 2, 1, 2, 1, 1]),
+        bigint("o", [3_i64, 1, 3, 2, 1, 5]),
+        bigint("x", [10_i64, 20, 30, 40, 50, 60]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = window_exec(
+        projection(cols_expr_plan(t, &["p", "o", "x"], &accessor), tab(t)),
+        vec![0],
+        vec![(1, OrderByDirection::Asc)],
+        vec![
+            (WindowFunction::RowNumber, "rn"),
+            (WindowFunction::Rank, "r"),
+            (WindowFunction::Sum(2), "s"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("p", [1, 1, 1, 1, 2, 2]),
+        bigint("o", [1_i64, 3, 3, 5, 1, 2]),
+        bigint("x", [50_i64, 10, 30, 60, 20, 40]),
+        bigint("rn", [1_i64, 2, 3, 4, 1, 2]),
+        bigint("r", [1_i64, 2, 2, 4, 1, 2]),
+        bigint("s", [50_i64, 90, 90, 150, 20, 60]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_window_functions_ordered_descending_without_partitions() {
+    let data = owned_table([
+        int("p", [1, 2, 1, 2, 1, 1]),
+        bigint("o", [3_i64, 1, 3, 2, 1, 5]),
+        decimal75("x", 10, 2, [10, 20, 30, 40, 50, 60]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = window_exec(
+        projection(cols_expr_plan(t, &["p", "o", "x"], &accessor), tab(t)),
+        vec![],
+        vec![(1, OrderByDirection::Desc)],
+        vec![(WindowFunction::Rank, "r"), (WindowFunction::Sum(2), "s")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("p", [1, 1, 1, 2, 2, 1]),
+        bigint("o", [5_i64, 3, 3, 2, 1, 1]),
+        decimal75("x", 10, 2, [60, 10, 30, 40, 20, 50]),
+        bigint("r", [1_i64, 2, 2, 4, 5, 5]),
+        decimal75("s", 10, 2, [60, 100, 100, 140, 210, 210]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_window_functions_over_the_whole_table() {
+    let data = owned_table([bigint("a", [3_i64, 1, 2]), varchar("b", ["3", "1", "2"])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = window_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![],
+        vec![],
+        vec![
+            (WindowFunction::RowNumber, "rn"),
+            (WindowFunction::Rank, "r"),
+            (WindowFunction::Sum(0), "s"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [3_i64, 1, 2]),
+        varchar("b", ["3", "1", "2"]),
+        bigint("rn", [1_i64, 2, 3]),
+        bigint("r", [1_i64, 1, 1]),
+        bigint("s", [6_i64, 6, 6]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_chained_window_execs_over_a_filter() {
+    let data = owned_table([
+        int("p", [1, 2, 1, 2, 1, 1, 3]),
+        bigint("o", [3_i64, 1, 3, 2, 1, 5, 4]),
+        bigint("x", [10_i64, 20, 30, 40, 50, 60, 70]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let where_clause = not(equal(column(t, "p", &accessor), const_int(3)));
+    let ast = window_exec(
+        window_exec(
+            filter(
+                cols_expr_plan(t, &["p", "o", "x"], &accessor),
+                tab(t),
+                where_clause,
+            ),
+            vec![0],
+            vec![],
+            vec![(WindowFunction::RowNumber, "rn")],
+        ),
+        vec![],
+        vec![(1, OrderByDirection::Desc)],
+        vec![(WindowFunction::Sum(2), "s")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("p", [1, 1, 1, 2, 1, 2]),
+        bigint("o", [5_i64, 3, 3, 2, 1, 1]),
+        bigint("x", [60_i64, 10, 30, 40, 50, 20]),
+        bigint("rn", [4_i64, 1, 2, 2, 3, 1]),
+        bigint("s", [60_i64, 100, 100, 140, 210, 210]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_window_exec_with_no_rows() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3]), int128("b", [1_i128, 2, 3])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let where_clause = equal(column(t, "a", &accessor), const_bigint(4));
+    let ast = window_exec(
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            where_clause,
+        ),
+        vec![0],
+        vec![(1, OrderByDirection::Desc)],
+        vec![(WindowFunction::Rank, "r"), (WindowFunction::Sum(1), "s")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [0_i64; 0]),
+        int128("b", [0_i128; 0]),
+        bigint("r", [0_i64; 0]),
+        int128("s", [0_i128; 0]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+#[should_panic(expected = "Window partition or order by column is out of bounds or not supported")]
+fn we_cannot_create_a_window_exec_partitioned_by_a_varchar_column() {
+    let data = owned_table([bigint("a", [1_i64, 2]), varchar("b", ["1", "2"])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    window_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![1],
+        vec![],
+        vec![(WindowFunction::RowNumber, "rn")],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Window sum column is out of bounds or not numeric")]
+fn we_cannot_create_a_window_exec_summing_a_varchar_column() {
+    let data = owned_table([bigint("a", [1_i64, 2]), varchar("b", ["1", "2"])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    window_exec(
+        projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        vec![0],
+        vec![],
+        vec![(WindowFunction::Sum(1), "s")],
+    );
+}
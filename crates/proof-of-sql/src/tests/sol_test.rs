@@ -1,5 +1,26 @@
-use crate::tests::{ForgeScript, ForgeScriptError};
-use alloy_sol_types::{private::primitives::U256, sol};
+use crate::{
+    base::{
+        polynomial::{
+            compute_rho_eval, compute_truncated_lagrange_basis_inner_product,
+            compute_truncated_lagrange_basis_sum, CompositePolynomial,
+        },
+        proof::{Keccak256Transcript, Transcript},
+        scalar::Scalar,
+    },
+    proof_primitive::{
+        hyperkzg::BNScalar,
+        sumcheck::{ProverState, SumcheckProof},
+    },
+    tests::{ForgeScript, ForgeScriptError},
+};
+use alloy_sol_types::{
+    private::primitives::{B256, U256},
+    sol,
+};
+use ark_std::{
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    UniformRand,
+};
 
 #[test]
 #[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
@@ -37,3 +58,154 @@ fn we_can_pass_custom_struct_into_solidity_from_rust() {
     .execute()
     .unwrap();
 }
+
+fn scalar_to_u256(scalar: BNScalar) -> U256 {
+    U256::from_limbs(scalar.into())
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_can_draw_the_same_challenges_in_solidity_as_in_rust() {
+    sol! {
+        struct ChallengeCase {
+            uint64[] integers;
+            uint256[] scalars;
+            uint256 expectedScalarChallenge;
+            bytes32 expectedChallenge;
+        }
+    }
+    let mut rng = StdRng::seed_from_u64(123);
+    for (num_integers, num_scalars) in [(0, 0), (1, 0), (0, 1), (2, 3), (5, 7)] {
+        let integers: Vec<u64> = (0..num_integers).map(|_| rng.gen()).collect();
+        let scalars: Vec<BNScalar> = (0..num_scalars).map(|_| BNScalar::rand(&mut rng)).collect();
+        let mut transcript = Keccak256Transcript::new();
+        transcript.extend_as_be(integers.iter().copied());
+        let scalar_challenge: BNScalar = transcript.scalar_challenge_as_be();
+        transcript.extend_scalars_as_be(&scalars);
+        let challenge = transcript.challenge_as_le();
+        let arg = ChallengeCase {
+            integers: integers.clone(),
+            scalars: scalars.iter().copied().map(scalar_to_u256).collect(),
+            expectedScalarChallenge: scalar_to_u256(scalar_challenge),
+            expectedChallenge: B256::from(challenge),
+        };
+        ForgeScript::new(
+            "./sol_src/tests/TranscriptTest.t.sol",
+            "rustTestWeCanDrawTheSameChallengesAsRust",
+        )
+        .arg(&arg)
+        .execute()
+        .unwrap();
+    }
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_can_verify_sumcheck_proofs_from_rust_in_solidity() {
+    sol! {
+        struct SumcheckCase {
+            uint256[] coefficients;
+            uint256 numVariables;
+            uint256 claimedSum;
+            uint256[] expectedEvaluationPoint;
+            uint256 expectedEvaluation;
+        }
+    }
+    let mut rng = StdRng::seed_from_u64(456);
+    for (num_variables, max_multiplicands, products) in [
+        (1, 1, vec![vec![0]]),
+        (2, 2, vec![vec![0, 1]]),
+        (3, 3, vec![vec![0, 1, 2], vec![1], vec![0, 2]]),
+    ] {
+        let poly = CompositePolynomial::<BNScalar>::rand(
+            num_variables,
+            max_multiplicands,
+            [1 << num_variables, (1 << num_variables) - 1, 3],
+            products,
+            &mut rng,
+        );
+        let claimed_sum = poly.hypercube_sum(1 << num_variables);
+        let mut evaluation_point = vec![BNScalar::ZERO; num_variables];
+        let proof = SumcheckProof::create(
+            &mut Keccak256Transcript::new(),
+            &mut evaluation_point,
+            ProverState::create(&poly),
+        );
+        let subclaim = proof
+            .verify_without_evaluation(&mut Keccak256Transcript::new(), num_variables, &claimed_sum)
+            .unwrap();
+        let sumcheck_case = |claimed_sum| SumcheckCase {
+            coefficients: proof
+                .coefficients
+                .iter()
+                .copied()
+                .map(scalar_to_u256)
+                .collect(),
+            numVariables: U256::from(num_variables),
+            claimedSum: scalar_to_u256(claimed_sum),
+            expectedEvaluationPoint: subclaim
+                .evaluation_point
+                .iter()
+                .copied()
+                .map(scalar_to_u256)
+                .collect(),
+            expectedEvaluation: scalar_to_u256(subclaim.expected_evaluation),
+        };
+        ForgeScript::new(
+            "./sol_src/tests/SumcheckTest.t.sol",
+            "rustTestWeCanVerifySumcheckProofsFromRust",
+        )
+        .arg(&sumcheck_case(claimed_sum))
+        .execute()
+        .unwrap();
+        assert!(matches!(
+            ForgeScript::new(
+                "./sol_src/tests/SumcheckTest.t.sol",
+                "rustTestWeCanVerifySumcheckProofsFromRust",
+            )
+            .arg(&sumcheck_case(claimed_sum + BNScalar::ONE))
+            .execute(),
+            Err(ForgeScriptError::SolidityError { .. })
+        ));
+    }
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_can_compute_the_same_lagrange_basis_evaluations_in_solidity_as_in_rust() {
+    sol! {
+        struct EvaluationCase {
+            uint256 length;
+            uint256[] a;
+            uint256[] b;
+            uint256 expectedSum;
+            uint256 expectedInnerProduct;
+            uint256 expectedRhoEval;
+        }
+    }
+    let mut rng = StdRng::seed_from_u64(789);
+    let evaluation_cases: Vec<EvaluationCase> = (0..5)
+        .flat_map(|num_vars| (0..=1 << num_vars).map(move |length| (num_vars, length)))
+        .map(|(num_vars, length)| {
+            let a: Vec<BNScalar> = (0..num_vars).map(|_| BNScalar::rand(&mut rng)).collect();
+            let b: Vec<BNScalar> = (0..num_vars).map(|_| BNScalar::rand(&mut rng)).collect();
+            EvaluationCase {
+                length: U256::from(length),
+                a: a.iter().copied().map(scalar_to_u256).collect(),
+                b: b.iter().copied().map(scalar_to_u256).collect(),
+                expectedSum: scalar_to_u256(compute_truncated_lagrange_basis_sum(length, &a)),
+                expectedInnerProduct: scalar_to_u256(
+                    compute_truncated_lagrange_basis_inner_product(length, &a, &b),
+                ),
+                expectedRhoEval: scalar_to_u256(compute_rho_eval(length, &a)),
+            }
+        })
+        .collect();
+    ForgeScript::new(
+        "./sol_src/tests/LagrangeBasisEvaluationTest.t.sol",
+        "rustTestWeCanComputeTheSameEvaluationsAsRust",
+    )
+    .arg(&evaluation_cases)
+    .execute()
+    .unwrap();
+}
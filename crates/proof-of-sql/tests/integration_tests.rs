@@ -9,15 +9,17 @@ use proof_of_sql::{
         scalar::Curve25519Scalar,
     },
     proof_primitive::dory::{
-        DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+        DoryEvaluationProof, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup,
         DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
     },
+    proof_primitive::hyperkzg::{HyperKZGCommitmentEvaluationProof, HyperKZGVerifierSetup},
     sql::{
         parse::{ConversionError, QueryExpr},
         postprocessing::apply_postprocessing_steps,
-        proof::{QueryError, VerifiableQueryResult},
+        proof::{BatchedVerifiableQueryResult, QueryError, VerifiableQueryResult},
     },
 };
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
 #[test]
 #[cfg(feature = "blitzar")]
@@ -110,6 +112,162 @@ fn we_can_prove_a_minimal_filter_query_with_dynamic_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_batch_of_queries_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            boolean("c", [true, false, true, true, false]),
+        ]),
+        0,
+    );
+    let queries = [
+        "SELECT a FROM table WHERE c",
+        "SELECT a, sum(b) as d, count(*) as e FROM table group by a",
+        "SELECT b FROM table WHERE a = 2",
+    ]
+    .map(|sql| QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap());
+    let exprs = queries
+        .iter()
+        .map(QueryExpr::proof_expr)
+        .collect::<Vec<_>>();
+    let verifiable_results = BatchedVerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        &exprs,
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_results = verifiable_results
+        .verify(&exprs, &accessor, &&verifier_setup)
+        .unwrap()
+        .into_iter()
+        .map(|query_data| query_data.table)
+        .collect::<Vec<_>>();
+    let expected_results = vec![
+        owned_table([bigint("a", [1, 2, 3])]),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            bigint("d", [1, 7, 2]),
+            bigint("e", [2, 2, 1]),
+        ]),
+        owned_table([bigint("b", [4, 3])]),
+    ];
+    assert_eq!(owned_table_results, expected_results);
+}
+
+#[test]
+fn we_can_prove_a_minimal_filter_query_with_hyperkzg() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(64, &mut test_rng());
+
+    let mut accessor =
+        OwnedTableTestAccessor::<HyperKZGCommitmentEvaluationProof>::new_empty_with_setup(
+            &prover_setup,
+        );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([boolean("a", [true, false])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE not a".parse().unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<HyperKZGCommitmentEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &prover_setup.as_slice(),
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([boolean("a", [false])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_on_a_table_with_one_row_with_hyperkzg() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(64, &mut test_rng());
+
+    let mut accessor =
+        OwnedTableTestAccessor::<HyperKZGCommitmentEvaluationProof>::new_empty_with_setup(
+            &prover_setup,
+        );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [3]), bigint("b", [5])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a + b AS c FROM table WHERE a = 3".parse().unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<HyperKZGCommitmentEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &prover_setup.as_slice(),
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("c", [8])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_basic_group_by_query_with_hyperkzg() {
+    let (prover_setup, verifier_setup) = HyperKZGVerifierSetup::test_rand(64, &mut test_rng());
+
+    let mut accessor =
+        OwnedTableTestAccessor::<HyperKZGCommitmentEvaluationProof>::new_empty_with_setup(
+            &prover_setup,
+        );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            bigint("c", [-2, 2, 1, 0, 1]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(2 * b + 1) as d, count(*) as e FROM table WHERE c >= 0 group by a"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<HyperKZGCommitmentEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &prover_setup.as_slice(),
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("d", [1, 16, 5]),
+        bigint("e", [1, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_equality_query_with_curve25519() {
@@ -758,6 +916,1096 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_an_order_by_with_a_limit_on_a_group_by_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 3, 2, 3, 1, 2, 3]),
+            bigint("b", [1, 0, 4, 2, 3, 5, 6]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(b) as s, count(*) as n FROM table GROUP BY a ORDER BY n DESC, a LIMIT 2"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [3, 1]),
+        bigint("s", [8, 4]),
+        bigint("n", [3, 2]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_join_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.cats".parse().unwrap(),
+        owned_table([
+            int("id", [1, 2, 3, 4, 5]),
+            varchar("name", ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper"]),
+            bigint("human_id", [2, 1, 2, 7, 3]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.humans".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("human", ["Ian", "Gretta", "Alice"]),
+            boolean("is_adult", [true, true, false]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "select name, human from cats join humans on cats.human_id = humans.id where is_adult order by name"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<DoryScalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("name", ["Chloe", "Margaret", "Prudence"]),
+        varchar("human", ["Gretta", "Ian", "Gretta"]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_join_query_with_group_by_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [1, 2, 1, 3, 1]),
+            bigint("amount", [10, 20, 30, 40, 50]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.customers".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [1, 2, 4]),
+            varchar("customer", ["a", "b", "d"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT customer, sum(amount) as total, count(*) as num_orders FROM orders JOIN customers ON orders.customer_id = customers.customer_id GROUP BY customer"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<DoryScalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("customer", ["a", "b"]),
+        bigint("total", [90, 20]),
+        bigint("num_orders", [3, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_aggregation_without_group_by_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            bigint("b", [10, 20, 30, 40, 50]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT sum(b) as total, count(*) as num FROM table WHERE a > 2"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<DoryScalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("total", [120]), bigint("num", [3])]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_aggregation_without_group_by_of_an_empty_table_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [0; 0]), bigint("b", [0; 0])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT sum(b) as total, count(*) as num FROM table WHERE a > 2"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<DoryScalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("total", [0]), bigint("num", [0])]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_max_and_min_with_and_without_group_by_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 1, 2, 3, 1]),
+            bigint("b", [10, -20, 30, 40, -50, 30]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a, max(b) as mx, min(b) as mn, count(*) as num FROM table GROUP BY a",
+            owned_table([
+                bigint("a", [1, 2, 3]),
+                bigint("mx", [30, 40, -50]),
+                bigint("mn", [10, -20, -50]),
+                bigint("num", [3, 2, 1]),
+            ]),
+        ),
+        (
+            "SELECT max(b) as mx, min(b) as mn FROM table WHERE a <> 3",
+            owned_table([
+                bigint("mx", [40]),
+                presence("mx", [true]),
+                bigint("mn", [-20]),
+                presence("mn", [true]),
+            ]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(query.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_averages_with_and_without_group_by_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 1, 2, 3, 1]),
+            bigint("b", [10, -20, 30, 40, -50, 30]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a, avg(b) as m, count(*) as num FROM table GROUP BY a ORDER BY m",
+            owned_table([
+                bigint("a", [3, 2, 1]),
+                decimal75("m", 25, 6, [-50_000_000, 10_000_000, 23_333_333]),
+                bigint("num", [1, 2, 3]),
+            ]),
+        ),
+        (
+            "SELECT avg(b) as m FROM table WHERE a <> 3",
+            owned_table([decimal75("m", 25, 6, [18_000_000]), presence("m", [true])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(query.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_medians_and_percentiles_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 1, 2, 3, 1]),
+            bigint("b", [10, -20, 30, 40, -50, 30]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a, median(b) as m, percentile_disc(0.5) within group (order by b) as d, count(*) as num FROM table GROUP BY a ORDER BY a",
+            owned_table([
+                bigint("a", [1, 2, 3]),
+                decimal75("m", 20, 1, [300, 100, -500]),
+                bigint("d", [30, -20, -50]),
+                bigint("num", [3, 2, 1]),
+            ]),
+        ),
+        (
+            "SELECT percentile_cont(0.75) within group (order by b) as p FROM table WHERE a <> 3",
+            owned_table([decimal75("p", 21, 2, [3000]), presence("p", [true])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(query.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_time_series_rollups_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    let utc = PoSQLTimeZone::utc();
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            timestamptz(
+                "ts",
+                PoSQLTimeUnit::Second,
+                utc,
+                [
+                    1_718_841_600, // 2024-06-20 00:00:00
+                    1_718_900_000, // 2024-06-20 16:13:20
+                    1_718_928_000, // 2024-06-21 00:00:00
+                    1_719_014_399, // 2024-06-21 23:59:59
+                    1_704_067_199, // 2023-12-31 23:59:59
+                ],
+            ),
+            bigint("v", [10, 20, 30, 40, 50]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT date_trunc('day', ts) as day, sum(v) as total, count(*) as num FROM table WHERE extract(year from ts) = 2024 GROUP BY date_trunc('day', ts) ORDER BY day",
+            owned_table([
+                timestamptz("day", PoSQLTimeUnit::Second, utc, [1_718_841_600, 1_718_928_000]),
+                bigint("total", [30, 70]),
+                bigint("num", [2, 2]),
+            ]),
+        ),
+        (
+            "SELECT extract(hour from ts) as h, v FROM table WHERE ts + interval '1 day' >= timestamp '2024-06-22T00:00:00Z'",
+            owned_table([bigint("h", [0, 23]), bigint("v", [30, 40])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(query.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_a_top_k_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 1, 2, 3, 1]),
+            bigint("b", [10, -20, 30, 40, -50, 20]),
+            varchar("c", ["u", "v", "w", "x", "y", "z"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, b, c FROM table WHERE a < 3 ORDER BY a, b DESC LIMIT 3 OFFSET 1"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 1, 2]),
+        bigint("b", [20, 10, 40]),
+        varchar("c", ["z", "u", "x"]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_division_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [7, -7, 10, 3, 9]),
+            bigint("b", [2, 2, 0, 4, -4]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a / b AS q, b FROM table WHERE a / 3 >= 1 AND b <> 0"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("q", [3, 0, -2]), bigint("b", [2, 4, -4])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_cannot_prove_a_division_by_zero_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [7, -7, 10, 3, 9]),
+            bigint("b", [2, 2, 0, 4, -4]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a / b AS q FROM table WHERE a >= 3".parse().unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    assert!(matches!(
+        verifiable_result.verify(query.proof_expr(), &accessor, &&verifier_setup),
+        Err(QueryError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn we_can_prove_a_query_with_nulls_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 0, 3, 5, 0]),
+            presence("a", [true, false, true, true, false]),
+            bigint("b", [4, 0, 0, 2, 7]),
+            presence("b", [true, false, false, true, true]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, b FROM table WHERE a >= 2 OR b IS NULL"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [0, 3, 5]),
+        presence("a", [false, true, true]),
+        bigint("b", [0, 0, 2]),
+        presence("b", [false, false, true]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_aggregation_over_nulls_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 0, 3, 5, 0]),
+            presence("a", [true, false, true, true, false]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT COUNT(a) AS c, SUM(a) AS s, MIN(a) AS m, COUNT(*) AS n FROM table WHERE NOT a >= 3"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("c", [1]),
+        bigint("s", [1]),
+        bigint("m", [1]),
+        presence("m", [true]),
+        bigint("n", [1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_like_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table(
+            varchar_with_characters("name", ["abc", "ab", "xab", "a", "abd"], 2)
+                .into_iter()
+                .chain([bigint("id", [1, 2, 3, 4, 5])]),
+        ),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE name LIKE 'ab%' AND name NOT LIKE 'abd'"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([varchar("name", ["abc", "ab"]), bigint("id", [1, 2])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_in_list_and_between_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            varchar("name", ["a", "b", "c", "d", "e", "f"]),
+            bigint("id", [1, 2, 3, 4, 5, 6]),
+            decimal75("price", 10, 2, [150, 250, 1000, 199, 300, 2000]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name FROM table WHERE name NOT IN ('c', 'x') AND price BETWEEN 1.5 AND 10 AND id IN (1, 2, 4, 5)"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([varchar("name", ["a", "b", "d", "e"])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_case_query_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            varchar("name", ["a", "b", "c", "d"]),
+            bigint("id", [1, 2, 3, 4]),
+            decimal75("price", 10, 2, [150, 250, 1000, 199]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT CASE WHEN price >= 5 THEN 'high' WHEN id = 1 THEN name ELSE 'low' END AS tier, CASE WHEN id <= 2 THEN price ELSE id END AS amount FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        varchar("tier", ["a", "low", "high", "low"]),
+        decimal75("amount", 21, 2, [150, 250, 300, 400]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_subqueries_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.employees".parse().unwrap(),
+        owned_table([
+            varchar("name", ["Ann", "Bob", "Cat", "Dan", "Eve"]),
+            bigint("salary", [100, 250, 175, 300, 50]),
+            bigint("dept_id", [10, 20, 10, 40, 20]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.departments".parse().unwrap(),
+        owned_table([
+            bigint("dept_id", [10, 20, 30]),
+            bigint("budget", [500, 50, 200]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name, salary FROM employees WHERE dept_id IN (SELECT dept_id FROM departments WHERE budget >= 100) AND salary >= (SELECT MIN(salary) AS m FROM (SELECT salary FROM employees WHERE salary >= 150) AS s)"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([varchar("name", ["Cat"]), bigint("salary", [175])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_distinct_queries_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.events".parse().unwrap(),
+        owned_table([
+            bigint("user_id", [3, 1, 3, 2, 1, 3]),
+            varchar("kind", ["view", "click", "view", "view", "view", "click"]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT DISTINCT kind, user_id FROM events ORDER BY user_id DESC",
+            owned_table([
+                varchar("kind", ["click", "view", "view", "click", "view"]),
+                bigint("user_id", [3, 3, 2, 1, 1]),
+            ]),
+        ),
+        (
+            "SELECT COUNT(DISTINCT user_id) AS users FROM events WHERE kind = 'view'",
+            owned_table([bigint("users", [3])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_queries_with_having_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [3, 1, 3, 2, 1, 3, 4]),
+            bigint("amount", [10, 20, 30, 40, 50, 60, 70]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT customer_id, SUM(amount) AS total, COUNT(*) AS n FROM orders GROUP BY customer_id HAVING COUNT(*) >= 2 ORDER BY total DESC",
+            owned_table([
+                bigint("customer_id", [3, 1]),
+                bigint("total", [100, 70]),
+                bigint("n", [3, 2]),
+            ]),
+        ),
+        (
+            "SELECT customer_id, MAX(amount) AS biggest FROM orders WHERE amount >= 20 GROUP BY customer_id HAVING SUM(amount) >= 50 AND customer_id <= 3",
+            owned_table([bigint("customer_id", [1, 3]), bigint("biggest", [50, 60])]),
+        ),
+        (
+            "SELECT SUM(amount) AS total FROM orders HAVING COUNT(*) >= 10",
+            owned_table([bigint("total", [0; 0])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_queries_with_group_by_expressions_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [3, 1, 3, 2, 1, 3, 4]),
+            bigint("amount", [10, 20, 30, 40, 50, 60, 70]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT customer_id / 2 AS half_id, SUM(amount) AS total, COUNT(*) AS n FROM orders GROUP BY customer_id / 2",
+            owned_table([
+                bigint("half_id", [0, 1, 2]),
+                bigint("total", [70, 140, 70]),
+                bigint("n", [2, 4, 1]),
+            ]),
+        ),
+        (
+            "SELECT customer_id + 1 AS next_id, MAX(amount) AS biggest FROM orders GROUP BY customer_id + 1 HAVING COUNT(*) >= 2 ORDER BY next_id DESC",
+            owned_table([bigint("next_id", [4, 2]), bigint("biggest", [60, 50])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_queries_with_casts_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.items".parse().unwrap(),
+        owned_table([
+            int("quantity", [1, 2, 3, 4]),
+            decimal75("price", 6, 2, [1050, 299, -125, 1000]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT CAST(quantity AS DECIMAL(12, 2)) + price AS total FROM items WHERE CAST(price AS INT) >= 2",
+            owned_table([decimal75("total", 13, 2, [1150, 499, 1400])]),
+        ),
+        (
+            "SELECT CAST(price AS SMALLINT) AS whole, CAST(quantity AS BIGINT) AS quantity FROM items",
+            owned_table([
+                smallint("whole", [10_i16, 2, -1, 10]),
+                bigint("quantity", [1, 2, 3, 4]),
+            ]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_queries_with_window_functions_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [3, 1, 3, 2, 1, 3, 4]),
+            bigint("amount", [10, 20, 30, 40, 50, 60, 70]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT customer_id, amount, ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY amount DESC) AS rn, SUM(amount) OVER (PARTITION BY customer_id ORDER BY amount) AS running FROM orders ORDER BY customer_id, amount",
+            owned_table([
+                bigint("customer_id", [1, 1, 2, 3, 3, 3, 4]),
+                bigint("amount", [20, 50, 40, 10, 30, 60, 70]),
+                bigint("rn", [2, 1, 1, 3, 2, 1, 1]),
+                bigint("running", [20, 70, 40, 10, 40, 100, 70]),
+            ]),
+        ),
+        (
+            "SELECT customer_id, amount FROM (SELECT customer_id, amount, RANK() OVER (PARTITION BY customer_id ORDER BY amount DESC) AS r FROM orders) AS ranked WHERE r = 1 ORDER BY customer_id",
+            owned_table([
+                bigint("customer_id", [1, 2, 3, 4]),
+                bigint("amount", [50, 40, 60, 70]),
+            ]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_set_operations_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer_id", [3, 1, 3, 2, 1, 3]),
+            varchar("item", ["pen", "ink", "pen", "cap", "pad", "ink"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.customers".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 4]),
+            varchar("name", ["Ann", "Bob", "Dan"]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT customer_id FROM orders UNION SELECT id FROM customers ORDER BY customer_id DESC",
+            owned_table([bigint("customer_id", [4, 3, 2, 1])]),
+        ),
+        (
+            "SELECT customer_id FROM orders WHERE item = 'pen' UNION ALL SELECT id FROM customers",
+            owned_table([bigint("customer_id", [3, 3, 1, 2, 4])]),
+        ),
+        (
+            "SELECT customer_id FROM orders INTERSECT SELECT id FROM customers",
+            owned_table([bigint("customer_id", [1, 2])]),
+        ),
+        (
+            "SELECT id FROM customers EXCEPT SELECT customer_id FROM orders WHERE item <> 'pad'",
+            owned_table([bigint("id", [4])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &&prover_setup,
+        );
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap()
+            .table;
+        let transformed_result: OwnedTable<DoryScalar> =
+            apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
 // Overflow checks
 #[test]
 #[cfg(feature = "blitzar")]